@@ -5,20 +5,47 @@ use crate::{
     },
     utils::token_mill::{constants::*, curve_generator::Curve},
 };
-use anchor_lang::{prelude::AccountMeta, Id, InstructionData};
+use anchor_lang::{prelude::AccountMeta, AnchorSerialize, Id, InstructionData};
 use anchor_spl::{
     associated_token::get_associated_token_address_with_program_id, metadata::Metadata,
     token::spl_token, token_2022::spl_token_2022,
 };
 use anyhow::Result;
 use litesvm::types::{FailedTransactionMetadata, TransactionMetadata};
-use solana_sdk::{instruction::Instruction, pubkey::Pubkey, transaction::TransactionError};
+use solana_sdk::{
+    account::Account, instruction::Instruction, pubkey::Pubkey, sysvar,
+    transaction::TransactionError,
+};
+use std::ops::Range;
 use token_mill::{
+    constant::REVENUE_EPOCH_DURATION,
     errors::TokenMillError,
-    manager::swap_manager::{SwapAmountType, SwapType},
+    manager::{
+        curve_manager::{CurveParameters, CurveShape},
+        migration_manager::{METEORA_DAMM_V2_PROGRAM_ID, RAYDIUM_CP_SWAP_PROGRAM_ID},
+        price_manager::{
+            PriceFeedMessage, PriceUpdateV2, VerificationLevel, PYTH_RECEIVER_PROGRAM_ID,
+        },
+        receipt_manager::{SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID},
+        swap_manager::{SwapAmountType, SwapType},
+    },
     state::{
-        QuoteTokenBadgeStatus, MARKET_PDA_SEED, MARKET_STAKING_PDA_SEED,
-        QUOTE_TOKEN_BADGE_PDA_SEED, REFERRAL_ACCOUNT_PDA_SEED, STAKING_POSITION_PDA_SEED,
+        ConfigChange, CreationBond, CreatorDraw, MigrationTarget, PriceBounds, ProtocolFeeShare,
+        QuoteTokenBadgeStatus, ReferralSettlement, ReferralTier, ScheduledUpdateKind,
+        StakingBoostSource, TraderRebateParams, UsdGraduation, VestingTemplate, WalletBuyLimit,
+        AIRDROP_CLAIM_PDA_SEED, BUYBACK_ORDER_PDA_SEED, BUY_QUEUE_PDA_SEED,
+        CLAIM_DELEGATE_PDA_SEED, CREATOR_ACTIVITY_PDA_SEED, CREATOR_MULTISIG_PDA_SEED,
+        CREATOR_VAULT_PDA_SEED, DENYLIST_PDA_SEED, FEE_EXEMPTION_PDA_SEED,
+        FEE_RECONCILIATION_PDA_SEED, GAS_VAULT_PDA_SEED, GRADUATION_ATTESTATION_PDA_SEED,
+        LAUNCH_PURCHASE_PDA_SEED, MARKET_ORACLE_PDA_SEED, MARKET_PDA_SEED,
+        MARKET_REFERRAL_ACCRUAL_PDA_SEED, MARKET_REFERRAL_CODE_PDA_SEED, MARKET_STAKING_PDA_SEED,
+        MARKET_STATS_PDA_SEED, MINT_REGISTRY_PDA_SEED, PENDING_CONFIG_CHANGE_PDA_SEED,
+        PRICE_FEED_PDA_SEED, PROTOCOL_FEE_CONVERTER_PDA_SEED, PROTOCOL_FEE_SPLIT_PDA_SEED,
+        QUEUED_BUY_ORDER_PDA_SEED, QUOTE_AIRDROP_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+        REFERRAL_ACCOUNT_PDA_SEED, REFERRAL_CODE_PDA_SEED, REFERRER_BINDING_PDA_SEED,
+        REVENUE_REPORT_PDA_SEED, RISK_PARAMS_PDA_SEED, SELL_ORDER_PDA_SEED,
+        SPONSORED_ACCOUNT_PDA_SEED, STAKING_POSITION_PDA_SEED, SWAP_RECEIPT_TREE_PDA_SEED,
+        TRADER_VOLUME_PDA_SEED,
     },
 };
 
@@ -121,6 +148,30 @@ impl TokenMillEnv {
         self
     }
 
+    pub fn with_transfer_fee_quote_token_mint(
+        mut self,
+        decimals: u8,
+        transfer_fee_basis_points: u16,
+    ) -> Self {
+        let quote_token = self
+            .svm
+            .create_token_with_transfer_fee(decimals, transfer_fee_basis_points, u64::MAX)
+            .unwrap();
+
+        self.svm
+            .create_ata(
+                &CreateReferralAccountAction::new().referral_account,
+                &quote_token,
+                TokenType::Token2022,
+            )
+            .unwrap();
+
+        self.quote_token_mint = Some(quote_token);
+        self.quote_token_type = TokenType::Token2022;
+
+        self
+    }
+
     pub fn with_base_token_type(mut self, base_token_type: TokenType) -> Self {
         self.base_token_type = base_token_type;
 
@@ -218,6 +269,146 @@ fn tm_event_authority() -> Pubkey {
     get_event_authority(token_mill::ID)
 }
 
+fn tm_market_stats(market: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MARKET_STATS_PDA_SEED.as_bytes(), &market.to_bytes()],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_queued_buy_order(market: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            QUEUED_BUY_ORDER_PDA_SEED.as_bytes(),
+            &market.to_bytes(),
+            &owner.to_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_indexed_stake_position(market: &Pubkey, user: &Pubkey, index: u32) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            STAKING_POSITION_PDA_SEED.as_bytes(),
+            &market.to_bytes(),
+            &user.to_bytes(),
+            &index.to_le_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_referral_code(code: &[u8; 32]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REFERRAL_CODE_PDA_SEED.as_bytes(),
+            &make_address("config").to_bytes(),
+            code,
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_referrer_binding(trader: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            REFERRER_BINDING_PDA_SEED.as_bytes(),
+            &make_address("config").to_bytes(),
+            &trader.to_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_pending_config_change(kind: u8) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            PENDING_CONFIG_CHANGE_PDA_SEED.as_bytes(),
+            &make_address("config").to_bytes(),
+            &[kind],
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+fn tm_denylist() -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            DENYLIST_PDA_SEED.as_bytes(),
+            &make_address("config").to_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+/// Revenue report of the current epoch, recording the fees of the swaps.
+fn tm_revenue_report(testing_env: &TokenMillEnv, quote_token_mint: &Pubkey) -> Pubkey {
+    let epoch = (testing_env.svm.get_unix_timestamp() / REVENUE_EPOCH_DURATION) as u64;
+
+    Pubkey::find_program_address(
+        &[
+            REVENUE_REPORT_PDA_SEED.as_bytes(),
+            &make_address("config").to_bytes(),
+            &quote_token_mint.to_bytes(),
+            &epoch.to_le_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+/// Writes a fully verified Pyth price update of `feed_id` at a new address.
+pub fn tm_set_price_update(
+    svm: &mut JoelanaEnv,
+    feed_id: [u8; 32],
+    price: i64,
+    conf: u64,
+    exponent: i32,
+    publish_time: i64,
+) -> Pubkey {
+    let price_update = PriceUpdateV2 {
+        write_authority: Pubkey::new_unique(),
+        verification_level: VerificationLevel::Full,
+        price_message: PriceFeedMessage {
+            feed_id,
+            price,
+            conf,
+            exponent,
+            publish_time,
+            prev_publish_time: publish_time - 1,
+            ema_price: price,
+            ema_conf: conf,
+        },
+        posted_slot: svm.get_slot(),
+    };
+
+    let mut data = PriceUpdateV2::discriminator().to_vec();
+    price_update.serialize(&mut data).unwrap();
+
+    let address = Pubkey::new_unique();
+
+    svm.set_account(
+        &address,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: PYTH_RECEIVER_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    address
+}
+
 pub fn tm_parse_error(
     result: Result<TransactionMetadata, FailedTransactionMetadata>,
 ) -> Result<TokenMillError, TransactionError> {
@@ -458,55 +649,40 @@ impl InstructionGenerator for UpdateProtocolFeeRecipientAction {
     }
 }
 
-pub struct CreateQuoteAssetBadgeAction {
+#[derive(Debug)]
+pub struct UpdatePolicyProgramAction {
     // Accounts
     pub config: Pubkey,
-    pub quote_asset_badge: Pubkey,
-    pub token_mint: Pubkey,
     pub signer: Pubkey,
+    // Args
+    pub new_policy_program: Option<Pubkey>,
 }
 
-impl CreateQuoteAssetBadgeAction {
-    pub fn new(token_mint: Pubkey) -> Self {
-        let config = make_address("config");
-
-        let quote_asset_badge = Pubkey::find_program_address(
-            &[
-                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
-                &config.to_bytes(),
-                &token_mint.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
+impl UpdatePolicyProgramAction {
+    pub fn new(new_policy_program: Option<Pubkey>) -> Self {
         Self {
-            config,
-            quote_asset_badge,
-            token_mint,
+            config: make_address("config"),
             signer: make_address("admin"),
+            new_policy_program,
         }
     }
 }
 
-impl InstructionGenerator for CreateQuoteAssetBadgeAction {
+impl InstructionGenerator for UpdatePolicyProgramAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![
-            AccountMeta::new_readonly(self.config, false),
-            AccountMeta::new(self.quote_asset_badge, false),
-            AccountMeta::new_readonly(self.token_mint, false),
-        ];
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
         accounts
             .append_payer(self.signer)
-            .append_system_program()
             .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateQuoteAssetBadge {};
+        let input = token_mill::instruction::UpdatePolicyProgram {
+            new_policy_program: self.new_policy_program,
+        };
 
         Instruction {
             program_id: token_mill::ID,
@@ -516,46 +692,28 @@ impl InstructionGenerator for CreateQuoteAssetBadgeAction {
     }
 }
 
-pub struct UpdateQuoteAssetBadgeAction {
+#[derive(Debug)]
+pub struct UpdateFeeHookProgramAction {
     // Accounts
     pub config: Pubkey,
-    pub quote_asset_badge: Pubkey,
-    pub token_mint: Pubkey,
     pub signer: Pubkey,
     // Args
-    pub status: QuoteTokenBadgeStatus,
+    pub new_fee_hook_program: Option<Pubkey>,
 }
 
-impl UpdateQuoteAssetBadgeAction {
-    pub fn new(token_mint: Pubkey, status: QuoteTokenBadgeStatus) -> Self {
-        let config = make_address("config");
-        let quote_asset_badge = Pubkey::find_program_address(
-            &[
-                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
-                &config.to_bytes(),
-                &token_mint.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
+impl UpdateFeeHookProgramAction {
+    pub fn new(new_fee_hook_program: Option<Pubkey>) -> Self {
         Self {
-            config,
-            quote_asset_badge,
-            token_mint,
+            config: make_address("config"),
             signer: make_address("admin"),
-            status,
+            new_fee_hook_program,
         }
     }
 }
 
-impl InstructionGenerator for UpdateQuoteAssetBadgeAction {
+impl InstructionGenerator for UpdateFeeHookProgramAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![
-            AccountMeta::new_readonly(self.config, false),
-            AccountMeta::new(self.quote_asset_badge, false),
-            AccountMeta::new_readonly(self.token_mint, false),
-        ];
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
         accounts
             .append_payer(self.signer)
@@ -565,8 +723,8 @@ impl InstructionGenerator for UpdateQuoteAssetBadgeAction {
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::UpdateQuoteAssetBadge {
-            status: self.status,
+        let input = token_mill::instruction::UpdateFeeHookProgram {
+            new_fee_hook_program: self.new_fee_hook_program,
         };
 
         Instruction {
@@ -577,97 +735,91 @@ impl InstructionGenerator for UpdateQuoteAssetBadgeAction {
     }
 }
 
-pub struct CreateMarketAction {
+pub struct UpdateGraduationHookProgramAction {
     // Accounts
     pub config: Pubkey,
-    pub market: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub quote_token_badge: Pubkey,
-    pub quote_token_mint: Pubkey,
     pub signer: Pubkey,
     // Args
-    pub total_supply: u64,
+    pub new_graduation_hook_program: Option<Pubkey>,
 }
 
-impl CreateMarketAction {
-    pub fn new(testing_env: &TokenMillEnv) -> Self {
-        let config = make_address("config");
-
-        let base_token_mint = testing_env.base_token_mint.unwrap();
+impl UpdateGraduationHookProgramAction {
+    pub fn new(new_graduation_hook_program: Option<Pubkey>) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_graduation_hook_program,
+        }
+    }
+}
 
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+impl InstructionGenerator for UpdateGraduationHookProgramAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
-        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
 
-        let quote_asset_badge = Pubkey::find_program_address(
-            &[
-                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
-                &config.to_bytes(),
-                &quote_token_mint.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
+        accounts
+    }
 
-        let market_base_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &base_token_mint,
-            &spl_token_2022::id(),
-        );
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateGraduationHookProgram {
+            new_graduation_hook_program: self.new_graduation_hook_program,
+        };
 
-        Self {
-            config,
-            market,
-            base_token_mint,
-            market_base_token_ata,
-            quote_token_mint,
-            quote_token_badge: quote_asset_badge,
-            signer: make_address("alice"),
-            total_supply: DEFAULT_TOTAL_SUPPLY,
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
         }
     }
+}
 
-    pub fn no_badge(&mut self) -> &mut Self {
-        self.quote_token_badge = token_mill::ID;
+pub struct UpdateDefaultCurveAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_default_curve: Option<CurveParameters>,
+}
 
-        self
+impl Default for UpdateDefaultCurveAction {
+    fn default() -> Self {
+        Self::new(Some(CurveParameters {
+            shape: CurveShape::Exponential,
+            start_price: 1_000_000,
+            end_price: 100_000_000,
+            bid_spread_bps: 1_000,
+        }))
     }
 }
 
-impl InstructionGenerator for CreateMarketAction {
+impl UpdateDefaultCurveAction {
+    pub fn new(new_default_curve: Option<CurveParameters>) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_default_curve,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateDefaultCurveAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![
-            AccountMeta::new_readonly(self.config, false),
-            AccountMeta::new(self.market, false),
-            AccountMeta::new(self.base_token_mint, true),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new_readonly(self.quote_token_badge, false),
-            AccountMeta::new_readonly(self.quote_token_mint, false),
-        ];
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
         accounts
             .append_payer(self.signer)
-            .append_system_program()
-            .append_token_2022_program()
-            .append_associated_token_program()
             .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateMarket {
-            name: "name".to_string(),
-            symbol: "symbol".to_string(),
-            uri: "uri".to_string(),
-            total_supply: self.total_supply,
-            creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
-            staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+        let input = token_mill::instruction::UpdateDefaultCurve {
+            new_default_curve: self.new_default_curve,
         };
 
         Instruction {
@@ -678,161 +830,8820 @@ impl InstructionGenerator for CreateMarketAction {
     }
 }
 
-pub struct CreateMarketWithSplAction {
+pub struct UpdateMaxPauseDurationAction {
     // Accounts
     pub config: Pubkey,
-    pub market: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub base_token_metadata: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub quote_token_badge: Pubkey,
-    pub quote_token_mint: Pubkey,
     pub signer: Pubkey,
     // Args
-    pub total_supply: u64,
+    pub new_max_pause_duration: u64,
 }
 
-impl CreateMarketWithSplAction {
-    pub fn new(testing_env: &TokenMillEnv) -> Self {
-        let config = make_address("config");
-
-        let base_token_mint = testing_env.base_token_mint.unwrap();
+impl UpdateMaxPauseDurationAction {
+    pub fn new(new_max_pause_duration: u64) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_max_pause_duration,
+        }
+    }
+}
 
-        let base_token_metadata = Pubkey::find_program_address(
-            &[
-                "metadata".as_bytes(),
-                &Metadata::id().to_bytes(),
-                &base_token_mint.to_bytes(),
-            ],
-            &Metadata::id(),
-        )
-        .0;
+impl InstructionGenerator for UpdateMaxPauseDurationAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
 
-        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        accounts
+    }
 
-        let quote_asset_badge = Pubkey::find_program_address(
-            &[
-                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
-                &config.to_bytes(),
-                &quote_token_mint.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMaxPauseDuration {
+            new_max_pause_duration: self.new_max_pause_duration,
+        };
 
-        let market_base_token_ata = get_associated_token_address_with_program_id(
-            &market,
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMaxSwapSizeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_max_swap_size_bps: u16,
+}
+
+impl UpdateMaxSwapSizeAction {
+    pub fn new(new_max_swap_size_bps: u16) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_max_swap_size_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMaxSwapSizeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMaxSwapSize {
+            new_max_swap_size_bps: self.new_max_swap_size_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMaxDailyMarketsPerCreatorAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_max_daily_markets_per_creator: u16,
+}
+
+impl UpdateMaxDailyMarketsPerCreatorAction {
+    pub fn new(new_max_daily_markets_per_creator: u16) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_max_daily_markets_per_creator,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMaxDailyMarketsPerCreatorAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMaxDailyMarketsPerCreator {
+            new_max_daily_markets_per_creator: self.new_max_daily_markets_per_creator,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateReferralTiersAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_referral_tiers: Vec<ReferralTier>,
+}
+
+impl UpdateReferralTiersAction {
+    pub fn new(new_referral_tiers: Vec<ReferralTier>) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_referral_tiers,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateReferralTiersAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateReferralTiers {
+            new_referral_tiers: self.new_referral_tiers.clone(),
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateReferralSettlementAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub referral_settlement: ReferralSettlement,
+}
+
+impl UpdateReferralSettlementAction {
+    pub fn new(referral_settlement: ReferralSettlement) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            referral_settlement,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateReferralSettlementAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateReferralSettlement {
+            referral_settlement: self.referral_settlement,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdatePriceBoundsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_price_bounds: PriceBounds,
+}
+
+impl UpdatePriceBoundsAction {
+    pub fn new(new_price_bounds: PriceBounds) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_price_bounds,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdatePriceBoundsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdatePriceBounds {
+            new_price_bounds: self.new_price_bounds,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct AddVestingTemplateAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub template: VestingTemplate,
+}
+
+impl Default for AddVestingTemplateAction {
+    fn default() -> Self {
+        let mut name = [0; 32];
+        let label = b"6m cliff + 18m linear";
+        name[..label.len()].copy_from_slice(label);
+
+        Self::new(VestingTemplate {
+            name,
+            vesting_duration: 62_208_000,
+            cliff_duration: 15_552_000,
+        })
+    }
+}
+
+impl AddVestingTemplateAction {
+    pub fn new(template: VestingTemplate) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            template,
+        }
+    }
+}
+
+impl InstructionGenerator for AddVestingTemplateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::AddVestingTemplate {
+            template: self.template,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateDenylistAction {
+    // Accounts
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl Default for CreateDenylistAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreateDenylistAction {
+    pub fn new() -> Self {
+        Self {
+            config: make_address("config"),
+            denylist: tm_denylist(),
+            signer: make_address("admin"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateDenylistAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(self.denylist, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateDenylist {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct AddToDenylistAction {
+    // Accounts
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub wallet: Pubkey,
+}
+
+impl AddToDenylistAction {
+    pub fn new(wallet: Pubkey) -> Self {
+        Self {
+            config: make_address("config"),
+            denylist: tm_denylist(),
+            signer: make_address("admin"),
+            wallet,
+        }
+    }
+}
+
+impl InstructionGenerator for AddToDenylistAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.denylist, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::AddToDenylist {
+            wallet: self.wallet,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct RemoveFromDenylistAction {
+    // Accounts
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub wallet: Pubkey,
+}
+
+impl RemoveFromDenylistAction {
+    pub fn new(wallet: Pubkey) -> Self {
+        Self {
+            config: make_address("config"),
+            denylist: tm_denylist(),
+            signer: make_address("admin"),
+            wallet,
+        }
+    }
+}
+
+impl InstructionGenerator for RemoveFromDenylistAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.denylist, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::RemoveFromDenylist {
+            wallet: self.wallet,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct PauseUntilAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub slot: u64,
+}
+
+impl PauseUntilAction {
+    pub fn new(testing_env: &TokenMillEnv, slot: u64) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: make_address("admin"),
+            slot,
+        }
+    }
+}
+
+impl InstructionGenerator for PauseUntilAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PauseUntil { slot: self.slot };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateVerifierAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_verifier: Option<Pubkey>,
+}
+
+impl UpdateVerifierAction {
+    pub fn new(new_verifier: Option<Pubkey>) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_verifier,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateVerifierAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateVerifier {
+            new_verifier: self.new_verifier,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetMarketVerificationAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub verified: bool,
+}
+
+impl SetMarketVerificationAction {
+    pub fn new(testing_env: &TokenMillEnv, verified: bool) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: make_address("carol"),
+            verified,
+        }
+    }
+}
+
+impl InstructionGenerator for SetMarketVerificationAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetMarketVerification {
+            verified: self.verified,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateSecondaryMarketPolicyAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub secondary_markets_enabled: bool,
+}
+
+impl UpdateSecondaryMarketPolicyAction {
+    pub fn new(secondary_markets_enabled: bool) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            secondary_markets_enabled,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateSecondaryMarketPolicyAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateSecondaryMarketPolicy {
+            secondary_markets_enabled: self.secondary_markets_enabled,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateQuoteAssetBadgeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub quote_asset_badge: Pubkey,
+    pub token_mint: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateQuoteAssetBadgeAction {
+    pub fn new(token_mint: Pubkey) -> Self {
+        let config = make_address("config");
+
+        let quote_asset_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            quote_asset_badge,
+            token_mint,
+            signer: make_address("admin"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateQuoteAssetBadgeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.quote_asset_badge, false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateQuoteAssetBadge {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateQuoteAssetBadgeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub quote_asset_badge: Pubkey,
+    pub token_mint: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub status: QuoteTokenBadgeStatus,
+}
+
+impl UpdateQuoteAssetBadgeAction {
+    pub fn new(token_mint: Pubkey, status: QuoteTokenBadgeStatus) -> Self {
+        let config = make_address("config");
+        let quote_asset_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            quote_asset_badge,
+            token_mint,
+            signer: make_address("admin"),
+            status,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateQuoteAssetBadgeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.quote_asset_badge, false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateQuoteAssetBadge {
+            status: self.status,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateQuoteTokenPriceFeedAction {
+    // Accounts
+    pub config: Pubkey,
+    pub quote_asset_badge: Pubkey,
+    pub token_mint: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub price_feed_id: Option<[u8; 32]>,
+}
+
+impl UpdateQuoteTokenPriceFeedAction {
+    pub fn new(token_mint: Pubkey, price_feed_id: Option<[u8; 32]>) -> Self {
+        let config = make_address("config");
+        let quote_asset_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            quote_asset_badge,
+            token_mint,
+            signer: make_address("admin"),
+            price_feed_id,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateQuoteTokenPriceFeedAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.quote_asset_badge, false),
+            AccountMeta::new_readonly(self.token_mint, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateQuoteTokenPriceFeed {
+            price_feed_id: self.price_feed_id,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateCreatorActivityAction {
+    // Accounts
+    pub config: Pubkey,
+    pub creator_activity: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl Default for CreateCreatorActivityAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreateCreatorActivityAction {
+    pub fn new() -> Self {
+        let config = make_address("config");
+        let signer = make_address("alice");
+
+        let creator_activity = Pubkey::find_program_address(
+            &[
+                CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            creator_activity,
+            signer,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateCreatorActivityAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.creator_activity, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateCreatorActivity {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateLaunchPurchaseAction {
+    // Accounts
+    pub market: Pubkey,
+    pub launch_purchase: Pubkey,
+    pub wallet: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateLaunchPurchaseAction {
+    pub fn new(testing_env: &TokenMillEnv, wallet: Pubkey) -> Self {
+        let market = testing_env.market;
+
+        let launch_purchase = Pubkey::find_program_address(
+            &[
+                LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &wallet.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            launch_purchase,
+            wallet,
+            signer: wallet,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateLaunchPurchaseAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.launch_purchase, false),
+            AccountMeta::new_readonly(self.wallet, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateLaunchPurchase {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub mint_registry: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub policy_program: Pubkey,
+    pub creator_activity: Pubkey,
+    // Args
+    pub total_supply: u64,
+    pub wallet_buy_limit: Option<WalletBuyLimit>,
+    pub swap_fee_bps: u16,
+}
+
+impl CreateMarketAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let config = make_address("config");
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let quote_asset_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &spl_token_2022::id(),
+        );
+
+        let mint_registry = Pubkey::find_program_address(
+            &[
+                MINT_REGISTRY_PDA_SEED.as_bytes(),
+                &base_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            market,
+            base_token_mint,
+            market_base_token_ata,
+            mint_registry,
+            quote_token_mint,
+            quote_token_badge: quote_asset_badge,
+            signer: make_address("alice"),
+            policy_program: token_mill::ID,
+            creator_activity: token_mill::ID,
+            total_supply: DEFAULT_TOTAL_SUPPLY,
+            wallet_buy_limit: None,
+            swap_fee_bps: 0,
+        }
+    }
+
+    pub fn no_badge(&mut self) -> &mut Self {
+        self.quote_token_badge = token_mill::ID;
+
+        self
+    }
+}
+
+impl InstructionGenerator for CreateMarketAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.base_token_mint, true),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.mint_registry, false),
+            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_token_2022_program()
+            .append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.creator_activity, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarket {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            uri: "uri".to_string(),
+            total_supply: self.total_supply,
+            creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
+            staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+            wallet_buy_limit: self.wallet_buy_limit,
+            swap_fee_bps: self.swap_fee_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketsBatchAction {
+    // Accounts
+    pub config: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub policy_program: Pubkey,
+    pub creator_activity: Pubkey,
+    pub markets: Vec<Pubkey>,
+    pub base_token_mints: Vec<Pubkey>,
+    pub market_base_token_atas: Vec<Pubkey>,
+    pub mint_registries: Vec<Pubkey>,
+    // Args
+    pub total_supply: u64,
+    pub wallet_buy_limit: Option<WalletBuyLimit>,
+}
+
+impl CreateMarketsBatchAction {
+    pub fn new(testing_env: &TokenMillEnv, market_count: usize) -> Self {
+        let config = make_address("config");
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let quote_token_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let base_token_mints = (0..market_count)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+
+        let markets = base_token_mints
+            .iter()
+            .map(|base_token_mint| {
+                Pubkey::find_program_address(
+                    &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+                    &token_mill::ID,
+                )
+                .0
+            })
+            .collect::<Vec<_>>();
+
+        let market_base_token_atas = markets
+            .iter()
+            .zip(&base_token_mints)
+            .map(|(market, base_token_mint)| {
+                get_associated_token_address_with_program_id(
+                    market,
+                    base_token_mint,
+                    &spl_token_2022::id(),
+                )
+            })
+            .collect();
+
+        let mint_registries = base_token_mints
+            .iter()
+            .map(|base_token_mint| {
+                Pubkey::find_program_address(
+                    &[
+                        MINT_REGISTRY_PDA_SEED.as_bytes(),
+                        &base_token_mint.to_bytes(),
+                    ],
+                    &token_mill::ID,
+                )
+                .0
+            })
+            .collect();
+
+        Self {
+            config,
+            quote_token_badge,
+            quote_token_mint,
+            signer: make_address("alice"),
+            policy_program: token_mill::ID,
+            creator_activity: token_mill::ID,
+            markets,
+            base_token_mints,
+            market_base_token_atas,
+            mint_registries,
+            total_supply: DEFAULT_TOTAL_SUPPLY,
+            wallet_buy_limit: None,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateMarketsBatchAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_token_2022_program()
+            .append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.creator_activity, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        for i in 0..self.markets.len() {
+            accounts.push(AccountMeta::new(self.markets[i], false));
+            accounts.push(AccountMeta::new(self.base_token_mints[i], true));
+            accounts.push(AccountMeta::new(self.market_base_token_atas[i], false));
+            accounts.push(AccountMeta::new(self.mint_registries[i], false));
+        }
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let markets = (0..self.markets.len())
+            .map(|_| token_mill::MarketCreationParams {
+                name: "name".to_string(),
+                symbol: "symbol".to_string(),
+                uri: "uri".to_string(),
+                total_supply: self.total_supply,
+                creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
+                staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+                wallet_buy_limit: self.wallet_buy_limit,
+            })
+            .collect();
+
+        let input = token_mill::instruction::CreateMarketsBatch { markets };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct LaunchAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub mint_registry: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub creator_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub vesting_plan: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub policy_program: Pubkey,
+    pub creator_activity: Pubkey,
+    pub denylist: Pubkey,
+    pub revenue_report: Pubkey,
+    pub fee_hook_program: Pubkey,
+    // Args
+    pub total_supply: u64,
+    pub curve: CurveParameters,
+    pub dev_buy_amount: u64,
+    pub min_dev_buy_base_amount: u64,
+    pub vesting_start: i64,
+    pub vesting_duration: i64,
+    pub cliff_duration: i64,
+}
+
+impl LaunchAction {
+    pub fn new(
+        testing_env: &TokenMillEnv,
+        dev_buy_amount: u64,
+        vesting_start: i64,
+        vesting_duration: i64,
+        cliff_duration: i64,
+    ) -> Self {
+        let config = make_address("config");
+        let signer = make_address("alice");
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &spl_token_2022::id(),
+        );
+
+        let mint_registry = Pubkey::find_program_address(
+            &[
+                MINT_REGISTRY_PDA_SEED.as_bytes(),
+                &base_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let creator_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let protocol_quote_token_ata = get_associated_token_address_with_program_id(
+            &make_address("dave"),
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            market,
+            base_token_mint,
+            market_base_token_ata,
+            mint_registry,
+            quote_token_badge: token_mill::ID,
+            quote_token_mint,
+            market_quote_token_ata,
+            creator_quote_token_ata,
+            protocol_quote_token_ata,
+            staking,
+            stake_position,
+            vesting_plan: make_address("vesting_plan"),
+            signer,
+            quote_token_program,
+            policy_program: token_mill::ID,
+            creator_activity: token_mill::ID,
+            denylist: token_mill::ID,
+            revenue_report: tm_revenue_report(testing_env, &quote_token_mint),
+            fee_hook_program: token_mill::ID,
+            total_supply: DEFAULT_TOTAL_SUPPLY,
+            curve: CurveParameters {
+                shape: CurveShape::Linear,
+                start_price: 1_000,
+                end_price: 1_000_000_000,
+                bid_spread_bps: 1_000,
+            },
+            dev_buy_amount,
+            min_dev_buy_base_amount: 0,
+            vesting_start,
+            vesting_duration,
+            cliff_duration,
+        }
+    }
+}
+
+impl InstructionGenerator for LaunchAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.base_token_mint, true),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.mint_registry, false),
+            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new(self.vesting_plan, true),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_token_2022_program();
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.creator_activity, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+        accounts.push(AccountMeta::new(self.revenue_report, false));
+        accounts.push(AccountMeta::new_readonly(self.fee_hook_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::Launch {
+            params: token_mill::LaunchParams {
+                name: "name".to_string(),
+                symbol: "symbol".to_string(),
+                uri: "uri".to_string(),
+                total_supply: self.total_supply,
+                creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
+                staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+                curve: self.curve,
+                dev_buy_amount: self.dev_buy_amount,
+                min_dev_buy_base_amount: self.min_dev_buy_base_amount,
+                vesting_start: self.vesting_start,
+                vesting_duration: self.vesting_duration,
+                cliff_duration: self.cliff_duration,
+            },
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketWithSplAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_metadata: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub mint_registry: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub policy_program: Pubkey,
+    pub creator_activity: Pubkey,
+    pub token_program: Pubkey,
+    pub token_metadata_program: Pubkey,
+    // Args
+    pub total_supply: u64,
+    pub use_token_2022: bool,
+    pub wallet_buy_limit: Option<WalletBuyLimit>,
+}
+
+impl CreateMarketWithSplAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let config = make_address("config");
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+
+        let base_token_metadata = Pubkey::find_program_address(
+            &[
+                "metadata".as_bytes(),
+                &Metadata::id().to_bytes(),
+                &base_token_mint.to_bytes(),
+            ],
+            &Metadata::id(),
+        )
+        .0;
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let quote_asset_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &spl_token::id(),
+        );
+
+        let mint_registry = Pubkey::find_program_address(
+            &[
+                MINT_REGISTRY_PDA_SEED.as_bytes(),
+                &base_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            market,
+            base_token_mint,
+            base_token_metadata,
+            market_base_token_ata,
+            mint_registry,
+            quote_token_mint,
+            quote_token_badge: quote_asset_badge,
+            signer: make_address("alice"),
+            policy_program: token_mill::ID,
+            creator_activity: token_mill::ID,
+            token_program: spl_token::id(),
+            token_metadata_program: Metadata::id(),
+            total_supply: DEFAULT_TOTAL_SUPPLY,
+            use_token_2022: false,
+            wallet_buy_limit: None,
+        }
+    }
+
+    pub fn no_badge(&mut self) -> &mut Self {
+        self.quote_token_badge = token_mill::ID;
+
+        self
+    }
+
+    pub fn with_token_2022(&mut self) -> &mut Self {
+        self.use_token_2022 = true;
+        self.token_program = spl_token_2022::id();
+        self.base_token_metadata = token_mill::ID;
+        self.token_metadata_program = token_mill::ID;
+        self.market_base_token_ata = get_associated_token_address_with_program_id(
+            &self.market,
+            &self.base_token_mint,
+            &spl_token_2022::id(),
+        );
+
+        self
+    }
+}
+
+impl InstructionGenerator for CreateMarketWithSplAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.base_token_mint, true),
+            AccountMeta::new(self.base_token_metadata, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.mint_registry, false),
+            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.token_program, false));
+        accounts.push(AccountMeta::new_readonly(
+            self.token_metadata_program,
+            false,
+        ));
+
+        accounts.append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.creator_activity, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarketWithSpl {
+            name: "name".to_string(),
+            symbol: "symbol".to_string(),
+            uri: "uri".to_string(),
+            total_supply: self.total_supply,
+            creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
+            staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+            use_token_2022: self.use_token_2022,
+            wallet_buy_limit: self.wallet_buy_limit,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateSecondaryMarketAction {
+    // Accounts
+    pub config: Pubkey,
+    pub primary_market: Pubkey,
+    pub mint_registry: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub creator_base_token_account: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub signer: Pubkey,
+    pub policy_program: Pubkey,
+    pub creator_activity: Pubkey,
+    pub base_token_program: Pubkey,
+    // Args
+    pub total_supply: u64,
+}
+
+impl CreateSecondaryMarketAction {
+    pub fn new(testing_env: &TokenMillEnv, quote_token_mint: Pubkey, total_supply: u64) -> Self {
+        let config = make_address("config");
+        let signer = make_address("alice");
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let base_token_program = testing_env.base_token_type.program_address();
+
+        let primary_market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let mint_registry = Pubkey::find_program_address(
+            &[
+                MINT_REGISTRY_PDA_SEED.as_bytes(),
+                &base_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &base_token_mint.to_bytes(),
+                &quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let creator_base_token_account = get_associated_token_address_with_program_id(
+            &signer,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let quote_token_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            primary_market,
+            mint_registry,
+            market,
+            base_token_mint,
+            market_base_token_ata,
+            creator_base_token_account,
+            quote_token_badge,
+            quote_token_mint,
+            signer,
+            base_token_program,
+            policy_program: token_mill::ID,
+            creator_activity: token_mill::ID,
+            total_supply,
+        }
+    }
+
+    pub fn no_badge(&mut self) -> &mut Self {
+        self.quote_token_badge = token_mill::ID;
+
+        self
+    }
+}
+
+impl InstructionGenerator for CreateSecondaryMarketAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.primary_market, false),
+            AccountMeta::new(self.mint_registry, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.creator_base_token_account, false),
+            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+
+        accounts.append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.creator_activity, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateSecondaryMarket {
+            total_supply: self.total_supply,
+            creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
+            staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetMarketPricesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub price_curve: Curve,
+}
+
+impl SetMarketPricesAction {
+    pub fn new(curve: Curve) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &make_address("base_token_mint").to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config: make_address("config"),
+            market,
+            signer: make_address("alice"),
+            price_curve: curve,
+        }
+    }
+
+    pub fn with_custom_base_token_mint(mut self, base_token_mint: Pubkey) -> Self {
+        self.market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+}
+
+impl InstructionGenerator for SetMarketPricesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let Curve {
+            bid_prices,
+            ask_prices,
+        } = self.price_curve;
+
+        let input = token_mill::instruction::SetMarketPrices {
+            bid_prices,
+            ask_prices,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct AppendPricesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub start_index: u8,
+    pub bid_prices: Vec<u64>,
+    pub ask_prices: Vec<u64>,
+}
+
+impl AppendPricesAction {
+    pub fn new(curve: Curve, range: Range<usize>) -> Self {
+        let SetMarketPricesAction {
+            config,
+            market,
+            signer,
+            ..
+        } = SetMarketPricesAction::new(curve);
+
+        Self {
+            config,
+            market,
+            signer,
+            start_index: range.start as u8,
+            bid_prices: curve.bid_prices[range.clone()].to_vec(),
+            ask_prices: curve.ask_prices[range].to_vec(),
+        }
+    }
+}
+
+impl InstructionGenerator for AppendPricesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::AppendPrices {
+            start_index: self.start_index,
+            bid_prices: self.bid_prices.clone(),
+            ask_prices: self.ask_prices.clone(),
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CompactPriceTailAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub price_points: u8,
+    pub tail_supply_bps: u16,
+}
+
+impl CompactPriceTailAction {
+    pub fn new(price_points: u8, tail_supply_bps: u16) -> Self {
+        let SetMarketPricesAction { market, signer, .. } =
+            SetMarketPricesAction::new(Curve::default());
+
+        Self {
+            market,
+            signer,
+            price_points,
+            tail_supply_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for CompactPriceTailAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CompactPriceTail {
+            price_points: self.price_points,
+            tail_supply_bps: self.tail_supply_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetPricesForRaiseAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub curve_shape: CurveShape,
+    pub target_raise: u64,
+    pub bid_spread_bps: u16,
+}
+
+impl SetPricesForRaiseAction {
+    pub fn new(curve_shape: CurveShape, target_raise: u64, bid_spread_bps: u16) -> Self {
+        let set_market_prices_action = SetMarketPricesAction::new(Curve::default());
+
+        Self {
+            config: set_market_prices_action.config,
+            market: set_market_prices_action.market,
+            signer: set_market_prices_action.signer,
+            curve_shape,
+            target_raise,
+            bid_spread_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for SetPricesForRaiseAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetPricesForRaise {
+            curve_shape: self.curve_shape,
+            target_raise: self.target_raise,
+            bid_spread_bps: self.bid_spread_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetPricesFromPresetAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub parameters: CurveParameters,
+}
+
+impl SetPricesFromPresetAction {
+    pub fn new(parameters: CurveParameters) -> Self {
+        let set_market_prices_action = SetMarketPricesAction::new(Curve::default());
+
+        Self {
+            config: set_market_prices_action.config,
+            market: set_market_prices_action.market,
+            signer: set_market_prices_action.signer,
+            parameters,
+        }
+    }
+}
+
+impl InstructionGenerator for SetPricesFromPresetAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetPricesFromPreset {
+            parameters: self.parameters,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ResetPricesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub price_curve: Curve,
+}
+
+impl ResetPricesAction {
+    pub fn new(curve: Curve) -> Self {
+        let set_market_prices_action = SetMarketPricesAction::new(curve);
+
+        Self {
+            config: set_market_prices_action.config,
+            market: set_market_prices_action.market,
+            signer: set_market_prices_action.signer,
+            price_curve: curve,
+        }
+    }
+}
+
+impl InstructionGenerator for ResetPricesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let Curve {
+            bid_prices,
+            ask_prices,
+        } = self.price_curve;
+
+        let input = token_mill::instruction::ResetPrices {
+            bid_prices,
+            ask_prices,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SwapAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub user_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub referral_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub revenue_report: Pubkey,
+    pub policy_program: Pubkey,
+    pub referral_accrual: Pubkey,
+    pub referral_code: Pubkey,
+    pub denylist: Pubkey,
+    pub referral_account: Pubkey,
+    pub trader_volume: Pubkey,
+    pub revenue_report_quote_token_ata: Pubkey,
+    pub swap_receipt_tree: Pubkey,
+    pub receipt_merkle_tree: Pubkey,
+    pub compression_program: Pubkey,
+    pub noop_program: Pubkey,
+    pub launch_purchase: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
+    pub referrer_binding: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub fee_exemption: Pubkey,
+    // Remaining accounts
+    pub referrer_code: Option<Pubkey>,
+    // Args
+    pub swap_type: SwapType,
+    pub swap_amount_type: SwapAmountType,
+    pub amount: u64,
+    pub other_amount_threshold: u64,
+    pub deadline: Option<i64>,
+}
+
+impl SwapAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        swap_type: SwapType,
+        swap_amount_type: SwapAmountType,
+        amount: u64,
+        other_amount_threshold: u64,
+        referrer: Option<Pubkey>,
+    ) -> Self {
+        let config = make_address("config");
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let base_token_program = token_mill_env.base_token_type.program_address();
+
+        let signer = make_address("bob");
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &token_mill_env.base_token_type.program_address(),
+        );
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let user_base_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &base_token_mint,
+            &token_mill_env.base_token_type.program_address(),
+        );
+
+        let user_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let protocol_quote_token_ata = get_associated_token_address_with_program_id(
+            &make_address("dave"),
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let referral_account = if let Some(referrer) = referrer {
+            Pubkey::find_program_address(
+                &[
+                    REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
+                    &config.to_bytes(),
+                    &referrer.to_bytes(),
+                ],
+                &token_mill::ID,
+            )
+            .0
+        } else {
+            Pubkey::new_unique()
+        };
+
+        let referral_quote_token_ata = if referrer.is_some() {
+            get_associated_token_address_with_program_id(
+                &referral_account,
+                &quote_token_mint,
+                &quote_token_program,
+            )
+        } else {
+            token_mill::ID
+        };
+
+        // Swaps record their fees in the report of the current epoch
+        let epoch = (token_mill_env.svm.get_unix_timestamp() / REVENUE_EPOCH_DURATION) as u64;
+        let revenue_report = Pubkey::find_program_address(
+            &[
+                REVENUE_REPORT_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+                &epoch.to_le_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            market,
+            base_token_mint,
+            quote_token_mint,
+            market_base_token_ata,
+            market_quote_token_ata,
+            user_base_token_ata,
+            user_quote_token_ata,
+            protocol_quote_token_ata,
+            referral_quote_token_ata,
+            signer,
+            base_token_program,
+            quote_token_program,
+            revenue_report,
+            policy_program: token_mill::ID,
+            referral_accrual: token_mill::ID,
+            referral_code: token_mill::ID,
+            denylist: token_mill::ID,
+            referral_account: token_mill::ID,
+            trader_volume: token_mill::ID,
+            revenue_report_quote_token_ata: token_mill::ID,
+            swap_receipt_tree: token_mill::ID,
+            receipt_merkle_tree: token_mill::ID,
+            compression_program: token_mill::ID,
+            noop_program: token_mill::ID,
+            launch_purchase: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+            referrer_binding: tm_referrer_binding(&signer),
+            fee_hook_program: token_mill::ID,
+            fee_exemption: token_mill::ID,
+            referrer_code: None,
+            swap_type,
+            swap_amount_type,
+            amount,
+            other_amount_threshold,
+            deadline: None,
+        }
+    }
+
+    pub fn with_revenue_report(mut self, revenue_report: Pubkey) -> Self {
+        self.revenue_report = revenue_report;
+
+        self
+    }
+
+    pub fn with_referral_accrual(mut self, referral_accrual: Pubkey) -> Self {
+        self.referral_accrual = referral_accrual;
+
+        self
+    }
+
+    pub fn with_referral_code(mut self, referral_code: Pubkey) -> Self {
+        self.referral_code = referral_code;
+
+        self
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+
+    pub fn with_referral_account(mut self, referral_account: Pubkey) -> Self {
+        self.referral_account = referral_account;
+
+        self
+    }
+
+    pub fn with_trader_volume(
+        mut self,
+        trader_volume: Pubkey,
+        revenue_report_quote_token_ata: Pubkey,
+    ) -> Self {
+        self.trader_volume = trader_volume;
+        self.revenue_report_quote_token_ata = revenue_report_quote_token_ata;
+
+        self
+    }
+
+    pub fn with_market_oracle(mut self) -> Self {
+        self.market_oracle = Pubkey::find_program_address(
+            &[MARKET_ORACLE_PDA_SEED.as_bytes(), &self.market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+
+    pub fn with_fee_exemption(mut self) -> Self {
+        self.fee_exemption = Pubkey::find_program_address(
+            &[
+                FEE_EXEMPTION_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &self.signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+
+    pub fn with_referrer_code(mut self, code: &str) -> Self {
+        self.referrer_code = Some(CreateReferralCodeAction::new(code).referral_code);
+
+        self
+    }
+
+    pub fn with_market_stats(mut self) -> Self {
+        self.market_stats = tm_market_stats(&self.market);
+
+        self
+    }
+
+    pub fn with_signer(mut self, signer: Pubkey) -> Self {
+        self.user_base_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &self.base_token_mint,
+            &self.base_token_program,
+        );
+        self.user_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &self.quote_token_mint,
+            &self.quote_token_program,
+        );
+        self.referrer_binding = tm_referrer_binding(&signer);
+        self.signer = signer;
+
+        self
+    }
+}
+
+impl InstructionGenerator for SwapAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.user_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new(self.referral_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+        accounts.push(AccountMeta::new(self.revenue_report, false));
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.referral_accrual, false));
+        accounts.push(AccountMeta::new(self.referral_code, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+        accounts.push(AccountMeta::new(self.referral_account, false));
+        accounts.push(AccountMeta::new(self.trader_volume, false));
+        accounts.push(AccountMeta::new(self.revenue_report_quote_token_ata, false));
+        accounts.push(AccountMeta::new(self.swap_receipt_tree, false));
+        accounts.push(AccountMeta::new(self.receipt_merkle_tree, false));
+        accounts.push(AccountMeta::new_readonly(self.compression_program, false));
+        accounts.push(AccountMeta::new_readonly(self.noop_program, false));
+        accounts.push(AccountMeta::new(self.launch_purchase, false));
+        accounts.push(AccountMeta::new(self.market_oracle, false));
+        accounts.push(AccountMeta::new(self.market_stats, false));
+        accounts.push(AccountMeta::new(self.referrer_binding, false));
+        accounts.push(AccountMeta::new_readonly(self.fee_hook_program, false));
+        accounts.push(AccountMeta::new_readonly(self.fee_exemption, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        if let Some(referrer_code) = self.referrer_code {
+            accounts.push(AccountMeta::new_readonly(referrer_code, false));
+        }
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::Swap {
+            swap_type: self.swap_type,
+            swap_amount_type: self.swap_amount_type,
+            amount: self.amount,
+            other_amount_threshold: self.other_amount_threshold,
+            deadline: self.deadline,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateCreatorAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_creator: Pubkey,
+}
+
+impl UpdateCreatorAction {
+    pub fn new(new_creator: Pubkey) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &make_address("base_token_mint").to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            signer: make_address("alice"),
+            new_creator,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateCreatorAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateCreator {
+            new_creator: self.new_creator,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMarketFeeSharesAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_creator_fee_share: u16,
+    pub new_staking_fee_share: u16,
+}
+
+impl UpdateMarketFeeSharesAction {
+    pub fn new(new_creator_fee_share: u16, new_staking_fee_share: u16) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &make_address("base_token_mint").to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            signer: make_address("alice"),
+            new_creator_fee_share,
+            new_staking_fee_share,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMarketFeeSharesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMarketFeeShares {
+            new_creator_fee_share: self.new_creator_fee_share,
+            new_staking_fee_share: self.new_staking_fee_share,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct AdoptNewFeeDefaultsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_creator_fee_share: u16,
+    pub new_staking_fee_share: u16,
+}
+
+impl AdoptNewFeeDefaultsAction {
+    pub fn new(
+        testing_env: &TokenMillEnv,
+        new_creator_fee_share: u16,
+        new_staking_fee_share: u16,
+    ) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: make_address("alice"),
+            new_creator_fee_share,
+            new_staking_fee_share,
+        }
+    }
+}
+
+impl InstructionGenerator for AdoptNewFeeDefaultsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::AdoptNewFeeDefaults {
+            new_creator_fee_share: self.new_creator_fee_share,
+            new_staking_fee_share: self.new_staking_fee_share,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct BoostStakingRewardsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub creator_quote_token_account: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+    // Args
+    pub amount: u64,
+    pub source: StakingBoostSource,
+}
+
+impl BoostStakingRewardsAction {
+    pub fn new(testing_env: &TokenMillEnv, amount: u64, source: StakingBoostSource) -> Self {
+        let market = testing_env.market;
+        let signer = make_address("alice");
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let creator_quote_token_account = match source {
+            StakingBoostSource::Wallet => get_associated_token_address_with_program_id(
+                &signer,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            StakingBoostSource::CreatorFees => token_mill::ID,
+        };
+
+        Self {
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            creator_quote_token_account,
+            signer,
+            quote_token_program,
+            amount,
+            source,
+        }
+    }
+}
+
+impl InstructionGenerator for BoostStakingRewardsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_account, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::BoostStakingRewards {
+            amount: self.amount,
+            source: self.source,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMarketMaxSwapSizeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_max_swap_size_bps: u16,
+}
+
+impl UpdateMarketMaxSwapSizeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, new_max_swap_size_bps: u16) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            new_max_swap_size_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMarketMaxSwapSizeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMarketMaxSwapSize {
+            new_max_swap_size_bps: self.new_max_swap_size_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateHolderRewardsFeeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_holder_rewards_fee_bps: u16,
+}
+
+impl UpdateHolderRewardsFeeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, new_holder_rewards_fee_bps: u16) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            new_holder_rewards_fee_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateHolderRewardsFeeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateHolderRewardsFee {
+            new_holder_rewards_fee_bps: self.new_holder_rewards_fee_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateLaunchFeeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub launch_fee_bps: u16,
+    pub launch_fee_slots: u64,
+}
+
+impl UpdateLaunchFeeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, launch_fee_bps: u16, launch_fee_slots: u64) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            launch_fee_bps,
+            launch_fee_slots,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateLaunchFeeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateLaunchFee {
+            launch_fee_bps: self.launch_fee_bps,
+            launch_fee_slots: self.launch_fee_slots,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateEmissionScheduleAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub initial_supply: u64,
+    pub start: i64,
+    pub duration: i64,
+}
+
+impl UpdateEmissionScheduleAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        initial_supply: u64,
+        start: i64,
+        duration: i64,
+    ) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            initial_supply,
+            start,
+            duration,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateEmissionScheduleAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateEmissionSchedule {
+            initial_supply: self.initial_supply,
+            start: self.start,
+            duration: self.duration,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMinReferredVolumeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_min_referred_volume: u64,
+}
+
+impl UpdateMinReferredVolumeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, new_min_referred_volume: u64) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            new_min_referred_volume,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMinReferredVolumeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMinReferredVolume {
+            new_min_referred_volume: self.new_min_referred_volume,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMinClaimAmountAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_min_claim_amount: u64,
+}
+
+impl UpdateMinClaimAmountAction {
+    pub fn new(token_mill_env: &TokenMillEnv, new_min_claim_amount: u64) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            new_min_claim_amount,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMinClaimAmountAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMinClaimAmount {
+            new_min_claim_amount: self.new_min_claim_amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateStakingFreezeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub freeze_staking_on_migration: bool,
+}
+
+impl UpdateStakingFreezeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, freeze_staking_on_migration: bool) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            freeze_staking_on_migration,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateStakingFreezeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateStakingFreeze {
+            freeze_staking_on_migration: self.freeze_staking_on_migration,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ScheduleMarketUpdateAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub kind: ScheduledUpdateKind,
+    pub value: u64,
+    pub slot: u64,
+}
+
+impl ScheduleMarketUpdateAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        kind: ScheduledUpdateKind,
+        value: u64,
+        slot: u64,
+    ) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            kind,
+            value,
+            slot,
+        }
+    }
+}
+
+impl InstructionGenerator for ScheduleMarketUpdateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ScheduleMarketUpdate {
+            kind: self.kind,
+            value: self.value,
+            slot: self.slot,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CancelScheduledUpdateAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub index: u8,
+}
+
+impl CancelScheduledUpdateAction {
+    pub fn new(token_mill_env: &TokenMillEnv, index: u8) -> Self {
+        Self {
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            index,
+        }
+    }
+}
+
+impl InstructionGenerator for CancelScheduledUpdateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CancelScheduledUpdate { index: self.index };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimCreatorFeesAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub creator_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+}
+
+impl ClaimCreatorFeesAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &token_mill_env.base_token_mint.unwrap().to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let signer = make_address("alice");
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let creator_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            creator_quote_token_ata,
+            signer,
+            quote_token_program,
+            config: make_address("config"),
+            denylist: token_mill::ID,
+        }
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+}
+
+impl InstructionGenerator for ClaimCreatorFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.push(AccountMeta::new_readonly(self.config, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimCreatorFees {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimProtocolFeesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub authority_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+pub struct PrepareTradeAccountsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub user_quote_token_ata: Pubkey,
+    pub stake_position: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl PrepareTradeAccountsAction {
+    pub fn new(token_mill_env: &TokenMillEnv, signer: Pubkey) -> Self {
+        let market = token_mill_env.market;
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let base_token_program = token_mill_env.base_token_type.program_address();
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let user_base_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let user_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            base_token_mint,
+            quote_token_mint,
+            user_base_token_ata,
+            user_quote_token_ata,
+            stake_position,
+            signer,
+            base_token_program,
+            quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for PrepareTradeAccountsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.user_quote_token_ata, false),
+            AccountMeta::new(self.stake_position, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts
+            .append_associated_token_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PrepareTradeAccounts {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ReconcileFeesAction {
+    // Accounts
+    pub market: Pubkey,
+    pub fee_reconciliation: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl ReconcileFeesAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let fee_reconciliation = Pubkey::find_program_address(
+            &[FEE_RECONCILIATION_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            fee_reconciliation,
+            quote_token_mint,
+            market_quote_token_ata,
+            quote_token_program,
+            signer: token_mill_env.svm.payer,
+        }
+    }
+}
+
+impl InstructionGenerator for ReconcileFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.fee_reconciliation, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new_readonly(self.market_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ReconcileFees {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct QuoteSwapAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    // Args
+    pub swap_type: SwapType,
+    pub swap_amount_type: SwapAmountType,
+    pub amount: u64,
+}
+
+impl QuoteSwapAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        swap_type: SwapType,
+        swap_amount_type: SwapAmountType,
+        amount: u64,
+    ) -> Self {
+        Self {
+            config: make_address("config"),
+            market: token_mill_env.market,
+            quote_token_mint: token_mill_env.quote_token_mint.unwrap(),
+            swap_type,
+            swap_amount_type,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for QuoteSwapAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+        ]
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::QuoteSwap {
+            swap_type: self.swap_type,
+            swap_amount_type: self.swap_amount_type,
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct GraduationProgressAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+}
+
+impl GraduationProgressAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        Self {
+            config: make_address("config"),
+            market: token_mill_env.market,
+        }
+    }
+}
+
+impl InstructionGenerator for GraduationProgressAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+        ]
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::GraduationProgress {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct MarketHealthAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub price_feed: Pubkey,
+    // Args
+    pub max_oracle_staleness: u64,
+}
+
+impl MarketHealthAction {
+    pub fn new(token_mill_env: &TokenMillEnv, max_oracle_staleness: u64) -> Self {
+        let market = token_mill_env.market;
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            quote_token_program,
+            price_feed: token_mill::ID,
+            max_oracle_staleness,
+        }
+    }
+
+    pub fn with_price_feed(mut self) -> Self {
+        self.price_feed = Pubkey::find_program_address(
+            &[PRICE_FEED_PDA_SEED.as_bytes(), &self.market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+}
+
+impl InstructionGenerator for MarketHealthAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new_readonly(self.market_quote_token_ata, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(self.price_feed, false),
+        ]
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::MarketHealth {
+            max_oracle_staleness: self.max_oracle_staleness,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+/// The AMM programs aren't loaded in the test environment, so the pool accounts are placeholders.
+pub struct WalletPositionAction {
+    // Accounts
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub wallet: Pubkey,
+    pub wallet_base_token_account: Pubkey,
+    pub staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub referral_account: Pubkey,
+    pub referral_account_quote_token_ata: Pubkey,
+    pub referral_accrual: Pubkey,
+    pub vesting_plans: Vec<Pubkey>,
+}
+
+impl WalletPositionAction {
+    pub fn new(token_mill_env: &TokenMillEnv, wallet: Pubkey) -> Self {
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let wallet_base_token_account = get_associated_token_address_with_program_id(
+            &wallet,
+            &base_token_mint,
+            &token_mill_env.base_token_type.program_address(),
+        );
+
+        Self {
+            market: token_mill_env.market,
+            base_token_mint,
+            wallet,
+            wallet_base_token_account: if token_mill_env
+                .svm
+                .account_exists(&wallet_base_token_account)
+            {
+                wallet_base_token_account
+            } else {
+                token_mill::ID
+            },
+            staking: token_mill::ID,
+            stake_position: token_mill::ID,
+            referral_account: token_mill::ID,
+            referral_account_quote_token_ata: token_mill::ID,
+            referral_accrual: token_mill::ID,
+            vesting_plans: Vec::new(),
+        }
+    }
+
+    pub fn with_staking(mut self) -> Self {
+        self.staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &self.market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+        self.stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &self.wallet.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+}
+
+impl InstructionGenerator for WalletPositionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.wallet, false),
+            AccountMeta::new_readonly(self.wallet_base_token_account, false),
+            AccountMeta::new_readonly(self.staking, false),
+            AccountMeta::new_readonly(self.stake_position, false),
+            AccountMeta::new_readonly(self.referral_account, false),
+            AccountMeta::new_readonly(self.referral_account_quote_token_ata, false),
+            AccountMeta::new_readonly(self.referral_accrual, false),
+        ];
+
+        accounts.extend(
+            self.vesting_plans
+                .iter()
+                .map(|vesting_plan| AccountMeta::new_readonly(*vesting_plan, false)),
+        );
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::WalletPosition {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct MigrateAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub authority_base_token_ata: Pubkey,
+    pub authority_quote_token_ata: Pubkey,
+    pub migration_program: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub quote_token_badge: Pubkey,
+    pub quote_price_update: Pubkey,
+    pub graduation_attestation: Pubkey,
+    pub graduation_hook_program: Pubkey,
+    pub pool_accounts: Vec<AccountMeta>,
+    // Sends `migrate_idempotent` instead
+    pub idempotent: bool,
+}
+
+impl MigrateAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+        let signer = make_address("admin");
+
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let base_token_program = token_mill_env.base_token_type.program_address();
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let lp_mint = Pubkey::new_unique();
+
+        // Raydium CPMM pool accounts
+        let pool_accounts = vec![
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(lp_mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &signer,
+                    &lp_mint,
+                    &anchor_spl::token::ID,
+                ),
+                false,
+            ),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(anchor_spl::token::ID, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &market,
+                    &lp_mint,
+                    &anchor_spl::token::ID,
+                ),
+                false,
+            ),
+        ];
+
+        Self {
+            config: make_address("config"),
+            market,
+            base_token_mint,
+            quote_token_mint,
+            market_base_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &base_token_mint,
+                &base_token_program,
+            ),
+            market_quote_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            authority_base_token_ata: get_associated_token_address_with_program_id(
+                &signer,
+                &base_token_mint,
+                &base_token_program,
+            ),
+            authority_quote_token_ata: get_associated_token_address_with_program_id(
+                &signer,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            migration_program: RAYDIUM_CP_SWAP_PROGRAM_ID,
+            signer,
+            base_token_program,
+            quote_token_program,
+            quote_token_badge: token_mill::ID,
+            quote_price_update: token_mill::ID,
+            graduation_attestation: Pubkey::find_program_address(
+                &[
+                    GRADUATION_ATTESTATION_PDA_SEED.as_bytes(),
+                    &market.to_bytes(),
+                ],
+                &token_mill::ID,
+            )
+            .0,
+            graduation_hook_program: token_mill::ID,
+            pool_accounts,
+            idempotent: false,
+        }
+    }
+
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+
+        self
+    }
+
+    /// Values the curve proceeds with the price update of the quote token badge's feed.
+    pub fn with_price_update(mut self, quote_price_update: Pubkey) -> Self {
+        self.quote_token_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &self.config.to_bytes(),
+                &self.quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+        self.quote_price_update = quote_price_update;
+
+        self
+    }
+
+    pub fn with_meteora(mut self) -> Self {
+        self.migration_program = METEORA_DAMM_V2_PROGRAM_ID;
+        self.pool_accounts = vec![
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new(Pubkey::new_unique(), false),
+            AccountMeta::new_readonly(spl_token_2022::id(), false),
+            AccountMeta::new_readonly(Pubkey::new_unique(), false),
+        ];
+
+        self
+    }
+}
+
+impl InstructionGenerator for MigrateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = Vec::new();
+
+        // The accounts of `migrate` are passed as remaining accounts of `migrate_idempotent`
+        if self.idempotent {
+            accounts.push(AccountMeta::new_readonly(self.market, false));
+            accounts.push(AccountMeta::new_readonly(
+                self.graduation_attestation,
+                false,
+            ));
+        }
+
+        accounts.extend([
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.authority_base_token_ata, false),
+            AccountMeta::new(self.authority_quote_token_ata, false),
+            AccountMeta::new_readonly(self.migration_program, false),
+        ]);
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts
+            .append_associated_token_program()
+            .append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(sysvar::rent::ID, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_badge, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_price_update, false));
+        accounts.push(AccountMeta::new(self.graduation_attestation, false));
+        accounts.push(AccountMeta::new_readonly(
+            self.graduation_hook_program,
+            false,
+        ));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts.extend(self.pool_accounts.iter().cloned());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let data = if self.idempotent {
+            token_mill::instruction::MigrateIdempotent {}.data()
+        } else {
+            token_mill::instruction::Migrate {}.data()
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data,
+        }
+    }
+}
+
+pub struct MigrateQuoteTokenAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub new_quote_token_mint: Pubkey,
+    pub new_quote_token_badge: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub market_new_quote_token_ata: Pubkey,
+    pub authority_quote_token_ata: Pubkey,
+    pub authority_new_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub creator: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub new_quote_token_program: Pubkey,
+    // Args
+    pub conversion_rate: u64,
+}
+
+impl MigrateQuoteTokenAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        new_quote_token_mint: Pubkey,
+        new_quote_token_type: TokenType,
+        conversion_rate: u64,
+    ) -> Self {
+        let config = make_address("config");
+        let market = token_mill_env.market;
+        let signer = make_address("admin");
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+        let new_quote_token_program = new_quote_token_type.program_address();
+
+        let staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let new_quote_token_badge = Pubkey::find_program_address(
+            &[
+                QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &new_quote_token_mint.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            market,
+            staking,
+            quote_token_mint,
+            new_quote_token_mint,
+            new_quote_token_badge,
+            market_quote_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            market_new_quote_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &new_quote_token_mint,
+                &new_quote_token_program,
+            ),
+            authority_quote_token_ata: get_associated_token_address_with_program_id(
+                &signer,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            authority_new_quote_token_ata: get_associated_token_address_with_program_id(
+                &signer,
+                &new_quote_token_mint,
+                &new_quote_token_program,
+            ),
+            signer,
+            creator: make_address("alice"),
+            quote_token_program,
+            new_quote_token_program,
+            conversion_rate,
+        }
+    }
+}
+
+impl InstructionGenerator for MigrateQuoteTokenAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.staking, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new_readonly(self.new_quote_token_mint, false),
+            AccountMeta::new_readonly(self.new_quote_token_badge, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.market_new_quote_token_ata, false),
+            AccountMeta::new(self.authority_quote_token_ata, false),
+            AccountMeta::new(self.authority_new_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.creator, true));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+        accounts.push(AccountMeta::new_readonly(
+            self.new_quote_token_program,
+            false,
+        ));
+
+        accounts
+            .append_associated_token_program()
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::MigrateQuoteToken {
+            conversion_rate: self.conversion_rate,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CloseMarketAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl CloseMarketAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let base_token_program = token_mill_env.base_token_type.program_address();
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        Self {
+            config: make_address("config"),
+            market,
+            staking: Pubkey::find_program_address(
+                &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+                &token_mill::ID,
+            )
+            .0,
+            base_token_mint,
+            quote_token_mint,
+            market_base_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &base_token_mint,
+                &base_token_program,
+            ),
+            market_quote_token_ata: get_associated_token_address_with_program_id(
+                &market,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            rent_recipient: make_address("admin"),
+            base_token_program,
+            quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for CloseMarketAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.rent_recipient, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CloseMarket {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateMigrationTargetAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_migration_target: MigrationTarget,
+}
+
+impl UpdateMigrationTargetAction {
+    pub fn new(new_migration_target: MigrationTarget) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_migration_target,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateMigrationTargetAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateMigrationTarget {
+            new_migration_target: self.new_migration_target,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateGraduationThresholdsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_graduation_quote_threshold: u64,
+    pub new_graduation_supply_bps: u16,
+}
+
+impl UpdateGraduationThresholdsAction {
+    pub fn new(new_graduation_quote_threshold: u64, new_graduation_supply_bps: u16) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_graduation_quote_threshold,
+            new_graduation_supply_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateGraduationThresholdsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateGraduationThresholds {
+            new_graduation_quote_threshold: self.new_graduation_quote_threshold,
+            new_graduation_supply_bps: self.new_graduation_supply_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateUsdGraduationAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub usd_graduation: UsdGraduation,
+}
+
+impl UpdateUsdGraduationAction {
+    pub fn new(usd_graduation: UsdGraduation) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            usd_graduation,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateUsdGraduationAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateUsdGraduation {
+            usd_graduation: self.usd_graduation,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateRentRecipientAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_rent_recipient: Pubkey,
+}
+
+impl UpdateRentRecipientAction {
+    pub fn new(new_rent_recipient: Pubkey) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_rent_recipient,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateRentRecipientAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateRentRecipient {
+            new_rent_recipient: self.new_rent_recipient,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateStakingAction {
+    // Accounts
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateStakingAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &testing_env.base_token_mint.unwrap().to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            staking,
+            signer: make_address("admin"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateStakingAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.staking, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateStaking {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateStakingIdempotentAction {
+    // Accounts
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateStakingIdempotentAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let create_staking_action = CreateStakingAction::new(testing_env);
+
+        Self {
+            market: create_staking_action.market,
+            staking: create_staking_action.staking,
+            signer: testing_env.svm.payer,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateStakingIdempotentAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.staking, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateStakingIdempotent {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateStakePositionAction {
+    // Accounts
+    pub market: Pubkey,
+    pub stake_position: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateStakePositionAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let market = Pubkey::find_program_address(
+            &[
+                MARKET_PDA_SEED.as_bytes(),
+                &testing_env.base_token_mint.unwrap().to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let signer: Pubkey = make_address("bob");
+
+        let stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            stake_position,
+            signer,
+        }
+    }
+
+    pub fn with_signer(mut self, signer: Pubkey) -> Self {
+        self.stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+        self.signer = signer;
+
+        self
+    }
+}
+
+impl InstructionGenerator for CreateStakePositionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.stake_position, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateStakePosition {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateIndexedStakePositionAction {
+    // Accounts
+    pub market: Pubkey,
+    pub stake_position: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub index: u32,
+}
+
+impl CreateIndexedStakePositionAction {
+    pub fn new(testing_env: &TokenMillEnv, index: u32) -> Self {
+        let CreateStakePositionAction { market, signer, .. } =
+            CreateStakePositionAction::new(testing_env);
+
+        Self {
+            market,
+            stake_position: tm_indexed_stake_position(&market, &signer, index),
+            signer,
+            index,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateIndexedStakePositionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.stake_position, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateIndexedStakePosition { index: self.index };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct DepositAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl DepositAction {
+    pub fn new(testing_env: &TokenMillEnv, amount: u64) -> Self {
+        let signer = make_address("bob");
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let base_token_program = testing_env.base_token_type.program_address();
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let user_base_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        Self {
+            market,
+            market_staking,
+            stake_position,
+            base_token_mint,
+            base_token_program,
+            market_base_token_ata,
+            user_base_token_ata,
+            signer,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for DepositAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::Deposit {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct WithdrawAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl WithdrawAction {
+    pub fn new(testing_env: &TokenMillEnv, amount: u64) -> Self {
+        let deposit_action = DepositAction::new(testing_env, amount);
+
+        Self {
+            market: deposit_action.market,
+            market_staking: deposit_action.market_staking,
+            stake_position: deposit_action.stake_position,
+            base_token_mint: deposit_action.base_token_mint,
+            base_token_program: deposit_action.base_token_program,
+            market_base_token_ata: deposit_action.market_base_token_ata,
+            user_base_token_ata: deposit_action.user_base_token_ata,
+            signer: deposit_action.signer,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for WithdrawAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::Withdraw {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct GetStakingAprAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+}
+
+impl GetStakingAprAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let market = testing_env.market;
+
+        let market_staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            market_staking,
+        }
+    }
+}
+
+impl InstructionGenerator for GetStakingAprAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.market_staking, false),
+        ]
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::GetStakingApr {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SplitPositionAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub destination_stake_position: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl SplitPositionAction {
+    /// Splits into the position of the signer at `index`, see `CreateIndexedStakePositionAction`.
+    pub fn new(testing_env: &TokenMillEnv, index: u32, amount: u64) -> Self {
+        let deposit_action = DepositAction::new(testing_env, amount);
+
+        let destination_stake_position =
+            tm_indexed_stake_position(&deposit_action.market, &deposit_action.signer, index);
+
+        Self {
+            market: deposit_action.market,
+            market_staking: deposit_action.market_staking,
+            stake_position: deposit_action.stake_position,
+            destination_stake_position,
+            signer: deposit_action.signer,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for SplitPositionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new(self.destination_stake_position, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SplitPosition {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct MergePositionsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub destination_stake_position: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl MergePositionsAction {
+    pub fn new(testing_env: &TokenMillEnv, index: u32) -> Self {
+        let split_position_action = SplitPositionAction::new(testing_env, index, 0);
+
+        Self {
+            market: split_position_action.market,
+            market_staking: split_position_action.market_staking,
+            stake_position: split_position_action.stake_position,
+            destination_stake_position: split_position_action.destination_stake_position,
+            signer: split_position_action.signer,
+        }
+    }
+}
+
+impl InstructionGenerator for MergePositionsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new(self.destination_stake_position, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::MergePositions {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+fn get_claim_delegate_address(stake_position: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CLAIM_DELEGATE_PDA_SEED.as_bytes(),
+            &stake_position.to_bytes(),
+        ],
+        &token_mill::ID,
+    )
+    .0
+}
+
+pub struct SetClaimDelegateAction {
+    // Accounts
+    pub stake_position: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub delegate: Pubkey,
+}
+
+impl SetClaimDelegateAction {
+    pub fn new(token_mill_env: &TokenMillEnv, delegate: Pubkey) -> Self {
+        let stake_position = CreateStakePositionAction::new(token_mill_env).stake_position;
+
+        Self {
+            stake_position,
+            claim_delegate: get_claim_delegate_address(&stake_position),
+            signer: make_address("bob"),
+            delegate,
+        }
+    }
+}
+
+impl InstructionGenerator for SetClaimDelegateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.stake_position, false),
+            AccountMeta::new(self.claim_delegate, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetClaimDelegate {
+            delegate: self.delegate,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct RemoveClaimDelegateAction {
+    // Accounts
+    pub stake_position: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl RemoveClaimDelegateAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let stake_position = CreateStakePositionAction::new(token_mill_env).stake_position;
+
+        Self {
+            stake_position,
+            claim_delegate: get_claim_delegate_address(&stake_position),
+            signer: make_address("bob"),
+        }
+    }
+}
+
+impl InstructionGenerator for RemoveClaimDelegateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.stake_position, false),
+            AccountMeta::new(self.claim_delegate, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::RemoveClaimDelegate {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetAutoClaimAction {
+    // Accounts
+    pub stake_position: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub auto_claim: bool,
+}
+
+impl SetAutoClaimAction {
+    pub fn new(token_mill_env: &TokenMillEnv, auto_claim: bool) -> Self {
+        let SetClaimDelegateAction {
+            stake_position,
+            claim_delegate,
+            signer,
+            ..
+        } = SetClaimDelegateAction::new(token_mill_env, Pubkey::default());
+
+        Self {
+            stake_position,
+            claim_delegate,
+            signer,
+            auto_claim,
+        }
+    }
+}
+
+impl InstructionGenerator for SetAutoClaimAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.stake_position, false),
+            AccountMeta::new(self.claim_delegate, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetAutoClaim {
+            auto_claim: self.auto_claim,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimStakingRewardsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub user_quote_token_ata: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub signer: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub delegate: Pubkey,
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+}
+
+impl ClaimStakingRewardsAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let signer = make_address("bob");
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let user_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            market_staking,
+            stake_position,
+            quote_token_mint,
+            market_quote_token_ata,
+            user_quote_token_ata,
+            quote_token_program,
+            signer,
+            claim_delegate: token_mill::ID,
+            delegate: token_mill::ID,
+            config: make_address("config"),
+            denylist: token_mill::ID,
+        }
+    }
+
+    /// Triggers the claim from the delegate of the stake position, the owner doesn't sign.
+    pub fn with_delegate(mut self, delegate: Pubkey) -> Self {
+        self.claim_delegate = get_claim_delegate_address(&self.stake_position);
+        self.delegate = delegate;
+
+        self
+    }
+
+    /// Triggers the claim from a keeper, for positions registered for auto-claim.
+    pub fn with_auto_claim(mut self) -> Self {
+        self.claim_delegate = get_claim_delegate_address(&self.stake_position);
+
+        self
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+}
+
+impl InstructionGenerator for ClaimStakingRewardsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.user_quote_token_ata, false),
+            AccountMeta::new_readonly(self.signer, self.claim_delegate == token_mill::ID),
+        ];
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.push(AccountMeta::new_readonly(self.claim_delegate, false));
+        accounts.push(AccountMeta::new_readonly(
+            self.delegate,
+            self.delegate != token_mill::ID,
+        ));
+        accounts.push(AccountMeta::new_readonly(self.config, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimStakingRewards {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateReferrerBindingAction {
+    // Accounts
+    pub config: Pubkey,
+    pub referrer_binding: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl Default for CreateReferrerBindingAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreateReferrerBindingAction {
+    pub fn new() -> Self {
+        let signer = make_address("bob");
+
+        Self {
+            config: make_address("config"),
+            referrer_binding: tm_referrer_binding(&signer),
+            signer,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateReferrerBindingAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.referrer_binding, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateReferrerBinding {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateReferralAccountAction {
+    // Accounts
+    pub config: Pubkey,
+    pub referral_account: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub referrer: Pubkey,
+}
+
+impl Default for CreateReferralAccountAction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CreateReferralAccountAction {
+    pub fn new() -> Self {
+        let config = make_address("config");
+
+        let referrer = make_address("carol");
+
+        let referral_account = Pubkey::find_program_address(
+            &[
+                REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
+                (config.as_ref()),
+                (referrer.as_ref()),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            referral_account,
+            signer: make_address("admin"),
+            referrer,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateReferralAccountAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.referral_account, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateReferralAccount {
+            referrer: self.referrer,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateReferralCodeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub referral_account: Pubkey,
+    pub referral_code: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub code: [u8; 32],
+}
+
+impl CreateReferralCodeAction {
+    pub fn new(code: &str) -> Self {
+        let config = make_address("config");
+
+        let mut code_bytes = [0; 32];
+        code_bytes[..code.len()].copy_from_slice(code.as_bytes());
+
+        Self {
+            config,
+            referral_account: CreateReferralAccountAction::new().referral_account,
+            referral_code: tm_referral_code(&code_bytes),
+            signer: make_address("carol"),
+            code: code_bytes,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateReferralCodeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.referral_account, false),
+            AccountMeta::new(self.referral_code, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateReferralCode { code: self.code };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimReferralFeesAction {
+    pub config: Pubkey,
+    pub referral_account: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub referral_account_quote_token_ata: Pubkey,
+    pub referrer_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub denylist: Pubkey,
+}
+
+impl ClaimReferralFeesAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let config = make_address("config");
+        let signer = make_address("carol");
+
+        let referral_account = Pubkey::find_program_address(
+            &[
+                REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
+                (config.as_ref()),
+                (signer.as_ref()),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let referral_account_quote_token_ata = get_associated_token_address_with_program_id(
+            &referral_account,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let referrer_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            config,
+            referral_account,
+            quote_token_mint,
+            referral_account_quote_token_ata,
+            referrer_quote_token_ata,
+            signer,
+            quote_token_program,
+            denylist: token_mill::ID,
+        }
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+}
+
+impl InstructionGenerator for ClaimReferralFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.referral_account, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.referral_account_quote_token_ata, false),
+            AccountMeta::new(self.referrer_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.push(AccountMeta::new_readonly(self.config, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimReferralFees {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketReferralAccrualAction {
+    // Accounts
+    pub market: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub referral_accrual: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateMarketReferralAccrualAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        let referral_token_account = get_associated_token_address_with_program_id(
+            &CreateReferralAccountAction::new().referral_account,
+            &token_mill_env.quote_token_mint.unwrap(),
+            &token_mill_env.quote_token_type.program_address(),
+        );
+
+        let referral_accrual = Pubkey::find_program_address(
+            &[
+                MARKET_REFERRAL_ACCRUAL_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &referral_token_account.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            referral_token_account,
+            referral_accrual,
+            signer: make_address("carol"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateMarketReferralAccrualAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.referral_token_account, false),
+            AccountMeta::new(self.referral_accrual, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarketReferralAccrual {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimMarketReferralFeesAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub referral_accrual: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl ClaimMarketReferralFeesAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let CreateMarketReferralAccrualAction {
+            referral_token_account,
+            referral_accrual,
+            ..
+        } = CreateMarketReferralAccrualAction::new(token_mill_env);
+
+        Self {
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            referral_accrual,
+            referral_token_account,
+            quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for ClaimMarketReferralFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.referral_accrual, false),
+            AccountMeta::new(self.referral_token_account, false),
+        ];
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimMarketReferralFees {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateRevenueReportAction {
+    // Accounts
+    pub config: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub revenue_report: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub epoch: u64,
+}
+
+impl CreateRevenueReportAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let config = make_address("config");
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let epoch = (testing_env.svm.get_unix_timestamp() / REVENUE_EPOCH_DURATION) as u64;
+
+        let revenue_report = Pubkey::find_program_address(
+            &[
+                REVENUE_REPORT_PDA_SEED.as_bytes(),
+                &config.to_bytes(),
+                &quote_token_mint.to_bytes(),
+                &epoch.to_le_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            quote_token_mint,
+            revenue_report,
+            signer: testing_env.svm.payer,
+            epoch,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateRevenueReportAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.revenue_report, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateRevenueReport { epoch: self.epoch };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct FinalizeRevenueReportAction {
+    // Accounts
+    pub revenue_report: Pubkey,
+}
+
+impl FinalizeRevenueReportAction {
+    pub fn new(revenue_report: Pubkey) -> Self {
+        Self { revenue_report }
+    }
+}
+
+impl InstructionGenerator for FinalizeRevenueReportAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.revenue_report, false)];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::FinalizeRevenueReport {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct UpdateTraderRebateParamsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub new_trader_rebate_params: TraderRebateParams,
+}
+
+impl UpdateTraderRebateParamsAction {
+    pub fn new(new_trader_rebate_params: TraderRebateParams) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_trader_rebate_params,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateTraderRebateParamsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateTraderRebateParams {
+            new_trader_rebate_params: self.new_trader_rebate_params,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateTraderVolumeAction {
+    // Accounts
+    pub revenue_report: Pubkey,
+    pub trader_volume: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub revenue_report_quote_token_ata: Pubkey,
+    pub owner: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl CreateTraderVolumeAction {
+    pub fn new(testing_env: &TokenMillEnv, revenue_report: Pubkey) -> Self {
+        let owner = make_address("bob");
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        let trader_volume = Pubkey::find_program_address(
+            &[
+                TRADER_VOLUME_PDA_SEED.as_bytes(),
+                &revenue_report.to_bytes(),
+                &owner.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let revenue_report_quote_token_ata = get_associated_token_address_with_program_id(
+            &revenue_report,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            revenue_report,
+            trader_volume,
+            quote_token_mint,
+            revenue_report_quote_token_ata,
+            owner,
+            signer: owner,
+            quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateTraderVolumeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.revenue_report, false),
+            AccountMeta::new(self.trader_volume, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.revenue_report_quote_token_ata, false),
+            AccountMeta::new_readonly(self.owner, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts
+            .append_associated_token_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateTraderVolume {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimTraderRebateAction {
+    // Accounts
+    pub revenue_report: Pubkey,
+    pub trader_volume: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub revenue_report_quote_token_ata: Pubkey,
+    pub owner_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl ClaimTraderRebateAction {
+    pub fn new(testing_env: &TokenMillEnv, create_action: &CreateTraderVolumeAction) -> Self {
+        let owner_quote_token_ata = get_associated_token_address_with_program_id(
+            &create_action.owner,
+            &create_action.quote_token_mint,
+            &create_action.quote_token_program,
+        );
+
+        Self {
+            revenue_report: create_action.revenue_report,
+            trader_volume: create_action.trader_volume,
+            quote_token_mint: create_action.quote_token_mint,
+            revenue_report_quote_token_ata: create_action.revenue_report_quote_token_ata,
+            owner_quote_token_ata,
+            signer: create_action.owner,
+            quote_token_program: testing_env.quote_token_type.program_address(),
+        }
+    }
+}
+
+impl InstructionGenerator for ClaimTraderRebateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.revenue_report, false),
+            AccountMeta::new(self.trader_volume, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.revenue_report_quote_token_ata, false),
+            AccountMeta::new(self.owner_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimTraderRebate {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct PlaceBuybackOrderAction {
+    // Accounts
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub buyback_order_base_token_ata: Pubkey,
+    pub buyback_order_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    // Args
+    pub price_floor: u64,
+}
+
+impl PlaceBuybackOrderAction {
+    pub fn new(testing_env: &TokenMillEnv, price_floor: u64) -> Self {
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let base_token_program = testing_env.base_token_type.program_address();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let buyback_order = Pubkey::find_program_address(
+            &[BUYBACK_ORDER_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let buyback_order_base_token_ata = get_associated_token_address_with_program_id(
+            &buyback_order,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let buyback_order_quote_token_ata = get_associated_token_address_with_program_id(
+            &buyback_order,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            buyback_order,
+            base_token_mint,
+            quote_token_mint,
+            market_quote_token_ata,
+            buyback_order_base_token_ata,
+            buyback_order_quote_token_ata,
+            signer: make_address("alice"),
+            base_token_program,
+            quote_token_program,
+            price_floor,
+        }
+    }
+}
+
+impl InstructionGenerator for PlaceBuybackOrderAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.buyback_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.buyback_order_base_token_ata, false),
+            AccountMeta::new(self.buyback_order_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts
+            .append_associated_token_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PlaceBuybackOrder {
+            price_floor: self.price_floor,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ExecuteBuybackOrderAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub buyback_order_base_token_ata: Pubkey,
+    pub buyback_order_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub revenue_report: Pubkey,
+    pub denylist: Pubkey,
+    pub policy_program: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
+    pub launch_purchase: Pubkey,
+    // Args
+    pub quote_amount: u64,
+}
+
+impl ExecuteBuybackOrderAction {
+    pub fn new(testing_env: &TokenMillEnv, quote_amount: u64) -> Self {
+        let place_action = PlaceBuybackOrderAction::new(testing_env, 0);
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &place_action.market,
+            &place_action.base_token_mint,
+            &place_action.base_token_program,
+        );
+
+        let protocol_quote_token_ata = get_associated_token_address_with_program_id(
+            &make_address("dave"),
+            &place_action.quote_token_mint,
+            &place_action.quote_token_program,
+        );
+
+        Self {
+            config: make_address("config"),
+            market: place_action.market,
+            buyback_order: place_action.buyback_order,
+            base_token_mint: place_action.base_token_mint,
+            quote_token_mint: place_action.quote_token_mint,
+            market_base_token_ata,
+            market_quote_token_ata: place_action.market_quote_token_ata,
+            buyback_order_base_token_ata: place_action.buyback_order_base_token_ata,
+            buyback_order_quote_token_ata: place_action.buyback_order_quote_token_ata,
+            protocol_quote_token_ata,
+            base_token_program: place_action.base_token_program,
+            quote_token_program: place_action.quote_token_program,
+            revenue_report: tm_revenue_report(testing_env, &place_action.quote_token_mint),
+            denylist: token_mill::ID,
+            policy_program: token_mill::ID,
+            fee_hook_program: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+            launch_purchase: token_mill::ID,
+            quote_amount,
+        }
+    }
+
+    /// Launch purchase of the creator of the market.
+    pub fn with_launch_purchase(mut self) -> Self {
+        self.launch_purchase = Pubkey::find_program_address(
+            &[
+                LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &make_address("alice").to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+
+    pub fn with_market_stats(mut self) -> Self {
+        self.market_stats = tm_market_stats(&self.market);
+
+        self
+    }
+}
+
+impl InstructionGenerator for ExecuteBuybackOrderAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.buyback_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.buyback_order_base_token_ata, false),
+            AccountMeta::new(self.buyback_order_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new(self.revenue_report, false),
+            AccountMeta::new_readonly(self.denylist, false),
+            AccountMeta::new_readonly(self.policy_program, false),
+            AccountMeta::new_readonly(self.fee_hook_program, false),
+            AccountMeta::new(self.market_oracle, false),
+            AccountMeta::new(self.market_stats, false),
+            AccountMeta::new(self.launch_purchase, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ExecuteBuybackOrder {
+            quote_amount: self.quote_amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CancelBuybackOrderAction {
+    // Accounts
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub buyback_order_base_token_ata: Pubkey,
+    pub buyback_order_quote_token_ata: Pubkey,
+    pub creator_base_token_ata: Pubkey,
+    pub creator_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl CancelBuybackOrderAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let place_action = PlaceBuybackOrderAction::new(testing_env, 0);
+
+        let creator_base_token_ata = get_associated_token_address_with_program_id(
+            &place_action.signer,
+            &place_action.base_token_mint,
+            &place_action.base_token_program,
+        );
+
+        let creator_quote_token_ata = get_associated_token_address_with_program_id(
+            &place_action.signer,
+            &place_action.quote_token_mint,
+            &place_action.quote_token_program,
+        );
+
+        Self {
+            market: place_action.market,
+            buyback_order: place_action.buyback_order,
+            base_token_mint: place_action.base_token_mint,
+            quote_token_mint: place_action.quote_token_mint,
+            buyback_order_base_token_ata: place_action.buyback_order_base_token_ata,
+            buyback_order_quote_token_ata: place_action.buyback_order_quote_token_ata,
+            creator_base_token_ata,
+            creator_quote_token_ata,
+            signer: place_action.signer,
+            base_token_program: place_action.base_token_program,
+            quote_token_program: place_action.quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for CancelBuybackOrderAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.buyback_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.buyback_order_base_token_ata, false),
+            AccountMeta::new(self.buyback_order_quote_token_ata, false),
+            AccountMeta::new(self.creator_base_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CancelBuybackOrder {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SetProtocolFeeConverterAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub protocol_fee_converter: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub treasury: Pubkey,
+    pub max_price: u64,
+}
+
+impl SetProtocolFeeConverterAction {
+    pub fn new(testing_env: &TokenMillEnv, max_price: u64) -> Self {
+        let config = make_address("config");
+
+        Self {
+            config,
+            market: testing_env.market,
+            protocol_fee_converter: Pubkey::find_program_address(
+                &[
+                    PROTOCOL_FEE_CONVERTER_PDA_SEED.as_bytes(),
+                    &config.to_bytes(),
+                ],
+                &token_mill::ID,
+            )
+            .0,
+            signer: make_address("admin"),
+            treasury: make_address("admin"),
+            max_price,
+        }
+    }
+}
+
+impl InstructionGenerator for SetProtocolFeeConverterAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.protocol_fee_converter, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetProtocolFeeConverter {
+            treasury: self.treasury,
+            max_price: self.max_price,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ConvertProtocolFeesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub protocol_fee_converter: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub treasury_base_token_ata: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub revenue_report: Pubkey,
+    pub denylist: Pubkey,
+    pub policy_program: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
+    // Args
+    pub quote_amount: u64,
+    pub min_base_amount: u64,
+}
+
+impl ConvertProtocolFeesAction {
+    pub fn new(testing_env: &TokenMillEnv, quote_amount: u64, min_base_amount: u64) -> Self {
+        let set_action = SetProtocolFeeConverterAction::new(testing_env, 0);
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let base_token_program = testing_env.base_token_type.program_address();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        Self {
+            config: set_action.config,
+            protocol_fee_converter: set_action.protocol_fee_converter,
+            market: set_action.market,
+            base_token_mint,
+            quote_token_mint,
+            market_base_token_ata: get_associated_token_address_with_program_id(
+                &set_action.market,
+                &base_token_mint,
+                &base_token_program,
+            ),
+            market_quote_token_ata: get_associated_token_address_with_program_id(
+                &set_action.market,
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            protocol_quote_token_ata: get_associated_token_address_with_program_id(
+                &make_address("dave"),
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            treasury_base_token_ata: get_associated_token_address_with_program_id(
+                &set_action.treasury,
+                &base_token_mint,
+                &base_token_program,
+            ),
+            base_token_program,
+            quote_token_program,
+            revenue_report: tm_revenue_report(testing_env, &quote_token_mint),
+            denylist: token_mill::ID,
+            policy_program: token_mill::ID,
+            fee_hook_program: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+            quote_amount,
+            min_base_amount,
+        }
+    }
+}
+
+impl InstructionGenerator for ConvertProtocolFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.protocol_fee_converter, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new(self.treasury_base_token_ata, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new(self.revenue_report, false),
+            AccountMeta::new_readonly(self.denylist, false),
+            AccountMeta::new_readonly(self.policy_program, false),
+            AccountMeta::new_readonly(self.fee_hook_program, false),
+            AccountMeta::new(self.market_oracle, false),
+            AccountMeta::new(self.market_stats, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ConvertProtocolFees {
+            quote_amount: self.quote_amount,
+            min_base_amount: self.min_base_amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct SplitSellAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub sell_order_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub user_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub denylist: Pubkey,
+    pub policy_program: Pubkey,
+    pub revenue_report: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
+    // Args
+    pub base_amount: u64,
+    pub limit_price: u64,
+    pub min_quote_amount_out: u64,
+}
+
+impl SplitSellAction {
+    pub fn new(testing_env: &TokenMillEnv, base_amount: u64, limit_price: u64) -> Self {
+        let swap_action = SwapAction::new(
+            testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            base_amount,
+            0,
+            None,
+        );
+
+        let sell_order = Pubkey::find_program_address(
+            &[
+                SELL_ORDER_PDA_SEED.as_bytes(),
+                &swap_action.market.to_bytes(),
+                &swap_action.signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let sell_order_base_token_ata = get_associated_token_address_with_program_id(
+            &sell_order,
+            &swap_action.base_token_mint,
+            &swap_action.base_token_program,
+        );
+
+        Self {
+            config: swap_action.config,
+            market: swap_action.market,
+            sell_order,
+            base_token_mint: swap_action.base_token_mint,
+            quote_token_mint: swap_action.quote_token_mint,
+            market_base_token_ata: swap_action.market_base_token_ata,
+            market_quote_token_ata: swap_action.market_quote_token_ata,
+            sell_order_base_token_ata,
+            user_base_token_ata: swap_action.user_base_token_ata,
+            user_quote_token_ata: swap_action.user_quote_token_ata,
+            protocol_quote_token_ata: swap_action.protocol_quote_token_ata,
+            signer: swap_action.signer,
+            base_token_program: swap_action.base_token_program,
+            quote_token_program: swap_action.quote_token_program,
+            denylist: token_mill::ID,
+            policy_program: token_mill::ID,
+            revenue_report: swap_action.revenue_report,
+            fee_hook_program: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+            base_amount,
+            limit_price,
+            min_quote_amount_out: 0,
+        }
+    }
+}
+
+impl InstructionGenerator for SplitSellAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.sell_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.sell_order_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.user_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_associated_token_program();
+
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new(self.revenue_report, false));
+        accounts.push(AccountMeta::new_readonly(self.fee_hook_program, false));
+        accounts.push(AccountMeta::new(self.market_oracle, false));
+        accounts.push(AccountMeta::new(self.market_stats, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SplitSell {
+            base_amount: self.base_amount,
+            limit_price: self.limit_price,
+            min_quote_amount_out: self.min_quote_amount_out,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ExecuteSellOrderAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub sell_order_base_token_ata: Pubkey,
+    pub owner_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub denylist: Pubkey,
+    pub revenue_report: Pubkey,
+    pub policy_program: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
+    // Args
+    pub base_amount: u64,
+}
+
+impl ExecuteSellOrderAction {
+    pub fn new(testing_env: &TokenMillEnv, base_amount: u64) -> Self {
+        let split_sell_action = SplitSellAction::new(testing_env, 0, 0);
+
+        Self {
+            config: split_sell_action.config,
+            market: split_sell_action.market,
+            sell_order: split_sell_action.sell_order,
+            base_token_mint: split_sell_action.base_token_mint,
+            quote_token_mint: split_sell_action.quote_token_mint,
+            market_base_token_ata: split_sell_action.market_base_token_ata,
+            market_quote_token_ata: split_sell_action.market_quote_token_ata,
+            sell_order_base_token_ata: split_sell_action.sell_order_base_token_ata,
+            owner_quote_token_ata: split_sell_action.user_quote_token_ata,
+            protocol_quote_token_ata: split_sell_action.protocol_quote_token_ata,
+            base_token_program: split_sell_action.base_token_program,
+            quote_token_program: split_sell_action.quote_token_program,
+            denylist: token_mill::ID,
+            revenue_report: split_sell_action.revenue_report,
+            policy_program: token_mill::ID,
+            fee_hook_program: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+            base_amount,
+        }
+    }
+}
+
+impl InstructionGenerator for ExecuteSellOrderAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.sell_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.sell_order_base_token_ata, false),
+            AccountMeta::new(self.owner_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new_readonly(self.base_token_program, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(self.denylist, false),
+            AccountMeta::new(self.revenue_report, false),
+            AccountMeta::new_readonly(self.policy_program, false),
+            AccountMeta::new_readonly(self.fee_hook_program, false),
+            AccountMeta::new(self.market_oracle, false),
+            AccountMeta::new(self.market_stats, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ExecuteSellOrder {
+            base_amount: self.base_amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CancelSellOrderAction {
+    // Accounts
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub sell_order_base_token_ata: Pubkey,
+    pub owner_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub base_token_program: Pubkey,
+}
+
+impl CancelSellOrderAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let split_sell_action = SplitSellAction::new(testing_env, 0, 0);
+
+        Self {
+            market: split_sell_action.market,
+            sell_order: split_sell_action.sell_order,
+            base_token_mint: split_sell_action.base_token_mint,
+            sell_order_base_token_ata: split_sell_action.sell_order_base_token_ata,
+            owner_base_token_ata: split_sell_action.user_base_token_ata,
+            signer: split_sell_action.signer,
+            base_token_program: split_sell_action.base_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for CancelSellOrderAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.sell_order, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.sell_order_base_token_ata, false),
+            AccountMeta::new(self.owner_base_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CancelSellOrder {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct DepositGasVaultAction {
+    // Accounts
+    pub market: Pubkey,
+    pub gas_vault: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl DepositGasVaultAction {
+    pub fn new(token_mill_env: &TokenMillEnv, amount: u64) -> Self {
+        let market = token_mill_env.market;
+
+        let gas_vault = Pubkey::find_program_address(
+            &[GAS_VAULT_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            gas_vault,
+            signer: make_address("alice"),
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for DepositGasVaultAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.gas_vault, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::DepositGasVault {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct WithdrawGasVaultAction {
+    // Accounts
+    pub market: Pubkey,
+    pub gas_vault: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl WithdrawGasVaultAction {
+    pub fn new(token_mill_env: &TokenMillEnv, amount: u64) -> Self {
+        let DepositGasVaultAction {
+            market,
+            gas_vault,
+            signer,
+            ..
+        } = DepositGasVaultAction::new(token_mill_env, amount);
+
+        Self {
+            market,
+            gas_vault,
+            signer,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for WithdrawGasVaultAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.gas_vault, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::WithdrawGasVault {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateSponsoredAccountAction {
+    // Accounts
+    pub market: Pubkey,
+    pub gas_vault: Pubkey,
+    pub stake_position: Pubkey,
+    pub token_mint: Pubkey,
+    pub user_token_ata: Pubkey,
+    pub sponsored_account: Pubkey,
+    pub signer: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl CreateSponsoredAccountAction {
+    pub fn new(token_mill_env: &TokenMillEnv, token_mint: Pubkey, token_type: TokenType) -> Self {
+        let DepositGasVaultAction {
+            market, gas_vault, ..
+        } = DepositGasVaultAction::new(token_mill_env, 0);
+
+        Self {
+            market,
+            gas_vault,
+            stake_position: Pubkey::default(),
+            token_mint,
+            user_token_ata: Pubkey::default(),
+            sponsored_account: Pubkey::default(),
+            signer: Pubkey::default(),
+            token_program: token_type.program_address(),
+        }
+        .with_signer(make_address("bob"))
+    }
+
+    pub fn with_signer(mut self, signer: Pubkey) -> Self {
+        self.stake_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+        self.user_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &self.token_mint,
+            &self.token_program,
+        );
+        self.sponsored_account = Pubkey::find_program_address(
+            &[
+                SPONSORED_ACCOUNT_PDA_SEED.as_bytes(),
+                &self.gas_vault.to_bytes(),
+                &self.user_token_ata.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+        self.signer = signer;
+
+        self
+    }
+}
+
+impl InstructionGenerator for CreateSponsoredAccountAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.gas_vault, false),
+            AccountMeta::new_readonly(self.stake_position, false),
+            AccountMeta::new_readonly(self.token_mint, false),
+            AccountMeta::new(self.user_token_ata, false),
+            AccountMeta::new(self.sponsored_account, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        match self.token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts
+            .append_associated_token_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateSponsoredAccount {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateVestingPlanAction {
+    // Accounts
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub staking_position: Pubkey,
+    pub vesting_plan: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub vesting_amount: u64,
+    pub start: i64,
+    pub vesting_duration: i64,
+    pub cliff_duration: i64,
+}
+
+impl CreateVestingPlanAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        vesting_amount: u64,
+        start: i64,
+        vesting_duration: i64,
+        cliff_duration: i64,
+    ) -> Self {
+        let base_token_mint = make_address("base_token_mint");
+        let base_token_program = token_mill_env.base_token_type.program_address();
+
+        let vesting_plan = make_address("vesting_plan");
+        let signer = make_address("bob");
+
+        let market = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let staking = Pubkey::find_program_address(
+            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let staking_position = Pubkey::find_program_address(
+            &[
+                STAKING_POSITION_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &market,
             &base_token_mint,
-            &spl_token::id(),
+            &base_token_program,
+        );
+
+        let user_base_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        Self {
+            market,
+            staking,
+            staking_position,
+            vesting_plan,
+            base_token_mint,
+            base_token_program,
+            market_base_token_ata,
+            user_base_token_ata,
+            signer,
+            vesting_amount,
+            start,
+            vesting_duration,
+            cliff_duration,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateVestingPlanAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.staking_position, false),
+            AccountMeta::new(self.vesting_plan, true),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateVestingPlan {
+            vesting_amount: self.vesting_amount,
+            start: self.start,
+            vesting_duration: self.vesting_duration,
+            cliff_duration: self.cliff_duration,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateVestingPlanFromTemplateAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub staking_position: Pubkey,
+    pub vesting_plan: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub vesting_amount: u64,
+    pub start: i64,
+    pub template_index: u8,
+}
+
+impl CreateVestingPlanFromTemplateAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        vesting_amount: u64,
+        start: i64,
+        template_index: u8,
+    ) -> Self {
+        let CreateVestingPlanAction {
+            market,
+            staking,
+            staking_position,
+            vesting_plan,
+            base_token_mint,
+            base_token_program,
+            market_base_token_ata,
+            user_base_token_ata,
+            signer,
+            ..
+        } = CreateVestingPlanAction::new(token_mill_env, vesting_amount, start, 0, 0);
+
+        Self {
+            config: make_address("config"),
+            market,
+            staking,
+            staking_position,
+            vesting_plan,
+            base_token_mint,
+            base_token_program,
+            market_base_token_ata,
+            user_base_token_ata,
+            signer,
+            vesting_amount,
+            start,
+            template_index,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateVestingPlanFromTemplateAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.staking_position, false),
+            AccountMeta::new(self.vesting_plan, true),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateVestingPlanFromTemplate {
+            start: self.start,
+            vesting_amount: self.vesting_amount,
+            template_index: self.template_index,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ReleaseAction {
+    // Accounts
+    pub market: Pubkey,
+    pub staking: Pubkey,
+    pub staking_position: Pubkey,
+    pub vesting_plan: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub base_token_program: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub user_base_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub claim_delegate: Pubkey,
+    pub delegate: Pubkey,
+}
+
+impl ReleaseAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let create_vesting_plan_action = CreateVestingPlanAction::new(token_mill_env, 0, 0, 0, 0);
+
+        Self {
+            market: create_vesting_plan_action.market,
+            staking: create_vesting_plan_action.staking,
+            staking_position: create_vesting_plan_action.staking_position,
+            vesting_plan: create_vesting_plan_action.vesting_plan,
+            base_token_mint: create_vesting_plan_action.base_token_mint,
+            base_token_program: create_vesting_plan_action.base_token_program,
+            market_base_token_ata: create_vesting_plan_action.market_base_token_ata,
+            user_base_token_ata: create_vesting_plan_action.user_base_token_ata,
+            signer: create_vesting_plan_action.signer,
+            claim_delegate: token_mill::ID,
+            delegate: token_mill::ID,
+        }
+    }
+
+    /// Triggers the release from the delegate of the stake position, the owner doesn't sign.
+    pub fn with_delegate(mut self, delegate: Pubkey) -> Self {
+        self.claim_delegate = get_claim_delegate_address(&self.staking_position);
+        self.delegate = delegate;
+
+        self
+    }
+}
+
+impl InstructionGenerator for ReleaseAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.staking_position, false),
+            AccountMeta::new(self.vesting_plan, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new_readonly(self.signer, self.delegate == token_mill::ID),
+        ];
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.claim_delegate, false));
+        accounts.push(AccountMeta::new_readonly(
+            self.delegate,
+            self.delegate != token_mill::ID,
+        ));
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::Release {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateQuoteAirdropAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_airdrop: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub merkle_root: [u8; 32],
+    pub amount: u64,
+}
+
+impl CreateQuoteAirdropAction {
+    pub fn new(token_mill_env: &TokenMillEnv, merkle_root: [u8; 32], amount: u64) -> Self {
+        let market = token_mill_env.market;
+
+        let quote_airdrop = Pubkey::find_program_address(
+            &[
+                QUOTE_AIRDROP_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &merkle_root,
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            quote_airdrop,
+            signer: make_address("alice"),
+            merkle_root,
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateQuoteAirdropAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.quote_airdrop, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateQuoteAirdrop {
+            merkle_root: self.merkle_root,
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct PublishHolderRewardsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_airdrop: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub merkle_root: [u8; 32],
+}
+
+impl PublishHolderRewardsAction {
+    pub fn new(token_mill_env: &TokenMillEnv, merkle_root: [u8; 32]) -> Self {
+        let market = token_mill_env.market;
+
+        let quote_airdrop = Pubkey::find_program_address(
+            &[
+                QUOTE_AIRDROP_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &merkle_root,
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            quote_airdrop,
+            signer: make_address("alice"),
+            merkle_root,
+        }
+    }
+}
+
+impl InstructionGenerator for PublishHolderRewardsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.quote_airdrop, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PublishHolderRewards {
+            merkle_root: self.merkle_root,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct ClaimQuoteAirdropAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_airdrop: Pubkey,
+    pub airdrop_claim: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub claimant_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+    // Args
+    pub amount: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+impl ClaimQuoteAirdropAction {
+    pub fn new(
+        token_mill_env: &TokenMillEnv,
+        quote_airdrop: Pubkey,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Self {
+        let market = token_mill_env.market;
+        let signer = token_mill_env.svm.payer;
+
+        let airdrop_claim = Pubkey::find_program_address(
+            &[
+                AIRDROP_CLAIM_PDA_SEED.as_bytes(),
+                &quote_airdrop.to_bytes(),
+                &signer.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let claimant_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            market,
+            quote_airdrop,
+            airdrop_claim,
+            quote_token_mint,
+            market_quote_token_ata,
+            claimant_quote_token_ata,
+            signer,
+            quote_token_program,
+            config: make_address("config"),
+            denylist: token_mill::ID,
+            amount,
+            proof,
+        }
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+}
+
+impl InstructionGenerator for ClaimQuoteAirdropAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.quote_airdrop, false),
+            AccountMeta::new(self.airdrop_claim, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.claimant_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.append_system_program();
+        accounts.push(AccountMeta::new_readonly(self.config, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ClaimQuoteAirdrop {
+            amount: self.amount,
+            proof: self.proof.clone(),
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CloseQuoteAirdropAction {
+    // Accounts
+    pub market: Pubkey,
+    pub quote_airdrop: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CloseQuoteAirdropAction {
+    pub fn new(token_mill_env: &TokenMillEnv, quote_airdrop: Pubkey) -> Self {
+        Self {
+            market: token_mill_env.market,
+            quote_airdrop,
+            signer: make_address("alice"),
+        }
+    }
+}
+
+impl InstructionGenerator for CloseQuoteAirdropAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.quote_airdrop, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CloseQuoteAirdrop {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreatePriceFeedAction {
+    // Accounts
+    pub market: Pubkey,
+    pub price_feed: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreatePriceFeedAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        let price_feed = Pubkey::find_program_address(
+            &[PRICE_FEED_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            price_feed,
+            signer: make_address("alice"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreatePriceFeedAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.price_feed, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreatePriceFeed {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct PushPriceAction {
+    // Accounts
+    pub market: Pubkey,
+    pub price_feed: Pubkey,
+}
+
+impl PushPriceAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let CreatePriceFeedAction {
+            market, price_feed, ..
+        } = CreatePriceFeedAction::new(token_mill_env);
+
+        Self { market, price_feed }
+    }
+}
+
+impl InstructionGenerator for PushPriceAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.price_feed, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PushPrice {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketOracleAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_oracle: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateMarketOracleAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        let market_oracle = Pubkey::find_program_address(
+            &[MARKET_ORACLE_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            market_oracle,
+            signer: make_address("bob"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateMarketOracleAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.market_oracle, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarketOracle {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct GetTwapAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_oracle: Pubkey,
+    // Args
+    pub window: i64,
+}
+
+impl GetTwapAction {
+    pub fn new(token_mill_env: &TokenMillEnv, window: i64) -> Self {
+        let CreateMarketOracleAction {
+            market,
+            market_oracle,
+            ..
+        } = CreateMarketOracleAction::new(token_mill_env);
+
+        Self {
+            market,
+            market_oracle,
+            window,
+        }
+    }
+}
+
+impl InstructionGenerator for GetTwapAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.market_oracle, false),
+        ]
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::GetTwap {
+            window: self.window,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketStatsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_stats: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateMarketStatsAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        Self {
+            market,
+            market_stats: tm_market_stats(&market),
+            signer: make_address("bob"),
+        }
+    }
+}
+
+impl InstructionGenerator for CreateMarketStatsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.market_stats, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarketStats {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct RollMarketStatsAction {
+    // Accounts
+    pub market: Pubkey,
+    pub market_stats: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl RollMarketStatsAction {
+    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
+        let market = token_mill_env.market;
+
+        Self {
+            market,
+            market_stats: tm_market_stats(&market),
+            signer: make_address("carol"),
+        }
+    }
+}
+
+impl InstructionGenerator for RollMarketStatsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.market_stats, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::RollMarketStats {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateCreatorMultisigAction {
+    // Accounts
+    pub creator_multisig: Pubkey,
+    pub vault: Pubkey,
+    pub create_key: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+impl CreateCreatorMultisigAction {
+    pub fn new(members: Vec<Pubkey>, threshold: u8) -> Self {
+        let create_key = make_address("multisig");
+
+        let creator_multisig = Pubkey::find_program_address(
+            &[CREATOR_MULTISIG_PDA_SEED.as_bytes(), &create_key.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        let vault = Pubkey::find_program_address(
+            &[
+                CREATOR_VAULT_PDA_SEED.as_bytes(),
+                &creator_multisig.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            creator_multisig,
+            vault,
+            create_key,
+            signer: make_address("alice"),
+            members,
+            threshold,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateCreatorMultisigAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.creator_multisig, false),
+            AccountMeta::new_readonly(self.vault, false),
+            AccountMeta::new_readonly(self.create_key, true),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateCreatorMultisig {
+            members: self.members.clone(),
+            threshold: self.threshold,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+/// Wraps an instruction signed by the vault of a creator multisig, e.g. a creator action built
+/// with the vault as its signer.
+pub struct ExecuteCreatorInstructionAction {
+    // Accounts
+    pub creator_multisig: Pubkey,
+    pub vault: Pubkey,
+    pub instruction: Instruction,
+    pub signers: Vec<Pubkey>,
+}
+
+impl ExecuteCreatorInstructionAction {
+    pub fn new(
+        creator_multisig: Pubkey,
+        vault: Pubkey,
+        action: &dyn InstructionGenerator,
+        signers: Vec<Pubkey>,
+    ) -> Self {
+        Self {
+            creator_multisig,
+            vault,
+            instruction: action.instruction(),
+            signers,
+        }
+    }
+}
+
+impl InstructionGenerator for ExecuteCreatorInstructionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.creator_multisig, false),
+            AccountMeta::new(self.vault, false),
+            AccountMeta::new_readonly(self.instruction.program_id, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        // The multisig signs for the vault
+        accounts.extend(self.instruction.accounts.iter().map(|account| AccountMeta {
+            is_signer: account.is_signer && account.pubkey != self.vault,
+            ..account.clone()
+        }));
+
+        accounts.extend(
+            self.signers
+                .iter()
+                .map(|signer| AccountMeta::new_readonly(*signer, true)),
+        );
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ExecuteCreatorInstruction {
+            account_count: self.instruction.accounts.len() as u8,
+            data: self.instruction.data.clone(),
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateMarketReferralCodeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub referral_code: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub code: [u8; 32],
+    pub creator_fee_share: u16,
+}
+
+impl CreateMarketReferralCodeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, code: [u8; 32], creator_fee_share: u16) -> Self {
+        let CreateMarketReferralAccrualAction {
+            market,
+            referral_token_account,
+            ..
+        } = CreateMarketReferralAccrualAction::new(token_mill_env);
+
+        let referral_code = Pubkey::find_program_address(
+            &[
+                MARKET_REFERRAL_CODE_PDA_SEED.as_bytes(),
+                &market.to_bytes(),
+                &code,
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            referral_token_account,
+            referral_code,
+            signer: make_address("alice"),
+            code,
+            creator_fee_share,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateMarketReferralCodeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new_readonly(self.referral_token_account, false),
+            AccountMeta::new(self.referral_code, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateMarketReferralCode {
+            code: self.code,
+            creator_fee_share: self.creator_fee_share,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CloseMarketReferralCodeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub referral_code: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CloseMarketReferralCodeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, referral_code: Pubkey) -> Self {
+        Self {
+            market: token_mill_env.market,
+            referral_code,
+            signer: make_address("alice"),
+        }
+    }
+}
+
+impl InstructionGenerator for CloseMarketReferralCodeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.referral_code, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CloseMarketReferralCode {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct CreateSwapReceiptTreeAction {
+    // Accounts
+    pub market: Pubkey,
+    pub swap_receipt_tree: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub signer: Pubkey,
+    pub compression_program: Pubkey,
+    pub noop_program: Pubkey,
+    // Args
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+impl CreateSwapReceiptTreeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, merkle_tree: Pubkey) -> Self {
+        let market = token_mill_env.market;
+
+        let swap_receipt_tree = Pubkey::find_program_address(
+            &[SWAP_RECEIPT_TREE_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            market,
+            swap_receipt_tree,
+            merkle_tree,
+            signer: make_address("alice"),
+            compression_program: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop_program: SPL_NOOP_PROGRAM_ID,
+            max_depth: 14,
+            max_buffer_size: 64,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateSwapReceiptTreeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.swap_receipt_tree, false),
+            AccountMeta::new(self.merkle_tree, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new_readonly(self.compression_program, false));
+        accounts.push(AccountMeta::new_readonly(self.noop_program, false));
+
+        accounts
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateSwapReceiptTree {
+            max_depth: self.max_depth,
+            max_buffer_size: self.max_buffer_size,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateReferralParamsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub referral_fee_share: u16,
+    pub referral_enabled: bool,
+}
+
+impl UpdateReferralParamsAction {
+    pub fn new(referral_fee_share: u16, referral_enabled: bool) -> Self {
+        Self {
+            config: make_address("config"),
+            market: token_mill::ID,
+            signer: make_address("admin"),
+            referral_fee_share,
+            referral_enabled,
+        }
+    }
+
+    pub fn with_market(mut self, market: Pubkey) -> Self {
+        self.market = market;
+        self
+    }
+}
+
+impl InstructionGenerator for UpdateReferralParamsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateReferralParams {
+            referral_fee_share: self.referral_fee_share,
+            referral_enabled: self.referral_enabled,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateCreationBondAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub creation_bond: CreationBond,
+}
+
+impl UpdateCreationBondAction {
+    pub fn new(creation_bond: CreationBond) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            creation_bond,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateCreationBondAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateCreationBond {
+            creation_bond: self.creation_bond,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ReleaseCreationBondAction {
+    // Accounts
+    pub market: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl ReleaseCreationBondAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        Self {
+            market: testing_env.market,
+            signer: make_address("alice"),
+        }
+    }
+}
+
+impl InstructionGenerator for ReleaseCreationBondAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ReleaseCreationBond {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SlashCreationBondAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub protocol_fee_recipient: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl SlashCreationBondAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            protocol_fee_recipient: make_address("dave"),
+            signer: make_address("admin"),
+        }
+    }
+}
+
+impl InstructionGenerator for SlashCreationBondAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.protocol_fee_recipient, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SlashCreationBond {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateCreatorDrawAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub creator_draw: CreatorDraw,
+}
+
+impl UpdateCreatorDrawAction {
+    pub fn new(creator_draw: CreatorDraw) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            creator_draw,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateCreatorDrawAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateCreatorDraw {
+            creator_draw: self.creator_draw,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct RequestQuoteDrawAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+    // Args
+    pub amount: u64,
+}
+
+impl RequestQuoteDrawAction {
+    pub fn new(testing_env: &TokenMillEnv, amount: u64) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: make_address("alice"),
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for RequestQuoteDrawAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::RequestQuoteDraw {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecuteQuoteDrawAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub creator_quote_token_ata: Pubkey,
+    pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
+}
+
+impl ExecuteQuoteDrawAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let market = testing_env.market;
+        let signer = make_address("alice");
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &market,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        let creator_quote_token_ata = get_associated_token_address_with_program_id(
+            &signer,
+            &quote_token_mint,
+            &quote_token_program,
+        );
+
+        Self {
+            config: make_address("config"),
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            creator_quote_token_ata,
+            signer,
+            quote_token_program,
+        }
+    }
+}
+
+impl InstructionGenerator for ExecuteQuoteDrawAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_ata, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        match self.quote_token_program {
+            spl_token::ID => accounts.append_token_program(),
+            spl_token_2022::ID => accounts.append_token_2022_program(),
+            _ => unreachable!(),
+        };
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ExecuteQuoteDraw {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct QueueConfigChangeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub signer: Pubkey,
+
+    // Args
+    pub change: ConfigChange,
+}
+
+impl QueueConfigChangeAction {
+    pub fn new(change: ConfigChange) -> Self {
+        Self {
+            config: make_address("config"),
+            pending_config_change: tm_pending_config_change(change.kind()),
+            signer: make_address("admin"),
+            change,
+        }
+    }
+}
+
+impl InstructionGenerator for QueueConfigChangeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.pending_config_change, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::QueueConfigChange {
+            change: self.change,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecuteConfigChangeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub signer: Pubkey,
+    pub protocol_fee_split: Pubkey,
+    pub denylist: Pubkey,
+}
+
+impl ExecuteConfigChangeAction {
+    pub fn new(kind: u8) -> Self {
+        Self {
+            config: make_address("config"),
+            pending_config_change: tm_pending_config_change(kind),
+            signer: make_address("admin"),
+            protocol_fee_split: token_mill::ID,
+            denylist: token_mill::ID,
+        }
+    }
+
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
+
+        self
+    }
+
+    pub fn with_protocol_fee_split(mut self) -> Self {
+        self.protocol_fee_split = Pubkey::find_program_address(
+            &[
+                PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(),
+                &self.config.to_bytes(),
+            ],
+            &token_mill::ID,
+        )
+        .0;
+
+        self
+    }
+}
+
+impl InstructionGenerator for ExecuteConfigChangeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.config, false),
+            AccountMeta::new(self.pending_config_change, false),
+        ];
+
+        accounts.append_payer(self.signer);
+
+        accounts.push(AccountMeta::new(self.protocol_fee_split, false));
+        accounts.push(AccountMeta::new(self.denylist, false));
+
+        accounts
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::ExecuteConfigChange {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CancelConfigChangeAction {
+    // Accounts
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CancelConfigChangeAction {
+    pub fn new(kind: u8) -> Self {
+        Self {
+            config: make_address("config"),
+            pending_config_change: tm_pending_config_change(kind),
+            signer: make_address("admin"),
+        }
+    }
+}
+
+impl InstructionGenerator for CancelConfigChangeAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.pending_config_change, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CancelConfigChange {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CreateRiskParamsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub risk_params: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl CreateRiskParamsAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let market = testing_env.market;
+
+        let risk_params = Pubkey::find_program_address(
+            &[RISK_PARAMS_PDA_SEED.as_bytes(), &market.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config: make_address("config"),
+            market,
+            risk_params,
+            signer: testing_env.svm.payer,
+        }
+    }
+}
+
+impl InstructionGenerator for CreateRiskParamsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.risk_params, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::CreateRiskParams {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PushRiskParamsAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub risk_params: Pubkey,
+}
+
+impl PushRiskParamsAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let CreateRiskParamsAction {
+            config,
+            market,
+            risk_params,
+            ..
+        } = CreateRiskParamsAction::new(testing_env);
+
+        Self {
+            config,
+            market,
+            risk_params,
+        }
+    }
+}
+
+impl InstructionGenerator for PushRiskParamsAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.risk_params, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PushRiskParams {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetProtocolFeeSplitAction {
+    // Accounts
+    pub config: Pubkey,
+    pub protocol_fee_split: Pubkey,
+    pub signer: Pubkey,
+
+    // Args
+    pub recipients: Vec<ProtocolFeeShare>,
+}
+
+impl SetProtocolFeeSplitAction {
+    pub fn new(recipients: Vec<ProtocolFeeShare>) -> Self {
+        let config = make_address("config");
+
+        let protocol_fee_split = Pubkey::find_program_address(
+            &[PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(), &config.to_bytes()],
+            &token_mill::ID,
+        )
+        .0;
+
+        Self {
+            config,
+            protocol_fee_split,
+            signer: make_address("admin"),
+            recipients,
+        }
+    }
+}
+
+impl InstructionGenerator for SetProtocolFeeSplitAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.protocol_fee_split, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::SetProtocolFeeSplit {
+            recipients: self.recipients.clone(),
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DistributeProtocolFeesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub protocol_fee_split: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub recipient_token_accounts: Vec<Pubkey>,
+
+    // Args
+    pub amount: u64,
+}
+
+impl DistributeProtocolFeesAction {
+    pub fn new(testing_env: &TokenMillEnv, recipients: &[Pubkey], amount: u64) -> Self {
+        let set_action = SetProtocolFeeSplitAction::new(Vec::new());
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_token_program = testing_env.quote_token_type.program_address();
+
+        Self {
+            config: set_action.config,
+            protocol_fee_split: set_action.protocol_fee_split,
+            quote_token_mint,
+            protocol_quote_token_ata: get_associated_token_address_with_program_id(
+                &make_address("dave"),
+                &quote_token_mint,
+                &quote_token_program,
+            ),
+            quote_token_program,
+            recipient_token_accounts: recipients
+                .iter()
+                .map(|recipient| {
+                    get_associated_token_address_with_program_id(
+                        recipient,
+                        &quote_token_mint,
+                        &quote_token_program,
+                    )
+                })
+                .collect(),
+            amount,
+        }
+    }
+}
+
+impl InstructionGenerator for DistributeProtocolFeesAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.protocol_fee_split, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+        ];
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts.extend(
+            self.recipient_token_accounts
+                .iter()
+                .map(|recipient_token_account| AccountMeta::new(*recipient_token_account, false)),
         );
 
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::DistributeProtocolFees {
+            amount: self.amount,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UpdateSweepTipAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+
+    // Args
+    pub new_sweep_tip_bps: u16,
+}
+
+impl UpdateSweepTipAction {
+    pub fn new(new_sweep_tip_bps: u16) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_sweep_tip_bps,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateSweepTipAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
+
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::UpdateSweepTip {
+            new_sweep_tip_bps: self.new_sweep_tip_bps,
+        };
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SweepFeesAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub creator_quote_token_ata: Pubkey,
+    pub staking: Pubkey,
+    pub keeper_quote_token_account: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub denylist: Pubkey,
+    // Sends `sweep_fees_idempotent` instead
+    pub idempotent: bool,
+}
+
+impl SweepFeesAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let ClaimCreatorFeesAction {
+            market,
+            quote_token_mint,
+            market_quote_token_ata,
+            creator_quote_token_ata,
+            signer,
+            quote_token_program,
+            config,
+            denylist,
+        } = ClaimCreatorFeesAction::new(testing_env);
+
         Self {
             config,
             market,
-            base_token_mint,
-            base_token_metadata,
-            market_base_token_ata,
+            creator: signer,
             quote_token_mint,
-            quote_token_badge: quote_asset_badge,
-            signer: make_address("alice"),
-            total_supply: DEFAULT_TOTAL_SUPPLY,
+            market_quote_token_ata,
+            creator_quote_token_ata,
+            staking: CreateStakingAction::new(testing_env).staking,
+            keeper_quote_token_account: token_mill::ID,
+            quote_token_program,
+            denylist,
+            idempotent: false,
         }
     }
 
-    pub fn no_badge(&mut self) -> &mut Self {
-        self.quote_token_badge = token_mill::ID;
+    pub fn idempotent(mut self) -> Self {
+        self.idempotent = true;
+
+        self
+    }
+
+    pub fn with_keeper(mut self, keeper: Pubkey) -> Self {
+        self.keeper_quote_token_account = get_associated_token_address_with_program_id(
+            &keeper,
+            &self.quote_token_mint,
+            &self.quote_token_program,
+        );
 
         self
     }
 }
 
-impl InstructionGenerator for CreateMarketWithSplAction {
+impl InstructionGenerator for SweepFeesAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
             AccountMeta::new_readonly(self.config, false),
             AccountMeta::new(self.market, false),
-            AccountMeta::new(self.base_token_mint, true),
-            AccountMeta::new(self.base_token_metadata, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new_readonly(self.quote_token_badge, false),
+            AccountMeta::new_readonly(self.creator, false),
             AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.creator_quote_token_ata, false),
+            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.keeper_quote_token_account, false),
+            AccountMeta::new_readonly(self.quote_token_program, false),
+            AccountMeta::new_readonly(self.denylist, false),
         ];
 
-        accounts
-            .append_payer(self.signer)
-            .append_system_program()
-            .append_token_program()
-            .append_metadata_program()
-            .append_associated_token_program()
-            .append_cpi_event_accounts(tm_event_authority());
+        accounts.append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateMarketWithSpl {
-            name: "name".to_string(),
-            symbol: "symbol".to_string(),
-            uri: "uri".to_string(),
-            total_supply: self.total_supply,
-            creator_fee_share: DEFAULT_CREATOR_FEE_SHARE,
-            staking_fee_share: DEFAULT_STAKING_FEE_SHARE,
+        let data = if self.idempotent {
+            token_mill::instruction::SweepFeesIdempotent {}.data()
+        } else {
+            token_mill::instruction::SweepFees {}.data()
         };
 
         Instruction {
             program_id: token_mill::ID,
             accounts: self.accounts(),
-            data: input.data(),
+            data,
         }
     }
 }
 
-pub struct SetMarketPricesAction {
+#[derive(Debug)]
+pub struct BackfillMarketAction {
     // Accounts
+    pub config: Pubkey,
     pub market: Pubkey,
     pub signer: Pubkey,
-    // Args
-    pub price_curve: Curve,
 }
 
-impl SetMarketPricesAction {
-    pub fn new(curve: Curve) -> Self {
-        let market = Pubkey::find_program_address(
-            &[
-                MARKET_PDA_SEED.as_bytes(),
-                &make_address("base_token_mint").to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
+impl BackfillMarketAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        Self {
+            config: testing_env.config,
+            market: testing_env.market,
+            signer: testing_env.svm.payer,
+        }
+    }
+}
+
+impl InstructionGenerator for BackfillMarketAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
+
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::BackfillMarket {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+pub struct BackfillConfigAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+}
 
+impl BackfillConfigAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
         Self {
-            market,
-            signer: make_address("alice"),
-            price_curve: curve,
+            config: testing_env.config,
+            signer: testing_env.svm.payer,
         }
     }
+}
 
-    pub fn with_custom_base_token_mint(mut self, base_token_mint: Pubkey) -> Self {
-        self.market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+impl InstructionGenerator for BackfillConfigAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
-        self
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::BackfillConfig {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
     }
 }
 
-impl InstructionGenerator for SetMarketPricesAction {
+#[derive(Debug)]
+pub struct UpdateCreatorPauseAction {
+    // Accounts
+    pub config: Pubkey,
+    pub signer: Pubkey,
+
+    // Args
+    pub creator_pause_enabled: bool,
+}
+
+impl UpdateCreatorPauseAction {
+    pub fn new(creator_pause_enabled: bool) -> Self {
+        Self {
+            config: make_address("config"),
+            signer: make_address("admin"),
+            creator_pause_enabled,
+        }
+    }
+}
+
+impl InstructionGenerator for UpdateCreatorPauseAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![AccountMeta::new(self.market, false)];
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
         accounts
             .append_payer(self.signer)
@@ -842,14 +9653,8 @@ impl InstructionGenerator for SetMarketPricesAction {
     }
 
     fn instruction(&self) -> Instruction {
-        let Curve {
-            bid_prices,
-            ask_prices,
-        } = self.price_curve;
-
-        let input = token_mill::instruction::SetMarketPrices {
-            bid_prices,
-            ask_prices,
+        let input = token_mill::instruction::UpdateCreatorPause {
+            creator_pause_enabled: self.creator_pause_enabled,
         };
 
         Instruction {
@@ -860,160 +9665,83 @@ impl InstructionGenerator for SetMarketPricesAction {
     }
 }
 
-pub struct SwapAction {
+#[derive(Debug)]
+pub struct PauseMarketAction {
     // Accounts
     pub config: Pubkey,
     pub market: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub quote_token_mint: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub market_quote_token_ata: Pubkey,
-    pub user_base_token_ata: Pubkey,
-    pub user_quote_token_ata: Pubkey,
-    pub protocol_quote_token_ata: Pubkey,
-    pub referral_quote_token_ata: Pubkey,
     pub signer: Pubkey,
-    pub base_token_program: Pubkey,
-    pub quote_token_program: Pubkey,
-    // Args
-    pub swap_type: SwapType,
-    pub swap_amount_type: SwapAmountType,
-    pub amount: u64,
-    pub other_amount_threshold: u64,
 }
 
-impl SwapAction {
-    pub fn new(
-        token_mill_env: &TokenMillEnv,
-        swap_type: SwapType,
-        swap_amount_type: SwapAmountType,
-        amount: u64,
-        other_amount_threshold: u64,
-        referrer: Option<Pubkey>,
-    ) -> Self {
-        let config = make_address("config");
-        let base_token_mint = token_mill_env.base_token_mint.unwrap();
-        let base_token_program = token_mill_env.base_token_type.program_address();
-
-        let signer = make_address("bob");
-
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
-
-        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
-        let quote_token_program = token_mill_env.quote_token_type.program_address();
-
-        let market_base_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &base_token_mint,
-            &token_mill_env.base_token_type.program_address(),
-        );
+impl PauseMarketAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        Self {
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: testing_env.svm.payer,
+        }
+    }
+}
 
-        let market_quote_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &quote_token_mint,
-            &quote_token_program,
-        );
+impl InstructionGenerator for PauseMarketAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new(self.market, false),
+        ];
 
-        let user_base_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &base_token_mint,
-            &token_mill_env.base_token_type.program_address(),
-        );
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
 
-        let user_quote_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &quote_token_mint,
-            &quote_token_program,
-        );
+        accounts
+    }
 
-        let protocol_quote_token_ata = get_associated_token_address_with_program_id(
-            &make_address("dave"),
-            &quote_token_mint,
-            &quote_token_program,
-        );
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::PauseMarket {};
 
-        let referral_account = if let Some(referrer) = referrer {
-            Pubkey::find_program_address(
-                &[
-                    REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
-                    &config.to_bytes(),
-                    &referrer.to_bytes(),
-                ],
-                &token_mill::ID,
-            )
-            .0
-        } else {
-            Pubkey::new_unique()
-        };
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
 
-        let referral_quote_token_ata = if referrer.is_some() {
-            get_associated_token_address_with_program_id(
-                &referral_account,
-                &quote_token_mint,
-                &quote_token_program,
-            )
-        } else {
-            token_mill::ID
-        };
+#[derive(Debug)]
+pub struct UnpauseMarketAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub signer: Pubkey,
+}
 
+impl UnpauseMarketAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
         Self {
-            config,
-            market,
-            base_token_mint,
-            quote_token_mint,
-            market_base_token_ata,
-            market_quote_token_ata,
-            user_base_token_ata,
-            user_quote_token_ata,
-            protocol_quote_token_ata,
-            referral_quote_token_ata,
-            signer,
-            base_token_program,
-            quote_token_program,
-            swap_type,
-            swap_amount_type,
-            amount,
-            other_amount_threshold,
+            config: make_address("config"),
+            market: testing_env.market,
+            signer: testing_env.svm.payer,
         }
     }
 }
 
-impl InstructionGenerator for SwapAction {
+impl InstructionGenerator for UnpauseMarketAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
             AccountMeta::new_readonly(self.config, false),
             AccountMeta::new(self.market, false),
-            AccountMeta::new_readonly(self.base_token_mint, false),
-            AccountMeta::new_readonly(self.quote_token_mint, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new(self.market_quote_token_ata, false),
-            AccountMeta::new(self.user_base_token_ata, false),
-            AccountMeta::new(self.user_quote_token_ata, false),
-            AccountMeta::new(self.protocol_quote_token_ata, false),
-            AccountMeta::new(self.referral_quote_token_ata, false),
         ];
 
-        accounts.append_payer(self.signer);
-
-        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
-        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
-
-        accounts.append_cpi_event_accounts(tm_event_authority());
+        accounts
+            .append_payer(self.signer)
+            .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::Swap {
-            swap_type: self.swap_type,
-            swap_amount_type: self.swap_amount_type,
-            amount: self.amount,
-            other_amount_threshold: self.other_amount_threshold,
-        };
+        let input = token_mill::instruction::UnpauseMarket {};
 
         Instruction {
             program_id: token_mill::ID,
@@ -1023,36 +9751,31 @@ impl InstructionGenerator for SwapAction {
     }
 }
 
-pub struct UpdateCreatorAction {
+#[derive(Debug)]
+pub struct UpdateSwapFeeBoundsAction {
     // Accounts
-    pub market: Pubkey,
+    pub config: Pubkey,
     pub signer: Pubkey,
+
     // Args
-    pub new_creator: Pubkey,
+    pub new_min_swap_fee_bps: u16,
+    pub new_max_swap_fee_bps: u16,
 }
 
-impl UpdateCreatorAction {
-    pub fn new(new_creator: Pubkey) -> Self {
-        let market = Pubkey::find_program_address(
-            &[
-                MARKET_PDA_SEED.as_bytes(),
-                &make_address("base_token_mint").to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
+impl UpdateSwapFeeBoundsAction {
+    pub fn new(new_min_swap_fee_bps: u16, new_max_swap_fee_bps: u16) -> Self {
         Self {
-            market,
-            signer: make_address("alice"),
-            new_creator,
+            config: make_address("config"),
+            signer: make_address("admin"),
+            new_min_swap_fee_bps,
+            new_max_swap_fee_bps,
         }
     }
 }
 
-impl InstructionGenerator for UpdateCreatorAction {
+impl InstructionGenerator for UpdateSwapFeeBoundsAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![AccountMeta::new(self.market, false)];
+        let mut accounts = vec![AccountMeta::new(self.config, false)];
 
         accounts
             .append_payer(self.signer)
@@ -1062,8 +9785,9 @@ impl InstructionGenerator for UpdateCreatorAction {
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::UpdateCreator {
-            new_creator: self.new_creator,
+        let input = token_mill::instruction::UpdateSwapFeeBounds {
+            new_min_swap_fee_bps: self.new_min_swap_fee_bps,
+            new_max_swap_fee_bps: self.new_max_swap_fee_bps,
         };
 
         Instruction {
@@ -1074,36 +9798,26 @@ impl InstructionGenerator for UpdateCreatorAction {
     }
 }
 
-pub struct UpdateMarketFeeSharesAction {
+#[derive(Debug)]
+pub struct UpdateDynamicFeeAction {
     // Accounts
     pub market: Pubkey,
     pub signer: Pubkey,
     // Args
-    pub new_creator_fee_share: u16,
-    pub new_staking_fee_share: u16,
+    pub dynamic_fee: bool,
 }
 
-impl UpdateMarketFeeSharesAction {
-    pub fn new(new_creator_fee_share: u16, new_staking_fee_share: u16) -> Self {
-        let market = Pubkey::find_program_address(
-            &[
-                MARKET_PDA_SEED.as_bytes(),
-                &make_address("base_token_mint").to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
+impl UpdateDynamicFeeAction {
+    pub fn new(token_mill_env: &TokenMillEnv, dynamic_fee: bool) -> Self {
         Self {
-            market,
+            market: token_mill_env.market,
             signer: make_address("alice"),
-            new_creator_fee_share,
-            new_staking_fee_share,
+            dynamic_fee,
         }
     }
 }
 
-impl InstructionGenerator for UpdateMarketFeeSharesAction {
+impl InstructionGenerator for UpdateDynamicFeeAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![AccountMeta::new(self.market, false)];
 
@@ -1115,9 +9829,8 @@ impl InstructionGenerator for UpdateMarketFeeSharesAction {
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::UpdateMarketFeeShares {
-            new_creator_fee_share: self.new_creator_fee_share,
-            new_staking_fee_share: self.new_staking_fee_share,
+        let input = token_mill::instruction::UpdateDynamicFee {
+            dynamic_fee: self.dynamic_fee,
         };
 
         Instruction {
@@ -1128,79 +9841,59 @@ impl InstructionGenerator for UpdateMarketFeeSharesAction {
     }
 }
 
-pub struct ClaimCreatorFeesAction {
+#[derive(Debug)]
+pub struct CreateFeeExemptionAction {
     // Accounts
+    pub config: Pubkey,
     pub market: Pubkey,
-    pub quote_token_mint: Pubkey,
-    pub market_quote_token_ata: Pubkey,
-    pub creator_quote_token_ata: Pubkey,
+    pub fee_exemption: Pubkey,
     pub signer: Pubkey,
-    pub quote_token_program: Pubkey,
+    // Args
+    pub wallet: Pubkey,
 }
 
-impl ClaimCreatorFeesAction {
-    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
-        let market = Pubkey::find_program_address(
+impl CreateFeeExemptionAction {
+    pub fn new(token_mill_env: &TokenMillEnv, wallet: Pubkey) -> Self {
+        let fee_exemption = Pubkey::find_program_address(
             &[
-                MARKET_PDA_SEED.as_bytes(),
-                &token_mill_env.base_token_mint.unwrap().to_bytes(),
+                FEE_EXEMPTION_PDA_SEED.as_bytes(),
+                &token_mill_env.market.to_bytes(),
+                &wallet.to_bytes(),
             ],
             &token_mill::ID,
         )
         .0;
 
-        let signer = make_address("alice");
-
-        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
-        let quote_token_program = token_mill_env.quote_token_type.program_address();
-
-        let market_quote_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &quote_token_mint,
-            &quote_token_program,
-        );
-
-        let creator_quote_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &quote_token_mint,
-            &quote_token_program,
-        );
-
         Self {
-            market,
-            quote_token_mint,
-            market_quote_token_ata,
-            creator_quote_token_ata,
-            signer,
-            quote_token_program,
+            config: make_address("config"),
+            market: token_mill_env.market,
+            fee_exemption,
+            signer: make_address("admin"),
+            wallet,
         }
     }
 }
 
-impl InstructionGenerator for ClaimCreatorFeesAction {
+impl InstructionGenerator for CreateFeeExemptionAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
-            AccountMeta::new(self.market, false),
-            AccountMeta::new_readonly(self.quote_token_mint, false),
-            AccountMeta::new(self.market_quote_token_ata, false),
-            AccountMeta::new(self.creator_quote_token_ata, false),
+            AccountMeta::new_readonly(self.config, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.fee_exemption, false),
         ];
 
-        accounts.append_payer(self.signer);
-
-        match self.quote_token_program {
-            spl_token::ID => accounts.append_token_program(),
-            spl_token_2022::ID => accounts.append_token_2022_program(),
-            _ => unreachable!(),
-        };
-
-        accounts.append_cpi_event_accounts(tm_event_authority());
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::ClaimCreatorFees {};
+        let input = token_mill::instruction::CreateFeeExemption {
+            wallet: self.wallet,
+        };
 
         Instruction {
             program_id: token_mill::ID,
@@ -1210,66 +9903,45 @@ impl InstructionGenerator for ClaimCreatorFeesAction {
     }
 }
 
-pub struct ClaimProtocolFeesAction {
+#[derive(Debug)]
+pub struct CloseFeeExemptionAction {
     // Accounts
     pub config: Pubkey,
     pub market: Pubkey,
-    pub quote_token_mint: Pubkey,
-    pub market_quote_token_ata: Pubkey,
-    pub authority_quote_token_ata: Pubkey,
-    pub signer: Pubkey,
-    pub quote_token_program: Pubkey,
-}
-
-pub struct CreateStakingAction {
-    // Accounts
-    pub market: Pubkey,
-    pub staking: Pubkey,
+    pub fee_exemption: Pubkey,
     pub signer: Pubkey,
 }
 
-impl CreateStakingAction {
-    pub fn new(testing_env: &TokenMillEnv) -> Self {
-        let market = Pubkey::find_program_address(
-            &[
-                MARKET_PDA_SEED.as_bytes(),
-                &testing_env.base_token_mint.unwrap().to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
-        let staking = Pubkey::find_program_address(
-            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+impl CloseFeeExemptionAction {
+    pub fn new(token_mill_env: &TokenMillEnv, wallet: Pubkey) -> Self {
+        let create_action = CreateFeeExemptionAction::new(token_mill_env, wallet);
 
         Self {
-            market,
-            staking,
-            signer: make_address("admin"),
+            config: create_action.config,
+            market: create_action.market,
+            fee_exemption: create_action.fee_exemption,
+            signer: create_action.signer,
         }
     }
 }
 
-impl InstructionGenerator for CreateStakingAction {
+impl InstructionGenerator for CloseFeeExemptionAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
             AccountMeta::new_readonly(self.market, false),
-            AccountMeta::new(self.staking, false),
+            AccountMeta::new(self.fee_exemption, false),
         ];
 
         accounts
             .append_payer(self.signer)
-            .append_system_program()
             .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateStaking {};
+        let input = token_mill::instruction::CloseFeeExemption {};
 
         Instruction {
             program_id: token_mill::ID,
@@ -1279,61 +9951,40 @@ impl InstructionGenerator for CreateStakingAction {
     }
 }
 
-pub struct CreateStakePositionAction {
+#[derive(Debug)]
+pub struct UpdateMaxWalletHoldingAction {
     // Accounts
     pub market: Pubkey,
-    pub stake_position: Pubkey,
     pub signer: Pubkey,
+    // Args
+    pub new_max_wallet_holding_bps: u16,
 }
 
-impl CreateStakePositionAction {
-    pub fn new(testing_env: &TokenMillEnv) -> Self {
-        let market = Pubkey::find_program_address(
-            &[
-                MARKET_PDA_SEED.as_bytes(),
-                &testing_env.base_token_mint.unwrap().to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
-        let signer: Pubkey = make_address("bob");
-
-        let stake_position = Pubkey::find_program_address(
-            &[
-                STAKING_POSITION_PDA_SEED.as_bytes(),
-                &market.to_bytes(),
-                &signer.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
+impl UpdateMaxWalletHoldingAction {
+    pub fn new(token_mill_env: &TokenMillEnv, new_max_wallet_holding_bps: u16) -> Self {
         Self {
-            market,
-            stake_position,
-            signer,
+            market: token_mill_env.market,
+            signer: make_address("alice"),
+            new_max_wallet_holding_bps,
         }
     }
 }
 
-impl InstructionGenerator for CreateStakePositionAction {
+impl InstructionGenerator for UpdateMaxWalletHoldingAction {
     fn accounts(&self) -> Vec<AccountMeta> {
-        let mut accounts = vec![
-            AccountMeta::new_readonly(self.market, false),
-            AccountMeta::new(self.stake_position, false),
-        ];
+        let mut accounts = vec![AccountMeta::new(self.market, false)];
 
         accounts
             .append_payer(self.signer)
-            .append_system_program()
             .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateStakePosition {};
+        let input = token_mill::instruction::UpdateMaxWalletHolding {
+            new_max_wallet_holding_bps: self.new_max_wallet_holding_bps,
+        };
 
         Instruction {
             program_id: token_mill::ID,
@@ -1342,96 +9993,79 @@ impl InstructionGenerator for CreateStakePositionAction {
         }
     }
 }
-
-pub struct DepositAction {
-    // Accounts
-    pub market: Pubkey,
-    pub market_staking: Pubkey,
-    pub stake_position: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub base_token_program: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub user_base_token_ata: Pubkey,
+
+#[derive(Debug)]
+pub struct CreateBuyQueueAction {
+    // Accounts
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub buy_queue: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub buy_queue_quote_token_ata: Pubkey,
     pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
     // Args
-    pub amount: u64,
+    pub max_quote_amount: u64,
+    pub open_slot: u64,
 }
 
-impl DepositAction {
-    pub fn new(testing_env: &TokenMillEnv, amount: u64) -> Self {
-        let signer = make_address("bob");
-        let base_token_mint = testing_env.base_token_mint.unwrap();
-        let base_token_program = testing_env.base_token_type.program_address();
-
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
-
-        let market_staking = Pubkey::find_program_address(
-            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+impl CreateBuyQueueAction {
+    pub fn new(token_mill_env: &TokenMillEnv, max_quote_amount: u64, open_slot: u64) -> Self {
+        let market = token_mill_env.market;
+        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
+        let quote_token_program = token_mill_env.quote_token_type.program_address();
 
-        let stake_position = Pubkey::find_program_address(
-            &[
-                STAKING_POSITION_PDA_SEED.as_bytes(),
-                &market.to_bytes(),
-                &signer.to_bytes(),
-            ],
+        let buy_queue = Pubkey::find_program_address(
+            &[BUY_QUEUE_PDA_SEED.as_bytes(), &market.to_bytes()],
             &token_mill::ID,
         )
         .0;
 
-        let market_base_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &base_token_mint,
-            &base_token_program,
-        );
-
-        let user_base_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &base_token_mint,
-            &base_token_program,
+        let buy_queue_quote_token_ata = get_associated_token_address_with_program_id(
+            &buy_queue,
+            &quote_token_mint,
+            &quote_token_program,
         );
 
         Self {
+            config: make_address("config"),
             market,
-            market_staking,
-            stake_position,
-            base_token_mint,
-            base_token_program,
-            market_base_token_ata,
-            user_base_token_ata,
-            signer,
-            amount,
+            buy_queue,
+            quote_token_mint,
+            buy_queue_quote_token_ata,
+            signer: make_address("alice"),
+            quote_token_program,
+            max_quote_amount,
+            open_slot,
         }
     }
 }
 
-impl InstructionGenerator for DepositAction {
+impl InstructionGenerator for CreateBuyQueueAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
+            AccountMeta::new_readonly(self.config, false),
             AccountMeta::new(self.market, false),
-            AccountMeta::new(self.market_staking, false),
-            AccountMeta::new(self.stake_position, false),
-            AccountMeta::new_readonly(self.base_token_mint, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.buy_queue, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.buy_queue_quote_token_ata, false),
         ];
 
-        accounts.append_payer(self.signer);
-        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
-        accounts.append_cpi_event_accounts(tm_event_authority());
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts
+            .append_associated_token_program()
+            .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::Deposit {
-            amount: self.amount,
+        let input = token_mill::instruction::CreateBuyQueue {
+            max_quote_amount: self.max_quote_amount,
+            open_slot: self.open_slot,
         };
 
         Instruction {
@@ -1442,59 +10076,68 @@ impl InstructionGenerator for DepositAction {
     }
 }
 
-pub struct WithdrawAction {
+#[derive(Debug)]
+pub struct PlaceQueuedBuyOrderAction {
     // Accounts
     pub market: Pubkey,
-    pub market_staking: Pubkey,
-    pub stake_position: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub base_token_program: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub user_base_token_ata: Pubkey,
+    pub buy_queue: Pubkey,
+    pub queued_buy_order: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub buy_queue_quote_token_ata: Pubkey,
+    pub user_quote_token_ata: Pubkey,
     pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
     // Args
-    pub amount: u64,
+    pub quote_amount: u64,
 }
 
-impl WithdrawAction {
-    pub fn new(testing_env: &TokenMillEnv, amount: u64) -> Self {
-        let deposit_action = DepositAction::new(testing_env, amount);
+impl PlaceQueuedBuyOrderAction {
+    pub fn new(token_mill_env: &TokenMillEnv, owner: Pubkey, quote_amount: u64) -> Self {
+        let create_action = CreateBuyQueueAction::new(token_mill_env, 0, 0);
+
+        let user_quote_token_ata = get_associated_token_address_with_program_id(
+            &owner,
+            &create_action.quote_token_mint,
+            &create_action.quote_token_program,
+        );
 
         Self {
-            market: deposit_action.market,
-            market_staking: deposit_action.market_staking,
-            stake_position: deposit_action.stake_position,
-            base_token_mint: deposit_action.base_token_mint,
-            base_token_program: deposit_action.base_token_program,
-            market_base_token_ata: deposit_action.market_base_token_ata,
-            user_base_token_ata: deposit_action.user_base_token_ata,
-            signer: deposit_action.signer,
-            amount,
+            market: create_action.market,
+            buy_queue: create_action.buy_queue,
+            queued_buy_order: tm_queued_buy_order(&create_action.market, &owner),
+            quote_token_mint: create_action.quote_token_mint,
+            buy_queue_quote_token_ata: create_action.buy_queue_quote_token_ata,
+            user_quote_token_ata,
+            signer: owner,
+            quote_token_program: create_action.quote_token_program,
+            quote_amount,
         }
     }
 }
 
-impl InstructionGenerator for WithdrawAction {
+impl InstructionGenerator for PlaceQueuedBuyOrderAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
-            AccountMeta::new(self.market, false),
-            AccountMeta::new(self.market_staking, false),
-            AccountMeta::new(self.stake_position, false),
-            AccountMeta::new_readonly(self.base_token_mint, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.buy_queue, false),
+            AccountMeta::new(self.queued_buy_order, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.buy_queue_quote_token_ata, false),
+            AccountMeta::new(self.user_quote_token_ata, false),
         ];
 
-        accounts.append_payer(self.signer);
-        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
         accounts.append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::Withdraw {
-            amount: self.amount,
+        let input = token_mill::instruction::PlaceQueuedBuyOrder {
+            quote_amount: self.quote_amount,
         };
 
         Instruction {
@@ -1505,91 +10148,50 @@ impl InstructionGenerator for WithdrawAction {
     }
 }
 
-pub struct ClaimStakingRewardsAction {
+#[derive(Debug)]
+pub struct CancelQueuedBuyOrderAction {
     // Accounts
     pub market: Pubkey,
-    pub market_staking: Pubkey,
-    pub stake_position: Pubkey,
+    pub buy_queue: Pubkey,
+    pub queued_buy_order: Pubkey,
     pub quote_token_mint: Pubkey,
-    pub market_quote_token_ata: Pubkey,
-    pub user_quote_token_ata: Pubkey,
-    pub quote_token_program: Pubkey,
+    pub buy_queue_quote_token_ata: Pubkey,
+    pub owner_quote_token_ata: Pubkey,
     pub signer: Pubkey,
+    pub quote_token_program: Pubkey,
 }
 
-impl ClaimStakingRewardsAction {
-    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
-        let base_token_mint = token_mill_env.base_token_mint.unwrap();
-        let signer = make_address("bob");
-
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
-
-        let market_staking = Pubkey::find_program_address(
-            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
-
-        let stake_position = Pubkey::find_program_address(
-            &[
-                STAKING_POSITION_PDA_SEED.as_bytes(),
-                &market.to_bytes(),
-                &signer.to_bytes(),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
-        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
-        let quote_token_program = token_mill_env.quote_token_type.program_address();
-
-        let market_quote_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &quote_token_mint,
-            &quote_token_program,
-        );
-
-        let user_quote_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &quote_token_mint,
-            &quote_token_program,
-        );
+impl CancelQueuedBuyOrderAction {
+    pub fn new(token_mill_env: &TokenMillEnv, owner: Pubkey) -> Self {
+        let place_action = PlaceQueuedBuyOrderAction::new(token_mill_env, owner, 0);
 
         Self {
-            market,
-            market_staking,
-            stake_position,
-            quote_token_mint,
-            market_quote_token_ata,
-            user_quote_token_ata,
-            quote_token_program,
-            signer,
+            market: place_action.market,
+            buy_queue: place_action.buy_queue,
+            queued_buy_order: place_action.queued_buy_order,
+            quote_token_mint: place_action.quote_token_mint,
+            buy_queue_quote_token_ata: place_action.buy_queue_quote_token_ata,
+            owner_quote_token_ata: place_action.user_quote_token_ata,
+            signer: owner,
+            quote_token_program: place_action.quote_token_program,
         }
     }
 }
 
-impl InstructionGenerator for ClaimStakingRewardsAction {
+impl InstructionGenerator for CancelQueuedBuyOrderAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
-            AccountMeta::new(self.market, false),
-            AccountMeta::new(self.market_staking, false),
-            AccountMeta::new(self.stake_position, false),
+            AccountMeta::new_readonly(self.market, false),
+            AccountMeta::new(self.buy_queue, false),
+            AccountMeta::new(self.queued_buy_order, false),
             AccountMeta::new_readonly(self.quote_token_mint, false),
-            AccountMeta::new(self.market_quote_token_ata, false),
-            AccountMeta::new(self.user_quote_token_ata, false),
+            AccountMeta::new(self.buy_queue_quote_token_ata, false),
+            AccountMeta::new(self.owner_quote_token_ata, false),
         ];
 
         accounts.append_payer(self.signer);
 
-        match self.quote_token_program {
-            spl_token::ID => accounts.append_token_program(),
-            spl_token_2022::ID => accounts.append_token_2022_program(),
-            _ => unreachable!(),
-        };
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
 
         accounts.append_cpi_event_accounts(tm_event_authority());
 
@@ -1597,7 +10199,7 @@ impl InstructionGenerator for ClaimStakingRewardsAction {
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::ClaimStakingRewards {};
+        let input = token_mill::instruction::CancelQueuedBuyOrder {};
 
         Instruction {
             program_id: token_mill::ID,
@@ -1607,65 +10209,153 @@ impl InstructionGenerator for ClaimStakingRewardsAction {
     }
 }
 
-pub struct CreateReferralAccountAction {
+#[derive(Debug)]
+pub struct FillQueuedBuyOrderAction {
     // Accounts
     pub config: Pubkey,
-    pub referral_account: Pubkey,
+    pub market: Pubkey,
+    pub buy_queue: Pubkey,
+    pub queued_buy_order: Pubkey,
+    pub owner: Pubkey,
+    pub launch_purchase: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub market_base_token_ata: Pubkey,
+    pub market_quote_token_ata: Pubkey,
+    pub buy_queue_quote_token_ata: Pubkey,
+    pub owner_base_token_ata: Pubkey,
+    pub owner_quote_token_ata: Pubkey,
+    pub protocol_quote_token_ata: Pubkey,
     pub signer: Pubkey,
-    // Args
-    pub referrer: Pubkey,
+    pub base_token_program: Pubkey,
+    pub quote_token_program: Pubkey,
+    pub revenue_report: Pubkey,
+    pub denylist: Pubkey,
+    pub policy_program: Pubkey,
+    pub fee_hook_program: Pubkey,
+    pub market_oracle: Pubkey,
+    pub market_stats: Pubkey,
 }
 
-impl Default for CreateReferralAccountAction {
-    fn default() -> Self {
-        Self::new()
+impl FillQueuedBuyOrderAction {
+    pub fn new(token_mill_env: &TokenMillEnv, owner: Pubkey) -> Self {
+        let place_action = PlaceQueuedBuyOrderAction::new(token_mill_env, owner, 0);
+
+        let base_token_mint = token_mill_env.base_token_mint.unwrap();
+        let base_token_program = token_mill_env.base_token_type.program_address();
+
+        let market_base_token_ata = get_associated_token_address_with_program_id(
+            &place_action.market,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let market_quote_token_ata = get_associated_token_address_with_program_id(
+            &place_action.market,
+            &place_action.quote_token_mint,
+            &place_action.quote_token_program,
+        );
+
+        let owner_base_token_ata = get_associated_token_address_with_program_id(
+            &owner,
+            &base_token_mint,
+            &base_token_program,
+        );
+
+        let protocol_quote_token_ata = get_associated_token_address_with_program_id(
+            &make_address("dave"),
+            &place_action.quote_token_mint,
+            &place_action.quote_token_program,
+        );
+
+        Self {
+            config: make_address("config"),
+            market: place_action.market,
+            buy_queue: place_action.buy_queue,
+            queued_buy_order: place_action.queued_buy_order,
+            owner,
+            launch_purchase: token_mill::ID,
+            base_token_mint,
+            quote_token_mint: place_action.quote_token_mint,
+            market_base_token_ata,
+            market_quote_token_ata,
+            buy_queue_quote_token_ata: place_action.buy_queue_quote_token_ata,
+            owner_base_token_ata,
+            owner_quote_token_ata: place_action.user_quote_token_ata,
+            protocol_quote_token_ata,
+            signer: make_address("carol"),
+            base_token_program,
+            quote_token_program: place_action.quote_token_program,
+            revenue_report: tm_revenue_report(token_mill_env, &place_action.quote_token_mint),
+            denylist: token_mill::ID,
+            policy_program: token_mill::ID,
+            fee_hook_program: token_mill::ID,
+            market_oracle: token_mill::ID,
+            market_stats: token_mill::ID,
+        }
     }
-}
 
-impl CreateReferralAccountAction {
-    pub fn new() -> Self {
-        let config = make_address("config");
+    pub fn with_denylist(mut self) -> Self {
+        self.denylist = tm_denylist();
 
-        let referrer = make_address("carol");
+        self
+    }
 
-        let referral_account = Pubkey::find_program_address(
+    pub fn with_launch_purchase(mut self) -> Self {
+        self.launch_purchase = Pubkey::find_program_address(
             &[
-                REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
-                (config.as_ref()),
-                (referrer.as_ref()),
+                LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+                &self.market.to_bytes(),
+                &self.owner.to_bytes(),
             ],
             &token_mill::ID,
         )
         .0;
 
-        Self {
-            config,
-            referral_account,
-            signer: make_address("admin"),
-            referrer,
-        }
+        self
     }
 }
 
-impl InstructionGenerator for CreateReferralAccountAction {
+impl InstructionGenerator for FillQueuedBuyOrderAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
             AccountMeta::new_readonly(self.config, false),
-            AccountMeta::new(self.referral_account, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new(self.buy_queue, false),
+            AccountMeta::new(self.queued_buy_order, false),
+            AccountMeta::new(self.owner, false),
+            AccountMeta::new(self.launch_purchase, false),
+            AccountMeta::new_readonly(self.base_token_mint, false),
+            AccountMeta::new_readonly(self.quote_token_mint, false),
+            AccountMeta::new(self.market_base_token_ata, false),
+            AccountMeta::new(self.market_quote_token_ata, false),
+            AccountMeta::new(self.buy_queue_quote_token_ata, false),
+            AccountMeta::new(self.owner_base_token_ata, false),
+            AccountMeta::new(self.owner_quote_token_ata, false),
+            AccountMeta::new(self.protocol_quote_token_ata, false),
         ];
 
-        accounts
-            .append_payer(self.signer)
-            .append_system_program()
-            .append_cpi_event_accounts(tm_event_authority());
+        accounts.append_payer(self.signer).append_system_program();
+
+        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
+        accounts.push(AccountMeta::new_readonly(self.quote_token_program, false));
+
+        accounts.append_associated_token_program();
+
+        accounts.push(AccountMeta::new(self.revenue_report, false));
+        accounts.push(AccountMeta::new_readonly(self.denylist, false));
+        accounts.push(AccountMeta::new_readonly(self.policy_program, false));
+        accounts.push(AccountMeta::new_readonly(self.fee_hook_program, false));
+        accounts.push(AccountMeta::new(self.market_oracle, false));
+        accounts.push(AccountMeta::new(self.market_stats, false));
+
+        accounts.append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateReferralAccount {
-            referrer: self.referrer,
-        };
+        let input = token_mill::instruction::FillQueuedBuyOrder {};
 
         Instruction {
             program_id: token_mill::ID,
@@ -1675,82 +10365,38 @@ impl InstructionGenerator for CreateReferralAccountAction {
     }
 }
 
-pub struct ClaimReferralFeesAction {
-    pub config: Pubkey,
-    pub referral_account: Pubkey,
-    pub quote_token_mint: Pubkey,
-    pub referral_account_quote_token_ata: Pubkey,
-    pub referrer_quote_token_ata: Pubkey,
-    pub signer: Pubkey,
-    pub quote_token_program: Pubkey,
+#[derive(Debug)]
+pub struct OpenBuyQueueAction {
+    // Accounts
+    pub market: Pubkey,
+    pub buy_queue: Pubkey,
 }
 
-impl ClaimReferralFeesAction {
+impl OpenBuyQueueAction {
     pub fn new(token_mill_env: &TokenMillEnv) -> Self {
-        let config = make_address("config");
-        let signer = make_address("carol");
-
-        let referral_account = Pubkey::find_program_address(
-            &[
-                REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
-                (config.as_ref()),
-                (signer.as_ref()),
-            ],
-            &token_mill::ID,
-        )
-        .0;
-
-        let quote_token_mint = token_mill_env.quote_token_mint.unwrap();
-        let quote_token_program = token_mill_env.quote_token_type.program_address();
-
-        let referral_account_quote_token_ata = get_associated_token_address_with_program_id(
-            &referral_account,
-            &quote_token_mint,
-            &quote_token_program,
-        );
-
-        let referrer_quote_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &quote_token_mint,
-            &quote_token_program,
-        );
+        let create_action = CreateBuyQueueAction::new(token_mill_env, 0, 0);
 
         Self {
-            config,
-            referral_account,
-            quote_token_mint,
-            referral_account_quote_token_ata,
-            referrer_quote_token_ata,
-            signer,
-            quote_token_program,
+            market: create_action.market,
+            buy_queue: create_action.buy_queue,
         }
     }
 }
 
-impl InstructionGenerator for ClaimReferralFeesAction {
+impl InstructionGenerator for OpenBuyQueueAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
-            AccountMeta::new_readonly(self.referral_account, false),
-            AccountMeta::new_readonly(self.quote_token_mint, false),
-            AccountMeta::new(self.referral_account_quote_token_ata, false),
-            AccountMeta::new(self.referrer_quote_token_ata, false),
+            AccountMeta::new(self.market, false),
+            AccountMeta::new_readonly(self.buy_queue, false),
         ];
 
-        accounts.append_payer(self.signer);
-
-        match self.quote_token_program {
-            spl_token::ID => accounts.append_token_program(),
-            spl_token_2022::ID => accounts.append_token_2022_program(),
-            _ => unreachable!(),
-        };
-
         accounts.append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::ClaimReferralFees {};
+        let input = token_mill::instruction::OpenBuyQueue {};
 
         Instruction {
             program_id: token_mill::ID,
@@ -1760,117 +10406,64 @@ impl InstructionGenerator for ClaimReferralFeesAction {
     }
 }
 
-pub struct CreateVestingPlanAction {
+#[derive(Debug)]
+pub struct LockStakeAction {
     // Accounts
     pub market: Pubkey,
-    pub staking: Pubkey,
-    pub staking_position: Pubkey,
-    pub vesting_plan: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub base_token_program: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub user_base_token_ata: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
     pub signer: Pubkey,
     // Args
-    pub vesting_amount: u64,
-    pub start: i64,
-    pub vesting_duration: i64,
-    pub cliff_duration: i64,
+    pub lock_tier: u8,
 }
 
-impl CreateVestingPlanAction {
-    pub fn new(
-        token_mill_env: &TokenMillEnv,
-        vesting_amount: u64,
-        start: i64,
-        vesting_duration: i64,
-        cliff_duration: i64,
-    ) -> Self {
-        let base_token_mint = make_address("base_token_mint");
-        let base_token_program = token_mill_env.base_token_type.program_address();
-
-        let vesting_plan = make_address("vesting_plan");
-        let signer = make_address("bob");
-
-        let market = Pubkey::find_program_address(
-            &[MARKET_PDA_SEED.as_bytes(), &base_token_mint.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+impl LockStakeAction {
+    pub fn new(testing_env: &TokenMillEnv, lock_tier: u8) -> Self {
+        let deposit_action = DepositAction::new(testing_env, 0);
 
-        let staking = Pubkey::find_program_address(
-            &[MARKET_STAKING_PDA_SEED.as_bytes(), &market.to_bytes()],
-            &token_mill::ID,
-        )
-        .0;
+        Self {
+            market: deposit_action.market,
+            market_staking: deposit_action.market_staking,
+            stake_position: deposit_action.stake_position,
+            signer: deposit_action.signer,
+            lock_tier,
+        }
+    }
 
-        let staking_position = Pubkey::find_program_address(
+    pub fn with_signer(mut self, signer: Pubkey) -> Self {
+        self.stake_position = Pubkey::find_program_address(
             &[
                 STAKING_POSITION_PDA_SEED.as_bytes(),
-                &market.to_bytes(),
+                &self.market.to_bytes(),
                 &signer.to_bytes(),
             ],
             &token_mill::ID,
         )
         .0;
+        self.signer = signer;
 
-        let market_base_token_ata = get_associated_token_address_with_program_id(
-            &market,
-            &base_token_mint,
-            &base_token_program,
-        );
-
-        let user_base_token_ata = get_associated_token_address_with_program_id(
-            &signer,
-            &base_token_mint,
-            &base_token_program,
-        );
-
-        Self {
-            market,
-            staking,
-            staking_position,
-            vesting_plan,
-            base_token_mint,
-            base_token_program,
-            market_base_token_ata,
-            user_base_token_ata,
-            signer,
-            vesting_amount,
-            start,
-            vesting_duration,
-            cliff_duration,
-        }
+        self
     }
 }
 
-impl InstructionGenerator for CreateVestingPlanAction {
+impl InstructionGenerator for LockStakeAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
             AccountMeta::new(self.market, false),
-            AccountMeta::new(self.staking, false),
-            AccountMeta::new(self.staking_position, false),
-            AccountMeta::new(self.vesting_plan, true),
-            AccountMeta::new_readonly(self.base_token_mint, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
         ];
 
-        accounts.append_payer(self.signer);
-        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
         accounts
-            .append_system_program()
+            .append_payer(self.signer)
             .append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::CreateVestingPlan {
-            vesting_amount: self.vesting_amount,
-            start: self.start,
-            vesting_duration: self.vesting_duration,
-            cliff_duration: self.cliff_duration,
+        let input = token_mill::instruction::LockStake {
+            lock_tier: self.lock_tier,
         };
 
         Instruction {
@@ -1881,58 +10474,87 @@ impl InstructionGenerator for CreateVestingPlanAction {
     }
 }
 
-pub struct ReleaseAction {
+#[derive(Debug)]
+pub struct ExpireStakeLockAction {
     // Accounts
     pub market: Pubkey,
-    pub staking: Pubkey,
-    pub staking_position: Pubkey,
-    pub vesting_plan: Pubkey,
-    pub base_token_mint: Pubkey,
-    pub base_token_program: Pubkey,
-    pub market_base_token_ata: Pubkey,
-    pub user_base_token_ata: Pubkey,
-    pub signer: Pubkey,
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
 }
 
-impl ReleaseAction {
-    pub fn new(token_mill_env: &TokenMillEnv) -> Self {
-        let create_vesting_plan_action = CreateVestingPlanAction::new(token_mill_env, 0, 0, 0, 0);
+impl ExpireStakeLockAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let deposit_action = DepositAction::new(testing_env, 0);
 
         Self {
-            market: create_vesting_plan_action.market,
-            staking: create_vesting_plan_action.staking,
-            staking_position: create_vesting_plan_action.staking_position,
-            vesting_plan: create_vesting_plan_action.vesting_plan,
-            base_token_mint: create_vesting_plan_action.base_token_mint,
-            base_token_program: create_vesting_plan_action.base_token_program,
-            market_base_token_ata: create_vesting_plan_action.market_base_token_ata,
-            user_base_token_ata: create_vesting_plan_action.user_base_token_ata,
-            signer: create_vesting_plan_action.signer,
+            market: deposit_action.market,
+            market_staking: deposit_action.market_staking,
+            stake_position: deposit_action.stake_position,
         }
     }
 }
 
-impl InstructionGenerator for ReleaseAction {
+impl InstructionGenerator for ExpireStakeLockAction {
     fn accounts(&self) -> Vec<AccountMeta> {
         let mut accounts = vec![
             AccountMeta::new(self.market, false),
-            AccountMeta::new(self.staking, false),
-            AccountMeta::new(self.staking_position, false),
-            AccountMeta::new(self.vesting_plan, false),
-            AccountMeta::new_readonly(self.base_token_mint, false),
-            AccountMeta::new(self.market_base_token_ata, false),
-            AccountMeta::new(self.user_base_token_ata, false),
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
         ];
 
-        accounts.append_payer(self.signer);
-        accounts.push(AccountMeta::new_readonly(self.base_token_program, false));
         accounts.append_cpi_event_accounts(tm_event_authority());
 
         accounts
     }
 
     fn instruction(&self) -> Instruction {
-        let input = token_mill::instruction::Release {};
+        let input = token_mill::instruction::ExpireStakeLock {};
+
+        Instruction {
+            program_id: token_mill::ID,
+            accounts: self.accounts(),
+            data: input.data(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct BackfillStakePositionAction {
+    // Accounts
+    pub market_staking: Pubkey,
+    pub stake_position: Pubkey,
+    pub signer: Pubkey,
+}
+
+impl BackfillStakePositionAction {
+    pub fn new(testing_env: &TokenMillEnv) -> Self {
+        let deposit_action = DepositAction::new(testing_env, 0);
+
+        Self {
+            market_staking: deposit_action.market_staking,
+            stake_position: deposit_action.stake_position,
+            signer: deposit_action.signer,
+        }
+    }
+}
+
+impl InstructionGenerator for BackfillStakePositionAction {
+    fn accounts(&self) -> Vec<AccountMeta> {
+        let mut accounts = vec![
+            AccountMeta::new(self.market_staking, false),
+            AccountMeta::new(self.stake_position, false),
+        ];
+
+        accounts
+            .append_payer(self.signer)
+            .append_system_program()
+            .append_cpi_event_accounts(tm_event_authority());
+
+        accounts
+    }
+
+    fn instruction(&self) -> Instruction {
+        let input = token_mill::instruction::BackfillStakePosition {};
 
         Instruction {
             program_id: token_mill::ID,
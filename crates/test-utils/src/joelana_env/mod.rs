@@ -9,7 +9,11 @@ use anchor_spl::{
 use anchor_spl::{
     metadata::Metadata,
     token_interface::spl_token_2022::{
-        extension::StateWithExtensions, solana_program::program_pack::Pack,
+        extension::{
+            transfer_fee::instruction::initialize_transfer_fee_config, ExtensionType,
+            StateWithExtensions,
+        },
+        solana_program::program_pack::Pack,
         state::Account as SplAccount,
     },
 };
@@ -27,13 +31,17 @@ use solana_sdk::{
     native_token::sol_to_lamports,
     pubkey::Pubkey,
     signature::Keypair,
+    system_instruction,
     transaction::{Transaction, TransactionError},
 };
 use std::collections::HashMap;
 
 pub use token_mill::{
     errors::TokenMillError,
-    manager::swap_manager::{SwapAmountType, SwapType},
+    manager::{
+        curve_manager::{CurveParameters, CurveShape},
+        swap_manager::{SwapAmountType, SwapType},
+    },
     state::QuoteTokenBadgeStatus,
 };
 
@@ -157,6 +165,18 @@ impl JoelanaEnv {
         self.svm_engine.set_sysvar(&clock);
     }
 
+    pub fn warp_to_slot(&mut self, slot: u64) {
+        self.svm_engine.warp_to_slot(slot);
+    }
+
+    pub fn get_slot(&self) -> u64 {
+        self.svm_engine.get_sysvar::<Clock>().slot
+    }
+
+    pub fn get_unix_timestamp(&self) -> i64 {
+        self.svm_engine.get_sysvar::<Clock>().unix_timestamp
+    }
+
     pub fn create_token(&mut self, token_type: TokenType, decimals: u8) -> Result<Pubkey> {
         let payer = Keypair::new();
 
@@ -168,13 +188,78 @@ impl JoelanaEnv {
             .send()
             .unwrap();
 
+        self.mint_to_actors(&payer, &token_address, token_type)?;
+
+        Ok(token_address)
+    }
+
+    /// Creates a Token-2022 mint with the TransferFee extension, withholding
+    /// `transfer_fee_basis_points` of every transfer, up to `maximum_fee`.
+    pub fn create_token_with_transfer_fee(
+        &mut self,
+        decimals: u8,
+        transfer_fee_basis_points: u16,
+        maximum_fee: u64,
+    ) -> Result<Pubkey> {
+        let payer = Keypair::new();
+        let mint = Keypair::new();
+
+        self.airdrop(&payer.pubkey());
+
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])?;
+
+        let instructions = [
+            system_instruction::create_account(
+                &payer.pubkey(),
+                &mint.pubkey(),
+                self.svm_engine.minimum_balance_for_rent_exemption(space),
+                space as u64,
+                &spl_token_2022::id(),
+            ),
+            initialize_transfer_fee_config(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                None,
+                None,
+                transfer_fee_basis_points,
+                maximum_fee,
+            )?,
+            spl_token_2022::instruction::initialize_mint2(
+                &spl_token_2022::id(),
+                &mint.pubkey(),
+                &payer.pubkey(),
+                None,
+                decimals,
+            )?,
+        ];
+
+        self.svm_engine
+            .send_transaction(Transaction::new_unsigned(Message::new(
+                &instructions,
+                Some(&payer.pubkey()),
+            )))
+            .unwrap();
+
+        self.mint_to_actors(&payer, &mint.pubkey(), TokenType::Token2022)?;
+
+        Ok(mint.pubkey())
+    }
+
+    fn mint_to_actors(
+        &mut self,
+        payer: &Keypair,
+        token_address: &Pubkey,
+        token_type: TokenType,
+    ) -> Result<()> {
         for actor in ACTORS {
-            let actor_ata = self.create_ata(&make_address(actor), &token_address, token_type)?;
+            let actor_ata = self.create_ata(&make_address(actor), token_address, token_type)?;
 
             MintTo::new(
                 &mut self.svm_engine,
-                &payer,
-                &token_address,
+                payer,
+                token_address,
                 &actor_ata,
                 (u64::MAX - 1) / ACTORS.len() as u64,
             )
@@ -183,9 +268,9 @@ impl JoelanaEnv {
             .unwrap();
         }
 
-        self.tokens.insert(token_address, token_type);
+        self.tokens.insert(*token_address, token_type);
 
-        Ok(token_address)
+        Ok(())
     }
 
     pub fn create_ata(
@@ -208,12 +293,44 @@ impl JoelanaEnv {
         Ok(ata_address)
     }
 
+    pub fn approve(
+        &mut self,
+        token_mint: &Pubkey,
+        owner: &Pubkey,
+        delegate: &Pubkey,
+        amount: u64,
+    ) -> Result<TransactionMetadata, FailedTransactionMetadata> {
+        let token_program = self.tokens.get(token_mint).unwrap().program_address();
+
+        let approve_instruction = spl_token_2022::instruction::approve(
+            &token_program,
+            &self.get_ata_address(token_mint, owner),
+            delegate,
+            owner,
+            &[],
+            amount,
+        )
+        .unwrap();
+
+        self.execute(&[approve_instruction])
+    }
+
     pub fn get_account(&self, pubkey: &Pubkey) -> Account {
         self.svm_engine
             .get_account(pubkey)
             .expect("Account not found")
     }
 
+    pub fn set_account(&mut self, pubkey: &Pubkey, account: Account) {
+        self.svm_engine.set_account(*pubkey, account).unwrap();
+    }
+
+    pub fn account_exists(&self, pubkey: &Pubkey) -> bool {
+        self.svm_engine
+            .get_account(pubkey)
+            .is_some_and(|account| account.lamports > 0)
+    }
+
     pub fn get_parsed_account<T>(&self, pubkey: &Pubkey) -> T
     where
         T: AccountDeserialize,
@@ -0,0 +1,72 @@
+//! Golden layout checks of zero-copy accounts, which are read in place from the account data.
+//! A field moved, resized or inserted in the middle reinterprets the bytes of existing accounts
+//! without any error, so the expected offsets should only change together with a migration.
+//! Accounts already deployed pin the layout they were created with as a prefix of the current
+//! one, see `assert_prefix_layout`.
+
+pub use anchor_lang::Space;
+
+/// Asserts the size of a zero-copy struct, that its `INIT_SPACE` allocates exactly that size,
+/// and the offset of each listed field.
+///
+/// ```ignore
+/// assert_layout!(MarketVolume, 40, {
+///     window_start: 0,
+///     quote_volume: 8,
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_layout {
+    ($type:ty, $size:expr, { $($field:ident: $offset:expr),* $(,)? }) => {{
+        assert_eq!(
+            std::mem::size_of::<$type>(),
+            $size,
+            "size of {}",
+            stringify!($type)
+        );
+        assert_eq!(
+            <$type as $crate::utils::layout::Space>::INIT_SPACE,
+            $size,
+            "init space of {}",
+            stringify!($type)
+        );
+        $(
+            assert_eq!(
+                std::mem::offset_of!($type, $field),
+                $offset,
+                "offset of {}::{}",
+                stringify!($type),
+                stringify!($field)
+            );
+        )*
+    }};
+}
+
+/// Asserts that a zero-copy struct is at least `$size` bytes and the offset of each listed field,
+/// for the layout of accounts created before fields were appended to the struct.
+///
+/// ```ignore
+/// assert_prefix_layout!(Market, 360, {
+///     config: 0,
+///     bump: 353,
+/// });
+/// ```
+#[macro_export]
+macro_rules! assert_prefix_layout {
+    ($type:ty, $size:expr, { $($field:ident: $offset:expr),* $(,)? }) => {{
+        assert!(
+            std::mem::size_of::<$type>() >= $size,
+            "size of {} below its prefix",
+            stringify!($type)
+        );
+        $(
+            assert_eq!(
+                std::mem::offset_of!($type, $field),
+                $offset,
+                "offset of {}::{}",
+                stringify!($type),
+                stringify!($field)
+            );
+        )*
+    }};
+}
@@ -0,0 +1,488 @@
+use std::sync::atomic::Ordering;
+
+use anchor_lang::{AccountDeserialize, Id};
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_2022::spl_token_2022::{
+        self,
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+        },
+    },
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use jupiter_amm_interface::{
+    AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, Quote, QuoteParams, Side, Swap,
+    SwapAndAccountMetas, SwapMode, SwapParams,
+};
+use rust_decimal::Decimal;
+use solana_sdk::{instruction::AccountMeta, pubkey::Pubkey};
+use token_mill::{
+    manager::swap_manager::{self, SwapAmountType, SwapType},
+    pda,
+    state::{Market, RevenueReport, TokenMillConfig},
+};
+
+/// Jupiter adapter of a Token Mill market, quoting swaps against the bonding curve with the
+/// same pricing, fees and limits as the `swap` instruction.
+#[derive(Clone)]
+pub struct TokenMillAmm {
+    key: Pubkey,
+    market: Market,
+    config: Option<TokenMillConfig>,
+    base_token_program: Option<Pubkey>,
+    quote_token_program: Option<Pubkey>,
+    /// Set for Token 2022 quote tokens with the TransferFee extension.
+    quote_transfer_fee_config: Option<TransferFeeConfig>,
+    clock_ref: ClockRef,
+}
+
+impl TokenMillAmm {
+    fn market(account_data: &[u8]) -> Result<Market> {
+        Market::try_deserialize(&mut &account_data[..]).map_err(|e| anyhow!(e))
+    }
+
+    fn config(&self) -> Result<&TokenMillConfig> {
+        self.config.as_ref().context("Config not loaded")
+    }
+
+    fn get_transfer_fee(&self, amount: u64) -> Result<u64> {
+        let Some(transfer_fee_config) = &self.quote_transfer_fee_config else {
+            return Ok(0);
+        };
+
+        transfer_fee_config
+            .calculate_epoch_fee(self.clock_ref.epoch.load(Ordering::Relaxed), amount)
+            .context("Transfer fee overflow")
+    }
+
+    fn get_transfer_inverse_fee(&self, post_fee_amount: u64) -> Result<u64> {
+        let Some(transfer_fee_config) = &self.quote_transfer_fee_config else {
+            return Ok(0);
+        };
+
+        transfer_fee_config
+            .calculate_inverse_epoch_fee(
+                self.clock_ref.epoch.load(Ordering::Relaxed),
+                post_fee_amount,
+            )
+            .context("Transfer fee overflow")
+    }
+
+    fn get_swap_type(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<SwapType> {
+        let market = &self.market;
+
+        if *input_mint == market.quote_token_mint && *output_mint == market.base_token_mint {
+            Ok(SwapType::Buy)
+        } else if *input_mint == market.base_token_mint && *output_mint == market.quote_token_mint {
+            Ok(SwapType::Sell)
+        } else {
+            Err(anyhow!("Invalid mints"))
+        }
+    }
+}
+
+impl Amm for TokenMillAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self> {
+        Ok(Self {
+            key: keyed_account.key,
+            market: Self::market(&keyed_account.account.data)?,
+            config: None,
+            base_token_program: None,
+            quote_token_program: None,
+            quote_transfer_fee_config: None,
+            clock_ref: amm_context.clock_ref.clone(),
+        })
+    }
+
+    fn label(&self) -> String {
+        "Token Mill".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        token_mill::ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.market.base_token_mint, self.market.quote_token_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![
+            self.key,
+            self.market.config,
+            self.market.base_token_mint,
+            self.market.quote_token_mint,
+        ]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let get_account = |key: &Pubkey| {
+            account_map
+                .get(key)
+                .with_context(|| format!("Account {key} not found"))
+        };
+
+        self.market = Self::market(&get_account(&self.key)?.data)?;
+
+        let config = get_account(&self.market.config)?;
+        self.config =
+            Some(TokenMillConfig::try_deserialize(&mut &config.data[..]).map_err(|e| anyhow!(e))?);
+
+        self.base_token_program = Some(get_account(&self.market.base_token_mint)?.owner);
+
+        let quote_token_mint = get_account(&self.market.quote_token_mint)?;
+        self.quote_token_program = Some(quote_token_mint.owner);
+        self.quote_transfer_fee_config = if quote_token_mint.owner == spl_token_2022::ID {
+            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&quote_token_mint.data)?
+                .get_extension::<TransferFeeConfig>()
+                .ok()
+                .copied()
+        } else {
+            None
+        };
+
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let current_slot = self.clock_ref.slot.load(Ordering::Relaxed);
+        let timestamp = self.clock_ref.unix_timestamp.load(Ordering::Relaxed);
+
+        let mut market = self.market;
+        market.apply_scheduled_updates(current_slot, timestamp);
+        let market = &market;
+        let swap_type = self.get_swap_type(&quote_params.input_mint, &quote_params.output_mint)?;
+        let swap_amount_type = match quote_params.swap_mode {
+            SwapMode::ExactIn => SwapAmountType::ExactInput,
+            SwapMode::ExactOut => SwapAmountType::ExactOutput,
+        };
+        let amount = quote_params.amount;
+
+        ensure!(amount > 0, "Invalid amount");
+
+        // Mirrors the `quote_swap` view
+        let curve_amount = match (swap_type, swap_amount_type) {
+            (SwapType::Buy, SwapAmountType::ExactInput) => {
+                amount - self.get_transfer_fee(amount)?
+            }
+            (SwapType::Sell, SwapAmountType::ExactOutput) => {
+                amount + self.get_transfer_inverse_fee(amount)?
+            }
+            _ => amount,
+        };
+
+        let outcome = swap_manager::get_swap_outcome(
+            market,
+            swap_type,
+            swap_amount_type,
+            curve_amount,
+            None,
+            current_slot,
+            timestamp,
+        )
+        .map_err(|e| anyhow!(e))?;
+
+        if let Some(max_base_amount) = market
+            .get_max_swap_base_amount(swap_type, self.config()?.max_swap_size_bps)
+            .map_err(|e| anyhow!(e))?
+        {
+            ensure!(outcome.base_amount <= max_base_amount, "Swap size exceeded");
+        }
+
+        let (in_amount, out_amount) = match swap_type {
+            SwapType::Buy => {
+                let quote_amount_in =
+                    outcome.quote_amount + self.get_transfer_inverse_fee(outcome.quote_amount)?;
+
+                let quote_amount_in = match swap_amount_type {
+                    SwapAmountType::ExactInput => quote_amount_in.min(amount),
+                    SwapAmountType::ExactOutput => quote_amount_in,
+                };
+
+                (quote_amount_in, outcome.base_amount)
+            }
+            SwapType::Sell => (
+                outcome.base_amount,
+                outcome.quote_amount - self.get_transfer_fee(outcome.quote_amount)?,
+            ),
+        };
+
+        let fee_amount = outcome.swap_fee + outcome.launch_fee + outcome.holder_rewards_fee;
+
+        Ok(Quote {
+            in_amount,
+            out_amount,
+            fee_amount,
+            fee_mint: market.quote_token_mint,
+            fee_pct: if outcome.quote_amount > 0 {
+                Decimal::from(fee_amount) / Decimal::from(outcome.quote_amount)
+            } else {
+                Decimal::ZERO
+            },
+            ..Quote::default()
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        let market = &self.market;
+        let config = self.config()?;
+        let base_token_program = self.base_token_program.context("Base mint not loaded")?;
+        let quote_token_program = self.quote_token_program.context("Quote mint not loaded")?;
+
+        let swap_type =
+            self.get_swap_type(&swap_params.source_mint, &swap_params.destination_mint)?;
+
+        let (user_base_token_account, user_quote_token_account, side) = match swap_type {
+            SwapType::Buy => (
+                swap_params.destination_token_account,
+                swap_params.source_token_account,
+                Side::Bid,
+            ),
+            SwapType::Sell => (
+                swap_params.source_token_account,
+                swap_params.destination_token_account,
+                Side::Ask,
+            ),
+        };
+
+        let denylist = if config.denylist_enabled {
+            pda::find_denylist_address(&market.config).0
+        } else {
+            token_mill::ID
+        };
+
+        let epoch = RevenueReport::get_epoch(self.clock_ref.unix_timestamp.load(Ordering::Relaxed));
+        let revenue_report =
+            pda::find_revenue_report_address(&market.config, &market.quote_token_mint, epoch).0;
+
+        // Optional accounts not provided are set to the program id
+        let none = AccountMeta::new_readonly(token_mill::ID, false);
+
+        let mut account_metas = vec![
+            AccountMeta::new_readonly(market.config, false),
+            AccountMeta::new(self.key, false),
+            AccountMeta::new_readonly(market.base_token_mint, false),
+            AccountMeta::new_readonly(market.quote_token_mint, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &self.key,
+                    &market.base_token_mint,
+                    &base_token_program,
+                ),
+                false,
+            ),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &self.key,
+                    &market.quote_token_mint,
+                    &quote_token_program,
+                ),
+                false,
+            ),
+            AccountMeta::new(user_base_token_account, false),
+            AccountMeta::new(user_quote_token_account, false),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(
+                    &config.protocol_fee_recipient,
+                    &market.quote_token_mint,
+                    &quote_token_program,
+                ),
+                false,
+            ),
+            // Referral token account
+            none.clone(),
+            AccountMeta::new_readonly(swap_params.token_transfer_authority, true),
+            AccountMeta::new_readonly(base_token_program, false),
+            AccountMeta::new_readonly(quote_token_program, false),
+            AccountMeta::new(revenue_report, false),
+            // Policy program, referral accrual and referral code
+            none.clone(),
+            none.clone(),
+            none.clone(),
+            AccountMeta::new_readonly(denylist, false),
+        ];
+
+        // Referral account, trader volume, revenue report token account, swap receipt tree,
+        // merkle tree, compression and noop programs and launch purchase
+        account_metas.extend(std::iter::repeat(none.clone()).take(8));
+
+        // Dynamic fee markets require their oracle
+        account_metas.push(if market.is_dynamic_fee_enabled() {
+            AccountMeta::new(pda::find_market_oracle_address(&self.key).0, false)
+        } else {
+            none.clone()
+        });
+
+        // Market stats
+        account_metas.push(none.clone());
+
+        // Swaps of traders bound to a referrer must name it, see `ReferrerBinding`
+        account_metas.push(AccountMeta::new(
+            pda::find_referrer_binding_address(
+                &market.config,
+                &swap_params.token_transfer_authority,
+            )
+            .0,
+            false,
+        ));
+
+        // Fee hook program and fee exemption
+        account_metas.extend(std::iter::repeat(none).take(2));
+
+        account_metas.push(AccountMeta::new_readonly(
+            pda::find_event_authority_address().0,
+            false,
+        ));
+        account_metas.push(AccountMeta::new_readonly(token_mill::ID, false));
+
+        Ok(SwapAndAccountMetas {
+            swap: Swap::TokenMill { side },
+            account_metas,
+        })
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+
+    fn program_dependencies(&self) -> Vec<(Pubkey, String)> {
+        vec![(AssociatedToken::id(), "associated_token".to_string())]
+    }
+
+    fn get_accounts_len(&self) -> usize {
+        33
+    }
+
+    /// Swaps of markets with a policy or a fee hook program need their accounts, and buys of
+    /// markets in their launch window need a launch purchase, neither of which a route provides.
+    fn is_active(&self) -> bool {
+        let market = &self.market;
+        let timestamp = self.clock_ref.unix_timestamp.load(Ordering::Relaxed);
+
+        !market.is_migrated()
+            && market.are_prices_set()
+            && !market.is_paused(self.clock_ref.slot.load(Ordering::Relaxed))
+            && market.get_max_buy_per_wallet(timestamp).is_none()
+            && self.config.as_ref().is_some_and(|config| {
+                config.policy_program.is_none() && config.fee_hook_program.is_none()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use joelana_test_utils::joelana_env::actions::token_mill::{SwapAction, TokenMillEnv};
+
+    fn setup_amm(testing_env: &TokenMillEnv) -> TokenMillAmm {
+        let market = testing_env.market;
+
+        let mut amm = TokenMillAmm::from_keyed_account(
+            &KeyedAccount {
+                key: market,
+                account: testing_env.svm.get_account(&market),
+                params: None,
+            },
+            &AmmContext {
+                clock_ref: ClockRef::default(),
+            },
+        )
+        .unwrap();
+
+        let account_map = amm
+            .get_accounts_to_update()
+            .into_iter()
+            .map(|key| (key, testing_env.svm.get_account(&key)))
+            .collect::<AccountMap>();
+
+        amm.update(&account_map).unwrap();
+
+        amm
+    }
+
+    #[test]
+    fn quote() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let amm = setup_amm(&testing_env);
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let quote = amm
+            .quote(&QuoteParams {
+                amount: 1_000_000_000,
+                input_mint: quote_token_mint,
+                output_mint: base_token_mint,
+                swap_mode: SwapMode::ExactIn,
+            })
+            .unwrap();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactInput,
+                1_000_000_000,
+                0,
+                None,
+            )])
+            .unwrap();
+
+        assert!(amm.is_active());
+        assert_eq!(quote.in_amount, 1_000_000_000);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&base_token_mint, &testing_env.svm.payer),
+            quote.out_amount
+        );
+        assert!(quote.fee_amount > 0);
+    }
+
+    #[test]
+    fn swap_account_metas() {
+        let testing_env = TokenMillEnv::default();
+
+        let amm = setup_amm(&testing_env);
+        let user = testing_env.svm.payer;
+
+        let swap_and_account_metas = amm
+            .get_swap_and_account_metas(&SwapParams {
+                swap_mode: SwapMode::ExactIn,
+                in_amount: 1_000_000_000,
+                out_amount: 0,
+                source_mint: testing_env.quote_token_mint.unwrap(),
+                destination_mint: testing_env.base_token_mint.unwrap(),
+                source_token_account: Pubkey::new_unique(),
+                destination_token_account: Pubkey::new_unique(),
+                token_transfer_authority: user,
+                open_order_address: None,
+                quote_mint_to_referrer: None,
+                jupiter_program_id: &Pubkey::new_unique(),
+                missing_dynamic_accounts_as_default: false,
+            })
+            .unwrap();
+
+        let account_metas = swap_and_account_metas.account_metas;
+
+        assert_eq!(account_metas.len(), amm.get_accounts_len());
+        assert_eq!(account_metas[1].pubkey, testing_env.market);
+        assert_eq!(account_metas[10], AccountMeta::new_readonly(user, true));
+        assert_eq!(
+            swap_and_account_metas.swap,
+            Swap::TokenMill { side: Side::Bid }
+        );
+    }
+}
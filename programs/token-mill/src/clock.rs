@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Time of the first slot of simulation builds, see `get_clock`.
+#[cfg(feature = "simulation")]
+pub const SIMULATION_GENESIS_TIMESTAMP: i64 = 1_700_000_000;
+#[cfg(feature = "simulation")]
+pub const SIMULATION_SLOT_DURATION_MS: i64 = 400;
+
+/// Clock read by the instructions of the program.
+/// With the `simulation` feature, the unix timestamp is derived from the slot instead of the
+/// validator wall clock, so local forks and CI replay time dependent logic deterministically,
+/// injecting time by warping slots.
+pub fn get_clock() -> Result<Clock> {
+    let clock = Clock::get()?;
+
+    #[cfg(feature = "simulation")]
+    let clock = Clock {
+        unix_timestamp: SIMULATION_GENESIS_TIMESTAMP
+            + clock.slot as i64 * SIMULATION_SLOT_DURATION_MS / 1_000,
+        ..clock
+    };
+
+    Ok(clock)
+}
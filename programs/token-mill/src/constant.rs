@@ -4,6 +4,32 @@ pub const MAX_TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1e9 * 1e6
 pub const MAX_PRICE: u64 = 1_000_000_000_000_000_000; // 1e18
 pub const MILL_TOKEN_DECIMALS: u8 = 6;
 pub const BASE_PRECISION: u64 = 1_000_000; // 1e6
+pub const USD_PRECISION: u64 = 1_000_000; // 1e6
 pub const SCALE: u128 = 10_000_000_000; // 1e10
 pub const STAKING_SCALE: u128 = 1_000_000_000_000_000_000; // 1e18
 pub const MAX_BPS: u64 = 10_000;
+pub const MAX_REFERRAL_CODE_FEE_SHARE: u64 = 5_000; // 50% of the creator fee
+pub const MAX_HOLDER_REWARDS_FEE_BPS: u64 = 1_000; // 10% of the sell proceeds
+pub const MAX_LAUNCH_FEE_BPS: u64 = 5_000; // 50% of the quote amount of buys
+pub const MAX_SWAP_FEE_BPS: u64 = 1_000; // 10% of the quote amount of swaps
+pub const MAX_LAUNCH_FEE_SLOTS: u64 = 9_000; // ~1 hour
+pub const MAX_LAUNCH_WINDOW_DURATION: i64 = 86_400; // 1 day
+pub const MAX_DENYLIST_LENGTH: usize = 200;
+pub const MAX_SCHEDULED_UPDATES: usize = 4;
+pub const GRADUATION_MILESTONE_BPS: u16 = 500; // 5% of the total supply
+pub const MAX_MARKETS_PER_BATCH: usize = 4;
+pub const MAX_REFERRAL_TIERS: usize = 8;
+pub const MAX_VESTING_TEMPLATES: usize = 8;
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+pub const REVENUE_EPOCH_DURATION: i64 = 604_800; // 1 week
+pub const VOLUME_WINDOW_DURATION: i64 = 86_400; // 1 day
+pub const VOLATILITY_EWMA_ALPHA_BPS: u64 = 2_000; // Weight of the latest daily price change
+pub const STATS_KEEPER_REWARD: u64 = 10_000; // Lamports per rollover
+pub const MARKET_CREATION_WINDOW_DURATION: i64 = 86_400; // 1 day
+pub const SECONDS_PER_YEAR: i64 = 31_536_000; // 365 days
+pub const MAX_CREATOR_DRAW_BPS: u16 = 500; // 5% of the raised quote
+pub const MAX_CONFIG_CHANGE_DELAY: i64 = 2_592_000; // 30 days
+pub const MAX_PROTOCOL_FEE_RECIPIENTS: usize = 8;
+pub const MAX_SWEEP_TIP_BPS: u16 = 100; // 1% of the swept fees
+pub const DYNAMIC_FEE_WINDOW: i64 = 300; // 5 minutes
+pub const DYNAMIC_FEE_MAX_VOLATILITY_BPS: u64 = 2_000; // 20% away from the twap
@@ -25,4 +25,114 @@ pub enum TokenMillError {
     InvalidStakePosition,
     InvalidVestingDuration,
     InvalidVestingStartTime,
+    CirculatingSupplyNotZero,
+    SecondaryMarketsDisabled,
+    InvalidCurveParameters,
+    InvalidEpoch,
+    EpochNotOver,
+    InvalidPolicyProgram,
+    BuybackFloorExceeded,
+    MarketPaused,
+    InvalidPauseDuration,
+    ReentrancyDetected,
+    DisallowedCallPattern,
+    ReferralVolumeNotMet,
+    InsufficientGasVaultBalance,
+    InvalidSwapSizeLimit,
+    SwapSizeExceeded,
+    InsufficientCreatorFees,
+    InvalidMerkleProof,
+    InsufficientFeeBalance,
+    ClaimBelowMinimum,
+    InvalidDenylist,
+    WalletDenied,
+    WalletAlreadyDenied,
+    WalletNotDenied,
+    DenylistFull,
+    InvalidBatchSize,
+    InvalidReferralTiers,
+    InvalidPriceChunk,
+    MissingTokenAccount,
+    InvalidVestingTemplate,
+    VestingTemplatesFull,
+    InvalidPriceBounds,
+    TotalRaiseTooLow,
+    TotalRaiseTooHigh,
+    SpreadTooNarrow,
+    SpreadTooWide,
+    LimitPriceNotReached,
+    InvalidRebateVault,
+    RebateThresholdNotMet,
+    MarketMigrated,
+    NothingToMigrate,
+    InvalidMigrationProgram,
+    GraduationThresholdNotMet,
+    InvalidGraduationThresholds,
+    InvalidPriceTail,
+    MarketNotMigrated,
+    MarketNotEmpty,
+    InvalidRentRecipient,
+    InvalidHolderRewardsFee,
+    InvalidTokenProgram,
+    MissingMetadataAccount,
+    Expired,
+    InvalidCompressionProgram,
+    InvalidSwapReceiptTree,
+    InvalidLaunchFee,
+    InvalidCreatorActivity,
+    MarketCreationLimitExceeded,
+    InvalidWalletBuyLimit,
+    InvalidLaunchPurchase,
+    WalletBuyLimitExceeded,
+    InvalidQuoteTokenMigration,
+    InvalidConversionRate,
+    MarketAlreadyTraded,
+    InvalidTwapWindow,
+    InvalidMultisig,
+    InsufficientMultisigSigners,
+    InvalidPriceFeed,
+    StalePrice,
+    PriceConfidenceTooWide,
+    RolloverTooEarly,
+    PricesNotSet,
+    SupplyLocked,
+    InvalidEmissionSchedule,
+    StakingFrozen,
+    InvalidReferralCode,
+    ConversionPriceExceeded,
+    InvalidScheduledUpdate,
+    ScheduledUpdatesFull,
+    InvalidFeeHookProgram,
+    CreationBondLocked,
+    InvalidProtocolFeeRecipient,
+    InvalidCreatorDraw,
+    CreatorDrawExceeded,
+    CreatorDrawLocked,
+    InsufficientCurveProceeds,
+    InvalidConfigChange,
+    ConfigChangeLocked,
+    ConfigChangeTimelocked,
+    InvalidProtocolFeeSplit,
+    InvalidSweepTip,
+    MarketUpToDate,
+    InvalidPauseState,
+    CreatorPauseDisabled,
+    InvalidGraduationHookProgram,
+    InvalidSwapFee,
+    MissingMarketOracle,
+    WalletHoldingCapExceeded,
+    InvalidWalletHoldingCap,
+    MarketAlreadyOpen,
+    BuyQueueOpen,
+    BuyQueueNotOpen,
+    InvalidFillSequence,
+    PendingQueuedBuyOrders,
+    InvalidLockTier,
+    StakeLocked,
+    StakingUpToDate,
+    InvalidWalletHoldingAccount,
+    BuyQueueFull,
+    QueuedBuyOrderTooSmall,
+    BuyQueueClosed,
+    ConfigUpToDate,
 }
@@ -2,7 +2,13 @@ use anchor_lang::event;
 use anchor_lang::prelude::*;
 
 use crate::constant::PRICES_LENGTH;
+use crate::manager::curve_manager::CurveParameters;
 use crate::manager::swap_manager::SwapType;
+use crate::state::{
+    ConfigChange, CreationBond, CreatorDraw, MarketPauser, MigrationTarget, PriceBounds,
+    ProtocolFeeShare, ReferralSettlement, ReferralTier, ScheduledUpdateKind, StakingBoostSource,
+    TraderRebateParams, UsdGraduation, VestingTemplate, WalletBuyLimit,
+};
 use crate::QuoteTokenBadgeStatus;
 
 #[event]
@@ -38,6 +44,23 @@ pub struct TokenMillMarketCreationEvent {
     pub referral_fee_share: u16,
     pub creator_fee_share: u16,
     pub staking_fee_share: u16,
+    pub wallet_buy_limit: Option<WalletBuyLimit>,
+    pub swap_fee_bps: u16,
+}
+
+#[event]
+pub struct TokenMillSecondaryMarketCreationEvent {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub primary_market: Pubkey,
+    pub creator: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub total_supply: u64,
+    pub protocol_fee_share: u16,
+    pub referral_fee_share: u16,
+    pub creator_fee_share: u16,
+    pub staking_fee_share: u16,
 }
 
 #[event]
@@ -59,6 +82,9 @@ pub struct TokenMillSwapEvent {
     pub staking_fee: u64,
     pub protocol_fee: u64,
     pub referral_fee: u64,
+    pub quote_refund: u64,
+    pub trader_rebate_fee: u64,
+    pub launch_fee: u64,
 }
 
 #[event]
@@ -81,6 +107,18 @@ pub struct TokenMillProtocolFeeRecipientUpdateEvent {
     pub new_protocol_fee_recipient: Pubkey,
 }
 
+#[event]
+pub struct TokenMillSecondaryMarketPolicyUpdateEvent {
+    pub config: Pubkey,
+    pub secondary_markets_enabled: bool,
+}
+
+#[event]
+pub struct TokenMillPolicyProgramUpdateEvent {
+    pub config: Pubkey,
+    pub new_policy_program: Option<Pubkey>,
+}
+
 #[event]
 pub struct TokenMillCreatorUpdateEvent {
     pub market: Pubkey,
@@ -108,11 +146,21 @@ pub struct TokenMillStakingWithdrawalEvent {
     pub amount: u64,
 }
 
+#[event]
+pub struct TokenMillStakeTransferEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub stake_position: Pubkey,
+    pub destination_stake_position: Pubkey,
+    pub amount: u64,
+}
+
 #[event]
 pub struct TokenMillReferralFeeClaimEvent {
     pub referrer: Pubkey,
     pub quote_token_mint: Pubkey,
     pub fees_distributed: u64,
+    pub tier_fee_share: u16,
 }
 
 #[event]
@@ -138,3 +186,831 @@ pub struct TokenMillVestingPlanReleaseEvent {
     pub vesting_plan: Pubkey,
     pub amount_released: u64,
 }
+
+#[event]
+pub struct TokenMillRevenueReportFinalizationEvent {
+    pub config: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub epoch: u64,
+    pub protocol_fees: u64,
+    pub creator_fees: u64,
+    pub staking_fees: u64,
+    pub referral_fees: u64,
+}
+
+#[event]
+pub struct TokenMillBuybackOrderPlacementEvent {
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub price_floor: u64,
+    pub fees_deposited: u64,
+}
+
+#[event]
+pub struct TokenMillBuybackExecutionEvent {
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct TokenMillBuybackOrderCancellationEvent {
+    pub market: Pubkey,
+    pub buyback_order: Pubkey,
+    pub base_amount_withdrawn: u64,
+    pub quote_amount_withdrawn: u64,
+}
+
+#[event]
+pub struct TokenMillMaxPauseDurationUpdateEvent {
+    pub config: Pubkey,
+    pub new_max_pause_duration: u64,
+}
+
+#[event]
+pub struct TokenMillMarketPauseEvent {
+    pub market: Pubkey,
+    pub paused_until_slot: u64,
+}
+
+#[event]
+pub struct TokenMillDefaultCurveUpdateEvent {
+    pub config: Pubkey,
+    pub new_default_curve: Option<CurveParameters>,
+}
+
+#[event]
+pub struct TokenMillMinReferredVolumeUpdateEvent {
+    pub market: Pubkey,
+    pub new_min_referred_volume: u64,
+}
+
+#[event]
+pub struct TokenMillMarketReferralFeeClaimEvent {
+    pub market: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub referred_volume: u64,
+    pub fees_distributed: u64,
+}
+
+#[event]
+pub struct TokenMillGasVaultDepositEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillGasVaultWithdrawalEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillSponsoredAccountCreationEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub token_account: Pubkey,
+    pub lamports: u64,
+}
+
+#[event]
+pub struct TokenMillClaimDelegateUpdateEvent {
+    pub stake_position: Pubkey,
+    pub user: Pubkey,
+    pub delegate: Option<Pubkey>,
+}
+
+#[event]
+pub struct TokenMillMaxSwapSizeUpdateEvent {
+    pub config: Pubkey,
+    pub new_max_swap_size_bps: u16,
+}
+
+#[event]
+pub struct TokenMillMarketMaxSwapSizeUpdateEvent {
+    pub market: Pubkey,
+    pub new_max_swap_size_bps: u16,
+}
+
+#[event]
+pub struct TokenMillQuoteAirdropCreationEvent {
+    pub market: Pubkey,
+    pub airdrop: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct TokenMillQuoteAirdropClaimEvent {
+    pub airdrop: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillQuoteAirdropClosureEvent {
+    pub market: Pubkey,
+    pub airdrop: Pubkey,
+    pub amount_returned: u64,
+}
+
+#[event]
+pub struct TokenMillPriceFeedCreationEvent {
+    pub market: Pubkey,
+    pub price_feed: Pubkey,
+}
+
+#[event]
+pub struct TokenMillPriceFeedUpdateEvent {
+    pub price_feed: Pubkey,
+    pub price: i64,
+    pub conf: u64,
+    pub publish_slot: u64,
+}
+
+#[event]
+pub struct TokenMillMinClaimAmountUpdateEvent {
+    pub market: Pubkey,
+    pub new_min_claim_amount: u64,
+}
+
+#[event]
+pub struct TokenMillAutoClaimUpdateEvent {
+    pub stake_position: Pubkey,
+    pub user: Pubkey,
+    pub auto_claim: bool,
+}
+
+#[event]
+pub struct TokenMillMarketReferralCodeCreationEvent {
+    pub market: Pubkey,
+    pub referral_code: Pubkey,
+    pub code: [u8; 32],
+    pub referral_token_account: Pubkey,
+    pub creator_fee_share: u16,
+}
+
+#[event]
+pub struct TokenMillMarketReferralCodeClosureEvent {
+    pub market: Pubkey,
+    pub referral_code: Pubkey,
+    pub referred_volume: u64,
+    pub referral_fees: u64,
+}
+
+#[event]
+pub struct TokenMillDenylistCreationEvent {
+    pub config: Pubkey,
+    pub denylist: Pubkey,
+}
+
+#[event]
+pub struct TokenMillDenylistUpdateEvent {
+    pub config: Pubkey,
+    pub wallet: Pubkey,
+    pub denied: bool,
+}
+
+#[event]
+pub struct TokenMillReferralTiersUpdateEvent {
+    pub config: Pubkey,
+    pub new_referral_tiers: Vec<ReferralTier>,
+}
+
+#[event]
+pub struct TokenMillFeeDefaultsAdoptionEvent {
+    pub market: Pubkey,
+    pub new_creator_fee_share: u16,
+    pub new_staking_fee_share: u16,
+    pub new_protocol_fee_share: u16,
+    pub new_referral_fee_share: u16,
+}
+
+#[event]
+pub struct TokenMillStakingBoostEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub source: StakingBoostSource,
+}
+
+#[event]
+pub struct TokenMillVestingTemplateCreationEvent {
+    pub config: Pubkey,
+    pub index: u8,
+    pub template: VestingTemplate,
+}
+
+#[event]
+pub struct TokenMillPriceBoundsUpdateEvent {
+    pub config: Pubkey,
+    pub new_price_bounds: PriceBounds,
+}
+
+#[event]
+pub struct TokenMillSplitSellEvent {
+    pub user: Pubkey,
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub limit_price: u64,
+    pub base_amount_sold: u64,
+    pub quote_amount: u64,
+    pub base_amount_resting: u64,
+}
+
+#[event]
+pub struct TokenMillSellOrderExecutionEvent {
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub owner: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct TokenMillSellOrderCancellationEvent {
+    pub market: Pubkey,
+    pub sell_order: Pubkey,
+    pub owner: Pubkey,
+    pub base_amount_withdrawn: u64,
+}
+
+#[event]
+pub struct TokenMillTraderRebateParamsUpdateEvent {
+    pub config: Pubkey,
+    pub new_trader_rebate_params: TraderRebateParams,
+}
+
+#[event]
+pub struct TokenMillTraderRebateClaimEvent {
+    pub revenue_report: Pubkey,
+    pub owner: Pubkey,
+    pub epoch: u64,
+    pub volume: u64,
+    pub rebate: u64,
+}
+
+#[event]
+pub struct TokenMillMarketGraduationEvent {
+    pub market: Pubkey,
+    pub migration_target: MigrationTarget,
+    pub pool: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub liquidity: u128,
+}
+
+#[event]
+pub struct TokenMillVerifierUpdateEvent {
+    pub config: Pubkey,
+    pub new_verifier: Option<Pubkey>,
+}
+
+#[event]
+pub struct TokenMillMarketVerificationEvent {
+    pub market: Pubkey,
+    pub verifier: Pubkey,
+    pub verified: bool,
+}
+
+#[event]
+pub struct TokenMillMigrationTargetUpdateEvent {
+    pub config: Pubkey,
+    pub new_migration_target: MigrationTarget,
+}
+
+#[event]
+pub struct TokenMillGraduationThresholdsUpdateEvent {
+    pub config: Pubkey,
+    pub new_graduation_quote_threshold: u64,
+    pub new_graduation_supply_bps: u16,
+}
+
+#[event]
+pub struct TokenMillPriceTailCompactionEvent {
+    pub market: Pubkey,
+    pub price_points: u8,
+    pub tail_supply_bps: u16,
+}
+
+#[event]
+pub struct TokenMillMarketClosureEvent {
+    pub market: Pubkey,
+    pub rent_recipient: Pubkey,
+}
+
+#[event]
+pub struct TokenMillRentRecipientUpdateEvent {
+    pub config: Pubkey,
+    pub new_rent_recipient: Pubkey,
+}
+
+#[event]
+pub struct TokenMillHolderRewardsFeeUpdateEvent {
+    pub market: Pubkey,
+    pub new_holder_rewards_fee_bps: u16,
+}
+
+#[event]
+pub struct TokenMillHolderRewardsPublicationEvent {
+    pub market: Pubkey,
+    pub airdrop: Pubkey,
+    pub merkle_root: [u8; 32],
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct TokenMillFeeReconciliationEvent {
+    pub market: Pubkey,
+    pub quote_vault_amount: u64,
+    pub curve_proceeds: u64,
+    pub pending_fees: u64,
+    pub deficit: u64,
+}
+
+#[event]
+pub struct TokenMillSwapReceiptTreeCreationEvent {
+    pub market: Pubkey,
+    pub swap_receipt_tree: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+#[event]
+pub struct TokenMillLaunchFeeUpdateEvent {
+    pub market: Pubkey,
+    pub launch_fee_bps: u16,
+    pub launch_fee_slots: u64,
+}
+
+#[event]
+pub struct TokenMillMaxDailyMarketsPerCreatorUpdateEvent {
+    pub config: Pubkey,
+    pub new_max_daily_markets_per_creator: u16,
+}
+
+#[event]
+pub struct TokenMillQuoteTokenMigrationEvent {
+    pub market: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub new_quote_token_mint: Pubkey,
+    pub conversion_rate: u64,
+    pub quote_amount: u64,
+    pub new_quote_amount: u64,
+}
+
+#[event]
+pub struct TokenMillMarketOracleCreationEvent {
+    pub market: Pubkey,
+    pub market_oracle: Pubkey,
+}
+
+#[event]
+pub struct TokenMillCreatorMultisigCreationEvent {
+    pub creator_multisig: Pubkey,
+    pub vault: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct TokenMillUsdGraduationUpdateEvent {
+    pub config: Pubkey,
+    pub usd_graduation: UsdGraduation,
+}
+
+#[event]
+pub struct TokenMillQuoteTokenPriceFeedUpdateEvent {
+    pub config: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub price_feed_id: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct TokenMillMarketStatsCreationEvent {
+    pub market: Pubkey,
+    pub market_stats: Pubkey,
+}
+
+#[event]
+pub struct TokenMillMarketStatsRolloverEvent {
+    pub market: Pubkey,
+    pub window: u64,
+    pub quote_volume: u64,
+    pub swap_fees: u64,
+    pub price: u64,
+    pub volatility_bps: u64,
+    pub keeper: Pubkey,
+    pub keeper_reward: u64,
+}
+
+#[event]
+pub struct TokenMillReferralSettlementUpdateEvent {
+    pub config: Pubkey,
+    pub referral_settlement: ReferralSettlement,
+}
+
+#[event]
+pub struct TokenMillOffChainReferralEvent {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub referrer: Pubkey,
+    pub quote_amount: u64,
+    pub referral_fee: u64,
+}
+
+#[event]
+pub struct TokenMillConfigOwnershipTransferProposalEvent {
+    pub config: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct TokenMillStakingCreationEvent {
+    pub market: Pubkey,
+    pub staking: Pubkey,
+}
+
+#[event]
+pub struct TokenMillStakePositionCreationEvent {
+    pub market: Pubkey,
+    pub stake_position: Pubkey,
+    pub user: Pubkey,
+}
+
+#[event]
+pub struct TokenMillReferralAccountCreationEvent {
+    pub config: Pubkey,
+    pub referral_account: Pubkey,
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct TokenMillMarketReferralAccrualCreationEvent {
+    pub market: Pubkey,
+    pub referral_accrual: Pubkey,
+    pub referral_token_account: Pubkey,
+}
+
+#[event]
+pub struct TokenMillRevenueReportCreationEvent {
+    pub config: Pubkey,
+    pub revenue_report: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub epoch: u64,
+}
+
+#[event]
+pub struct TokenMillTraderVolumeCreationEvent {
+    pub revenue_report: Pubkey,
+    pub trader_volume: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct TokenMillLaunchPurchaseCreationEvent {
+    pub market: Pubkey,
+    pub launch_purchase: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct TokenMillCreatorActivityCreationEvent {
+    pub config: Pubkey,
+    pub creator_activity: Pubkey,
+    pub creator: Pubkey,
+}
+
+#[event]
+pub struct TokenMillCreatorInstructionExecutionEvent {
+    pub creator_multisig: Pubkey,
+    pub vault: Pubkey,
+    pub program: Pubkey,
+    pub data: Vec<u8>,
+}
+
+#[event]
+pub struct TokenMillEmissionScheduleUpdateEvent {
+    pub market: Pubkey,
+    pub emission_initial_supply: u64,
+    pub emission_start: i64,
+    pub emission_duration: i64,
+}
+
+#[event]
+pub struct TokenMillStakingFreezeUpdateEvent {
+    pub market: Pubkey,
+    pub freeze_staking_on_migration: bool,
+}
+
+#[event]
+pub struct TokenMillReferralCodeCreationEvent {
+    pub config: Pubkey,
+    pub referral_code: Pubkey,
+    pub code: [u8; 32],
+    pub referrer: Pubkey,
+}
+
+#[event]
+pub struct TokenMillProtocolFeeConverterUpdateEvent {
+    pub config: Pubkey,
+    pub protocol_fee_converter: Pubkey,
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub max_price: u64,
+}
+
+#[event]
+pub struct TokenMillProtocolFeeConversionEvent {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub treasury: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct TokenMillMarketUpdateScheduleEvent {
+    pub market: Pubkey,
+    pub index: u8,
+    pub kind: ScheduledUpdateKind,
+    pub value: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TokenMillScheduledUpdateCancelEvent {
+    pub market: Pubkey,
+    pub index: u8,
+    pub kind: ScheduledUpdateKind,
+    pub value: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct TokenMillReferrerBindingEvent {
+    pub config: Pubkey,
+    pub trader: Pubkey,
+    pub referrer_binding: Pubkey,
+    pub referral_token_account: Pubkey,
+}
+
+#[event]
+pub struct TokenMillGraduationProgressEvent {
+    pub market: Pubkey,
+    pub milestone_bps: u16,
+    pub supply_sold_bps: u16,
+    pub quote_raised: u64,
+}
+
+#[event]
+pub struct TokenMillReferralParamsUpdateEvent {
+    pub config: Pubkey,
+    pub market: Option<Pubkey>,
+    pub referral_fee_share: u16,
+    pub referral_enabled: bool,
+}
+
+#[event]
+pub struct TokenMillFeeHookProgramUpdateEvent {
+    pub config: Pubkey,
+    pub new_fee_hook_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct TokenMillCreationBondUpdateEvent {
+    pub config: Pubkey,
+    pub creation_bond: CreationBond,
+}
+
+#[event]
+pub struct TokenMillCreationBondReleaseEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillCreationBondSlashEvent {
+    pub market: Pubkey,
+    pub protocol_fee_recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillCreatorDrawUpdateEvent {
+    pub config: Pubkey,
+    pub creator_draw: CreatorDraw,
+}
+
+#[event]
+pub struct TokenMillQuoteDrawRequestEvent {
+    pub market: Pubkey,
+    pub amount: u64,
+    pub unlock_time: i64,
+}
+
+#[event]
+pub struct TokenMillQuoteDrawEvent {
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_drawn: u64,
+}
+
+#[event]
+pub struct TokenMillConfigChangeQueueEvent {
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub change: ConfigChange,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct TokenMillConfigChangeExecutionEvent {
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub change: ConfigChange,
+}
+
+#[event]
+pub struct TokenMillConfigChangeCancellationEvent {
+    pub config: Pubkey,
+    pub pending_config_change: Pubkey,
+    pub change: ConfigChange,
+}
+
+#[event]
+pub struct TokenMillRiskParamsCreationEvent {
+    pub market: Pubkey,
+    pub risk_params: Pubkey,
+}
+
+#[event]
+pub struct TokenMillRiskParamsUpdateEvent {
+    pub risk_params: Pubkey,
+    pub max_swap_size_bps: u16,
+    pub paused_until_slot: u64,
+    pub price_conf_bps: u16,
+    pub migrated: bool,
+    pub update_slot: u64,
+}
+
+#[event]
+pub struct TokenMillProtocolFeeSplitUpdateEvent {
+    pub config: Pubkey,
+    pub protocol_fee_split: Pubkey,
+    pub recipients: Vec<ProtocolFeeShare>,
+}
+
+#[event]
+pub struct TokenMillProtocolFeeDistributionEvent {
+    pub config: Pubkey,
+    pub protocol_fee_split: Pubkey,
+    pub amounts: Vec<u64>,
+}
+
+#[event]
+pub struct TokenMillSweepTipUpdateEvent {
+    pub config: Pubkey,
+    pub new_sweep_tip_bps: u16,
+}
+
+#[event]
+pub struct TokenMillFeeSweepEvent {
+    pub market: Pubkey,
+    pub creator_fees: u64,
+    pub staking_fees: u64,
+    pub keeper_quote_token_account: Option<Pubkey>,
+    pub tip: u64,
+}
+
+#[event]
+pub struct TokenMillMarketBackfillEvent {
+    pub market: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct TokenMillConfigBackfillEvent {
+    pub config: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct TokenMillCreatorPauseUpdateEvent {
+    pub config: Pubkey,
+    pub creator_pause_enabled: bool,
+}
+
+#[event]
+pub struct TokenMillMarketStatusEvent {
+    pub market: Pubkey,
+    pub pauser: MarketPauser,
+    pub paused: bool,
+}
+
+#[event]
+pub struct TokenMillGraduationHookProgramUpdateEvent {
+    pub config: Pubkey,
+    pub new_graduation_hook_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct TokenMillSwapFeeBoundsUpdateEvent {
+    pub config: Pubkey,
+    pub new_min_swap_fee_bps: u16,
+    pub new_max_swap_fee_bps: u16,
+}
+
+#[event]
+pub struct TokenMillDynamicFeeUpdateEvent {
+    pub market: Pubkey,
+    pub dynamic_fee: bool,
+}
+
+#[event]
+pub struct TokenMillFeeExemptionEvent {
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+    pub exempt: bool,
+}
+
+#[event]
+pub struct TokenMillMaxWalletHoldingUpdateEvent {
+    pub market: Pubkey,
+    pub new_max_wallet_holding_bps: u16,
+}
+
+#[event]
+pub struct TokenMillBuyQueueCreationEvent {
+    pub market: Pubkey,
+    pub buy_queue: Pubkey,
+    pub max_quote_amount: u64,
+    pub open_slot: u64,
+    pub close_slot: u64,
+}
+
+#[event]
+pub struct TokenMillQueuedBuyOrderPlacementEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub quote_amount: u64,
+}
+
+#[event]
+pub struct TokenMillQueuedBuyOrderCancellationEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub quote_amount_withdrawn: u64,
+}
+
+#[event]
+pub struct TokenMillQueuedBuyOrderFillEvent {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub quote_amount_refunded: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+}
+
+#[event]
+pub struct TokenMillBuyQueueOpenEvent {
+    pub market: Pubkey,
+    pub total_quote_queued: u64,
+    pub unfilled_order_count: u64,
+}
+
+#[event]
+pub struct TokenMillStakeLockEvent {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub stake_position: Pubkey,
+    pub lock_tier: u8,
+    pub unlock_timestamp: i64,
+}
+
+#[event]
+pub struct TokenMillStakeBackfillEvent {
+    pub market: Pubkey,
+    pub stake_position: Pubkey,
+    pub shares: u64,
+    pub total_shares: u64,
+}
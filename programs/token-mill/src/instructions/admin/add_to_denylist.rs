@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillDenylistUpdateEvent,
+    state::{Denylist, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DenylistUpdate<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Account<'info, Denylist>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Goes through `ConfigChange::DenylistUpdate` once a config change delay is set.
+pub fn handler(ctx: Context<DenylistUpdate>, wallet: Pubkey) -> Result<()> {
+    ctx.accounts.config.check_no_config_change_delay()?;
+
+    ctx.accounts.denylist.add(wallet)?;
+
+    emit_cpi!(TokenMillDenylistUpdateEvent {
+        config: ctx.accounts.config.key(),
+        wallet,
+        denied: true,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Denylist;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, AddToDenylistAction, ClaimCreatorFeesAction, CreateDenylistAction,
+            SwapAction, TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, AddToDenylistAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateDenylistAction::new()])
+            .unwrap();
+
+        let action = AddToDenylistAction::new(make_address("bob"));
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn add_to_denylist() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let denylist = testing_env
+            .svm
+            .get_parsed_account::<Denylist>(&action.denylist);
+
+        assert_eq!(denylist.wallets, vec![make_address("bob")]);
+    }
+
+    #[test]
+    fn add_to_denylist_twice() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action, &action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletAlreadyDenied);
+    }
+
+    #[test]
+    fn swap_with_denied_wallet() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000,
+            0,
+            None,
+        )
+        .with_signer(bob);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidDenylist);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&swap_action.with_denylist()]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletDenied);
+    }
+
+    #[test]
+    fn claim_creator_fees_with_denied_wallet() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&AddToDenylistAction::new(make_address("alice"))])
+            .unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            0,
+            None,
+        )
+        .with_signer(bob);
+
+        testing_env
+            .svm
+            .execute_actions(&[&swap_action.with_denylist()])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let claim_action = ClaimCreatorFeesAction::new(&testing_env).with_denylist();
+
+        let result = testing_env.svm.execute_actions(&[&claim_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletDenied);
+    }
+
+    #[test]
+    fn add_to_denylist_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
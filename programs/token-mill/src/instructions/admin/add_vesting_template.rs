@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    constant::MAX_VESTING_TEMPLATES,
+    errors::TokenMillError,
+    events::TokenMillVestingTemplateCreationEvent,
+    state::{VestingPlan, VestingTemplate},
+};
+
+pub fn handler(ctx: Context<ConfigUpdate>, template: VestingTemplate) -> Result<()> {
+    VestingPlan::check_durations(template.vesting_duration, template.cliff_duration)?;
+
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        config.vesting_templates.len() < MAX_VESTING_TEMPLATES,
+        TokenMillError::VestingTemplatesFull
+    );
+
+    config.vesting_templates.push(template);
+
+    emit_cpi!(TokenMillVestingTemplateCreationEvent {
+        config: ctx.accounts.config.key(),
+        index: (ctx.accounts.config.vesting_templates.len() - 1) as u8,
+        template,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_VESTING_TEMPLATES, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, AddVestingTemplateAction, TokenMillEnv},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, AddVestingTemplateAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = AddVestingTemplateAction::default();
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn add_vesting_template() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.vesting_templates, vec![action.template]);
+    }
+
+    #[test]
+    fn add_vesting_template_with_invalid_durations() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.template.cliff_duration = action.template.vesting_duration;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidVestingDuration);
+    }
+
+    #[test]
+    fn add_vesting_template_when_full() {
+        let (mut testing_env, mut action) = setup_env();
+
+        for i in 0..MAX_VESTING_TEMPLATES {
+            action.template.name[0] = i as u8;
+
+            testing_env.svm.execute_actions(&[&action]).unwrap();
+        }
+
+        action.template.name[0] = MAX_VESTING_TEMPLATES as u8;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::VestingTemplatesFull);
+    }
+
+    #[test]
+    fn add_vesting_template_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillConfigChangeCancellationEvent,
+    state::{PendingConfigChange, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        close = authority
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelConfigChange>) -> Result<()> {
+    emit_cpi!(TokenMillConfigChangeCancellationEvent {
+        config: ctx.accounts.config.key(),
+        pending_config_change: ctx.accounts.pending_config_change.key(),
+        change: ctx.accounts.pending_config_change.change,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ConfigChange;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CancelConfigChangeAction, QueueConfigChangeAction, TokenMillEnv,
+        },
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, CancelConfigChangeAction) {
+        let mut testing_env = TokenMillEnv::new();
+
+        let change = ConfigChange::ProtocolFeeRecipient {
+            protocol_fee_recipient: make_address("dave"),
+        };
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(change)])
+            .unwrap();
+
+        let action = CancelConfigChangeAction::new(change.kind());
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn cancel_config_change() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert!(!testing_env
+            .svm
+            .account_exists(&action.pending_config_change));
+    }
+
+    #[test]
+    fn cancel_config_change_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
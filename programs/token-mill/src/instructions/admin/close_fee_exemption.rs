@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillFeeExemptionEvent,
+    state::{FeeExemption, Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseFeeExemption<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        close = authority
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Revokes a fee exemption, the swaps of the wallet pay the swap fee again.
+pub fn handler(ctx: Context<CloseFeeExemption>) -> Result<()> {
+    emit_cpi!(TokenMillFeeExemptionEvent {
+        market: ctx.accounts.market.key(),
+        wallet: ctx.accounts.fee_exemption.wallet,
+        exempt: false,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CloseFeeExemptionAction, CreateFeeExemptionAction, TokenMillEnv,
+        },
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn close_fee_exemption() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let create_action = CreateFeeExemptionAction::new(&testing_env, make_address("bob"));
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let action = CloseFeeExemptionAction::new(&testing_env, make_address("bob"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert!(!testing_env.svm.account_exists(&action.fee_exemption));
+    }
+
+    #[test]
+    fn close_fee_exemption_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateFeeExemptionAction::new(
+                &testing_env,
+                make_address("bob"),
+            )])
+            .unwrap();
+
+        let mut action = CloseFeeExemptionAction::new(&testing_env, make_address("bob"));
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
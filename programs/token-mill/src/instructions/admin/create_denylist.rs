@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillDenylistCreationEvent,
+    state::{Denylist, TokenMillConfig, DENYLIST_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateDenylist<'info> {
+    #[account(mut, has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [DENYLIST_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + Denylist::INIT_SPACE
+    )]
+    pub denylist: Account<'info, Denylist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the denylist of the config and starts enforcing it on swaps and claims.
+pub fn handler(ctx: Context<CreateDenylist>) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+
+    ctx.accounts
+        .denylist
+        .initialize(ctx.bumps.denylist, config_key)?;
+
+    ctx.accounts.config.denylist_enabled = true;
+
+    emit_cpi!(TokenMillDenylistCreationEvent {
+        config: config_key,
+        denylist: ctx.accounts.denylist.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Denylist, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateDenylistAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn create_denylist() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = CreateDenylistAction::new();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let denylist = testing_env
+            .svm
+            .get_parsed_account::<Denylist>(&action.denylist);
+
+        assert_eq!(denylist.config, make_address("config"));
+        assert!(denylist.wallets.is_empty());
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert!(config.denylist_enabled);
+    }
+
+    #[test]
+    fn create_denylist_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = CreateDenylistAction::new();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillFeeExemptionEvent,
+    state::{FeeExemption, Market, TokenMillConfig, FEE_EXEMPTION_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct CreateFeeExemption<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [FEE_EXEMPTION_PDA_SEED.as_bytes(), market.key().as_ref(), wallet.as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + FeeExemption::INIT_SPACE
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Exempts the swaps of `wallet` on the market from the swap fee, for designated market makers.
+pub fn handler(ctx: Context<CreateFeeExemption>, wallet: Pubkey) -> Result<()> {
+    let market = ctx.accounts.market.key();
+
+    ctx.accounts
+        .fee_exemption
+        .initialize(ctx.bumps.fee_exemption, market, wallet)?;
+
+    emit_cpi!(TokenMillFeeExemptionEvent {
+        market,
+        wallet,
+        exempt: true,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FeeExemption, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateFeeExemptionAction, SwapAction, TokenMillEnv},
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn buy(testing_env: &mut TokenMillEnv, fee_exempt: bool) -> u64 {
+        testing_env.svm.change_payer("bob");
+
+        let mut action = SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        if fee_exempt {
+            action = action.with_fee_exemption();
+        }
+
+        let quote_mint = testing_env.quote_token_mint.unwrap();
+        let balance_before = testing_env
+            .svm
+            .get_balance(&quote_mint, &make_address("bob"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        balance_before
+            - testing_env
+                .svm
+                .get_balance(&quote_mint, &make_address("bob"))
+    }
+
+    #[test]
+    fn create_fee_exemption() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let action = CreateFeeExemptionAction::new(&testing_env, make_address("bob"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let fee_exemption = testing_env
+            .svm
+            .get_parsed_account::<FeeExemption>(&action.fee_exemption);
+
+        assert_eq!(fee_exemption.market, testing_env.market);
+        assert_eq!(fee_exemption.wallet, make_address("bob"));
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let pending_creator_fees = market.fees.pending_creator_fees;
+        let pending_staking_fees = market.fees.pending_staking_fees;
+
+        let exempt_quote_paid = buy(&mut testing_env, true);
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.fees.pending_creator_fees, pending_creator_fees);
+        assert_eq!(market.fees.pending_staking_fees, pending_staking_fees);
+
+        // Further up the curve, at the ask price
+        let quote_paid = buy(&mut testing_env, false);
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert!(quote_paid > exempt_quote_paid);
+        assert!(market.fees.pending_creator_fees > pending_creator_fees);
+    }
+
+    #[test]
+    fn swap_with_fee_exemption_of_other_wallet() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateFeeExemptionAction::new(
+                &testing_env,
+                make_address("alice"),
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let mut action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        // Derived for the user of the swap, the exemption of alice doesn't match
+        action.fee_exemption =
+            CreateFeeExemptionAction::new(&testing_env, make_address("alice")).fee_exemption;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn create_fee_exemption_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = CreateFeeExemptionAction::new(&testing_env, make_address("bob"));
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
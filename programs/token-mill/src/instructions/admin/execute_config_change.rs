@@ -0,0 +1,439 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillConfigChangeExecutionEvent,
+    state::{
+        ConfigChange, Denylist, PendingConfigChange, ProtocolFeeSplit, TokenMillConfig,
+        PROTOCOL_FEE_SPLIT_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(mut, has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        close = authority
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Required by `ConfigChange::ProtocolFeeSplit`, created if needed
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProtocolFeeSplit::INIT_SPACE,
+        seeds = [PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_split: Option<Account<'info, ProtocolFeeSplit>>,
+
+    /// Required by `ConfigChange::DenylistUpdate`
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let pending_config_change = &ctx.accounts.pending_config_change;
+
+    pending_config_change.execute(&mut ctx.accounts.config, get_clock()?.unix_timestamp)?;
+
+    if let Some(recipients) = pending_config_change.change.get_protocol_fee_recipients() {
+        let protocol_fee_split = ctx
+            .accounts
+            .protocol_fee_split
+            .as_mut()
+            .ok_or(TokenMillError::InvalidProtocolFeeSplit)?;
+
+        if protocol_fee_split.config == Pubkey::default() {
+            protocol_fee_split.initialize(
+                ctx.bumps
+                    .protocol_fee_split
+                    .ok_or(TokenMillError::InvalidProtocolFeeSplit)?,
+                ctx.accounts.config.key(),
+            )?;
+        }
+
+        protocol_fee_split.set_recipients(recipients)?;
+    }
+
+    if let ConfigChange::DenylistUpdate { wallet, denied } = pending_config_change.change {
+        let denylist = ctx
+            .accounts
+            .denylist
+            .as_mut()
+            .ok_or(TokenMillError::InvalidDenylist)?;
+
+        if denied {
+            denylist.add(wallet)?;
+        } else {
+            denylist.remove(wallet)?;
+        }
+    }
+
+    emit_cpi!(TokenMillConfigChangeExecutionEvent {
+        config: ctx.accounts.config.key(),
+        pending_config_change: pending_config_change.key(),
+        change: pending_config_change.change,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ConfigChange, Denylist, MigrationTarget, ProtocolFeeShare, ProtocolFeeSplit,
+        TokenMillConfig, UsdGraduation,
+    };
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, AddToDenylistAction, CreateDenylistAction, ExecuteConfigChangeAction,
+            QueueConfigChangeAction, SetProtocolFeeSplitAction, TokenMillEnv,
+            UpdateMigrationTargetAction, UpdateProtocolFeeRecipientAction,
+            UpdateReferralParamsAction, UpdateUsdGraduationAction,
+        },
+        make_address, TokenMillError,
+    };
+
+    const CONFIG_CHANGE_DELAY: i64 = 86_400;
+
+    fn new_change() -> ConfigChange {
+        ConfigChange::ProtocolFeeRecipient {
+            protocol_fee_recipient: make_address("dave"),
+        }
+    }
+
+    /// Sets a delay through the timelock itself, executable right away as no delay is set yet.
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::new();
+
+        let change = ConfigChange::ConfigChangeDelay {
+            config_change_delay: CONFIG_CHANGE_DELAY,
+        };
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &QueueConfigChangeAction::new(change),
+                &ExecuteConfigChangeAction::new(change.kind()),
+            ])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn execute_config_change() {
+        let mut testing_env = setup_env();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&make_address("config"));
+
+        assert_eq!(config.config_change_delay, CONFIG_CHANGE_DELAY);
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(new_change())])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        let action = ExecuteConfigChangeAction::new(new_change().kind());
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config.protocol_fee_recipient, make_address("dave"));
+        assert!(!testing_env
+            .svm
+            .account_exists(&action.pending_config_change));
+    }
+
+    #[test]
+    fn execute_config_change_before_delay() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(new_change())])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY - 10);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&ExecuteConfigChangeAction::new(new_change().kind())]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigChangeLocked);
+    }
+
+    #[test]
+    fn execute_config_change_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(new_change())])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        let mut action = ExecuteConfigChangeAction::new(new_change().kind());
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+
+    #[test]
+    fn direct_update_with_config_change_delay() {
+        let mut testing_env = setup_env();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&UpdateProtocolFeeRecipientAction::new(make_address("dave"))]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigChangeTimelocked);
+    }
+
+    #[test]
+    fn execute_protocol_fee_split_change() {
+        let mut testing_env = setup_env();
+
+        let recipients = vec![
+            ProtocolFeeShare {
+                recipient: make_address("dave"),
+                weight_bps: 7_000,
+            },
+            ProtocolFeeShare {
+                recipient: make_address("carol"),
+                weight_bps: 3_000,
+            },
+        ];
+
+        // Redirecting the protocol fees directly is timelocked as well
+        let result = testing_env
+            .svm
+            .execute_actions(&[&SetProtocolFeeSplitAction::new(recipients.clone())]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigChangeTimelocked);
+
+        let change = ConfigChange::protocol_fee_split(&recipients).unwrap();
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(change)])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        // The split is required to apply the change
+        let result = testing_env
+            .svm
+            .execute_actions(&[&ExecuteConfigChangeAction::new(change.kind())]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidProtocolFeeSplit);
+
+        let action = ExecuteConfigChangeAction::new(change.kind()).with_protocol_fee_split();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_split = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeSplit>(&action.protocol_fee_split);
+
+        assert_eq!(protocol_fee_split.config, action.config);
+        assert_eq!(protocol_fee_split.recipients, recipients);
+    }
+
+    #[test]
+    fn execute_referral_params_change() {
+        let mut testing_env = setup_env();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&UpdateReferralParamsAction::new(2_000, false)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigChangeTimelocked);
+
+        let change = ConfigChange::ReferralParams {
+            referral_fee_share: 2_000,
+            referral_enabled: false,
+        };
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(change)])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        testing_env
+            .svm
+            .execute_actions(&[&ExecuteConfigChangeAction::new(change.kind())])
+            .unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&make_address("config"));
+
+        assert_eq!(config.referral_fee_share, 2_000);
+        assert!(!config.referral_enabled);
+    }
+
+    #[test]
+    fn execute_graduation_changes() {
+        let mut testing_env = setup_env();
+
+        let usd_graduation = UsdGraduation {
+            usd_threshold: 69_000_000_000,
+            max_price_staleness: 60,
+            max_price_conf_bps: 100,
+        };
+
+        for result in [
+            testing_env
+                .svm
+                .execute_actions(&[&UpdateUsdGraduationAction::new(usd_graduation)]),
+            testing_env
+                .svm
+                .execute_actions(&[&UpdateMigrationTargetAction::new(
+                    MigrationTarget::MeteoraDammV2,
+                )]),
+        ] {
+            assert!(result.is_err());
+
+            let error = tm_parse_error(result).unwrap();
+
+            assert_eq!(error, TokenMillError::ConfigChangeTimelocked);
+        }
+
+        let changes = [
+            ConfigChange::UsdGraduation { usd_graduation },
+            ConfigChange::MigrationTarget {
+                migration_target: MigrationTarget::MeteoraDammV2,
+            },
+        ];
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &QueueConfigChangeAction::new(changes[0]),
+                &QueueConfigChangeAction::new(changes[1]),
+            ])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &ExecuteConfigChangeAction::new(changes[0].kind()),
+                &ExecuteConfigChangeAction::new(changes[1].kind()),
+            ])
+            .unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&make_address("config"));
+
+        assert_eq!(config.usd_graduation, usd_graduation);
+        assert_eq!(config.migration_target, MigrationTarget::MeteoraDammV2);
+    }
+
+    #[test]
+    fn execute_denylist_update_change() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateDenylistAction::new()])
+            .unwrap();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&AddToDenylistAction::new(make_address("bob"))]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigChangeTimelocked);
+
+        let change = ConfigChange::DenylistUpdate {
+            wallet: make_address("bob"),
+            denied: true,
+        };
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(change)])
+            .unwrap();
+
+        testing_env.svm.warp(CONFIG_CHANGE_DELAY);
+
+        // The denylist is required to apply the change
+        let result = testing_env
+            .svm
+            .execute_actions(&[&ExecuteConfigChangeAction::new(change.kind())]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidDenylist);
+
+        let action = ExecuteConfigChangeAction::new(change.kind()).with_denylist();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let denylist = testing_env
+            .svm
+            .get_parsed_account::<Denylist>(&action.denylist);
+
+        assert_eq!(denylist.wallets, vec![make_address("bob")]);
+    }
+}
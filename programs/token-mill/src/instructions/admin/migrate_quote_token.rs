@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQuoteTokenMigrationEvent,
+    manager::token_manager::{get_transfer_inverse_fee, transfer_from_eoa, transfer_from_pda},
+    state::{
+        Market, MarketStaking, QuoteTokenBadge, QuoteTokenBadgeStatus, TokenMillConfig,
+        MARKET_STAKING_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QuoteTokenMigration<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: Staking of the market, which must be empty if it exists
+    #[account(
+        seeds = [MARKET_STAKING_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub staking: UncheckedAccount<'info>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub new_quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            new_quote_token_mint.key().as_ref(),
+        ],
+        bump = new_quote_token_badge.bump,
+        constraint = new_quote_token_badge.status == QuoteTokenBadgeStatus::Enabled @ TokenMillError::InvalidQuoteAssetBadge,
+    )]
+    pub new_quote_token_badge: Account<'info, QuoteTokenBadge>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = new_quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = new_quote_token_program
+    )]
+    pub market_new_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_token_mint,
+        token::authority = authority,
+        token::token_program = quote_token_program
+    )]
+    pub authority_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = new_quote_token_mint,
+        token::authority = authority,
+        token::token_program = new_quote_token_program
+    )]
+    pub authority_new_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub creator: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub new_quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves a live market to a new quote token, e.g. away from a depegged stablecoin, instead of
+/// stranding it. Co-signed by the config authority and the market creator, who agree on
+/// `conversion_rate`, the oracle price of the old quote token in new quote tokens scaled by
+/// `SCALE`. The authority swaps the whole quote vault at that rate, rounded up, and the curve
+/// prices are rescaled, see `Market::migrate_quote_token`.
+/// The market must be paused for the migration, its staking empty and its airdrops and held
+/// referral fees paid out, as stake rewards, airdrop leaves and referral accruals are accounted in
+/// the old quote token.
+pub fn handler(ctx: Context<QuoteTokenMigration>, conversion_rate: u64) -> Result<()> {
+    let staking = &ctx.accounts.staking;
+
+    if !staking.data_is_empty() {
+        let staking = MarketStaking::try_deserialize(&mut &staking.try_borrow_data()?[..])?;
+
+        require!(
+            staking.amount_staked == 0 && staking.total_amount_vested == 0,
+            TokenMillError::InvalidQuoteTokenMigration
+        );
+    }
+
+    let quote_amount = ctx.accounts.market_quote_token_ata.amount;
+
+    let (new_quote_amount, market_seeds) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        require!(
+            market.is_paused(get_clock()?.slot),
+            TokenMillError::InvalidQuoteTokenMigration
+        );
+
+        let new_quote_amount = market.migrate_quote_token(
+            ctx.accounts.new_quote_token_mint.key(),
+            ctx.accounts.new_quote_token_mint.decimals,
+            conversion_rate,
+            quote_amount,
+        )?;
+
+        (new_quote_amount, market.seeds())
+    };
+
+    // The vault must receive the full converted amount
+    let new_quote_transfer_fee =
+        get_transfer_inverse_fee(&ctx.accounts.new_quote_token_mint, new_quote_amount)?;
+
+    transfer_from_eoa(
+        &ctx.accounts.new_quote_token_mint,
+        &ctx.accounts.authority,
+        &ctx.accounts.authority_new_quote_token_account,
+        &ctx.accounts.market_new_quote_token_ata,
+        &ctx.accounts.new_quote_token_program,
+        new_quote_amount + new_quote_transfer_fee,
+    )?;
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.authority_quote_token_account,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+        &market_seeds.signer_seeds(),
+    )?;
+
+    emit_cpi!(TokenMillQuoteTokenMigrationEvent {
+        market: ctx.accounts.market.key(),
+        quote_token_mint: ctx.accounts.quote_token_mint.key(),
+        new_quote_token_mint: ctx.accounts.new_quote_token_mint.key(),
+        conversion_rate,
+        quote_amount,
+        new_quote_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateQuoteAssetBadgeAction, MigrateQuoteTokenAction, PauseUntilAction,
+            SwapAction, TokenMillEnv, UpdateMaxPauseDurationAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError, TokenType,
+    };
+
+    // The new quote token is worth 2 old quote tokens
+    const CONVERSION_RATE: u64 = 5_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, MigrateQuoteTokenAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                100_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        let new_quote_token_mint = testing_env.svm.create_token(TokenType::Token, 6).unwrap();
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateQuoteAssetBadgeAction::new(new_quote_token_mint),
+                &UpdateMaxPauseDurationAction::new(1_000),
+            ])
+            .unwrap();
+
+        let action = MigrateQuoteTokenAction::new(
+            &testing_env,
+            new_quote_token_mint,
+            TokenType::Token,
+            CONVERSION_RATE,
+        );
+
+        (testing_env, action)
+    }
+
+    fn pause(testing_env: &mut TokenMillEnv) {
+        let slot = testing_env.svm.get_slot() + 1_000;
+
+        testing_env
+            .svm
+            .execute_actions(&[&PauseUntilAction::new(testing_env, slot)])
+            .unwrap();
+    }
+
+    #[test]
+    fn migrate_quote_token() {
+        let (mut testing_env, action) = setup_env();
+
+        pause(&mut testing_env);
+
+        let quote_amount = testing_env
+            .svm
+            .get_balance(&action.quote_token_mint, &action.market);
+        let market_before = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.quote_token_mint, action.new_quote_token_mint);
+        assert_eq!(market.quote_token_decimals, 6);
+        assert_eq!(market.bid_prices[1], market_before.bid_prices[1] / 2);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.market),
+            0
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.new_quote_token_mint, &action.market),
+            quote_amount.div_ceil(2_000)
+        );
+    }
+
+    #[test]
+    fn migrate_quote_token_while_live() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidQuoteTokenMigration);
+    }
+
+    #[test]
+    fn migrate_quote_token_with_invalid_creator() {
+        let (mut testing_env, mut action) = setup_env();
+
+        pause(&mut testing_env);
+
+        action.creator = make_address("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -1,13 +1,65 @@
 pub mod accept_config_ownership;
+pub mod add_to_denylist;
+pub mod add_vesting_template;
+pub mod cancel_config_change;
+pub mod close_fee_exemption;
 pub mod create_config;
+pub mod create_denylist;
+pub mod create_fee_exemption;
 pub mod create_quote_asset_badge;
+pub mod execute_config_change;
+pub mod migrate_quote_token;
+pub mod pause_market;
+pub mod pause_until;
+pub mod queue_config_change;
+pub mod remove_from_denylist;
+pub mod set_market_verification;
+pub mod slash_creation_bond;
 pub mod transfer_config_ownership;
+pub mod unpause_market;
+pub mod update_creation_bond;
+pub mod update_creator_draw;
+pub mod update_creator_pause;
+pub mod update_default_curve;
 pub mod update_default_fee_shares;
+pub mod update_fee_hook_program;
+pub mod update_graduation_hook_program;
+pub mod update_graduation_thresholds;
+pub mod update_max_daily_markets_per_creator;
+pub mod update_max_pause_duration;
+pub mod update_max_swap_size;
+pub mod update_migration_target;
+pub mod update_policy_program;
+pub mod update_price_bounds;
 pub mod update_protocol_fee_recipient;
 pub mod update_quote_asset_badge;
+pub mod update_quote_token_price_feed;
+pub mod update_referral_params;
+pub mod update_referral_settlement;
+pub mod update_referral_tiers;
+pub mod update_rent_recipient;
+pub mod update_secondary_market_policy;
+pub mod update_swap_fee_bounds;
+pub mod update_sweep_tip;
+pub mod update_trader_rebate_params;
+pub mod update_usd_graduation;
+pub mod update_verifier;
 
 pub use accept_config_ownership::*;
+pub use add_to_denylist::*;
+pub use cancel_config_change::*;
+pub use close_fee_exemption::*;
 pub use create_config::*;
+pub use create_denylist::*;
+pub use create_fee_exemption::*;
 pub use create_quote_asset_badge::*;
+pub use execute_config_change::*;
+pub use migrate_quote_token::*;
+pub use pause_market::*;
+pub use pause_until::*;
+pub use queue_config_change::*;
+pub use set_market_verification::*;
+pub use slash_creation_bond::*;
 pub use transfer_config_ownership::*;
 pub use update_quote_asset_badge::*;
+pub use update_referral_params::*;
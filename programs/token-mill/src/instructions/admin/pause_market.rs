@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketStatusEvent,
+    state::{Market, MarketPauser, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketStatusUpdate<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    /// The config authority, or the market creator
+    pub signer: Signer<'info>,
+}
+
+impl MarketStatusUpdate<'_> {
+    pub fn get_pauser(&self) -> Result<MarketPauser> {
+        if self.signer.key() == self.config.authority {
+            Ok(MarketPauser::Authority)
+        } else if self.signer.key() == self.market.load()?.creator {
+            Ok(MarketPauser::Creator)
+        } else {
+            Err(TokenMillError::InvalidAuthority.into())
+        }
+    }
+}
+
+/// Halts swaps on the market until it is unpaused. Fee claims, unstaking and vesting claims
+/// aren't affected. Creators can only pause their market when the config allows it.
+pub fn handler(ctx: Context<MarketStatusUpdate>) -> Result<()> {
+    let pauser = ctx.accounts.get_pauser()?;
+
+    require!(
+        pauser == MarketPauser::Authority || ctx.accounts.config.creator_pause_enabled,
+        TokenMillError::CreatorPauseDisabled
+    );
+
+    ctx.accounts.market.load_mut()?.pause(pauser)?;
+
+    emit_cpi!(TokenMillMarketStatusEvent {
+        market: ctx.accounts.market.key(),
+        pauser,
+        paused: true,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimCreatorFeesAction, PauseMarketAction, SwapAction, TokenMillEnv,
+            UnpauseMarketAction, UpdateCreatorPauseAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn buy(testing_env: &mut TokenMillEnv) -> Result<(), TokenMillError> {
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.warp(1);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        if result.is_ok() {
+            Ok(())
+        } else {
+            Err(tm_parse_error(result).unwrap())
+        }
+    }
+
+    #[test]
+    fn pause_market() {
+        let mut testing_env = TokenMillEnv::default();
+
+        assert_eq!(buy(&mut testing_env), Ok(()));
+
+        testing_env.svm.change_payer("admin");
+
+        let action = PauseMarketAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert!(market.is_paused(testing_env.svm.get_slot()));
+        assert_eq!(buy(&mut testing_env), Err(TokenMillError::MarketPaused));
+
+        // Exits keep working while paused
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&ClaimCreatorFeesAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UnpauseMarketAction::new(&testing_env)])
+            .unwrap();
+
+        assert_eq!(buy(&mut testing_env), Ok(()));
+    }
+
+    #[test]
+    fn pause_market_as_creator() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = PauseMarketAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::CreatorPauseDisabled);
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateCreatorPauseAction::new(true)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(buy(&mut testing_env), Err(TokenMillError::MarketPaused));
+
+        // The authority takes over the pause, the creator can no longer lift it
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&PauseMarketAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&UnpauseMarketAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+
+    #[test]
+    fn pause_market_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("mallory");
+
+        let action = PauseMarketAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
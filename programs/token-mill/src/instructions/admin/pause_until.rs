@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillMarketPauseEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketPause<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Halts swaps on the market until `slot`, at most `max_pause_duration` slots from now.
+/// An ongoing pause can be shortened, or lifted with a past slot, but never extended.
+pub fn handler(ctx: Context<MarketPause>, slot: u64) -> Result<()> {
+    let current_slot = get_clock()?.slot;
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    require!(
+        slot <= current_slot + ctx.accounts.config.max_pause_duration
+            && (market.paused_until_slot <= current_slot || slot <= market.paused_until_slot),
+        TokenMillError::InvalidPauseDuration
+    );
+
+    market.paused_until_slot = slot;
+
+    emit_cpi!(TokenMillMarketPauseEvent {
+        market: ctx.accounts.market.key(),
+        paused_until_slot: slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, PauseUntilAction, SwapAction, TokenMillEnv,
+            UpdateMaxPauseDurationAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env() -> (TokenMillEnv, PauseUntilAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION)])
+            .unwrap();
+
+        let slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+        let action = PauseUntilAction::new(&testing_env, slot);
+
+        (testing_env, action)
+    }
+
+    fn buy(testing_env: &mut TokenMillEnv) -> Result<(), TokenMillError> {
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000,
+            u64::MAX,
+            None,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        if result.is_ok() {
+            Ok(())
+        } else {
+            Err(tm_parse_error(result).unwrap())
+        }
+    }
+
+    #[test]
+    fn pause_until() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.paused_until_slot, action.slot);
+        assert_eq!(buy(&mut testing_env), Err(TokenMillError::MarketPaused));
+
+        testing_env.svm.warp_to_slot(action.slot);
+
+        assert_eq!(buy(&mut testing_env), Ok(()));
+    }
+
+    #[test]
+    fn pause_until_and_resume_early() {
+        let (mut testing_env, mut action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        action.slot = 0;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+        assert_eq!(buy(&mut testing_env), Ok(()));
+    }
+
+    #[test]
+    fn pause_until_above_max_duration() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.slot += 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPauseDuration);
+    }
+
+    #[test]
+    fn pause_until_extending_ongoing_pause() {
+        let (mut testing_env, mut action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.warp_to_slot(testing_env.svm.get_slot() + 1);
+
+        action.slot += 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPauseDuration);
+    }
+
+    #[test]
+    fn pause_until_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
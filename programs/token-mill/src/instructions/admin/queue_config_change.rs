@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillConfigChangeQueueEvent,
+    state::{ConfigChange, PendingConfigChange, TokenMillConfig, PENDING_CONFIG_CHANGE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(change: ConfigChange)]
+pub struct QueueConfigChange<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            PENDING_CONFIG_CHANGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            &[change.kind()]
+        ],
+        bump,
+        payer = authority,
+        space = 8 + PendingConfigChange::INIT_SPACE
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Queues a sensitive change, executable with `execute_config_change` once the config change
+/// delay has elapsed.
+pub fn handler(ctx: Context<QueueConfigChange>, change: ConfigChange) -> Result<()> {
+    let config_key = ctx.accounts.config.key();
+    let executable_at = get_clock()?.unix_timestamp + ctx.accounts.config.config_change_delay;
+
+    ctx.accounts.pending_config_change.initialize(
+        ctx.bumps.pending_config_change,
+        config_key,
+        change,
+        executable_at,
+    )?;
+
+    emit_cpi!(TokenMillConfigChangeQueueEvent {
+        config: config_key,
+        pending_config_change: ctx.accounts.pending_config_change.key(),
+        change,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_BPS, ConfigChange, PendingConfigChange};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, QueueConfigChangeAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    fn new_change() -> ConfigChange {
+        ConfigChange::ProtocolFeeRecipient {
+            protocol_fee_recipient: make_address("dave"),
+        }
+    }
+
+    #[test]
+    fn queue_config_change() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = QueueConfigChangeAction::new(new_change());
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let pending_config_change = testing_env
+            .svm
+            .get_parsed_account::<PendingConfigChange>(&action.pending_config_change);
+
+        assert_eq!(pending_config_change.config, make_address("config"));
+        assert_eq!(pending_config_change.change, new_change());
+    }
+
+    #[test]
+    fn queue_config_change_twice() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(new_change())])
+            .unwrap();
+
+        testing_env.svm.warp(1);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&QueueConfigChangeAction::new(
+                ConfigChange::ProtocolFeeRecipient {
+                    protocol_fee_recipient: make_address("mallory"),
+                },
+            )]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn queue_invalid_config_change() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = QueueConfigChangeAction::new(ConfigChange::DefaultFeeShares {
+            default_protocol_fee_share: MAX_BPS as u16 + 1,
+            referral_fee_share: 0,
+        });
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+    }
+
+    #[test]
+    fn queue_config_change_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = QueueConfigChangeAction::new(new_change());
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+
+use super::DenylistUpdate;
+use crate::events::TokenMillDenylistUpdateEvent;
+
+/// Goes through `ConfigChange::DenylistUpdate` once a config change delay is set.
+pub fn handler(ctx: Context<DenylistUpdate>, wallet: Pubkey) -> Result<()> {
+    ctx.accounts.config.check_no_config_change_delay()?;
+
+    ctx.accounts.denylist.remove(wallet)?;
+
+    emit_cpi!(TokenMillDenylistUpdateEvent {
+        config: ctx.accounts.config.key(),
+        wallet,
+        denied: false,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Denylist;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, AddToDenylistAction, CreateDenylistAction, RemoveFromDenylistAction,
+            SwapAction, TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, RemoveFromDenylistAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateDenylistAction::new(),
+                &AddToDenylistAction::new(make_address("bob")),
+            ])
+            .unwrap();
+
+        let action = RemoveFromDenylistAction::new(make_address("bob"));
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn remove_from_denylist() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let denylist = testing_env
+            .svm
+            .get_parsed_account::<Denylist>(&action.denylist);
+
+        assert!(denylist.wallets.is_empty());
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000,
+            0,
+            None,
+        )
+        .with_signer(bob)
+        .with_denylist();
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn remove_from_denylist_not_denied() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.wallet = make_address("carol");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletNotDenied);
+    }
+
+    #[test]
+    fn remove_from_denylist_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketVerificationEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketVerification<'info> {
+    #[account(constraint = config.verifier == Some(verifier.key()) @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    pub verifier: Signer<'info>,
+}
+
+/// Attests, or revokes, that the market passed the off-chain checks of the verifier.
+pub fn handler(ctx: Context<MarketVerification>, verified: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.verified = u8::from(verified);
+
+    emit_cpi!(TokenMillMarketVerificationEvent {
+        market: ctx.accounts.market.key(),
+        verifier: ctx.accounts.verifier.key(),
+        verified,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, SetMarketVerificationAction, TokenMillEnv, UpdateVerifierAction,
+        },
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, SetMarketVerificationAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateVerifierAction::new(Some(make_address("carol")))])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let action = SetMarketVerificationAction::new(&testing_env, true);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn set_market_verification() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.is_verified());
+
+        action.verified = false;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(!market.is_verified());
+    }
+
+    #[test]
+    fn set_market_verification_without_verifier() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateVerifierAction::new(None)])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+
+    #[test]
+    fn set_market_verification_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
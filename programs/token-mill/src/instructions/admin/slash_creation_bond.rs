@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillCreationBondSlashEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SlashCreationBond<'info> {
+    #[account(
+        has_one = authority @ TokenMillError::InvalidAuthority,
+        has_one = protocol_fee_recipient @ TokenMillError::InvalidProtocolFeeRecipient
+    )]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: Checked against the protocol fee recipient of the config
+    #[account(mut)]
+    pub protocol_fee_recipient: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Sends the creation bond of a market impersonating another project to the protocol fee
+/// recipient.
+pub fn handler(ctx: Context<SlashCreationBond>) -> Result<()> {
+    let amount = ctx.accounts.market.load_mut()?.slash_creation_bond()?;
+
+    ctx.accounts.market.sub_lamports(amount)?;
+    ctx.accounts.protocol_fee_recipient.add_lamports(amount)?;
+
+    emit_cpi!(TokenMillCreationBondSlashEvent {
+        market: ctx.accounts.market.key(),
+        protocol_fee_recipient: ctx.accounts.protocol_fee_recipient.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CreationBond, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, SlashCreationBondAction, TokenMillEnv, UpdateCreationBondAction,
+        },
+        make_address, TokenMillError,
+    };
+
+    const CREATION_BOND: u64 = 1_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, SlashCreationBondAction) {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateCreationBondAction::new(CreationBond {
+                amount: CREATION_BOND,
+                release_epochs: 1,
+            })])
+            .unwrap();
+
+        let mut testing_env = testing_env
+            .with_default_quote_token_mint()
+            .with_default_market();
+
+        testing_env.svm.change_payer("admin");
+
+        let action = SlashCreationBondAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn slash_creation_bond() {
+        let (mut testing_env, action) = setup_env();
+
+        let recipient_lamports = testing_env
+            .svm
+            .get_account(&action.protocol_fee_recipient)
+            .lamports;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.creation_bond, 0);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_account(&action.protocol_fee_recipient)
+                .lamports,
+            recipient_lamports + CREATION_BOND
+        );
+
+        // Nothing left to slash
+        testing_env.svm.warp(1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+
+    #[test]
+    fn slash_creation_bond_with_invalid_recipient() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.protocol_fee_recipient = make_address("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidProtocolFeeRecipient);
+    }
+
+    #[test]
+    fn slash_creation_bond_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::{errors::TokenMillError, state::TokenMillConfig};
+use crate::{
+    errors::TokenMillError, events::TokenMillConfigOwnershipTransferProposalEvent,
+    state::TokenMillConfig,
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -16,6 +19,11 @@ pub fn handler(ctx: Context<ConfigUpdate>, new_authority: Option<Pubkey>) -> Res
 
     config.pending_authority = new_authority;
 
+    emit_cpi!(TokenMillConfigOwnershipTransferProposalEvent {
+        config: ctx.accounts.config.key(),
+        pending_authority: new_authority,
+    });
+
     Ok(())
 }
 
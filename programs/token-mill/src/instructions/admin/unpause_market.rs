@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use super::MarketStatusUpdate;
+use crate::events::TokenMillMarketStatusEvent;
+
+/// Lifts a pause of `pause_market`, the creator can only lift its own pause.
+pub fn handler(ctx: Context<MarketStatusUpdate>) -> Result<()> {
+    let pauser = ctx.accounts.get_pauser()?;
+
+    ctx.accounts.market.load_mut()?.unpause(pauser)?;
+
+    emit_cpi!(TokenMillMarketStatusEvent {
+        market: ctx.accounts.market.key(),
+        pauser,
+        paused: false,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UnpauseMarketAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn unpause_unpaused_market() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let action = UnpauseMarketAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPauseState);
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillCreationBondUpdateEvent, state::CreationBond};
+
+/// Only applies to markets created afterwards, existing bonds keep their amount and release time.
+pub fn handler(ctx: Context<ConfigUpdate>, creation_bond: CreationBond) -> Result<()> {
+    ctx.accounts.config.creation_bond = creation_bond;
+
+    emit_cpi!(TokenMillCreationBondUpdateEvent {
+        config: ctx.accounts.config.key(),
+        creation_bond,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CreationBond, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateCreationBondAction},
+        TokenMillError,
+    };
+
+    const CREATION_BOND: CreationBond = CreationBond {
+        amount: 1_000_000_000,
+        release_epochs: 4,
+    };
+
+    #[test]
+    fn update_creation_bond() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateCreationBondAction::new(CREATION_BOND);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.creation_bond, CREATION_BOND);
+    }
+
+    #[test]
+    fn update_creation_bond_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateCreationBondAction::new(CREATION_BOND);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillCreatorDrawUpdateEvent, state::CreatorDraw};
+
+/// Applies to the next draw requests and executions of every market of the config.
+pub fn handler(ctx: Context<ConfigUpdate>, creator_draw: CreatorDraw) -> Result<()> {
+    creator_draw.check()?;
+
+    ctx.accounts.config.creator_draw = creator_draw;
+
+    emit_cpi!(TokenMillCreatorDrawUpdateEvent {
+        config: ctx.accounts.config.key(),
+        creator_draw,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_CREATOR_DRAW_BPS, CreatorDraw, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateCreatorDrawAction},
+        TokenMillError,
+    };
+
+    const CREATOR_DRAW: CreatorDraw = CreatorDraw {
+        max_bps: 500,
+        delay: 86_400,
+    };
+
+    #[test]
+    fn update_creator_draw() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateCreatorDrawAction::new(CREATOR_DRAW);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        assert_eq!(config.creator_draw, CREATOR_DRAW);
+    }
+
+    #[test]
+    fn update_creator_draw_above_cap() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateCreatorDrawAction::new(CreatorDraw {
+            max_bps: MAX_CREATOR_DRAW_BPS + 1,
+            ..CREATOR_DRAW
+        });
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCreatorDraw);
+    }
+
+    #[test]
+    fn update_creator_draw_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateCreatorDrawAction::new(CREATOR_DRAW);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillCreatorPauseUpdateEvent;
+
+pub fn handler(ctx: Context<ConfigUpdate>, creator_pause_enabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.creator_pause_enabled = creator_pause_enabled;
+
+    emit_cpi!(TokenMillCreatorPauseUpdateEvent {
+        config: ctx.accounts.config.key(),
+        creator_pause_enabled,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateCreatorPauseAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_creator_pause() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateCreatorPauseAction::new(true);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert!(config.creator_pause_enabled);
+    }
+
+    #[test]
+    fn update_creator_pause_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateCreatorPauseAction::new(true);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    events::TokenMillDefaultCurveUpdateEvent,
+    manager::curve_manager::{self, CurveParameters},
+    state::Market,
+};
+
+pub fn handler(
+    ctx: Context<ConfigUpdate>,
+    new_default_curve: Option<CurveParameters>,
+) -> Result<()> {
+    if let Some(default_curve) = &new_default_curve {
+        let (bid_prices, ask_prices) = curve_manager::get_prices_from_parameters(default_curve)?;
+
+        Market::check_prices(&bid_prices, &ask_prices)?;
+    }
+
+    let config = &mut ctx.accounts.config;
+
+    config.default_curve = new_default_curve;
+
+    emit_cpi!(TokenMillDefaultCurveUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_default_curve,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateDefaultCurveAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateDefaultCurveAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateDefaultCurveAction::default();
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_default_curve() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.default_curve, action.new_default_curve);
+
+        action.new_default_curve = None;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.default_curve, None);
+    }
+
+    #[test]
+    fn update_default_curve_with_invalid_parameters() {
+        let (mut testing_env, mut action) = setup_env();
+
+        if let Some(default_curve) = &mut action.new_default_curve {
+            default_curve.end_price = default_curve.start_price;
+        }
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCurveParameters);
+    }
+
+    #[test]
+    fn update_default_curve_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
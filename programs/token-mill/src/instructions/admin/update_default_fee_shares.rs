@@ -1,8 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::{
-    constant::MAX_BPS, errors::TokenMillError, events::TokenMillDefaultFeeSharesUpdateEvent,
-};
+use crate::{events::TokenMillDefaultFeeSharesUpdateEvent, state::ConfigChange};
 
 use super::ConfigUpdate;
 
@@ -11,16 +9,15 @@ pub fn handler(
     new_default_protocol_fee_share: u16,
     new_referral_fee_share: u16,
 ) -> Result<()> {
-    require!(
-        new_default_protocol_fee_share <= MAX_BPS as u16
-            && new_referral_fee_share <= MAX_BPS as u16,
-        TokenMillError::InvalidFeeShare
-    );
-
     let config = &mut ctx.accounts.config;
 
-    config.default_protocol_fee_share = new_default_protocol_fee_share;
-    config.referral_fee_share = new_referral_fee_share;
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::DefaultFeeShares {
+        default_protocol_fee_share: new_default_protocol_fee_share,
+        referral_fee_share: new_referral_fee_share,
+    }
+    .apply(config)?;
 
     emit_cpi!(TokenMillDefaultFeeSharesUpdateEvent {
         config: ctx.accounts.config.key(),
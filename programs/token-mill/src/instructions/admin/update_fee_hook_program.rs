@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillFeeHookProgramUpdateEvent, state::ConfigChange};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_fee_hook_program: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::FeeHookProgram {
+        fee_hook_program: new_fee_hook_program,
+    }
+    .apply(config)?;
+
+    emit_cpi!(TokenMillFeeHookProgramUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_fee_hook_program,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateFeeHookProgramAction},
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateFeeHookProgramAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateFeeHookProgramAction::new(Some(make_address("fee_hook_program")));
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_fee_hook_program() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.fee_hook_program, action.new_fee_hook_program);
+
+        action.new_fee_hook_program = None;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.fee_hook_program, None);
+    }
+
+    #[test]
+    fn update_fee_hook_program_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillGraduationThresholdsUpdateEvent, state::ConfigChange};
+
+/// Applies to every market of the config that isn't migrated yet.
+pub fn handler(
+    ctx: Context<ConfigUpdate>,
+    new_graduation_quote_threshold: u64,
+    new_graduation_supply_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::GraduationThresholds {
+        graduation_quote_threshold: new_graduation_quote_threshold,
+        graduation_supply_bps: new_graduation_supply_bps,
+    }
+    .apply(config)?;
+
+    emit_cpi!(TokenMillGraduationThresholdsUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_graduation_quote_threshold,
+        new_graduation_supply_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateGraduationThresholdsAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateGraduationThresholdsAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateGraduationThresholdsAction::new(60_000_000_000_000, 8_000);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_graduation_thresholds() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.graduation_quote_threshold,
+            action.new_graduation_quote_threshold
+        );
+        assert_eq!(
+            config_account.graduation_supply_bps,
+            action.new_graduation_supply_bps
+        );
+    }
+
+    #[test]
+    fn update_graduation_thresholds_above_max_bps() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_graduation_supply_bps = 10_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidGraduationThresholds);
+    }
+
+    #[test]
+    fn update_graduation_thresholds_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillMaxDailyMarketsPerCreatorUpdateEvent;
+
+/// Throttles market creations per wallet on open-creation configs, 0 lifting the limit.
+/// Creators then pass their `CreatorActivity` to every market creation.
+pub fn handler(ctx: Context<ConfigUpdate>, new_max_daily_markets_per_creator: u16) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.max_daily_markets_per_creator = new_max_daily_markets_per_creator;
+
+    emit_cpi!(TokenMillMaxDailyMarketsPerCreatorUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_max_daily_markets_per_creator,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MARKET_CREATION_WINDOW_DURATION, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateCreatorActivityAction, CreateMarketAction, TokenMillEnv,
+            UpdateMaxDailyMarketsPerCreatorAction,
+        },
+        TokenMillError,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    fn setup_env() -> (TokenMillEnv, UpdateMaxDailyMarketsPerCreatorAction) {
+        let testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        let action = UpdateMaxDailyMarketsPerCreatorAction::new(1);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_max_daily_markets_per_creator() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.max_daily_markets_per_creator, 1);
+    }
+
+    #[test]
+    fn create_markets_above_daily_limit() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        // The creator activity is required once creations are throttled
+        let result = testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCreatorActivity);
+
+        let create_creator_activity_action = CreateCreatorActivityAction::new();
+
+        create_market_action.creator_activity = create_creator_activity_action.creator_activity;
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_creator_activity_action, &create_market_action])
+            .unwrap();
+
+        testing_env.base_token_mint = Some(Pubkey::new_unique());
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+        create_market_action.creator_activity = create_creator_activity_action.creator_activity;
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::MarketCreationLimitExceeded);
+
+        testing_env.svm.warp(MARKET_CREATION_WINDOW_DURATION * 2);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_market_action])
+            .unwrap();
+    }
+
+    #[test]
+    fn update_max_daily_markets_per_creator_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillMaxPauseDurationUpdateEvent;
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_max_pause_duration: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.max_pause_duration = new_max_pause_duration;
+
+    emit_cpi!(TokenMillMaxPauseDurationUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_max_pause_duration,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateMaxPauseDurationAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateMaxPauseDurationAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateMaxPauseDurationAction::new(1_000);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_max_pause_duration() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.max_pause_duration, 1_000);
+    }
+
+    #[test]
+    fn update_max_pause_duration_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
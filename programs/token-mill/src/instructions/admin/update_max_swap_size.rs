@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{constant::MAX_BPS, errors::TokenMillError, events::TokenMillMaxSwapSizeUpdateEvent};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_max_swap_size_bps: u16) -> Result<()> {
+    require_gte!(
+        MAX_BPS,
+        u64::from(new_max_swap_size_bps),
+        TokenMillError::InvalidSwapSizeLimit
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    config.max_swap_size_bps = new_max_swap_size_bps;
+
+    emit_cpi!(TokenMillMaxSwapSizeUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_max_swap_size_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateMaxSwapSizeAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateMaxSwapSizeAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateMaxSwapSizeAction::new(1_000);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_max_swap_size() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.max_swap_size_bps, 1_000);
+    }
+
+    #[test]
+    fn update_max_swap_size_above_max_bps() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_max_swap_size_bps = 10_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidSwapSizeLimit);
+    }
+
+    #[test]
+    fn update_max_swap_size_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
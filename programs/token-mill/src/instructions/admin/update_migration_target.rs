@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    events::TokenMillMigrationTargetUpdateEvent,
+    state::{ConfigChange, MigrationTarget},
+};
+
+/// Applies to every market of the config that isn't migrated yet.
+pub fn handler(ctx: Context<ConfigUpdate>, new_migration_target: MigrationTarget) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::MigrationTarget {
+        migration_target: new_migration_target,
+    }
+    .apply(config)?;
+
+    emit_cpi!(TokenMillMigrationTargetUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_migration_target,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MigrationTarget, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateMigrationTargetAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateMigrationTargetAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateMigrationTargetAction::new(MigrationTarget::MeteoraDammV2);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_migration_target() {
+        let (mut testing_env, action) = setup_env();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.migration_target,
+            MigrationTarget::RaydiumCpmm
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.migration_target,
+            MigrationTarget::MeteoraDammV2
+        );
+    }
+
+    #[test]
+    fn update_migration_target_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillPriceBoundsUpdateEvent, state::PriceBounds};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_price_bounds: PriceBounds) -> Result<()> {
+    new_price_bounds.check()?;
+
+    let config = &mut ctx.accounts.config;
+
+    config.price_bounds = new_price_bounds;
+
+    emit_cpi!(TokenMillPriceBoundsUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_price_bounds,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PriceBounds, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdatePriceBoundsAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdatePriceBoundsAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdatePriceBoundsAction::new(PriceBounds {
+            min_total_raise: 1_000_000_000,
+            max_total_raise: 100_000_000_000_000,
+            min_spread_bps: 100,
+            max_spread_bps: 2_000,
+        });
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_price_bounds() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.price_bounds, action.new_price_bounds);
+    }
+
+    #[test]
+    fn update_price_bounds_with_inverted_raise_bounds() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_price_bounds.min_total_raise = action.new_price_bounds.max_total_raise + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPriceBounds);
+    }
+
+    #[test]
+    fn update_price_bounds_above_max_bps() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_price_bounds.max_spread_bps = 10_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPriceBounds);
+    }
+
+    #[test]
+    fn update_price_bounds_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -1,12 +1,17 @@
 use anchor_lang::prelude::*;
 
 use super::ConfigUpdate;
-use crate::events::TokenMillProtocolFeeRecipientUpdateEvent;
+use crate::{events::TokenMillProtocolFeeRecipientUpdateEvent, state::ConfigChange};
 
 pub fn handler(ctx: Context<ConfigUpdate>, new_protocol_fee_recipient: Pubkey) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
-    config.protocol_fee_recipient = new_protocol_fee_recipient;
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::ProtocolFeeRecipient {
+        protocol_fee_recipient: new_protocol_fee_recipient,
+    }
+    .apply(config)?;
 
     emit_cpi!(TokenMillProtocolFeeRecipientUpdateEvent {
         config: ctx.accounts.config.key(),
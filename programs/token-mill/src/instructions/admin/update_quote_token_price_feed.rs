@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use super::UpdateQuoteAssetBadge;
+use crate::events::TokenMillQuoteTokenPriceFeedUpdateEvent;
+
+/// Sets the Pyth feed valuing the quote token in USD for graduation, see `UsdGraduation`.
+pub fn handler(ctx: Context<UpdateQuoteAssetBadge>, price_feed_id: Option<[u8; 32]>) -> Result<()> {
+    ctx.accounts.quote_asset_badge.price_feed_id = price_feed_id;
+
+    emit_cpi!(TokenMillQuoteTokenPriceFeedUpdateEvent {
+        config: ctx.accounts.config.key(),
+        quote_token_mint: ctx.accounts.token_mint.key(),
+        price_feed_id,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MAX_BPS,
+    errors::TokenMillError,
+    events::TokenMillReferralParamsUpdateEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReferralParamsUpdate<'info> {
+    #[account(mut, has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Updated instead of the config when provided
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: Option<AccountLoader<'info, Market>>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Updates the referral fee share and the referral switch of a market, or of the config when no
+/// market is provided. Config changes only reach existing markets through `adopt_new_fee_defaults`,
+/// the config switch stops referral fees on every market at once. Once a config change delay is
+/// set, the referral fee share of the config goes through `ConfigChange::ReferralParams`.
+pub fn handler(
+    ctx: Context<ReferralParamsUpdate>,
+    referral_fee_share: u16,
+    referral_enabled: bool,
+) -> Result<()> {
+    match &ctx.accounts.market {
+        Some(market) => {
+            let mut market = market.load_mut()?;
+
+            // Backfilled first so that a later backfill can't override the switch
+            market.backfill(ctx.accounts.config.referral_fee_share);
+            market.set_referral_params(referral_fee_share, referral_enabled)?;
+        }
+        None => {
+            require!(
+                referral_fee_share <= MAX_BPS as u16,
+                TokenMillError::InvalidFeeShare
+            );
+
+            let config = &mut ctx.accounts.config;
+
+            if referral_fee_share != config.referral_fee_share {
+                config.check_no_config_change_delay()?;
+            }
+
+            config.referral_fee_share = referral_fee_share;
+            config.referral_enabled = referral_enabled;
+        }
+    }
+
+    emit_cpi!(TokenMillReferralParamsUpdateEvent {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.as_ref().map(|market| market.key()),
+        referral_fee_share,
+        referral_enabled,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_BPS, Market, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimReferralFeesAction, SwapAction, TokenMillEnv,
+            UpdateReferralParamsAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const NEW_REFERRAL_FEE_SHARE: u16 = 5_000;
+
+    fn setup_env() -> (TokenMillEnv, UpdateReferralParamsAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let action = UpdateReferralParamsAction::new(NEW_REFERRAL_FEE_SHARE, false);
+
+        (testing_env, action)
+    }
+
+    /// Swaps twice with carol as referrer, returning the referral fees of the second swap.
+    fn get_referral_fee(testing_env: &mut TokenMillEnv) -> u64 {
+        testing_env.svm.change_payer("bob");
+
+        let referral_account = ClaimReferralFeesAction::new(testing_env).referral_account;
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let swap_action = SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        );
+
+        testing_env.svm.warp(1);
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.warp(1);
+
+        let balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &referral_account);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env
+            .svm
+            .get_balance(&quote_token_mint, &referral_account)
+            - balance_before
+    }
+
+    #[test]
+    fn update_referral_params() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config.referral_fee_share, NEW_REFERRAL_FEE_SHARE);
+        assert!(!config.referral_enabled);
+
+        // Referral fees stop on every market of the config
+        assert_eq!(get_referral_fee(&mut testing_env), 0);
+    }
+
+    #[test]
+    fn update_market_referral_params() {
+        let (mut testing_env, action) = setup_env();
+
+        assert!(get_referral_fee(&mut testing_env) > 0);
+
+        testing_env.svm.change_payer("admin");
+
+        let action = action.with_market(testing_env.market);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.fees.referral_fee_share, NEW_REFERRAL_FEE_SHARE);
+        assert!(!market.is_referral_enabled());
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert!(config.referral_enabled);
+
+        assert_eq!(get_referral_fee(&mut testing_env), 0);
+    }
+
+    #[test]
+    fn update_referral_params_with_invalid_share() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.referral_fee_share = MAX_BPS as u16 + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+
+        let action = action.with_market(testing_env.market);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+    }
+
+    #[test]
+    fn update_referral_params_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillReferralSettlementUpdateEvent, state::ReferralSettlement};
+
+pub fn handler(ctx: Context<ConfigUpdate>, referral_settlement: ReferralSettlement) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.referral_settlement = referral_settlement;
+
+    emit_cpi!(TokenMillReferralSettlementUpdateEvent {
+        config: ctx.accounts.config.key(),
+        referral_settlement,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReferralSettlement, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateReferralSettlementAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_referral_settlement() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateReferralSettlementAction::new(ReferralSettlement::OffChain);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.referral_settlement,
+            ReferralSettlement::OffChain
+        );
+    }
+
+    #[test]
+    fn update_referral_settlement_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateReferralSettlementAction::new(ReferralSettlement::OffChain);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    events::TokenMillReferralTiersUpdateEvent,
+    state::{ConfigChange, ReferralTier},
+};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_referral_tiers: Vec<ReferralTier>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::referral_tiers(&new_referral_tiers)?.apply(config)?;
+
+    emit_cpi!(TokenMillReferralTiersUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_referral_tiers,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReferralTier, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateReferralTiersAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateReferralTiersAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateReferralTiersAction::new(vec![
+            ReferralTier {
+                min_referred_volume: 1_000_000_000,
+                referral_fee_share: 4_000,
+            },
+            ReferralTier {
+                min_referred_volume: 10_000_000_000,
+                referral_fee_share: 5_000,
+            },
+        ]);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_referral_tiers() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.referral_tiers, action.new_referral_tiers);
+        assert_eq!(config_account.get_referral_tier_fee_share(999_999_999), 0);
+        assert_eq!(
+            config_account.get_referral_tier_fee_share(1_000_000_000),
+            4_000
+        );
+        assert_eq!(config_account.get_referral_tier_fee_share(u64::MAX), 5_000);
+    }
+
+    #[test]
+    fn update_referral_tiers_with_unsorted_tiers() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_referral_tiers.reverse();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidReferralTiers);
+    }
+
+    #[test]
+    fn update_referral_tiers_above_max_bps() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_referral_tiers[1].referral_fee_share = 10_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidReferralTiers);
+    }
+
+    #[test]
+    fn update_referral_tiers_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
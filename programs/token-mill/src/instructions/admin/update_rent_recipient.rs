@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillRentRecipientUpdateEvent;
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_rent_recipient: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.rent_recipient = new_rent_recipient;
+
+    emit_cpi!(TokenMillRentRecipientUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_rent_recipient,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateRentRecipientAction},
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateRentRecipientAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateRentRecipientAction::new(make_address("new_rent_recipient"));
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_rent_recipient() {
+        let (mut testing_env, action) = setup_env();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.rent_recipient, config_account.authority);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.rent_recipient,
+            make_address("new_rent_recipient")
+        );
+    }
+
+    #[test]
+    fn update_rent_recipient_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillSecondaryMarketPolicyUpdateEvent;
+
+pub fn handler(ctx: Context<ConfigUpdate>, secondary_markets_enabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.secondary_markets_enabled = secondary_markets_enabled;
+
+    emit_cpi!(TokenMillSecondaryMarketPolicyUpdateEvent {
+        config: ctx.accounts.config.key(),
+        secondary_markets_enabled,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateSecondaryMarketPolicyAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateSecondaryMarketPolicyAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateSecondaryMarketPolicyAction::new(true);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_secondary_market_policy() {
+        let (mut testing_env, action) = setup_env();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert!(!config_account.secondary_markets_enabled);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert!(config_account.secondary_markets_enabled);
+    }
+
+    #[test]
+    fn update_secondary_market_policy_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
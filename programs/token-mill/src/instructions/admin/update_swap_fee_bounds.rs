@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillSwapFeeBoundsUpdateEvent, state::ConfigChange};
+
+/// Only applies to markets created afterwards, the swap fee of a market is set at creation.
+pub fn handler(
+    ctx: Context<ConfigUpdate>,
+    new_min_swap_fee_bps: u16,
+    new_max_swap_fee_bps: u16,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::SwapFeeBounds {
+        min_swap_fee_bps: new_min_swap_fee_bps,
+        max_swap_fee_bps: new_max_swap_fee_bps,
+    }
+    .apply(config)?;
+
+    emit_cpi!(TokenMillSwapFeeBoundsUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_min_swap_fee_bps,
+        new_max_swap_fee_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_SWAP_FEE_BPS, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateSwapFeeBoundsAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_swap_fee_bounds() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateSwapFeeBoundsAction::new(30, MAX_SWAP_FEE_BPS as u16);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config.min_swap_fee_bps, 30);
+        assert_eq!(config.max_swap_fee_bps, MAX_SWAP_FEE_BPS as u16);
+    }
+
+    #[test]
+    fn update_swap_fee_bounds_with_invalid_bounds() {
+        let mut testing_env = TokenMillEnv::new();
+
+        for (min, max) in [(100, 30), (0, MAX_SWAP_FEE_BPS as u16 + 1)] {
+            let action = UpdateSwapFeeBoundsAction::new(min, max);
+
+            let result = testing_env.svm.execute_actions(&[&action]);
+
+            assert!(result.is_err());
+
+            let error = tm_parse_error(result).unwrap();
+
+            assert_eq!(error, TokenMillError::InvalidSwapFee);
+        }
+    }
+
+    #[test]
+    fn update_swap_fee_bounds_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateSwapFeeBoundsAction::new(0, 100);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    constant::MAX_SWEEP_TIP_BPS, errors::TokenMillError, events::TokenMillSweepTipUpdateEvent,
+};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_sweep_tip_bps: u16) -> Result<()> {
+    require_gte!(
+        MAX_SWEEP_TIP_BPS,
+        new_sweep_tip_bps,
+        TokenMillError::InvalidSweepTip
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    config.sweep_tip_bps = new_sweep_tip_bps;
+
+    emit_cpi!(TokenMillSweepTipUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_sweep_tip_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_SWEEP_TIP_BPS, TokenMillConfig};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateSweepTipAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_sweep_tip() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateSweepTipAction::new(MAX_SWEEP_TIP_BPS);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config.sweep_tip_bps, MAX_SWEEP_TIP_BPS);
+    }
+
+    #[test]
+    fn update_sweep_tip_above_maximum() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateSweepTipAction::new(MAX_SWEEP_TIP_BPS + 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidSweepTip);
+    }
+
+    #[test]
+    fn update_sweep_tip_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateSweepTipAction::new(MAX_SWEEP_TIP_BPS);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    constant::MAX_BPS, errors::TokenMillError, events::TokenMillTraderRebateParamsUpdateEvent,
+    state::TraderRebateParams,
+};
+
+/// Applies to the revenue reports created afterwards, existing reports keep their snapshot.
+pub fn handler(
+    ctx: Context<ConfigUpdate>,
+    new_trader_rebate_params: TraderRebateParams,
+) -> Result<()> {
+    require_gte!(
+        MAX_BPS,
+        u64::from(new_trader_rebate_params.protocol_fee_share),
+        TokenMillError::InvalidFeeShare
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    config.trader_rebate_params = new_trader_rebate_params;
+
+    emit_cpi!(TokenMillTraderRebateParamsUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_trader_rebate_params,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TokenMillConfig, TraderRebateParams};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateTraderRebateParamsAction},
+        TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateTraderRebateParamsAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateTraderRebateParamsAction::new(TraderRebateParams {
+            volume_threshold: 1_000_000_000,
+            protocol_fee_share: 2_000,
+        });
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_trader_rebate_params() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(
+            config_account.trader_rebate_params,
+            action.new_trader_rebate_params
+        );
+    }
+
+    #[test]
+    fn update_trader_rebate_params_above_max_bps() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_trader_rebate_params.protocol_fee_share = 10_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+    }
+
+    #[test]
+    fn update_trader_rebate_params_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
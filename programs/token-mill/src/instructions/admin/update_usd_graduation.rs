@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    events::TokenMillUsdGraduationUpdateEvent,
+    state::{ConfigChange, UsdGraduation},
+};
+
+/// Applies to every market of the config that isn't migrated yet, on top of the quote threshold.
+/// Markets can only graduate once their quote token has a price feed, see
+/// `update_quote_token_price_feed`.
+pub fn handler(ctx: Context<ConfigUpdate>, usd_graduation: UsdGraduation) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.check_no_config_change_delay()?;
+
+    ConfigChange::UsdGraduation { usd_graduation }.apply(config)?;
+
+    emit_cpi!(TokenMillUsdGraduationUpdateEvent {
+        config: ctx.accounts.config.key(),
+        usd_graduation,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TokenMillConfig, UsdGraduation};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateUsdGraduationAction},
+        TokenMillError,
+    };
+
+    const USD_GRADUATION: UsdGraduation = UsdGraduation {
+        usd_threshold: 69_000_000_000,
+        max_price_staleness: 60,
+        max_price_conf_bps: 100,
+    };
+
+    #[test]
+    fn update_usd_graduation() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateUsdGraduationAction::new(USD_GRADUATION);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        assert_eq!(config.usd_graduation, USD_GRADUATION);
+    }
+
+    #[test]
+    fn update_usd_graduation_without_staleness() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let action = UpdateUsdGraduationAction::new(UsdGraduation {
+            max_price_staleness: 0,
+            ..USD_GRADUATION
+        });
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidGraduationThresholds);
+    }
+
+    #[test]
+    fn update_usd_graduation_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = UpdateUsdGraduationAction::new(USD_GRADUATION);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillVerifierUpdateEvent;
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_verifier: Option<Pubkey>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.verifier = new_verifier;
+
+    emit_cpi!(TokenMillVerifierUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_verifier,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::TokenMillConfig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateVerifierAction},
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateVerifierAction) {
+        let testing_env = TokenMillEnv::new();
+
+        let action = UpdateVerifierAction::new(Some(make_address("carol")));
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn update_verifier() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.verifier, action.new_verifier);
+
+        action.new_verifier = None;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let config_account = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&action.config);
+
+        assert_eq!(config_account.verifier, None);
+    }
+
+    #[test]
+    fn update_verifier_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,309 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillQuoteAirdropClaimEvent,
+    manager::{merkle_manager, token_manager::transfer_from_pda},
+    state::{
+        AirdropClaim, Denylist, Market, QuoteAirdrop, TokenMillConfig, AIRDROP_CLAIM_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimQuoteAirdrop<'info> {
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub quote_airdrop: Account<'info, QuoteAirdrop>,
+
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + AirdropClaim::INIT_SPACE,
+        seeds = [
+            AIRDROP_CLAIM_PDA_SEED.as_bytes(),
+            quote_airdrop.key().as_ref(),
+            claimant.key().as_ref()
+        ],
+        bump
+    )]
+    pub airdrop_claim: Account<'info, AirdropClaim>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = claimant,
+        associated_token::token_program = quote_token_program
+    )]
+    pub claimant_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+}
+
+/// Pays out the leaf of `claimant` in the airdrop tree. Leaves commit to the market, and each
+/// claimant can only claim once per airdrop.
+pub fn handler(ctx: Context<ClaimQuoteAirdrop>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    Denylist::check(
+        &ctx.accounts.config,
+        ctx.accounts.denylist.as_ref(),
+        &ctx.accounts.claimant.key(),
+    )?;
+
+    let quote_airdrop = &mut ctx.accounts.quote_airdrop;
+    let claimant = &ctx.accounts.claimant;
+
+    require!(
+        merkle_manager::verify(
+            &proof,
+            &quote_airdrop.merkle_root,
+            merkle_manager::get_leaf(&quote_airdrop.market, &claimant.key(), amount),
+        ),
+        TokenMillError::InvalidMerkleProof
+    );
+
+    require_gte!(
+        quote_airdrop.get_amount_left(),
+        amount,
+        TokenMillError::InvalidAmount
+    );
+
+    quote_airdrop.amount_claimed += amount;
+
+    let airdrop_claim = &mut ctx.accounts.airdrop_claim;
+
+    airdrop_claim.bump = ctx.bumps.airdrop_claim;
+    airdrop_claim.airdrop = quote_airdrop.key();
+    airdrop_claim.claimant = claimant.key();
+    airdrop_claim.amount = amount;
+
+    let market_seeds = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        market.lock()?;
+
+        market.check_fee_withdrawal(ctx.accounts.market_quote_token_ata.amount, amount)?;
+
+        market.airdropped_quote -= amount;
+
+        market.seeds()
+    };
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.claimant_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        amount,
+        &market_seeds.signer_seeds(),
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillQuoteAirdropClaimEvent {
+        airdrop: ctx.accounts.quote_airdrop.key(),
+        claimant: ctx.accounts.claimant.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{manager::merkle_manager, QuoteAirdrop};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimQuoteAirdropAction, CreateQuoteAirdropAction, SwapAction,
+            TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const BOB_AMOUNT: u64 = 1_000;
+    const CAROL_AMOUNT: u64 = 2_000;
+
+    struct Tree {
+        root: [u8; 32],
+        bob_proof: Vec<[u8; 32]>,
+        carol_proof: Vec<[u8; 32]>,
+    }
+
+    fn build_tree(testing_env: &TokenMillEnv) -> Tree {
+        let bob_leaf =
+            merkle_manager::get_leaf(&testing_env.market, &make_address("bob"), BOB_AMOUNT);
+        let carol_leaf =
+            merkle_manager::get_leaf(&testing_env.market, &make_address("carol"), CAROL_AMOUNT);
+
+        Tree {
+            root: merkle_manager::hash_pair(&bob_leaf, &carol_leaf),
+            bob_proof: vec![carol_leaf],
+            carol_proof: vec![bob_leaf],
+        }
+    }
+
+    fn setup_env() -> (TokenMillEnv, Tree, CreateQuoteAirdropAction) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let tree = build_tree(&testing_env);
+        let create_action =
+            CreateQuoteAirdropAction::new(&testing_env, tree.root, BOB_AMOUNT + CAROL_AMOUNT);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        (testing_env, tree, create_action)
+    }
+
+    #[test]
+    fn claim_quote_airdrop() {
+        let (mut testing_env, tree, create_action) = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+        let bob_balance_before = testing_env.svm.get_balance(&quote_token_mint, &bob);
+
+        let action = ClaimQuoteAirdropAction::new(
+            &testing_env,
+            create_action.quote_airdrop,
+            BOB_AMOUNT,
+            tree.bob_proof,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &bob),
+            bob_balance_before + BOB_AMOUNT
+        );
+
+        let carol = testing_env.svm.change_payer("carol");
+        let carol_balance_before = testing_env.svm.get_balance(&quote_token_mint, &carol);
+
+        let action = ClaimQuoteAirdropAction::new(
+            &testing_env,
+            create_action.quote_airdrop,
+            CAROL_AMOUNT,
+            tree.carol_proof,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &carol),
+            carol_balance_before + CAROL_AMOUNT
+        );
+
+        let quote_airdrop = testing_env
+            .svm
+            .get_parsed_account::<QuoteAirdrop>(&create_action.quote_airdrop);
+
+        assert_eq!(quote_airdrop.amount_claimed, BOB_AMOUNT + CAROL_AMOUNT);
+        assert_eq!(quote_airdrop.get_amount_left(), 0);
+    }
+
+    #[test]
+    fn claim_quote_airdrop_twice() {
+        let (mut testing_env, tree, create_action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = ClaimQuoteAirdropAction::new(
+            &testing_env,
+            create_action.quote_airdrop,
+            BOB_AMOUNT,
+            tree.bob_proof,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn claim_quote_airdrop_with_invalid_amount() {
+        let (mut testing_env, tree, create_action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = ClaimQuoteAirdropAction::new(
+            &testing_env,
+            create_action.quote_airdrop,
+            CAROL_AMOUNT,
+            tree.bob_proof,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidMerkleProof);
+    }
+
+    #[test]
+    fn claim_quote_airdrop_with_invalid_claimant() {
+        let (mut testing_env, tree, create_action) = setup_env();
+
+        testing_env.svm.change_payer("mallory");
+
+        let action = ClaimQuoteAirdropAction::new(
+            &testing_env,
+            create_action.quote_airdrop,
+            BOB_AMOUNT,
+            tree.bob_proof,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidMerkleProof);
+    }
+}
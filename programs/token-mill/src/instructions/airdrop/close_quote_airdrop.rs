@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillQuoteAirdropClosureEvent,
+    state::{Market, QuoteAirdrop, QuoteAirdropSource},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseQuoteAirdrop<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        close = creator
+    )]
+    pub quote_airdrop: Account<'info, QuoteAirdrop>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Closes the airdrop, returning the unclaimed tokens to the pending fees funding it.
+pub fn handler(ctx: Context<CloseQuoteAirdrop>) -> Result<()> {
+    let amount_returned = ctx.accounts.quote_airdrop.get_amount_left();
+
+    {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        match ctx.accounts.quote_airdrop.source {
+            QuoteAirdropSource::CreatorFees => market.fees.pending_creator_fees += amount_returned,
+            QuoteAirdropSource::HolderRewards => market.pending_holder_rewards += amount_returned,
+        }
+
+        market.airdropped_quote -= amount_returned;
+    }
+
+    emit_cpi!(TokenMillQuoteAirdropClosureEvent {
+        market: ctx.accounts.market.key(),
+        airdrop: ctx.accounts.quote_airdrop.key(),
+        amount_returned,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CloseQuoteAirdropAction, CreateQuoteAirdropAction, SwapAction,
+            TokenMillEnv,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, CloseQuoteAirdropAction, u64) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+
+        let create_action =
+            CreateQuoteAirdropAction::new(&testing_env, [1; 32], pending_creator_fees);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let action = CloseQuoteAirdropAction::new(&testing_env, create_action.quote_airdrop);
+
+        (testing_env, action, pending_creator_fees)
+    }
+
+    #[test]
+    fn close_quote_airdrop() {
+        let (mut testing_env, action, pending_creator_fees) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.fees.pending_creator_fees, pending_creator_fees);
+        assert!(!testing_env.svm.account_exists(&action.quote_airdrop));
+    }
+
+    #[test]
+    fn close_quote_airdrop_with_invalid_signer() {
+        let (mut testing_env, mut action, _) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
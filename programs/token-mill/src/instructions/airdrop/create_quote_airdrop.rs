@@ -0,0 +1,160 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillQuoteAirdropCreationEvent,
+    state::{Market, QuoteAirdrop, QuoteAirdropSource, QUOTE_AIRDROP_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct CreateQuoteAirdrop<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + QuoteAirdrop::INIT_SPACE,
+        seeds = [QUOTE_AIRDROP_PDA_SEED.as_bytes(), market.key().as_ref(), merkle_root.as_ref()],
+        bump
+    )]
+    pub quote_airdrop: Account<'info, QuoteAirdrop>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets `amount` of the pending creator fees aside for the leaves of `merkle_root`.
+pub fn handler(ctx: Context<CreateQuoteAirdrop>, merkle_root: [u8; 32], amount: u64) -> Result<()> {
+    require!(amount > 0, TokenMillError::InvalidAmount);
+
+    {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require_gte!(
+            market.fees.pending_creator_fees,
+            amount,
+            TokenMillError::InsufficientCreatorFees
+        );
+
+        market.fees.pending_creator_fees -= amount;
+        market.airdropped_quote += amount;
+    }
+
+    ctx.accounts.quote_airdrop.initialize(
+        ctx.bumps.quote_airdrop,
+        ctx.accounts.market.key(),
+        merkle_root,
+        amount,
+        QuoteAirdropSource::CreatorFees,
+    )?;
+
+    emit_cpi!(TokenMillQuoteAirdropCreationEvent {
+        market: ctx.accounts.market.key(),
+        airdrop: ctx.accounts.quote_airdrop.key(),
+        merkle_root,
+        total_amount: amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, QuoteAirdrop};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateQuoteAirdropAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+    }
+
+    #[test]
+    fn create_quote_airdrop() {
+        let mut testing_env = setup_env();
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+
+        let action = CreateQuoteAirdropAction::new(&testing_env, [1; 32], pending_creator_fees / 2);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let quote_airdrop = testing_env
+            .svm
+            .get_parsed_account::<QuoteAirdrop>(&action.quote_airdrop);
+
+        assert_eq!(
+            market.fees.pending_creator_fees,
+            pending_creator_fees - pending_creator_fees / 2
+        );
+        assert_eq!(quote_airdrop.market, testing_env.market);
+        assert_eq!(quote_airdrop.merkle_root, [1; 32]);
+        assert_eq!(quote_airdrop.total_amount, pending_creator_fees / 2);
+    }
+
+    #[test]
+    fn create_quote_airdrop_above_creator_fees() {
+        let mut testing_env = setup_env();
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+
+        let action = CreateQuoteAirdropAction::new(&testing_env, [1; 32], pending_creator_fees + 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientCreatorFees);
+    }
+
+    #[test]
+    fn create_quote_airdrop_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = CreateQuoteAirdropAction::new(&testing_env, [1; 32], 1);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod claim_quote_airdrop;
+pub mod close_quote_airdrop;
+pub mod create_quote_airdrop;
+pub mod publish_holder_rewards;
+
+pub use claim_quote_airdrop::*;
+pub use close_quote_airdrop::*;
+pub use create_quote_airdrop::*;
+pub use publish_holder_rewards::*;
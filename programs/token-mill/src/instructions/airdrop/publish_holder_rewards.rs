@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillHolderRewardsPublicationEvent,
+    state::{Market, QuoteAirdrop, QuoteAirdropSource, QUOTE_AIRDROP_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(merkle_root: [u8; 32])]
+pub struct PublishHolderRewards<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + QuoteAirdrop::INIT_SPACE,
+        seeds = [QUOTE_AIRDROP_PDA_SEED.as_bytes(), market.key().as_ref(), merkle_root.as_ref()],
+        bump
+    )]
+    pub quote_airdrop: Account<'info, QuoteAirdrop>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes the current holder rewards epoch, setting all the sell taxes accrued since the previous
+/// one aside for the leaves of `merkle_root`, a pro-rata snapshot of the holders computed off-chain.
+/// Holders claim with `claim_quote_airdrop`.
+pub fn handler(ctx: Context<PublishHolderRewards>, merkle_root: [u8; 32]) -> Result<()> {
+    let amount = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        let amount = market.pending_holder_rewards;
+
+        require!(amount > 0, TokenMillError::InvalidAmount);
+
+        market.pending_holder_rewards = 0;
+        market.airdropped_quote += amount;
+
+        amount
+    };
+
+    ctx.accounts.quote_airdrop.initialize(
+        ctx.bumps.quote_airdrop,
+        ctx.accounts.market.key(),
+        merkle_root,
+        amount,
+        QuoteAirdropSource::HolderRewards,
+    )?;
+
+    emit_cpi!(TokenMillHolderRewardsPublicationEvent {
+        market: ctx.accounts.market.key(),
+        airdrop: ctx.accounts.quote_airdrop.key(),
+        merkle_root,
+        total_amount: amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, QuoteAirdrop, QuoteAirdropSource};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, PublishHolderRewardsAction, SwapAction, TokenMillEnv,
+            UpdateHolderRewardsFeeAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateHolderRewardsFeeAction::new(&testing_env, 500)])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &SwapAction::new(
+                    &testing_env,
+                    SwapType::Buy,
+                    SwapAmountType::ExactOutput,
+                    100_000_000_000,
+                    u64::MAX,
+                    None,
+                ),
+                &SwapAction::new(
+                    &testing_env,
+                    SwapType::Sell,
+                    SwapAmountType::ExactInput,
+                    50_000_000_000,
+                    0,
+                    None,
+                ),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+    }
+
+    #[test]
+    fn publish_holder_rewards() {
+        let mut testing_env = setup_env();
+
+        let pending_holder_rewards = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .pending_holder_rewards;
+
+        assert!(pending_holder_rewards > 0);
+
+        let action = PublishHolderRewardsAction::new(&testing_env, [1; 32]);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let quote_airdrop = testing_env
+            .svm
+            .get_parsed_account::<QuoteAirdrop>(&action.quote_airdrop);
+
+        assert_eq!(market.pending_holder_rewards, 0);
+        assert_eq!(quote_airdrop.total_amount, pending_holder_rewards);
+        assert_eq!(quote_airdrop.source, QuoteAirdropSource::HolderRewards);
+
+        // Nothing accrued since the previous epoch
+        let result = testing_env
+            .svm
+            .execute_actions(&[&PublishHolderRewardsAction::new(&testing_env, [2; 32])]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+
+    #[test]
+    fn publish_holder_rewards_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = PublishHolderRewardsAction::new(&testing_env, [1; 32]);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError, events::TokenMillConfigBackfillEvent,
+    instructions::staking::backfill_stake_position::grow, state::TokenMillConfig,
+};
+
+/// Configs created before their latest fields are too short to be deserialized with the current
+/// layout, so they are grown before being loaded.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BackfillConfig<'info> {
+    /// CHECK: Loaded as `TokenMillConfig` once grown, checking its discriminator
+    #[account(mut, owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, brings a config created before its latest fields to the current layout, see
+/// `TokenMillConfig::backfill`.
+pub fn handler(ctx: Context<BackfillConfig>) -> Result<()> {
+    grow(
+        &ctx.accounts.config,
+        8 + TokenMillConfig::INIT_SPACE,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    let mut config = TokenMillConfig::try_deserialize(&mut &ctx.accounts.config.data.borrow()[..])?;
+
+    require!(config.backfill(), TokenMillError::ConfigUpToDate);
+
+    config.try_serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
+    emit_cpi!(TokenMillConfigBackfillEvent {
+        config: ctx.accounts.config.key(),
+        version: config.version,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Space;
+
+    use crate::{
+        state::{BASELINE_CONFIG_SIZE, CONFIG_VERSION},
+        TokenMillConfig,
+    };
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, BackfillConfigAction, SwapAction, TokenMillEnv,
+            UpdateRentRecipientAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    /// Account data of `config` as written by the first release, field by field.
+    fn baseline_config_data(discriminator: &[u8], config: &TokenMillConfig) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+
+        data.extend_from_slice(config.authority.as_ref());
+        data.push(0);
+        data.extend_from_slice(config.protocol_fee_recipient.as_ref());
+        data.extend_from_slice(&config.default_protocol_fee_share.to_le_bytes());
+        data.extend_from_slice(&config.referral_fee_share.to_le_bytes());
+
+        // Space of the unset pending authority
+        data.resize(8 + BASELINE_CONFIG_SIZE, 0);
+
+        data
+    }
+
+    fn setup_legacy_config() -> (TokenMillEnv, TokenMillConfig) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        let mut account = testing_env.svm.get_account(&testing_env.config);
+        account.data = baseline_config_data(&account.data[..8], &config);
+
+        testing_env.svm.set_account(&testing_env.config, account);
+
+        (testing_env, config)
+    }
+
+    #[test]
+    fn backfill_config() {
+        let (mut testing_env, legacy_config) = setup_legacy_config();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        // Legacy configs can't be loaded until backfilled
+        assert!(testing_env.svm.execute_actions(&[&swap_action]).is_err());
+
+        testing_env
+            .svm
+            .execute_actions(&[&BackfillConfigAction::new(&testing_env)])
+            .unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_account(&testing_env.config).data.len(),
+            8 + TokenMillConfig::INIT_SPACE
+        );
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        // Fields of the first release are read back
+        assert_eq!(config.authority, legacy_config.authority);
+        assert_eq!(config.pending_authority, None);
+        assert_eq!(
+            config.protocol_fee_recipient,
+            legacy_config.protocol_fee_recipient
+        );
+        assert_eq!(
+            config.default_protocol_fee_share,
+            legacy_config.default_protocol_fee_share
+        );
+        assert_eq!(config.referral_fee_share, legacy_config.referral_fee_share);
+
+        assert!(config.is_backfilled());
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.rent_recipient, config.authority);
+        assert!(config.referral_enabled);
+        assert!(config.referral_tiers.is_empty());
+        assert_eq!(config.config_change_delay, 0);
+
+        // The config is loaded again by swaps and admin updates
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateRentRecipientAction::new(make_address("rent"))])
+            .unwrap();
+    }
+
+    #[test]
+    fn backfill_up_to_date_config() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&BackfillConfigAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConfigUpToDate);
+    }
+}
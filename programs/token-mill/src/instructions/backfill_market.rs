@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketBackfillEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BackfillMarket<'info> {
+    /// CHECK: Loaded with `TokenMillConfig::try_deserialize_legacy`, so that markets can be
+    /// backfilled whether or not their config already is
+    #[account(owner = crate::ID)]
+    pub config: UncheckedAccount<'info>,
+
+    /// Grown to the current layout when created with a smaller one, new bytes are zeroed. Fields
+    /// keep their offsets across layouts, later ones being appended, see `BASELINE_MARKET_SIZE`.
+    #[account(
+        mut,
+        realloc = 8 + Market::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = true
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, brings a market created before its latest fields to the current layout,
+/// initializing them from the existing state of the market, see `Market::backfill`.
+pub fn handler(ctx: Context<BackfillMarket>) -> Result<()> {
+    let config = TokenMillConfig::try_deserialize_legacy(&ctx.accounts.config.data.borrow())?;
+
+    let version = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        // Checked once reallocated, legacy markets are too short to be loaded before
+        require_keys_eq!(
+            market.config,
+            ctx.accounts.config.key(),
+            TokenMillError::InvalidConfigAccount
+        );
+
+        require!(
+            market.backfill(config.referral_fee_share),
+            TokenMillError::MarketUpToDate
+        );
+
+        market.version
+    };
+
+    emit_cpi!(TokenMillMarketBackfillEvent {
+        market: ctx.accounts.market.key(),
+        version,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Space;
+
+    use crate::{
+        state::{BASELINE_CONFIG_SIZE, BASELINE_MARKET_SIZE},
+        Market, TokenMillConfig,
+    };
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, BackfillMarketAction, SwapAction, TokenMillEnv},
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    /// Account data of `market` as written by the first release, field by field.
+    fn baseline_market_data(discriminator: &[u8], market: &Market) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+
+        data.extend_from_slice(market.config.as_ref());
+        data.extend_from_slice(market.creator.as_ref());
+        data.extend_from_slice(market.base_token_mint.as_ref());
+        data.extend_from_slice(market.quote_token_mint.as_ref());
+        data.extend_from_slice(&market.base_reserve.to_le_bytes());
+
+        for price in market.bid_prices.iter().chain(market.ask_prices.iter()) {
+            data.extend_from_slice(&price.to_le_bytes());
+        }
+
+        data.extend_from_slice(&market.width_scaled.to_le_bytes());
+        data.extend_from_slice(&market.total_supply.to_le_bytes());
+
+        data.extend_from_slice(&market.fees.staking_fee_share.to_le_bytes());
+        data.extend_from_slice(&market.fees.creator_fee_share.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&market.fees.pending_staking_fees.to_le_bytes());
+        data.extend_from_slice(&market.fees.pending_creator_fees.to_le_bytes());
+
+        data.push(market.quote_token_decimals);
+        data.push(market.bump);
+        data.extend_from_slice(&[0; 6]);
+
+        assert_eq!(data.len(), 8 + BASELINE_MARKET_SIZE);
+
+        data
+    }
+
+    fn setup_legacy_market() -> (TokenMillEnv, Market) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        let mut account = testing_env.svm.get_account(&testing_env.market);
+        account.data = baseline_market_data(&account.data[..8], &market);
+
+        testing_env.svm.set_account(&testing_env.market, account);
+
+        (testing_env, market)
+    }
+
+    #[test]
+    fn backfill_market() {
+        let (mut testing_env, legacy_market) = setup_legacy_market();
+
+        let action = BackfillMarketAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_account(&testing_env.market).data.len(),
+            8 + Market::INIT_SPACE
+        );
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        // Fields of the first release are read back at their offsets
+        assert_eq!(market.config, legacy_market.config);
+        assert_eq!(market.base_token_mint, legacy_market.base_token_mint);
+        assert_eq!(market.base_reserve, legacy_market.base_reserve);
+        assert_eq!(market.ask_prices, legacy_market.ask_prices);
+        assert_eq!(
+            market.fees.pending_staking_fees,
+            legacy_market.fees.pending_staking_fees
+        );
+        assert_eq!(
+            market.fees.pending_creator_fees,
+            legacy_market.fees.pending_creator_fees
+        );
+        assert_eq!(
+            market.quote_token_decimals,
+            legacy_market.quote_token_decimals
+        );
+        assert_eq!(market.bump, legacy_market.bump);
+
+        assert!(market.is_backfilled());
+        assert!(!market.is_secondary());
+        assert_eq!(market.fees.referral_fee_share, config.referral_fee_share);
+        assert!(market.is_referral_enabled());
+        assert!(market.has_traded());
+        assert_eq!(market.freeze_staking_on_migration, 0);
+        assert_eq!(market.paused, 0);
+
+        // The market trades again with its signer seeds
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Sell,
+                SwapAmountType::ExactInput,
+                1_000_000,
+                0,
+                None,
+            )])
+            .unwrap();
+    }
+
+    #[test]
+    fn backfill_market_with_legacy_config() {
+        let (mut testing_env, _) = setup_legacy_market();
+
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        // Config of the first release: its fields, the pending authority being unset, then zeros
+        let mut account = testing_env.svm.get_account(&testing_env.config);
+        account.data.truncate(8 + 32 + 1 + 32 + 2 + 2);
+        account.data.resize(8 + BASELINE_CONFIG_SIZE, 0);
+
+        testing_env.svm.set_account(&testing_env.config, account);
+
+        testing_env
+            .svm
+            .execute_actions(&[&BackfillMarketAction::new(&testing_env)])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert!(market.is_backfilled());
+        assert_eq!(market.fees.referral_fee_share, config.referral_fee_share);
+    }
+
+    #[test]
+    fn backfill_market_with_invalid_config() {
+        let (mut testing_env, _) = setup_legacy_market();
+
+        // Copy of the config of the market at another address
+        let config = testing_env.svm.get_account(&testing_env.config);
+        let other_config = make_address("other_config");
+
+        testing_env.svm.set_account(&other_config, config);
+
+        let mut action = BackfillMarketAction::new(&testing_env);
+        action.config = other_config;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidConfigAccount);
+    }
+
+    #[test]
+    fn backfill_up_to_date_market() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let action = BackfillMarketAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::MarketUpToDate);
+    }
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQueuedBuyOrderCancellationEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{BuyQueue, Market, QueuedBuyOrder, BUY_QUEUE_PDA_SEED, QUEUED_BUY_ORDER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelQueuedBuyOrder<'info> {
+    #[account(has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [BUY_QUEUE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buy_queue.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buy_queue: Account<'info, BuyQueue>,
+
+    #[account(
+        mut,
+        seeds = [
+            QUEUED_BUY_ORDER_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            queued_buy_order.owner.as_ref()
+        ],
+        bump = queued_buy_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = owner @ TokenMillError::InvalidAuthority
+    )]
+    pub queued_buy_order: Account<'info, QueuedBuyOrder>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buy_queue,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buy_queue_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = quote_token_mint)]
+    pub owner_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub owner: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraws the quote of the order before `open_slot`, or once `close_slot` is reached if it
+/// wasn't filled. The order keeps its place in the queue with no quote left, and is closed by
+/// `fill_queued_buy_order`.
+pub fn handler(ctx: Context<CancelQueuedBuyOrder>) -> Result<()> {
+    let buy_queue = &mut ctx.accounts.buy_queue;
+    let queued_buy_order = &mut ctx.accounts.queued_buy_order;
+    let current_slot = get_clock()?.slot;
+
+    require!(
+        !buy_queue.is_open(current_slot) || buy_queue.is_closed(current_slot),
+        TokenMillError::BuyQueueOpen
+    );
+
+    let quote_amount = queued_buy_order.quote_amount;
+
+    require!(quote_amount > 0, TokenMillError::InvalidAmount);
+
+    buy_queue.cancel(quote_amount);
+    queued_buy_order.quote_amount = 0;
+
+    let market_key = ctx.accounts.market.key();
+    let buy_queue_seeds = [
+        BUY_QUEUE_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        &[buy_queue.bump],
+    ];
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        buy_queue.to_account_info(),
+        &ctx.accounts.buy_queue_quote_token_ata,
+        &ctx.accounts.owner_quote_token_account,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+        &buy_queue_seeds,
+    )?;
+
+    emit_cpi!(TokenMillQueuedBuyOrderCancellationEvent {
+        market: market_key,
+        owner: ctx.accounts.owner.key(),
+        quote_amount_withdrawn: quote_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuyQueue, QueuedBuyOrder};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CancelQueuedBuyOrderAction, CreateBuyQueueAction, PauseUntilAction,
+            PlaceQueuedBuyOrderAction, TokenMillEnv, UpdateMaxPauseDurationAction,
+        },
+        make_address, TokenMillError,
+    };
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env() -> (TokenMillEnv, PlaceQueuedBuyOrderAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION),
+                &PauseUntilAction::new(&testing_env, open_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateBuyQueueAction::new(
+                &testing_env,
+                1_000_000_000,
+                open_slot,
+            )])
+            .unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let place_action = PlaceQueuedBuyOrderAction::new(&testing_env, bob, 600_000_000);
+
+        testing_env.svm.execute_actions(&[&place_action]).unwrap();
+
+        (testing_env, place_action)
+    }
+
+    #[test]
+    fn cancel_queued_buy_order() {
+        let (mut testing_env, place_action) = setup_env();
+
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.signer);
+
+        let action = CancelQueuedBuyOrderAction::new(&testing_env, place_action.signer);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&place_action.quote_token_mint, &place_action.signer)
+                - quote_balance_before,
+            600_000_000
+        );
+
+        // The order keeps its place in the queue
+        let queued_buy_order = testing_env
+            .svm
+            .get_parsed_account::<QueuedBuyOrder>(&action.queued_buy_order);
+
+        assert_eq!(queued_buy_order.sequence, 0);
+        assert_eq!(queued_buy_order.quote_amount, 0);
+
+        let buy_queue = testing_env
+            .svm
+            .get_parsed_account::<BuyQueue>(&action.buy_queue);
+
+        assert_eq!(buy_queue.order_count, 1);
+        assert_eq!(buy_queue.total_quote_queued, 0);
+    }
+
+    #[test]
+    fn cancel_queued_buy_order_after_open_slot() {
+        let (mut testing_env, place_action) = setup_env();
+
+        let buy_queue = testing_env
+            .svm
+            .get_parsed_account::<BuyQueue>(&place_action.buy_queue);
+
+        testing_env.svm.warp_to_slot(buy_queue.open_slot);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&CancelQueuedBuyOrderAction::new(
+                &testing_env,
+                place_action.signer,
+            )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::BuyQueueOpen
+        );
+    }
+
+    #[test]
+    fn cancel_queued_buy_order_with_invalid_signer() {
+        let (mut testing_env, place_action) = setup_env();
+
+        let mut action = CancelQueuedBuyOrderAction::new(&testing_env, place_action.signer);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+        action.owner_quote_token_ata = testing_env
+            .svm
+            .get_ata_address(&place_action.quote_token_mint, &make_address("mallory"));
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidAuthority
+        );
+    }
+}
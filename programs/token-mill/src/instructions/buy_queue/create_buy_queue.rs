@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillBuyQueueCreationEvent,
+    state::{BuyQueue, Market, MarketPauser, TokenMillConfig, BUY_QUEUE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateBuyQueue<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BuyQueue::INIT_SPACE,
+        seeds = [BUY_QUEUE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub buy_queue: Account<'info, BuyQueue>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buy_queue,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buy_queue_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Opens the buy queue of a market that isn't trading yet, its prices unset or a pause ongoing.
+/// The market stays paused by the queue past `open_slot`, at most `max_pause_duration` slots from
+/// now, until the queued orders are filled or another `max_pause_duration` slots passed.
+pub fn handler(ctx: Context<CreateBuyQueue>, max_quote_amount: u64, open_slot: u64) -> Result<()> {
+    let current_slot = get_clock()?.slot;
+    let max_pause_duration = ctx.accounts.config.max_pause_duration;
+
+    require!(
+        open_slot > current_slot && open_slot <= current_slot + max_pause_duration,
+        TokenMillError::InvalidPauseDuration
+    );
+
+    let close_slot = open_slot + max_pause_duration;
+
+    {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.are_prices_set() || market.is_paused(current_slot),
+            TokenMillError::MarketAlreadyOpen
+        );
+
+        market.pause(MarketPauser::BuyQueue)?;
+    }
+
+    ctx.accounts.buy_queue.initialize(
+        ctx.bumps.buy_queue,
+        ctx.accounts.market.key(),
+        max_quote_amount,
+        open_slot,
+        close_slot,
+    )?;
+
+    emit_cpi!(TokenMillBuyQueueCreationEvent {
+        market: ctx.accounts.market.key(),
+        buy_queue: ctx.accounts.buy_queue.key(),
+        max_quote_amount,
+        open_slot,
+        close_slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuyQueue, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateBuyQueueAction, PauseUntilAction, SwapAction, TokenMillEnv,
+            UpdateMaxPauseDurationAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env() -> (TokenMillEnv, CreateBuyQueueAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION),
+                &PauseUntilAction::new(&testing_env, open_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateBuyQueueAction::new(&testing_env, 1_000_000_000, open_slot);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn create_buy_queue() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let buy_queue = testing_env
+            .svm
+            .get_parsed_account::<BuyQueue>(&action.buy_queue);
+
+        assert_eq!(buy_queue.market, action.market);
+        assert_eq!(buy_queue.max_quote_amount, action.max_quote_amount);
+        assert_eq!(buy_queue.open_slot, action.open_slot);
+        assert_eq!(buy_queue.close_slot, action.open_slot + MAX_PAUSE_DURATION);
+
+        // Swaps stay halted by the queue past the open slot
+        testing_env.svm.warp_to_slot(action.open_slot);
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.is_paused(action.open_slot));
+
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000,
+            u64::MAX,
+            None,
+        )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::MarketPaused
+        );
+    }
+
+    #[test]
+    fn create_buy_queue_on_open_market() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+        let action = CreateBuyQueueAction::new(&testing_env, 1_000_000_000, open_slot);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::MarketAlreadyOpen
+        );
+    }
+
+    #[test]
+    fn create_buy_queue_above_max_pause_duration() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.open_slot += 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidPauseDuration
+        );
+    }
+
+    #[test]
+    fn create_buy_queue_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidAuthority
+        );
+    }
+}
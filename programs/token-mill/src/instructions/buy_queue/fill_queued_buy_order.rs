@@ -0,0 +1,655 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQueuedBuyOrderFillEvent,
+    manager::{
+        policy_manager::ValidateSwapArgs,
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::{get_transfer_fee, get_transfer_inverse_fee, transfer_from_pda},
+    },
+    state::{
+        BuyQueue, Denylist, LaunchPurchase, Market, MarketOracle, MarketStats, QueuedBuyOrder,
+        TokenMillConfig, BUY_QUEUE_PDA_SEED, LAUNCH_PURCHASE_PDA_SEED, QUEUED_BUY_ORDER_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FillQueuedBuyOrder<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [BUY_QUEUE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buy_queue.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buy_queue: Account<'info, BuyQueue>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            QUEUED_BUY_ORDER_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = queued_buy_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = owner @ TokenMillError::InvalidAuthority
+    )]
+    pub queued_buy_order: Account<'info, QueuedBuyOrder>,
+
+    /// CHECK: Owner of the order, checked against it. Gets back the rent of the order it paid for
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// Required while the launch window of the market is open
+    #[account(
+        mut,
+        seeds = [
+            LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump = launch_purchase.bump
+    )]
+    pub launch_purchase: Option<Account<'info, LaunchPurchase>>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buy_queue,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buy_queue_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = base_token_program
+    )]
+    pub owner_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = quote_token_program
+    )]
+    pub owner_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the fill once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Records the price of the market before the fill when provided, required by dynamic fee
+    /// markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the fill into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+}
+
+/// Permissionless crank filling the next order of the queue between `open_slot` and `close_slot`,
+/// ahead of the swaps held by the queue. The pro rata share of the order is spent on the curve, down to the
+/// base amount the owner can still buy under the swap size, launch window and wallet holding caps,
+/// and the rest of its quote is refunded. The owner of the order is checked and reported as the
+/// trader.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, FillQueuedBuyOrder<'info>>) -> Result<()> {
+    let current_slot = get_clock()?.slot;
+    let timestamp = get_clock()?.unix_timestamp;
+
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let owner = ctx.accounts.owner.key();
+
+    swap_accounts.check_trader(&owner)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    require!(
+        ctx.accounts.buy_queue.is_open(current_slot),
+        TokenMillError::BuyQueueNotOpen
+    );
+    require!(
+        !ctx.accounts.buy_queue.is_closed(current_slot),
+        TokenMillError::BuyQueueClosed
+    );
+
+    let quote_amount_queued = ctx.accounts.queued_buy_order.quote_amount;
+    let fill_amount = ctx
+        .accounts
+        .buy_queue
+        .fill(&ctx.accounts.queued_buy_order)?;
+
+    let quote_token_mint = &ctx.accounts.quote_token_mint;
+    let curve_amount = fill_amount - get_transfer_fee(quote_token_mint, fill_amount)?;
+
+    let (outcome, market_seeds, max_buy_per_wallet, swap_amount_type) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused_except_buy_queue(current_slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+        market.apply_scheduled_updates(current_slot, timestamp);
+
+        let max_swap_base_amount = swap_accounts.before_swap(market, SwapType::Buy)?;
+
+        let max_buy_per_wallet = market.get_max_buy_per_wallet(timestamp);
+
+        let remaining_wallet_buy = match max_buy_per_wallet {
+            Some(max_buy_per_wallet) => Some(
+                max_buy_per_wallet.saturating_sub(
+                    ctx.accounts
+                        .launch_purchase
+                        .as_ref()
+                        .ok_or(TokenMillError::InvalidLaunchPurchase)?
+                        .base_amount,
+                ),
+            ),
+            None => None,
+        };
+
+        let max_base_amount = [
+            max_swap_base_amount,
+            remaining_wallet_buy,
+            market.get_max_wallet_holding()?.map(|max_wallet_holding| {
+                max_wallet_holding.saturating_sub(ctx.accounts.owner_base_token_ata.amount)
+            }),
+        ]
+        .into_iter()
+        .flatten()
+        .min();
+
+        let base_amount_out = if curve_amount > 0 {
+            swap_manager::get_swap_outcome(
+                market,
+                SwapType::Buy,
+                SwapAmountType::ExactInput,
+                curve_amount,
+                None,
+                current_slot,
+                timestamp,
+            )?
+            .base_amount
+        } else {
+            0
+        };
+
+        // Clipped to the caps of the owner, the rest of the order is refunded
+        let (swap_amount_type, amount) = match max_base_amount {
+            Some(max_base_amount) if base_amount_out > max_base_amount => {
+                (SwapAmountType::ExactOutput, max_base_amount)
+            }
+            _ => (SwapAmountType::ExactInput, curve_amount),
+        };
+
+        let outcome = if amount > 0 {
+            let outcome = swap_manager::swap(
+                market,
+                SwapType::Buy,
+                swap_amount_type,
+                amount,
+                None,
+                current_slot,
+                timestamp,
+            )?;
+
+            swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+            outcome
+        } else {
+            SwapOutcome::default()
+        };
+
+        (
+            outcome,
+            market.seeds(),
+            max_buy_per_wallet,
+            swap_amount_type,
+        )
+    };
+
+    let SwapOutcome {
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+        quote_refund,
+        ..
+    } = outcome;
+
+    if let Some(max_buy_per_wallet) = max_buy_per_wallet {
+        ctx.accounts
+            .launch_purchase
+            .as_mut()
+            .ok_or(TokenMillError::InvalidLaunchPurchase)?
+            .record(base_amount, max_buy_per_wallet)?;
+    }
+
+    // Grossed up for the market vault to receive `quote_amount`, within the share of the order
+    let quote_amount_in = if quote_amount > 0 {
+        (quote_amount + get_transfer_inverse_fee(quote_token_mint, quote_amount)?).min(fill_amount)
+    } else {
+        0
+    };
+    let quote_amount_refunded = quote_amount_queued - quote_amount_in;
+
+    let market_key = ctx.accounts.market.key();
+    let buy_queue_seeds = [
+        BUY_QUEUE_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        &[ctx.accounts.buy_queue.bump],
+    ];
+    let seeds = market_seeds.signer_seeds();
+
+    if quote_amount_in > 0 {
+        transfer_from_pda(
+            quote_token_mint,
+            ctx.accounts.buy_queue.to_account_info(),
+            &ctx.accounts.buy_queue_quote_token_ata,
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            quote_amount_in,
+            &buy_queue_seeds,
+        )?;
+    }
+
+    if base_amount > 0 {
+        transfer_from_pda(
+            &ctx.accounts.base_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_base_token_ata,
+            &ctx.accounts.owner_base_token_ata,
+            &ctx.accounts.base_token_program,
+            base_amount,
+            &seeds,
+        )?;
+    }
+
+    if quote_refund > 0 {
+        transfer_from_pda(
+            quote_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.owner_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            quote_refund,
+            &seeds,
+        )?;
+    }
+
+    if base_amount > 0 {
+        swap_accounts.validate(&ValidateSwapArgs {
+            config: ctx.accounts.config.key(),
+            market: market_key,
+            user: owner,
+            swap_type: SwapType::Buy,
+            swap_amount_type,
+            base_amount,
+            quote_amount,
+        })?;
+
+        swap_accounts.settle(
+            revenue_report.as_mut(),
+            owner,
+            SwapType::Buy,
+            &outcome,
+            0,
+            &seeds,
+        )?;
+    }
+
+    if quote_amount_refunded > 0 {
+        transfer_from_pda(
+            quote_token_mint,
+            ctx.accounts.buy_queue.to_account_info(),
+            &ctx.accounts.buy_queue_quote_token_ata,
+            &ctx.accounts.owner_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            quote_amount_refunded,
+            &buy_queue_seeds,
+        )?;
+    }
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillQueuedBuyOrderFillEvent {
+        market: market_key,
+        owner: ctx.accounts.owner.key(),
+        sequence: ctx.accounts.queued_buy_order.sequence,
+        base_amount,
+        quote_amount,
+        quote_amount_refunded,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BuyQueue;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, AddToDenylistAction, CreateBuyQueueAction, CreateDenylistAction,
+            FillQueuedBuyOrderAction, PauseUntilAction, PlaceQueuedBuyOrderAction, TokenMillEnv,
+            UpdateMaxPauseDurationAction, UpdateMaxWalletHoldingAction, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, TokenMillError,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env(max_quote_amount: u64) -> (TokenMillEnv, CreateBuyQueueAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION),
+                &PauseUntilAction::new(&testing_env, open_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let create_action = CreateBuyQueueAction::new(&testing_env, max_quote_amount, open_slot);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        for (owner, quote_amount) in [("bob", 600_000_000), ("carol", 400_000_000)] {
+            let owner = testing_env.svm.change_payer(owner);
+
+            testing_env
+                .svm
+                .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                    &testing_env,
+                    owner,
+                    quote_amount,
+                )])
+                .unwrap();
+        }
+
+        (testing_env, create_action)
+    }
+
+    fn fill(testing_env: &mut TokenMillEnv, owner: Pubkey) -> Result<(), TokenMillError> {
+        testing_env.svm.change_payer("carol");
+
+        let action = FillQueuedBuyOrderAction::new(testing_env, owner);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        if result.is_ok() {
+            Ok(())
+        } else {
+            Err(tm_parse_error(result).unwrap())
+        }
+    }
+
+    #[test]
+    fn fill_queued_buy_orders() {
+        let (mut testing_env, create_action) = setup_env(1_000_000_000);
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let (bob, carol) = (make_address("bob"), make_address("carol"));
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        // Filled in placement order
+        assert_eq!(
+            fill(&mut testing_env, carol),
+            Err(TokenMillError::InvalidFillSequence)
+        );
+
+        let bob_base_balance_before = testing_env.svm.get_balance(&base_token_mint, &bob);
+        let carol_base_balance_before = testing_env.svm.get_balance(&base_token_mint, &carol);
+
+        assert_eq!(fill(&mut testing_env, bob), Ok(()));
+        assert_eq!(fill(&mut testing_env, carol), Ok(()));
+
+        let bob_base_amount =
+            testing_env.svm.get_balance(&base_token_mint, &bob) - bob_base_balance_before;
+        let carol_base_amount =
+            testing_env.svm.get_balance(&base_token_mint, &carol) - carol_base_balance_before;
+
+        // The earlier order fills lower on the curve
+        assert!(bob_base_amount * 400 > carol_base_amount * 600);
+
+        let buy_queue = testing_env
+            .svm
+            .get_parsed_account::<BuyQueue>(&create_action.buy_queue);
+
+        assert!(buy_queue.is_filled());
+        assert!(!testing_env
+            .svm
+            .account_exists(&FillQueuedBuyOrderAction::new(&testing_env, bob).queued_buy_order));
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &create_action.buy_queue),
+            0
+        );
+    }
+
+    #[test]
+    fn fill_queued_buy_order_refunds_rent_to_owner() {
+        let (mut testing_env, create_action) = setup_env(1_000_000_000);
+
+        let bob = make_address("bob");
+        let action = FillQueuedBuyOrderAction::new(&testing_env, bob);
+
+        let order_rent = testing_env
+            .svm
+            .get_account(&action.queued_buy_order)
+            .lamports;
+        let lamports_before = testing_env.svm.get_account(&bob).lamports;
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        assert_eq!(fill(&mut testing_env, bob), Ok(()));
+
+        // The cranker doesn't collect the rent paid by the owner
+        assert_eq!(
+            testing_env.svm.get_account(&bob).lamports,
+            lamports_before + order_rent
+        );
+    }
+
+    #[test]
+    fn fill_queued_buy_orders_pro_rata() {
+        let (mut testing_env, create_action) = setup_env(500_000_000);
+
+        let bob = make_address("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&create_action.quote_token_mint, &bob);
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        assert_eq!(fill(&mut testing_env, bob), Ok(()));
+
+        // Half of the queue is spent, the rest is refunded
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &bob)
+                - quote_balance_before
+                >= 300_000_000
+        );
+
+        assert_eq!(fill(&mut testing_env, make_address("carol")), Ok(()));
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &create_action.buy_queue),
+            0
+        );
+    }
+
+    #[test]
+    fn fill_queued_buy_order_above_wallet_holding_cap() {
+        let (mut testing_env, create_action) = setup_env(1_000_000_000);
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxWalletHoldingAction::new(&testing_env, 100)])
+            .unwrap();
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let bob = make_address("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&create_action.quote_token_mint, &bob);
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        assert_eq!(fill(&mut testing_env, bob), Ok(()));
+
+        // Clipped to the cap, the rest of the order is refunded
+        assert_eq!(
+            testing_env.svm.get_balance(&base_token_mint, &bob),
+            DEFAULT_TOTAL_SUPPLY / 100
+        );
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &bob)
+                > quote_balance_before
+        );
+    }
+
+    #[test]
+    fn fill_queued_buy_order_of_denied_owner() {
+        let (mut testing_env, create_action) = setup_env(1_000_000_000);
+
+        let bob = make_address("bob");
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateDenylistAction::new(), &AddToDenylistAction::new(bob)])
+            .unwrap();
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+        testing_env.svm.change_payer("carol");
+
+        let action = FillQueuedBuyOrderAction::new(&testing_env, bob).with_denylist();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletDenied);
+    }
+
+    #[test]
+    fn fill_queued_buy_order_before_open_slot() {
+        let (mut testing_env, _) = setup_env(1_000_000_000);
+
+        assert_eq!(
+            fill(&mut testing_env, make_address("bob")),
+            Err(TokenMillError::BuyQueueNotOpen)
+        );
+    }
+}
@@ -0,0 +1,11 @@
+pub mod cancel_queued_buy_order;
+pub mod create_buy_queue;
+pub mod fill_queued_buy_order;
+pub mod open_buy_queue;
+pub mod place_queued_buy_order;
+
+pub use cancel_queued_buy_order::*;
+pub use create_buy_queue::*;
+pub use fill_queued_buy_order::*;
+pub use open_buy_queue::*;
+pub use place_queued_buy_order::*;
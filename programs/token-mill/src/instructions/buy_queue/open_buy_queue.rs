@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillBuyQueueOpenEvent,
+    state::{BuyQueue, Market, MarketPauser, BUY_QUEUE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenBuyQueue<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        seeds = [BUY_QUEUE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buy_queue.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buy_queue: Account<'info, BuyQueue>,
+}
+
+/// Permissionless, lifts the pause held by the buy queue once all its orders are filled or
+/// `close_slot` is reached, opening the market to swaps. Unfilled orders are then refunded through
+/// `cancel_queued_buy_order`.
+pub fn handler(ctx: Context<OpenBuyQueue>) -> Result<()> {
+    let buy_queue = &ctx.accounts.buy_queue;
+    let current_slot = get_clock()?.slot;
+
+    require!(
+        buy_queue.is_open(current_slot),
+        TokenMillError::BuyQueueNotOpen
+    );
+    require!(
+        buy_queue.is_filled() || buy_queue.is_closed(current_slot),
+        TokenMillError::PendingQueuedBuyOrders
+    );
+
+    ctx.accounts
+        .market
+        .load_mut()?
+        .unpause(MarketPauser::BuyQueue)?;
+
+    emit_cpi!(TokenMillBuyQueueOpenEvent {
+        market: ctx.accounts.market.key(),
+        total_quote_queued: buy_queue.total_quote_queued,
+        unfilled_order_count: buy_queue.order_count - buy_queue.next_fill_sequence,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CancelQueuedBuyOrderAction, CreateBuyQueueAction,
+            FillQueuedBuyOrderAction, OpenBuyQueueAction, PauseUntilAction,
+            PlaceQueuedBuyOrderAction, SwapAction, TokenMillEnv, UpdateMaxPauseDurationAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env() -> (TokenMillEnv, CreateBuyQueueAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION),
+                &PauseUntilAction::new(&testing_env, open_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let create_action = CreateBuyQueueAction::new(&testing_env, 1_000_000_000, open_slot);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                bob,
+                600_000_000,
+            )])
+            .unwrap();
+
+        (testing_env, create_action)
+    }
+
+    #[test]
+    fn open_buy_queue() {
+        let (mut testing_env, create_action) = setup_env();
+
+        let action = OpenBuyQueueAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::BuyQueueNotOpen
+        );
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::PendingQueuedBuyOrders
+        );
+
+        testing_env.svm.change_payer("carol");
+
+        testing_env
+            .svm
+            .execute_actions(&[&FillQueuedBuyOrderAction::new(
+                &testing_env,
+                make_address("bob"),
+            )])
+            .unwrap();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(!market.is_paused(create_action.open_slot));
+
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000,
+            u64::MAX,
+            None,
+        )]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn open_buy_queue_after_close_slot() {
+        let (mut testing_env, create_action) = setup_env();
+
+        let close_slot = create_action.open_slot + MAX_PAUSE_DURATION;
+
+        testing_env.svm.warp_to_slot(close_slot);
+
+        testing_env.svm.change_payer("carol");
+
+        // Orders left unfilled are refunded instead
+        let result = testing_env
+            .svm
+            .execute_actions(&[&FillQueuedBuyOrderAction::new(
+                &testing_env,
+                make_address("bob"),
+            )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::BuyQueueClosed
+        );
+
+        let action = OpenBuyQueueAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(!market.is_paused(close_slot));
+
+        let bob = testing_env.svm.change_payer("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&create_action.quote_token_mint, &bob);
+
+        testing_env
+            .svm
+            .execute_actions(&[&CancelQueuedBuyOrderAction::new(&testing_env, bob)])
+            .unwrap();
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &bob)
+                - quote_balance_before,
+            600_000_000
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQueuedBuyOrderPlacementEvent,
+    manager::token_manager::{get_transfer_fee, transfer_from_eoa},
+    state::{BuyQueue, Market, QueuedBuyOrder, BUY_QUEUE_PDA_SEED, QUEUED_BUY_ORDER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceQueuedBuyOrder<'info> {
+    #[account(has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [BUY_QUEUE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buy_queue.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buy_queue: Account<'info, BuyQueue>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + QueuedBuyOrder::INIT_SPACE,
+        seeds = [
+            QUEUED_BUY_ORDER_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub queued_buy_order: Account<'info, QueuedBuyOrder>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buy_queue,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buy_queue_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = quote_token_mint)]
+    pub user_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Escrows `quote_amount` in the buy queue until `open_slot`, taking the next place in the queue.
+/// A wallet places a single order per queue, see `BuyQueue::place` for the limits of the queue.
+pub fn handler(ctx: Context<PlaceQueuedBuyOrder>, quote_amount: u64) -> Result<()> {
+    let buy_queue = &mut ctx.accounts.buy_queue;
+
+    require!(
+        !buy_queue.is_open(get_clock()?.slot),
+        TokenMillError::BuyQueueOpen
+    );
+
+    // The queue is credited with the amount received by its vault
+    let quote_amount_received =
+        quote_amount - get_transfer_fee(&ctx.accounts.quote_token_mint, quote_amount)?;
+
+    require!(quote_amount_received > 0, TokenMillError::InvalidAmount);
+
+    transfer_from_eoa(
+        &ctx.accounts.quote_token_mint,
+        &ctx.accounts.user,
+        &ctx.accounts.user_quote_token_account,
+        &ctx.accounts.buy_queue_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+    )?;
+
+    let sequence = buy_queue.place(quote_amount_received)?;
+
+    ctx.accounts.queued_buy_order.initialize(
+        ctx.bumps.queued_buy_order,
+        ctx.accounts.market.key(),
+        ctx.accounts.user.key(),
+        sequence,
+        quote_amount_received,
+    )?;
+
+    emit_cpi!(TokenMillQueuedBuyOrderPlacementEvent {
+        market: ctx.accounts.market.key(),
+        owner: ctx.accounts.user.key(),
+        sequence,
+        quote_amount: quote_amount_received,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuyQueue, QueuedBuyOrder, MAX_QUEUED_BUY_ORDERS};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateBuyQueueAction, PauseUntilAction, PlaceQueuedBuyOrderAction,
+            TokenMillEnv, UpdateMaxPauseDurationAction,
+        },
+        make_address, TokenMillError,
+    };
+
+    const MAX_PAUSE_DURATION: u64 = 1_000;
+
+    fn setup_env() -> (TokenMillEnv, CreateBuyQueueAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let open_slot = testing_env.svm.get_slot() + MAX_PAUSE_DURATION;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(MAX_PAUSE_DURATION),
+                &PauseUntilAction::new(&testing_env, open_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateBuyQueueAction::new(&testing_env, 1_000_000_000, open_slot);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn place_queued_buy_order() {
+        let (mut testing_env, create_action) = setup_env();
+
+        let bob = testing_env.svm.change_payer("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&create_action.quote_token_mint, &bob);
+
+        let action = PlaceQueuedBuyOrderAction::new(&testing_env, bob, 600_000_000);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                make_address("carol"),
+                400_000_000,
+            )])
+            .unwrap();
+
+        let queued_buy_order = testing_env
+            .svm
+            .get_parsed_account::<QueuedBuyOrder>(&action.queued_buy_order);
+
+        assert_eq!(queued_buy_order.owner, bob);
+        assert_eq!(queued_buy_order.sequence, 0);
+        assert_eq!(queued_buy_order.quote_amount, 600_000_000);
+
+        let buy_queue = testing_env
+            .svm
+            .get_parsed_account::<BuyQueue>(&action.buy_queue);
+
+        assert_eq!(buy_queue.order_count, 2);
+        assert_eq!(buy_queue.total_quote_queued, 1_000_000_000);
+
+        assert_eq!(
+            quote_balance_before
+                - testing_env
+                    .svm
+                    .get_balance(&create_action.quote_token_mint, &bob),
+            600_000_000
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&create_action.quote_token_mint, &action.buy_queue),
+            1_000_000_000
+        );
+    }
+
+    #[test]
+    fn place_queued_buy_order_twice() {
+        let (mut testing_env, _) = setup_env();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                bob,
+                600_000_000,
+            )])
+            .unwrap();
+
+        // Already initialized
+        let result = testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                bob,
+                400_000_000,
+            )]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn place_queued_buy_order_after_open_slot() {
+        let (mut testing_env, create_action) = setup_env();
+
+        testing_env.svm.warp_to_slot(create_action.open_slot);
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                bob,
+                600_000_000,
+            )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::BuyQueueOpen
+        );
+    }
+
+    #[test]
+    fn place_queued_buy_order_below_min_amount() {
+        let (mut testing_env, create_action) = setup_env();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&PlaceQueuedBuyOrderAction::new(
+                &testing_env,
+                bob,
+                create_action.max_quote_amount / MAX_QUEUED_BUY_ORDERS - 1,
+            )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::QueuedBuyOrderTooSmall
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillBuybackOrderCancellationEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{BuybackOrder, Market, BUYBACK_ORDER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelBuybackOrder<'info> {
+    #[account(
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [BUYBACK_ORDER_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buyback_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buyback_order: Account<'info, BuybackOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub buyback_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buyback_order_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = base_token_program
+    )]
+    pub creator_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Closes the buyback order of the market, sending the unspent vault and the bought back tokens to the creator.
+pub fn handler(ctx: Context<CancelBuybackOrder>) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let buyback_order_seeds = [
+        BUYBACK_ORDER_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        &[ctx.accounts.buyback_order.bump],
+    ];
+
+    let base_amount = ctx.accounts.buyback_order_base_token_ata.amount;
+    let quote_amount = ctx.accounts.buyback_order_quote_token_ata.amount;
+
+    if base_amount > 0 {
+        transfer_from_pda(
+            &ctx.accounts.base_token_mint,
+            ctx.accounts.buyback_order.to_account_info(),
+            &ctx.accounts.buyback_order_base_token_ata,
+            &ctx.accounts.creator_base_token_ata,
+            &ctx.accounts.base_token_program,
+            base_amount,
+            &buyback_order_seeds,
+        )?;
+    }
+
+    if quote_amount > 0 {
+        transfer_from_pda(
+            &ctx.accounts.quote_token_mint,
+            ctx.accounts.buyback_order.to_account_info(),
+            &ctx.accounts.buyback_order_quote_token_ata,
+            &ctx.accounts.creator_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            quote_amount,
+            &buyback_order_seeds,
+        )?;
+    }
+
+    emit_cpi!(TokenMillBuybackOrderCancellationEvent {
+        market: market_key,
+        buyback_order: ctx.accounts.buyback_order.key(),
+        base_amount_withdrawn: base_amount,
+        quote_amount_withdrawn: quote_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CancelBuybackOrderAction, PlaceBuybackOrderAction, SwapAction,
+            TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, CancelBuybackOrderAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&PlaceBuybackOrderAction::new(&testing_env, 1_000_000)])
+            .unwrap();
+
+        let action = CancelBuybackOrderAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn cancel_buyback_order() {
+        let (mut testing_env, action) = setup_env();
+
+        let alice = make_address("alice");
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&action.quote_token_mint, &action.buyback_order);
+        let creator_balance_before = testing_env
+            .svm
+            .get_balance(&action.quote_token_mint, &alice);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &alice),
+            creator_balance_before + vault_balance
+        );
+        assert!(!testing_env.svm.account_exists(&action.buyback_order));
+    }
+
+    #[test]
+    fn cancel_buyback_order_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
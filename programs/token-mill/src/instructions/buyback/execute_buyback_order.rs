@@ -0,0 +1,616 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as sysvar_instructions};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillBuybackExecutionEvent,
+    manager::{
+        guard_manager,
+        policy_manager::ValidateSwapArgs,
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::{get_transfer_fee, get_transfer_inverse_fee, transfer_from_pda},
+    },
+    state::{
+        BuybackOrder, Denylist, LaunchPurchase, Market, MarketOracle, MarketStats, TokenMillConfig,
+        BUYBACK_ORDER_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteBuybackOrder<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [BUYBACK_ORDER_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = buyback_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub buyback_order: Account<'info, BuybackOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub buyback_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buyback_order_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Instructions sysvar, see `guard_manager`
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the buyback once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Records the price of the market before the buyback when provided, required by dynamic fee
+    /// markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the buyback into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+
+    /// Launch purchase of the creator, required while the launch window of the market is open
+    #[account(mut, has_one = market @ TokenMillError::InvalidLaunchPurchase)]
+    pub launch_purchase: Option<Account<'info, LaunchPurchase>>,
+}
+
+/// Permissionless crank spending up to `quote_amount` of the order vault on the curve.
+/// The buy must leave the ask price at or below the price floor of the order, and can't share its
+/// transaction with other instructions on the market so that it can't be sandwiched.
+/// Buybacks are funded by the creator of the market, who is checked and reported as the trader.
+/// The bought tokens count towards the launch window cap of the creator and the wallet holding
+/// cap of the order vault, as for a buy of the creator.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteBuybackOrder<'info>>,
+    quote_amount: u64,
+) -> Result<()> {
+    require!(quote_amount > 0, TokenMillError::InvalidAmount);
+
+    guard_manager::check_isolated_call(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.market.key(),
+    )?;
+
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let creator = ctx.accounts.market.load()?.creator;
+
+    swap_accounts.check_trader(&creator)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    // The curve only accounts for the quote tokens moving in the market vault
+    let quote_token_mint = &ctx.accounts.quote_token_mint;
+    let curve_amount = quote_amount - get_transfer_fee(quote_token_mint, quote_amount)?;
+
+    let buyback_order = &mut ctx.accounts.buyback_order;
+
+    let (outcome, market_seeds, max_buy_per_wallet, max_wallet_holding) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused(get_clock()?.slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+
+        let max_base_amount = swap_accounts.before_swap(market, SwapType::Buy)?;
+
+        let outcome = swap_manager::swap(
+            market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            curve_amount,
+            None,
+            get_clock()?.slot,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+        require_gte!(
+            buyback_order.price_floor,
+            market.get_ask_price()?,
+            TokenMillError::BuybackFloorExceeded
+        );
+
+        (
+            outcome,
+            market.seeds(),
+            market.get_max_buy_per_wallet(get_clock()?.unix_timestamp),
+            market.get_max_wallet_holding()?,
+        )
+    };
+
+    // Grossed up for the market vault to receive `quote_amount`, within the amount of the crank
+    let quote_amount_in = (outcome.quote_amount
+        + get_transfer_inverse_fee(quote_token_mint, outcome.quote_amount)?)
+    .min(quote_amount);
+
+    let SwapOutcome {
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+        ..
+    } = outcome;
+
+    buyback_order.record(quote_amount_in, base_amount);
+
+    if let Some(max_buy_per_wallet) = max_buy_per_wallet {
+        let launch_purchase = ctx
+            .accounts
+            .launch_purchase
+            .as_mut()
+            .ok_or(TokenMillError::InvalidLaunchPurchase)?;
+
+        require_keys_eq!(
+            launch_purchase.wallet,
+            creator,
+            TokenMillError::InvalidLaunchPurchase
+        );
+
+        launch_purchase.record(base_amount, max_buy_per_wallet)?;
+    }
+
+    if let Some(max_wallet_holding) = max_wallet_holding {
+        require_gte!(
+            max_wallet_holding,
+            ctx.accounts.buyback_order_base_token_ata.amount + base_amount,
+            TokenMillError::WalletHoldingCapExceeded
+        );
+    }
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        user: creator,
+        swap_type: SwapType::Buy,
+        swap_amount_type: SwapAmountType::ExactInput,
+        base_amount,
+        quote_amount,
+    })?;
+
+    let market_key = ctx.accounts.market.key();
+    let buyback_order_seeds = [
+        BUYBACK_ORDER_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        &[buyback_order.bump],
+    ];
+    let seeds = market_seeds.signer_seeds();
+
+    transfer_from_pda(
+        quote_token_mint,
+        buyback_order.to_account_info(),
+        &ctx.accounts.buyback_order_quote_token_ata,
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        quote_amount_in,
+        &buyback_order_seeds,
+    )?;
+
+    transfer_from_pda(
+        &ctx.accounts.base_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.buyback_order_base_token_ata,
+        &ctx.accounts.base_token_program,
+        base_amount,
+        &seeds,
+    )?;
+
+    swap_accounts.settle(
+        revenue_report.as_mut(),
+        creator,
+        SwapType::Buy,
+        &outcome,
+        0,
+        &seeds,
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillBuybackExecutionEvent {
+        market: market_key,
+        buyback_order: ctx.accounts.buyback_order.key(),
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuybackOrder, Market, RevenueReport};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateRevenueReportAction, ExecuteBuybackOrderAction,
+            PlaceBuybackOrderAction, SwapAction, TokenMillEnv, UpdateMaxWalletHoldingAction,
+            DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, PlaceBuybackOrderAction) {
+        setup_env_with(TokenMillEnv::default())
+    }
+
+    fn setup_env_with(mut testing_env: TokenMillEnv) -> (TokenMillEnv, PlaceBuybackOrderAction) {
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let price_floor = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_ask_price()
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let place_action = PlaceBuybackOrderAction::new(&testing_env, price_floor);
+
+        testing_env.svm.execute_actions(&[&place_action]).unwrap();
+
+        (testing_env, place_action)
+    }
+
+    #[test]
+    fn execute_buyback_order() {
+        let (mut testing_env, place_action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let bob = make_address("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &bob);
+
+        let sell_action = SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            DEFAULT_TOTAL_SUPPLY / 10,
+            0,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&sell_action]).unwrap();
+
+        let quote_amount_received = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &bob)
+            - quote_balance_before;
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.buyback_order);
+
+        let quote_amount = vault_balance.min(quote_amount_received) / 2;
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, quote_amount);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let buyback_order = testing_env
+            .svm
+            .get_parsed_account::<BuybackOrder>(&action.buyback_order);
+
+        assert_eq!(buyback_order.total_quote_amount_spent, quote_amount);
+        assert!(buyback_order.total_base_amount_bought > 0);
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &action.buyback_order),
+            buyback_order.total_base_amount_bought
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.buyback_order),
+            vault_balance - quote_amount
+        );
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.get_ask_price().unwrap() <= buyback_order.price_floor);
+    }
+
+    #[test]
+    fn execute_buyback_order_with_revenue_report() {
+        let (mut testing_env, place_action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let sell_action = SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            DEFAULT_TOTAL_SUPPLY / 10,
+            0,
+            None,
+        );
+        let create_action = CreateRevenueReportAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[&sell_action, &create_action])
+            .unwrap();
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.buyback_order);
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, vault_balance / 10);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let revenue_report = testing_env
+            .svm
+            .get_parsed_account::<RevenueReport>(&create_action.revenue_report);
+
+        // Fees of the buyback are reported as for any swap
+        assert_eq!(action.revenue_report, create_action.revenue_report);
+        assert!(revenue_report.protocol_fees > 0);
+        assert!(revenue_report.creator_fees > 0);
+    }
+
+    #[test]
+    fn execute_buyback_order_above_wallet_holding_cap() {
+        let (mut testing_env, place_action) = setup_env();
+
+        // 0.01% of the supply
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxWalletHoldingAction::new(&testing_env, 1)])
+            .unwrap();
+
+        let bob = testing_env.svm.change_payer("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &bob);
+
+        let sell_action = SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            DEFAULT_TOTAL_SUPPLY / 10,
+            0,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&sell_action]).unwrap();
+
+        let quote_amount_received = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &bob)
+            - quote_balance_before;
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.buyback_order);
+
+        let action = ExecuteBuybackOrderAction::new(
+            &testing_env,
+            vault_balance.min(quote_amount_received) / 2,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletHoldingCapExceeded);
+    }
+
+    #[test]
+    fn execute_buyback_order_with_transfer_fee_quote_token() {
+        // 1% transfer fee
+        let (mut testing_env, place_action) = setup_env_with(
+            TokenMillEnv::new()
+                .with_transfer_fee_quote_token_mint(9, 100)
+                .with_default_market(),
+        );
+
+        testing_env.svm.change_payer("bob");
+
+        let sell_action = SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            DEFAULT_TOTAL_SUPPLY / 10,
+            0,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&sell_action]).unwrap();
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.buyback_order);
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, vault_balance / 10);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let buyback_order = testing_env
+            .svm
+            .get_parsed_account::<BuybackOrder>(&action.buyback_order);
+
+        assert!(buyback_order.total_quote_amount_spent <= vault_balance / 10);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.buyback_order),
+            vault_balance - buyback_order.total_quote_amount_spent
+        );
+
+        // The market vault received the quote amount accounted for by the curve
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.market)
+                >= market.get_curve_proceeds().unwrap() + market.get_pending_fees()
+        );
+    }
+
+    #[test]
+    fn execute_buyback_order_above_price_floor() {
+        let (mut testing_env, place_action) = setup_env();
+
+        let vault_balance = testing_env
+            .svm
+            .get_balance(&place_action.quote_token_mint, &place_action.buyback_order);
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, vault_balance);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::BuybackFloorExceeded);
+    }
+
+    #[test]
+    fn execute_buyback_order_within_swap_transaction() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let sell_action = SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            DEFAULT_TOTAL_SUPPLY / 10,
+            0,
+            None,
+        );
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, 1_000);
+
+        let result = testing_env.svm.execute_actions(&[&sell_action, &action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::DisallowedCallPattern);
+    }
+
+    #[test]
+    fn execute_buyback_order_with_zero_amount() {
+        let (mut testing_env, _) = setup_env();
+
+        let action = ExecuteBuybackOrderAction::new(&testing_env, 0);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod cancel_buyback_order;
+pub mod execute_buyback_order;
+pub mod place_buyback_order;
+
+pub use cancel_buyback_order::*;
+pub use execute_buyback_order::*;
+pub use place_buyback_order::*;
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillBuybackOrderPlacementEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{BuybackOrder, Market, BUYBACK_ORDER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceBuybackOrder<'info> {
+    #[account(
+        mut,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + BuybackOrder::INIT_SPACE,
+        seeds = [BUYBACK_ORDER_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub buyback_order: Account<'info, BuybackOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub buyback_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = buyback_order,
+        associated_token::token_program = quote_token_program
+    )]
+    pub buyback_order_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Places or updates the buyback order of the market, and claims the pending creator fees into its vault.
+pub fn handler(ctx: Context<PlaceBuybackOrder>, price_floor: u64) -> Result<()> {
+    require!(price_floor > 0, TokenMillError::InvalidAmount);
+
+    let buyback_order = &mut ctx.accounts.buyback_order;
+
+    if buyback_order.market == Pubkey::default() {
+        buyback_order.initialize(ctx.bumps.buyback_order, ctx.accounts.market.key())?;
+    }
+
+    buyback_order.price_floor = price_floor;
+
+    let (pending_fees, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        let pending_fees = market.fees.pending_creator_fees;
+        market.fees.pending_creator_fees = 0;
+
+        market.check_fee_withdrawal(ctx.accounts.market_quote_token_ata.amount, pending_fees)?;
+
+        (pending_fees, market.seeds())
+    };
+
+    if pending_fees > 0 {
+        transfer_from_pda(
+            &ctx.accounts.quote_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.buyback_order_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            pending_fees,
+            &market_seeds.signer_seeds(),
+        )?;
+    }
+
+    emit_cpi!(TokenMillBuybackOrderPlacementEvent {
+        market: ctx.accounts.market.key(),
+        buyback_order: ctx.accounts.buyback_order.key(),
+        price_floor,
+        fees_deposited: pending_fees,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BuybackOrder, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, PlaceBuybackOrderAction, SwapAction, TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const PRICE_FLOOR: u64 = 1_000_000;
+
+    fn setup_env() -> (TokenMillEnv, PlaceBuybackOrderAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = PlaceBuybackOrderAction::new(&testing_env, PRICE_FLOOR);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn place_buyback_order() {
+        let (mut testing_env, action) = setup_env();
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&action.market)
+            .fees
+            .pending_creator_fees;
+
+        assert!(pending_creator_fees > 0);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let buyback_order = testing_env
+            .svm
+            .get_parsed_account::<BuybackOrder>(&action.buyback_order);
+
+        assert_eq!(buyback_order.market, action.market);
+        assert_eq!(buyback_order.price_floor, PRICE_FLOOR);
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.buyback_order),
+            pending_creator_fees
+        );
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.fees.pending_creator_fees, 0);
+    }
+
+    #[test]
+    fn update_buyback_order() {
+        let (mut testing_env, mut action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        action.price_floor = PRICE_FLOOR * 2;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let buyback_order = testing_env
+            .svm
+            .get_parsed_account::<BuybackOrder>(&action.buyback_order);
+
+        assert_eq!(buyback_order.price_floor, PRICE_FLOOR * 2);
+    }
+
+    #[test]
+    fn place_buyback_order_with_zero_price_floor() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.price_floor = 0;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+
+    #[test]
+    fn place_buyback_order_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::TokenMillCreatorActivityCreationEvent,
+    state::{CreatorActivity, TokenMillConfig, CREATOR_ACTIVITY_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCreatorActivity<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump,
+        payer = creator,
+        space = 8 + CreatorActivity::INIT_SPACE
+    )]
+    pub creator_activity: Account<'info, CreatorActivity>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the creation counters of the creator on the config, required by the market creations
+/// of configs throttling creators.
+pub fn handler(ctx: Context<CreateCreatorActivity>) -> Result<()> {
+    ctx.accounts.creator_activity.initialize(
+        ctx.bumps.creator_activity,
+        ctx.accounts.config.key(),
+        ctx.accounts.creator.key(),
+    )?;
+
+    emit_cpi!(TokenMillCreatorActivityCreationEvent {
+        config: ctx.accounts.config.key(),
+        creator_activity: ctx.accounts.creator_activity.key(),
+        creator: ctx.accounts.creator.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::TokenMillLaunchPurchaseCreationEvent,
+    state::{LaunchPurchase, Market, LAUNCH_PURCHASE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateLaunchPurchase<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [
+            LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + LaunchPurchase::INIT_SPACE
+    )]
+    pub launch_purchase: Account<'info, LaunchPurchase>,
+
+    /// CHECK: Wallet whose launch purchases are tracked
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, opens the launch purchases of `wallet`, required by its buys while the launch
+/// window of the market is open.
+pub fn handler(ctx: Context<CreateLaunchPurchase>) -> Result<()> {
+    ctx.accounts.launch_purchase.initialize(
+        ctx.bumps.launch_purchase,
+        ctx.accounts.market.key(),
+        ctx.accounts.wallet.key(),
+    )?;
+
+    emit_cpi!(TokenMillLaunchPurchaseCreationEvent {
+        market: ctx.accounts.market.key(),
+        launch_purchase: ctx.accounts.launch_purchase.key(),
+        wallet: ctx.accounts.wallet.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LaunchPurchase, WalletBuyLimit};
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, CreateLaunchPurchaseAction, CreateMarketAction,
+                SetMarketPricesAction, SwapAction, TokenMillEnv,
+            },
+            SwapAmountType, SwapType, TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    const MAX_BUY_PER_WALLET: u64 = 2_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, CreateLaunchPurchaseAction, SwapAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        create_market_action.wallet_buy_limit = Some(WalletBuyLimit {
+            max_buy_per_wallet: MAX_BUY_PER_WALLET,
+            launch_window_seconds: 3_600,
+        });
+
+        let set_market_prices_action = SetMarketPricesAction::new(Curve::default());
+
+        testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge(), &set_market_prices_action])
+            .unwrap();
+
+        testing_env.market = set_market_prices_action.market;
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .create_ata(&bob, &base_token_mint, testing_env.base_token_type)
+            .unwrap();
+        testing_env
+            .svm
+            .create_ata(
+                &testing_env.market,
+                &quote_token_mint,
+                testing_env.quote_token_type,
+            )
+            .unwrap();
+        testing_env
+            .svm
+            .tokens
+            .insert(base_token_mint, testing_env.base_token_type);
+
+        let action = CreateLaunchPurchaseAction::new(&testing_env, bob);
+
+        let mut swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            MAX_BUY_PER_WALLET / 2,
+            u64::MAX,
+            None,
+        );
+
+        swap_action.launch_purchase = action.launch_purchase;
+
+        (testing_env, action, swap_action)
+    }
+
+    #[test]
+    fn buy_within_wallet_limit() {
+        let (mut testing_env, action, swap_action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &swap_action])
+            .unwrap();
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let launch_purchase = testing_env
+            .svm
+            .get_parsed_account::<LaunchPurchase>(&action.launch_purchase);
+
+        assert_eq!(launch_purchase.base_amount, MAX_BUY_PER_WALLET);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::WalletBuyLimitExceeded);
+    }
+
+    #[test]
+    fn buy_without_launch_purchase() {
+        let (mut testing_env, _, mut swap_action) = setup_env();
+
+        swap_action.launch_purchase = crate::ID;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidLaunchPurchase);
+    }
+
+    #[test]
+    fn buy_after_launch_window() {
+        let (mut testing_env, _, mut swap_action) = setup_env();
+
+        testing_env.svm.warp(3_600);
+
+        swap_action.launch_purchase = crate::ID;
+        swap_action.amount = MAX_BUY_PER_WALLET * 2;
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+    }
+}
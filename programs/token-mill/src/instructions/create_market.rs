@@ -11,12 +11,21 @@ use anchor_spl::{
 };
 
 use crate::{
+    clock::get_clock,
     constant::{MAX_BPS, MILL_TOKEN_DECIMALS},
     errors::TokenMillError,
-    events::TokenMillMarketCreationEvent,
-    manager::token_manager::check_mint_extensions,
-    state::{Market, TokenMillConfig},
-    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    events::{TokenMillMarketCreationEvent, TokenMillMarketPriceSetEvent},
+    manager::{
+        curve_manager,
+        policy_manager::{self, ValidateCreateMarketArgs, VALIDATE_CREATE_MARKET_DISCRIMINATOR},
+        token_manager::check_mint_extensions,
+    },
+    state::{
+        CreatorActivity, Market, MintRegistry, TokenMillConfig, WalletBuyLimit,
+        CREATOR_ACTIVITY_PDA_SEED,
+    },
+    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, MINT_REGISTRY_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
 };
 
 #[event_cpi]
@@ -53,6 +62,15 @@ pub struct CreateMarket<'info> {
     )]
     pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
 
+    #[account(
+        init,
+        seeds = [MINT_REGISTRY_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + MintRegistry::INIT_SPACE
+    )]
+    pub mint_registry: Box<Account<'info, MintRegistry>>,
+
     #[account(
         seeds = [
             QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
@@ -72,16 +90,34 @@ pub struct CreateMarket<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token2022>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required once the config throttles market creations, see `CreatorActivity`
+    #[account(
+        mut,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump = creator_activity.bump
+    )]
+    pub creator_activity: Option<Box<Account<'info, CreatorActivity>>>,
 }
 
-pub fn handler(
-    ctx: Context<CreateMarket>,
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateMarket<'info>>,
     name: String,
     symbol: String,
     uri: String,
     total_supply: u64,
     creator_fee_share: u16,
     staking_fee_share: u16,
+    wallet_buy_limit: Option<WalletBuyLimit>,
+    swap_fee_bps: u16,
 ) -> Result<()> {
     let config = &ctx.accounts.config;
 
@@ -91,12 +127,26 @@ pub fn handler(
         TokenMillError::InvalidFeeShare
     );
 
+    config.check_swap_fee_bps(swap_fee_bps)?;
+
     require!(
         check_mint_extensions(&ctx.accounts.quote_token_mint)?,
         TokenMillError::UnsupportedTokenMint
     );
 
-    {
+    CreatorActivity::check(
+        config,
+        ctx.accounts.creator_activity.as_deref_mut(),
+        get_clock()?.unix_timestamp,
+    )?;
+
+    let default_prices = config
+        .default_curve
+        .as_ref()
+        .map(curve_manager::get_prices_from_parameters)
+        .transpose()?;
+
+    let creation_bond = {
         let mut market = ctx.accounts.market.load_init()?;
 
         market.initialize(
@@ -109,9 +159,43 @@ pub fn handler(
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            config.referral_fee_share,
+        )?;
+
+        market.swap_fee_bps = swap_fee_bps;
+
+        if let Some(wallet_buy_limit) = &wallet_buy_limit {
+            market.set_wallet_buy_limit(wallet_buy_limit, get_clock()?.unix_timestamp)?;
+        }
+
+        if let Some((bid_prices, ask_prices)) = default_prices {
+            market.check_and_set_prices(bid_prices, ask_prices, &config.price_bounds)?;
+        }
+
+        market.post_creation_bond(&config.creation_bond, get_clock()?.unix_timestamp)
+    };
+
+    if creation_bond > 0 {
+        invoke(
+            &transfer(
+                ctx.accounts.creator.key,
+                &ctx.accounts.market.key(),
+                creation_bond,
+            ),
+            &[
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.market.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
         )?;
     }
 
+    ctx.accounts.mint_registry.initialize(
+        ctx.bumps.mint_registry,
+        ctx.accounts.base_token_mint.key(),
+        ctx.accounts.market.key(),
+    )?;
+
     let base_token_mint_key = ctx.accounts.base_token_mint.key();
     let market_seeds = [
         MARKET_PDA_SEED.as_bytes(),
@@ -125,6 +209,21 @@ pub fn handler(
     ctx.accounts
         .mint_supply_and_remove_authority(total_supply, &market_seeds)?;
 
+    policy_manager::validate(
+        config.policy_program,
+        ctx.accounts.policy_program.as_ref(),
+        ctx.remaining_accounts,
+        VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+        &ValidateCreateMarketArgs {
+            config: config.key(),
+            market: ctx.accounts.market.key(),
+            creator: ctx.accounts.creator.key(),
+            base_token_mint: ctx.accounts.base_token_mint.key(),
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            total_supply,
+        },
+    )?;
+
     emit_cpi!(TokenMillMarketCreationEvent {
         config: ctx.accounts.config.key(),
         market: ctx.accounts.market.key(),
@@ -136,8 +235,18 @@ pub fn handler(
         referral_fee_share: config.referral_fee_share,
         creator_fee_share,
         staking_fee_share,
+        wallet_buy_limit,
+        swap_fee_bps,
     });
 
+    if let Some((bid_prices, ask_prices)) = default_prices {
+        emit_cpi!(TokenMillMarketPriceSetEvent {
+            market: ctx.accounts.market.key(),
+            bid_prices,
+            ask_prices,
+        });
+    }
+
     Ok(())
 }
 
@@ -230,12 +339,13 @@ impl<'info> CreateMarket<'info> {
 mod tests {
     use crate::{
         constant::{INTERVAL_NUMBER, MAX_TOTAL_SUPPLY},
-        Market,
+        Market, MintRegistry,
     };
     use joelana_test_utils::joelana_env::{
         actions::token_mill::{
             tm_parse_error, CreateMarketAction, CreateQuoteAssetBadgeAction, TokenMillEnv,
-            UpdateQuoteAssetBadgeAction, DEFAULT_TOTAL_SUPPLY,
+            UpdateDefaultCurveAction, UpdateQuoteAssetBadgeAction, UpdateSwapFeeBoundsAction,
+            DEFAULT_TOTAL_SUPPLY,
         },
         TokenMillError, TokenType,
     };
@@ -283,6 +393,73 @@ mod tests {
         assert_eq!(market.quote_token_decimals, quote_token_decimals);
         assert_eq!(market.total_supply, DEFAULT_TOTAL_SUPPLY);
         assert_eq!(market.base_reserve, DEFAULT_TOTAL_SUPPLY);
+
+        let mint_registry = testing_env
+            .svm
+            .get_parsed_account::<MintRegistry>(&action.mint_registry);
+
+        assert_eq!(mint_registry.base_token_mint, action.base_token_mint);
+        assert_eq!(mint_registry.active_market, action.market);
+    }
+
+    #[test]
+    fn create_market_with_default_curve() {
+        let (mut testing_env, action) = setup_env(TokenType::Token, 6);
+
+        let update_default_curve_action = UpdateDefaultCurveAction::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&update_default_curve_action])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+        let default_curve = update_default_curve_action.new_default_curve.unwrap();
+
+        assert!(market.are_prices_set());
+        assert_eq!(market.ask_prices[0], default_curve.start_price);
+        assert_eq!(
+            market.ask_prices[INTERVAL_NUMBER as usize],
+            default_curve.end_price
+        );
+    }
+
+    #[test]
+    fn create_market_with_swap_fee() {
+        let (mut testing_env, mut action) = setup_env(TokenType::Token, 6);
+
+        action.swap_fee_bps = 100;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidSwapFee);
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateSwapFeeBoundsAction::new(30, 100)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.swap_fee_bps, 100);
     }
 
     #[test]
@@ -1,24 +1,41 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, program_pack::Pack, system_instruction::transfer},
+    system_program::{create_account, CreateAccount},
+};
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{self, AssociatedToken},
     metadata::{
         create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
         Metadata,
     },
-    token::{
-        mint_to, set_authority, spl_token::instruction::AuthorityType, Mint, MintTo, SetAuthority,
-        Token, TokenAccount,
+    token::{self, spl_token},
+    token_2022::{
+        self,
+        spl_token_2022::{self, extension::ExtensionType, instruction::AuthorityType},
+    },
+    token_interface::{
+        self, metadata_pointer_initialize, token_metadata_initialize, InitializeMint2,
+        MetadataPointerInitialize, Mint as MintInterface, TokenInterface, TokenMetadataInitialize,
     },
-    token_interface::Mint as MintInterface,
 };
 
 use crate::{
+    clock::get_clock,
     constant::{MAX_BPS, MILL_TOKEN_DECIMALS},
     errors::TokenMillError,
-    events::TokenMillMarketCreationEvent,
-    manager::token_manager::check_mint_extensions,
-    state::{Market, TokenMillConfig},
-    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    events::{TokenMillMarketCreationEvent, TokenMillMarketPriceSetEvent},
+    manager::{
+        curve_manager,
+        policy_manager::{self, ValidateCreateMarketArgs, VALIDATE_CREATE_MARKET_DISCRIMINATOR},
+        token_manager::check_mint_extensions,
+    },
+    state::{
+        CreatorActivity, Market, MintRegistry, TokenMillConfig, WalletBuyLimit,
+        CREATOR_ACTIVITY_PDA_SEED,
+    },
+    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, MINT_REGISTRY_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
 };
 
 #[event_cpi]
@@ -35,26 +52,26 @@ pub struct CreateMarketWithSpl<'info> {
     )]
     pub market: AccountLoader<'info, Market>,
 
-    #[account(
-        init,
-        payer = creator,
-        mint::authority = market,
-        mint::decimals = MILL_TOKEN_DECIMALS
-    )]
-    pub base_token_mint: Box<Account<'info, Mint>>,
+    /// Created in the handler, as the extensions of the mint depend on the token program
+    #[account(mut)]
+    pub base_token_mint: Signer<'info>,
+
+    /// CHECK: New Metaplex Account being created, only used by SPL mints
+    #[account(mut)]
+    pub base_token_metadata: Option<UncheckedAccount<'info>>,
 
-    /// CHECK: New Metaplex Account being created
+    /// CHECK: Created and checked by the associated token program
     #[account(mut)]
-    pub base_token_metadata: UncheckedAccount<'info>,
+    pub market_base_token_ata: UncheckedAccount<'info>,
 
     #[account(
         init,
+        seeds = [MINT_REGISTRY_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump,
         payer = creator,
-        associated_token::mint = base_token_mint,
-        associated_token::authority = market,
-        associated_token::token_program = token_program
+        space = 8 + MintRegistry::INIT_SPACE
     )]
-    pub market_base_token_ata: Box<Account<'info, TokenAccount>>,
+    pub mint_registry: Box<Account<'info, MintRegistry>>,
 
     #[account(
         seeds = [
@@ -73,19 +90,40 @@ pub struct CreateMarketWithSpl<'info> {
     pub creator: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required once the config throttles market creations, see `CreatorActivity`
+    #[account(
+        mut,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump = creator_activity.bump
+    )]
+    pub creator_activity: Option<Box<Account<'info, CreatorActivity>>>,
 }
 
-pub fn handler(
-    ctx: Context<CreateMarketWithSpl>,
+/// Creates a market with a new base token mint owned by the SPL Token program, with Metaplex
+/// metadata, or by the Token-2022 program when `use_token_2022` is set, with the metadata stored
+/// on the mint through the MetadataPointer and TokenMetadata extensions.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateMarketWithSpl<'info>>,
     name: String,
     symbol: String,
     uri: String,
     total_supply: u64,
     creator_fee_share: u16,
     staking_fee_share: u16,
+    use_token_2022: bool,
+    wallet_buy_limit: Option<WalletBuyLimit>,
 ) -> Result<()> {
     let config = &ctx.accounts.config;
 
@@ -95,12 +133,34 @@ pub fn handler(
         TokenMillError::InvalidFeeShare
     );
 
+    require_keys_eq!(
+        ctx.accounts.token_program.key(),
+        if use_token_2022 {
+            token_2022::ID
+        } else {
+            token::ID
+        },
+        TokenMillError::InvalidTokenProgram
+    );
+
     require!(
         check_mint_extensions(&ctx.accounts.quote_token_mint)?,
         TokenMillError::UnsupportedTokenMint
     );
 
-    {
+    CreatorActivity::check(
+        config,
+        ctx.accounts.creator_activity.as_deref_mut(),
+        get_clock()?.unix_timestamp,
+    )?;
+
+    let default_prices = config
+        .default_curve
+        .as_ref()
+        .map(curve_manager::get_prices_from_parameters)
+        .transpose()?;
+
+    let creation_bond = {
         let mut market = ctx.accounts.market.load_init()?;
 
         market.initialize(
@@ -113,9 +173,43 @@ pub fn handler(
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            config.referral_fee_share,
+        )?;
+
+        market.swap_fee_bps = config.min_swap_fee_bps;
+
+        if let Some(wallet_buy_limit) = &wallet_buy_limit {
+            market.set_wallet_buy_limit(wallet_buy_limit, get_clock()?.unix_timestamp)?;
+        }
+
+        if let Some((bid_prices, ask_prices)) = default_prices {
+            market.check_and_set_prices(bid_prices, ask_prices, &config.price_bounds)?;
+        }
+
+        market.post_creation_bond(&config.creation_bond, get_clock()?.unix_timestamp)
+    };
+
+    if creation_bond > 0 {
+        invoke(
+            &transfer(
+                ctx.accounts.creator.key,
+                &ctx.accounts.market.key(),
+                creation_bond,
+            ),
+            &[
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.market.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
         )?;
     }
 
+    ctx.accounts.mint_registry.initialize(
+        ctx.bumps.mint_registry,
+        ctx.accounts.base_token_mint.key(),
+        ctx.accounts.market.key(),
+    )?;
+
     let base_token_mint_key = ctx.accounts.base_token_mint.key();
     let market_seeds = [
         MARKET_PDA_SEED.as_bytes(),
@@ -123,12 +217,34 @@ pub fn handler(
         &[ctx.bumps.market],
     ];
 
-    ctx.accounts
-        .initialize_token_metadata(name, symbol, uri, &market_seeds)?;
+    ctx.accounts.create_base_token_mint(use_token_2022)?;
+
+    if use_token_2022 {
+        ctx.accounts
+            .initialize_token_2022_metadata(name, symbol, uri, &market_seeds)?;
+    } else {
+        ctx.accounts
+            .initialize_token_metadata(name, symbol, uri, &market_seeds)?;
+    }
 
     ctx.accounts
         .mint_supply_and_remove_authority(total_supply, &market_seeds)?;
 
+    policy_manager::validate(
+        config.policy_program,
+        ctx.accounts.policy_program.as_ref(),
+        ctx.remaining_accounts,
+        VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+        &ValidateCreateMarketArgs {
+            config: config.key(),
+            market: ctx.accounts.market.key(),
+            creator: ctx.accounts.creator.key(),
+            base_token_mint: ctx.accounts.base_token_mint.key(),
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            total_supply,
+        },
+    )?;
+
     emit_cpi!(TokenMillMarketCreationEvent {
         config: ctx.accounts.config.key(),
         market: ctx.accounts.market.key(),
@@ -140,12 +256,85 @@ pub fn handler(
         referral_fee_share: config.referral_fee_share,
         creator_fee_share,
         staking_fee_share,
+        wallet_buy_limit,
+        swap_fee_bps: config.min_swap_fee_bps,
     });
 
+    if let Some((bid_prices, ask_prices)) = default_prices {
+        emit_cpi!(TokenMillMarketPriceSetEvent {
+            market: ctx.accounts.market.key(),
+            bid_prices,
+            ask_prices,
+        });
+    }
+
     Ok(())
 }
 
 impl<'info> CreateMarketWithSpl<'info> {
+    /// Creates the base token mint and the base token ATA of the market. Token-2022 mints reserve
+    /// the MetadataPointer extension, pointing to the mint itself.
+    fn create_base_token_mint(&self, use_token_2022: bool) -> Result<()> {
+        let space = if use_token_2022 {
+            ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+                ExtensionType::MetadataPointer,
+            ])?
+        } else {
+            spl_token::state::Mint::LEN
+        };
+
+        create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: self.base_token_mint.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            self.token_program.key,
+        )?;
+
+        if use_token_2022 {
+            metadata_pointer_initialize(
+                CpiContext::new(
+                    self.token_program.to_account_info(),
+                    MetadataPointerInitialize {
+                        token_program_id: self.token_program.to_account_info(),
+                        mint: self.base_token_mint.to_account_info(),
+                    },
+                ),
+                Some(self.market.key()),
+                Some(self.base_token_mint.key()),
+            )?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: self.base_token_mint.to_account_info(),
+                },
+            ),
+            MILL_TOKEN_DECIMALS,
+            &self.market.key(),
+            None,
+        )?;
+
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: self.market_base_token_ata.to_account_info(),
+                authority: self.market.to_account_info(),
+                mint: self.base_token_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))
+    }
+
     fn initialize_token_metadata(
         &self,
         name: String,
@@ -153,6 +342,12 @@ impl<'info> CreateMarketWithSpl<'info> {
         uri: String,
         market_seeds: &[&[u8]],
     ) -> Result<()> {
+        let (Some(token_metadata_program), Some(base_token_metadata)) =
+            (&self.token_metadata_program, &self.base_token_metadata)
+        else {
+            return err!(TokenMillError::MissingMetadataAccount);
+        };
+
         let token_data: DataV2 = DataV2 {
             name,
             symbol,
@@ -165,12 +360,12 @@ impl<'info> CreateMarketWithSpl<'info> {
 
         create_metadata_accounts_v3(
             CpiContext::new_with_signer(
-                self.token_metadata_program.to_account_info(),
+                token_metadata_program.to_account_info(),
                 CreateMetadataAccountsV3 {
                     payer: self.creator.to_account_info(),
                     update_authority: self.market.to_account_info(),
                     mint: self.base_token_mint.to_account_info(),
-                    metadata: self.base_token_metadata.to_account_info(),
+                    metadata: base_token_metadata.to_account_info(),
                     mint_authority: self.market.to_account_info(),
                     system_program: self.system_program.to_account_info(),
                     rent: self.system_program.to_account_info(),
@@ -186,18 +381,64 @@ impl<'info> CreateMarketWithSpl<'info> {
         Ok(())
     }
 
+    fn initialize_token_2022_metadata(
+        &self,
+        name: String,
+        symbol: String,
+        uri: String,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let cpi_accounts = TokenMetadataInitialize {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.base_token_mint.to_account_info(),
+            metadata: self.base_token_mint.to_account_info(),
+            mint_authority: self.market.to_account_info(),
+            update_authority: self.creator.to_account_info(),
+        };
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            name,
+            symbol,
+            uri,
+        )?;
+
+        // The metadata is appended to the mint, which needs to stay rent exempt
+        let mint_account_info = self.base_token_mint.to_account_info();
+
+        let extra_lamports = Rent::get()?.minimum_balance(mint_account_info.data_len())
+            - mint_account_info.get_lamports();
+
+        if extra_lamports > 0 {
+            invoke(
+                &transfer(self.creator.key, mint_account_info.key, extra_lamports),
+                &[
+                    self.creator.to_account_info(),
+                    mint_account_info,
+                    self.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
     fn mint_supply_and_remove_authority(
         &self,
         total_supply: u64,
         market_seeds: &[&[u8]],
     ) -> Result<()> {
-        let cpi_accounts = MintTo {
+        let cpi_accounts = token_interface::MintTo {
             mint: self.base_token_mint.to_account_info(),
             to: self.market_base_token_ata.to_account_info(),
             authority: self.market.to_account_info(),
         };
 
-        mint_to(
+        token_interface::mint_to(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 cpi_accounts,
@@ -206,12 +447,12 @@ impl<'info> CreateMarketWithSpl<'info> {
             total_supply,
         )?;
 
-        let cpi_accounts = SetAuthority {
+        let cpi_accounts = token_interface::SetAuthority {
             account_or_mint: self.base_token_mint.to_account_info(),
             current_authority: self.market.to_account_info(),
         };
 
-        set_authority(
+        token_interface::set_authority(
             CpiContext::new_with_signer(
                 self.token_program.to_account_info(),
                 cpi_accounts,
@@ -229,7 +470,17 @@ impl<'info> CreateMarketWithSpl<'info> {
 mod tests {
     use crate::{
         constant::{INTERVAL_NUMBER, MAX_TOTAL_SUPPLY},
-        Market,
+        Market, MintRegistry,
+    };
+    use anchor_lang::prelude::Pubkey;
+    use anchor_spl::{
+        token_2022::spl_token_2022::{
+            extension::{
+                metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+            },
+            state::Mint,
+        },
+        token_interface::spl_token_metadata_interface::state::TokenMetadata,
     };
     use joelana_test_utils::joelana_env::{
         actions::token_mill::{
@@ -282,6 +533,63 @@ mod tests {
         assert_eq!(market.quote_token_decimals, quote_token_decimals);
         assert_eq!(market.total_supply, DEFAULT_TOTAL_SUPPLY);
         assert_eq!(market.base_reserve, DEFAULT_TOTAL_SUPPLY);
+
+        let mint_registry = testing_env
+            .svm
+            .get_parsed_account::<MintRegistry>(&action.mint_registry);
+
+        assert_eq!(mint_registry.base_token_mint, action.base_token_mint);
+        assert_eq!(mint_registry.active_market, action.market);
+    }
+
+    #[test]
+    fn create_market_with_token_2022() {
+        let (mut testing_env, mut action) = setup_env(TokenType::Token, 6);
+
+        let result = testing_env.svm.execute_actions(&[action.with_token_2022()]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.base_token_mint, action.base_token_mint);
+        assert_eq!(market.base_reserve, DEFAULT_TOTAL_SUPPLY);
+
+        let base_token_mint = testing_env.svm.get_account(&action.base_token_mint);
+
+        assert_eq!(base_token_mint.owner, anchor_spl::token_2022::ID);
+
+        let mint_data = StateWithExtensions::<Mint>::unpack(&base_token_mint.data).unwrap();
+
+        let metadata_pointer = mint_data.get_extension::<MetadataPointer>().unwrap();
+
+        assert_eq!(
+            Option::<Pubkey>::from(metadata_pointer.metadata_address),
+            Some(action.base_token_mint)
+        );
+
+        let metadata = mint_data
+            .get_variable_len_extension::<TokenMetadata>()
+            .unwrap();
+
+        assert_eq!(metadata.name, "name");
+        assert_eq!(metadata.symbol, "symbol");
+        assert_eq!(metadata.uri, "uri");
+    }
+
+    #[test]
+    fn create_market_with_mismatched_token_program() {
+        let (mut testing_env, mut action) = setup_env(TokenType::Token, 6);
+
+        action.with_token_2022().use_token_2022 = false;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidTokenProgram);
     }
 
     #[test]
@@ -0,0 +1,578 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction::transfer},
+    system_program::{create_account, CreateAccount},
+};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token_2022::{
+        spl_token_2022::{
+            extension::ExtensionType, instruction::AuthorityType, state::Mint as SplMint,
+        },
+        Token2022,
+    },
+    token_interface::{
+        self, metadata_pointer_initialize, token_metadata_initialize, InitializeMint2,
+        MetadataPointerInitialize, Mint, TokenMetadataInitialize,
+    },
+};
+
+use crate::{
+    clock::get_clock,
+    constant::{MAX_BPS, MAX_MARKETS_PER_BATCH, MILL_TOKEN_DECIMALS, PRICES_LENGTH},
+    errors::TokenMillError,
+    events::{TokenMillMarketCreationEvent, TokenMillMarketPriceSetEvent},
+    manager::{
+        curve_manager,
+        policy_manager::{self, ValidateCreateMarketArgs, VALIDATE_CREATE_MARKET_DISCRIMINATOR},
+        token_manager::check_mint_extensions,
+    },
+    state::{
+        CreatorActivity, Market, MintRegistry, TokenMillConfig, WalletBuyLimit,
+        CREATOR_ACTIVITY_PDA_SEED,
+    },
+    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, MINT_REGISTRY_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
+};
+
+/// Remaining accounts of each market: market, base token mint (signer), market base token ATA
+/// and mint registry.
+pub const BATCH_MARKET_ACCOUNTS_LENGTH: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MarketCreationParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub total_supply: u64,
+    pub creator_fee_share: u16,
+    pub staking_fee_share: u16,
+    pub wallet_buy_limit: Option<WalletBuyLimit>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMarketsBatch<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        seeds = [
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            quote_token_mint.key().as_ref(),
+        ],
+        bump = quote_token_badge.bump,
+        constraint = quote_token_badge.status == QuoteTokenBadgeStatus::Enabled || creator.key() == config.authority @ TokenMillError::InvalidQuoteAssetBadge,
+    )]
+    pub quote_token_badge: Option<Account<'info, QuoteTokenBadge>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required once the config throttles market creations, see `CreatorActivity`
+    #[account(
+        mut,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump = creator_activity.bump
+    )]
+    pub creator_activity: Option<Box<Account<'info, CreatorActivity>>>,
+}
+
+/// Creates up to `MAX_MARKETS_PER_BATCH` markets of the same creator and quote token, so that
+/// launch platforms only pay for the config, quote token and program accounts once.
+/// Markets are created as in `create_market`, from `BATCH_MARKET_ACCOUNTS_LENGTH` remaining accounts
+/// each. The remaining accounts following the last market are forwarded to the policy program.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateMarketsBatch<'info>>,
+    markets: Vec<MarketCreationParams>,
+) -> Result<()> {
+    require!(
+        !markets.is_empty() && markets.len() <= MAX_MARKETS_PER_BATCH,
+        TokenMillError::InvalidBatchSize
+    );
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        markets.len() * BATCH_MARKET_ACCOUNTS_LENGTH,
+        TokenMillError::InvalidBatchSize
+    );
+
+    require!(
+        check_mint_extensions(&ctx.accounts.quote_token_mint)?,
+        TokenMillError::UnsupportedTokenMint
+    );
+
+    let default_prices = ctx
+        .accounts
+        .config
+        .default_curve
+        .as_ref()
+        .map(curve_manager::get_prices_from_parameters)
+        .transpose()?;
+
+    let (market_accounts, policy_accounts) = ctx
+        .remaining_accounts
+        .split_at(markets.len() * BATCH_MARKET_ACCOUNTS_LENGTH);
+
+    for (params, accounts) in markets
+        .into_iter()
+        .zip(market_accounts.chunks_exact(BATCH_MARKET_ACCOUNTS_LENGTH))
+    {
+        let [market, base_token_mint, market_base_token_ata, mint_registry] = accounts else {
+            return Err(TokenMillError::InvalidBatchSize.into());
+        };
+
+        CreatorActivity::check(
+            &ctx.accounts.config,
+            ctx.accounts.creator_activity.as_deref_mut(),
+            get_clock()?.unix_timestamp,
+        )?;
+
+        ctx.accounts.create_market(
+            &params,
+            market,
+            base_token_mint,
+            market_base_token_ata,
+            mint_registry,
+            default_prices,
+            policy_accounts,
+        )?;
+
+        let config = &ctx.accounts.config;
+
+        emit_cpi!(TokenMillMarketCreationEvent {
+            config: config.key(),
+            market: market.key(),
+            creator: ctx.accounts.creator.key(),
+            base_token_mint: base_token_mint.key(),
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            total_supply: params.total_supply,
+            protocol_fee_share: config.default_protocol_fee_share,
+            referral_fee_share: config.referral_fee_share,
+            creator_fee_share: params.creator_fee_share,
+            staking_fee_share: params.staking_fee_share,
+            wallet_buy_limit: params.wallet_buy_limit,
+            swap_fee_bps: config.min_swap_fee_bps,
+        });
+
+        if let Some((bid_prices, ask_prices)) = default_prices {
+            emit_cpi!(TokenMillMarketPriceSetEvent {
+                market: market.key(),
+                bid_prices,
+                ask_prices,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl<'info> CreateMarketsBatch<'info> {
+    #[allow(clippy::too_many_arguments)]
+    fn create_market(
+        &self,
+        params: &MarketCreationParams,
+        market: &AccountInfo<'info>,
+        base_token_mint: &AccountInfo<'info>,
+        market_base_token_ata: &AccountInfo<'info>,
+        mint_registry: &AccountInfo<'info>,
+        default_prices: Option<([u64; PRICES_LENGTH], [u64; PRICES_LENGTH])>,
+        policy_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let config = &self.config;
+
+        require_eq!(
+            params.creator_fee_share + params.staking_fee_share + config.default_protocol_fee_share,
+            MAX_BPS as u16,
+            TokenMillError::InvalidFeeShare
+        );
+
+        let base_token_mint_key = base_token_mint.key();
+
+        let (market_key, market_bump) = Pubkey::find_program_address(
+            &[MARKET_PDA_SEED.as_bytes(), base_token_mint_key.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(market.key(), market_key, TokenMillError::InvalidMarket);
+
+        let (mint_registry_key, mint_registry_bump) = Pubkey::find_program_address(
+            &[
+                MINT_REGISTRY_PDA_SEED.as_bytes(),
+                base_token_mint_key.as_ref(),
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(
+            mint_registry.key(),
+            mint_registry_key,
+            TokenMillError::InvalidMintAccount
+        );
+
+        let market_seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            base_token_mint_key.as_ref(),
+            &[market_bump],
+        ];
+        let mint_registry_seeds = [
+            MINT_REGISTRY_PDA_SEED.as_bytes(),
+            base_token_mint_key.as_ref(),
+            &[mint_registry_bump],
+        ];
+
+        self.init_account(
+            market,
+            8 + Market::INIT_SPACE,
+            &crate::ID,
+            &[&market_seeds[..]],
+        )?;
+        self.init_account(
+            mint_registry,
+            8 + MintRegistry::INIT_SPACE,
+            &crate::ID,
+            &[&mint_registry_seeds[..]],
+        )?;
+
+        self.initialize_base_token_mint(base_token_mint, market)?;
+
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: market_base_token_ata.clone(),
+                authority: market.clone(),
+                mint: base_token_mint.clone(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+
+        let market_loader = AccountLoader::<Market>::try_from_unchecked(&crate::ID, market)?;
+
+        let creation_bond = {
+            let mut market = market_loader.load_init()?;
+
+            market.initialize(
+                market_bump,
+                config.key(),
+                self.creator.key(),
+                base_token_mint_key,
+                self.quote_token_mint.key(),
+                self.quote_token_mint.decimals,
+                params.total_supply,
+                params.creator_fee_share,
+                params.staking_fee_share,
+                config.referral_fee_share,
+            )?;
+
+            market.swap_fee_bps = config.min_swap_fee_bps;
+
+            if let Some(wallet_buy_limit) = &params.wallet_buy_limit {
+                market.set_wallet_buy_limit(wallet_buy_limit, get_clock()?.unix_timestamp)?;
+            }
+
+            if let Some((bid_prices, ask_prices)) = default_prices {
+                market.check_and_set_prices(bid_prices, ask_prices, &config.price_bounds)?;
+            }
+
+            market.post_creation_bond(&config.creation_bond, get_clock()?.unix_timestamp)
+        };
+
+        market_loader.exit(&crate::ID)?;
+
+        if creation_bond > 0 {
+            invoke(
+                &transfer(self.creator.key, market.key, creation_bond),
+                &[
+                    self.creator.to_account_info(),
+                    market.clone(),
+                    self.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        let mut registry = Account::<MintRegistry>::try_from_unchecked(mint_registry)?;
+
+        registry.initialize(mint_registry_bump, base_token_mint_key, market_key)?;
+        registry.exit(&crate::ID)?;
+
+        self.initialize_token_metadata(params, base_token_mint, market, &market_seeds)?;
+        self.mint_supply_and_remove_authority(
+            params.total_supply,
+            base_token_mint,
+            market_base_token_ata,
+            market,
+            &market_seeds,
+        )?;
+
+        policy_manager::validate(
+            config.policy_program,
+            self.policy_program.as_ref(),
+            policy_accounts,
+            VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+            &ValidateCreateMarketArgs {
+                config: config.key(),
+                market: market_key,
+                creator: self.creator.key(),
+                base_token_mint: base_token_mint_key,
+                quote_token_mint: self.quote_token_mint.key(),
+                total_supply: params.total_supply,
+            },
+        )
+    }
+
+    fn init_account(
+        &self,
+        account: &AccountInfo<'info>,
+        space: usize,
+        owner: &Pubkey,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            owner,
+        )
+    }
+
+    fn initialize_base_token_mint(
+        &self,
+        base_token_mint: &AccountInfo<'info>,
+        market: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let space =
+            ExtensionType::try_calculate_account_len::<SplMint>(&[ExtensionType::MetadataPointer])?;
+
+        // The mint is a keypair account, its signature is forwarded
+        self.init_account(base_token_mint, space, &self.token_program.key(), &[])?;
+
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.to_account_info(),
+                    mint: base_token_mint.clone(),
+                },
+            ),
+            Some(market.key()),
+            Some(base_token_mint.key()),
+        )?;
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: base_token_mint.clone(),
+                },
+            ),
+            MILL_TOKEN_DECIMALS,
+            &market.key(),
+            None,
+        )
+    }
+
+    fn initialize_token_metadata(
+        &self,
+        params: &MarketCreationParams,
+        base_token_mint: &AccountInfo<'info>,
+        market: &AccountInfo<'info>,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let cpi_accounts = TokenMetadataInitialize {
+            token_program_id: self.token_program.to_account_info(),
+            mint: base_token_mint.clone(),
+            metadata: base_token_mint.clone(),
+            mint_authority: market.clone(),
+            update_authority: self.creator.to_account_info(),
+        };
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            params.name.clone(),
+            params.symbol.clone(),
+            params.uri.clone(),
+        )?;
+
+        let extra_lamports = Rent::get()?
+            .minimum_balance(base_token_mint.data_len())
+            .saturating_sub(base_token_mint.get_lamports());
+
+        if extra_lamports > 0 {
+            invoke(
+                &transfer(self.creator.key, base_token_mint.key, extra_lamports),
+                &[
+                    self.creator.to_account_info(),
+                    base_token_mint.clone(),
+                    self.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn mint_supply_and_remove_authority(
+        &self,
+        total_supply: u64,
+        base_token_mint: &AccountInfo<'info>,
+        market_base_token_ata: &AccountInfo<'info>,
+        market: &AccountInfo<'info>,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let cpi_accounts = token_interface::MintTo {
+            mint: base_token_mint.clone(),
+            to: market_base_token_ata.clone(),
+            authority: market.clone(),
+        };
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            total_supply,
+        )?;
+
+        let cpi_accounts = token_interface::SetAuthority {
+            account_or_mint: base_token_mint.clone(),
+            current_authority: market.clone(),
+        };
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MintRegistry};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketsBatchAction, CreateQuoteAssetBadgeAction, TokenMillEnv,
+            DEFAULT_TOTAL_SUPPLY,
+        },
+        TokenMillError, TokenType,
+    };
+
+    fn setup_env(market_count: usize) -> (TokenMillEnv, CreateMarketsBatchAction) {
+        let mut testing_env = TokenMillEnv::new().with_quote_token_mint(TokenType::Token, 6);
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateQuoteAssetBadgeAction::new(
+                testing_env.quote_token_mint.unwrap(),
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateMarketsBatchAction::new(&testing_env, market_count);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn create_markets_batch() {
+        let (mut testing_env, action) = setup_env(3);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        for (market_address, base_token_mint) in action.markets.iter().zip(&action.base_token_mints)
+        {
+            let market = testing_env.svm.get_parsed_account::<Market>(market_address);
+
+            assert_eq!(market.config, action.config);
+            assert_eq!(market.creator, testing_env.svm.payer);
+            assert_eq!(market.base_token_mint, *base_token_mint);
+            assert_eq!(market.quote_token_mint, action.quote_token_mint);
+            assert_eq!(market.total_supply, DEFAULT_TOTAL_SUPPLY);
+            assert_eq!(market.base_reserve, DEFAULT_TOTAL_SUPPLY);
+
+            assert_eq!(
+                testing_env.svm.get_balance(base_token_mint, market_address),
+                DEFAULT_TOTAL_SUPPLY
+            );
+        }
+
+        for (mint_registry_address, market) in action.mint_registries.iter().zip(&action.markets) {
+            let mint_registry = testing_env
+                .svm
+                .get_parsed_account::<MintRegistry>(mint_registry_address);
+
+            assert_eq!(mint_registry.active_market, *market);
+        }
+    }
+
+    #[test]
+    fn create_markets_batch_with_invalid_size() {
+        let (mut testing_env, action) = setup_env(0);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidBatchSize);
+
+        let (mut testing_env, action) = setup_env(5);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidBatchSize);
+    }
+
+    #[test]
+    fn create_markets_batch_with_invalid_market() {
+        let (mut testing_env, mut action) = setup_env(2);
+
+        action.markets.swap(0, 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidMarket);
+    }
+}
@@ -0,0 +1,387 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    clock::get_clock,
+    constant::MAX_BPS,
+    errors::TokenMillError,
+    events::TokenMillSecondaryMarketCreationEvent,
+    manager::{
+        policy_manager::{self, ValidateCreateMarketArgs, VALIDATE_CREATE_MARKET_DISCRIMINATOR},
+        token_manager::{check_mint_extensions, transfer_from_eoa},
+    },
+    state::{
+        CreatorActivity, Market, MarketKind, MintRegistry, TokenMillConfig,
+        CREATOR_ACTIVITY_PDA_SEED,
+    },
+    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, MINT_REGISTRY_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSecondaryMarket<'info> {
+    #[account(constraint = config.secondary_markets_enabled @ TokenMillError::SecondaryMarketsDisabled)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub primary_market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [MINT_REGISTRY_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump = mint_registry.bump,
+        constraint = mint_registry.active_market == primary_market.key() @ TokenMillError::InvalidMarket
+    )]
+    pub mint_registry: Box<Account<'info, MintRegistry>>,
+
+    #[account(
+        init,
+        seeds = [
+            MARKET_PDA_SEED.as_bytes(),
+            base_token_mint.key().as_ref(),
+            quote_token_mint.key().as_ref()
+        ],
+        bump,
+        payer = creator,
+        space = 8 + Market::INIT_SPACE
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = base_token_mint,
+        token::authority = creator,
+        token::token_program = base_token_program
+    )]
+    pub creator_base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            quote_token_mint.key().as_ref(),
+        ],
+        bump = quote_token_badge.bump,
+        constraint = quote_token_badge.status == QuoteTokenBadgeStatus::Enabled || creator.key() == config.authority @ TokenMillError::InvalidQuoteAssetBadge,
+    )]
+    pub quote_token_badge: Option<Account<'info, QuoteTokenBadge>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required once the config throttles market creations, see `CreatorActivity`
+    #[account(
+        mut,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump = creator_activity.bump
+    )]
+    pub creator_activity: Option<Box<Account<'info, CreatorActivity>>>,
+}
+
+/// Opens an independent curve for an existing base token against another quote token.
+/// The base reserve isn't minted but deposited by the creator of the primary market.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateSecondaryMarket<'info>>,
+    total_supply: u64,
+    creator_fee_share: u16,
+    staking_fee_share: u16,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    require_eq!(
+        creator_fee_share + staking_fee_share + config.default_protocol_fee_share,
+        MAX_BPS as u16,
+        TokenMillError::InvalidFeeShare
+    );
+
+    let primary_market_key = ctx.accounts.primary_market.key();
+    let primary_quote_token_mint = ctx.accounts.primary_market.load()?.quote_token_mint;
+
+    require_keys_neq!(
+        ctx.accounts.quote_token_mint.key(),
+        primary_quote_token_mint,
+        TokenMillError::InvalidQuoteTokenMint
+    );
+
+    require!(
+        check_mint_extensions(&ctx.accounts.quote_token_mint)?,
+        TokenMillError::UnsupportedTokenMint
+    );
+
+    CreatorActivity::check(
+        config,
+        ctx.accounts.creator_activity.as_deref_mut(),
+        get_clock()?.unix_timestamp,
+    )?;
+
+    {
+        let mut market = ctx.accounts.market.load_init()?;
+
+        market.initialize(
+            ctx.bumps.market,
+            config.key(),
+            ctx.accounts.creator.key(),
+            ctx.accounts.base_token_mint.key(),
+            ctx.accounts.quote_token_mint.key(),
+            ctx.accounts.quote_token_mint.decimals,
+            total_supply,
+            creator_fee_share,
+            staking_fee_share,
+            config.referral_fee_share,
+        )?;
+
+        market.swap_fee_bps = config.min_swap_fee_bps;
+
+        market.kind = MarketKind::Secondary as u8;
+    }
+
+    ctx.accounts.mint_registry.secondary_market_count += 1;
+
+    transfer_from_eoa(
+        &ctx.accounts.base_token_mint,
+        &ctx.accounts.creator,
+        &ctx.accounts.creator_base_token_account,
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.base_token_program,
+        total_supply,
+    )?;
+
+    policy_manager::validate(
+        config.policy_program,
+        ctx.accounts.policy_program.as_ref(),
+        ctx.remaining_accounts,
+        VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+        &ValidateCreateMarketArgs {
+            config: config.key(),
+            market: ctx.accounts.market.key(),
+            creator: ctx.accounts.creator.key(),
+            base_token_mint: ctx.accounts.base_token_mint.key(),
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            total_supply,
+        },
+    )?;
+
+    emit_cpi!(TokenMillSecondaryMarketCreationEvent {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        primary_market: primary_market_key,
+        creator: ctx.accounts.creator.key(),
+        base_token_mint: ctx.accounts.base_token_mint.key(),
+        quote_token_mint: ctx.accounts.quote_token_mint.key(),
+        total_supply,
+        protocol_fee_share: config.default_protocol_fee_share,
+        referral_fee_share: config.referral_fee_share,
+        creator_fee_share,
+        staking_fee_share,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MintRegistry};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateQuoteAssetBadgeAction, CreateSecondaryMarketAction,
+            SetMarketPricesAction, SwapAction, TokenMillEnv, UpdateSecondaryMarketPolicyAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError, TokenType,
+    };
+    use joelana_test_utils::utils::token_mill::curve_generator::Curve;
+
+    const SECONDARY_TOTAL_SUPPLY: u64 = 100_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, CreateSecondaryMarketAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        let secondary_quote_token_mint = testing_env.svm.create_token(TokenType::Token, 6).unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateSecondaryMarketPolicyAction::new(true),
+                &CreateQuoteAssetBadgeAction::new(secondary_quote_token_mint),
+            ])
+            .unwrap();
+
+        let alice = testing_env.svm.change_payer("alice");
+
+        let buy_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            SECONDARY_TOTAL_SUPPLY,
+            u64::MAX,
+            None,
+        )
+        .with_signer(alice);
+
+        testing_env.svm.execute_actions(&[&buy_action]).unwrap();
+
+        let action = CreateSecondaryMarketAction::new(
+            &testing_env,
+            secondary_quote_token_mint,
+            SECONDARY_TOTAL_SUPPLY,
+        );
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn create_secondary_market() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.is_secondary());
+        assert_eq!(market.creator, make_address("alice"));
+        assert_eq!(market.base_token_mint, action.base_token_mint);
+        assert_eq!(market.quote_token_mint, action.quote_token_mint);
+        assert_eq!(market.quote_token_decimals, 6);
+        assert_eq!(market.total_supply, SECONDARY_TOTAL_SUPPLY);
+        assert_eq!(market.base_reserve, SECONDARY_TOTAL_SUPPLY);
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &action.market),
+            SECONDARY_TOTAL_SUPPLY
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &make_address("alice")),
+            0
+        );
+
+        let mint_registry = testing_env
+            .svm
+            .get_parsed_account::<MintRegistry>(&action.mint_registry);
+
+        assert_eq!(mint_registry.active_market, action.primary_market);
+        assert_eq!(mint_registry.secondary_market_count, 1);
+
+        let primary_market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&action.primary_market);
+
+        assert!(!primary_market.is_secondary());
+    }
+
+    #[test]
+    fn create_secondary_market_and_set_prices() {
+        let (mut testing_env, action) = setup_env();
+
+        let mut set_prices_action = SetMarketPricesAction::new(Curve::default());
+        set_prices_action.market = action.market;
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action, &set_prices_action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+        let primary_market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&action.primary_market);
+
+        assert!(market.are_prices_set());
+        assert_ne!(market.base_reserve, primary_market.base_reserve);
+    }
+
+    #[test]
+    fn create_secondary_market_with_policy_disabled() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateSecondaryMarketPolicyAction::new(false)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::SecondaryMarketsDisabled);
+    }
+
+    #[test]
+    fn create_secondary_market_with_primary_quote_token() {
+        let (mut testing_env, _) = setup_env();
+
+        let mut action = CreateSecondaryMarketAction::new(
+            &testing_env,
+            testing_env.quote_token_mint.unwrap(),
+            SECONDARY_TOTAL_SUPPLY,
+        );
+
+        let result = testing_env.svm.execute_actions(&[action.no_badge()]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidQuoteTokenMint);
+    }
+
+    #[test]
+    fn create_secondary_market_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MAX_BPS,
+    errors::TokenMillError,
+    events::TokenMillFeeDefaultsAdoptionEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AdoptNewFeeDefaults<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Markets keep the fee shares they were created with when the config defaults change.
+/// The creator can opt into the current protocol and referral shares of the config, redistributing
+/// what's left between creator and stakers.
+pub fn handler(
+    ctx: Context<AdoptNewFeeDefaults>,
+    new_creator_fee_share: u16,
+    new_staking_fee_share: u16,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    require_eq!(
+        new_creator_fee_share + new_staking_fee_share + config.default_protocol_fee_share,
+        MAX_BPS as u16,
+        TokenMillError::InvalidFeeShare
+    );
+
+    market.fees.creator_fee_share = new_creator_fee_share;
+    market.fees.staking_fee_share = new_staking_fee_share;
+    market.fees.referral_fee_share = config.referral_fee_share;
+
+    emit_cpi!(TokenMillFeeDefaultsAdoptionEvent {
+        market: ctx.accounts.market.key(),
+        new_creator_fee_share,
+        new_staking_fee_share,
+        new_protocol_fee_share: config.default_protocol_fee_share,
+        new_referral_fee_share: config.referral_fee_share,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, AdoptNewFeeDefaultsAction, TokenMillEnv,
+                UpdateDefaultFeeSharesAction,
+            },
+            TokenMillError,
+        },
+        utils::token_mill::constants::*,
+    };
+
+    const NEW_PROTOCOL_FEE_SHARE: u16 = 2_000;
+    const NEW_REFERRAL_FEE_SHARE: u16 = 4_000;
+    const NEW_CREATOR_FEE_SHARE: u16 = 3_500;
+
+    fn setup_env() -> (TokenMillEnv, AdoptNewFeeDefaultsAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateDefaultFeeSharesAction::new(
+                NEW_PROTOCOL_FEE_SHARE,
+                NEW_REFERRAL_FEE_SHARE,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = AdoptNewFeeDefaultsAction::new(
+            &testing_env,
+            NEW_CREATOR_FEE_SHARE,
+            DEFAULT_STAKING_FEE_SHARE,
+        );
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn adopt_new_fee_defaults() {
+        let (mut testing_env, action) = setup_env();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        // Existing markets are grandfathered
+        assert_eq!(market.fees.creator_fee_share, DEFAULT_CREATOR_FEE_SHARE);
+        assert_eq!(market.fees.referral_fee_share, DEFAULT_REFERRAL_FEE_SHARE);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.fees.creator_fee_share, NEW_CREATOR_FEE_SHARE);
+        assert_eq!(market.fees.staking_fee_share, DEFAULT_STAKING_FEE_SHARE);
+        assert_eq!(market.fees.referral_fee_share, NEW_REFERRAL_FEE_SHARE);
+    }
+
+    #[test]
+    fn adopt_new_fee_defaults_with_invalid_distribution() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.new_creator_fee_share = DEFAULT_CREATOR_FEE_SHARE;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+    }
+
+    #[test]
+    fn adopt_new_fee_defaults_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use super::MarketPricesUpdate;
+use crate::{clock::get_clock, events::TokenMillMarketPriceSetEvent};
+
+/// Lazy alternative to `set_market_prices`, writing the price arrays in ordered chunks.
+pub fn handler(
+    ctx: Context<MarketPricesUpdate>,
+    start_index: u8,
+    bid_prices: Vec<u64>,
+    ask_prices: Vec<u64>,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    let prices_set = market.append_prices(
+        start_index,
+        &bid_prices,
+        &ask_prices,
+        &ctx.accounts.config.price_bounds,
+    )?;
+
+    if prices_set {
+        market.launch_slot = get_clock()?.slot;
+
+        emit_cpi!(TokenMillMarketPriceSetEvent {
+            market: ctx.accounts.market.key(),
+            bid_prices: market.bid_prices,
+            ask_prices: market.ask_prices,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::PRICES_LENGTH, Market};
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, AppendPricesAction, CreateMarketAction,
+                CreateQuoteAssetBadgeAction, SetMarketPricesAction, TokenMillEnv,
+            },
+            TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    const SPLIT_INDEX: usize = 6;
+
+    fn setup_env() -> (TokenMillEnv, AppendPricesAction, AppendPricesAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        let action = CreateQuoteAssetBadgeAction::new(testing_env.quote_token_mint.unwrap());
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_market_action])
+            .unwrap();
+
+        let curve = Curve::default();
+
+        let first_action = AppendPricesAction::new(curve, 0..SPLIT_INDEX);
+        let second_action = AppendPricesAction::new(curve, SPLIT_INDEX..PRICES_LENGTH);
+
+        (testing_env, first_action, second_action)
+    }
+
+    #[test]
+    fn append_prices() {
+        let (mut testing_env, first_action, second_action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&first_action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&first_action.market);
+
+        assert!(!market.are_prices_set());
+        assert_eq!(market.appended_prices, SPLIT_INDEX as u8);
+
+        let result = testing_env.svm.execute_actions(&[&second_action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&first_action.market);
+
+        let curve = Curve::default();
+
+        assert!(market.are_prices_set());
+        assert_eq!(market.bid_prices, curve.bid_prices);
+        assert_eq!(market.ask_prices, curve.ask_prices);
+    }
+
+    #[test]
+    fn append_prices_out_of_order() {
+        let (mut testing_env, _, second_action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&second_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPriceChunk);
+    }
+
+    #[test]
+    fn append_prices_with_decreasing_prices_across_chunks() {
+        let (mut testing_env, first_action, mut second_action) = setup_env();
+
+        second_action.bid_prices[0] = first_action.bid_prices[SPLIT_INDEX - 1];
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&first_action, &second_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::DecreasingPrices);
+    }
+
+    #[test]
+    fn append_prices_after_prices_set() {
+        let (mut testing_env, first_action, _) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&first_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::PricesAlreadySet);
+    }
+
+    #[test]
+    fn append_prices_with_invalid_signer() {
+        let (mut testing_env, mut first_action, _) = setup_env();
+
+        first_action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&first_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
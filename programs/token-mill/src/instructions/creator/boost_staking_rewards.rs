@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillStakingBoostEvent,
+    manager::token_manager::{get_transfer_inverse_fee, transfer_from_eoa},
+    state::{Market, StakingBoostSource},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BoostStakingRewards<'info> {
+    #[account(
+        mut,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Only required when boosting from the wallet
+    #[account(
+        mut,
+        token::mint = quote_token_mint,
+        token::authority = creator,
+        token::token_program = quote_token_program
+    )]
+    pub creator_quote_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub creator: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// One-off top up of the staking rewards of the market, distributed to stakers like staking fees.
+pub fn handler(
+    ctx: Context<BoostStakingRewards>,
+    amount: u64,
+    source: StakingBoostSource,
+) -> Result<()> {
+    require!(amount > 0, TokenMillError::InvalidAmount);
+
+    match source {
+        StakingBoostSource::Wallet => {
+            let creator_quote_token_account = ctx
+                .accounts
+                .creator_quote_token_account
+                .as_ref()
+                .ok_or(TokenMillError::MissingTokenAccount)?;
+
+            // The market receives `amount` net of the transfer fee of the quote token
+            transfer_from_eoa(
+                &ctx.accounts.quote_token_mint,
+                &ctx.accounts.creator,
+                creator_quote_token_account,
+                &ctx.accounts.market_quote_token_ata,
+                &ctx.accounts.quote_token_program,
+                amount + get_transfer_inverse_fee(&ctx.accounts.quote_token_mint, amount)?,
+            )?;
+        }
+        StakingBoostSource::CreatorFees => {
+            let market = &mut ctx.accounts.market.load_mut()?;
+
+            require_gte!(
+                market.fees.pending_creator_fees,
+                amount,
+                TokenMillError::InsufficientCreatorFees
+            );
+
+            market.fees.pending_creator_fees -= amount;
+        }
+    }
+
+    ctx.accounts.market.load_mut()?.fees.pending_staking_fees += amount;
+
+    emit_cpi!(TokenMillStakingBoostEvent {
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+        source,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, StakingBoostSource};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, BoostStakingRewardsAction, SwapAction, TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const BOOST_AMOUNT: u64 = 1_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+    }
+
+    #[test]
+    fn boost_staking_rewards_from_wallet() {
+        let mut testing_env = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let market_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let creator_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("alice"));
+
+        let action =
+            BoostStakingRewardsAction::new(&testing_env, BOOST_AMOUNT, StakingBoostSource::Wallet);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(
+            market.fees.pending_staking_fees,
+            market_before.fees.pending_staking_fees + BOOST_AMOUNT
+        );
+        assert_eq!(
+            market.fees.pending_creator_fees,
+            market_before.fees.pending_creator_fees
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &make_address("alice")),
+            creator_balance_before - BOOST_AMOUNT
+        );
+    }
+
+    #[test]
+    fn boost_staking_rewards_from_creator_fees() {
+        let mut testing_env = setup_env();
+
+        let market_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        let action = BoostStakingRewardsAction::new(
+            &testing_env,
+            market_before.fees.pending_creator_fees,
+            StakingBoostSource::CreatorFees,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.fees.pending_creator_fees, 0);
+        assert_eq!(
+            market.fees.pending_staking_fees,
+            market_before.fees.pending_staking_fees + market_before.fees.pending_creator_fees
+        );
+    }
+
+    #[test]
+    fn boost_staking_rewards_above_creator_fees() {
+        let mut testing_env = setup_env();
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+
+        let action = BoostStakingRewardsAction::new(
+            &testing_env,
+            pending_creator_fees + 1,
+            StakingBoostSource::CreatorFees,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientCreatorFees);
+    }
+
+    #[test]
+    fn boost_staking_rewards_from_wallet_without_token_account() {
+        let mut testing_env = setup_env();
+
+        let mut action =
+            BoostStakingRewardsAction::new(&testing_env, BOOST_AMOUNT, StakingBoostSource::Wallet);
+        action.creator_quote_token_account = crate::ID;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::MissingTokenAccount);
+    }
+
+    #[test]
+    fn boost_staking_rewards_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action =
+            BoostStakingRewardsAction::new(&testing_env, BOOST_AMOUNT, StakingBoostSource::Wallet);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillScheduledUpdateCancelEvent;
+
+use super::MarketSettingsUpdate;
+
+/// Removes a pending entry queued by `schedule_market_update`.
+pub fn handler(ctx: Context<MarketSettingsUpdate>, index: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    let update = market.cancel_scheduled_update(usize::from(index))?;
+
+    emit_cpi!(TokenMillScheduledUpdateCancelEvent {
+        market: ctx.accounts.market.key(),
+        index,
+        kind: update.kind(),
+        value: update.value,
+        slot: update.slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{state::ScheduledUpdateKind, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CancelScheduledUpdateAction, ScheduleMarketUpdateAction, TokenMillEnv,
+        },
+        TokenMillError,
+    };
+
+    #[test]
+    fn cancel_scheduled_update() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let slot = testing_env.svm.get_slot() + 100;
+
+        let schedule_action = ScheduleMarketUpdateAction::new(
+            &testing_env,
+            ScheduledUpdateKind::MinClaimAmount,
+            1_000_000,
+            slot,
+        );
+        let action = CancelScheduledUpdateAction::new(&testing_env, 0);
+
+        testing_env
+            .svm
+            .execute_actions(&[&schedule_action, &action])
+            .unwrap();
+
+        testing_env.svm.warp_to_slot(slot);
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.scheduled_updates[0].is_empty());
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidScheduledUpdate);
+    }
+}
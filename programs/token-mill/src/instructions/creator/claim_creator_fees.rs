@@ -2,8 +2,11 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    errors::TokenMillError, events::TokenMillCreatorFeeClaimEvent,
-    manager::token_manager::transfer_from_pda, state::Market, MARKET_PDA_SEED,
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillCreatorFeeClaimEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Denylist, Market, TokenMillConfig},
 };
 
 #[event_cpi]
@@ -11,6 +14,7 @@ use crate::{
 pub struct ClaimCreatorFees<'info> {
     #[account(
         mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
         has_one = creator @ TokenMillError::InvalidAuthority,
         has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
     )]
@@ -37,23 +41,42 @@ pub struct ClaimCreatorFees<'info> {
     pub creator: Signer<'info>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
 }
 
 pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
-    let (pending_fees, base_token_mint, bump) = {
+    Denylist::check(
+        &ctx.accounts.config,
+        ctx.accounts.denylist.as_ref(),
+        &ctx.accounts.creator.key(),
+    )?;
+
+    let (pending_fees, market_seeds) = {
         let market = &mut ctx.accounts.market.load_mut()?;
 
+        market.lock()?;
+
+        let clock = get_clock()?;
+        market.apply_scheduled_updates(clock.slot, clock.unix_timestamp);
+
         let pending_fees = market.fees.pending_creator_fees;
         market.fees.pending_creator_fees = 0;
 
-        (pending_fees, market.base_token_mint, market.bump)
-    };
+        require_gte!(
+            pending_fees,
+            market.min_claim_amount,
+            TokenMillError::ClaimBelowMinimum
+        );
 
-    let market_seeds = [
-        MARKET_PDA_SEED.as_bytes(),
-        base_token_mint.as_ref(),
-        &[bump],
-    ];
+        market.check_fee_withdrawal(ctx.accounts.market_quote_token_ata.amount, pending_fees)?;
+
+        (pending_fees, market.seeds())
+    };
 
     transfer_from_pda(
         &ctx.accounts.quote_token_mint,
@@ -62,9 +85,11 @@ pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
         &ctx.accounts.creator_quote_token_ata,
         &ctx.accounts.quote_token_program,
         pending_fees,
-        &market_seeds,
+        &market_seeds.signer_seeds(),
     )?;
 
+    ctx.accounts.market.load_mut()?.unlock();
+
     emit_cpi!(TokenMillCreatorFeeClaimEvent {
         market: ctx.accounts.market.key(),
         creator: ctx.accounts.creator.key(),
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillPriceTailCompactionEvent;
+
+use super::MarketSettingsUpdate;
+
+/// Declares a compacted tail before the prices are written, for curves that flatten near the
+/// top. The prices then only fill the first `price_points` points of the curve.
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    price_points: u8,
+    tail_supply_bps: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.compact_price_tail(price_points, tail_supply_bps)?;
+
+    emit_cpi!(TokenMillPriceTailCompactionEvent {
+        market: ctx.accounts.market.key(),
+        price_points,
+        tail_supply_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::PRICES_LENGTH, Market};
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, CompactPriceTailAction, CreateMarketAction,
+                CreateQuoteAssetBadgeAction, SetMarketPricesAction, TokenMillEnv,
+            },
+            TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    fn setup_env() -> (TokenMillEnv, CompactPriceTailAction, SetMarketPricesAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        let action = CreateQuoteAssetBadgeAction::new(testing_env.quote_token_mint.unwrap());
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_market_action])
+            .unwrap();
+
+        let mut curve = Curve::default();
+
+        for i in 6..PRICES_LENGTH {
+            curve.bid_prices[i] = 0;
+            curve.ask_prices[i] = 0;
+        }
+
+        let action = CompactPriceTailAction::new(6, 5_000);
+
+        (testing_env, action, SetMarketPricesAction::new(curve))
+    }
+
+    #[test]
+    fn compact_price_tail() {
+        let (mut testing_env, action, set_prices_action) = setup_env();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action, &set_prices_action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.are_prices_set());
+        assert_eq!(market.price_points, 6);
+        assert_eq!(market.last_price_index(), 5);
+    }
+
+    #[test]
+    fn set_compacted_prices_without_compact_tail() {
+        let (mut testing_env, _, set_prices_action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&set_prices_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::DecreasingPrices);
+    }
+
+    #[test]
+    fn compact_price_tail_with_invalid_parameters() {
+        let (mut testing_env, mut action, _) = setup_env();
+
+        action.tail_supply_bps = 10_000;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPriceTail);
+    }
+
+    #[test]
+    fn compact_price_tail_after_prices_set() {
+        let (mut testing_env, action, _) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::PricesAlreadySet);
+    }
+
+    #[test]
+    fn compact_price_tail_with_invalid_signer() {
+        let (mut testing_env, mut action, _) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillSwapReceiptTreeCreationEvent,
+    manager::receipt_manager::{
+        init_empty_merkle_tree, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID,
+    },
+    state::{Market, SwapReceiptTree, SWAP_RECEIPT_TREE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSwapReceiptTree<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + SwapReceiptTree::INIT_SPACE,
+        seeds = [SWAP_RECEIPT_TREE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub swap_receipt_tree: Account<'info, SwapReceiptTree>,
+
+    /// CHECK: Allocated by the creator for the account compression program, which initializes it
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID @ TokenMillError::InvalidCompressionProgram)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL noop program
+    #[account(address = SPL_NOOP_PROGRAM_ID @ TokenMillError::InvalidCompressionProgram)]
+    pub noop_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opts the market into swap receipts, initializing the concurrent merkle tree receiving them.
+/// The tree account is allocated beforehand by the creator, its size depending on `max_depth`
+/// and `max_buffer_size`, and swaps passing the receipt accounts append a leaf to it.
+pub fn handler(
+    ctx: Context<CreateSwapReceiptTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let merkle_tree_key = ctx.accounts.merkle_tree.key();
+
+    ctx.accounts.swap_receipt_tree.initialize(
+        ctx.bumps.swap_receipt_tree,
+        market_key,
+        merkle_tree_key,
+    )?;
+
+    let swap_receipt_tree_seeds = [
+        SWAP_RECEIPT_TREE_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        &[ctx.bumps.swap_receipt_tree],
+    ];
+
+    init_empty_merkle_tree(
+        &ctx.accounts.compression_program,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.swap_receipt_tree.to_account_info(),
+        &ctx.accounts.noop_program,
+        max_depth,
+        max_buffer_size,
+        &swap_receipt_tree_seeds,
+    )?;
+
+    emit_cpi!(TokenMillSwapReceiptTreeCreationEvent {
+        market: market_key,
+        swap_receipt_tree: ctx.accounts.swap_receipt_tree.key(),
+        merkle_tree: merkle_tree_key,
+        max_depth,
+        max_buffer_size,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateSwapReceiptTreeAction, TokenMillEnv},
+        TokenMillError,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    #[test]
+    fn create_swap_receipt_tree_with_invalid_compression_program() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let mut action = CreateSwapReceiptTreeAction::new(&testing_env, Pubkey::new_unique());
+        action.compression_program = Pubkey::new_unique();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCompressionProgram);
+    }
+
+    #[test]
+    fn create_swap_receipt_tree_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = CreateSwapReceiptTreeAction::new(&testing_env, Pubkey::new_unique());
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
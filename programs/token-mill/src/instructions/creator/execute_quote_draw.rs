@@ -0,0 +1,252 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQuoteDrawEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteQuoteDraw<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub creator: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Sends the pending draw to the creator. Drawn quote no longer counts in the curve proceeds,
+/// so it is neither migrated nor paid to sellers.
+pub fn handler(ctx: Context<ExecuteQuoteDraw>) -> Result<()> {
+    let (amount, total_drawn, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        market.lock()?;
+
+        let amount = market.execute_draw(
+            &ctx.accounts.config.creator_draw,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        (amount, market.creator_drawn_quote, market.seeds())
+    };
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.creator_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        amount,
+        &market_seeds.signer_seeds(),
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillQuoteDrawEvent {
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+        total_drawn,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CreatorDraw, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ExecuteQuoteDrawAction, RequestQuoteDrawAction, SwapAction,
+            TokenMillEnv, UpdateCreatorDrawAction, DEFAULT_TOTAL_SUPPLY,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const BUY_AMOUNT: u64 = DEFAULT_TOTAL_SUPPLY / 2;
+    const DRAW_DELAY: i64 = 86_400;
+
+    fn setup_env() -> (TokenMillEnv, ExecuteQuoteDrawAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateCreatorDrawAction::new(CreatorDraw {
+                max_bps: 500,
+                delay: DRAW_DELAY,
+            })])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                BUY_AMOUNT,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = ExecuteQuoteDrawAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    fn request_max_draw(testing_env: &mut TokenMillEnv) -> u64 {
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let amount = market.get_max_draw_amount(500).unwrap();
+
+        let action = RequestQuoteDrawAction::new(testing_env, amount);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        amount
+    }
+
+    #[test]
+    fn execute_quote_draw() {
+        let (mut testing_env, action) = setup_env();
+
+        let amount = request_max_draw(&mut testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::CreatorDrawLocked);
+
+        testing_env.svm.warp(DRAW_DELAY);
+
+        let balance_before = testing_env
+            .svm
+            .get_balance(&action.quote_token_mint, &action.signer);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.signer)
+                - balance_before,
+            amount
+        );
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.creator_drawn_quote, amount);
+        assert_eq!(market.pending_draw_amount, 0);
+        assert_eq!(
+            market.get_curve_proceeds().unwrap(),
+            market.get_raised_quote().unwrap() - amount
+        );
+    }
+
+    #[test]
+    fn request_quote_draw_above_cap() {
+        let (mut testing_env, _) = setup_env();
+
+        let amount = request_max_draw(&mut testing_env);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&RequestQuoteDrawAction::new(&testing_env, amount + 1)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::CreatorDrawExceeded);
+    }
+
+    #[test]
+    fn sell_into_drawn_quote() {
+        let (mut testing_env, action) = setup_env();
+
+        request_max_draw(&mut testing_env);
+        testing_env.svm.warp(DRAW_DELAY);
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        // The last holders can't sell back the drawn quote
+        let result = testing_env.svm.execute_actions(&[&SwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            BUY_AMOUNT,
+            0,
+            None,
+        )]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientCurveProceeds);
+    }
+
+    #[test]
+    fn execute_quote_draw_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        request_max_draw(&mut testing_env);
+        testing_env.svm.warp(DRAW_DELAY);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
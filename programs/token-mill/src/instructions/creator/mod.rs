@@ -1,7 +1,35 @@
+pub mod adopt_new_fee_defaults;
+pub mod append_prices;
+pub mod boost_staking_rewards;
+pub mod cancel_scheduled_update;
 pub mod claim_creator_fees;
+pub mod compact_price_tail;
+pub mod create_swap_receipt_tree;
+pub mod execute_quote_draw;
+pub mod release_creation_bond;
+pub mod request_quote_draw;
+pub mod reset_prices;
+pub mod schedule_market_update;
 pub mod set_market_prices;
+pub mod set_prices_for_raise;
+pub mod set_prices_from_preset;
 pub mod update_creator;
+pub mod update_dynamic_fee;
+pub mod update_emission_schedule;
+pub mod update_holder_rewards_fee;
+pub mod update_launch_fee;
 pub mod update_market_fee_shares;
+pub mod update_market_max_swap_size;
+pub mod update_max_wallet_holding;
+pub mod update_min_claim_amount;
+pub mod update_min_referred_volume;
+pub mod update_staking_freeze;
 
+pub use adopt_new_fee_defaults::*;
+pub use boost_staking_rewards::*;
 pub use claim_creator_fees::*;
+pub use create_swap_receipt_tree::*;
+pub use execute_quote_draw::*;
+pub use release_creation_bond::*;
+pub use request_quote_draw::*;
 pub use set_market_prices::*;
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock, errors::TokenMillError, events::TokenMillCreationBondReleaseEvent,
+    state::Market,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseCreationBond<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Refunds the creation bond once the market graduated, or traded through the release epochs of
+/// the config.
+pub fn handler(ctx: Context<ReleaseCreationBond>) -> Result<()> {
+    let amount = ctx
+        .accounts
+        .market
+        .load_mut()?
+        .release_creation_bond(get_clock()?.unix_timestamp)?;
+
+    ctx.accounts.market.sub_lamports(amount)?;
+    ctx.accounts.creator.add_lamports(amount)?;
+
+    emit_cpi!(TokenMillCreationBondReleaseEvent {
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::REVENUE_EPOCH_DURATION, CreationBond, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ReleaseCreationBondAction, SwapAction, TokenMillEnv,
+            UpdateCreationBondAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const CREATION_BOND: u64 = 1_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, ReleaseCreationBondAction) {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateCreationBondAction::new(CreationBond {
+                amount: CREATION_BOND,
+                release_epochs: 1,
+            })])
+            .unwrap();
+
+        let mut testing_env = testing_env
+            .with_default_quote_token_mint()
+            .with_default_market();
+
+        let action = ReleaseCreationBondAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn release_creation_bond() {
+        let (mut testing_env, action) = setup_env();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.creation_bond, CREATION_BOND);
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+        testing_env.svm.change_payer("alice");
+
+        let market_lamports = testing_env.svm.get_account(&testing_env.market).lamports;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.creation_bond, 0);
+        assert_eq!(
+            testing_env.svm.get_account(&testing_env.market).lamports,
+            market_lamports - CREATION_BOND
+        );
+    }
+
+    #[test]
+    fn release_creation_bond_too_early() {
+        let (mut testing_env, action) = setup_env();
+
+        // Waiting out the release epochs isn't enough without trading
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::CreationBondLocked);
+    }
+
+    #[test]
+    fn release_creation_bond_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillQuoteDrawRequestEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct QuoteDrawRequest<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+/// Announces a draw of the curve proceeds, executable once the delay of the config elapsed.
+pub fn handler(ctx: Context<QuoteDrawRequest>, amount: u64) -> Result<()> {
+    let unlock_time = ctx.accounts.market.load_mut()?.request_draw(
+        &ctx.accounts.config.creator_draw,
+        amount,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillQuoteDrawRequestEvent {
+        market: ctx.accounts.market.key(),
+        amount,
+        unlock_time,
+    });
+
+    Ok(())
+}
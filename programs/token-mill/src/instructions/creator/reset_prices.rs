@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::PRICES_LENGTH, events::TokenMillMarketPriceSetEvent};
+
+use super::MarketPricesUpdate;
+
+pub fn handler(
+    ctx: Context<MarketPricesUpdate>,
+    bid_prices: [u64; PRICES_LENGTH],
+    ask_prices: [u64; PRICES_LENGTH],
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.reset_prices(bid_prices, ask_prices, &ctx.accounts.config.price_bounds)?;
+
+    emit_cpi!(TokenMillMarketPriceSetEvent {
+        market: ctx.accounts.market.key(),
+        bid_prices,
+        ask_prices,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{tm_parse_error, ResetPricesAction, SwapAction, TokenMillEnv},
+            SwapAmountType, SwapType, TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    fn setup_env() -> (TokenMillEnv, ResetPricesAction) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let mut curve = Curve::default();
+        curve.ask_prices[1] += 1;
+
+        let action = ResetPricesAction::new(curve);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn reset_prices() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.ask_prices[1], action.price_curve.ask_prices[1]);
+        assert_eq!(market.bid_prices[1], action.price_curve.bid_prices[1]);
+    }
+
+    #[test]
+    fn reset_prices_after_trade() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::CirculatingSupplyNotZero);
+    }
+
+    #[test]
+    fn reset_prices_after_round_trip() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        let mut swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        swap_action.swap_type = SwapType::Sell;
+        swap_action.swap_amount_type = SwapAmountType::ExactInput;
+        swap_action.other_amount_threshold = 0;
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.circulating_supply(), 0);
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::MarketAlreadyTraded);
+    }
+
+    #[test]
+    fn reset_prices_with_decreasing_prices() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.price_curve.ask_prices[2] = action.price_curve.ask_prices[3] + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::DecreasingPrices);
+    }
+
+    #[test]
+    fn reset_prices_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock, events::TokenMillMarketUpdateScheduleEvent, state::ScheduledUpdateKind,
+};
+
+use super::MarketSettingsUpdate;
+
+/// Queues the change of a market parameter to `value` at `slot`. It is applied by the first
+/// instruction of the market reading it from that slot, see `Market::apply_scheduled_updates`.
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    kind: ScheduledUpdateKind,
+    value: u64,
+    slot: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let clock = get_clock()?;
+
+    let index = market.schedule_update(kind, value, slot, clock.slot, clock.unix_timestamp)?;
+
+    emit_cpi!(TokenMillMarketUpdateScheduleEvent {
+        market: ctx.accounts.market.key(),
+        index: index as u8,
+        kind,
+        value,
+        slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{state::ScheduledUpdateKind, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimCreatorFeesAction, ScheduleMarketUpdateAction, SwapAction,
+            TokenMillEnv,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    #[test]
+    fn scheduled_min_claim_amount() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+        let slot = testing_env.svm.get_slot() + 100;
+
+        let action = ScheduleMarketUpdateAction::new(
+            &testing_env,
+            ScheduledUpdateKind::MinClaimAmount,
+            pending_creator_fees + 1,
+            slot,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.min_claim_amount, 0);
+        assert_eq!(market.scheduled_updates[0].slot, slot);
+        assert_eq!(market.scheduled_updates[0].value, pending_creator_fees + 1);
+
+        // Applied by the claim itself
+        testing_env.svm.warp_to_slot(slot);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&ClaimCreatorFeesAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ClaimBelowMinimum);
+    }
+
+    #[test]
+    fn schedule_market_update_at_current_slot() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = ScheduleMarketUpdateAction::new(
+            &testing_env,
+            ScheduledUpdateKind::MinClaimAmount,
+            1_000_000,
+            testing_env.svm.get_slot(),
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidScheduledUpdate);
+    }
+
+    #[test]
+    fn schedule_market_update_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = ScheduleMarketUpdateAction::new(
+            &testing_env,
+            ScheduledUpdateKind::MinClaimAmount,
+            1_000_000,
+            testing_env.svm.get_slot() + 100,
+        );
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
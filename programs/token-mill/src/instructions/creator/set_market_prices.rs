@@ -1,8 +1,11 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constant::PRICES_LENGTH, errors::TokenMillError, events::TokenMillMarketPriceSetEvent,
-    state::Market,
+    clock::get_clock,
+    constant::PRICES_LENGTH,
+    errors::TokenMillError,
+    events::TokenMillMarketPriceSetEvent,
+    state::{Market, TokenMillConfig},
 };
 
 #[event_cpi]
@@ -14,14 +17,31 @@ pub struct MarketSettingsUpdate<'info> {
     pub creator: Signer<'info>,
 }
 
+/// Creator update of the price curve, checked against the price bounds of the config.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketPricesUpdate<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
 pub fn handler(
-    ctx: Context<MarketSettingsUpdate>,
+    ctx: Context<MarketPricesUpdate>,
     bid_prices: [u64; PRICES_LENGTH],
     ask_prices: [u64; PRICES_LENGTH],
 ) -> Result<()> {
     let market = &mut ctx.accounts.market.load_mut()?;
 
-    market.check_and_set_prices(bid_prices, ask_prices)?;
+    market.check_and_set_prices(bid_prices, ask_prices, &ctx.accounts.config.price_bounds)?;
+    market.launch_slot = get_clock()?.slot;
 
     emit_cpi!(TokenMillMarketPriceSetEvent {
         market: ctx.accounts.market.key(),
@@ -36,13 +56,13 @@ pub fn handler(
 mod tests {
     use crate::{
         constant::{INTERVAL_NUMBER, MAX_PRICE},
-        Market,
+        Market, PriceBounds,
     };
     use joelana_test_utils::{
         joelana_env::{
             actions::token_mill::{
                 tm_parse_error, CreateMarketAction, CreateQuoteAssetBadgeAction,
-                SetMarketPricesAction, TokenMillEnv,
+                SetMarketPricesAction, TokenMillEnv, UpdatePriceBoundsAction,
             },
             TokenMillError,
         },
@@ -70,6 +90,17 @@ mod tests {
         (testing_env, action)
     }
 
+    fn set_price_bounds(testing_env: &mut TokenMillEnv, price_bounds: PriceBounds) {
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdatePriceBoundsAction::new(price_bounds)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+    }
+
     #[test]
     fn set_market_prices() {
         let (mut testing_env, action) = setup_env();
@@ -164,6 +195,68 @@ mod tests {
         assert_eq!(error, TokenMillError::PriceTooHigh);
     }
 
+    #[test]
+    fn set_market_prices_within_price_bounds() {
+        let (mut testing_env, action) = setup_env();
+
+        // The default curve has a 10% spread at every interval
+        set_price_bounds(
+            &mut testing_env,
+            PriceBounds {
+                min_total_raise: 1,
+                max_total_raise: u64::MAX,
+                min_spread_bps: 1_000,
+                max_spread_bps: 1_000,
+            },
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn set_market_prices_with_total_raise_too_high() {
+        let (mut testing_env, action) = setup_env();
+
+        set_price_bounds(
+            &mut testing_env,
+            PriceBounds {
+                max_total_raise: 1,
+                ..Default::default()
+            },
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::TotalRaiseTooHigh);
+    }
+
+    #[test]
+    fn set_market_prices_with_spread_too_wide() {
+        let (mut testing_env, action) = setup_env();
+
+        set_price_bounds(
+            &mut testing_env,
+            PriceBounds {
+                max_spread_bps: 500,
+                ..Default::default()
+            },
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::SpreadTooWide);
+    }
+
     #[test]
     fn set_market_prices_with_invalid_signer() {
         let (mut testing_env, mut action) = setup_env();
@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    events::TokenMillMarketPriceSetEvent,
+    manager::curve_manager::{self, CurveShape},
+};
+
+use super::MarketPricesUpdate;
+
+pub fn handler(
+    ctx: Context<MarketPricesUpdate>,
+    curve_shape: CurveShape,
+    target_raise: u64,
+    bid_spread_bps: u16,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    let (bid_prices, ask_prices) =
+        curve_manager::get_prices_for_raise(market, curve_shape, target_raise, bid_spread_bps)?;
+
+    market.check_and_set_prices(bid_prices, ask_prices, &ctx.accounts.config.price_bounds)?;
+    market.launch_slot = get_clock()?.slot;
+
+    emit_cpi!(TokenMillMarketPriceSetEvent {
+        market: ctx.accounts.market.key(),
+        bid_prices,
+        ask_prices,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_BPS, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketAction, SetMarketPricesAction, SetPricesForRaiseAction,
+            SwapAction, TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        CurveShape, SwapAmountType, SwapType, TokenMillError,
+    };
+    use joelana_test_utils::utils::token_mill::curve_generator::Curve;
+
+    const TARGET_RAISE: u64 = 85_000_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, SetPricesForRaiseAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()])
+            .unwrap();
+
+        let action = SetPricesForRaiseAction::new(CurveShape::Exponential, TARGET_RAISE, 1_000);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn set_prices_for_raise() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.are_prices_set());
+        assert_eq!(market.bid_prices[1], market.ask_prices[1] * 9 / 10);
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .create_ata(&bob, &base_token_mint, testing_env.base_token_type)
+            .unwrap();
+        testing_env
+            .svm
+            .create_ata(
+                &action.market,
+                &quote_token_mint,
+                testing_env.quote_token_type,
+            )
+            .unwrap();
+        testing_env
+            .svm
+            .tokens
+            .insert(base_token_mint, testing_env.base_token_type);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY,
+            u64::MAX,
+            None,
+        );
+
+        let balance_before = testing_env.svm.get_balance(&quote_token_mint, &bob);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let quote_amount = balance_before - testing_env.svm.get_balance(&quote_token_mint, &bob);
+
+        assert!(quote_amount.abs_diff(TARGET_RAISE) <= TARGET_RAISE / 1_000_000);
+    }
+
+    #[test]
+    fn set_prices_for_raise_with_prices_already_set() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::PricesAlreadySet);
+    }
+
+    #[test]
+    fn set_prices_for_raise_with_invalid_spread() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.bid_spread_bps = MAX_BPS as u16 + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCurveParameters);
+    }
+
+    #[test]
+    fn set_prices_for_raise_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
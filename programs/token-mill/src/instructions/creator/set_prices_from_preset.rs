@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    events::TokenMillMarketPriceSetEvent,
+    manager::curve_manager::{self, CurveParameters},
+};
+
+use super::MarketPricesUpdate;
+
+/// Sets the price curve from a preset instead of raw price arrays, going from `start_price` to
+/// `end_price` along the shape of the preset.
+pub fn handler(ctx: Context<MarketPricesUpdate>, parameters: CurveParameters) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    let (bid_prices, ask_prices) = curve_manager::get_prices_from_parameters(&parameters)?;
+
+    market.check_and_set_prices(bid_prices, ask_prices, &ctx.accounts.config.price_bounds)?;
+    market.launch_slot = get_clock()?.slot;
+
+    emit_cpi!(TokenMillMarketPriceSetEvent {
+        market: ctx.accounts.market.key(),
+        bid_prices,
+        ask_prices,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::PRICES_LENGTH, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketAction, SetPricesFromPresetAction, TokenMillEnv,
+        },
+        CurveParameters, CurveShape, TokenMillError,
+    };
+    use rstest::rstest;
+
+    fn setup_env(shape: CurveShape) -> (TokenMillEnv, SetPricesFromPresetAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()])
+            .unwrap();
+
+        let action = SetPricesFromPresetAction::new(CurveParameters {
+            shape,
+            start_price: 1_000,
+            end_price: 1_000_000_000,
+            bid_spread_bps: 1_000,
+        });
+
+        (testing_env, action)
+    }
+
+    #[rstest]
+    fn set_prices_from_preset(
+        #[values(
+            CurveShape::Linear,
+            CurveShape::Exponential,
+            CurveShape::Logistic,
+            CurveShape::ConstantProduct
+        )]
+        shape: CurveShape,
+    ) {
+        let (mut testing_env, action) = setup_env(shape);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.are_prices_set());
+        assert_eq!(market.ask_prices[0], 1_000);
+        assert_eq!(market.ask_prices[PRICES_LENGTH - 1], 1_000_000_000);
+        assert_eq!(market.bid_prices[1], market.ask_prices[1] * 9 / 10);
+    }
+
+    #[test]
+    fn set_prices_from_invalid_preset() {
+        let (mut testing_env, mut action) = setup_env(CurveShape::Logistic);
+
+        action.parameters.end_price = action.parameters.start_price;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidCurveParameters);
+    }
+
+    #[test]
+    fn set_prices_from_preset_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env(CurveShape::Linear);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
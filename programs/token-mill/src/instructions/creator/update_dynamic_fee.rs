@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillDynamicFeeUpdateEvent;
+
+use super::MarketSettingsUpdate;
+
+/// Scales the swap fee with the volatility of the market, see `swap_manager::update_variable_fee`.
+/// Swaps then require the market oracle.
+pub fn handler(ctx: Context<MarketSettingsUpdate>, dynamic_fee: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.dynamic_fee = u8::from(dynamic_fee);
+
+    if !dynamic_fee {
+        market.variable_fee_bps = 0;
+    }
+
+    emit_cpi!(TokenMillDynamicFeeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        dynamic_fee,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketOracleAction, SwapAction, TokenMillEnv,
+            UpdateDynamicFeeAction, UpdateSwapFeeBoundsAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn buy(testing_env: &mut TokenMillEnv, amount: u64) {
+        testing_env.svm.change_payer("bob");
+
+        let action = SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            amount,
+            u64::MAX,
+            None,
+        )
+        .with_market_oracle();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+    }
+
+    #[test]
+    fn update_dynamic_fee() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateSwapFeeBoundsAction::new(0, 500)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateDynamicFeeAction::new(&testing_env, true);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.is_dynamic_fee_enabled());
+
+        // Swaps require the market oracle
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        )]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::MissingMarketOracle
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateMarketOracleAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env.svm.warp(100);
+
+        buy(&mut testing_env, 1_000_000_000);
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.variable_fee_bps, 0);
+
+        // The price moves away from the twap of the oracle
+        testing_env.svm.warp(1);
+
+        buy(&mut testing_env, 200_000_000_000);
+
+        testing_env.svm.warp(1);
+
+        buy(&mut testing_env, 1_000_000_000);
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.variable_fee_bps > 0);
+        assert!(market.get_swap_fee_bps() <= 500);
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateDynamicFeeAction::new(&testing_env, false)])
+            .unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(!market.is_dynamic_fee_enabled());
+        assert_eq!(market.variable_fee_bps, 0);
+    }
+
+    #[test]
+    fn update_dynamic_fee_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateDynamicFeeAction::new(&testing_env, true);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
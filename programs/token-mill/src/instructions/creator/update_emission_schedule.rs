@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::TokenMillError, events::TokenMillEmissionScheduleUpdateEvent};
+
+use super::MarketSettingsUpdate;
+
+/// Opts the market into a gradual supply unlock before its curve goes live. Buys can only take
+/// `initial_supply` at `start`, the rest of the supply unlocking linearly over `duration`.
+/// A 0 `duration` unlocks the whole supply.
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    initial_supply: u64,
+    start: i64,
+    duration: i64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    require!(!market.are_prices_set(), TokenMillError::PricesAlreadySet);
+
+    market.set_emission_schedule(initial_supply, start, duration)?;
+
+    emit_cpi!(TokenMillEmissionScheduleUpdateEvent {
+        market: ctx.accounts.market.key(),
+        emission_initial_supply: initial_supply,
+        emission_start: start,
+        emission_duration: duration,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, CreateMarketAction, SetMarketPricesAction, SwapAction,
+                TokenMillEnv, UpdateEmissionScheduleAction, DEFAULT_TOTAL_SUPPLY,
+            },
+            SwapAmountType, SwapType, TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    const INITIAL_SUPPLY: u64 = DEFAULT_TOTAL_SUPPLY / 10;
+
+    fn setup_env() -> (TokenMillEnv, UpdateEmissionScheduleAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()])
+            .unwrap();
+
+        testing_env.market = SetMarketPricesAction::new(Curve::default()).market;
+
+        let start = testing_env.svm.get_unix_timestamp();
+        let action = UpdateEmissionScheduleAction::new(&testing_env, INITIAL_SUPPLY, start, 1_000);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn emission_schedule() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.emission_initial_supply, INITIAL_SUPPLY);
+        assert_eq!(market.emission_start, action.start);
+        assert_eq!(market.emission_duration, 1_000);
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .create_ata(&bob, &base_token_mint, testing_env.base_token_type)
+            .unwrap();
+        testing_env
+            .svm
+            .create_ata(
+                &action.market,
+                &quote_token_mint,
+                testing_env.quote_token_type,
+            )
+            .unwrap();
+        testing_env
+            .svm
+            .tokens
+            .insert(base_token_mint, testing_env.base_token_type);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            INITIAL_SUPPLY * 2,
+            u64::MAX,
+            None,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::SupplyLocked);
+
+        testing_env.svm.warp(1_000);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_balance(&base_token_mint, &bob),
+            INITIAL_SUPPLY * 2
+        );
+    }
+
+    #[test]
+    fn update_emission_schedule_after_launch() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::PricesAlreadySet);
+    }
+
+    #[test]
+    fn update_emission_schedule_above_total_supply() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.initial_supply = DEFAULT_TOTAL_SUPPLY + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidEmissionSchedule);
+    }
+
+    #[test]
+    fn update_emission_schedule_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
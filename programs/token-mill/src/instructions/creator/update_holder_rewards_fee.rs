@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MAX_HOLDER_REWARDS_FEE_BPS, errors::TokenMillError,
+    events::TokenMillHolderRewardsFeeUpdateEvent,
+};
+
+use super::MarketSettingsUpdate;
+
+/// Sets the sell tax of the market, accrued to the holders and distributed with
+/// `publish_holder_rewards`.
+pub fn handler(ctx: Context<MarketSettingsUpdate>, new_holder_rewards_fee_bps: u16) -> Result<()> {
+    require_gte!(
+        MAX_HOLDER_REWARDS_FEE_BPS,
+        u64::from(new_holder_rewards_fee_bps),
+        TokenMillError::InvalidHolderRewardsFee
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.fees.holder_rewards_fee_bps = new_holder_rewards_fee_bps;
+
+    emit_cpi!(TokenMillHolderRewardsFeeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        new_holder_rewards_fee_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateHolderRewardsFeeAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_holder_rewards_fee() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateHolderRewardsFeeAction::new(&testing_env, 500);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.fees.holder_rewards_fee_bps, 500);
+    }
+
+    #[test]
+    fn update_holder_rewards_fee_above_limit() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateHolderRewardsFeeAction::new(&testing_env, 1_001);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidHolderRewardsFee);
+    }
+
+    #[test]
+    fn update_holder_rewards_fee_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateHolderRewardsFeeAction::new(&testing_env, 500);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{MAX_LAUNCH_FEE_BPS, MAX_LAUNCH_FEE_SLOTS},
+    errors::TokenMillError,
+    events::TokenMillLaunchFeeUpdateEvent,
+};
+
+use super::MarketSettingsUpdate;
+
+/// Opts the market into launch protection before its curve goes live. Buys pay an extra
+/// `launch_fee_bps` at the launch slot, decaying linearly to 0 over `launch_fee_slots`, and the
+/// extra fee is routed to staking.
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    launch_fee_bps: u16,
+    launch_fee_slots: u64,
+) -> Result<()> {
+    require!(
+        u64::from(launch_fee_bps) <= MAX_LAUNCH_FEE_BPS && launch_fee_slots <= MAX_LAUNCH_FEE_SLOTS,
+        TokenMillError::InvalidLaunchFee
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    require!(!market.are_prices_set(), TokenMillError::PricesAlreadySet);
+
+    market.launch_fee_bps = launch_fee_bps;
+    market.launch_fee_slots = launch_fee_slots;
+
+    emit_cpi!(TokenMillLaunchFeeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        launch_fee_bps,
+        launch_fee_slots,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, CreateMarketAction, SetMarketPricesAction, SwapAction,
+                TokenMillEnv, UpdateLaunchFeeAction,
+            },
+            SwapAmountType, SwapType, TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+
+    fn setup_env() -> (TokenMillEnv, UpdateLaunchFeeAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .execute_actions(&[create_market_action.no_badge()])
+            .unwrap();
+
+        testing_env.market = SetMarketPricesAction::new(Curve::default()).market;
+
+        let action = UpdateLaunchFeeAction::new(&testing_env, 3_000, 100);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn launch_fee_ramp() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.launch_fee_bps, 3_000);
+        assert_eq!(market.launch_fee_slots, 100);
+        assert_eq!(market.launch_slot, testing_env.svm.get_slot());
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let bob = testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .create_ata(&bob, &base_token_mint, testing_env.base_token_type)
+            .unwrap();
+        testing_env
+            .svm
+            .create_ata(
+                &action.market,
+                &quote_token_mint,
+                testing_env.quote_token_type,
+            )
+            .unwrap();
+        testing_env
+            .svm
+            .tokens
+            .insert(base_token_mint, testing_env.base_token_type);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        let balance_before = testing_env.svm.get_balance(&quote_token_mint, &bob);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let balance_after = testing_env.svm.get_balance(&quote_token_mint, &bob);
+        let launch_quote_amount = balance_before - balance_after;
+
+        testing_env.svm.warp_to_slot(market.launch_slot + 100);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let quote_amount = balance_after - testing_env.svm.get_balance(&quote_token_mint, &bob);
+
+        // Both buys land in the first interval of the curve
+        assert!(launch_quote_amount > quote_amount * 12 / 10);
+    }
+
+    #[test]
+    fn update_launch_fee_after_launch() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::PricesAlreadySet);
+    }
+
+    #[test]
+    fn update_launch_fee_above_limit() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.launch_fee_bps = 5_001;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidLaunchFee);
+    }
+
+    #[test]
+    fn update_launch_fee_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MAX_BPS, errors::TokenMillError, events::TokenMillMarketMaxSwapSizeUpdateEvent,
+};
+
+use super::MarketSettingsUpdate;
+
+/// Sets the swap size limit of the market. It only tightens the limit of the config,
+/// as the lowest of both applies.
+pub fn handler(ctx: Context<MarketSettingsUpdate>, new_max_swap_size_bps: u16) -> Result<()> {
+    require_gte!(
+        MAX_BPS,
+        u64::from(new_max_swap_size_bps),
+        TokenMillError::InvalidSwapSizeLimit
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.max_swap_size_bps = new_max_swap_size_bps;
+
+    emit_cpi!(TokenMillMarketMaxSwapSizeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        new_max_swap_size_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, SwapAction, TokenMillEnv, UpdateMarketMaxSwapSizeAction,
+            UpdateMaxSwapSizeAction, DEFAULT_TOTAL_SUPPLY,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, SwapAction) {
+        let testing_env = TokenMillEnv::default();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 5,
+            u64::MAX,
+            None,
+        );
+
+        (testing_env, swap_action)
+    }
+
+    #[test]
+    fn update_market_max_swap_size() {
+        let (mut testing_env, swap_action) = setup_env();
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateMarketMaxSwapSizeAction::new(&testing_env, 1_000);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.max_swap_size_bps, 1_000);
+
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::SwapSizeExceeded);
+    }
+
+    #[test]
+    fn update_market_max_swap_size_above_config_limit() {
+        let (mut testing_env, swap_action) = setup_env();
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxSwapSizeAction::new(1_000)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMarketMaxSwapSizeAction::new(&testing_env, 5_000)])
+            .unwrap();
+
+        // The lower limit of the config still applies
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::SwapSizeExceeded);
+    }
+
+    #[test]
+    fn update_market_max_swap_size_with_invalid_signer() {
+        let (mut testing_env, _) = setup_env();
+
+        let mut action = UpdateMarketMaxSwapSizeAction::new(&testing_env, 1_000);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
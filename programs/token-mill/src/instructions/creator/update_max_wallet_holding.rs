@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MAX_BPS, errors::TokenMillError, events::TokenMillMaxWalletHoldingUpdateEvent,
+};
+
+use super::MarketSettingsUpdate;
+
+/// Caps the base tokens a wallet can hold after a buy on the curve, in bps of the total supply.
+/// The cap is checked against the base token ATA of the buyer, 0 lifts it.
+pub fn handler(ctx: Context<MarketSettingsUpdate>, new_max_wallet_holding_bps: u16) -> Result<()> {
+    require_gte!(
+        MAX_BPS,
+        u64::from(new_max_wallet_holding_bps),
+        TokenMillError::InvalidWalletHoldingCap
+    );
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.max_wallet_holding_bps = new_max_wallet_holding_bps;
+
+    emit_cpi!(TokenMillMaxWalletHoldingUpdateEvent {
+        market: ctx.accounts.market.key(),
+        new_max_wallet_holding_bps,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, SwapAction, TokenMillEnv, UpdateMaxWalletHoldingAction,
+            DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn buy_action(testing_env: &TokenMillEnv, base_amount: u64) -> SwapAction {
+        SwapAction::new(
+            testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            base_amount,
+            u64::MAX,
+            None,
+        )
+    }
+
+    #[test]
+    fn update_max_wallet_holding() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        // 1% of the supply
+        let action = UpdateMaxWalletHoldingAction::new(&testing_env, 100);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.max_wallet_holding_bps, 100);
+
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 50)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::WalletHoldingCapExceeded
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 200)])
+            .unwrap();
+
+        // The balance of the wallet counts towards the cap
+        let result = testing_env
+            .svm
+            .execute_actions(&[&buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 150)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::WalletHoldingCapExceeded
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 200)])
+            .unwrap();
+    }
+
+    #[test]
+    fn update_max_wallet_holding_buy_into_non_ata() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxWalletHoldingAction::new(&testing_env, 100)])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 200);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        // Another token account of the same wallet, which would start from an empty balance
+        let user_base_token_account = make_address("bob_base_token_account");
+        let mut account = testing_env.svm.get_account(&action.user_base_token_ata);
+        account.data[64..72].copy_from_slice(&0u64.to_le_bytes());
+
+        testing_env
+            .svm
+            .set_account(&user_base_token_account, account);
+
+        let mut action = buy_action(&testing_env, DEFAULT_TOTAL_SUPPLY / 200);
+        action.user_base_token_ata = user_base_token_account;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidWalletHoldingAccount
+        );
+    }
+
+    #[test]
+    fn update_max_wallet_holding_above_total_supply() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&UpdateMaxWalletHoldingAction::new(&testing_env, 10_001)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidWalletHoldingCap
+        );
+    }
+
+    #[test]
+    fn update_max_wallet_holding_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateMaxWalletHoldingAction::new(&testing_env, 100);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillMinClaimAmountUpdateEvent;
+
+use super::MarketSettingsUpdate;
+
+pub fn handler(ctx: Context<MarketSettingsUpdate>, new_min_claim_amount: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.min_claim_amount = new_min_claim_amount;
+
+    emit_cpi!(TokenMillMinClaimAmountUpdateEvent {
+        market: ctx.accounts.market.key(),
+        new_min_claim_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimCreatorFeesAction, SwapAction, TokenMillEnv,
+            UpdateMinClaimAmountAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    #[test]
+    fn update_min_claim_amount() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateMinClaimAmountAction::new(&testing_env, 1_000_000);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.min_claim_amount, 1_000_000);
+    }
+
+    #[test]
+    fn claim_creator_fees_below_min_claim_amount() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        let pending_creator_fees = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .fees
+            .pending_creator_fees;
+
+        let action = UpdateMinClaimAmountAction::new(&testing_env, pending_creator_fees + 1);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action, &ClaimCreatorFeesAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ClaimBelowMinimum);
+
+        let action = UpdateMinClaimAmountAction::new(&testing_env, pending_creator_fees);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action, &ClaimCreatorFeesAction::new(&testing_env)]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn update_min_claim_amount_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateMinClaimAmountAction::new(&testing_env, 1_000_000);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillMinReferredVolumeUpdateEvent;
+
+use super::MarketSettingsUpdate;
+
+pub fn handler(ctx: Context<MarketSettingsUpdate>, new_min_referred_volume: u64) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.min_referred_volume = new_min_referred_volume;
+
+    emit_cpi!(TokenMillMinReferredVolumeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        new_min_referred_volume,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, TokenMillEnv, UpdateMinReferredVolumeAction},
+        TokenMillError,
+    };
+
+    #[test]
+    fn update_min_referred_volume() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateMinReferredVolumeAction::new(&testing_env, 1_000_000_000);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.min_referred_volume, 1_000_000_000);
+    }
+
+    #[test]
+    fn update_min_referred_volume_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateMinReferredVolumeAction::new(&testing_env, 1_000_000_000);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::events::TokenMillStakingFreezeUpdateEvent;
+
+use super::MarketSettingsUpdate;
+
+/// Stops new staking deposits once the market is migrated, see `Market::is_staking_frozen`.
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    freeze_staking_on_migration: bool,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    market.freeze_staking_on_migration = u8::from(freeze_staking_on_migration);
+
+    emit_cpi!(TokenMillStakingFreezeUpdateEvent {
+        market: ctx.accounts.market.key(),
+        freeze_staking_on_migration,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, StakePosition};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, DepositAction, TokenMillEnv, UpdateStakingFreezeAction,
+        },
+        TokenMillError,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    #[test]
+    fn update_staking_freeze() {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env.svm.change_payer("alice");
+
+        let action = UpdateStakingFreezeAction::new(&testing_env, true);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.freeze_staking_on_migration, 1);
+
+        // Deposits are only frozen once the market is migrated
+        testing_env.svm.change_payer("bob");
+
+        let deposit_action = DepositAction::new(&testing_env, STAKE_AMOUNT);
+
+        testing_env.svm.execute_actions(&[&deposit_action]).unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&deposit_action.stake_position);
+
+        assert_eq!(stake_position.amount_staked, STAKE_AMOUNT);
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateStakingFreezeAction::new(&testing_env, false)])
+            .unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.freeze_staking_on_migration, 0);
+    }
+
+    #[test]
+    fn update_staking_freeze_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = UpdateStakingFreezeAction::new(&testing_env, true);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
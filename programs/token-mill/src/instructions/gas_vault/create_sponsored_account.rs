@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillSponsoredAccountCreationEvent,
+    state::{
+        GasVault, Market, SponsoredAccount, StakePosition, GAS_VAULT_PDA_SEED,
+        SPONSORED_ACCOUNT_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateSponsoredAccount<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [GAS_VAULT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = gas_vault.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub gas_vault: Account<'info, GasVault>,
+
+    #[account(
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = user @ TokenMillError::InvalidAuthority
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        constraint = token_mint.key() == market.load()?.base_token_mint
+            || token_mint.key() == market.load()?.quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = user,
+        associated_token::mint = token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program
+    )]
+    pub user_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + SponsoredAccount::INIT_SPACE,
+        seeds = [
+            SPONSORED_ACCOUNT_PDA_SEED.as_bytes(),
+            gas_vault.key().as_ref(),
+            user_token_ata.key().as_ref()
+        ],
+        bump
+    )]
+    pub sponsored_account: Account<'info, SponsoredAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Creates the ATA a staker needs to claim base or quote tokens from the market, refunding its
+/// rent from the gas vault of the market. Each token account is sponsored at most once.
+pub fn handler(ctx: Context<CreateSponsoredAccount>) -> Result<()> {
+    ctx.accounts.sponsored_account.bump = ctx.bumps.sponsored_account;
+
+    let lamports = ctx.accounts.user_token_ata.to_account_info().lamports()
+        + ctx.accounts.sponsored_account.get_lamports();
+
+    let gas_vault = &mut ctx.accounts.gas_vault;
+
+    require_gte!(
+        GasVault::get_available_lamports(gas_vault.get_lamports())?,
+        lamports,
+        TokenMillError::InsufficientGasVaultBalance
+    );
+
+    gas_vault.sub_lamports(lamports)?;
+    ctx.accounts.user.add_lamports(lamports)?;
+
+    gas_vault.record_sponsorship(lamports);
+
+    emit_cpi!(TokenMillSponsoredAccountCreationEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        token_account: ctx.accounts.user_token_ata.key(),
+        lamports,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GasVault;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateSponsoredAccountAction, CreateStakePositionAction,
+            DepositGasVaultAction, TokenMillEnv,
+        },
+        make_address, TokenMillError,
+    };
+
+    fn setup_env(deposit_amount: u64) -> (TokenMillEnv, CreateSponsoredAccountAction) {
+        let mut testing_env = TokenMillEnv::default().with_staking(0);
+
+        let erin = make_address("erin");
+        testing_env.svm.airdrop(&erin);
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositGasVaultAction::new(&testing_env, deposit_amount)])
+            .unwrap();
+
+        testing_env.svm.change_payer("erin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateStakePositionAction::new(&testing_env).with_signer(erin)])
+            .unwrap();
+
+        let action = CreateSponsoredAccountAction::new(
+            &testing_env,
+            testing_env.base_token_mint.unwrap(),
+            testing_env.base_token_type,
+        )
+        .with_signer(erin);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn create_sponsored_account() {
+        let (mut testing_env, action) = setup_env(1_000_000_000);
+
+        let user_lamports_before = testing_env.svm.get_account(&action.signer).lamports;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let gas_vault = testing_env
+            .svm
+            .get_parsed_account::<GasVault>(&action.gas_vault);
+
+        let sponsored_lamports = testing_env.svm.get_account(&action.user_token_ata).lamports
+            + testing_env
+                .svm
+                .get_account(&action.sponsored_account)
+                .lamports;
+
+        assert_eq!(gas_vault.sponsored_accounts, 1);
+        assert_eq!(gas_vault.total_sponsored, sponsored_lamports);
+        assert_eq!(
+            testing_env.svm.get_account(&action.signer).lamports,
+            user_lamports_before
+        );
+    }
+
+    #[test]
+    fn create_sponsored_account_with_empty_vault() {
+        let (mut testing_env, action) = setup_env(1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientGasVaultBalance);
+    }
+
+    #[test]
+    fn create_sponsored_account_without_stake_position() {
+        let (mut testing_env, mut action) = setup_env(1_000_000_000);
+
+        // Stakers can't get accounts sponsored for anyone else
+        let frank = make_address("frank");
+        testing_env.svm.airdrop(&frank);
+        testing_env.svm.change_payer("frank");
+
+        let stake_position = action.stake_position;
+        action = action.with_signer(frank);
+        action.stake_position = stake_position;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillGasVaultDepositEvent,
+    state::{GasVault, Market, GAS_VAULT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositGasVault<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + GasVault::INIT_SPACE,
+        seeds = [GAS_VAULT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub gas_vault: Account<'info, GasVault>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositGasVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, TokenMillError::InvalidAmount);
+
+    let gas_vault = &mut ctx.accounts.gas_vault;
+
+    if gas_vault.market == Pubkey::default() {
+        gas_vault.initialize(ctx.bumps.gas_vault, ctx.accounts.market.key())?;
+    }
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator.to_account_info(),
+                to: gas_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    gas_vault.total_deposited += amount;
+
+    emit_cpi!(TokenMillGasVaultDepositEvent {
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GasVault;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, DepositGasVaultAction, TokenMillEnv},
+        TokenMillError,
+    };
+
+    const DEPOSIT_AMOUNT: u64 = 1_000_000_000;
+
+    #[test]
+    fn deposit_gas_vault() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = DepositGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &action])
+            .unwrap();
+
+        let gas_vault = testing_env
+            .svm
+            .get_parsed_account::<GasVault>(&action.gas_vault);
+
+        assert_eq!(gas_vault.market, action.market);
+        assert_eq!(gas_vault.total_deposited, 2 * DEPOSIT_AMOUNT);
+        assert!(testing_env.svm.get_account(&action.gas_vault).lamports > 2 * DEPOSIT_AMOUNT);
+    }
+
+    #[test]
+    fn deposit_gas_vault_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = DepositGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod create_sponsored_account;
+pub mod deposit_gas_vault;
+pub mod withdraw_gas_vault;
+
+pub use create_sponsored_account::*;
+pub use deposit_gas_vault::*;
+pub use withdraw_gas_vault::*;
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillGasVaultWithdrawalEvent,
+    state::{GasVault, Market, GAS_VAULT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawGasVault<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [GAS_VAULT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = gas_vault.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub gas_vault: Account<'info, GasVault>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Refunds unspent lamports of the gas vault to the creator.
+pub fn handler(ctx: Context<WithdrawGasVault>, amount: u64) -> Result<()> {
+    let gas_vault = &mut ctx.accounts.gas_vault;
+
+    require_gte!(
+        GasVault::get_available_lamports(gas_vault.get_lamports())?,
+        amount,
+        TokenMillError::InsufficientGasVaultBalance
+    );
+
+    gas_vault.sub_lamports(amount)?;
+    ctx.accounts.creator.add_lamports(amount)?;
+
+    gas_vault.total_withdrawn += amount;
+
+    emit_cpi!(TokenMillGasVaultWithdrawalEvent {
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::GasVault;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, DepositGasVaultAction, TokenMillEnv, WithdrawGasVaultAction,
+        },
+        TokenMillError,
+    };
+
+    const DEPOSIT_AMOUNT: u64 = 1_000_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT)])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn withdraw_gas_vault() {
+        let mut testing_env = setup_env();
+
+        let action = WithdrawGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT);
+
+        let vault_lamports_before = testing_env.svm.get_account(&action.gas_vault).lamports;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let gas_vault = testing_env
+            .svm
+            .get_parsed_account::<GasVault>(&action.gas_vault);
+
+        assert_eq!(gas_vault.total_withdrawn, DEPOSIT_AMOUNT);
+        assert_eq!(
+            testing_env.svm.get_account(&action.gas_vault).lamports,
+            vault_lamports_before - DEPOSIT_AMOUNT
+        );
+    }
+
+    #[test]
+    fn withdraw_gas_vault_above_balance() {
+        let mut testing_env = setup_env();
+
+        let action = WithdrawGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT + 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientGasVaultBalance);
+    }
+
+    #[test]
+    fn withdraw_gas_vault_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = WithdrawGasVaultAction::new(&testing_env, DEPOSIT_AMOUNT);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,188 @@
+use anchor_lang::{common::close, prelude::*};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketClosureEvent,
+    manager::token_manager::close_pda_token_account,
+    state::{Market, MarketStaking, TokenMillConfig, MARKET_STAKING_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseMarket<'info> {
+    #[account(has_one = rent_recipient @ TokenMillError::InvalidRentRecipient)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount,
+        close = rent_recipient
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: Loaded as `MarketStaking` when created, so that it can't be left out
+    #[account(
+        mut,
+        seeds = [MARKET_STAKING_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub staking: UncheckedAccount<'info>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Checked against the rent recipient of the config
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Reclaims the rent of a migrated market once nothing is left in it: the reserves moved to the
+/// pool, the fees claimed and the stakes withdrawn or vested. Permissionless, the rent goes to the config.
+pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+    let market_seeds = {
+        let market = ctx.accounts.market.load()?;
+
+        require!(market.is_migrated(), TokenMillError::MarketNotMigrated);
+
+        require!(
+            market.base_reserve == 0
+                && market.creation_bond == 0
+                && market.fees.pending_creator_fees == 0
+                && market.fees.pending_staking_fees == 0
+                && market.pending_holder_rewards == 0
+                && ctx.accounts.market_base_token_ata.amount == 0
+                && ctx.accounts.market_quote_token_ata.amount == 0,
+            TokenMillError::MarketNotEmpty
+        );
+
+        market.seeds()
+    };
+
+    if !ctx.accounts.staking.data_is_empty() {
+        // Legacy stakings are shorter than the current layout, missing fields read as zero
+        let mut data = ctx.accounts.staking.try_borrow_data()?.to_vec();
+        data.resize(data.len().max(8 + MarketStaking::INIT_SPACE), 0);
+
+        let staking = MarketStaking::try_deserialize(&mut &data[..])?;
+
+        require!(
+            staking.amount_staked == 0 && staking.total_amount_vested == 0,
+            TokenMillError::MarketNotEmpty
+        );
+
+        close(
+            ctx.accounts.staking.to_account_info(),
+            ctx.accounts.rent_recipient.to_account_info(),
+        )?;
+    }
+
+    let seeds = market_seeds.signer_seeds();
+
+    close_pda_token_account(
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_base_token_ata,
+        ctx.accounts.rent_recipient.to_account_info(),
+        &ctx.accounts.base_token_program,
+        &seeds,
+    )?;
+
+    close_pda_token_account(
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        ctx.accounts.rent_recipient.to_account_info(),
+        &ctx.accounts.quote_token_program,
+        &seeds,
+    )?;
+
+    emit_cpi!(TokenMillMarketClosureEvent {
+        market: ctx.accounts.market.key(),
+        rent_recipient: ctx.accounts.rent_recipient.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CloseMarketAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> (TokenMillEnv, CloseMarketAction) {
+        let testing_env = TokenMillEnv::default();
+
+        let action = CloseMarketAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn close_market_before_migration() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::MarketNotMigrated);
+
+        assert!(testing_env.svm.account_exists(&action.market));
+    }
+
+    #[test]
+    fn close_market_with_invalid_rent_recipient() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.rent_recipient = make_address("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidRentRecipient);
+    }
+
+    #[test]
+    fn close_market_without_staking() {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        let mut action = CloseMarketAction::new(&testing_env);
+        action.staking = crate::ID;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        assert!(testing_env.svm.account_exists(&action.market));
+    }
+}
@@ -0,0 +1,65 @@
+use std::slice::Iter;
+
+use anchor_lang::prelude::*;
+
+use super::Migrate;
+use crate::manager::migration_manager::{self, MeteoraInitializePoolArgs};
+
+/// Remaining accounts: `position_nft_mint` (signer), `position_nft_account`, `amm_config`,
+/// `pool_authority`, `pool`, `position`, `token_a_vault`, `token_b_vault`, `token_2022_program`
+/// and the event authority of DAMM v2. The market is the pool creator, so it owns the position
+/// NFT, and no instruction transfers it out. Rounding dust of the liquidity stays with the
+/// config authority.
+pub fn migrate_to_meteora<'info>(
+    accounts: &Migrate<'info>,
+    remaining_accounts: &mut Iter<'_, AccountInfo<'info>>,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Result<(Pubkey, u128)> {
+    let position_nft_mint = next_account_info(remaining_accounts)?;
+    let position_nft_account = next_account_info(remaining_accounts)?;
+    let amm_config = next_account_info(remaining_accounts)?;
+    let pool_authority = next_account_info(remaining_accounts)?;
+    let pool = next_account_info(remaining_accounts)?;
+    let position = next_account_info(remaining_accounts)?;
+    let token_a_vault = next_account_info(remaining_accounts)?;
+    let token_b_vault = next_account_info(remaining_accounts)?;
+    let token_2022_program = next_account_info(remaining_accounts)?;
+    let amm_event_authority = next_account_info(remaining_accounts)?;
+
+    let (sqrt_price, liquidity) =
+        migration_manager::get_meteora_pool_parameters(base_amount, quote_amount)?;
+
+    migration_manager::initialize_meteora_pool(
+        &accounts.migration_program,
+        &[
+            accounts.market.to_account_info(),
+            position_nft_mint.clone(),
+            position_nft_account.clone(),
+            accounts.authority.to_account_info(),
+            amm_config.clone(),
+            pool_authority.clone(),
+            pool.clone(),
+            position.clone(),
+            accounts.base_token_mint.to_account_info(),
+            accounts.quote_token_mint.to_account_info(),
+            token_a_vault.clone(),
+            token_b_vault.clone(),
+            accounts.authority_base_token_account.to_account_info(),
+            accounts.authority_quote_token_account.to_account_info(),
+            accounts.base_token_program.to_account_info(),
+            accounts.quote_token_program.to_account_info(),
+            token_2022_program.clone(),
+            accounts.system_program.to_account_info(),
+            amm_event_authority.clone(),
+            accounts.migration_program.to_account_info(),
+        ],
+        &MeteoraInitializePoolArgs {
+            liquidity,
+            sqrt_price,
+            activation_point: None,
+        },
+    )?;
+
+    Ok((pool.key(), liquidity))
+}
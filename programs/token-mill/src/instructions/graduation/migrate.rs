@@ -0,0 +1,543 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use super::{meteora::migrate_to_meteora, raydium::migrate_to_raydium};
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillMarketGraduationEvent,
+    manager::{
+        graduation_hook_manager::{self, OnGraduationArgs},
+        price_manager::{self, PriceUpdateV2},
+        token_manager::transfer_from_pda,
+    },
+    state::{
+        GraduationAttestation, Market, MigrationTarget, QuoteTokenBadge, TokenMillConfig,
+        GRADUATION_ATTESTATION_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = base_token_mint,
+        token::authority = authority,
+        token::token_program = base_token_program
+    )]
+    pub authority_base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = quote_token_mint,
+        token::authority = authority,
+        token::token_program = quote_token_program
+    )]
+    pub authority_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Checked against the migration target of the config
+    #[account(address = config.migration_target.program_id() @ TokenMillError::InvalidMigrationProgram)]
+    pub migration_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// Required with a price update once the config sets a USD graduation threshold
+    #[account(
+        seeds = [
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            quote_token_mint.key().as_ref(),
+        ],
+        bump = quote_token_badge.bump,
+    )]
+    pub quote_token_badge: Option<Account<'info, QuoteTokenBadge>>,
+
+    /// CHECK: Pyth price update of the feed of the quote token badge, see `PriceUpdateV2::load`
+    pub quote_price_update: Option<UncheckedAccount<'info>>,
+
+    /// Only exists once the market migrated, see `migrate_idempotent`
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + GraduationAttestation::INIT_SPACE,
+        seeds = [GRADUATION_ATTESTATION_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub graduation_attestation: Box<Account<'info, GraduationAttestation>>,
+
+    /// CHECK: Checked against the graduation hook program of the config
+    pub graduation_hook_program: Option<UncheckedAccount<'info>>,
+}
+
+/// Graduates the market to a pool of the migration target of the config, seeded with the unsold
+/// base reserve and the curve proceeds. The reserves transit through the accounts of the config
+/// authority, which creates the pool, and the liquidity ends up locked with the market.
+/// The pool accounts are passed as remaining accounts, see `migrate_to_raydium` and
+/// `migrate_to_meteora`, followed by the accounts of the graduation hook of the config, if any.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+    graduation_hook_manager::check(
+        ctx.accounts.config.graduation_hook_program,
+        ctx.accounts.graduation_hook_program.as_ref(),
+    )?;
+
+    let (base_amount, quote_amount, market_seeds) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        let usd_graduation = &ctx.accounts.config.usd_graduation;
+
+        if usd_graduation.usd_threshold > 0 {
+            let feed_id = ctx
+                .accounts
+                .quote_token_badge
+                .as_ref()
+                .and_then(|badge| badge.price_feed_id)
+                .ok_or(TokenMillError::InvalidPriceFeed)?;
+            let quote_price_update = ctx
+                .accounts
+                .quote_price_update
+                .as_ref()
+                .ok_or(TokenMillError::InvalidPriceFeed)?;
+
+            let price_update = PriceUpdateV2::load(quote_price_update, &feed_id)?;
+
+            let usd_value = price_manager::get_usd_value(
+                &price_update.price_message,
+                usd_graduation,
+                market.get_curve_proceeds()?,
+                market.quote_token_decimals,
+                get_clock()?.unix_timestamp,
+            )?;
+
+            require_gte!(
+                usd_value,
+                usd_graduation.usd_threshold,
+                TokenMillError::GraduationThresholdNotMet
+            );
+        }
+
+        let (base_amount, quote_amount) = market.migrate(
+            ctx.accounts.config.graduation_quote_threshold,
+            ctx.accounts.config.graduation_supply_bps,
+        )?;
+
+        (base_amount, quote_amount, market.seeds())
+    };
+
+    let seeds = market_seeds.signer_seeds();
+
+    transfer_from_pda(
+        &ctx.accounts.base_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.authority_base_token_account,
+        &ctx.accounts.base_token_program,
+        base_amount,
+        &seeds,
+    )?;
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.authority_quote_token_account,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+        &seeds,
+    )?;
+
+    let remaining_accounts = &mut ctx.remaining_accounts.iter();
+
+    // Venues aren't deployed on simulation forks, the liquidity then stays with the authority
+    let (pool, liquidity) = if cfg!(feature = "simulation") {
+        (Pubkey::default(), 0)
+    } else {
+        match ctx.accounts.config.migration_target {
+            MigrationTarget::RaydiumCpmm => {
+                migrate_to_raydium(ctx.accounts, remaining_accounts, base_amount, quote_amount)?
+            }
+            MigrationTarget::MeteoraDammV2 => {
+                migrate_to_meteora(ctx.accounts, remaining_accounts, base_amount, quote_amount)?
+            }
+        }
+    };
+
+    let market_key = ctx.accounts.market.key();
+    let graduation_attestation = &mut ctx.accounts.graduation_attestation;
+
+    graduation_attestation.initialize(
+        ctx.bumps.graduation_attestation,
+        market_key,
+        ctx.accounts.base_token_mint.key(),
+        ctx.accounts.quote_token_mint.key(),
+        pool,
+        base_amount,
+        quote_amount,
+        &get_clock()?,
+    )?;
+
+    // Written before the hook can read it
+    graduation_attestation.exit(&crate::ID)?;
+
+    graduation_hook_manager::notify(
+        ctx.accounts.config.graduation_hook_program,
+        ctx.accounts.graduation_hook_program.as_ref(),
+        graduation_attestation.to_account_info(),
+        &[
+            GRADUATION_ATTESTATION_PDA_SEED.as_bytes(),
+            market_key.as_ref(),
+            &[ctx.bumps.graduation_attestation],
+        ],
+        remaining_accounts.as_slice(),
+        &OnGraduationArgs {
+            config: ctx.accounts.config.key(),
+            market: market_key,
+            base_token_mint: ctx.accounts.base_token_mint.key(),
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            pool,
+            base_amount,
+            quote_amount,
+            price: graduation_attestation.price,
+        },
+    )?;
+
+    emit_cpi!(TokenMillMarketGraduationEvent {
+        market: ctx.accounts.market.key(),
+        migration_target: ctx.accounts.config.migration_target,
+        pool,
+        base_amount,
+        quote_amount,
+        liquidity,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MigrationTarget, UsdGraduation};
+    use anchor_lang::prelude::Pubkey;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, tm_set_price_update, CreateQuoteAssetBadgeAction, MigrateAction,
+            SwapAction, TokenMillEnv, UpdateGraduationHookProgramAction,
+            UpdateGraduationThresholdsAction, UpdateMigrationTargetAction,
+            UpdateQuoteTokenPriceFeedAction, UpdateUsdGraduationAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const FEED_ID: [u8; 32] = [1; 32];
+
+    fn setup_env() -> (TokenMillEnv, MigrateAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = MigrateAction::new(&testing_env);
+
+        testing_env
+            .svm
+            .create_ata(
+                &action.signer,
+                &action.base_token_mint,
+                testing_env.base_token_type,
+            )
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn migrate_without_curve_proceeds() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::NothingToMigrate);
+    }
+
+    #[test]
+    fn migrate_below_graduation_thresholds() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateGraduationThresholdsAction::new(u64::MAX, 0)])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::GraduationThresholdNotMet);
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateGraduationThresholdsAction::new(0, 10_000)])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::GraduationThresholdNotMet);
+    }
+
+    // Buys some supply and sets a USD threshold above the curve proceeds, valued at 1 USD
+    fn setup_usd_graduation(testing_env: &mut TokenMillEnv, action: &MigrateAction) {
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateQuoteAssetBadgeAction::new(action.quote_token_mint),
+                &UpdateQuoteTokenPriceFeedAction::new(action.quote_token_mint, Some(FEED_ID)),
+                &UpdateUsdGraduationAction::new(UsdGraduation {
+                    usd_threshold: u64::MAX,
+                    max_price_staleness: 60,
+                    max_price_conf_bps: 100,
+                }),
+            ])
+            .unwrap();
+    }
+
+    #[test]
+    fn migrate_below_usd_graduation_threshold() {
+        let (mut testing_env, action) = setup_env();
+
+        setup_usd_graduation(&mut testing_env, &action);
+
+        let publish_time = testing_env.svm.get_unix_timestamp();
+        let price_update = tm_set_price_update(
+            &mut testing_env.svm,
+            FEED_ID,
+            100_000_000,
+            10_000,
+            -8,
+            publish_time,
+        );
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action.with_price_update(price_update)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::GraduationThresholdNotMet);
+    }
+
+    #[test]
+    fn migrate_without_price_update() {
+        let (mut testing_env, action) = setup_env();
+
+        setup_usd_graduation(&mut testing_env, &action);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPriceFeed);
+    }
+
+    #[test]
+    fn migrate_with_stale_price() {
+        let (mut testing_env, action) = setup_env();
+
+        setup_usd_graduation(&mut testing_env, &action);
+
+        let publish_time = testing_env.svm.get_unix_timestamp() - 61;
+        let price_update = tm_set_price_update(
+            &mut testing_env.svm,
+            FEED_ID,
+            100_000_000,
+            10_000,
+            -8,
+            publish_time,
+        );
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action.with_price_update(price_update)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::StalePrice);
+    }
+
+    #[test]
+    fn migrate_with_invalid_program() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.migration_program = Pubkey::new_unique();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidMigrationProgram);
+    }
+
+    #[test]
+    fn migrate_without_graduation_hook_program() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateGraduationHookProgramAction::new(Some(make_address(
+                "graduation_hook_program",
+            )))])
+            .unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidGraduationHookProgram);
+    }
+
+    #[test]
+    fn migrate_to_meteora_without_curve_proceeds() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMigrationTargetAction::new(
+                MigrationTarget::MeteoraDammV2,
+            )])
+            .unwrap();
+
+        // Pool accounts of the previous target
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidMigrationProgram);
+
+        let result = testing_env.svm.execute_actions(&[&action.with_meteora()]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::NothingToMigrate);
+    }
+
+    #[test]
+    fn migrate_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let mallory = testing_env.svm.change_payer("mallory");
+
+        action.signer = mallory;
+        action.authority_base_token_ata = testing_env
+            .svm
+            .create_ata(
+                &mallory,
+                &action.base_token_mint,
+                testing_env.base_token_type,
+            )
+            .unwrap();
+        action.authority_quote_token_ata = testing_env
+            .svm
+            .get_ata_address(&action.quote_token_mint, &mallory);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
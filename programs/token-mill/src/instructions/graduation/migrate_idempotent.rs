@@ -0,0 +1,167 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::prelude::*;
+
+use super::migrate::{self, Migrate, MigrateBumps};
+use crate::{
+    errors::TokenMillError,
+    state::{Market, GRADUATION_ATTESTATION_PDA_SEED},
+};
+
+#[derive(Accounts)]
+pub struct MigrateIdempotent<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: Only exists once the market migrated, checked against its address
+    #[account(
+        seeds = [GRADUATION_ATTESTATION_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub graduation_attestation: UncheckedAccount<'info>,
+}
+
+/// Bundle-friendly variant of `migrate`: succeeds without changes if the market already migrated,
+/// as attested by its graduation attestation. The accounts of `migrate` follow as remaining
+/// accounts, and are only loaded when the market still has to migrate.
+/// The config authority gates the migration itself, not the retries: once migrated, the call is
+/// a permissionless no-op that needs neither the signature of the authority nor the pool
+/// accounts, so a retried bundle can't fail on accounts that changed since the migration.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, MigrateIdempotent<'info>>) -> Result<()> {
+    if ctx.accounts.graduation_attestation.owner == &crate::ID {
+        return Ok(());
+    }
+
+    let mut remaining_accounts = ctx.remaining_accounts;
+    let mut bumps = MigrateBumps::default();
+
+    let mut accounts = Migrate::try_accounts(
+        ctx.program_id,
+        &mut remaining_accounts,
+        &[],
+        &mut bumps,
+        &mut BTreeSet::new(),
+    )?;
+
+    require_keys_eq!(
+        accounts.market.key(),
+        ctx.accounts.market.key(),
+        TokenMillError::InvalidMarket
+    );
+
+    migrate::handler(Context::new(
+        ctx.program_id,
+        &mut accounts,
+        remaining_accounts,
+        bumps,
+    ))?;
+
+    accounts.exit(ctx.program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::{AccountSerialize, Space};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, MigrateAction, TokenMillEnv},
+        TokenMillError,
+    };
+    use solana_sdk::{account::Account, pubkey::Pubkey};
+
+    use crate::GraduationAttestation;
+
+    fn setup_env() -> (TokenMillEnv, MigrateAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = MigrateAction::new(&testing_env).idempotent();
+
+        testing_env
+            .svm
+            .create_ata(
+                &action.signer,
+                &action.base_token_mint,
+                testing_env.base_token_type,
+            )
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        (testing_env, action)
+    }
+
+    fn attest_migration(testing_env: &mut TokenMillEnv, action: &MigrateAction) {
+        let graduation_attestation = GraduationAttestation {
+            bump: 0,
+            market: action.market,
+            base_token_mint: action.base_token_mint,
+            quote_token_mint: action.quote_token_mint,
+            pool: action.pool_accounts[2].pubkey,
+            base_amount: 1,
+            quote_amount: 1,
+            price: 1,
+            slot: 0,
+            timestamp: 0,
+        };
+
+        let mut data = Vec::with_capacity(8 + GraduationAttestation::INIT_SPACE);
+        graduation_attestation.try_serialize(&mut data).unwrap();
+
+        testing_env.svm.set_account(
+            &action.graduation_attestation,
+            Account {
+                lamports: 1_000_000_000,
+                data,
+                owner: crate::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+    }
+
+    #[test]
+    fn migrate_idempotent_when_already_migrated() {
+        let (mut testing_env, action) = setup_env();
+
+        attest_migration(&mut testing_env, &action);
+
+        let market_before = testing_env.svm.get_account(&action.market);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(testing_env.svm.get_account(&action.market), market_before);
+    }
+
+    #[test]
+    fn migrate_idempotent_retry_with_changed_accounts() {
+        let (mut testing_env, mut action) = setup_env();
+
+        attest_migration(&mut testing_env, &action);
+
+        // Neither the signature of the authority nor valid migration accounts are needed
+        action.signer = testing_env.svm.change_payer("mallory");
+        action.migration_program = Pubkey::new_unique();
+        action.pool_accounts.clear();
+
+        let market_before = testing_env.svm.get_account(&action.market);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(testing_env.svm.get_account(&action.market), market_before);
+    }
+
+    #[test]
+    fn migrate_idempotent_without_curve_proceeds() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::NothingToMigrate);
+
+        assert!(!testing_env
+            .svm
+            .account_exists(&action.graduation_attestation));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod close_market;
+mod meteora;
+pub mod migrate;
+pub mod migrate_idempotent;
+mod raydium;
+
+pub use close_market::*;
+pub use migrate::*;
+pub use migrate_idempotent::*;
@@ -0,0 +1,117 @@
+use std::slice::Iter;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, Create},
+    token_interface::{transfer_checked, Mint, TokenAccount, TransferChecked},
+};
+
+use super::Migrate;
+use crate::manager::migration_manager::{self, RaydiumInitializeArgs};
+
+/// Remaining accounts: `amm_config`, `pool_authority`, `pool_state`, `lp_mint`,
+/// `authority_lp_token`, `token_0_vault`, `token_1_vault`, `create_pool_fee`,
+/// `observation_state`, `lp_token_program` and `market_lp_token_ata`, the associated LP token
+/// account of the market, created once the pool is. No instruction transfers the LP tokens out.
+pub fn migrate_to_raydium<'info>(
+    accounts: &Migrate<'info>,
+    remaining_accounts: &mut Iter<'_, AccountInfo<'info>>,
+    base_amount: u64,
+    quote_amount: u64,
+) -> Result<(Pubkey, u128)> {
+    let amm_config = next_account_info(remaining_accounts)?;
+    let pool_authority = next_account_info(remaining_accounts)?;
+    let pool_state = next_account_info(remaining_accounts)?;
+    let lp_mint = next_account_info(remaining_accounts)?;
+    let authority_lp_token = next_account_info(remaining_accounts)?;
+    let token_0_vault = next_account_info(remaining_accounts)?;
+    let token_1_vault = next_account_info(remaining_accounts)?;
+    let create_pool_fee = next_account_info(remaining_accounts)?;
+    let observation_state = next_account_info(remaining_accounts)?;
+    let lp_token_program = next_account_info(remaining_accounts)?;
+    let market_lp_token_ata = next_account_info(remaining_accounts)?;
+
+    let base = (
+        accounts.base_token_mint.to_account_info(),
+        accounts.authority_base_token_account.to_account_info(),
+        accounts.base_token_program.to_account_info(),
+        base_amount,
+    );
+    let quote = (
+        accounts.quote_token_mint.to_account_info(),
+        accounts.authority_quote_token_account.to_account_info(),
+        accounts.quote_token_program.to_account_info(),
+        quote_amount,
+    );
+
+    // CP-Swap orders the pool mints by address
+    let (token_0, token_1) = if accounts.base_token_mint.key() < accounts.quote_token_mint.key() {
+        (base, quote)
+    } else {
+        (quote, base)
+    };
+
+    migration_manager::initialize_raydium_pool(
+        &accounts.migration_program,
+        &[
+            accounts.authority.to_account_info(),
+            amm_config.clone(),
+            pool_authority.clone(),
+            pool_state.clone(),
+            token_0.0,
+            token_1.0,
+            lp_mint.clone(),
+            token_0.1,
+            token_1.1,
+            authority_lp_token.clone(),
+            token_0_vault.clone(),
+            token_1_vault.clone(),
+            create_pool_fee.clone(),
+            observation_state.clone(),
+            lp_token_program.clone(),
+            token_0.2,
+            token_1.2,
+            accounts.associated_token_program.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.rent.to_account_info(),
+        ],
+        &RaydiumInitializeArgs {
+            init_amount_0: token_0.3,
+            init_amount_1: token_1.3,
+            open_time: 0,
+        },
+    )?;
+
+    associated_token::create(CpiContext::new(
+        accounts.associated_token_program.to_account_info(),
+        Create {
+            payer: accounts.authority.to_account_info(),
+            associated_token: market_lp_token_ata.clone(),
+            authority: accounts.market.to_account_info(),
+            mint: lp_mint.clone(),
+            system_program: accounts.system_program.to_account_info(),
+            token_program: lp_token_program.clone(),
+        },
+    ))?;
+
+    // The LP accounts only exist from the pool creation on, so they are read by hand
+    let lp_decimals = Mint::try_deserialize(&mut &lp_mint.try_borrow_data()?[..])?.decimals;
+    let lp_amount =
+        TokenAccount::try_deserialize(&mut &authority_lp_token.try_borrow_data()?[..])?.amount;
+
+    transfer_checked(
+        CpiContext::new(
+            lp_token_program.clone(),
+            TransferChecked {
+                from: authority_lp_token.clone(),
+                mint: lp_mint.clone(),
+                to: market_lp_token_ata.clone(),
+                authority: accounts.authority.to_account_info(),
+            },
+        ),
+        lp_amount,
+        lp_decimals,
+    )?;
+
+    Ok((pool_state.key(), u128::from(lp_amount)))
+}
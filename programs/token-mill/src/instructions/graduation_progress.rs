@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    state::{Market, TokenMillConfig},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Clone, PartialEq)]
+pub struct GraduationProgressSummary {
+    /// Share of the total supply in circulation, in bps.
+    pub supply_sold_bps: u16,
+    pub graduation_supply_bps: u16,
+    /// Curve proceeds of the market, in quote tokens.
+    pub quote_raised: u64,
+    pub graduation_quote_threshold: u64,
+    /// Whether the market meets the graduation thresholds of the config, the USD threshold being
+    /// checked against the price update passed to `migrate`.
+    pub thresholds_met: bool,
+}
+
+#[derive(Accounts)]
+pub struct GraduationProgressView<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+}
+
+/// View returning the progress of the market towards the graduation thresholds of its config.
+pub fn handler(ctx: Context<GraduationProgressView>) -> Result<GraduationProgressSummary> {
+    let config = &ctx.accounts.config;
+    let market = ctx.accounts.market.load()?;
+
+    Ok(GraduationProgressSummary {
+        supply_sold_bps: market.get_supply_sold_bps(),
+        graduation_supply_bps: config.graduation_supply_bps,
+        quote_raised: market.get_curve_proceeds()?,
+        graduation_quote_threshold: config.graduation_quote_threshold,
+        thresholds_met: market.meets_graduation_thresholds(
+            config.graduation_quote_threshold,
+            config.graduation_supply_bps,
+        )? && !market.is_migrated(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constant::MAX_BPS;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            GraduationProgressAction, SwapAction, TokenMillEnv, UpdateGraduationThresholdsAction,
+        },
+        SwapAmountType, SwapType,
+    };
+
+    fn get_graduation_progress(testing_env: &mut TokenMillEnv) -> GraduationProgressSummary {
+        let metadata = testing_env
+            .svm
+            .execute_actions(&[&GraduationProgressAction::new(testing_env)])
+            .unwrap();
+
+        GraduationProgressSummary::try_from_slice(&metadata.return_data.data).unwrap()
+    }
+
+    #[test]
+    fn graduation_progress() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateGraduationThresholdsAction::new(1, 1_000)])
+            .unwrap();
+
+        let progress = get_graduation_progress(&mut testing_env);
+
+        assert_eq!(progress.supply_sold_bps, 0);
+        assert_eq!(progress.quote_raised, 0);
+        assert_eq!(progress.graduation_quote_threshold, 1);
+        assert_eq!(progress.graduation_supply_bps, 1_000);
+        assert!(!progress.thresholds_met);
+
+        testing_env.svm.change_payer("bob");
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        // A tenth of the supply
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                market.total_supply / 10,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let progress = get_graduation_progress(&mut testing_env);
+
+        assert_eq!(u64::from(progress.supply_sold_bps), MAX_BPS / 10);
+        assert_eq!(progress.quote_raised, market.get_curve_proceeds().unwrap());
+        assert!(progress.thresholds_met);
+    }
+}
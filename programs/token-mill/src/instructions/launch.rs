@@ -0,0 +1,699 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{program::invoke, system_instruction::transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_2022::{spl_token_2022::instruction::AuthorityType, Token2022},
+    token_interface::{
+        self, token_metadata_initialize, Mint, TokenAccount, TokenInterface,
+        TokenMetadataInitialize,
+    },
+};
+
+use crate::{
+    clock::get_clock,
+    constant::{MAX_BPS, MILL_TOKEN_DECIMALS},
+    errors::TokenMillError,
+    events::{
+        TokenMillMarketCreationEvent, TokenMillMarketPriceSetEvent,
+        TokenMillStakePositionCreationEvent, TokenMillStakingCreationEvent, TokenMillSwapEvent,
+        TokenMillVestingPlanCreationEvent,
+    },
+    manager::{
+        curve_manager::{self, CurveParameters},
+        policy_manager::{
+            self, ValidateCreateMarketArgs, ValidateSwapArgs, VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+        },
+        settlement_manager::SwapAccounts,
+        staking_manager,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::{
+            check_mint_extensions, get_transfer_fee, get_transfer_inverse_fee, transfer_from_eoa,
+            transfer_from_pda,
+        },
+    },
+    state::{
+        CreatorActivity, Denylist, Market, MarketStaking, MintRegistry, StakePosition,
+        TokenMillConfig, VestingPlan, CREATOR_ACTIVITY_PDA_SEED,
+    },
+    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, MARKET_STAKING_PDA_SEED,
+    MINT_REGISTRY_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED, STAKING_POSITION_PDA_SEED,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LaunchParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub total_supply: u64,
+    pub creator_fee_share: u16,
+    pub staking_fee_share: u16,
+    pub curve: CurveParameters,
+    /// Quote amount spent by the creator on the dev buy.
+    pub dev_buy_amount: u64,
+    pub min_dev_buy_base_amount: u64,
+    pub vesting_start: i64,
+    pub vesting_duration: i64,
+    pub cliff_duration: i64,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Launch<'info> {
+    pub config: Box<Account<'info, TokenMillConfig>>,
+
+    #[account(
+        init,
+        seeds = [MARKET_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + Market::INIT_SPACE
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        mint::token_program = token_program,
+        mint::authority = market,
+        mint::decimals = MILL_TOKEN_DECIMALS,
+        extensions::metadata_pointer::authority = market,
+        extensions::metadata_pointer::metadata_address = base_token_mint,
+    )]
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        seeds = [MINT_REGISTRY_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + MintRegistry::INIT_SPACE
+    )]
+    pub mint_registry: Box<Account<'info, MintRegistry>>,
+
+    #[account(
+        seeds = [
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            quote_token_mint.key().as_ref(),
+        ],
+        bump = quote_token_badge.bump,
+        constraint = quote_token_badge.status == QuoteTokenBadgeStatus::Enabled || creator.key() == config.authority @ TokenMillError::InvalidQuoteAssetBadge,
+    )]
+    pub quote_token_badge: Option<Box<Account<'info, QuoteTokenBadge>>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + MarketStaking::INIT_SPACE,
+        seeds = [MARKET_STAKING_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub staking: Box<Account<'info, MarketStaking>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [STAKING_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Box<Account<'info, StakePosition>>,
+
+    #[account(init, payer = creator, space = 8 + VestingPlan::INIT_SPACE)]
+    pub vesting_plan: Box<Account<'info, VestingPlan>>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required once the config throttles market creations, see `CreatorActivity`
+    #[account(
+        mut,
+        seeds = [
+            CREATOR_ACTIVITY_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            creator.key().as_ref()
+        ],
+        bump = creator_activity.bump
+    )]
+    pub creator_activity: Option<Box<Account<'info, CreatorActivity>>>,
+
+    /// Required once the config enables its denylist
+    pub denylist: Option<Box<Account<'info, Denylist>>>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the dev buy once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+}
+
+/// One-click launch: creates the market with its metadata and preset prices as in
+/// `create_market` and `set_prices_from_preset`, then buys `dev_buy_amount` of quote tokens worth
+/// of base tokens for the creator, vested in their stake position instead of being transferred.
+/// The remaining accounts are forwarded to the policy program, for both the creation and the buy.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Launch<'info>>,
+    params: LaunchParams,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+
+    require_eq!(
+        params.creator_fee_share + params.staking_fee_share + config.default_protocol_fee_share,
+        MAX_BPS as u16,
+        TokenMillError::InvalidFeeShare
+    );
+    require_gt!(params.dev_buy_amount, 0, TokenMillError::InvalidAmount);
+
+    require!(
+        check_mint_extensions(&ctx.accounts.quote_token_mint)?,
+        TokenMillError::UnsupportedTokenMint
+    );
+
+    VestingPlan::check_schedule(
+        params.vesting_start,
+        params.vesting_duration,
+        params.cliff_duration,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    CreatorActivity::check(
+        config,
+        ctx.accounts.creator_activity.as_deref_mut(),
+        get_clock()?.unix_timestamp,
+    )?;
+
+    // The market was just created, without an oracle nor stats yet
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_deref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: None,
+        market_stats: None,
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let creator = ctx.accounts.creator.key();
+
+    swap_accounts.check_trader(&creator)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    let (bid_prices, ask_prices) = curve_manager::get_prices_from_parameters(&params.curve)?;
+
+    // The curve only accounts for the quote tokens moving in the market vault
+    let quote_token_mint = &ctx.accounts.quote_token_mint;
+    let curve_amount =
+        params.dev_buy_amount - get_transfer_fee(quote_token_mint, params.dev_buy_amount)?;
+
+    let (outcome, creation_bond) = {
+        let mut market = ctx.accounts.market.load_init()?;
+
+        market.initialize(
+            ctx.bumps.market,
+            config.key(),
+            ctx.accounts.creator.key(),
+            ctx.accounts.base_token_mint.key(),
+            quote_token_mint.key(),
+            quote_token_mint.decimals,
+            params.total_supply,
+            params.creator_fee_share,
+            params.staking_fee_share,
+            config.referral_fee_share,
+        )?;
+
+        market.swap_fee_bps = config.min_swap_fee_bps;
+
+        market.check_and_set_prices(bid_prices, ask_prices, &config.price_bounds)?;
+        market.launch_slot = get_clock()?.slot;
+
+        let max_base_amount = swap_accounts.before_swap(&mut market, SwapType::Buy)?;
+
+        let outcome = swap_manager::swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            curve_amount,
+            None,
+            get_clock()?.slot,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        swap_accounts.after_swap(&mut market, &outcome, max_base_amount)?;
+
+        require_gte!(
+            outcome.base_amount,
+            params.min_dev_buy_base_amount,
+            TokenMillError::AmountThresholdNotMet
+        );
+
+        let market_key = ctx.accounts.market.key();
+        let creator_key = ctx.accounts.creator.key();
+        let staking = &mut ctx.accounts.staking;
+        let stake_position = &mut ctx.accounts.stake_position;
+
+        staking.initialize(market_key)?;
+        stake_position.initialize(market_key, creator_key)?;
+
+        ctx.accounts.vesting_plan.initialize(
+            stake_position.key(),
+            params.vesting_start,
+            outcome.base_amount,
+            params.vesting_duration,
+            params.cliff_duration,
+        )?;
+
+        // The bought tokens never leave the market vault, which holds the staked tokens
+        staking_manager::deposit_vested(
+            &mut market,
+            staking,
+            stake_position,
+            outcome.base_amount,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        let creation_bond =
+            market.post_creation_bond(&config.creation_bond, get_clock()?.unix_timestamp);
+
+        (outcome, creation_bond)
+    };
+
+    if creation_bond > 0 {
+        invoke(
+            &transfer(
+                ctx.accounts.creator.key,
+                &ctx.accounts.market.key(),
+                creation_bond,
+            ),
+            &[
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.market.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+    }
+
+    ctx.accounts.mint_registry.initialize(
+        ctx.bumps.mint_registry,
+        ctx.accounts.base_token_mint.key(),
+        ctx.accounts.market.key(),
+    )?;
+
+    let base_token_mint_key = ctx.accounts.base_token_mint.key();
+    let market_seeds = [
+        MARKET_PDA_SEED.as_bytes(),
+        base_token_mint_key.as_ref(),
+        &[ctx.bumps.market],
+    ];
+
+    ctx.accounts
+        .initialize_token_metadata(&params, &market_seeds)?;
+
+    ctx.accounts
+        .mint_supply_and_remove_authority(params.total_supply, &market_seeds)?;
+
+    ctx.accounts
+        .transfer_dev_buy(&outcome, params.dev_buy_amount, &market_seeds)?;
+
+    let config = &ctx.accounts.config;
+
+    policy_manager::validate(
+        config.policy_program,
+        ctx.accounts.policy_program.as_ref(),
+        ctx.remaining_accounts,
+        VALIDATE_CREATE_MARKET_DISCRIMINATOR,
+        &ValidateCreateMarketArgs {
+            config: config.key(),
+            market: ctx.accounts.market.key(),
+            creator: ctx.accounts.creator.key(),
+            base_token_mint: base_token_mint_key,
+            quote_token_mint: ctx.accounts.quote_token_mint.key(),
+            total_supply: params.total_supply,
+        },
+    )?;
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: config.key(),
+        market: ctx.accounts.market.key(),
+        user: creator,
+        swap_type: SwapType::Buy,
+        swap_amount_type: SwapAmountType::ExactInput,
+        base_amount: outcome.base_amount,
+        quote_amount: outcome.quote_amount,
+    })?;
+
+    swap_accounts.settle(
+        revenue_report.as_mut(),
+        creator,
+        SwapType::Buy,
+        &outcome,
+        0,
+        &market_seeds,
+    )?;
+
+    emit_cpi!(TokenMillMarketCreationEvent {
+        config: config.key(),
+        market: ctx.accounts.market.key(),
+        creator: ctx.accounts.creator.key(),
+        base_token_mint: base_token_mint_key,
+        quote_token_mint: ctx.accounts.quote_token_mint.key(),
+        total_supply: params.total_supply,
+        protocol_fee_share: config.default_protocol_fee_share,
+        referral_fee_share: config.referral_fee_share,
+        creator_fee_share: params.creator_fee_share,
+        staking_fee_share: params.staking_fee_share,
+        wallet_buy_limit: None,
+        swap_fee_bps: config.min_swap_fee_bps,
+    });
+
+    emit_cpi!(TokenMillMarketPriceSetEvent {
+        market: ctx.accounts.market.key(),
+        bid_prices,
+        ask_prices,
+    });
+
+    emit_cpi!(TokenMillStakingCreationEvent {
+        market: ctx.accounts.market.key(),
+        staking: ctx.accounts.staking.key(),
+    });
+
+    emit_cpi!(TokenMillStakePositionCreationEvent {
+        market: ctx.accounts.market.key(),
+        stake_position: ctx.accounts.stake_position.key(),
+        user: ctx.accounts.creator.key(),
+    });
+
+    emit_cpi!(TokenMillSwapEvent {
+        user: ctx.accounts.creator.key(),
+        market: ctx.accounts.market.key(),
+        swap_type: SwapType::Buy,
+        base_amount: outcome.base_amount,
+        quote_amount: outcome.quote_amount,
+        referral_token_account: None,
+        creator_fee: outcome.creator_fee,
+        staking_fee: outcome.staking_fee,
+        protocol_fee: outcome.protocol_fee,
+        referral_fee: outcome.referral_fee,
+        quote_refund: outcome.quote_refund,
+        trader_rebate_fee: 0,
+        launch_fee: outcome.launch_fee,
+    });
+
+    emit_cpi!(TokenMillVestingPlanCreationEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.creator.key(),
+        vesting_plan: ctx.accounts.vesting_plan.key(),
+        vesting_amount: outcome.base_amount,
+        start: params.vesting_start,
+        vesting_duration: params.vesting_duration,
+        cliff_duration: params.cliff_duration,
+    });
+
+    Ok(())
+}
+
+impl<'info> Launch<'info> {
+    fn initialize_token_metadata(
+        &self,
+        params: &LaunchParams,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let cpi_accounts = TokenMetadataInitialize {
+            token_program_id: self.token_program.to_account_info(),
+            mint: self.base_token_mint.to_account_info(),
+            metadata: self.base_token_mint.to_account_info(),
+            mint_authority: self.market.to_account_info(),
+            update_authority: self.creator.to_account_info(),
+        };
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            params.name.clone(),
+            params.symbol.clone(),
+            params.uri.clone(),
+        )?;
+
+        let mint_account_info = self.base_token_mint.to_account_info();
+
+        let extra_lamports = Rent::get()?
+            .minimum_balance(mint_account_info.data_len())
+            .saturating_sub(mint_account_info.get_lamports());
+
+        if extra_lamports > 0 {
+            invoke(
+                &transfer(self.creator.key, mint_account_info.key, extra_lamports),
+                &[
+                    self.creator.to_account_info(),
+                    mint_account_info,
+                    self.system_program.to_account_info(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn mint_supply_and_remove_authority(
+        &self,
+        total_supply: u64,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let cpi_accounts = token_interface::MintTo {
+            mint: self.base_token_mint.to_account_info(),
+            to: self.market_base_token_ata.to_account_info(),
+            authority: self.market.to_account_info(),
+        };
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            total_supply,
+        )?;
+
+        let cpi_accounts = token_interface::SetAuthority {
+            account_or_mint: self.base_token_mint.to_account_info(),
+            current_authority: self.market.to_account_info(),
+        };
+
+        token_interface::set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                &[market_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// Moves the quote tokens of the dev buy as in `swap`, the base tokens stay in the market.
+    /// The fees are settled by `SwapAccounts::settle`.
+    fn transfer_dev_buy(
+        &self,
+        outcome: &SwapOutcome,
+        dev_buy_amount: u64,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let quote_amount_in = (outcome.quote_amount
+            + get_transfer_inverse_fee(&self.quote_token_mint, outcome.quote_amount)?)
+        .min(dev_buy_amount);
+
+        transfer_from_eoa(
+            &self.quote_token_mint,
+            &self.creator,
+            &self.creator_quote_token_ata,
+            &self.market_quote_token_ata,
+            &self.quote_token_program,
+            quote_amount_in,
+        )?;
+
+        if outcome.quote_refund > 0 {
+            transfer_from_pda(
+                &self.quote_token_mint,
+                self.market.to_account_info(),
+                &self.market_quote_token_ata,
+                &self.creator_quote_token_ata,
+                &self.quote_token_program,
+                outcome.quote_refund,
+                market_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MarketStaking, StakePosition, VestingPlan};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, LaunchAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    const DEV_BUY_AMOUNT: u64 = 1_000_000_000;
+    const STARTING_TIMESTAMP: i64 = 333;
+
+    fn setup_env() -> (TokenMillEnv, LaunchAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.warp(STARTING_TIMESTAMP);
+        testing_env.svm.change_payer("alice");
+
+        let action = LaunchAction::new(&testing_env, DEV_BUY_AMOUNT, STARTING_TIMESTAMP, 300, 60);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn launch() {
+        let (mut testing_env, action) = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("alice"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.staking);
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        let vesting_plan = testing_env
+            .svm
+            .get_parsed_account::<VestingPlan>(&action.vesting_plan);
+
+        assert!(market.are_prices_set());
+        assert!(vesting_plan.amount_vested > 0);
+        assert_eq!(
+            market.base_reserve,
+            market.total_supply - vesting_plan.amount_vested
+        );
+        assert_eq!(vesting_plan.stake_position, action.stake_position);
+        assert_eq!(staking.total_amount_vested, vesting_plan.amount_vested);
+        assert_eq!(
+            stake_position.total_amount_vested,
+            vesting_plan.amount_vested
+        );
+
+        let quote_balance_after = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("alice"));
+
+        // Buys exceeding the supply are refunded
+        assert!(quote_balance_after < quote_balance_before);
+        assert!(quote_balance_before - quote_balance_after <= DEV_BUY_AMOUNT);
+    }
+
+    #[test]
+    fn launch_below_min_dev_buy_base_amount() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.min_dev_buy_base_amount = u64::MAX;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::AmountThresholdNotMet);
+    }
+
+    #[test]
+    fn launch_with_invalid_vesting_schedule() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.cliff_duration = action.vesting_duration;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidVestingDuration);
+    }
+
+    #[test]
+    fn launch_without_dev_buy() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.dev_buy_amount = 0;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
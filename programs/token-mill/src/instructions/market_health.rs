@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    state::{Market, PriceFeed},
+};
+
+/// The quote vault doesn't cover the curve proceeds.
+pub const MARKET_HEALTH_INSOLVENT: u32 = 1 << 0;
+/// The quote vault covers the curve proceeds but not the pending creator fees on top.
+pub const MARKET_HEALTH_FEES_UNCOVERED: u32 = 1 << 1;
+pub const MARKET_HEALTH_PAUSED: u32 = 1 << 2;
+/// The reentrancy flag outlived a transaction, which should never happen.
+pub const MARKET_HEALTH_LOCKED: u32 = 1 << 3;
+pub const MARKET_HEALTH_PRICES_NOT_SET: u32 = 1 << 4;
+/// The whole base reserve has been bought, the market is ready to be migrated.
+pub const MARKET_HEALTH_CURVE_COMPLETED: u32 = 1 << 5;
+pub const MARKET_HEALTH_ORACLE_STALE: u32 = 1 << 6;
+
+#[derive(Accounts)]
+pub struct MarketHealthView<'info> {
+    #[account(has_one = quote_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// Checked for staleness when provided
+    #[account(has_one = market @ TokenMillError::InvalidMarket)]
+    pub price_feed: Option<Account<'info, PriceFeed>>,
+}
+
+/// View returning the `MARKET_HEALTH_*` flags raised by the market, 0 meaning healthy.
+/// The price feed is stale when it wasn't pushed for more than `max_oracle_staleness` slots.
+pub fn handler(ctx: Context<MarketHealthView>, max_oracle_staleness: u64) -> Result<u32> {
+    let market = ctx.accounts.market.load()?;
+    let current_slot = get_clock()?.slot;
+
+    let quote_vault_amount = ctx.accounts.market_quote_token_ata.amount;
+    let curve_proceeds = market.get_curve_proceeds()?;
+
+    let mut health = 0;
+
+    if quote_vault_amount < curve_proceeds {
+        health |= MARKET_HEALTH_INSOLVENT;
+    } else if quote_vault_amount - curve_proceeds < market.fees.pending_creator_fees {
+        health |= MARKET_HEALTH_FEES_UNCOVERED;
+    }
+
+    if market.is_paused(current_slot) {
+        health |= MARKET_HEALTH_PAUSED;
+    }
+
+    if market.locked != 0 {
+        health |= MARKET_HEALTH_LOCKED;
+    }
+
+    if !market.are_prices_set() {
+        health |= MARKET_HEALTH_PRICES_NOT_SET;
+    } else if market.base_reserve == 0 {
+        health |= MARKET_HEALTH_CURVE_COMPLETED;
+    }
+
+    if let Some(price_feed) = &ctx.accounts.price_feed {
+        if current_slot.saturating_sub(price_feed.publish_slot) > max_oracle_staleness {
+            health |= MARKET_HEALTH_ORACLE_STALE;
+        }
+    }
+
+    Ok(health)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreatePriceFeedAction, MarketHealthAction, PauseUntilAction, PushPriceAction, TokenMillEnv,
+        UpdateMaxPauseDurationAction,
+    };
+
+    const MAX_ORACLE_STALENESS: u64 = 10;
+
+    fn get_market_health(testing_env: &mut TokenMillEnv, action: &MarketHealthAction) -> u32 {
+        let metadata = testing_env.svm.execute_actions(&[action]).unwrap();
+
+        u32::from_le_bytes(metadata.return_data.data[..4].try_into().unwrap())
+    }
+
+    #[test]
+    fn market_health() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = MarketHealthAction::new(&testing_env, MAX_ORACLE_STALENESS);
+
+        assert_eq!(get_market_health(&mut testing_env, &action), 0);
+    }
+
+    #[test]
+    fn market_health_while_paused() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let slot = testing_env.svm.get_slot() + 100;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxPauseDurationAction::new(100),
+                &PauseUntilAction::new(&testing_env, slot),
+            ])
+            .unwrap();
+
+        let action = MarketHealthAction::new(&testing_env, MAX_ORACLE_STALENESS);
+
+        assert_eq!(
+            get_market_health(&mut testing_env, &action),
+            MARKET_HEALTH_PAUSED
+        );
+
+        testing_env.svm.warp_to_slot(slot);
+
+        assert_eq!(get_market_health(&mut testing_env, &action), 0);
+    }
+
+    #[test]
+    fn market_health_with_stale_oracle() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreatePriceFeedAction::new(&testing_env),
+                &PushPriceAction::new(&testing_env),
+            ])
+            .unwrap();
+
+        let action = MarketHealthAction::new(&testing_env, MAX_ORACLE_STALENESS).with_price_feed();
+
+        assert_eq!(get_market_health(&mut testing_env, &action), 0);
+
+        testing_env
+            .svm
+            .warp_to_slot(testing_env.svm.get_slot() + MAX_ORACLE_STALENESS + 1);
+
+        assert_eq!(
+            get_market_health(&mut testing_env, &action),
+            MARKET_HEALTH_ORACLE_STALE
+        );
+    }
+}
@@ -1,17 +1,67 @@
 pub mod admin;
+pub mod airdrop;
+pub mod backfill_config;
+pub mod backfill_market;
+pub mod buy_queue;
+pub mod buyback;
+pub mod create_creator_activity;
+pub mod create_launch_purchase;
 pub mod create_market;
 pub mod create_market_with_spl;
+pub mod create_markets_batch;
+pub mod create_secondary_market;
 pub mod creator;
+pub mod gas_vault;
+pub mod graduation;
+pub mod graduation_progress;
+pub mod launch;
+pub mod market_health;
+pub mod multisig;
+pub mod oracle;
+pub mod prepare_trade_accounts;
+pub mod protocol_fees;
+pub mod quote_swap;
+pub mod reconcile_fees;
 pub mod referrals;
+pub mod revenue;
+pub mod sell_orders;
 pub mod staking;
 pub mod swap;
+pub mod sweep_fees;
+pub mod sweep_fees_idempotent;
 pub mod vesting;
+pub mod wallet_position;
 
 pub use admin::*;
+pub use airdrop::*;
+pub use backfill_config::*;
+pub use backfill_market::*;
+pub use buy_queue::*;
+pub use buyback::*;
+pub use create_creator_activity::*;
+pub use create_launch_purchase::*;
 pub use create_market::*;
 pub use create_market_with_spl::*;
+pub use create_markets_batch::*;
+pub use create_secondary_market::*;
 pub use creator::*;
+pub use gas_vault::*;
+pub use graduation::*;
+pub use graduation_progress::*;
+pub use launch::*;
+pub use market_health::*;
+pub use multisig::*;
+pub use oracle::*;
+pub use prepare_trade_accounts::*;
+pub use protocol_fees::*;
+pub use quote_swap::*;
+pub use reconcile_fees::*;
 pub use referrals::*;
+pub use revenue::*;
+pub use sell_orders::*;
 pub use staking::*;
 pub use swap::*;
+pub use sweep_fees::*;
+pub use sweep_fees_idempotent::*;
 pub use vesting::*;
+pub use wallet_position::*;
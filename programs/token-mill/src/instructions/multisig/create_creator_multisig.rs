@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    events::TokenMillCreatorMultisigCreationEvent,
+    state::{CreatorMultisig, CREATOR_MULTISIG_PDA_SEED, CREATOR_VAULT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCreatorMultisig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CreatorMultisig::INIT_SPACE,
+        seeds = [CREATOR_MULTISIG_PDA_SEED.as_bytes(), create_key.key().as_ref()],
+        bump
+    )]
+    pub creator_multisig: Account<'info, CreatorMultisig>,
+
+    /// CHECK: Vault of the multisig, only derived here
+    #[account(
+        seeds = [CREATOR_VAULT_PDA_SEED.as_bytes(), creator_multisig.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Any key, seed of the multisig
+    pub create_key: Signer<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates a `threshold`-of-`members` multisig. Markets created through its vault have the vault
+/// as their creator, so that every creator instruction requires the approval of the members.
+pub fn handler(
+    ctx: Context<CreateCreatorMultisig>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let creator_multisig = &mut ctx.accounts.creator_multisig;
+
+    creator_multisig.initialize(
+        ctx.bumps.creator_multisig,
+        ctx.bumps.vault,
+        ctx.accounts.create_key.key(),
+        members.clone(),
+        threshold,
+    )?;
+
+    emit_cpi!(TokenMillCreatorMultisigCreationEvent {
+        creator_multisig: creator_multisig.key(),
+        vault: ctx.accounts.vault.key(),
+        members,
+        threshold,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CreatorMultisig;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateCreatorMultisigAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn create_creator_multisig() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("alice");
+
+        let members = vec![make_address("alice"), make_address("bob")];
+        let action = CreateCreatorMultisigAction::new(members.clone(), 2);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let creator_multisig = testing_env
+            .svm
+            .get_parsed_account::<CreatorMultisig>(&action.creator_multisig);
+
+        assert_eq!(creator_multisig.members, members);
+        assert_eq!(creator_multisig.threshold, 2);
+        assert_eq!(creator_multisig.create_key, action.create_key);
+    }
+
+    #[test]
+    fn create_creator_multisig_with_invalid_threshold() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("alice");
+
+        for (members, threshold) in [
+            (vec![make_address("alice"), make_address("bob")], 0),
+            (vec![make_address("alice"), make_address("bob")], 3),
+            (vec![make_address("alice"), make_address("alice")], 2),
+        ] {
+            let result = testing_env
+                .svm
+                .execute_actions(&[&CreateCreatorMultisigAction::new(members, threshold)]);
+
+            assert!(result.is_err());
+
+            let error = tm_parse_error(result).unwrap();
+
+            assert_eq!(error, TokenMillError::InvalidMultisig);
+        }
+    }
+}
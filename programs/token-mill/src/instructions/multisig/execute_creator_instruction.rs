@@ -0,0 +1,217 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+use crate::{
+    events::TokenMillCreatorInstructionExecutionEvent,
+    state::{CreatorMultisig, CREATOR_VAULT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatorInstructionExecution<'info> {
+    pub creator_multisig: Account<'info, CreatorMultisig>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_VAULT_PDA_SEED.as_bytes(), creator_multisig.key().as_ref()],
+        bump = creator_multisig.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// CHECK: Program of the instruction, e.g. Token Mill itself for creator instructions
+    pub program: UncheckedAccount<'info>,
+}
+
+/// Invokes an instruction signed by the vault of the multisig, once enough members signed.
+/// The first `account_count` remaining accounts are the accounts of the instruction, the members
+/// can sign as any remaining account.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreatorInstructionExecution<'info>>,
+    account_count: u8,
+    data: Vec<u8>,
+) -> Result<()> {
+    let creator_multisig = &ctx.accounts.creator_multisig;
+
+    creator_multisig.check_signers(ctx.remaining_accounts)?;
+
+    let vault = &ctx.accounts.vault;
+    let instruction_accounts = ctx
+        .remaining_accounts
+        .get(..usize::from(account_count))
+        .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+
+    let accounts = instruction_accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer || account.key() == vault.key(),
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let mut account_infos = instruction_accounts.to_vec();
+    account_infos.push(vault.to_account_info());
+    account_infos.push(ctx.accounts.program.to_account_info());
+
+    let creator_multisig_key = creator_multisig.key();
+
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.accounts.program.key(),
+            accounts,
+            data: data.clone(),
+        },
+        &account_infos,
+        &[&[
+            CREATOR_VAULT_PDA_SEED.as_bytes(),
+            creator_multisig_key.as_ref(),
+            &[creator_multisig.vault_bump],
+        ]],
+    )?;
+
+    emit_cpi!(TokenMillCreatorInstructionExecutionEvent {
+        creator_multisig: creator_multisig_key,
+        vault: vault.key(),
+        program: ctx.accounts.program.key(),
+        data,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::{
+        joelana_env::{
+            actions::token_mill::{
+                tm_parse_error, CreateCreatorMultisigAction, CreateMarketAction,
+                ExecuteCreatorInstructionAction, SetMarketPricesAction, TokenMillEnv,
+            },
+            make_address, TokenMillError,
+        },
+        utils::token_mill::curve_generator::Curve,
+    };
+    use solana_sdk::pubkey::Pubkey;
+
+    // 2-of-3 multisig of alice, bob and carol, creator of the market through its vault
+    fn setup_env() -> (TokenMillEnv, CreateCreatorMultisigAction) {
+        let mut testing_env = TokenMillEnv::new().with_default_quote_token_mint();
+
+        testing_env.svm.change_payer("alice");
+
+        let multisig_action = CreateCreatorMultisigAction::new(
+            vec![
+                make_address("alice"),
+                make_address("bob"),
+                make_address("carol"),
+            ],
+            2,
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&multisig_action])
+            .unwrap();
+
+        testing_env.svm.airdrop(&multisig_action.vault);
+
+        let mut create_market_action = CreateMarketAction::new(&testing_env);
+        create_market_action.signer = multisig_action.vault;
+
+        testing_env
+            .svm
+            .execute_actions(&[&ExecuteCreatorInstructionAction::new(
+                multisig_action.creator_multisig,
+                multisig_action.vault,
+                create_market_action.no_badge(),
+                vec![make_address("alice"), make_address("carol")],
+            )])
+            .unwrap();
+
+        testing_env.market = create_market_action.market;
+
+        (testing_env, multisig_action)
+    }
+
+    fn set_prices_action(
+        multisig_action: &CreateCreatorMultisigAction,
+        signers: Vec<Pubkey>,
+    ) -> ExecuteCreatorInstructionAction {
+        let mut set_prices_action = SetMarketPricesAction::new(Curve::default());
+        set_prices_action.signer = multisig_action.vault;
+
+        ExecuteCreatorInstructionAction::new(
+            multisig_action.creator_multisig,
+            multisig_action.vault,
+            &set_prices_action,
+            signers,
+        )
+    }
+
+    #[test]
+    fn execute_creator_instruction() {
+        let (mut testing_env, multisig_action) = setup_env();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.creator, multisig_action.vault);
+        assert!(!market.are_prices_set());
+
+        testing_env
+            .svm
+            .execute_actions(&[&set_prices_action(
+                &multisig_action,
+                vec![make_address("alice"), make_address("bob")],
+            )])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert!(market.are_prices_set());
+    }
+
+    #[test]
+    fn execute_creator_instruction_below_threshold() {
+        let (mut testing_env, multisig_action) = setup_env();
+
+        // Signers count once, and only if they are members
+        for signers in [
+            vec![make_address("alice")],
+            vec![make_address("alice"), make_address("alice")],
+            vec![make_address("alice"), make_address("mallory")],
+        ] {
+            let result = testing_env
+                .svm
+                .execute_actions(&[&set_prices_action(&multisig_action, signers)]);
+
+            assert!(result.is_err());
+
+            let error = tm_parse_error(result).unwrap();
+
+            assert_eq!(error, TokenMillError::InsufficientMultisigSigners);
+        }
+    }
+
+    #[test]
+    fn creator_instruction_without_multisig() {
+        let (mut testing_env, _) = setup_env();
+
+        // A member alone can't act as the creator
+        let result = testing_env
+            .svm
+            .execute_actions(&[&SetMarketPricesAction::new(Curve::default())]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod create_creator_multisig;
+pub mod execute_creator_instruction;
+
+pub use create_creator_multisig::*;
+pub use execute_creator_instruction::*;
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    events::TokenMillMarketOracleCreationEvent,
+    state::{Market, MarketOracle, MARKET_ORACLE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMarketOracle<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MarketOracle::INIT_SPACE,
+        seeds = [MARKET_ORACLE_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub market_oracle: AccountLoader<'info, MarketOracle>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, the oracle starts recording the swaps that provide it from now on.
+pub fn handler(ctx: Context<CreateMarketOracle>) -> Result<()> {
+    let mut market_oracle = ctx.accounts.market_oracle.load_init()?;
+
+    market_oracle.initialize(
+        ctx.bumps.market_oracle,
+        ctx.accounts.market.key(),
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillMarketOracleCreationEvent {
+        market: ctx.accounts.market.key(),
+        market_oracle: ctx.accounts.market_oracle.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MarketOracle;
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreateMarketOracleAction, TokenMillEnv,
+    };
+
+    #[test]
+    fn create_market_oracle() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = CreateMarketOracleAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market_oracle = testing_env
+            .svm
+            .get_parsed_account::<MarketOracle>(&action.market_oracle);
+
+        assert_eq!(market_oracle.market, testing_env.market);
+        assert_eq!(market_oracle.length, 1);
+        assert_eq!(
+            market_oracle.observations[0].timestamp,
+            testing_env.svm.get_unix_timestamp()
+        );
+    }
+}
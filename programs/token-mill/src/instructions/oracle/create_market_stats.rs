@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    events::TokenMillMarketStatsCreationEvent,
+    state::{Market, MarketStats, MARKET_STATS_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMarketStats<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MarketStats::INIT_SPACE,
+        seeds = [MARKET_STATS_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub market_stats: AccountLoader<'info, MarketStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, the first rollover happens in the next volume window.
+pub fn handler(ctx: Context<CreateMarketStats>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    let mut market_stats = ctx.accounts.market_stats.load_init()?;
+
+    market_stats.initialize(
+        ctx.bumps.market_stats,
+        ctx.accounts.market.key(),
+        market.get_mid_price()?,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillMarketStatsCreationEvent {
+        market: ctx.accounts.market.key(),
+        market_stats: ctx.accounts.market_stats.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MarketStats};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{CreateMarketStatsAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType,
+    };
+
+    #[test]
+    fn create_market_stats() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = CreateMarketStatsAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let market_stats = testing_env
+            .svm
+            .get_parsed_account::<MarketStats>(&action.market_stats);
+
+        assert_eq!(market_stats.market, testing_env.market);
+        assert_eq!(market_stats.price, market.get_mid_price().unwrap());
+        assert_eq!(
+            market_stats.window,
+            MarketStats::get_window(testing_env.svm.get_unix_timestamp())
+        );
+    }
+
+    #[test]
+    fn swap_with_market_stats() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        )
+        .with_market_stats();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateMarketStatsAction::new(&testing_env), &swap_action])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let market_stats = testing_env
+            .svm
+            .get_parsed_account::<MarketStats>(&swap_action.market_stats);
+
+        let (base_volume, quote_volume, trade_count) =
+            market_stats.get_rolling_volume(testing_env.svm.get_unix_timestamp());
+
+        assert_eq!(market_stats.total_base_volume(), 100_000_000_000);
+        assert_eq!(
+            market_stats.total_quote_volume(),
+            u128::from(market.volume.quote_volume)
+        );
+        assert_eq!(market_stats.trade_count, 1);
+        assert_eq!(
+            market_stats.last_trade_price,
+            market.get_mid_price().unwrap()
+        );
+        assert_eq!(base_volume, 100_000_000_000);
+        assert_eq!(quote_volume, market.volume.quote_volume);
+        assert_eq!(trade_count, 1);
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillPriceFeedCreationEvent,
+    state::{Market, PriceFeed, PRICE_FEED_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatePriceFeed<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + PriceFeed::INIT_SPACE,
+        seeds = [PRICE_FEED_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceFeed>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opts the market into publishing its price, see `push_price`.
+pub fn handler(ctx: Context<CreatePriceFeed>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    let price_feed = &mut ctx.accounts.price_feed;
+
+    price_feed.initialize(
+        ctx.bumps.price_feed,
+        ctx.accounts.market.key(),
+        market.base_token_mint,
+        market.quote_token_mint,
+    )?;
+
+    price_feed.update(
+        market.get_bid_price()?,
+        market.get_ask_price()?,
+        &get_clock()?,
+    )?;
+
+    emit_cpi!(TokenMillPriceFeedCreationEvent {
+        market: ctx.accounts.market.key(),
+        price_feed: price_feed.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, PriceFeed};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreatePriceFeedAction, TokenMillEnv},
+        TokenMillError,
+    };
+
+    #[test]
+    fn create_price_feed() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = CreatePriceFeedAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let price_feed = testing_env
+            .svm
+            .get_parsed_account::<PriceFeed>(&action.price_feed);
+
+        assert_eq!(price_feed.market, testing_env.market);
+        assert_eq!(price_feed.base_token_mint, market.base_token_mint);
+        assert_eq!(price_feed.quote_token_mint, market.quote_token_mint);
+        assert_eq!(price_feed.expo, PriceFeed::EXPO);
+        assert_eq!(
+            price_feed.price,
+            (market.bid_prices[0] + (market.ask_prices[0] - market.bid_prices[0]) / 2) as i64
+        );
+    }
+
+    #[test]
+    fn create_price_feed_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = CreatePriceFeedAction::new(&testing_env);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
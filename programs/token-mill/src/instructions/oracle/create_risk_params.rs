@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillRiskParamsCreationEvent,
+    state::{Market, RiskParams, TokenMillConfig, RISK_PARAMS_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateRiskParams<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RiskParams::INIT_SPACE,
+        seeds = [RISK_PARAMS_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub risk_params: Account<'info, RiskParams>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Publishes the risk data of the market, see `push_risk_params`. Anyone can create the account.
+pub fn handler(ctx: Context<CreateRiskParams>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    let risk_params = &mut ctx.accounts.risk_params;
+
+    risk_params.initialize(ctx.bumps.risk_params, ctx.accounts.market.key())?;
+    risk_params.update(
+        &market,
+        ctx.accounts.config.max_swap_size_bps,
+        &get_clock()?,
+    )?;
+
+    emit_cpi!(TokenMillRiskParamsCreationEvent {
+        market: ctx.accounts.market.key(),
+        risk_params: risk_params.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::MAX_BPS, RiskParams};
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreateRiskParamsAction, TokenMillEnv,
+    };
+
+    #[test]
+    fn create_risk_params() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = CreateRiskParamsAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let risk_params = testing_env
+            .svm
+            .get_parsed_account::<RiskParams>(&action.risk_params);
+
+        assert_eq!(risk_params.market, testing_env.market);
+        assert_eq!(risk_params.max_swap_size_bps, 0);
+        assert!(risk_params.price_conf_bps < MAX_BPS as u16);
+        assert!(!risk_params.migrated);
+        assert!(!risk_params.curve_completed);
+        assert_eq!(risk_params.update_slot, testing_env.svm.get_slot());
+    }
+}
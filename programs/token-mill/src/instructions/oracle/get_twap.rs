@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    state::{Market, MarketOracle},
+};
+
+#[derive(Accounts)]
+pub struct TwapView<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: AccountLoader<'info, MarketOracle>,
+}
+
+/// View returning the time weighted average mid price of the market over the last `window`
+/// seconds, scaled by `SCALE`, see `MarketOracle::get_twap`.
+pub fn handler(ctx: Context<TwapView>, window: i64) -> Result<u64> {
+    let market = ctx.accounts.market.load()?;
+
+    ctx.accounts.market_oracle.load()?.get_twap(
+        window,
+        market.get_mid_price()?,
+        get_clock()?.unix_timestamp,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MarketOracle};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketOracleAction, GetTwapAction, SwapAction, TokenMillEnv,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateMarketOracleAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env
+    }
+
+    fn get_twap(testing_env: &mut TokenMillEnv, window: i64) -> u64 {
+        let metadata = testing_env
+            .svm
+            .execute_actions(&[&GetTwapAction::new(testing_env, window)])
+            .unwrap();
+
+        u64::from_le_bytes(metadata.return_data.data[..8].try_into().unwrap())
+    }
+
+    #[test]
+    fn get_twap_after_swap() {
+        let mut testing_env = setup_env();
+
+        let price_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_mid_price()
+            .unwrap();
+
+        testing_env.svm.warp(100);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            200_000_000_000,
+            u64::MAX,
+            None,
+        )
+        .with_market_oracle();
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let market_oracle = testing_env
+            .svm
+            .get_parsed_account::<MarketOracle>(&swap_action.market_oracle);
+
+        assert_eq!(market_oracle.length, 2);
+
+        let price_after = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_mid_price()
+            .unwrap();
+
+        assert!(price_after > price_before);
+
+        testing_env.svm.warp(100);
+
+        assert_eq!(
+            get_twap(&mut testing_env, 200),
+            (price_before + price_after) / 2
+        );
+        assert_eq!(get_twap(&mut testing_env, 100), price_after);
+    }
+
+    #[test]
+    fn get_twap_before_oldest_observation() {
+        let mut testing_env = setup_env();
+
+        testing_env.svm.warp(100);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&GetTwapAction::new(&testing_env, 101)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidTwapWindow);
+    }
+}
@@ -0,0 +1,17 @@
+pub mod create_market_oracle;
+pub mod create_market_stats;
+pub mod create_price_feed;
+pub mod create_risk_params;
+pub mod get_twap;
+pub mod push_price;
+pub mod push_risk_params;
+pub mod roll_market_stats;
+
+pub use create_market_oracle::*;
+pub use create_market_stats::*;
+pub use create_price_feed::*;
+pub use create_risk_params::*;
+pub use get_twap::*;
+pub use push_price::*;
+pub use push_risk_params::*;
+pub use roll_market_stats::*;
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillPriceFeedUpdateEvent,
+    state::{Market, PriceFeed},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PushPrice<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub price_feed: Account<'info, PriceFeed>,
+}
+
+/// Crank writing the current curve prices of the market to its price feed.
+pub fn handler(ctx: Context<PushPrice>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    let price_feed = &mut ctx.accounts.price_feed;
+
+    price_feed.update(
+        market.get_bid_price()?,
+        market.get_ask_price()?,
+        &get_clock()?,
+    )?;
+
+    emit_cpi!(TokenMillPriceFeedUpdateEvent {
+        price_feed: price_feed.key(),
+        price: price_feed.price,
+        conf: price_feed.conf,
+        publish_slot: price_feed.publish_slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, PriceFeed};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{CreatePriceFeedAction, PushPriceAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType,
+    };
+
+    #[test]
+    fn push_price() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let create_action = CreatePriceFeedAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let price_before = testing_env
+            .svm
+            .get_parsed_account::<PriceFeed>(&create_action.price_feed)
+            .price;
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.warp_to_slot(testing_env.svm.get_slot() + 1);
+
+        testing_env
+            .svm
+            .execute_actions(&[&swap_action, &PushPriceAction::new(&testing_env)])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let price_feed = testing_env
+            .svm
+            .get_parsed_account::<PriceFeed>(&create_action.price_feed);
+
+        let bid_price = market.get_bid_price().unwrap();
+        let ask_price = market.get_ask_price().unwrap();
+
+        assert!(price_feed.price > price_before);
+        assert_eq!(
+            price_feed.price,
+            (bid_price + (ask_price - bid_price) / 2) as i64
+        );
+        assert_eq!(price_feed.conf, (ask_price - bid_price) / 2);
+        assert_eq!(price_feed.publish_slot, testing_env.svm.get_slot());
+    }
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillRiskParamsUpdateEvent,
+    state::{Market, RiskParams, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PushRiskParams<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub risk_params: Account<'info, RiskParams>,
+}
+
+/// Crank writing the current risk data of the market to its risk params.
+pub fn handler(ctx: Context<PushRiskParams>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+    let risk_params = &mut ctx.accounts.risk_params;
+
+    risk_params.update(
+        &market,
+        ctx.accounts.config.max_swap_size_bps,
+        &get_clock()?,
+    )?;
+
+    emit_cpi!(TokenMillRiskParamsUpdateEvent {
+        risk_params: risk_params.key(),
+        max_swap_size_bps: risk_params.max_swap_size_bps,
+        paused_until_slot: risk_params.paused_until_slot,
+        price_conf_bps: risk_params.price_conf_bps,
+        migrated: risk_params.migrated,
+        update_slot: risk_params.update_slot,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RiskParams;
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreateRiskParamsAction, PauseUntilAction, PushRiskParamsAction, TokenMillEnv,
+        UpdateMaxPauseDurationAction, UpdateMaxSwapSizeAction,
+    };
+
+    fn setup_env() -> (TokenMillEnv, PushRiskParamsAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateRiskParamsAction::new(&testing_env)])
+            .unwrap();
+
+        let action = PushRiskParamsAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn push_risk_params() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.change_payer("admin");
+
+        let paused_until_slot = testing_env.svm.get_slot() + 10;
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &UpdateMaxSwapSizeAction::new(500),
+                &UpdateMaxPauseDurationAction::new(10),
+                &PauseUntilAction::new(&testing_env, paused_until_slot),
+            ])
+            .unwrap();
+
+        testing_env.svm.warp_to_slot(testing_env.svm.get_slot() + 1);
+
+        testing_env.svm.change_payer("bob");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let risk_params = testing_env
+            .svm
+            .get_parsed_account::<RiskParams>(&action.risk_params);
+
+        assert_eq!(risk_params.max_swap_size_bps, 500);
+        assert_eq!(risk_params.paused_until_slot, paused_until_slot);
+        assert_eq!(risk_params.update_slot, testing_env.svm.get_slot());
+    }
+}
@@ -0,0 +1,187 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock,
+    constant::STATS_KEEPER_REWARD,
+    errors::TokenMillError,
+    events::TokenMillMarketStatsRolloverEvent,
+    state::{Market, MarketStats, MARKET_STATS_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RollMarketStats<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_STATS_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = market_stats.load()?.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub market_stats: AccountLoader<'info, MarketStats>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+}
+
+/// Crank run once per volume window, rolling the volume counters of the market even without
+/// swaps and snapshotting them with the price into the stats. The keeper receives
+/// `STATS_KEEPER_REWARD` lamports, or what is left of them in the stats account.
+pub fn handler(ctx: Context<RollMarketStats>) -> Result<()> {
+    let timestamp = get_clock()?.unix_timestamp;
+    let market_stats_account = &ctx.accounts.market_stats;
+    let mut market_stats = market_stats_account.load_mut()?;
+
+    let (price, quote_volume, swap_fees) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        market.volume.roll(timestamp);
+
+        let (quote_volume, swap_fees) = market.volume.get_last_window(timestamp);
+
+        (market.get_mid_price()?, quote_volume, swap_fees)
+    };
+
+    market_stats.rollover(
+        MarketStats::get_window(timestamp),
+        price,
+        quote_volume,
+        swap_fees,
+    )?;
+
+    let keeper_reward = STATS_KEEPER_REWARD.min(MarketStats::get_available_lamports(
+        market_stats_account.get_lamports(),
+    )?);
+
+    if keeper_reward > 0 {
+        market_stats_account.sub_lamports(keeper_reward)?;
+        ctx.accounts.keeper.add_lamports(keeper_reward)?;
+
+        market_stats.total_keeper_rewards += keeper_reward;
+    }
+
+    emit_cpi!(TokenMillMarketStatsRolloverEvent {
+        market: ctx.accounts.market.key(),
+        window: market_stats.window,
+        quote_volume,
+        swap_fees,
+        price,
+        volatility_bps: market_stats.volatility_bps,
+        keeper: ctx.accounts.keeper.key(),
+        keeper_reward,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constant::{STATS_KEEPER_REWARD, VOLUME_WINDOW_DURATION},
+        Market, MarketStats,
+    };
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketStatsAction, RollMarketStatsAction, SwapAction,
+            TokenMillEnv,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, RollMarketStatsAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateMarketStatsAction::new(&testing_env),
+                &SwapAction::new(
+                    &testing_env,
+                    SwapType::Buy,
+                    SwapAmountType::ExactOutput,
+                    100_000_000_000,
+                    u64::MAX,
+                    None,
+                ),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let action = RollMarketStatsAction::new(&testing_env);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn roll_market_stats() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.airdrop(&action.market_stats);
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        let quote_volume = testing_env
+            .svm
+            .get_parsed_account::<Market>(&action.market)
+            .volume
+            .quote_volume;
+        let lamports_before = testing_env.svm.get_account(&action.market_stats).lamports;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+        let market_stats = testing_env
+            .svm
+            .get_parsed_account::<MarketStats>(&action.market_stats);
+
+        assert!(quote_volume > 0);
+        assert_eq!(market.volume.quote_volume, 0);
+        assert_eq!(market.volume.previous_quote_volume, quote_volume);
+        assert_eq!(market_stats.quote_volume, quote_volume);
+        assert_eq!(market_stats.price, market.get_mid_price().unwrap());
+        assert!(market_stats.volatility_bps > 0);
+        assert_eq!(market_stats.total_keeper_rewards, STATS_KEEPER_REWARD);
+        assert_eq!(
+            lamports_before - testing_env.svm.get_account(&action.market_stats).lamports,
+            STATS_KEEPER_REWARD
+        );
+    }
+
+    #[test]
+    fn roll_market_stats_without_rewards() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market_stats = testing_env
+            .svm
+            .get_parsed_account::<MarketStats>(&action.market_stats);
+
+        assert_eq!(market_stats.total_keeper_rewards, 0);
+    }
+
+    #[test]
+    fn roll_market_stats_twice_in_window() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.warp(1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::RolloverTooEarly);
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillStakePositionCreationEvent,
+    state::{Market, StakePosition},
+    STAKING_POSITION_PDA_SEED,
+};
+
+/// Creates the accounts a wallet trades and stakes with on a market, skipping the existing ones.
+/// Meant to be prepended to any token mill transaction.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PrepareTradeAccounts<'info> {
+    #[account(
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = base_token_program
+    )]
+    pub user_base_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = quote_token_program
+    )]
+    pub user_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [STAKING_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+pub fn handler(ctx: Context<PrepareTradeAccounts>) -> Result<()> {
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    if stake_position.market != Pubkey::default() {
+        return Ok(());
+    }
+
+    stake_position.initialize(ctx.accounts.market.key(), ctx.accounts.user.key())?;
+
+    emit_cpi!(TokenMillStakePositionCreationEvent {
+        market: ctx.accounts.market.key(),
+        stake_position: stake_position.key(),
+        user: ctx.accounts.user.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::StakePosition;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{PrepareTradeAccountsAction, TokenMillEnv},
+        make_address,
+    };
+
+    #[test]
+    fn prepare_trade_accounts() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let erin = make_address("erin");
+
+        testing_env.svm.airdrop(&erin);
+        testing_env.svm.change_payer("erin");
+
+        let action = PrepareTradeAccountsAction::new(&testing_env, erin);
+
+        assert!(!testing_env.svm.account_exists(&action.user_base_token_ata));
+        assert!(!testing_env.svm.account_exists(&action.user_quote_token_ata));
+        assert!(!testing_env.svm.account_exists(&action.stake_position));
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert!(testing_env.svm.account_exists(&action.user_base_token_ata));
+        assert!(testing_env.svm.account_exists(&action.user_quote_token_ata));
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+
+        assert_eq!(stake_position.market, action.market);
+        assert_eq!(stake_position.user, erin);
+    }
+
+    #[test]
+    fn prepare_trade_accounts_with_existing_accounts() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let bob = testing_env.svm.change_payer("bob");
+
+        let action = PrepareTradeAccountsAction::new(&testing_env, bob);
+
+        assert!(testing_env.svm.account_exists(&action.user_base_token_ata));
+
+        let result = testing_env.svm.execute_actions(&[&action, &action]);
+
+        assert!(result.is_ok());
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+
+        assert_eq!(stake_position.user, bob);
+    }
+}
@@ -0,0 +1,404 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as sysvar_instructions};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillProtocolFeeConversionEvent,
+    manager::{
+        guard_manager,
+        policy_manager::ValidateSwapArgs,
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::transfer_from_pda,
+    },
+    state::{
+        Denylist, Market, MarketOracle, MarketStats, ProtocolFeeConverter, TokenMillConfig,
+        PROTOCOL_FEE_CONVERTER_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConvertProtocolFees<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_CONVERTER_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = protocol_fee_converter.bump,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub protocol_fee_converter: Account<'info, ProtocolFeeConverter>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Source of the converted fees, the converter spending them as delegate
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = protocol_fee_converter.treasury,
+        associated_token::token_program = base_token_program
+    )]
+    pub treasury_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Instructions sysvar, see `guard_manager`
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the conversion once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Records the price of the market before the conversion when provided, required by dynamic
+    /// fee markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the conversion into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+}
+
+/// Permissionless crank spending `quote_amount` of the protocol fees on the market of the
+/// protocol token, the tokens bought going to the treasury.
+/// The buy must return at least `min_base_amount` and leave the ask price at or below the max
+/// price of the converter, and can't share its transaction with other instructions on the market
+/// so that it can't be sandwiched. The treasury is checked and reported as the trader.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ConvertProtocolFees<'info>>,
+    quote_amount: u64,
+    min_base_amount: u64,
+) -> Result<()> {
+    require!(quote_amount > 0, TokenMillError::InvalidAmount);
+
+    guard_manager::check_isolated_call(
+        &ctx.accounts.instructions_sysvar,
+        &ctx.accounts.market.key(),
+    )?;
+
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let protocol_fee_converter = &mut ctx.accounts.protocol_fee_converter;
+    let treasury = protocol_fee_converter.treasury;
+
+    swap_accounts.check_trader(&treasury)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    let (outcome, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused(get_clock()?.slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+
+        let max_base_amount = swap_accounts.before_swap(market, SwapType::Buy)?;
+
+        let outcome = swap_manager::swap(
+            market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            quote_amount,
+            None,
+            get_clock()?.slot,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+        require_gte!(
+            protocol_fee_converter.max_price,
+            market.get_ask_price()?,
+            TokenMillError::ConversionPriceExceeded
+        );
+
+        (outcome, market.seeds())
+    };
+
+    let SwapOutcome {
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+        ..
+    } = outcome;
+
+    require_gte!(
+        base_amount,
+        min_base_amount,
+        TokenMillError::AmountThresholdNotMet
+    );
+
+    protocol_fee_converter.record(quote_amount, base_amount);
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        user: treasury,
+        swap_type: SwapType::Buy,
+        swap_amount_type: SwapAmountType::ExactInput,
+        base_amount,
+        quote_amount,
+    })?;
+
+    let config_key = ctx.accounts.config.key();
+    let protocol_fee_converter_seeds = [
+        PROTOCOL_FEE_CONVERTER_PDA_SEED.as_bytes(),
+        config_key.as_ref(),
+        &[protocol_fee_converter.bump],
+    ];
+    let seeds = market_seeds.signer_seeds();
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        protocol_fee_converter.to_account_info(),
+        &ctx.accounts.protocol_quote_token_ata,
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+        &protocol_fee_converter_seeds,
+    )?;
+
+    transfer_from_pda(
+        &ctx.accounts.base_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.treasury_base_token_ata,
+        &ctx.accounts.base_token_program,
+        base_amount,
+        &seeds,
+    )?;
+
+    swap_accounts.settle(
+        revenue_report.as_mut(),
+        treasury,
+        SwapType::Buy,
+        &outcome,
+        0,
+        &seeds,
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillProtocolFeeConversionEvent {
+        config: config_key,
+        market: ctx.accounts.market.key(),
+        treasury,
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, ProtocolFeeConverter};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ConvertProtocolFeesAction, SetProtocolFeeConverterAction, SwapAction,
+            TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    // Returns the protocol fees approved to the converter
+    fn setup_env() -> (TokenMillEnv, u64) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                DEFAULT_TOTAL_SUPPLY / 2,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        let set_action = SetProtocolFeeConverterAction::new(&testing_env, u64::MAX);
+
+        testing_env.svm.execute_actions(&[&set_action]).unwrap();
+
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let dave = make_address("dave");
+
+        testing_env
+            .svm
+            .create_ata(
+                &make_address("admin"),
+                &base_token_mint,
+                testing_env.base_token_type,
+            )
+            .unwrap();
+
+        let protocol_fees = testing_env.svm.get_balance(&quote_token_mint, &dave);
+
+        testing_env.svm.change_payer("dave");
+
+        testing_env
+            .svm
+            .approve(
+                &quote_token_mint,
+                &dave,
+                &set_action.protocol_fee_converter,
+                protocol_fees,
+            )
+            .unwrap();
+
+        (testing_env, protocol_fees)
+    }
+
+    #[test]
+    fn convert_protocol_fees() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let action = ConvertProtocolFeesAction::new(&testing_env, protocol_fees / 2, 1);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_converter = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeConverter>(&action.protocol_fee_converter);
+
+        assert_eq!(
+            protocol_fee_converter.total_quote_amount_converted,
+            protocol_fees / 2
+        );
+        assert!(protocol_fee_converter.total_base_amount_bought > 0);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &make_address("admin")),
+            protocol_fee_converter.total_base_amount_bought
+        );
+
+        // Beyond the amount approved
+        let action = ConvertProtocolFeesAction::new(&testing_env, protocol_fees, 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn convert_protocol_fees_below_min_base_amount() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let action = ConvertProtocolFeesAction::new(&testing_env, protocol_fees, u64::MAX);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::AmountThresholdNotMet);
+    }
+
+    #[test]
+    fn convert_protocol_fees_above_max_price() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let max_price = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_ask_price()
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetProtocolFeeConverterAction::new(&testing_env, max_price)])
+            .unwrap();
+
+        let action = ConvertProtocolFeesAction::new(&testing_env, protocol_fees, 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ConversionPriceExceeded);
+    }
+}
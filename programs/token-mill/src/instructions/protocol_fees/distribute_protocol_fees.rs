@@ -0,0 +1,250 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillProtocolFeeDistributionEvent,
+    state::{ProtocolFeeSplit, TokenMillConfig, PROTOCOL_FEE_SPLIT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DistributeProtocolFees<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        seeds = [PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = protocol_fee_split.bump,
+        has_one = config @ TokenMillError::InvalidConfigAccount
+    )]
+    pub protocol_fee_split: Account<'info, ProtocolFeeSplit>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Source of the distributed fees, the split spending them as delegate
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless crank distributing `amount` of the protocol fees between the recipients of the
+/// split, pro rata of their weights. The quote token accounts of the recipients are passed as
+/// remaining accounts, in the order of the recipients.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeProtocolFees<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, TokenMillError::InvalidAmount);
+
+    let protocol_fee_split = &ctx.accounts.protocol_fee_split;
+
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        protocol_fee_split.recipients.len(),
+        TokenMillError::InvalidProtocolFeeSplit
+    );
+
+    let amounts = protocol_fee_split.get_amounts(amount)?;
+
+    let config_key = ctx.accounts.config.key();
+    let seeds = [
+        PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(),
+        config_key.as_ref(),
+        &[protocol_fee_split.bump],
+    ];
+
+    for ((share, recipient_amount), recipient_token_account) in protocol_fee_split
+        .recipients
+        .iter()
+        .zip(amounts.iter())
+        .zip(ctx.remaining_accounts)
+    {
+        require_keys_eq!(
+            recipient_token_account.key(),
+            get_associated_token_address_with_program_id(
+                &share.recipient,
+                &ctx.accounts.quote_token_mint.key(),
+                &ctx.accounts.quote_token_program.key(),
+            ),
+            TokenMillError::InvalidProtocolFeeSplit
+        );
+
+        if *recipient_amount == 0 {
+            continue;
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.protocol_quote_token_ata.to_account_info(),
+                    mint: ctx.accounts.quote_token_mint.to_account_info(),
+                    to: recipient_token_account.clone(),
+                    authority: protocol_fee_split.to_account_info(),
+                },
+                &[&seeds],
+            ),
+            *recipient_amount,
+            ctx.accounts.quote_token_mint.decimals,
+        )?;
+    }
+
+    let protocol_fee_split = &mut ctx.accounts.protocol_fee_split;
+
+    protocol_fee_split.total_distributed += amount;
+
+    emit_cpi!(TokenMillProtocolFeeDistributionEvent {
+        config: config_key,
+        protocol_fee_split: protocol_fee_split.key(),
+        amounts,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::prelude::Pubkey;
+
+    use crate::{ProtocolFeeShare, ProtocolFeeSplit};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, DistributeProtocolFeesAction, SetProtocolFeeSplitAction, SwapAction,
+            TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn recipients() -> [Pubkey; 2] {
+        [make_address("admin"), make_address("carol")]
+    }
+
+    /// Returns the protocol fees of a buy, approved by the protocol fee recipient to the split.
+    fn setup_env() -> (TokenMillEnv, u64) {
+        let mut testing_env = TokenMillEnv::default();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let dave = make_address("dave");
+
+        let balance_before = testing_env.svm.get_balance(&quote_token_mint, &dave);
+
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                DEFAULT_TOTAL_SUPPLY / 2,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        let protocol_fees = testing_env.svm.get_balance(&quote_token_mint, &dave) - balance_before;
+
+        testing_env.svm.change_payer("admin");
+
+        let [admin, carol] = recipients();
+
+        let set_action = SetProtocolFeeSplitAction::new(vec![
+            ProtocolFeeShare {
+                recipient: admin,
+                weight_bps: 7_000,
+            },
+            ProtocolFeeShare {
+                recipient: carol,
+                weight_bps: 3_000,
+            },
+        ]);
+
+        testing_env.svm.execute_actions(&[&set_action]).unwrap();
+
+        testing_env.svm.change_payer("dave");
+
+        testing_env
+            .svm
+            .approve(
+                &quote_token_mint,
+                &dave,
+                &set_action.protocol_fee_split,
+                protocol_fees,
+            )
+            .unwrap();
+
+        (testing_env, protocol_fees)
+    }
+
+    #[test]
+    fn distribute_protocol_fees() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let balances_before = recipients()
+            .map(|recipient| testing_env.svm.get_balance(&quote_token_mint, &recipient));
+
+        let action = DistributeProtocolFeesAction::new(&testing_env, &recipients(), protocol_fees);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let carol_amount = protocol_fees * 3_000 / 10_000;
+
+        let [admin, carol] = recipients();
+
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &admin) - balances_before[0],
+            protocol_fees - carol_amount
+        );
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &carol) - balances_before[1],
+            carol_amount
+        );
+
+        let protocol_fee_split = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeSplit>(&action.protocol_fee_split);
+
+        assert_eq!(protocol_fee_split.total_distributed, protocol_fees);
+    }
+
+    #[test]
+    fn distribute_protocol_fees_above_allowance() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let action =
+            DistributeProtocolFeesAction::new(&testing_env, &recipients(), protocol_fees + 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn distribute_protocol_fees_with_invalid_recipient() {
+        let (mut testing_env, protocol_fees) = setup_env();
+
+        let mut action =
+            DistributeProtocolFeesAction::new(&testing_env, &recipients(), protocol_fees);
+
+        action.recipient_token_accounts.swap(0, 1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidProtocolFeeSplit);
+    }
+}
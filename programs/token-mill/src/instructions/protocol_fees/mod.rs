@@ -0,0 +1,9 @@
+pub mod convert_protocol_fees;
+pub mod distribute_protocol_fees;
+pub mod set_protocol_fee_converter;
+pub mod set_protocol_fee_split;
+
+pub use convert_protocol_fees::*;
+pub use distribute_protocol_fees::*;
+pub use set_protocol_fee_converter::*;
+pub use set_protocol_fee_split::*;
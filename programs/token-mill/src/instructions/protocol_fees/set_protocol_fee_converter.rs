@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillProtocolFeeConverterUpdateEvent,
+    state::{Market, ProtocolFeeConverter, TokenMillConfig, PROTOCOL_FEE_CONVERTER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolFeeConverter<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Market of the protocol token
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProtocolFeeConverter::INIT_SPACE,
+        seeds = [PROTOCOL_FEE_CONVERTER_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_converter: Account<'info, ProtocolFeeConverter>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the protocol fee converter of the config.
+pub fn handler(
+    ctx: Context<SetProtocolFeeConverter>,
+    treasury: Pubkey,
+    max_price: u64,
+) -> Result<()> {
+    require!(max_price > 0, TokenMillError::InvalidAmount);
+
+    let protocol_fee_converter = &mut ctx.accounts.protocol_fee_converter;
+
+    if protocol_fee_converter.config == Pubkey::default() {
+        protocol_fee_converter
+            .initialize(ctx.bumps.protocol_fee_converter, ctx.accounts.config.key())?;
+    }
+
+    protocol_fee_converter.market = ctx.accounts.market.key();
+    protocol_fee_converter.treasury = treasury;
+    protocol_fee_converter.max_price = max_price;
+
+    emit_cpi!(TokenMillProtocolFeeConverterUpdateEvent {
+        config: ctx.accounts.config.key(),
+        protocol_fee_converter: protocol_fee_converter.key(),
+        market: protocol_fee_converter.market,
+        treasury,
+        max_price,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ProtocolFeeConverter;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, SetProtocolFeeConverterAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn set_protocol_fee_converter() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let mut action = SetProtocolFeeConverterAction::new(&testing_env, u64::MAX);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_converter = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeConverter>(&action.protocol_fee_converter);
+
+        assert_eq!(protocol_fee_converter.config, testing_env.config);
+        assert_eq!(protocol_fee_converter.market, testing_env.market);
+        assert_eq!(protocol_fee_converter.treasury, make_address("admin"));
+        assert_eq!(protocol_fee_converter.max_price, u64::MAX);
+
+        action.max_price = 1_000;
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_converter = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeConverter>(&action.protocol_fee_converter);
+
+        assert_eq!(protocol_fee_converter.max_price, 1_000);
+    }
+
+    #[test]
+    fn set_protocol_fee_converter_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = SetProtocolFeeConverterAction::new(&testing_env, u64::MAX);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
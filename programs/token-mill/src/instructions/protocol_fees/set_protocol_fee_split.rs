@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillProtocolFeeSplitUpdateEvent,
+    state::{ProtocolFeeShare, ProtocolFeeSplit, TokenMillConfig, PROTOCOL_FEE_SPLIT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetProtocolFeeSplit<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ProtocolFeeSplit::INIT_SPACE,
+        seeds = [PROTOCOL_FEE_SPLIT_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_split: Account<'info, ProtocolFeeSplit>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates or updates the protocol fee split of the config. Redirecting the protocol fees is
+/// as sensitive as changing the protocol fee recipient, so it is only allowed while no config
+/// change delay is set, and goes through `ConfigChange::ProtocolFeeSplit` otherwise.
+pub fn handler(ctx: Context<SetProtocolFeeSplit>, recipients: Vec<ProtocolFeeShare>) -> Result<()> {
+    ctx.accounts.config.check_no_config_change_delay()?;
+
+    let protocol_fee_split = &mut ctx.accounts.protocol_fee_split;
+
+    if protocol_fee_split.config == Pubkey::default() {
+        protocol_fee_split.initialize(ctx.bumps.protocol_fee_split, ctx.accounts.config.key())?;
+    }
+
+    protocol_fee_split.set_recipients(recipients)?;
+
+    emit_cpi!(TokenMillProtocolFeeSplitUpdateEvent {
+        config: ctx.accounts.config.key(),
+        protocol_fee_split: protocol_fee_split.key(),
+        recipients: protocol_fee_split.recipients.clone(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ProtocolFeeShare, ProtocolFeeSplit};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, SetProtocolFeeSplitAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    fn recipients() -> Vec<ProtocolFeeShare> {
+        vec![
+            ProtocolFeeShare {
+                recipient: make_address("dave"),
+                weight_bps: 7_000,
+            },
+            ProtocolFeeShare {
+                recipient: make_address("carol"),
+                weight_bps: 3_000,
+            },
+        ]
+    }
+
+    #[test]
+    fn set_protocol_fee_split() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = SetProtocolFeeSplitAction::new(recipients());
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_split = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeSplit>(&action.protocol_fee_split);
+
+        assert_eq!(protocol_fee_split.config, make_address("config"));
+        assert_eq!(protocol_fee_split.recipients, recipients());
+
+        action.recipients = vec![ProtocolFeeShare {
+            recipient: make_address("dave"),
+            weight_bps: 10_000,
+        }];
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let protocol_fee_split = testing_env
+            .svm
+            .get_parsed_account::<ProtocolFeeSplit>(&action.protocol_fee_split);
+
+        assert_eq!(protocol_fee_split.recipients, action.recipients);
+    }
+
+    #[test]
+    fn set_invalid_protocol_fee_split() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = SetProtocolFeeSplitAction::new(recipients());
+
+        action.recipients[1].weight_bps = 2_000;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidProtocolFeeSplit);
+    }
+
+    #[test]
+    fn set_protocol_fee_split_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = SetProtocolFeeSplitAction::new(recipients());
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    manager::{
+        swap_manager::{self, SwapAmountType, SwapType},
+        token_manager::{get_transfer_fee, get_transfer_inverse_fee},
+    },
+    state::{Market, TokenMillConfig},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Clone, PartialEq)]
+pub struct SwapQuote {
+    /// Amount sent by the user, checked against `other_amount_threshold` on exact outputs.
+    pub amount_in: u64,
+    /// Amount received by the user, checked against `other_amount_threshold` on exact inputs.
+    pub amount_out: u64,
+    /// Part of `amount_in` refunded to the user on exact output buys.
+    pub quote_refund: u64,
+    pub swap_fee: u64,
+    pub launch_fee: u64,
+    pub holder_rewards_fee: u64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwapView<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+}
+
+/// View quoting a swap with the same pricing, fees and limits as `swap`, so that routers can
+/// simulate it instead of replicating the curve math. Amounts include the transfer fees of the
+/// quote token.
+pub fn handler(
+    ctx: Context<QuoteSwapView>,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+) -> Result<SwapQuote> {
+    require_gt!(amount, 0, TokenMillError::InvalidAmount);
+
+    let mut market = *ctx.accounts.market.load()?;
+    let quote_token_mint = &ctx.accounts.quote_token_mint;
+    let current_slot = get_clock()?.slot;
+    let timestamp = get_clock()?.unix_timestamp;
+
+    // Quoted with the updates the swap would apply
+    market.apply_scheduled_updates(current_slot, timestamp);
+
+    require!(
+        !market.is_paused(current_slot),
+        TokenMillError::MarketPaused
+    );
+
+    let curve_amount = match (swap_type, swap_amount_type) {
+        (SwapType::Buy, SwapAmountType::ExactInput) => {
+            amount - get_transfer_fee(quote_token_mint, amount)?
+        }
+        (SwapType::Sell, SwapAmountType::ExactOutput) => {
+            amount + get_transfer_inverse_fee(quote_token_mint, amount)?
+        }
+        _ => amount,
+    };
+
+    // Referral fees are taken out of the protocol fee, they don't change the user amounts
+    let outcome = swap_manager::get_swap_outcome(
+        &market,
+        swap_type,
+        swap_amount_type,
+        curve_amount,
+        None,
+        current_slot,
+        timestamp,
+    )?;
+
+    if let Some(max_base_amount) =
+        market.get_max_swap_base_amount(swap_type, ctx.accounts.config.max_swap_size_bps)?
+    {
+        require_gte!(
+            max_base_amount,
+            outcome.base_amount,
+            TokenMillError::SwapSizeExceeded
+        );
+    }
+
+    let (amount_in, amount_out) = match swap_type {
+        SwapType::Buy => {
+            let quote_amount_in = outcome.quote_amount
+                + get_transfer_inverse_fee(quote_token_mint, outcome.quote_amount)?;
+
+            let quote_amount_in = match swap_amount_type {
+                SwapAmountType::ExactInput => quote_amount_in.min(amount),
+                SwapAmountType::ExactOutput => quote_amount_in,
+            };
+
+            (quote_amount_in, outcome.base_amount)
+        }
+        SwapType::Sell => (
+            outcome.base_amount,
+            outcome.quote_amount - get_transfer_fee(quote_token_mint, outcome.quote_amount)?,
+        ),
+    };
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        quote_refund: outcome.quote_refund,
+        swap_fee: outcome.swap_fee,
+        launch_fee: outcome.launch_fee,
+        holder_rewards_fee: outcome.holder_rewards_fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SwapQuote;
+    use anchor_lang::AnchorDeserialize;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, QuoteSwapAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType, TokenMillError,
+    };
+    use rstest::rstest;
+
+    fn setup_env(testing_env: TokenMillEnv) -> TokenMillEnv {
+        let mut testing_env = testing_env.with_default_market();
+
+        testing_env.svm.change_payer("bob");
+
+        // Supply to sell
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                100_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        testing_env
+    }
+
+    fn get_quote(testing_env: &mut TokenMillEnv, action: &QuoteSwapAction) -> SwapQuote {
+        let metadata = testing_env.svm.execute_actions(&[action]).unwrap();
+
+        SwapQuote::try_from_slice(&metadata.return_data.data).unwrap()
+    }
+
+    // Balance changes of the user as (base, quote)
+    fn swap(testing_env: &mut TokenMillEnv, action: &QuoteSwapAction) -> (u64, u64) {
+        let base_token_mint = testing_env.base_token_mint.unwrap();
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let user = testing_env.svm.payer;
+
+        let base_balance_before = testing_env.svm.get_balance(&base_token_mint, &user);
+        let quote_balance_before = testing_env.svm.get_balance(&quote_token_mint, &user);
+
+        let other_amount_threshold = match action.swap_amount_type {
+            SwapAmountType::ExactInput => 0,
+            SwapAmountType::ExactOutput => u64::MAX,
+        };
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                testing_env,
+                action.swap_type,
+                action.swap_amount_type,
+                action.amount,
+                other_amount_threshold,
+                None,
+            )])
+            .unwrap();
+
+        let base_balance = testing_env.svm.get_balance(&base_token_mint, &user);
+        let quote_balance = testing_env.svm.get_balance(&quote_token_mint, &user);
+
+        (
+            base_balance.abs_diff(base_balance_before),
+            quote_balance.abs_diff(quote_balance_before),
+        )
+    }
+
+    #[rstest]
+    fn quote_swap(
+        #[values(SwapType::Buy, SwapType::Sell)] swap_type: SwapType,
+        #[values(SwapAmountType::ExactInput, SwapAmountType::ExactOutput)]
+        swap_amount_type: SwapAmountType,
+    ) {
+        let mut testing_env = setup_env(TokenMillEnv::new().with_default_quote_token_mint());
+
+        let action = QuoteSwapAction::new(&testing_env, swap_type, swap_amount_type, 1_000_000_000);
+
+        let quote = get_quote(&mut testing_env, &action);
+        let (base_amount, quote_amount) = swap(&mut testing_env, &action);
+
+        match swap_type {
+            SwapType::Buy => {
+                assert_eq!(quote_amount, quote.amount_in - quote.quote_refund);
+                assert_eq!(base_amount, quote.amount_out);
+            }
+            SwapType::Sell => {
+                assert_eq!(base_amount, quote.amount_in);
+                assert_eq!(quote_amount, quote.amount_out);
+            }
+        }
+    }
+
+    #[test]
+    fn quote_swap_with_transfer_fee_quote_token() {
+        // 1% transfer fee
+        let mut testing_env =
+            setup_env(TokenMillEnv::new().with_transfer_fee_quote_token_mint(9, 100));
+
+        let action = QuoteSwapAction::new(
+            &testing_env,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+        );
+
+        let quote = get_quote(&mut testing_env, &action);
+        let (_, quote_amount) = swap(&mut testing_env, &action);
+
+        assert_eq!(quote_amount, quote.amount_out);
+    }
+
+    #[test]
+    fn quote_swap_with_zero_amount() {
+        let mut testing_env = setup_env(TokenMillEnv::new().with_default_quote_token_mint());
+
+        let action =
+            QuoteSwapAction::new(&testing_env, SwapType::Buy, SwapAmountType::ExactInput, 0);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
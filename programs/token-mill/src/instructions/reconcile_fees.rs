@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillFeeReconciliationEvent,
+    state::{FeeReconciliation, Market, FEE_RECONCILIATION_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReconcileFees<'info> {
+    #[account(has_one = quote_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeReconciliation::INIT_SPACE,
+        seeds = [FEE_RECONCILIATION_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub fee_reconciliation: Account<'info, FeeReconciliation>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Permissionless, checks the quote vault of the market against the curve proceeds and the
+/// pending fees, and records the outcome in the fee reconciliation account of the market.
+/// A deficit doesn't fail the instruction, it is recorded as a discrepancy for auditors.
+pub fn handler(ctx: Context<ReconcileFees>) -> Result<()> {
+    let (curve_proceeds, pending_fees) = {
+        let market = ctx.accounts.market.load()?;
+
+        (market.get_curve_proceeds()?, market.get_pending_fees())
+    };
+
+    let fee_reconciliation = &mut ctx.accounts.fee_reconciliation;
+
+    if fee_reconciliation.market == Pubkey::default() {
+        fee_reconciliation.initialize(ctx.bumps.fee_reconciliation, ctx.accounts.market.key())?;
+    }
+
+    let quote_vault_amount = ctx.accounts.market_quote_token_ata.amount;
+
+    let deficit = fee_reconciliation.record(
+        get_clock()?.slot,
+        quote_vault_amount,
+        curve_proceeds + pending_fees,
+    );
+
+    emit_cpi!(TokenMillFeeReconciliationEvent {
+        market: ctx.accounts.market.key(),
+        quote_vault_amount,
+        curve_proceeds,
+        pending_fees,
+        deficit,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FeeReconciliation, Market};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{ReconcileFeesAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType,
+    };
+
+    #[test]
+    fn reconcile_fees() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                100_000_000_000,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        let action = ReconcileFeesAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let fee_reconciliation = testing_env
+            .svm
+            .get_parsed_account::<FeeReconciliation>(&action.fee_reconciliation);
+
+        assert_eq!(fee_reconciliation.market, testing_env.market);
+        assert_eq!(fee_reconciliation.reconciliation_count, 1);
+        assert_eq!(fee_reconciliation.discrepancy_count, 0);
+        assert_eq!(
+            fee_reconciliation.last_expected_amount,
+            market.get_curve_proceeds().unwrap() + market.get_pending_fees()
+        );
+        assert!(
+            fee_reconciliation.last_quote_vault_amount >= fee_reconciliation.last_expected_amount
+        );
+    }
+
+    #[test]
+    fn reconcile_fees_twice() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("bob");
+
+        let action = ReconcileFeesAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.warp_to_slot(testing_env.svm.get_slot() + 1);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let fee_reconciliation = testing_env
+            .svm
+            .get_parsed_account::<FeeReconciliation>(&action.fee_reconciliation);
+
+        assert_eq!(fee_reconciliation.reconciliation_count, 2);
+        assert_eq!(fee_reconciliation.discrepancy_count, 0);
+        assert_eq!(fee_reconciliation.max_deficit, 0);
+    }
+}
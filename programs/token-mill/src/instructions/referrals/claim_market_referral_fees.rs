@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketReferralFeeClaimEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Market, MarketReferralAccrual},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimMarketReferralFees<'info> {
+    #[account(
+        mut,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = referral_token_account @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_accrual: Account<'info, MarketReferralAccrual>,
+
+    #[account(mut)]
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays out the referral fees held back by the market once the referred volume is reached.
+/// Fees can only go to the referral token account, so anyone can trigger the claim.
+pub fn handler(ctx: Context<ClaimMarketReferralFees>) -> Result<()> {
+    let referral_accrual = &mut ctx.accounts.referral_accrual;
+
+    let market_seeds = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        market.lock()?;
+
+        require_gte!(
+            referral_accrual.referred_volume,
+            market.min_referred_volume,
+            TokenMillError::ReferralVolumeNotMet
+        );
+
+        market.check_fee_withdrawal(
+            ctx.accounts.market_quote_token_ata.amount,
+            referral_accrual.pending_fees,
+        )?;
+
+        market.held_referral_fees -= referral_accrual.pending_fees;
+
+        market.seeds()
+    };
+
+    let pending_fees = referral_accrual.pending_fees;
+    referral_accrual.pending_fees = 0;
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.referral_token_account,
+        &ctx.accounts.quote_token_program,
+        pending_fees,
+        &market_seeds.signer_seeds(),
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillMarketReferralFeeClaimEvent {
+        market: ctx.accounts.market.key(),
+        referral_token_account: ctx.accounts.referral_token_account.key(),
+        referred_volume: ctx.accounts.referral_accrual.referred_volume,
+        fees_distributed: pending_fees,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MarketReferralAccrual;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimMarketReferralFeesAction, CreateMarketReferralAccrualAction,
+            CreateReferralAccountAction, SwapAction, TokenMillEnv, UpdateMinReferredVolumeAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const MIN_REFERRED_VOLUME: u64 = 10_000_000_000;
+
+    fn setup_env() -> (TokenMillEnv, SwapAction, ClaimMarketReferralFeesAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMinReferredVolumeAction::new(
+                &testing_env,
+                MIN_REFERRED_VOLUME,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let create_action = CreateMarketReferralAccrualAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            MIN_REFERRED_VOLUME / 2,
+            0,
+            Some(make_address("carol")),
+        )
+        .with_referral_accrual(create_action.referral_accrual);
+
+        let claim_action = ClaimMarketReferralFeesAction::new(&testing_env);
+
+        (testing_env, swap_action, claim_action)
+    }
+
+    #[test]
+    fn claim_market_referral_fees() {
+        let (mut testing_env, swap_action, claim_action) = setup_env();
+        let referral_account = CreateReferralAccountAction::new().referral_account;
+
+        testing_env
+            .svm
+            .execute_actions(&[&swap_action, &swap_action])
+            .unwrap();
+
+        let referral_accrual = testing_env
+            .svm
+            .get_parsed_account::<MarketReferralAccrual>(&claim_action.referral_accrual);
+
+        assert_eq!(referral_accrual.referred_volume, MIN_REFERRED_VOLUME);
+        assert!(referral_accrual.pending_fees > 0);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&claim_action.quote_token_mint, &referral_account),
+            0
+        );
+
+        testing_env.svm.execute_actions(&[&claim_action]).unwrap();
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&claim_action.quote_token_mint, &referral_account),
+            referral_accrual.pending_fees
+        );
+
+        let referral_accrual = testing_env
+            .svm
+            .get_parsed_account::<MarketReferralAccrual>(&claim_action.referral_accrual);
+
+        assert_eq!(referral_accrual.pending_fees, 0);
+    }
+
+    #[test]
+    fn claim_market_referral_fees_below_min_referred_volume() {
+        let (mut testing_env, swap_action, claim_action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&claim_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ReferralVolumeNotMet);
+
+        // Lowering the requirement releases the fees accrued so far
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMinReferredVolumeAction::new(&testing_env, 0)])
+            .unwrap();
+
+        testing_env.svm.execute_actions(&[&claim_action]).unwrap();
+    }
+}
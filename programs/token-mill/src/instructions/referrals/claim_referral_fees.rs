@@ -2,14 +2,21 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    events::TokenMillReferralFeeClaimEvent, manager::token_manager::transfer_from_pda,
+    errors::TokenMillError,
+    events::TokenMillReferralFeeClaimEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Denylist, TokenMillConfig},
     ReferralAccount, REFERRAL_ACCOUNT_PDA_SEED,
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimReferralFees<'info> {
-    #[account(has_one = referrer)]
+    #[account(
+        mut,
+        has_one = referrer,
+        has_one = config @ TokenMillError::InvalidConfigAccount
+    )]
     pub referral_account: Account<'info, ReferralAccount>,
 
     pub quote_token_mint: InterfaceAccount<'info, Mint>,
@@ -33,9 +40,25 @@ pub struct ClaimReferralFees<'info> {
     pub referrer: Signer<'info>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
+
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
 }
 
 pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
+    Denylist::check(
+        &ctx.accounts.config,
+        ctx.accounts.denylist.as_ref(),
+        &ctx.accounts.referrer.key(),
+    )?;
+
+    ctx.accounts
+        .referral_account
+        .update_tier(&ctx.accounts.config);
+
     let referral_account = &ctx.accounts.referral_account;
     let referral_account_quote_token_ata = &ctx.accounts.referral_account_quote_token_ata;
     let pending_fees = referral_account_quote_token_ata.amount;
@@ -61,6 +84,7 @@ pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
         referrer: ctx.accounts.referrer.key(),
         quote_token_mint: ctx.accounts.quote_token_mint.key(),
         fees_distributed: pending_fees,
+        tier_fee_share: referral_account.tier_fee_share,
     });
 
     Ok(())
@@ -68,9 +92,13 @@ pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
+    use crate::{manager::swap_manager, Market, ReferralAccount, ReferralTier};
     use joelana_test_utils::joelana_env::{
-        actions::token_mill::{ClaimReferralFeesAction, SwapAction, TokenMillEnv},
-        make_address, SwapAmountType, SwapType,
+        actions::token_mill::{
+            tm_parse_error, ClaimReferralFeesAction, SwapAction, TokenMillEnv,
+            UpdateReferralTiersAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
     };
 
     #[test]
@@ -97,4 +125,144 @@ mod tests {
             .execute_actions(&[&claim_referral_fees_action])
             .unwrap();
     }
+
+    #[test]
+    fn claim_referral_fees_with_referral_tier() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateReferralTiersAction::new(vec![ReferralTier {
+                min_referred_volume: 1,
+                referral_fee_share: 5_000,
+            }])])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let claim_referral_fees_action = ClaimReferralFeesAction::new(&testing_env);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        )
+        .with_referral_account(claim_referral_fees_action.referral_account);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let referral_account = testing_env
+            .svm
+            .get_parsed_account::<ReferralAccount>(&claim_referral_fees_action.referral_account);
+
+        assert!(referral_account.referred_volume > 0);
+        assert_eq!(referral_account.tier_fee_share, 0);
+
+        testing_env.svm.change_payer("carol");
+
+        testing_env
+            .svm
+            .execute_actions(&[&claim_referral_fees_action])
+            .unwrap();
+
+        let referral_account = testing_env
+            .svm
+            .get_parsed_account::<ReferralAccount>(&claim_referral_fees_action.referral_account);
+
+        assert_eq!(referral_account.tier_fee_share, 5_000);
+    }
+
+    #[test]
+    fn swap_with_referral_tier() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateReferralTiersAction::new(vec![ReferralTier {
+                min_referred_volume: 1,
+                referral_fee_share: 8_000,
+            }])])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let claim_referral_fees_action = ClaimReferralFeesAction::new(&testing_env);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        )
+        .with_referral_account(claim_referral_fees_action.referral_account);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.warp(1);
+
+        // The tier is reached by the volume of the first swap, without a claim
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let outcome = swap_manager::get_swap_outcome(
+            &market,
+            swap_manager::SwapType::Buy,
+            swap_manager::SwapAmountType::ExactOutput,
+            1_000_000_000,
+            Some(8_000),
+            testing_env.svm.get_slot(),
+            testing_env.svm.get_unix_timestamp(),
+        )
+        .unwrap();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let balance_before = testing_env.svm.get_balance(
+            &quote_token_mint,
+            &claim_referral_fees_action.referral_account,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_balance(
+                &quote_token_mint,
+                &claim_referral_fees_action.referral_account
+            ) - balance_before,
+            outcome.referral_fee
+        );
+    }
+
+    #[test]
+    fn swap_with_invalid_referral_account() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let claim_referral_fees_action = ClaimReferralFeesAction::new(&testing_env);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        )
+        .with_referral_account(claim_referral_fees_action.referral_account);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidReferralAccount);
+    }
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketReferralCodeClosureEvent,
+    state::{Market, MarketReferralCode},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseMarketReferralCode<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        close = creator
+    )]
+    pub referral_code: Account<'info, MarketReferralCode>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Ends the campaign of the code. Swaps can't be referred with it anymore.
+pub fn handler(ctx: Context<CloseMarketReferralCode>) -> Result<()> {
+    let referral_code = &ctx.accounts.referral_code;
+
+    emit_cpi!(TokenMillMarketReferralCodeClosureEvent {
+        market: ctx.accounts.market.key(),
+        referral_code: referral_code.key(),
+        referred_volume: referral_code.referred_volume,
+        referral_fees: referral_code.referral_fees,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CloseMarketReferralCodeAction, CreateMarketReferralCodeAction,
+            TokenMillEnv,
+        },
+        TokenMillError,
+    };
+
+    const CODE: [u8; 32] = [7; 32];
+
+    fn setup_env() -> (TokenMillEnv, CloseMarketReferralCodeAction) {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let create_action = CreateMarketReferralCodeAction::new(&testing_env, CODE, 1_000);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let action = CloseMarketReferralCodeAction::new(&testing_env, create_action.referral_code);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn close_market_referral_code() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert!(!testing_env.svm.account_exists(&action.referral_code));
+    }
+
+    #[test]
+    fn close_market_referral_code_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
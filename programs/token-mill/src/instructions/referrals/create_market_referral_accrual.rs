@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketReferralAccrualCreationEvent,
+    state::{Market, MarketReferralAccrual, MARKET_REFERRAL_ACCRUAL_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateMarketReferralAccrual<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        constraint = referral_token_account.mint == market.load()?.quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        seeds = [
+            MARKET_REFERRAL_ACCRUAL_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            referral_token_account.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + MarketReferralAccrual::INIT_SPACE
+    )]
+    pub referral_accrual: Account<'info, MarketReferralAccrual>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateMarketReferralAccrual>) -> Result<()> {
+    ctx.accounts.referral_accrual.initialize(
+        ctx.bumps.referral_accrual,
+        ctx.accounts.market.key(),
+        ctx.accounts.referral_token_account.key(),
+    );
+
+    emit_cpi!(TokenMillMarketReferralAccrualCreationEvent {
+        market: ctx.accounts.market.key(),
+        referral_accrual: ctx.accounts.referral_accrual.key(),
+        referral_token_account: ctx.accounts.referral_token_account.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreateMarketReferralAccrualAction, TokenMillEnv,
+    };
+
+    use crate::MarketReferralAccrual;
+
+    #[test]
+    fn create_market_referral_accrual() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("carol");
+
+        let action = CreateMarketReferralAccrualAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let referral_accrual = testing_env
+            .svm
+            .get_parsed_account::<MarketReferralAccrual>(&action.referral_accrual);
+
+        assert_eq!(referral_accrual.market, action.market);
+        assert_eq!(
+            referral_accrual.referral_token_account,
+            action.referral_token_account
+        );
+        assert_eq!(referral_accrual.referred_volume, 0);
+        assert_eq!(referral_accrual.pending_fees, 0);
+    }
+}
@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketReferralCodeCreationEvent,
+    state::{Market, MarketReferralCode, MARKET_REFERRAL_CODE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(code: [u8; 32])]
+pub struct CreateMarketReferralCode<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        constraint = referral_token_account.mint == market.load()?.quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        seeds = [MARKET_REFERRAL_CODE_PDA_SEED.as_bytes(), market.key().as_ref(), code.as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + MarketReferralCode::INIT_SPACE
+    )]
+    pub referral_code: Account<'info, MarketReferralCode>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateMarketReferralCode>,
+    code: [u8; 32],
+    creator_fee_share: u16,
+) -> Result<()> {
+    let referral_token_account = ctx.accounts.referral_token_account.key();
+
+    ctx.accounts.referral_code.initialize(
+        ctx.bumps.referral_code,
+        ctx.accounts.market.key(),
+        code,
+        referral_token_account,
+        creator_fee_share,
+    )?;
+
+    emit_cpi!(TokenMillMarketReferralCodeCreationEvent {
+        market: ctx.accounts.market.key(),
+        referral_code: ctx.accounts.referral_code.key(),
+        code,
+        referral_token_account,
+        creator_fee_share,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        constant::MAX_REFERRAL_CODE_FEE_SHARE, manager::swap_manager, Market, MarketReferralCode,
+        TokenMillConfig,
+    };
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateMarketReferralCodeAction, CreateReferralAccountAction,
+            SwapAction, TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const CODE: [u8; 32] = *b"influencer_campaign_00000000000_";
+    const CREATOR_FEE_SHARE: u16 = 2_000;
+
+    #[test]
+    fn create_market_referral_code() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateMarketReferralCodeAction::new(&testing_env, CODE, CREATOR_FEE_SHARE);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let referral_code = testing_env
+            .svm
+            .get_parsed_account::<MarketReferralCode>(&action.referral_code);
+
+        assert_eq!(referral_code.market, testing_env.market);
+        assert_eq!(referral_code.code, CODE);
+        assert_eq!(
+            referral_code.referral_token_account,
+            action.referral_token_account
+        );
+        assert_eq!(referral_code.creator_fee_share, CREATOR_FEE_SHARE);
+    }
+
+    #[test]
+    fn swap_with_market_referral_code() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateMarketReferralCodeAction::new(&testing_env, CODE, CREATOR_FEE_SHARE);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let config = testing_env
+            .svm
+            .get_parsed_account::<TokenMillConfig>(&testing_env.config);
+
+        // Fee split of the same swap without the code
+        let outcome = swap_manager::get_swap_outcome(
+            &market,
+            swap_manager::SwapType::Buy,
+            swap_manager::SwapAmountType::ExactOutput,
+            100_000_000_000,
+            Some(config.referral_fee_share),
+            0,
+            0,
+        )
+        .unwrap();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            100_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        )
+        .with_referral_code(action.referral_code);
+
+        let referral_account = CreateReferralAccountAction::new().referral_account;
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let referral_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &referral_account);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let creator_fee_cut = outcome.creator_fee * u64::from(CREATOR_FEE_SHARE) / 10_000;
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(
+            market.fees.pending_creator_fees,
+            outcome.creator_fee - creator_fee_cut
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &referral_account),
+            referral_balance_before + outcome.referral_fee + creator_fee_cut
+        );
+
+        let referral_code = testing_env
+            .svm
+            .get_parsed_account::<MarketReferralCode>(&action.referral_code);
+
+        assert_eq!(referral_code.referred_volume, outcome.quote_amount);
+        assert_eq!(
+            referral_code.referral_fees,
+            outcome.referral_fee + creator_fee_cut
+        );
+    }
+
+    #[test]
+    fn create_market_referral_code_above_cap() {
+        let mut testing_env = TokenMillEnv::default();
+        testing_env.svm.change_payer("alice");
+
+        let action = CreateMarketReferralCodeAction::new(
+            &testing_env,
+            CODE,
+            MAX_REFERRAL_CODE_FEE_SHARE as u16 + 1,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeShare);
+    }
+
+    #[test]
+    fn create_market_referral_code_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let mut action = CreateMarketReferralCodeAction::new(&testing_env, CODE, CREATOR_FEE_SHARE);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
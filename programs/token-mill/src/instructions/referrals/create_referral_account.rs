@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{ReferralAccount, TokenMillConfig, REFERRAL_ACCOUNT_PDA_SEED};
+use crate::{
+    events::TokenMillReferralAccountCreationEvent, ReferralAccount, TokenMillConfig,
+    REFERRAL_ACCOUNT_PDA_SEED,
+};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(referrer: Pubkey)]
 pub struct CreateReferralAccount<'info> {
@@ -28,6 +32,12 @@ pub fn handler(ctx: Context<CreateReferralAccount>, referrer: Pubkey) -> Result<
 
     referral_account.initialize(ctx.bumps.referral_account, config.key(), referrer)?;
 
+    emit_cpi!(TokenMillReferralAccountCreationEvent {
+        config: config.key(),
+        referral_account: referral_account.key(),
+        referrer,
+    });
+
     Ok(())
 }
 
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillReferralCodeCreationEvent,
+    state::{ReferralAccount, ReferralCode, TokenMillConfig, REFERRAL_CODE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(code: [u8; 32])]
+pub struct CreateReferralCode<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = referrer @ TokenMillError::InvalidAuthority
+    )]
+    pub referral_account: Account<'info, ReferralAccount>,
+
+    #[account(
+        init,
+        seeds = [REFERRAL_CODE_PDA_SEED.as_bytes(), config.key().as_ref(), code.as_ref()],
+        bump,
+        payer = referrer,
+        space = 8 + ReferralCode::INIT_SPACE
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// First come, first served, a code can't be registered twice within a config.
+pub fn handler(ctx: Context<CreateReferralCode>, code: [u8; 32]) -> Result<()> {
+    let config = ctx.accounts.config.key();
+    let referrer = ctx.accounts.referrer.key();
+
+    ctx.accounts.referral_code.initialize(
+        ctx.bumps.referral_code,
+        config,
+        code,
+        referrer,
+        ctx.accounts.referral_account.key(),
+    )?;
+
+    emit_cpi!(TokenMillReferralCodeCreationEvent {
+        config,
+        referral_code: ctx.accounts.referral_code.key(),
+        code,
+        referrer,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReferralCode;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateReferralAccountAction, CreateReferralCodeAction, TokenMillEnv,
+        },
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn create_referral_code() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("carol");
+
+        let action = CreateReferralCodeAction::new("carol");
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let referral_code = testing_env
+            .svm
+            .get_parsed_account::<ReferralCode>(&action.referral_code);
+
+        assert_eq!(referral_code.config, testing_env.config);
+        assert_eq!(referral_code.code, action.code);
+        assert_eq!(referral_code.referrer, make_address("carol"));
+        assert_eq!(
+            referral_code.referral_account,
+            CreateReferralAccountAction::new().referral_account
+        );
+    }
+
+    #[test]
+    fn create_referral_code_twice() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("carol");
+
+        let action = CreateReferralCodeAction::new("carol");
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        testing_env.svm.warp(1);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn create_invalid_referral_code() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("carol");
+
+        for code in ["", "Carol", "carol!", "car ol"] {
+            let action = CreateReferralCodeAction::new(code);
+
+            let result = testing_env.svm.execute_actions(&[&action]);
+
+            assert!(result.is_err());
+
+            let error = tm_parse_error(result).unwrap();
+
+            assert_eq!(error, TokenMillError::InvalidReferralCode);
+        }
+    }
+
+    #[test]
+    fn create_referral_code_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::new();
+
+        let mut action = CreateReferralCodeAction::new("carol");
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
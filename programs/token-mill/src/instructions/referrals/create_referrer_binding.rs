@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{ReferrerBinding, TokenMillConfig, REFERRER_BINDING_PDA_SEED};
+
+#[derive(Accounts)]
+pub struct CreateReferrerBinding<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [REFERRER_BINDING_PDA_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + ReferrerBinding::INIT_SPACE
+    )]
+    pub referrer_binding: Account<'info, ReferrerBinding>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Created unbound, the referrer is bound by the next referred swap of the user, see `swap`.
+pub fn handler(ctx: Context<CreateReferrerBinding>) -> Result<()> {
+    ctx.accounts.referrer_binding.initialize(
+        ctx.bumps.referrer_binding,
+        ctx.accounts.config.key(),
+        ctx.accounts.user.key(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ReferrerBinding;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{CreateReferrerBindingAction, TokenMillEnv},
+        make_address,
+    };
+
+    #[test]
+    fn create_referrer_binding() {
+        let mut testing_env = TokenMillEnv::new();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = CreateReferrerBindingAction::new();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let referrer_binding = testing_env
+            .svm
+            .get_parsed_account::<ReferrerBinding>(&action.referrer_binding);
+
+        assert_eq!(referrer_binding.config, action.config);
+        assert_eq!(referrer_binding.trader, make_address("bob"));
+        assert!(!referrer_binding.is_bound());
+
+        // A trader has a single binding per config
+        testing_env.svm.warp(1);
+
+        assert!(testing_env.svm.execute_actions(&[&action]).is_err());
+    }
+}
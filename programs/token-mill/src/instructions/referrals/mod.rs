@@ -1,5 +1,17 @@
+pub mod claim_market_referral_fees;
 pub mod claim_referral_fees;
+pub mod close_market_referral_code;
+pub mod create_market_referral_accrual;
+pub mod create_market_referral_code;
 pub mod create_referral_account;
+pub mod create_referral_code;
+pub mod create_referrer_binding;
 
+pub use claim_market_referral_fees::*;
 pub use claim_referral_fees::*;
+pub use close_market_referral_code::*;
+pub use create_market_referral_accrual::*;
+pub use create_market_referral_code::*;
 pub use create_referral_account::*;
+pub use create_referral_code::*;
+pub use create_referrer_binding::*;
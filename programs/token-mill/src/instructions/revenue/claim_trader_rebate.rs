@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillTraderRebateClaimEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{RevenueReport, TraderVolume, REVENUE_REPORT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimTraderRebate<'info> {
+    #[account(
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount,
+        constraint = revenue_report.finalized @ TokenMillError::EpochNotOver
+    )]
+    pub revenue_report: Account<'info, RevenueReport>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = revenue_report @ TokenMillError::InvalidEpoch,
+        has_one = owner @ TokenMillError::InvalidAuthority
+    )]
+    pub trader_volume: Account<'info, TraderVolume>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = revenue_report,
+        associated_token::token_program = quote_token_program
+    )]
+    pub revenue_report_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut, token::mint = quote_token_mint)]
+    pub owner_quote_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Pays the trader its share of the fees set aside during the epoch, and closes its volume.
+pub fn handler(ctx: Context<ClaimTraderRebate>) -> Result<()> {
+    let revenue_report = &ctx.accounts.revenue_report;
+    let trader_volume = &ctx.accounts.trader_volume;
+
+    let rebate = revenue_report.get_trader_rebate(trader_volume)?;
+
+    require!(rebate > 0, TokenMillError::RebateThresholdNotMet);
+
+    let epoch_bytes = revenue_report.epoch.to_le_bytes();
+    let revenue_report_seeds = [
+        REVENUE_REPORT_PDA_SEED.as_bytes(),
+        revenue_report.config.as_ref(),
+        revenue_report.quote_token_mint.as_ref(),
+        epoch_bytes.as_ref(),
+        &[revenue_report.bump],
+    ];
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        revenue_report.to_account_info(),
+        &ctx.accounts.revenue_report_quote_token_ata,
+        &ctx.accounts.owner_quote_token_account,
+        &ctx.accounts.quote_token_program,
+        rebate,
+        &revenue_report_seeds,
+    )?;
+
+    emit_cpi!(TokenMillTraderRebateClaimEvent {
+        revenue_report: revenue_report.key(),
+        owner: trader_volume.owner,
+        epoch: revenue_report.epoch,
+        volume: trader_volume.volume,
+        rebate,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::REVENUE_EPOCH_DURATION, RevenueReport, TraderRebateParams};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimTraderRebateAction, CreateRevenueReportAction,
+            CreateTraderVolumeAction, FinalizeRevenueReportAction, SwapAction, TokenMillEnv,
+            UpdateTraderRebateParamsAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env(volume_threshold: u64) -> (TokenMillEnv, ClaimTraderRebateAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateTraderRebateParamsAction::new(TraderRebateParams {
+                volume_threshold,
+                protocol_fee_share: 5_000,
+            })])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let create_report_action = CreateRevenueReportAction::new(&testing_env);
+        let create_volume_action =
+            CreateTraderVolumeAction::new(&testing_env, create_report_action.revenue_report);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_report_action, &create_volume_action])
+            .unwrap();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        )
+        .with_revenue_report(create_report_action.revenue_report)
+        .with_trader_volume(
+            create_volume_action.trader_volume,
+            create_volume_action.revenue_report_quote_token_ata,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+
+        testing_env
+            .svm
+            .execute_actions(&[&FinalizeRevenueReportAction::new(
+                create_report_action.revenue_report,
+            )])
+            .unwrap();
+
+        let action = ClaimTraderRebateAction::new(&testing_env, &create_volume_action);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn claim_trader_rebate() {
+        let (mut testing_env, action) = setup_env(0);
+
+        let revenue_report = testing_env
+            .svm
+            .get_parsed_account::<RevenueReport>(&action.revenue_report);
+
+        assert!(revenue_report.trader_rebate_fees > 0);
+
+        let bob_balance_before = testing_env
+            .svm
+            .get_balance(&action.quote_token_mint, &make_address("bob"));
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        // Sole trader of the epoch, so the whole set aside amount is rebated
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &make_address("bob")),
+            bob_balance_before + revenue_report.trader_rebate_fees
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.quote_token_mint, &action.revenue_report),
+            0
+        );
+        assert!(!testing_env.svm.account_exists(&action.trader_volume));
+    }
+
+    #[test]
+    fn claim_trader_rebate_below_threshold() {
+        let (mut testing_env, action) = setup_env(u64::MAX);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::RebateThresholdNotMet);
+    }
+
+    #[test]
+    fn claim_trader_rebate_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env(0);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
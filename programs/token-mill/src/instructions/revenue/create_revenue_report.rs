@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillRevenueReportCreationEvent,
+    state::{RevenueReport, TokenMillConfig, REVENUE_REPORT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct CreateRevenueReport<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        seeds = [
+            REVENUE_REPORT_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            quote_token_mint.key().as_ref(),
+            epoch.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + RevenueReport::INIT_SPACE
+    )]
+    pub revenue_report: Account<'info, RevenueReport>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, so the report of an epoch can be opened ahead of its first swap.
+pub fn handler(ctx: Context<CreateRevenueReport>, epoch: u64) -> Result<()> {
+    let current_epoch = RevenueReport::get_epoch(get_clock()?.unix_timestamp);
+
+    require!(epoch >= current_epoch, TokenMillError::InvalidEpoch);
+
+    ctx.accounts.revenue_report.initialize(
+        ctx.bumps.revenue_report,
+        ctx.accounts.config.key(),
+        ctx.accounts.quote_token_mint.key(),
+        epoch,
+        ctx.accounts.config.trader_rebate_params,
+    )?;
+
+    emit_cpi!(TokenMillRevenueReportCreationEvent {
+        config: ctx.accounts.config.key(),
+        revenue_report: ctx.accounts.revenue_report.key(),
+        quote_token_mint: ctx.accounts.quote_token_mint.key(),
+        epoch,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::REVENUE_EPOCH_DURATION, RevenueReport};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, CreateRevenueReportAction, TokenMillEnv},
+        TokenMillError,
+    };
+
+    #[test]
+    fn create_revenue_report() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = CreateRevenueReportAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let revenue_report = testing_env
+            .svm
+            .get_parsed_account::<RevenueReport>(&action.revenue_report);
+
+        assert_eq!(revenue_report.config, action.config);
+        assert_eq!(revenue_report.quote_token_mint, action.quote_token_mint);
+        assert_eq!(revenue_report.epoch, action.epoch);
+        assert!(!revenue_report.finalized);
+    }
+
+    #[test]
+    fn create_revenue_report_for_past_epoch() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = CreateRevenueReportAction::new(&testing_env);
+
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidEpoch);
+    }
+}
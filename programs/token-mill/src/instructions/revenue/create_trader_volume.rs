@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillTraderVolumeCreationEvent,
+    state::{RevenueReport, TraderVolume, TRADER_VOLUME_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateTraderVolume<'info> {
+    #[account(has_one = quote_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub revenue_report: Account<'info, RevenueReport>,
+
+    #[account(
+        init,
+        seeds = [
+            TRADER_VOLUME_PDA_SEED.as_bytes(),
+            revenue_report.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + TraderVolume::INIT_SPACE
+    )]
+    pub trader_volume: Account<'info, TraderVolume>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = revenue_report,
+        associated_token::token_program = quote_token_program
+    )]
+    pub revenue_report_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Owner of the trader volume
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Permissionless, opens the volume of `owner` for the epoch of the revenue report, along with
+/// the vault of the report holding the fees set aside for rebates.
+pub fn handler(ctx: Context<CreateTraderVolume>) -> Result<()> {
+    require!(
+        !ctx.accounts.revenue_report.finalized,
+        TokenMillError::InvalidEpoch
+    );
+
+    ctx.accounts.trader_volume.initialize(
+        ctx.bumps.trader_volume,
+        ctx.accounts.revenue_report.key(),
+        ctx.accounts.owner.key(),
+    )?;
+
+    emit_cpi!(TokenMillTraderVolumeCreationEvent {
+        revenue_report: ctx.accounts.revenue_report.key(),
+        trader_volume: ctx.accounts.trader_volume.key(),
+        owner: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    clock::get_clock, events::TokenMillRevenueReportFinalizationEvent, state::RevenueReport,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeRevenueReport<'info> {
+    #[account(mut)]
+    pub revenue_report: Account<'info, RevenueReport>,
+}
+
+/// Crank closing the report of an elapsed epoch. Succeeds without changes if already finalized.
+pub fn handler(ctx: Context<FinalizeRevenueReport>) -> Result<()> {
+    let revenue_report = &mut ctx.accounts.revenue_report;
+
+    if revenue_report.finalized {
+        return Ok(());
+    }
+
+    revenue_report.finalize(RevenueReport::get_epoch(get_clock()?.unix_timestamp))?;
+
+    emit_cpi!(TokenMillRevenueReportFinalizationEvent {
+        config: revenue_report.config,
+        quote_token_mint: revenue_report.quote_token_mint,
+        epoch: revenue_report.epoch,
+        protocol_fees: revenue_report.protocol_fees,
+        creator_fees: revenue_report.creator_fees,
+        staking_fees: revenue_report.staking_fees,
+        referral_fees: revenue_report.referral_fees,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{constant::REVENUE_EPOCH_DURATION, RevenueReport};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateRevenueReportAction, FinalizeRevenueReportAction, SwapAction,
+            TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, FinalizeRevenueReportAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        let create_action = CreateRevenueReportAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        )
+        .with_revenue_report(create_action.revenue_report);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let action = FinalizeRevenueReportAction::new(create_action.revenue_report);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn finalize_revenue_report() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let revenue_report = testing_env
+            .svm
+            .get_parsed_account::<RevenueReport>(&action.revenue_report);
+
+        assert!(revenue_report.finalized);
+        assert!(revenue_report.protocol_fees > 0);
+        assert!(revenue_report.creator_fees > 0);
+        assert!(revenue_report.staking_fees > 0);
+        assert!(revenue_report.referral_fees > 0);
+
+        // Cranking again is a no-op
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn finalize_revenue_report_before_epoch_end() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::EpochNotOver);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod claim_trader_rebate;
+pub mod create_revenue_report;
+pub mod create_trader_volume;
+pub mod finalize_revenue_report;
+
+pub use claim_trader_rebate::*;
+pub use create_revenue_report::*;
+pub use create_trader_volume::*;
+pub use finalize_revenue_report::*;
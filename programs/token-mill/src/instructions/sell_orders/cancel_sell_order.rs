@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillSellOrderCancellationEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Market, SellOrder, SELL_ORDER_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelSellOrder<'info> {
+    #[account(has_one = base_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [SELL_ORDER_PDA_SEED.as_bytes(), market.key().as_ref(), sell_order.owner.as_ref()],
+        bump = sell_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = owner @ TokenMillError::InvalidAuthority
+    )]
+    pub sell_order: Account<'info, SellOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = sell_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub sell_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = base_token_mint)]
+    pub owner_base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+}
+
+/// Closes the sell order, sending the unsold base tokens of its vault back to the owner.
+pub fn handler(ctx: Context<CancelSellOrder>) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    let owner_key = ctx.accounts.owner.key();
+    let sell_order_seeds = [
+        SELL_ORDER_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        owner_key.as_ref(),
+        &[ctx.accounts.sell_order.bump],
+    ];
+
+    let base_amount = ctx.accounts.sell_order_base_token_ata.amount;
+
+    if base_amount > 0 {
+        transfer_from_pda(
+            &ctx.accounts.base_token_mint,
+            ctx.accounts.sell_order.to_account_info(),
+            &ctx.accounts.sell_order_base_token_ata,
+            &ctx.accounts.owner_base_token_account,
+            &ctx.accounts.base_token_program,
+            base_amount,
+            &sell_order_seeds,
+        )?;
+    }
+
+    emit_cpi!(TokenMillSellOrderCancellationEvent {
+        market: market_key,
+        sell_order: ctx.accounts.sell_order.key(),
+        owner: owner_key,
+        base_amount_withdrawn: base_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            CancelSellOrderAction, SplitSellAction, SwapAction, TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType,
+    };
+
+    const SELL_AMOUNT: u64 = DEFAULT_TOTAL_SUPPLY / 10;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        let split_sell_action = SplitSellAction::new(&testing_env, SELL_AMOUNT, u64::MAX);
+
+        testing_env
+            .svm
+            .execute_actions(&[&swap_action, &split_sell_action])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn cancel_sell_order() {
+        let mut testing_env = setup_env();
+
+        let action = CancelSellOrderAction::new(&testing_env);
+
+        let bob = make_address("bob");
+        let base_balance_before = testing_env.svm.get_balance(&action.base_token_mint, &bob);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            testing_env.svm.get_balance(&action.base_token_mint, &bob) - base_balance_before,
+            SELL_AMOUNT
+        );
+        assert!(!testing_env.svm.account_exists(&action.sell_order));
+    }
+
+    #[test]
+    fn cancel_sell_order_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = CancelSellOrderAction::new(&testing_env);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
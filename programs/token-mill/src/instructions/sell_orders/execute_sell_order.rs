@@ -0,0 +1,380 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillSellOrderExecutionEvent,
+    manager::{
+        policy_manager::ValidateSwapArgs,
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::transfer_from_pda,
+    },
+    state::{
+        Denylist, Market, MarketOracle, MarketStats, SellOrder, TokenMillConfig,
+        SELL_ORDER_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteSellOrder<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [SELL_ORDER_PDA_SEED.as_bytes(), market.key().as_ref(), sell_order.owner.as_ref()],
+        bump = sell_order.bump,
+        has_one = market @ TokenMillError::InvalidMarket
+    )]
+    pub sell_order: Account<'info, SellOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = sell_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub sell_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = sell_order.owner,
+        associated_token::token_program = quote_token_program
+    )]
+    pub owner_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the sell once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Records the price of the market before the sell when provided, required by dynamic fee
+    /// markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the sell into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+}
+
+/// Permissionless crank selling up to `base_amount` of the order vault, as long as the bid price
+/// stays at or above the limit price of the order. The proceeds go to the owner of the order, who
+/// is checked and reported as the trader.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteSellOrder<'info>>,
+    base_amount: u64,
+) -> Result<()> {
+    require!(base_amount > 0, TokenMillError::InvalidAmount);
+
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let sell_order = &mut ctx.accounts.sell_order;
+    let owner = sell_order.owner;
+
+    swap_accounts.check_trader(&owner)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    let (outcome, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused(get_clock()?.slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+
+        let max_base_amount = swap_accounts.before_swap(market, SwapType::Sell)?;
+
+        let mut base_amount = market
+            .get_base_amount_to_bid_price(sell_order.limit_price)?
+            .min(base_amount)
+            .min(ctx.accounts.sell_order_base_token_ata.amount);
+
+        if let Some(max_base_amount) = max_base_amount {
+            base_amount = base_amount.min(max_base_amount);
+        }
+
+        require!(base_amount > 0, TokenMillError::LimitPriceNotReached);
+
+        let outcome = swap_manager::swap(
+            market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            base_amount,
+            None,
+            get_clock()?.slot,
+            get_clock()?.unix_timestamp,
+        )?;
+
+        swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+        (outcome, market.seeds())
+    };
+
+    let SwapOutcome {
+        base_amount,
+        quote_amount,
+        ..
+    } = outcome;
+
+    sell_order.record(base_amount, quote_amount);
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        user: owner,
+        swap_type: SwapType::Sell,
+        swap_amount_type: SwapAmountType::ExactInput,
+        base_amount,
+        quote_amount,
+    })?;
+
+    let market_key = ctx.accounts.market.key();
+    let sell_order_seeds = [
+        SELL_ORDER_PDA_SEED.as_bytes(),
+        market_key.as_ref(),
+        sell_order.owner.as_ref(),
+        &[sell_order.bump],
+    ];
+
+    transfer_from_pda(
+        &ctx.accounts.base_token_mint,
+        sell_order.to_account_info(),
+        &ctx.accounts.sell_order_base_token_ata,
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.base_token_program,
+        base_amount,
+        &sell_order_seeds,
+    )?;
+
+    let seeds = market_seeds.signer_seeds();
+
+    transfer_from_pda(
+        &ctx.accounts.quote_token_mint,
+        ctx.accounts.market.to_account_info(),
+        &ctx.accounts.market_quote_token_ata,
+        &ctx.accounts.owner_quote_token_ata,
+        &ctx.accounts.quote_token_program,
+        quote_amount,
+        &seeds,
+    )?;
+
+    swap_accounts.settle(
+        revenue_report.as_mut(),
+        owner,
+        SwapType::Sell,
+        &outcome,
+        0,
+        &seeds,
+    )?;
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillSellOrderExecutionEvent {
+        market: market_key,
+        sell_order: ctx.accounts.sell_order.key(),
+        owner: ctx.accounts.sell_order.owner,
+        base_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, SellOrder};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ExecuteSellOrderAction, SplitSellAction, SwapAction, TokenMillEnv,
+            DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    fn setup_env() -> (TokenMillEnv, SplitSellAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let bid_price = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_bid_price()
+            .unwrap();
+
+        let split_sell_action =
+            SplitSellAction::new(&testing_env, DEFAULT_TOTAL_SUPPLY / 10, bid_price * 9 / 10);
+
+        testing_env
+            .svm
+            .execute_actions(&[&split_sell_action])
+            .unwrap();
+
+        (testing_env, split_sell_action)
+    }
+
+    #[test]
+    fn execute_sell_order() {
+        let (mut testing_env, split_sell_action) = setup_env();
+
+        let alice = testing_env.svm.change_payer("alice");
+
+        let buy_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 20,
+            u64::MAX,
+            None,
+        )
+        .with_signer(alice);
+
+        testing_env.svm.execute_actions(&[&buy_action]).unwrap();
+
+        let bob = make_address("bob");
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&split_sell_action.quote_token_mint, &bob);
+        let vault_balance_before = testing_env.svm.get_balance(
+            &split_sell_action.base_token_mint,
+            &split_sell_action.sell_order,
+        );
+
+        let action = ExecuteSellOrderAction::new(&testing_env, u64::MAX);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let sell_order = testing_env
+            .svm
+            .get_parsed_account::<SellOrder>(&action.sell_order);
+
+        assert!(sell_order.total_base_amount_sold > 0);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &action.sell_order),
+            vault_balance_before - sell_order.total_base_amount_sold
+        );
+        assert_eq!(
+            testing_env.svm.get_balance(&action.quote_token_mint, &bob) - quote_balance_before,
+            sell_order.total_quote_amount_received
+        );
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.get_bid_price().unwrap() >= sell_order.limit_price);
+    }
+
+    #[test]
+    fn execute_sell_order_below_limit_price() {
+        let (mut testing_env, _) = setup_env();
+
+        let action = ExecuteSellOrderAction::new(&testing_env, u64::MAX);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::LimitPriceNotReached);
+    }
+
+    #[test]
+    fn execute_sell_order_with_zero_amount() {
+        let (mut testing_env, _) = setup_env();
+
+        let action = ExecuteSellOrderAction::new(&testing_env, 0);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
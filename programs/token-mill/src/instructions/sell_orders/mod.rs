@@ -0,0 +1,7 @@
+pub mod cancel_sell_order;
+pub mod execute_sell_order;
+pub mod split_sell;
+
+pub use cancel_sell_order::*;
+pub use execute_sell_order::*;
+pub use split_sell::*;
@@ -0,0 +1,426 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillSplitSellEvent,
+    manager::{
+        policy_manager::ValidateSwapArgs,
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::{transfer_from_eoa, transfer_from_pda},
+    },
+    state::{
+        Denylist, Market, MarketOracle, MarketStats, SellOrder, TokenMillConfig,
+        SELL_ORDER_PDA_SEED,
+    },
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SplitSell<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + SellOrder::INIT_SPACE,
+        seeds = [SELL_ORDER_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub sell_order: Account<'info, SellOrder>,
+
+    pub base_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub quote_token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = sell_order,
+        associated_token::token_program = base_token_program
+    )]
+    pub sell_order_base_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = base_token_mint)]
+    pub user_base_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, token::mint = quote_token_mint)]
+    pub user_quote_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = config.protocol_fee_recipient,
+        associated_token::token_program = quote_token_program
+    )]
+    pub protocol_quote_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the sell once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Records the price of the market before the sell when provided, required by dynamic fee
+    /// markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the sell into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+}
+
+/// Sells `base_amount` down to `limit_price` right away, and rests the remainder in the sell order
+/// of the user instead of selling it at a worse bid price. Reuses the existing order of the user,
+/// whose limit price is updated.
+/// The whole amount is validated by the policy program, as the resting part sells without the user.
+/// The fees of the part sold are settled as for any swap.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SplitSell<'info>>,
+    base_amount: u64,
+    limit_price: u64,
+    min_quote_amount_out: u64,
+) -> Result<()> {
+    require!(
+        base_amount > 0 && limit_price > 0,
+        TokenMillError::InvalidAmount
+    );
+
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint: &ctx.accounts.quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: ctx.remaining_accounts,
+    };
+
+    let user = ctx.accounts.user.key();
+
+    swap_accounts.check_trader(&user)?;
+
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
+
+    let sell_order = &mut ctx.accounts.sell_order;
+
+    if sell_order.market == Pubkey::default() {
+        sell_order.initialize(ctx.bumps.sell_order, ctx.accounts.market.key(), user)?;
+    }
+
+    sell_order.limit_price = limit_price;
+
+    let (outcome, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused(get_clock()?.slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+
+        let max_base_amount = swap_accounts.before_swap(market, SwapType::Sell)?;
+
+        let mut base_amount_sold = market
+            .get_base_amount_to_bid_price(limit_price)?
+            .min(base_amount);
+
+        if let Some(max_base_amount) = max_base_amount {
+            base_amount_sold = base_amount_sold.min(max_base_amount);
+        }
+
+        let outcome = if base_amount_sold > 0 {
+            let outcome = swap_manager::swap(
+                market,
+                SwapType::Sell,
+                SwapAmountType::ExactInput,
+                base_amount_sold,
+                None,
+                get_clock()?.slot,
+                get_clock()?.unix_timestamp,
+            )?;
+
+            swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+            outcome
+        } else {
+            SwapOutcome::default()
+        };
+
+        (outcome, market.seeds())
+    };
+
+    let SwapOutcome {
+        base_amount: base_amount_sold,
+        quote_amount,
+        ..
+    } = outcome;
+
+    require_gte!(
+        quote_amount,
+        min_quote_amount_out,
+        TokenMillError::AmountThresholdNotMet
+    );
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        user,
+        swap_type: SwapType::Sell,
+        swap_amount_type: SwapAmountType::ExactInput,
+        base_amount,
+        quote_amount,
+    })?;
+
+    let base_amount_resting = base_amount - base_amount_sold;
+
+    if base_amount_sold > 0 {
+        let seeds = market_seeds.signer_seeds();
+
+        transfer_from_eoa(
+            &ctx.accounts.base_token_mint,
+            &ctx.accounts.user,
+            &ctx.accounts.user_base_token_account,
+            &ctx.accounts.market_base_token_ata,
+            &ctx.accounts.base_token_program,
+            base_amount_sold,
+        )?;
+
+        transfer_from_pda(
+            &ctx.accounts.quote_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.user_quote_token_account,
+            &ctx.accounts.quote_token_program,
+            quote_amount,
+            &seeds,
+        )?;
+
+        swap_accounts.settle(
+            revenue_report.as_mut(),
+            user,
+            SwapType::Sell,
+            &outcome,
+            0,
+            &seeds,
+        )?;
+    }
+
+    if base_amount_resting > 0 {
+        transfer_from_eoa(
+            &ctx.accounts.base_token_mint,
+            &ctx.accounts.user,
+            &ctx.accounts.user_base_token_account,
+            &ctx.accounts.sell_order_base_token_ata,
+            &ctx.accounts.base_token_program,
+            base_amount_resting,
+        )?;
+    }
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillSplitSellEvent {
+        user,
+        market: ctx.accounts.market.key(),
+        sell_order: ctx.accounts.sell_order.key(),
+        limit_price,
+        base_amount_sold,
+        quote_amount,
+        base_amount_resting,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, SellOrder};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, SplitSellAction, SwapAction, TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const SELL_AMOUNT: u64 = DEFAULT_TOTAL_SUPPLY / 10;
+
+    fn setup_env() -> (TokenMillEnv, SplitSellAction) {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            DEFAULT_TOTAL_SUPPLY / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let bid_price = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market)
+            .get_bid_price()
+            .unwrap();
+
+        let action = SplitSellAction::new(&testing_env, SELL_AMOUNT, bid_price * 9 / 10);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn split_sell() {
+        let (mut testing_env, action) = setup_env();
+
+        let bob = make_address("bob");
+        let base_balance_before = testing_env.svm.get_balance(&action.base_token_mint, &bob);
+        let quote_balance_before = testing_env.svm.get_balance(&action.quote_token_mint, &bob);
+
+        let base_amount_sold = testing_env
+            .svm
+            .get_parsed_account::<Market>(&action.market)
+            .get_base_amount_to_bid_price(action.limit_price)
+            .unwrap();
+
+        assert!(base_amount_sold > 0 && base_amount_sold < SELL_AMOUNT);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            base_balance_before - testing_env.svm.get_balance(&action.base_token_mint, &bob),
+            SELL_AMOUNT
+        );
+        assert!(testing_env.svm.get_balance(&action.quote_token_mint, &bob) > quote_balance_before);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &action.sell_order),
+            SELL_AMOUNT - base_amount_sold
+        );
+
+        let sell_order = testing_env
+            .svm
+            .get_parsed_account::<SellOrder>(&action.sell_order);
+
+        assert_eq!(sell_order.market, action.market);
+        assert_eq!(sell_order.owner, bob);
+        assert_eq!(sell_order.limit_price, action.limit_price);
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert!(market.get_bid_price().unwrap() >= action.limit_price);
+    }
+
+    #[test]
+    fn split_sell_above_bid_price() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.limit_price = u64::MAX;
+
+        let market_before = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let market = testing_env.svm.get_parsed_account::<Market>(&action.market);
+
+        assert_eq!(market.base_reserve, market_before.base_reserve);
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&action.base_token_mint, &action.sell_order),
+            SELL_AMOUNT
+        );
+    }
+
+    #[test]
+    fn split_sell_with_threshold_not_met() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.min_quote_amount_out = u64::MAX;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::AmountThresholdNotMet);
+    }
+
+    #[test]
+    fn split_sell_with_zero_limit_price() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.limit_price = 0;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
@@ -0,0 +1,227 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillStakeBackfillEvent,
+    state::{MarketStaking, StakePosition},
+};
+
+/// Accounts created before reward shares are too short to be deserialized with their current
+/// layout, so they are grown before being loaded.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BackfillStakePosition<'info> {
+    /// CHECK: Loaded as `MarketStaking` once grown, checking its discriminator
+    #[account(mut, owner = crate::ID)]
+    pub market_staking: UncheckedAccount<'info>,
+
+    /// CHECK: Loaded as `StakePosition` once grown, checking its discriminator
+    #[account(mut, owner = crate::ID)]
+    pub stake_position: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless, grows a staking and one of its positions to the current layout and counts
+/// their stake in the reward shares, see `MarketStaking::backfill`. The staking is counted along
+/// its first position, its other positions only need their own shares.
+pub fn handler(ctx: Context<BackfillStakePosition>) -> Result<()> {
+    grow(
+        &ctx.accounts.market_staking,
+        8 + MarketStaking::INIT_SPACE,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+    grow(
+        &ctx.accounts.stake_position,
+        8 + StakePosition::INIT_SPACE,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    let mut staking =
+        MarketStaking::try_deserialize(&mut &ctx.accounts.market_staking.data.borrow()[..])?;
+    let mut stake_position =
+        StakePosition::try_deserialize(&mut &ctx.accounts.stake_position.data.borrow()[..])?;
+
+    require_keys_eq!(
+        stake_position.market,
+        staking.market,
+        TokenMillError::InvalidMarket
+    );
+
+    let staking_backfilled = staking.backfill();
+    let stake_position_backfilled = stake_position.backfill();
+
+    require!(
+        staking_backfilled || stake_position_backfilled,
+        TokenMillError::StakingUpToDate
+    );
+
+    staking.try_serialize(&mut &mut ctx.accounts.market_staking.data.borrow_mut()[..])?;
+    stake_position.try_serialize(&mut &mut ctx.accounts.stake_position.data.borrow_mut()[..])?;
+
+    emit_cpi!(TokenMillStakeBackfillEvent {
+        market: staking.market,
+        stake_position: ctx.accounts.stake_position.key(),
+        shares: stake_position.shares,
+        total_shares: staking.total_shares,
+    });
+
+    Ok(())
+}
+
+/// Reallocates `account` to `space` bytes, zeroing the new ones, the payer topping up its rent.
+pub(crate) fn grow<'info>(
+    account: &AccountInfo<'info>,
+    space: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let rent = Rent::get()?.minimum_balance(space);
+
+    if rent > account.lamports() {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent - account.lamports(),
+        )?;
+    }
+
+    account.realloc(space, true)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor_lang::Space;
+
+    use crate::{MarketStaking, StakePosition};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, BackfillStakePositionAction, DepositAction, TokenMillEnv,
+            WithdrawAction,
+        },
+        TokenMillError,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    /// Sizes of the accounts of the first release, whose fields are a prefix of the current ones.
+    const BASELINE_MARKET_STAKING_SIZE: usize = 8 + 64;
+    const BASELINE_STAKE_POSITION_SIZE: usize = 8 + 104;
+
+    fn setup_legacy_staking() -> (TokenMillEnv, BackfillStakePositionAction) {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositAction::new(&testing_env, STAKE_AMOUNT)])
+            .unwrap();
+
+        let action = BackfillStakePositionAction::new(&testing_env);
+
+        for (address, size) in [
+            (action.market_staking, BASELINE_MARKET_STAKING_SIZE),
+            (action.stake_position, BASELINE_STAKE_POSITION_SIZE),
+        ] {
+            let mut account = testing_env.svm.get_account(&address);
+            account.data.truncate(size);
+
+            testing_env.svm.set_account(&address, account);
+        }
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn backfill_stake_position() {
+        let (mut testing_env, action) = setup_legacy_staking();
+
+        // Legacy accounts can't be loaded until backfilled
+        let withdraw_action = WithdrawAction::new(&testing_env, STAKE_AMOUNT);
+
+        assert!(testing_env
+            .svm
+            .execute_actions(&[&withdraw_action])
+            .is_err());
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert_eq!(
+            testing_env
+                .svm
+                .get_account(&action.market_staking)
+                .data
+                .len(),
+            8 + MarketStaking::INIT_SPACE
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_account(&action.stake_position)
+                .data
+                .len(),
+            8 + StakePosition::INIT_SPACE
+        );
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+
+        assert_eq!(staking.total_shares, STAKE_AMOUNT);
+        assert_eq!(stake_position.amount_staked, STAKE_AMOUNT);
+        assert_eq!(stake_position.shares, STAKE_AMOUNT);
+        assert_eq!(stake_position.lock_tier, 0);
+
+        testing_env
+            .svm
+            .execute_actions(&[&withdraw_action])
+            .unwrap();
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+
+        assert_eq!(staking.total_shares, 0);
+    }
+
+    #[test]
+    fn backfill_up_to_date_stake_position() {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositAction::new(&testing_env, STAKE_AMOUNT)])
+            .unwrap();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&BackfillStakePositionAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::StakingUpToDate);
+    }
+}
@@ -1,9 +1,9 @@
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
     events::TokenMillStakingRewardsClaimEvent,
     manager::{staking_manager, token_manager::transfer_from_pda},
-    state::{Market, MarketStaking, StakePosition},
-    MARKET_PDA_SEED,
+    state::{ClaimDelegate, Denylist, Market, MarketStaking, StakePosition, TokenMillConfig},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -11,7 +11,11 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 #[event_cpi]
 #[derive(Accounts)]
 pub struct StakingRewardsClaim<'info> {
-    #[account(mut, has_one = quote_token_mint @ TokenMillError::InvalidMintAccount)]
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
     pub market: AccountLoader<'info, Market>,
 
     #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
@@ -42,36 +46,67 @@ pub struct StakingRewardsClaim<'info> {
     )]
     pub user_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub user: Signer<'info>,
+    /// CHECK: Owner of the stake position, signs unless the claim is triggered by its delegate
+    pub user: UncheckedAccount<'info>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
+
+    #[account(has_one = stake_position @ TokenMillError::InvalidStakePosition)]
+    pub claim_delegate: Option<Account<'info, ClaimDelegate>>,
+
+    pub delegate: Option<Signer<'info>>,
+
+    pub config: Account<'info, TokenMillConfig>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
 }
 
 pub fn handler(ctx: Context<StakingRewardsClaim>) -> Result<u64> {
+    ClaimDelegate::check_claim_authority(
+        &ctx.accounts.user,
+        ctx.accounts.claim_delegate.as_ref(),
+        ctx.accounts.delegate.as_ref(),
+    )?;
+
+    Denylist::check(
+        &ctx.accounts.config,
+        ctx.accounts.denylist.as_ref(),
+        &ctx.accounts.user.key(),
+    )?;
+
     let pending_rewards;
-    let base_token_mint;
-    let market_bump;
+    let market_seeds;
 
     {
         let market = &mut ctx.accounts.market.load_mut()?;
         let staking = &mut ctx.accounts.staking;
         let stake_position = &mut ctx.accounts.stake_position;
 
-        staking_manager::deposit(market, staking, stake_position, 0)?;
+        market.lock()?;
+
+        let clock = get_clock()?;
+        market.apply_scheduled_updates(clock.slot, clock.unix_timestamp);
+
+        staking_manager::deposit(market, staking, stake_position, 0, clock.unix_timestamp)?;
 
         pending_rewards = stake_position.pending_rewards;
-        base_token_mint = market.base_token_mint;
-        market_bump = market.bump;
+        market_seeds = market.seeds();
+
+        require_gte!(
+            pending_rewards,
+            market.min_claim_amount,
+            TokenMillError::ClaimBelowMinimum
+        );
+
+        market.check_fee_withdrawal(ctx.accounts.market_quote_token_ata.amount, pending_rewards)?;
 
         stake_position.pending_rewards = 0;
     };
 
     let quote_token_mint = &ctx.accounts.quote_token_mint;
-    let seeds = [
-        MARKET_PDA_SEED.as_bytes(),
-        base_token_mint.as_ref(),
-        &[market_bump],
-    ];
+    let seeds = market_seeds.signer_seeds();
 
     transfer_from_pda(
         quote_token_mint,
@@ -83,6 +118,8 @@ pub fn handler(ctx: Context<StakingRewardsClaim>) -> Result<u64> {
         &seeds,
     )?;
 
+    ctx.accounts.market.load_mut()?.unlock();
+
     emit_cpi!(TokenMillStakingRewardsClaimEvent {
         market: ctx.accounts.market.key(),
         user: ctx.accounts.user.key(),
@@ -96,10 +133,10 @@ pub fn handler(ctx: Context<StakingRewardsClaim>) -> Result<u64> {
 mod tests {
     use joelana_test_utils::joelana_env::{
         actions::token_mill::{
-            ClaimStakingRewardsAction, DepositAction,
-            SwapAction,TokenMillEnv
+            tm_parse_error, ClaimStakingRewardsAction, DepositAction,
+            SetClaimDelegateAction, SwapAction,TokenMillEnv
         },
-        make_address, SwapAmountType, SwapType, 
+        make_address, SwapAmountType, SwapType, TokenMillError,
     };
 
     const STAKE_AMOUNT: u64 = 100_000_000;
@@ -137,4 +174,82 @@ mod tests {
 
         assert!(quote_balance_after > quote_balance_before);
     }
+
+    fn setup_env_with_delegate() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000_000 / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+                &swap_action,
+                &SetClaimDelegateAction::new(&testing_env, make_address("carol")),
+            ])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn claim_staking_rewards_with_delegate() {
+        let mut testing_env = setup_env_with_delegate();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("bob"));
+        let delegate_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("carol"));
+
+        let delegate = testing_env.svm.change_payer("carol");
+
+        let claim_rewards_action =
+            ClaimStakingRewardsAction::new(&testing_env).with_delegate(delegate);
+
+        testing_env
+            .svm
+            .execute_actions(&[&claim_rewards_action])
+            .unwrap();
+
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &make_address("bob"))
+                > quote_balance_before
+        );
+        assert_eq!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &make_address("carol")),
+            delegate_balance_before
+        );
+    }
+
+    #[test]
+    fn claim_staking_rewards_with_invalid_delegate() {
+        let mut testing_env = setup_env_with_delegate();
+
+        let delegate = testing_env.svm.change_payer("mallory");
+
+        let claim_rewards_action =
+            ClaimStakingRewardsAction::new(&testing_env).with_delegate(delegate);
+
+        let result = testing_env.svm.execute_actions(&[&claim_rewards_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
 }
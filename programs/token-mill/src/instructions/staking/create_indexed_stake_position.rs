@@ -0,0 +1,93 @@
+use crate::{
+    events::TokenMillStakePositionCreationEvent,
+    state::{Market, StakePosition},
+    STAKING_POSITION_PDA_SEED,
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(index: u32)]
+pub struct CreateIndexedStakePosition<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [
+            STAKING_POSITION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref(),
+            &index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates an additional stake position of the user in the market, next to the one seeded by the
+/// user only, to carve stake into with `split_position` and consolidate with `merge_positions`.
+pub fn handler(ctx: Context<CreateIndexedStakePosition>, _index: u32) -> Result<()> {
+    let stake_position = &mut ctx.accounts.stake_position;
+    let market = &ctx.accounts.market;
+    let user = &ctx.accounts.user;
+
+    stake_position.initialize(market.key(), user.key())?;
+
+    emit_cpi!(TokenMillStakePositionCreationEvent {
+        market: market.key(),
+        stake_position: stake_position.key(),
+        user: user.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            CreateIndexedStakePositionAction, CreateStakePositionAction, TokenMillEnv,
+        },
+        make_address,
+    };
+
+    use crate::StakePosition;
+
+    #[test]
+    fn create_indexed_stake_position() {
+        let mut testing_env = TokenMillEnv::default().with_staking(0);
+
+        let action = CreateIndexedStakePositionAction::new(&testing_env, 1);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+
+        assert_eq!(stake_position.user, make_address("bob"));
+        assert_ne!(
+            action.stake_position,
+            CreateStakePositionAction::new(&testing_env).stake_position
+        );
+
+        // One position per index
+        let result = testing_env
+            .svm
+            .execute_actions(&[&CreateIndexedStakePositionAction::new(&testing_env, 1)]);
+
+        assert!(result.is_err());
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateIndexedStakePositionAction::new(&testing_env, 2)])
+            .unwrap();
+    }
+}
@@ -1,9 +1,11 @@
 use crate::{
+    events::TokenMillStakePositionCreationEvent,
     state::{Market, StakePosition},
     STAKING_POSITION_PDA_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateStakePosition<'info> {
     pub market: AccountLoader<'info, Market>,
@@ -30,6 +32,12 @@ pub fn handler(ctx: Context<CreateStakePosition>) -> Result<()> {
 
     stake_position.initialize(market.key(), user.key())?;
 
+    emit_cpi!(TokenMillStakePositionCreationEvent {
+        market: market.key(),
+        stake_position: stake_position.key(),
+        user: user.key(),
+    });
+
     Ok(())
 }
 
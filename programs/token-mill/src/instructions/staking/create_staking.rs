@@ -1,9 +1,11 @@
 use crate::{
+    events::TokenMillStakingCreationEvent,
     state::{Market, MarketStaking},
     MARKET_STAKING_PDA_SEED,
 };
 use anchor_lang::prelude::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct CreateStaking<'info> {
     pub market: AccountLoader<'info, Market>,
@@ -29,6 +31,11 @@ pub fn handler(ctx: Context<CreateStaking>) -> Result<()> {
 
     staking.initialize(market.key())?;
 
+    emit_cpi!(TokenMillStakingCreationEvent {
+        market: market.key(),
+        staking: staking.key(),
+    });
+
     Ok(())
 }
 
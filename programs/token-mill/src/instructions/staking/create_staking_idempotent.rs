@@ -0,0 +1,91 @@
+use crate::{
+    events::TokenMillStakingCreationEvent,
+    state::{Market, MarketStaking},
+    MARKET_STAKING_PDA_SEED,
+};
+use anchor_lang::prelude::*;
+
+/// Bundle-friendly variant of `create_staking`: succeeds without changes if staking already exists.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateStakingIdempotent<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MarketStaking::INIT_SPACE,
+        seeds = [MARKET_STAKING_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump
+    )]
+    pub staking: Account<'info, MarketStaking>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateStakingIdempotent>) -> Result<()> {
+    let staking = &mut ctx.accounts.staking;
+
+    if staking.market != Pubkey::default() {
+        return Ok(());
+    }
+
+    staking.initialize(ctx.accounts.market.key())?;
+
+    emit_cpi!(TokenMillStakingCreationEvent {
+        market: ctx.accounts.market.key(),
+        staking: staking.key(),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::MarketStaking;
+    use joelana_test_utils::joelana_env::actions::token_mill::{
+        CreateStakingAction, CreateStakingIdempotentAction, TokenMillEnv,
+    };
+
+    #[test]
+    fn create_staking_idempotent() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        let action = CreateStakingIdempotentAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.staking);
+
+        assert_eq!(staking.market, action.market);
+    }
+
+    #[test]
+    fn create_staking_idempotent_when_already_created() {
+        let mut testing_env = TokenMillEnv::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateStakingAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let action = CreateStakingIdempotentAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action, &action]);
+
+        assert!(result.is_ok());
+    }
+}
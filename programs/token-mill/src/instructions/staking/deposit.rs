@@ -1,4 +1,5 @@
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
     events::TokenMillStakingDepositEvent,
     manager::{staking_manager, token_manager::transfer_from_eoa},
@@ -51,7 +52,16 @@ pub fn handler(ctx: Context<StakeUpdate>, amount: u64) -> Result<()> {
     let staking = &mut ctx.accounts.staking;
     let stake_position = &mut ctx.accounts.stake_position;
 
-    staking_manager::deposit(market, staking, stake_position, amount)?;
+    require!(market.are_prices_set(), TokenMillError::PricesNotSet);
+    require!(!market.is_staking_frozen(), TokenMillError::StakingFrozen);
+
+    staking_manager::deposit(
+        market,
+        staking,
+        stake_position,
+        amount,
+        get_clock()?.unix_timestamp,
+    )?;
 
     transfer_from_eoa(
         &ctx.accounts.base_token_mint,
@@ -0,0 +1,117 @@
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillStakeLockEvent,
+    manager::staking_manager,
+    state::{Market, MarketStaking, StakePosition},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeLockExpiry<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Account<'info, MarketStaking>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub stake_position: Account<'info, StakePosition>,
+}
+
+/// Permissionless, drops the multiplier of an expired lock so that the position stops earning a
+/// boosted share of the rewards of the other stakers.
+pub fn handler(ctx: Context<StakeLockExpiry>) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    staking_manager::expire_lock(
+        market,
+        &mut ctx.accounts.staking,
+        stake_position,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillStakeLockEvent {
+        market: ctx.accounts.market.key(),
+        user: stake_position.user,
+        stake_position: stake_position.key(),
+        lock_tier: 0,
+        unlock_timestamp: stake_position.unlock_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, DepositAction, ExpireStakeLockAction, LockStakeAction, TokenMillEnv,
+        },
+        TokenMillError,
+    };
+
+    use crate::{state::STAKING_LOCK_TIERS, MarketStaking, StakePosition};
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositAction::new(&testing_env, STAKE_AMOUNT)])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn expire_stake_lock() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&LockStakeAction::new(&testing_env, 1)])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let action = ExpireStakeLockAction::new(&testing_env);
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert_eq!(tm_parse_error(result).unwrap(), TokenMillError::StakeLocked);
+
+        testing_env.svm.warp(STAKING_LOCK_TIERS[1].duration);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        assert_eq!(stake_position.lock_tier, 0);
+        assert_eq!(stake_position.shares, STAKE_AMOUNT);
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+        assert_eq!(staking.total_shares, STAKE_AMOUNT);
+    }
+
+    #[test]
+    fn expire_stake_lock_without_lock() {
+        let mut testing_env = setup_env();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&ExpireStakeLockAction::new(&testing_env)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidLockTier
+        );
+    }
+}
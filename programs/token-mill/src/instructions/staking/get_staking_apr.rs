@@ -0,0 +1,85 @@
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    manager::staking_manager,
+    state::{Market, MarketStaking},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+pub struct StakingAprView<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Account<'info, MarketStaking>,
+}
+
+/// View returning the estimated staking APR of the market in bps, see `staking_manager::get_staking_apr`.
+pub fn handler(ctx: Context<StakingAprView>) -> Result<u64> {
+    let market = ctx.accounts.market.load()?;
+
+    staking_manager::get_staking_apr(&market, &ctx.accounts.staking, get_clock()?.unix_timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::VOLUME_WINDOW_DURATION;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{DepositAction, GetStakingAprAction, SwapAction, TokenMillEnv},
+        SwapAmountType, SwapType,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000_000;
+
+    fn get_staking_apr(testing_env: &mut TokenMillEnv) -> u64 {
+        let metadata = testing_env
+            .svm
+            .execute_actions(&[&GetStakingAprAction::new(testing_env)])
+            .unwrap();
+
+        u64::from_le_bytes(metadata.return_data.data[..8].try_into().unwrap())
+    }
+
+    #[test]
+    fn get_staking_apr_after_volume_window() {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            STAKE_AMOUNT,
+            u64::MAX,
+            None,
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+                &swap_action,
+            ])
+            .unwrap();
+
+        assert_eq!(get_staking_apr(&mut testing_env), 0);
+
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        assert!(get_staking_apr(&mut testing_env) > 0);
+
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        assert_eq!(get_staking_apr(&mut testing_env), 0);
+    }
+
+    #[test]
+    fn get_staking_apr_without_stake() {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env.svm.warp(VOLUME_WINDOW_DURATION);
+
+        assert_eq!(get_staking_apr(&mut testing_env), 0);
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillStakeLockEvent,
+    manager::staking_manager,
+    state::{Market, MarketStaking, StakePosition},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeLock<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Account<'info, MarketStaking>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = user @ TokenMillError::InvalidAuthority
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    pub user: Signer<'info>,
+}
+
+/// Locks the position of the user in `lock_tier` of `STAKING_LOCK_TIERS`, weighting the reward
+/// shares of its staked amount until the lock expires. Calling it again extends the lock from now.
+pub fn handler(ctx: Context<StakeLock>, lock_tier: u8) -> Result<()> {
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    require!(!market.is_staking_frozen(), TokenMillError::StakingFrozen);
+
+    staking_manager::lock(
+        market,
+        &mut ctx.accounts.staking,
+        stake_position,
+        lock_tier,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillStakeLockEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        stake_position: stake_position.key(),
+        lock_tier,
+        unlock_timestamp: stake_position.unlock_timestamp,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateIndexedStakePositionAction, DepositAction, LockStakeAction,
+            SplitPositionAction, SwapAction, TokenMillEnv, WithdrawAction,
+        },
+        SwapAmountType, SwapType, TokenMillError,
+    };
+
+    use crate::{state::STAKING_LOCK_TIERS, MarketStaking, StakePosition};
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositAction::new(&testing_env, STAKE_AMOUNT)])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn lock_stake() {
+        let mut testing_env = setup_env();
+
+        let action = LockStakeAction::new(&testing_env, 2);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        assert_eq!(stake_position.lock_tier, 2);
+        assert_eq!(
+            stake_position.unlock_timestamp,
+            testing_env.svm.get_unix_timestamp() + STAKING_LOCK_TIERS[2].duration
+        );
+        assert_eq!(stake_position.shares, STAKE_AMOUNT * 3 / 2);
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+        assert_eq!(staking.amount_staked, STAKE_AMOUNT);
+        assert_eq!(staking.total_shares, STAKE_AMOUNT * 3 / 2);
+
+        // Unstaking is rejected until the lock expires
+        let withdraw_action = WithdrawAction::new(&testing_env, STAKE_AMOUNT);
+
+        let result = testing_env.svm.execute_actions(&[&withdraw_action]);
+
+        assert_eq!(tm_parse_error(result).unwrap(), TokenMillError::StakeLocked);
+
+        testing_env.svm.warp(STAKING_LOCK_TIERS[2].duration);
+
+        testing_env
+            .svm
+            .execute_actions(&[&withdraw_action])
+            .unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        assert_eq!(stake_position.lock_tier, 0);
+        assert_eq!(stake_position.shares, 0);
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+        assert_eq!(staking.total_shares, 0);
+    }
+
+    #[test]
+    fn lock_stake_weights_rewards() {
+        let mut testing_env = setup_env();
+
+        let create_indexed_stake_position_action =
+            CreateIndexedStakePositionAction::new(&testing_env, 1);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &create_indexed_stake_position_action,
+                &SplitPositionAction::new(&testing_env, 1, STAKE_AMOUNT / 2),
+                &LockStakeAction::new(&testing_env, 3),
+            ])
+            .unwrap();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000_000 / 2,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        // Relocking settles the rewards of both positions
+        testing_env.svm.warp(1);
+
+        let action = LockStakeAction::new(&testing_env, 3);
+
+        let mut unlocked_action = LockStakeAction::new(&testing_env, 0);
+        unlocked_action.stake_position = create_indexed_stake_position_action.stake_position;
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &unlocked_action])
+            .unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        let unlocked_stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&unlocked_action.stake_position);
+
+        // Same amount staked, but twice the shares
+        assert_eq!(
+            stake_position.amount_staked,
+            unlocked_stake_position.amount_staked
+        );
+        assert_eq!(stake_position.shares, 2 * unlocked_stake_position.shares);
+        assert!(unlocked_stake_position.pending_rewards > 0);
+        assert!(
+            stake_position
+                .pending_rewards
+                .abs_diff(2 * unlocked_stake_position.pending_rewards)
+                <= 2
+        );
+    }
+
+    #[test]
+    fn lock_stake_not_extended_by_transfers() {
+        let mut testing_env = setup_env();
+
+        let create_indexed_stake_position_action =
+            CreateIndexedStakePositionAction::new(&testing_env, 1);
+
+        let mut lock_action = LockStakeAction::new(&testing_env, 3);
+        lock_action.stake_position = create_indexed_stake_position_action.stake_position;
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_indexed_stake_position_action, &lock_action])
+            .unwrap();
+
+        let unlock_timestamp = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&lock_action.stake_position)
+            .unlock_timestamp;
+
+        testing_env.svm.warp(1_000);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SplitPositionAction::new(&testing_env, 1, STAKE_AMOUNT / 2)])
+            .unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&lock_action.stake_position);
+
+        assert_eq!(stake_position.amount_staked, STAKE_AMOUNT / 2);
+        assert_eq!(stake_position.unlock_timestamp, unlock_timestamp);
+        assert_eq!(stake_position.shares, STAKE_AMOUNT);
+    }
+
+    #[test]
+    fn lock_stake_with_invalid_tier() {
+        let mut testing_env = setup_env();
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&LockStakeAction::new(&testing_env, 4)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidLockTier
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&LockStakeAction::new(&testing_env, 2)])
+            .unwrap();
+
+        // Locks are never shortened
+        let result = testing_env
+            .svm
+            .execute_actions(&[&LockStakeAction::new(&testing_env, 1)]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidLockTier
+        );
+    }
+
+    #[test]
+    fn lock_stake_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = LockStakeAction::new(&testing_env, 1);
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{
+    clock::get_clock, errors::TokenMillError, events::TokenMillStakeTransferEvent,
+    manager::staking_manager,
+};
+use anchor_lang::prelude::*;
+
+use super::StakeTransfer;
+
+/// Moves the whole staked amount of the position of the user into another of its positions.
+/// Rewards accrued so far stay claimable from the source position.
+pub fn handler(ctx: Context<StakeTransfer>) -> Result<()> {
+    let amount = ctx.accounts.stake_position.amount_staked;
+
+    require!(amount > 0, TokenMillError::InsufficientStakeAmount);
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    staking_manager::transfer_stake(
+        market,
+        &mut ctx.accounts.staking,
+        &mut ctx.accounts.stake_position,
+        &mut ctx.accounts.destination_stake_position,
+        amount,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillStakeTransferEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        stake_position: ctx.accounts.stake_position.key(),
+        destination_stake_position: ctx.accounts.destination_stake_position.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateIndexedStakePositionAction, CreateStakePositionAction,
+            DepositAction, MergePositionsAction, TokenMillEnv,
+        },
+        TokenMillError,
+    };
+
+    use crate::StakePosition;
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> (TokenMillEnv, MergePositionsAction) {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateIndexedStakePositionAction::new(&testing_env, 1),
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+            ])
+            .unwrap();
+
+        let action = MergePositionsAction::new(&testing_env, 1);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn merge_positions() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        assert_eq!(stake_position.amount_staked, 0);
+
+        let destination_stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.destination_stake_position);
+        assert_eq!(destination_stake_position.amount_staked, STAKE_AMOUNT);
+    }
+
+    #[test]
+    fn merge_empty_position() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientStakeAmount);
+    }
+
+    #[test]
+    fn merge_positions_to_other_user() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let carol = testing_env.svm.change_payer("carol");
+        let create_stake_position_action =
+            CreateStakePositionAction::new(&testing_env).with_signer(carol);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_stake_position_action])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        action.destination_stake_position = create_stake_position_action.stake_position;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
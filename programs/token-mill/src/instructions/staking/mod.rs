@@ -1,10 +1,32 @@
+pub mod backfill_stake_position;
 pub mod claim_staking_rewards;
+pub mod create_indexed_stake_position;
 pub mod create_stake_position;
 pub mod create_staking;
+pub mod create_staking_idempotent;
 pub mod deposit;
+pub mod expire_stake_lock;
+pub mod get_staking_apr;
+pub mod lock_stake;
+pub mod merge_positions;
+pub mod remove_claim_delegate;
+pub mod set_auto_claim;
+pub mod set_claim_delegate;
+pub mod split_position;
 pub mod withdraw;
 
+pub use backfill_stake_position::*;
 pub use claim_staking_rewards::*;
+pub use create_indexed_stake_position::*;
 pub use create_stake_position::*;
 pub use create_staking::*;
+pub use create_staking_idempotent::*;
 pub use deposit::*;
+pub use expire_stake_lock::*;
+pub use get_staking_apr::*;
+pub use lock_stake::*;
+pub use merge_positions::*;
+pub use remove_claim_delegate::*;
+pub use set_auto_claim::*;
+pub use set_claim_delegate::*;
+pub use split_position::*;
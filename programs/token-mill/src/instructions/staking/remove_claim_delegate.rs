@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillClaimDelegateUpdateEvent,
+    state::{ClaimDelegate, StakePosition},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveClaimDelegate<'info> {
+    #[account(has_one = user @ TokenMillError::InvalidAuthority)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        mut,
+        close = user,
+        has_one = stake_position @ TokenMillError::InvalidStakePosition
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveClaimDelegate>) -> Result<()> {
+    emit_cpi!(TokenMillClaimDelegateUpdateEvent {
+        stake_position: ctx.accounts.stake_position.key(),
+        user: ctx.accounts.user.key(),
+        delegate: None,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, RemoveClaimDelegateAction, SetClaimDelegateAction, TokenMillEnv,
+        },
+        make_address, TokenMillError,
+    };
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(0);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetClaimDelegateAction::new(
+                &testing_env,
+                make_address("carol"),
+            )])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn remove_claim_delegate() {
+        let mut testing_env = setup_env();
+
+        let action = RemoveClaimDelegateAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        assert!(!testing_env.svm.account_exists(&action.claim_delegate));
+    }
+
+    #[test]
+    fn remove_claim_delegate_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = RemoveClaimDelegateAction::new(&testing_env);
+        action.signer = testing_env.svm.change_payer("carol");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillAutoClaimUpdateEvent,
+    state::{ClaimDelegate, StakePosition, CLAIM_DELEGATE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAutoClaim<'info> {
+    #[account(has_one = user @ TokenMillError::InvalidAuthority)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ClaimDelegate::INIT_SPACE,
+        seeds = [CLAIM_DELEGATE_PDA_SEED.as_bytes(), stake_position.key().as_ref()],
+        bump
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Registers the stake position for auto-claim, letting keepers batch its claims.
+/// The minimum claim amount of the market keeps them from claiming dust.
+pub fn handler(ctx: Context<SetAutoClaim>, auto_claim: bool) -> Result<()> {
+    let claim_delegate = &mut ctx.accounts.claim_delegate;
+
+    if claim_delegate.stake_position == Pubkey::default() {
+        claim_delegate.initialize(ctx.bumps.claim_delegate, ctx.accounts.stake_position.key())?;
+    }
+
+    claim_delegate.auto_claim = auto_claim;
+
+    emit_cpi!(TokenMillAutoClaimUpdateEvent {
+        stake_position: ctx.accounts.stake_position.key(),
+        user: ctx.accounts.user.key(),
+        auto_claim,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ClaimDelegate;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, ClaimStakingRewardsAction, DepositAction, SetAutoClaimAction,
+            SwapAction, TokenMillEnv, UpdateMinClaimAmountAction,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        let deposit_action = DepositAction::new(&testing_env, STAKE_AMOUNT);
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000_000 / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&deposit_action, &swap_action])
+            .unwrap();
+
+        testing_env
+    }
+
+    #[test]
+    fn set_auto_claim() {
+        let mut testing_env = setup_env();
+
+        let action = SetAutoClaimAction::new(&testing_env, true);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let claim_delegate = testing_env
+            .svm
+            .get_parsed_account::<ClaimDelegate>(&action.claim_delegate);
+
+        assert!(claim_delegate.auto_claim);
+        assert_eq!(claim_delegate.delegate, Default::default());
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &make_address("bob"));
+
+        testing_env.svm.change_payer("carol");
+
+        let claim_action = ClaimStakingRewardsAction::new(&testing_env).with_auto_claim();
+
+        testing_env.svm.execute_actions(&[&claim_action]).unwrap();
+
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &make_address("bob"))
+                > balance_before
+        );
+    }
+
+    #[test]
+    fn auto_claim_below_min_claim_amount() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetAutoClaimAction::new(&testing_env, true)])
+            .unwrap();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMinClaimAmountAction::new(&testing_env, u64::MAX)])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let claim_action = ClaimStakingRewardsAction::new(&testing_env).with_auto_claim();
+
+        let result = testing_env.svm.execute_actions(&[&claim_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::ClaimBelowMinimum);
+    }
+
+    #[test]
+    fn claim_after_auto_claim_disabled() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &SetAutoClaimAction::new(&testing_env, true),
+                &SetAutoClaimAction::new(&testing_env, false),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("carol");
+
+        let claim_action = ClaimStakingRewardsAction::new(&testing_env).with_auto_claim();
+
+        let result = testing_env.svm.execute_actions(&[&claim_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+
+    #[test]
+    fn set_auto_claim_with_invalid_signer() {
+        let mut testing_env = setup_env();
+
+        let mut action = SetAutoClaimAction::new(&testing_env, true);
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillClaimDelegateUpdateEvent,
+    state::{ClaimDelegate, StakePosition, CLAIM_DELEGATE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+    #[account(has_one = user @ TokenMillError::InvalidAuthority)]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + ClaimDelegate::INIT_SPACE,
+        seeds = [CLAIM_DELEGATE_PDA_SEED.as_bytes(), stake_position.key().as_ref()],
+        bump
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+    let claim_delegate = &mut ctx.accounts.claim_delegate;
+
+    if claim_delegate.stake_position == Pubkey::default() {
+        claim_delegate.initialize(ctx.bumps.claim_delegate, ctx.accounts.stake_position.key())?;
+    }
+
+    claim_delegate.delegate = delegate;
+
+    emit_cpi!(TokenMillClaimDelegateUpdateEvent {
+        stake_position: ctx.accounts.stake_position.key(),
+        user: ctx.accounts.user.key(),
+        delegate: Some(delegate),
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ClaimDelegate;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{tm_parse_error, SetClaimDelegateAction, TokenMillEnv},
+        make_address, TokenMillError,
+    };
+
+    #[test]
+    fn set_claim_delegate() {
+        let mut testing_env = TokenMillEnv::default().with_staking(0);
+
+        let action = SetClaimDelegateAction::new(&testing_env, make_address("carol"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let claim_delegate = testing_env
+            .svm
+            .get_parsed_account::<ClaimDelegate>(&action.claim_delegate);
+
+        assert_eq!(claim_delegate.stake_position, action.stake_position);
+        assert_eq!(claim_delegate.delegate, make_address("carol"));
+
+        let action = SetClaimDelegateAction::new(&testing_env, make_address("dave"));
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let claim_delegate = testing_env
+            .svm
+            .get_parsed_account::<ClaimDelegate>(&action.claim_delegate);
+
+        assert_eq!(claim_delegate.delegate, make_address("dave"));
+    }
+
+    #[test]
+    fn set_claim_delegate_with_invalid_signer() {
+        let mut testing_env = TokenMillEnv::default().with_staking(0);
+
+        let mut action = SetClaimDelegateAction::new(&testing_env, make_address("mallory"));
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
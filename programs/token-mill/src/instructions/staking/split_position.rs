@@ -0,0 +1,260 @@
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillStakeTransferEvent,
+    manager::staking_manager,
+    state::{Market, MarketStaking, StakePosition},
+};
+use anchor_lang::prelude::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakeTransfer<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Account<'info, MarketStaking>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = user @ TokenMillError::InvalidAuthority
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    /// Another position of the user, see `create_indexed_stake_position`
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = user @ TokenMillError::InvalidAuthority,
+        constraint = destination_stake_position.key() != stake_position.key() @ TokenMillError::InvalidStakePosition
+    )]
+    pub destination_stake_position: Account<'info, StakePosition>,
+
+    pub user: Signer<'info>,
+}
+
+/// Carves `amount` staked tokens out of the position of the user into another of its positions.
+pub fn handler(ctx: Context<StakeTransfer>, amount: u64) -> Result<()> {
+    require!(amount > 0, TokenMillError::InvalidAmount);
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+
+    staking_manager::transfer_stake(
+        market,
+        &mut ctx.accounts.staking,
+        &mut ctx.accounts.stake_position,
+        &mut ctx.accounts.destination_stake_position,
+        amount,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    emit_cpi!(TokenMillStakeTransferEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        stake_position: ctx.accounts.stake_position.key(),
+        destination_stake_position: ctx.accounts.destination_stake_position.key(),
+        amount,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, CreateIndexedStakePositionAction, CreateStakePositionAction,
+            DepositAction, LockStakeAction, SplitPositionAction, SwapAction, TokenMillEnv,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    use crate::{state::STAKING_LOCK_TIERS, MarketStaking, StakePosition};
+
+    const STAKE_AMOUNT: u64 = 100_000_000;
+
+    fn setup_env() -> (TokenMillEnv, SplitPositionAction) {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &CreateIndexedStakePositionAction::new(&testing_env, 1),
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+            ])
+            .unwrap();
+
+        let action = SplitPositionAction::new(&testing_env, 1, STAKE_AMOUNT / 4);
+
+        (testing_env, action)
+    }
+
+    #[test]
+    fn split_position() {
+        let (mut testing_env, action) = setup_env();
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_ok());
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        assert_eq!(stake_position.amount_staked, STAKE_AMOUNT * 3 / 4);
+
+        let destination_stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.destination_stake_position);
+        assert_eq!(destination_stake_position.amount_staked, STAKE_AMOUNT / 4);
+        assert_eq!(destination_stake_position.user, make_address("bob"));
+
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+        assert_eq!(staking.amount_staked, STAKE_AMOUNT);
+    }
+
+    #[test]
+    fn split_position_preserves_rewards() {
+        let (mut testing_env, action) = setup_env();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000_000 / 2,
+            u64::MAX,
+            None,
+        );
+
+        testing_env
+            .svm
+            .execute_actions(&[&swap_action, &action])
+            .unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+        let destination_stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.destination_stake_position);
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.market_staking);
+
+        assert!(stake_position.pending_rewards > 0);
+        assert_eq!(destination_stake_position.pending_rewards, 0);
+        assert_eq!(
+            destination_stake_position.acc_reward_amount_per_share,
+            staking.acc_reward_amount_per_share
+        );
+    }
+
+    #[test]
+    fn split_position_into_locked_position() {
+        let (mut testing_env, action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&action, &LockStakeAction::new(&testing_env, 3)])
+            .unwrap();
+
+        // Moved into the main position just before its lock expires
+        testing_env.svm.warp(STAKING_LOCK_TIERS[3].duration - 1);
+
+        let mut split_back_action = SplitPositionAction::new(&testing_env, 1, STAKE_AMOUNT / 4);
+        split_back_action.stake_position = action.destination_stake_position;
+        split_back_action.destination_stake_position = action.stake_position;
+
+        testing_env
+            .svm
+            .execute_actions(&[&split_back_action])
+            .unwrap();
+
+        let stake_position = testing_env
+            .svm
+            .get_parsed_account::<StakePosition>(&action.stake_position);
+
+        // The multiplier of the tier comes with its full lock
+        assert_eq!(stake_position.amount_staked, STAKE_AMOUNT);
+        assert_eq!(stake_position.lock_tier, 3);
+        assert_eq!(
+            stake_position.unlock_timestamp,
+            testing_env.svm.get_unix_timestamp() + STAKING_LOCK_TIERS[3].duration
+        );
+        assert_eq!(stake_position.shares, stake_position.get_shares().unwrap());
+    }
+
+    #[test]
+    fn split_position_with_insufficient_amount() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.amount = STAKE_AMOUNT + 1;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InsufficientStakeAmount);
+    }
+
+    #[test]
+    fn split_position_into_itself() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.destination_stake_position = action.stake_position;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidStakePosition);
+    }
+
+    #[test]
+    fn split_position_with_invalid_signer() {
+        let (mut testing_env, mut action) = setup_env();
+
+        action.signer = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+
+    #[test]
+    fn split_position_to_other_user() {
+        let (mut testing_env, mut action) = setup_env();
+
+        let carol = testing_env.svm.change_payer("carol");
+        let create_stake_position_action =
+            CreateStakePositionAction::new(&testing_env).with_signer(carol);
+
+        testing_env
+            .svm
+            .execute_actions(&[&create_stake_position_action])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        action.destination_stake_position = create_stake_position_action.stake_position;
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
+}
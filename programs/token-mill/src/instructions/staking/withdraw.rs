@@ -1,8 +1,8 @@
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
     events::TokenMillStakingWithdrawalEvent,
     manager::{staking_manager, token_manager::transfer_from_pda},
-    MARKET_PDA_SEED,
 };
 use anchor_lang::prelude::*;
 
@@ -18,21 +18,22 @@ pub fn handler(ctx: Context<StakeUpdate>, amount: u64) -> Result<()> {
         TokenMillError::InsufficientStakeAmount
     );
 
-    let market_bump = {
+    let market_seeds = {
         let market = &mut ctx.accounts.market.load_mut()?;
 
-        staking_manager::withdraw(market, staking, stake_position, amount)?;
+        staking_manager::withdraw(
+            market,
+            staking,
+            stake_position,
+            amount,
+            get_clock()?.unix_timestamp,
+        )?;
 
-        market.bump
+        market.seeds()
     };
 
     let base_token_mint = &ctx.accounts.base_token_mint;
-    let base_token_mint_key = base_token_mint.key();
-    let seeds = [
-        MARKET_PDA_SEED.as_bytes(),
-        base_token_mint_key.as_ref(),
-        &[market_bump],
-    ];
+    let seeds = market_seeds.signer_seeds();
 
     transfer_from_pda(
         base_token_mint,
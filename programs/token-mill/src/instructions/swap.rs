@@ -1,15 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
 
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
-    events::TokenMillSwapEvent,
+    events::{
+        TokenMillGraduationProgressEvent, TokenMillOffChainReferralEvent,
+        TokenMillReferrerBindingEvent, TokenMillSwapEvent,
+    },
     manager::{
-        swap_manager::{self, SwapAmountType, SwapType},
-        token_manager::{transfer_from_eoa, transfer_from_pda},
+        policy_manager::ValidateSwapArgs,
+        receipt_manager::{self, SPL_ACCOUNT_COMPRESSION_PROGRAM_ID, SPL_NOOP_PROGRAM_ID},
+        settlement_manager::SwapAccounts,
+        swap_manager::{self, SwapAmountType, SwapOutcome, SwapType},
+        token_manager::{
+            get_transfer_fee, get_transfer_inverse_fee, transfer_from_eoa, transfer_from_pda,
+        },
     },
-    state::Market,
-    TokenMillConfig, MARKET_PDA_SEED,
+    state::{
+        Denylist, FeeExemption, LaunchPurchase, Market, MarketOracle, MarketReferralAccrual,
+        MarketReferralCode, MarketStats, ReferralAccount, ReferralCode, ReferralSettlement,
+        ReferrerBinding, SwapReceiptTree, TraderVolume, FEE_EXEMPTION_PDA_SEED,
+        LAUNCH_PURCHASE_PDA_SEED, REFERRER_BINDING_PDA_SEED, SWAP_RECEIPT_TREE_PDA_SEED,
+    },
+    TokenMillConfig,
 };
 
 #[event_cpi]
@@ -69,54 +86,382 @@ pub struct Swap<'info> {
     pub base_token_program: Interface<'info, TokenInterface>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// CHECK: Revenue report of the current epoch, checked against its address. Records the fees
+    /// of the swap once created
+    #[account(mut)]
+    pub revenue_report: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the policy program of the config
+    pub policy_program: Option<UncheckedAccount<'info>>,
+
+    /// Required with a referral token account when the market sets a minimum referred volume
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        constraint = referral_token_account.as_ref().is_some_and(|a| a.key() == referral_accrual.referral_token_account) @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_accrual: Option<Account<'info, MarketReferralAccrual>>,
+
+    /// Referral code of the market, paying part of the creator fee to the referral token account
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        constraint = referral_token_account.as_ref().is_some_and(|a| a.key() == referral_code.referral_token_account) @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_code: Option<Account<'info, MarketReferralCode>>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+
+    /// Referral account owning the referral token account, applying its referral tier to the swap
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        constraint = referral_token_account.as_ref().is_some_and(|a| a.owner == referral_account.key()) @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_account: Option<Account<'info, ReferralAccount>>,
+
+    /// Volume of the user during the epoch of the revenue report, setting aside part of the protocol fee for trader rebates
+    #[account(
+        mut,
+        constraint = trader_volume.revenue_report == revenue_report.key() @ TokenMillError::InvalidEpoch,
+        constraint = trader_volume.owner == user.key() @ TokenMillError::InvalidAuthority
+    )]
+    pub trader_volume: Option<Account<'info, TraderVolume>>,
+
+    /// Required with a trader volume once the revenue report sets aside trader rebates
+    #[account(
+        mut,
+        token::mint = quote_token_mint,
+        constraint = revenue_report_quote_token_account.owner == revenue_report.key() @ TokenMillError::InvalidRebateVault
+    )]
+    pub revenue_report_quote_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Appends a receipt of the swap to the merkle tree of the market when provided
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        constraint = receipt_merkle_tree.as_ref().is_some_and(|t| t.key() == swap_receipt_tree.merkle_tree) @ TokenMillError::InvalidSwapReceiptTree
+    )]
+    pub swap_receipt_tree: Option<Account<'info, SwapReceiptTree>>,
+
+    /// CHECK: Checked against the swap receipt tree
+    #[account(mut)]
+    pub receipt_merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL account compression program
+    #[account(address = SPL_ACCOUNT_COMPRESSION_PROGRAM_ID @ TokenMillError::InvalidCompressionProgram)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL noop program
+    #[account(address = SPL_NOOP_PROGRAM_ID @ TokenMillError::InvalidCompressionProgram)]
+    pub noop_program: Option<UncheckedAccount<'info>>,
+
+    /// Required for buys while the launch window of the market is open
+    #[account(
+        mut,
+        seeds = [
+            LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = launch_purchase.bump
+    )]
+    pub launch_purchase: Option<Account<'info, LaunchPurchase>>,
+
+    /// Records the price of the market before the swap when provided, required by dynamic fee
+    /// markets
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_oracle: Option<AccountLoader<'info, MarketOracle>>,
+
+    /// Records the swap into the stats of the market when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub market_stats: Option<AccountLoader<'info, MarketStats>>,
+
+    /// CHECK: Referrer binding of the user, checked against its address so that bound users can't
+    /// leave it out. Once created, binds the referral token account of the swap if none is bound
+    /// yet
+    #[account(
+        mut,
+        seeds = [
+            REFERRER_BINDING_PDA_SEED.as_bytes(),
+            config.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump
+    )]
+    pub referrer_binding: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the fee hook program of the config
+    pub fee_hook_program: Option<UncheckedAccount<'info>>,
+
+    /// Waives the swap fee of the user when provided
+    #[account(
+        seeds = [
+            FEE_EXEMPTION_PDA_SEED.as_bytes(),
+            market.key().as_ref(),
+            user.key().as_ref()
+        ],
+        bump = fee_exemption.bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
 }
 
-pub fn handler(
-    ctx: Context<Swap>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
     swap_type: SwapType,
     swap_amount_type: SwapAmountType,
     amount: u64,
     other_amount_threshold: u64,
+    deadline: Option<i64>,
 ) -> Result<(u64, u64)> {
     if amount == 0 {
         return Err(TokenMillError::InvalidAmount.into());
     }
 
+    if let Some(deadline) = deadline {
+        require_gte!(
+            deadline,
+            get_clock()?.unix_timestamp,
+            TokenMillError::Expired
+        );
+    }
+
     let referral_token_account = &ctx.accounts.referral_token_account;
+    let quote_token_mint = &ctx.accounts.quote_token_mint;
+
+    // The referral code of a referrer can lead the remaining accounts, the referral token
+    // account must then be owned by the referral account of the code
+    let policy_accounts = match ctx.remaining_accounts.split_first() {
+        Some((account_info, policy_accounts)) if ReferralCode::is_referral_code(account_info) => {
+            let referrer_code = Account::<ReferralCode>::try_from(account_info)?;
+
+            require!(
+                referrer_code.config == ctx.accounts.config.key()
+                    && referral_token_account
+                        .as_ref()
+                        .is_some_and(|a| a.owner == referrer_code.referral_account),
+                TokenMillError::InvalidReferralAccount
+            );
+
+            policy_accounts
+        }
+        _ => ctx.remaining_accounts,
+    };
 
-    let base_amount;
-    let quote_amount;
-    let market_bump;
-    let swap_fee;
-    let creator_fee;
-    let staking_fee;
-    let protocol_fee;
-    let referral_fee;
+    let swap_accounts = SwapAccounts {
+        config: &ctx.accounts.config,
+        market: &ctx.accounts.market,
+        quote_token_mint,
+        market_quote_token_ata: &ctx.accounts.market_quote_token_ata,
+        protocol_quote_token_ata: &ctx.accounts.protocol_quote_token_ata,
+        quote_token_program: &ctx.accounts.quote_token_program,
+        revenue_report: &ctx.accounts.revenue_report,
+        denylist: ctx.accounts.denylist.as_ref(),
+        policy_program: ctx.accounts.policy_program.as_ref(),
+        fee_hook_program: ctx.accounts.fee_hook_program.as_ref(),
+        market_oracle: ctx.accounts.market_oracle.as_ref(),
+        market_stats: ctx.accounts.market_stats.as_ref(),
+        hook_accounts: policy_accounts,
+    };
 
-    {
-        let config = &ctx.accounts.config;
-        let market = &mut ctx.accounts.market.load_mut()?;
+    swap_accounts.check_trader(&ctx.accounts.user.key())?;
 
-        (base_amount, quote_amount, swap_fee) =
-            swap_manager::swap(market, swap_type, swap_amount_type, amount)?;
+    let mut revenue_report = swap_accounts.load_revenue_report()?;
 
-        (creator_fee, staking_fee, protocol_fee, referral_fee) = market.fees.distribute_fee(
-            swap_fee,
+    let mut referrer_binding = if ctx.accounts.referrer_binding.owner == &crate::ID {
+        Some(Account::<ReferrerBinding>::try_from(
+            &ctx.accounts.referrer_binding,
+        )?)
+    } else {
+        None
+    };
+
+    // Once bound, the referrer of the user is credited on each of their swaps
+    if let Some(referrer_binding) = referrer_binding.as_ref().filter(|b| b.is_bound()) {
+        require!(
             referral_token_account
                 .as_ref()
-                .map(|_| config.referral_fee_share),
+                .is_some_and(|a| a.key() == referrer_binding.referral_token_account),
+            TokenMillError::InvalidReferralAccount
+        );
+    }
+
+    // The curve only accounts for the quote tokens moving in and out of the market vault,
+    // exact quote amounts of the user are net of the transfer fee of the quote token
+    let curve_amount = match (swap_type, swap_amount_type) {
+        (SwapType::Buy, SwapAmountType::ExactInput) => {
+            amount - get_transfer_fee(quote_token_mint, amount)?
+        }
+        (SwapType::Sell, SwapAmountType::ExactOutput) => {
+            amount + get_transfer_inverse_fee(quote_token_mint, amount)?
+        }
+        _ => amount,
+    };
+
+    let (
+        mut outcome,
+        market_seeds,
+        min_referred_volume,
+        max_buy_per_wallet,
+        max_wallet_holding,
+        graduation_progress,
+    ) = {
+        let config = &ctx.accounts.config;
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        require!(
+            !market.is_paused(get_clock()?.slot),
+            TokenMillError::MarketPaused
+        );
+
+        market.lock()?;
+
+        let max_base_amount = swap_accounts.before_swap(market, swap_type)?;
+
+        let referral_fee_share = referral_token_account
+            .as_ref()
+            .filter(|_| config.referral_enabled && market.is_referral_enabled())
+            .map(|_| {
+                ctx.accounts.referral_account.as_ref().map_or(
+                    market.fees.referral_fee_share,
+                    |referral_account| {
+                        referral_account
+                            .get_referral_fee_share(config, market.fees.referral_fee_share)
+                    },
+                )
+            });
+
+        let milestone_bps = market.get_graduation_milestone_bps();
+
+        let mut outcome = swap_manager::swap(
+            market,
+            swap_type,
+            swap_amount_type,
+            curve_amount,
+            referral_fee_share,
+            get_clock()?.slot,
+            get_clock()?.unix_timestamp,
         )?;
 
-        market_bump = market.bump;
+        if ctx.accounts.fee_exemption.is_some() {
+            swap_manager::waive_swap_fee(market, &mut outcome, swap_type);
+        }
+
+        if let Some(referral_code) = &ctx.accounts.referral_code {
+            let creator_fee_cut = referral_code.get_creator_fee_cut(outcome.creator_fee)?;
+
+            market.fees.pending_creator_fees -= creator_fee_cut;
+            outcome.creator_fee -= creator_fee_cut;
+            outcome.referral_fee += creator_fee_cut;
+        }
+
+        swap_accounts.after_swap(market, &outcome, max_base_amount)?;
+
+        // Lets frontends update graduation bars on each 5% of the supply bought or sold back
+        let graduation_progress = if market.get_graduation_milestone_bps() != milestone_bps {
+            Some(TokenMillGraduationProgressEvent {
+                market: ctx.accounts.market.key(),
+                milestone_bps: market.get_graduation_milestone_bps(),
+                supply_sold_bps: market.get_supply_sold_bps(),
+                quote_raised: market.get_curve_proceeds()?,
+            })
+        } else {
+            None
+        };
+
+        (
+            outcome,
+            market.seeds(),
+            market.min_referred_volume,
+            market.get_max_buy_per_wallet(get_clock()?.unix_timestamp),
+            market.get_max_wallet_holding()?,
+            graduation_progress,
+        )
+    };
+
+    let trader_rebate_fee = match (&mut revenue_report, &mut ctx.accounts.trader_volume) {
+        (Some(revenue_report), Some(trader_volume)) => revenue_report.record_trader_volume(
+            trader_volume,
+            outcome.quote_amount,
+            outcome.protocol_fee,
+        )?,
+        _ => 0,
+    };
+
+    outcome.protocol_fee -= trader_rebate_fee;
+
+    let SwapOutcome {
+        base_amount,
+        quote_amount,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+        referral_fee,
+        quote_refund,
+        launch_fee,
+        ..
+    } = outcome;
+
+    if let (SwapType::Buy, Some(max_buy_per_wallet)) = (swap_type, max_buy_per_wallet) {
+        ctx.accounts
+            .launch_purchase
+            .as_mut()
+            .ok_or(TokenMillError::InvalidLaunchPurchase)?
+            .record(base_amount, max_buy_per_wallet)?;
+    }
+
+    // Anti-whale cap of the curve phase, on the base token account receiving the buy. It must be
+    // the ATA of the user, other accounts of the same wallet would each get their own cap
+    if let (SwapType::Buy, Some(max_wallet_holding)) = (swap_type, max_wallet_holding) {
+        require_keys_eq!(
+            ctx.accounts.user_base_token_account.key(),
+            get_associated_token_address_with_program_id(
+                &ctx.accounts.user.key(),
+                &ctx.accounts.base_token_mint.key(),
+                &ctx.accounts.base_token_program.key(),
+            ),
+            TokenMillError::InvalidWalletHoldingAccount
+        );
+
+        require_gte!(
+            max_wallet_holding,
+            ctx.accounts.user_base_token_account.amount + base_amount,
+            TokenMillError::WalletHoldingCapExceeded
+        );
+    }
+
+    // Settled off-chain by the platform, the referral fee goes to the protocol fee recipient
+    let off_chain_referral_fee = match (
+        ctx.accounts.config.referral_settlement,
+        referral_token_account,
+    ) {
+        (ReferralSettlement::OffChain, Some(_)) => referral_fee,
+        _ => 0,
     };
 
     let user = &ctx.accounts.user;
-    let base_token_mint_key = ctx.accounts.base_token_mint.key();
-    let seeds = [
-        MARKET_PDA_SEED.as_bytes(),
-        base_token_mint_key.as_ref(),
-        &[market_bump],
-    ];
+    let seeds = market_seeds.signer_seeds();
+
+    swap_accounts.validate(&ValidateSwapArgs {
+        config: ctx.accounts.config.key(),
+        market: ctx.accounts.market.key(),
+        user: user.key(),
+        swap_type,
+        swap_amount_type,
+        base_amount,
+        quote_amount,
+    })?;
+
+    // Grossed up for the market vault to receive `quote_amount` on buys, without exceeding the
+    // exact input of the user
+    let quote_amount_in = quote_amount + get_transfer_inverse_fee(quote_token_mint, quote_amount)?;
+    let quote_amount_in = match swap_amount_type {
+        SwapAmountType::ExactInput => quote_amount_in.min(amount),
+        SwapAmountType::ExactOutput => quote_amount_in,
+    };
 
     let (
         amount_in,
@@ -131,7 +476,7 @@ pub fn handler(
         token_program_out,
     ) = match swap_type {
         SwapType::Buy => (
-            quote_amount,
+            quote_amount_in,
             base_amount,
             &ctx.accounts.quote_token_mint,
             &ctx.accounts.base_token_mint,
@@ -156,9 +501,14 @@ pub fn handler(
         ),
     };
 
+    let amount_received = match swap_type {
+        SwapType::Buy => amount_out,
+        SwapType::Sell => amount_out - get_transfer_fee(quote_token_mint, amount_out)?,
+    };
+
     match swap_amount_type {
         SwapAmountType::ExactInput => {
-            if amount_out < other_amount_threshold {
+            if amount_received < other_amount_threshold {
                 return Err(TokenMillError::AmountThresholdNotMet.into());
             }
         }
@@ -178,6 +528,18 @@ pub fn handler(
         amount_in,
     )?;
 
+    if quote_refund > 0 {
+        transfer_from_pda(
+            &ctx.accounts.quote_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.user_quote_token_account,
+            &ctx.accounts.quote_token_program,
+            quote_refund,
+            &seeds,
+        )?;
+    }
+
     transfer_from_pda(
         mint_out,
         ctx.accounts.market.to_account_info(),
@@ -188,19 +550,52 @@ pub fn handler(
         &seeds,
     )?;
 
-    if protocol_fee > 0 {
+    if trader_rebate_fee > 0 {
+        let revenue_report_quote_token_account = ctx
+            .accounts
+            .revenue_report_quote_token_account
+            .as_ref()
+            .ok_or(TokenMillError::MissingTokenAccount)?;
+
         transfer_from_pda(
             &ctx.accounts.quote_token_mint,
             ctx.accounts.market.to_account_info(),
             &ctx.accounts.market_quote_token_ata,
-            &ctx.accounts.protocol_quote_token_ata,
+            revenue_report_quote_token_account,
             &ctx.accounts.quote_token_program,
-            protocol_fee,
+            trader_rebate_fee,
             &seeds,
         )?;
     }
 
-    if let Some(referral_token_account) = referral_token_account {
+    // With a referred volume requirement, referral fees are held by the market until claimed
+    if ctx.accounts.config.referral_settlement == ReferralSettlement::OffChain {
+        require!(
+            ctx.accounts.referral_accrual.is_none(),
+            TokenMillError::InvalidReferralAccount
+        );
+
+        if let Some(referral_token_account) = referral_token_account {
+            emit_cpi!(TokenMillOffChainReferralEvent {
+                config: ctx.accounts.config.key(),
+                market: ctx.accounts.market.key(),
+                user: user.key(),
+                referral_token_account: referral_token_account.key(),
+                referrer: referral_token_account.owner,
+                quote_amount,
+                referral_fee,
+            });
+        }
+    } else if let Some(referral_accrual) = &mut ctx.accounts.referral_accrual {
+        referral_accrual.record(quote_amount, referral_fee);
+
+        ctx.accounts.market.load_mut()?.held_referral_fees += referral_fee;
+    } else if let Some(referral_token_account) = referral_token_account {
+        require!(
+            min_referred_volume == 0,
+            TokenMillError::InvalidReferralAccount
+        );
+
         if referral_fee > 0 {
             transfer_from_pda(
                 &ctx.accounts.quote_token_mint,
@@ -214,6 +609,82 @@ pub fn handler(
         }
     }
 
+    if let Some(referral_code) = &mut ctx.accounts.referral_code {
+        referral_code.record(quote_amount, referral_fee);
+    }
+
+    if let Some(referral_account) = &mut ctx.accounts.referral_account {
+        referral_account.record(quote_amount);
+    }
+
+    // Sticky, later swaps of the user must name the bound referrer
+    if let (Some(referrer_binding), Some(referral_token_account)) =
+        (&mut referrer_binding, referral_token_account)
+    {
+        if referrer_binding.bind(referral_token_account.key()) {
+            referrer_binding.exit(&crate::ID)?;
+
+            emit_cpi!(TokenMillReferrerBindingEvent {
+                config: ctx.accounts.config.key(),
+                trader: user.key(),
+                referrer_binding: referrer_binding.key(),
+                referral_token_account: referral_token_account.key(),
+            });
+        }
+    }
+
+    swap_accounts.settle(
+        revenue_report.as_mut(),
+        user.key(),
+        swap_type,
+        &outcome,
+        off_chain_referral_fee,
+        &seeds,
+    )?;
+
+    if let Some(swap_receipt_tree) = &mut ctx.accounts.swap_receipt_tree {
+        let (Some(receipt_merkle_tree), Some(compression_program), Some(noop_program)) = (
+            &ctx.accounts.receipt_merkle_tree,
+            &ctx.accounts.compression_program,
+            &ctx.accounts.noop_program,
+        ) else {
+            return Err(TokenMillError::InvalidSwapReceiptTree.into());
+        };
+
+        let market_key = ctx.accounts.market.key();
+        let leaf = receipt_manager::get_swap_receipt_leaf(
+            &market_key,
+            &user.key(),
+            swap_type,
+            base_amount,
+            quote_amount,
+            get_clock()?.slot,
+        );
+
+        let swap_receipt_tree_seeds = [
+            SWAP_RECEIPT_TREE_PDA_SEED.as_bytes(),
+            market_key.as_ref(),
+            &[swap_receipt_tree.bump],
+        ];
+
+        receipt_manager::append(
+            compression_program,
+            receipt_merkle_tree,
+            &swap_receipt_tree.to_account_info(),
+            noop_program,
+            leaf,
+            &swap_receipt_tree_seeds,
+        )?;
+
+        swap_receipt_tree.receipt_count += 1;
+    }
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    if let Some(graduation_progress) = graduation_progress {
+        emit_cpi!(graduation_progress);
+    }
+
     emit_cpi!(TokenMillSwapEvent {
         user: ctx.accounts.user.key(),
         market: ctx.accounts.market.key(),
@@ -225,16 +696,27 @@ pub fn handler(
         staking_fee,
         protocol_fee,
         referral_fee,
+        quote_refund,
+        trader_rebate_fee,
+        launch_fee,
     });
 
-    Ok((base_amount, quote_amount))
+    Ok((base_amount, quote_amount - quote_refund))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{manager::swap_manager, Market};
+    use crate::{
+        constant::REVENUE_EPOCH_DURATION, manager::swap_manager, Market, ReferralSettlement,
+        ReferrerBinding, RevenueReport,
+    };
     use joelana_test_utils::joelana_env::{
-        actions::token_mill::{tm_parse_error, SwapAction, TokenMillEnv},
+        actions::token_mill::{
+            tm_parse_error, CreateReferralAccountAction, CreateReferralCodeAction,
+            CreateReferrerBindingAction, CreateRevenueReportAction, SwapAction, TokenMillEnv,
+            UpdateFeeHookProgramAction, UpdateMinReferredVolumeAction, UpdatePolicyProgramAction,
+            UpdateReferralSettlementAction,
+        },
         make_address, SwapAmountType, SwapType, TokenMillError, TokenType,
     };
     use rstest::rstest;
@@ -359,6 +841,27 @@ mod tests {
         assert_eq!(error, TokenMillError::AmountThresholdNotMet);
     }
 
+    #[test]
+    fn swap_after_deadline() {
+        let (mut testing_env, mut swap_action) = setup_env();
+
+        let now = testing_env.svm.get_unix_timestamp();
+
+        swap_action.deadline = Some(now - 1);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::Expired);
+
+        swap_action.deadline = Some(now);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+    }
+
     #[test]
     fn swap_with_invalid_amount() {
         let (mut testing_env, mut swap_action) = setup_env();
@@ -438,6 +941,114 @@ mod tests {
         );
     }
 
+    #[test]
+    fn swap_exact_output_with_quote_refund() {
+        let (mut testing_env, mut swap_action) = setup_env();
+
+        swap_action.amount = 1_234_567_891;
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        let outcome = swap_manager::get_swap_outcome(
+            &market,
+            swap_manager::SwapType::Buy,
+            swap_manager::SwapAmountType::ExactOutput,
+            swap_action.amount,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let quote_balance_before = testing_env.svm.get_balance(
+            &testing_env.quote_token_mint.unwrap(),
+            &testing_env.svm.payer,
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let quote_balance_after = testing_env.svm.get_balance(
+            &testing_env.quote_token_mint.unwrap(),
+            &testing_env.svm.payer,
+        );
+
+        assert_eq!(
+            quote_balance_before - quote_balance_after,
+            outcome.quote_amount - outcome.quote_refund
+        );
+    }
+
+    #[test]
+    fn swap_with_transfer_fee_quote_token() {
+        // 1% transfer fee
+        let mut testing_env = TokenMillEnv::new()
+            .with_transfer_fee_quote_token_mint(9, 100)
+            .with_default_market();
+        testing_env.svm.change_payer("bob");
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+        let check_quote_vault = |testing_env: &TokenMillEnv| {
+            let market = testing_env
+                .svm
+                .get_parsed_account::<Market>(&testing_env.market);
+            let quote_vault_amount = testing_env
+                .svm
+                .get_balance(&quote_token_mint, &testing_env.market);
+
+            assert!(
+                quote_vault_amount
+                    >= market.get_curve_proceeds().unwrap() + market.get_pending_fees()
+            );
+        };
+
+        let mut swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            10_000_000_000,
+            0,
+            None,
+        );
+
+        let quote_balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &testing_env.svm.payer);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let quote_balance_after = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &testing_env.svm.payer);
+
+        assert!(quote_balance_before - quote_balance_after <= 10_000_000_000);
+        check_quote_vault(&testing_env);
+
+        swap_action.swap_type = SwapType::Sell;
+        swap_action.swap_amount_type = SwapAmountType::ExactOutput;
+        swap_action.amount = 1_000_000_000;
+        swap_action.other_amount_threshold = u64::MAX;
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let quote_balance = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &testing_env.svm.payer);
+
+        assert!(quote_balance - quote_balance_after >= 1_000_000_000);
+        check_quote_vault(&testing_env);
+
+        swap_action.swap_type = SwapType::Buy;
+        swap_action.swap_amount_type = SwapAmountType::ExactOutput;
+        swap_action.amount = 1_000_000_000;
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        check_quote_vault(&testing_env);
+    }
+
     #[test]
     fn sell_more_than_available() {
         let (mut testing_env, mut swap_action) = setup_env();
@@ -483,4 +1094,439 @@ mod tests {
 
         testing_env.svm.execute_actions(&[&swap_action]).unwrap();
     }
+
+    #[test]
+    fn swap_with_referrer_code() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env.svm.change_payer("carol");
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateReferralCodeAction::new("carol")])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        )
+        .with_referrer_code("carol");
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        // The code doesn't resolve to the referral token account
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        )
+        .with_referrer_code("carol");
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidReferralAccount);
+    }
+
+    #[test]
+    fn swap_with_referrer_binding() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateReferrerBindingAction::new()])
+            .unwrap();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let referrer_binding = testing_env
+            .svm
+            .get_parsed_account::<ReferrerBinding>(&swap_action.referrer_binding);
+
+        assert_eq!(
+            referrer_binding.referral_token_account,
+            swap_action.referral_quote_token_ata
+        );
+
+        // Swaps of the user can't skip nor replace the bound referrer
+        let mut swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            None,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidReferralAccount
+        );
+
+        swap_action.referral_quote_token_ata = swap_action.user_quote_token_ata;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidReferralAccount
+        );
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            500_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        );
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let referral_account = CreateReferralAccountAction::new().referral_account;
+        let balance_before = testing_env
+            .svm
+            .get_balance(&quote_token_mint, &referral_account);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        assert!(
+            testing_env
+                .svm
+                .get_balance(&quote_token_mint, &referral_account)
+                > balance_before
+        );
+    }
+
+    #[test]
+    fn swap_without_referrer_binding() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateReferrerBindingAction::new()])
+            .unwrap();
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        );
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        // Leaving out the binding would let the user name another referrer or none
+        let mut swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            500_000_000,
+            u64::MAX,
+            None,
+        );
+        swap_action.referrer_binding = crate::ID;
+
+        assert!(testing_env.svm.execute_actions(&[&swap_action]).is_err());
+    }
+
+    #[test]
+    fn swap_with_off_chain_referral() {
+        let swap_with_settlement = |referral_settlement| {
+            let (mut testing_env, _) = setup_env();
+
+            testing_env.svm.change_payer("admin");
+
+            testing_env
+                .svm
+                .execute_actions(&[&UpdateReferralSettlementAction::new(referral_settlement)])
+                .unwrap();
+
+            testing_env.svm.change_payer("bob");
+
+            let swap_action = SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                1_000_000_000,
+                u64::MAX,
+                Some(make_address("carol")),
+            );
+
+            testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+            let quote_token_mint = testing_env.quote_token_mint.unwrap();
+
+            (
+                testing_env
+                    .svm
+                    .get_balance(&quote_token_mint, &make_address("dave")),
+                testing_env.svm.get_balance(
+                    &quote_token_mint,
+                    &CreateReferralAccountAction::new().referral_account,
+                ),
+            )
+        };
+
+        let (protocol_fees, referral_fees) = swap_with_settlement(ReferralSettlement::OnChain);
+        let (off_chain_protocol_fees, off_chain_referral_fees) =
+            swap_with_settlement(ReferralSettlement::OffChain);
+
+        assert!(referral_fees > 0);
+        assert_eq!(off_chain_referral_fees, 0);
+        assert_eq!(off_chain_protocol_fees, protocol_fees + referral_fees);
+    }
+
+    #[test]
+    fn swap_with_referral_without_accrual() {
+        let (mut testing_env, _) = setup_env();
+
+        testing_env.svm.change_payer("alice");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateMinReferredVolumeAction::new(
+                &testing_env,
+                1_000_000_000,
+            )])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        let swap_action = SwapAction::new(
+            &testing_env,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            u64::MAX,
+            Some(make_address("carol")),
+        );
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidReferralAccount);
+    }
+
+    #[test]
+    fn swap_with_revenue_report() {
+        let (mut testing_env, swap_action) = setup_env();
+
+        let create_action = CreateRevenueReportAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        let swap_action = swap_action.with_revenue_report(create_action.revenue_report);
+
+        testing_env.svm.execute_actions(&[&swap_action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&swap_action.market);
+        let revenue_report = testing_env
+            .svm
+            .get_parsed_account::<RevenueReport>(&create_action.revenue_report);
+
+        assert_eq!(
+            revenue_report.creator_fees,
+            market.fees.pending_creator_fees
+        );
+        assert_eq!(
+            revenue_report.staking_fees,
+            market.fees.pending_staking_fees
+        );
+        assert!(revenue_report.protocol_fees > 0);
+        assert_eq!(revenue_report.referral_fees, 0);
+    }
+
+    #[test]
+    fn swap_skipping_revenue_report() {
+        let (mut testing_env, swap_action) = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&CreateRevenueReportAction::new(&testing_env)])
+            .unwrap();
+
+        let swap_action = swap_action.with_revenue_report(crate::ID);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert_eq!(
+            tm_parse_error(result).unwrap(),
+            TokenMillError::InvalidEpoch
+        );
+    }
+
+    #[test]
+    fn swap_with_outdated_revenue_report() {
+        let (mut testing_env, swap_action) = setup_env();
+
+        let create_action = CreateRevenueReportAction::new(&testing_env);
+
+        testing_env.svm.execute_actions(&[&create_action]).unwrap();
+
+        testing_env.svm.warp(REVENUE_EPOCH_DURATION);
+
+        let swap_action = swap_action.with_revenue_report(create_action.revenue_report);
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidEpoch);
+    }
+
+    fn setup_env_with_policy_program() -> (TokenMillEnv, SwapAction) {
+        let (mut testing_env, swap_action) = setup_env();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdatePolicyProgramAction::new(Some(anchor_spl::token::ID))])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        (testing_env, swap_action)
+    }
+
+    #[test]
+    fn swap_without_policy_program() {
+        let (mut testing_env, swap_action) = setup_env_with_policy_program();
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPolicyProgram);
+    }
+
+    #[test]
+    fn swap_with_invalid_policy_program() {
+        let (mut testing_env, mut swap_action) = setup_env_with_policy_program();
+
+        swap_action.policy_program = anchor_spl::token_2022::ID;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidPolicyProgram);
+    }
+
+    #[test]
+    fn swap_rejected_by_policy_program() {
+        let (mut testing_env, mut swap_action) = setup_env_with_policy_program();
+
+        // The token program doesn't know `validate_swap` and fails the CPI
+        swap_action.policy_program = anchor_spl::token::ID;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&swap_action.market);
+
+        assert_eq!(market.base_reserve, market.total_supply);
+    }
+
+    fn setup_env_with_fee_hook_program() -> (TokenMillEnv, SwapAction) {
+        let (mut testing_env, swap_action) = setup_env();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateFeeHookProgramAction::new(Some(
+                anchor_spl::token::ID,
+            ))])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        (testing_env, swap_action)
+    }
+
+    #[test]
+    fn swap_without_fee_hook_program() {
+        let (mut testing_env, swap_action) = setup_env_with_fee_hook_program();
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeHookProgram);
+    }
+
+    #[test]
+    fn swap_with_invalid_fee_hook_program() {
+        let (mut testing_env, mut swap_action) = setup_env_with_fee_hook_program();
+
+        swap_action.fee_hook_program = anchor_spl::token_2022::ID;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidFeeHookProgram);
+    }
+
+    #[test]
+    fn swap_rejected_by_fee_hook_program() {
+        let (mut testing_env, mut swap_action) = setup_env_with_fee_hook_program();
+
+        // The token program doesn't know `on_swap_fees` and fails the CPI
+        swap_action.fee_hook_program = anchor_spl::token::ID;
+
+        let result = testing_env.svm.execute_actions(&[&swap_action]);
+
+        assert!(result.is_err());
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&swap_action.market);
+
+        assert_eq!(market.base_reserve, market.total_supply);
+    }
 }
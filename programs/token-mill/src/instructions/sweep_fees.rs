@@ -0,0 +1,314 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillFeeSweepEvent,
+    manager::token_manager::transfer_from_pda,
+    state::{Denylist, Market, MarketStaking, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepFees<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority,
+        has_one = quote_token_mint @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: Creator of the market, only used to derive its quote token account
+    pub creator: UncheckedAccount<'info>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = quote_token_program
+    )]
+    pub market_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = quote_token_program
+    )]
+    pub creator_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Accrues the pending staking fees when provided
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Option<Account<'info, MarketStaking>>,
+
+    /// Receives the sweep tip when provided
+    #[account(mut, token::mint = quote_token_mint, token::token_program = quote_token_program)]
+    pub keeper_quote_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub quote_token_program: Interface<'info, TokenInterface>,
+
+    /// Required once the config enforces a denylist
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub denylist: Option<Account<'info, Denylist>>,
+}
+
+/// Permissionless crank paying the pending creator fees to the creator and accruing the pending
+/// staking fees to the stakers, so that keepers can distribute fees on a schedule.
+/// Creator fees below the minimum claim amount of the market are left pending. The keeper can
+/// take `sweep_tip_bps` of the swept fees.
+pub fn handler(ctx: Context<SweepFees>) -> Result<()> {
+    Denylist::check(
+        &ctx.accounts.config,
+        ctx.accounts.denylist.as_ref(),
+        &ctx.accounts.creator.key(),
+    )?;
+
+    let config = &ctx.accounts.config;
+    let tip_recipient = ctx.accounts.keeper_quote_token_account.as_ref();
+
+    let (creator_fees, creator_tip, staking_fees, staking_tip, market_seeds) = {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        market.lock()?;
+
+        let clock = get_clock()?;
+        market.apply_scheduled_updates(clock.slot, clock.unix_timestamp);
+
+        let (creator_fees, staking_fees) =
+            get_sweepable_fees(market, ctx.accounts.staking.as_deref());
+
+        require!(
+            creator_fees + staking_fees > 0,
+            TokenMillError::InvalidAmount
+        );
+
+        let (creator_tip, staking_tip) = match tip_recipient {
+            Some(_) => (
+                config.get_sweep_tip(creator_fees)?,
+                config.get_sweep_tip(staking_fees)?,
+            ),
+            None => (0, 0),
+        };
+
+        market.check_fee_withdrawal(
+            ctx.accounts.market_quote_token_ata.amount,
+            creator_fees + staking_tip,
+        )?;
+
+        market.fees.pending_creator_fees -= creator_fees;
+        market.fees.pending_staking_fees -= staking_fees;
+
+        (
+            creator_fees,
+            creator_tip,
+            staking_fees,
+            staking_tip,
+            market.seeds(),
+        )
+    };
+
+    if let Some(staking) = &mut ctx.accounts.staking {
+        staking.accrue_rewards(staking_fees - staking_tip)?;
+    }
+
+    let seeds = market_seeds.signer_seeds();
+
+    if creator_fees > creator_tip {
+        transfer_from_pda(
+            &ctx.accounts.quote_token_mint,
+            ctx.accounts.market.to_account_info(),
+            &ctx.accounts.market_quote_token_ata,
+            &ctx.accounts.creator_quote_token_ata,
+            &ctx.accounts.quote_token_program,
+            creator_fees - creator_tip,
+            &seeds,
+        )?;
+    }
+
+    let tip = creator_tip + staking_tip;
+
+    if let Some(keeper_quote_token_account) = &ctx.accounts.keeper_quote_token_account {
+        if tip > 0 {
+            transfer_from_pda(
+                &ctx.accounts.quote_token_mint,
+                ctx.accounts.market.to_account_info(),
+                &ctx.accounts.market_quote_token_ata,
+                keeper_quote_token_account,
+                &ctx.accounts.quote_token_program,
+                tip,
+                &seeds,
+            )?;
+        }
+    }
+
+    ctx.accounts.market.load_mut()?.unlock();
+
+    emit_cpi!(TokenMillFeeSweepEvent {
+        market: ctx.accounts.market.key(),
+        creator_fees: creator_fees - creator_tip,
+        staking_fees: staking_fees - staking_tip,
+        keeper_quote_token_account: ctx
+            .accounts
+            .keeper_quote_token_account
+            .as_ref()
+            .map(|token_account| token_account.key()),
+        tip,
+    });
+
+    Ok(())
+}
+
+/// Creator and staking fees of the market a sweep pays out, see `handler`.
+pub fn get_sweepable_fees(market: &Market, staking: Option<&MarketStaking>) -> (u64, u64) {
+    let creator_fees = if market.fees.pending_creator_fees >= market.min_claim_amount {
+        market.fees.pending_creator_fees
+    } else {
+        0
+    };
+
+    let staking_fees = match staking {
+        Some(staking) if staking.total_shares > 0 => market.fees.pending_staking_fees,
+        _ => 0,
+    };
+
+    (creator_fees, staking_fees)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Market, MarketStaking};
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, DepositAction, SwapAction, SweepFeesAction, TokenMillEnv,
+            UpdateSweepTipAction, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType, TokenMillError,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000_000;
+    const SWEEP_TIP_BPS: u16 = 50;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+                &SwapAction::new(
+                    &testing_env,
+                    SwapType::Buy,
+                    SwapAmountType::ExactOutput,
+                    DEFAULT_TOTAL_SUPPLY / 2,
+                    u64::MAX,
+                    None,
+                ),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&UpdateSweepTipAction::new(SWEEP_TIP_BPS)])
+            .unwrap();
+
+        testing_env.svm.change_payer("mallory");
+
+        testing_env
+    }
+
+    #[test]
+    fn sweep_fees() {
+        let mut testing_env = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let alice = make_address("alice");
+        let mallory = make_address("mallory");
+
+        let market_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let alice_balance_before = testing_env.svm.get_balance(&quote_token_mint, &alice);
+        let mallory_balance_before = testing_env.svm.get_balance(&quote_token_mint, &mallory);
+
+        let action = SweepFeesAction::new(&testing_env).with_keeper(mallory);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let staking = testing_env
+            .svm
+            .get_parsed_account::<MarketStaking>(&action.staking);
+
+        let creator_fees = market_before.fees.pending_creator_fees;
+        let staking_fees = market_before.fees.pending_staking_fees;
+        let creator_tip = creator_fees * u64::from(SWEEP_TIP_BPS) / 10_000;
+        let staking_tip = staking_fees * u64::from(SWEEP_TIP_BPS) / 10_000;
+
+        assert_eq!(market.fees.pending_creator_fees, 0);
+        assert_eq!(market.fees.pending_staking_fees, 0);
+        assert!(staking.acc_reward_amount_per_share > 0);
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &alice) - alice_balance_before,
+            creator_fees - creator_tip
+        );
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &mallory) - mallory_balance_before,
+            creator_tip + staking_tip
+        );
+    }
+
+    #[test]
+    fn sweep_fees_without_keeper() {
+        let mut testing_env = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let alice = make_address("alice");
+
+        let market_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let alice_balance_before = testing_env.svm.get_balance(&quote_token_mint, &alice);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env)])
+            .unwrap();
+
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &alice) - alice_balance_before,
+            market_before.fees.pending_creator_fees
+        );
+    }
+
+    #[test]
+    fn sweep_fees_without_pending_fees() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env.svm.warp(1);
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAmount);
+    }
+}
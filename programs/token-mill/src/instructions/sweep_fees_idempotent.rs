@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::clock::get_clock;
+
+use super::sweep_fees::{self, get_sweepable_fees, SweepFees};
+
+/// Bundle-friendly variant of `sweep_fees`: succeeds without changes if there are no fees to
+/// sweep.
+pub fn handler(ctx: Context<SweepFees>) -> Result<()> {
+    {
+        let market = &mut ctx.accounts.market.load_mut()?;
+
+        let clock = get_clock()?;
+        market.apply_scheduled_updates(clock.slot, clock.unix_timestamp);
+
+        if get_sweepable_fees(market, ctx.accounts.staking.as_deref()) == (0, 0) {
+            return Ok(());
+        }
+    }
+
+    sweep_fees::handler(ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Market;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            DepositAction, SwapAction, SweepFeesAction, TokenMillEnv, DEFAULT_TOTAL_SUPPLY,
+        },
+        make_address, SwapAmountType, SwapType,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000_000;
+
+    fn setup_env() -> TokenMillEnv {
+        let mut testing_env = TokenMillEnv::default().with_staking(STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[
+                &DepositAction::new(&testing_env, STAKE_AMOUNT),
+                &SwapAction::new(
+                    &testing_env,
+                    SwapType::Buy,
+                    SwapAmountType::ExactOutput,
+                    DEFAULT_TOTAL_SUPPLY / 2,
+                    u64::MAX,
+                    None,
+                ),
+            ])
+            .unwrap();
+
+        testing_env.svm.change_payer("mallory");
+
+        testing_env
+    }
+
+    #[test]
+    fn sweep_fees_idempotent() {
+        let mut testing_env = setup_env();
+
+        let quote_token_mint = testing_env.quote_token_mint.unwrap();
+        let alice = make_address("alice");
+
+        let market_before = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+        let alice_balance_before = testing_env.svm.get_balance(&quote_token_mint, &alice);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env).idempotent()])
+            .unwrap();
+
+        let market = testing_env
+            .svm
+            .get_parsed_account::<Market>(&testing_env.market);
+
+        assert_eq!(market.fees.pending_creator_fees, 0);
+        assert_eq!(market.fees.pending_staking_fees, 0);
+        assert_eq!(
+            testing_env.svm.get_balance(&quote_token_mint, &alice) - alice_balance_before,
+            market_before.fees.pending_creator_fees
+        );
+    }
+
+    #[test]
+    fn sweep_fees_idempotent_without_pending_fees() {
+        let mut testing_env = setup_env();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env)])
+            .unwrap();
+
+        testing_env
+            .svm
+            .execute_actions(&[&SweepFeesAction::new(&testing_env).idempotent()])
+            .unwrap();
+    }
+}
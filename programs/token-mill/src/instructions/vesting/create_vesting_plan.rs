@@ -1,4 +1,5 @@
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
     events::TokenMillVestingPlanCreationEvent,
     manager::{staking_manager, token_manager::transfer_from_eoa},
@@ -59,26 +60,20 @@ pub fn handler(
     vesting_duration: i64,
     cliff_duration: i64,
 ) -> Result<()> {
-    require!(
-        start > 0 && vesting_duration > 0 && cliff_duration > 0,
-        TokenMillError::InvalidVestingDuration
-    );
-
-    require!(
-        vesting_duration > cliff_duration,
-        TokenMillError::InvalidVestingDuration
-    );
-
-    require!(
-        start + vesting_duration > Clock::get()?.unix_timestamp,
-        TokenMillError::InvalidVestingStartTime
-    );
+    VestingPlan::check_schedule(
+        start,
+        vesting_duration,
+        cliff_duration,
+        get_clock()?.unix_timestamp,
+    )?;
 
     let market = &mut ctx.accounts.market.load_mut()?;
     let staking = &mut ctx.accounts.staking;
     let stake_position = &mut ctx.accounts.stake_position;
     let vesting_plan = &mut ctx.accounts.vesting_plan;
 
+    require!(market.are_prices_set(), TokenMillError::PricesNotSet);
+
     vesting_plan.initialize(
         stake_position.key(),
         start,
@@ -87,7 +82,13 @@ pub fn handler(
         cliff_duration,
     )?;
 
-    staking_manager::deposit_vested(market, staking, stake_position, vesting_amount)?;
+    staking_manager::deposit_vested(
+        market,
+        staking,
+        stake_position,
+        vesting_amount,
+        get_clock()?.unix_timestamp,
+    )?;
 
     transfer_from_eoa(
         &ctx.accounts.base_token_mint,
@@ -0,0 +1,209 @@
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    events::TokenMillVestingPlanCreationEvent,
+    manager::{staking_manager, token_manager::transfer_from_eoa},
+    state::{Market, MarketStaking, StakePosition, TokenMillConfig},
+    VestingPlan,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateVestingPlanFromTemplate<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Account<'info, MarketStaking>,
+
+    #[account(
+        mut,
+        has_one = market @ TokenMillError::InvalidMarket,
+        has_one = user @ TokenMillError::InvalidAuthority
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+
+    #[account(init, payer = user, space = 8 + VestingPlan::INIT_SPACE)]
+    pub vesting_plan: Account<'info, VestingPlan>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = market,
+        associated_token::token_program = base_token_program
+    )]
+    pub market_base_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = user,
+        associated_token::token_program = base_token_program
+    )]
+    pub user_base_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub base_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Same as `create_vesting_plan`, with the durations of a vesting template of the config.
+pub fn handler(
+    ctx: Context<CreateVestingPlanFromTemplate>,
+    start: i64,
+    vesting_amount: u64,
+    template_index: u8,
+) -> Result<()> {
+    let template = *ctx
+        .accounts
+        .config
+        .vesting_templates
+        .get(usize::from(template_index))
+        .ok_or(TokenMillError::InvalidVestingTemplate)?;
+
+    VestingPlan::check_schedule(
+        start,
+        template.vesting_duration,
+        template.cliff_duration,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    let market = &mut ctx.accounts.market.load_mut()?;
+    let staking = &mut ctx.accounts.staking;
+    let stake_position = &mut ctx.accounts.stake_position;
+    let vesting_plan = &mut ctx.accounts.vesting_plan;
+
+    require!(market.are_prices_set(), TokenMillError::PricesNotSet);
+
+    vesting_plan.initialize(
+        stake_position.key(),
+        start,
+        vesting_amount,
+        template.vesting_duration,
+        template.cliff_duration,
+    )?;
+
+    staking_manager::deposit_vested(
+        market,
+        staking,
+        stake_position,
+        vesting_amount,
+        get_clock()?.unix_timestamp,
+    )?;
+
+    transfer_from_eoa(
+        &ctx.accounts.base_token_mint,
+        &ctx.accounts.user,
+        &ctx.accounts.user_base_token_ata,
+        &ctx.accounts.market_base_token_ata,
+        &ctx.accounts.base_token_program,
+        vesting_amount,
+    )?;
+
+    emit_cpi!(TokenMillVestingPlanCreationEvent {
+        market: ctx.accounts.market.key(),
+        user: ctx.accounts.user.key(),
+        vesting_plan: vesting_plan.key(),
+        vesting_amount,
+        start,
+        vesting_duration: template.vesting_duration,
+        cliff_duration: template.cliff_duration,
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::VestingPlan;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{
+            tm_parse_error, AddVestingTemplateAction, CreateVestingPlanFromTemplateAction,
+            TokenMillEnv,
+        },
+        TokenMillError,
+    };
+
+    const VESTING_AMOUNT: u64 = 1_000_000_000;
+    const STARTING_SLOT: i64 = 333;
+
+    fn setup_env() -> (TokenMillEnv, AddVestingTemplateAction) {
+        let mut testing_env = TokenMillEnv::default().with_staking(VESTING_AMOUNT);
+
+        testing_env.svm.warp(STARTING_SLOT);
+
+        let add_template_action = AddVestingTemplateAction::default();
+
+        testing_env.svm.change_payer("admin");
+
+        testing_env
+            .svm
+            .execute_actions(&[&add_template_action])
+            .unwrap();
+
+        testing_env.svm.change_payer("bob");
+
+        (testing_env, add_template_action)
+    }
+
+    #[test]
+    fn create_vesting_plan_from_template() {
+        let (mut testing_env, add_template_action) = setup_env();
+
+        let action = CreateVestingPlanFromTemplateAction::new(
+            &testing_env,
+            VESTING_AMOUNT,
+            STARTING_SLOT,
+            0,
+        );
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let vesting_plan = testing_env
+            .svm
+            .get_parsed_account::<VestingPlan>(&action.vesting_plan);
+
+        assert_eq!(vesting_plan.amount_vested, VESTING_AMOUNT);
+        assert_eq!(vesting_plan.start, STARTING_SLOT);
+        assert_eq!(
+            vesting_plan.vesting_duration,
+            add_template_action.template.vesting_duration
+        );
+        assert_eq!(
+            vesting_plan.cliff_duration,
+            add_template_action.template.cliff_duration
+        );
+    }
+
+    #[test]
+    fn create_vesting_plan_from_unknown_template() {
+        let (mut testing_env, _) = setup_env();
+
+        let action = CreateVestingPlanFromTemplateAction::new(
+            &testing_env,
+            VESTING_AMOUNT,
+            STARTING_SLOT,
+            1,
+        );
+
+        let result = testing_env.svm.execute_actions(&[&action]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidVestingTemplate);
+    }
+}
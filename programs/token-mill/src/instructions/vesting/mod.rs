@@ -1,5 +1,7 @@
 pub mod create_vesting_plan;
+pub mod create_vesting_plan_from_template;
 pub mod release;
 
 pub use create_vesting_plan::*;
+pub use create_vesting_plan_from_template::*;
 pub use release::*;
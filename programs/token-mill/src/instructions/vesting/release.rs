@@ -1,9 +1,10 @@
 use crate::{
+    clock::get_clock,
     errors::TokenMillError,
     events::TokenMillVestingPlanReleaseEvent,
     manager::{staking_manager, token_manager::transfer_from_pda},
-    state::{Market, MarketStaking, StakePosition},
-    VestingPlan, MARKET_PDA_SEED,
+    state::{ClaimDelegate, Market, MarketStaking, StakePosition},
+    VestingPlan,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -45,36 +46,49 @@ pub struct Release<'info> {
     )]
     pub user_base_token_ata: InterfaceAccount<'info, TokenAccount>,
 
-    pub user: Signer<'info>,
+    /// CHECK: Owner of the stake position, signs unless the release is triggered by its delegate
+    pub user: UncheckedAccount<'info>,
 
     pub base_token_program: Interface<'info, TokenInterface>,
+
+    #[account(has_one = stake_position @ TokenMillError::InvalidStakePosition)]
+    pub claim_delegate: Option<Account<'info, ClaimDelegate>>,
+
+    pub delegate: Option<Signer<'info>>,
 }
 
 pub fn handler(ctx: Context<Release>) -> Result<u64> {
+    ClaimDelegate::check_claim_authority(
+        &ctx.accounts.user,
+        ctx.accounts.claim_delegate.as_ref(),
+        ctx.accounts.delegate.as_ref(),
+    )?;
+
     let staking = &mut ctx.accounts.staking;
     let stake_position = &mut ctx.accounts.stake_position;
     let vesting_plan = &mut ctx.accounts.vesting_plan;
 
-    let current_time = Clock::get().unwrap().unix_timestamp;
+    let current_time = get_clock().unwrap().unix_timestamp;
 
     let amount_released = vesting_plan.release(current_time)?;
 
-    let market_bump = {
+    let market_seeds = {
         let market = &mut ctx.accounts.market.load_mut()?;
 
-        staking_manager::withdraw_vested(market, staking, stake_position, amount_released)?;
+        staking_manager::withdraw_vested(
+            market,
+            staking,
+            stake_position,
+            amount_released,
+            current_time,
+        )?;
 
-        market.bump
+        market.seeds()
     };
 
     if amount_released > 0 {
         let base_token_mint = &ctx.accounts.base_token_mint;
-        let base_token_mint_key = base_token_mint.key();
-        let seeds = [
-            MARKET_PDA_SEED.as_bytes(),
-            base_token_mint_key.as_ref(),
-            &[market_bump],
-        ];
+        let seeds = market_seeds.signer_seeds();
 
         transfer_from_pda(
             base_token_mint,
@@ -99,8 +113,11 @@ pub fn handler(ctx: Context<Release>) -> Result<u64> {
 mod tests {
     use crate::VestingPlan;
     use joelana_test_utils::joelana_env::{
-        actions::token_mill::{CreateVestingPlanAction, ReleaseAction, TokenMillEnv},
-        TokenType,
+        actions::token_mill::{
+            tm_parse_error, CreateVestingPlanAction, ReleaseAction, SetClaimDelegateAction,
+            TokenMillEnv,
+        },
+        make_address, TokenMillError, TokenType,
     };
     use rstest::rstest;
 
@@ -235,4 +252,57 @@ mod tests {
 
         assert_eq!(vesting_plan.amount_released, VESTING_AMOUNT);
     }
+
+    #[test]
+    fn release_with_delegate() {
+        let (mut testing_env, action) = setup_env(TokenType::Token2022);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetClaimDelegateAction::new(
+                &testing_env,
+                make_address("carol"),
+            )])
+            .unwrap();
+
+        testing_env.svm.warp(VESTING_DURATION + 1);
+
+        let delegate = testing_env.svm.change_payer("carol");
+        let action = action.with_delegate(delegate);
+
+        testing_env.svm.execute_actions(&[&action]).unwrap();
+
+        let vesting_plan = testing_env
+            .svm
+            .get_parsed_account::<VestingPlan>(&action.vesting_plan);
+
+        assert_eq!(vesting_plan.amount_released, VESTING_AMOUNT);
+    }
+
+    #[test]
+    fn release_with_invalid_delegate() {
+        let (mut testing_env, action) = setup_env(TokenType::Token2022);
+
+        testing_env
+            .svm
+            .execute_actions(&[&SetClaimDelegateAction::new(
+                &testing_env,
+                make_address("carol"),
+            )])
+            .unwrap();
+
+        testing_env.svm.warp(VESTING_DURATION + 1);
+
+        let delegate = testing_env.svm.change_payer("mallory");
+
+        let result = testing_env
+            .svm
+            .execute_actions(&[&action.with_delegate(delegate)]);
+
+        assert!(result.is_err());
+
+        let error = tm_parse_error(result).unwrap();
+
+        assert_eq!(error, TokenMillError::InvalidAuthority);
+    }
 }
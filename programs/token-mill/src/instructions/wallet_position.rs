@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    clock::get_clock,
+    errors::TokenMillError,
+    state::{Market, MarketReferralAccrual, MarketStaking, ReferralAccount, StakePosition},
+    VestingPlan,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, Default, Clone, PartialEq)]
+pub struct WalletPositionSummary {
+    pub base_balance: u64,
+    pub amount_staked: u64,
+    pub amount_vesting: u64,
+    pub pending_staking_rewards: u64,
+    pub vesting_claimable: u64,
+    /// Claimable referral fees plus the fees held back by the market accrual, in quote tokens.
+    pub pending_referral_fees: u64,
+}
+
+#[derive(Accounts)]
+pub struct WalletPositionView<'info> {
+    #[account(has_one = base_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Wallet whose position is returned
+    pub wallet: UncheckedAccount<'info>,
+
+    #[account(
+        token::mint = base_token_mint,
+        token::authority = wallet
+    )]
+    pub wallet_base_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(has_one = market @ TokenMillError::InvalidMarket)]
+    pub staking: Option<Account<'info, MarketStaking>>,
+
+    #[account(
+        has_one = market @ TokenMillError::InvalidMarket,
+        constraint = stake_position.user == wallet.key() @ TokenMillError::InvalidStakePosition
+    )]
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    #[account(
+        constraint = referral_account.referrer == wallet.key() @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_account: Option<Account<'info, ReferralAccount>>,
+
+    #[account(
+        constraint = referral_account_quote_token_ata.mint == market.load()?.quote_token_mint @ TokenMillError::InvalidMintAccount,
+        constraint = Some(referral_account_quote_token_ata.owner) == referral_account.as_ref().map(|account| account.key()) @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_account_quote_token_ata: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        has_one = market @ TokenMillError::InvalidMarket,
+        constraint = Some(referral_accrual.referral_token_account) == referral_account_quote_token_ata.as_ref().map(|ata| ata.key()) @ TokenMillError::InvalidReferralAccount
+    )]
+    pub referral_accrual: Option<Account<'info, MarketReferralAccrual>>,
+}
+
+/// View gathering the position of a wallet on a market, so that it doesn't have to be rebuilt
+/// off-chain from each account. Missing accounts count as empty, the vesting plans of the stake
+/// position are passed as remaining accounts.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, WalletPositionView<'info>>,
+) -> Result<WalletPositionSummary> {
+    let market = ctx.accounts.market.load()?;
+    let current_time = get_clock()?.unix_timestamp;
+
+    let mut position = WalletPositionSummary {
+        base_balance: ctx
+            .accounts
+            .wallet_base_token_account
+            .as_ref()
+            .map_or(0, |token_account| token_account.amount),
+        ..Default::default()
+    };
+
+    if let Some(stake_position) = &ctx.accounts.stake_position {
+        let mut stake_position = (**stake_position).clone();
+
+        if let Some(staking) = &ctx.accounts.staking {
+            let acc_reward_amount_per_share = (**staking)
+                .clone()
+                .accrue_rewards(market.fees.pending_staking_fees)?;
+
+            stake_position.accrue_rewards(acc_reward_amount_per_share)?;
+        }
+
+        position.amount_staked = stake_position.amount_staked;
+        position.amount_vesting = stake_position.total_amount_vested;
+        position.pending_staking_rewards = stake_position.pending_rewards;
+
+        for account_info in ctx.remaining_accounts {
+            let mut vesting_plan = Account::<VestingPlan>::try_from(account_info)?;
+
+            require_keys_eq!(
+                vesting_plan.stake_position,
+                ctx.accounts.stake_position.as_ref().unwrap().key(),
+                TokenMillError::InvalidStakePosition
+            );
+
+            position.vesting_claimable += vesting_plan.release(current_time)?;
+        }
+    }
+
+    if let Some(referral_account_quote_token_ata) = &ctx.accounts.referral_account_quote_token_ata {
+        position.pending_referral_fees += referral_account_quote_token_ata.amount;
+    }
+
+    if let Some(referral_accrual) = &ctx.accounts.referral_accrual {
+        position.pending_referral_fees += referral_accrual.pending_fees;
+    }
+
+    Ok(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use joelana_test_utils::joelana_env::{
+        actions::token_mill::{DepositAction, SwapAction, TokenMillEnv, WalletPositionAction},
+        make_address, SwapAmountType, SwapType,
+    };
+
+    const STAKE_AMOUNT: u64 = 100_000_000_000;
+
+    fn get_wallet_position(
+        testing_env: &mut TokenMillEnv,
+        action: &WalletPositionAction,
+    ) -> WalletPositionSummary {
+        let metadata = testing_env.svm.execute_actions(&[action]).unwrap();
+
+        WalletPositionSummary::try_from_slice(&metadata.return_data.data).unwrap()
+    }
+
+    #[test]
+    fn wallet_position() {
+        let mut testing_env = TokenMillEnv::default().with_staking(2 * STAKE_AMOUNT);
+
+        testing_env
+            .svm
+            .execute_actions(&[&DepositAction::new(&testing_env, STAKE_AMOUNT)])
+            .unwrap();
+
+        let bob = make_address("bob");
+        let action = WalletPositionAction::new(&testing_env, bob).with_staking();
+
+        let position = get_wallet_position(&mut testing_env, &action);
+
+        assert_eq!(
+            position.base_balance,
+            testing_env
+                .svm
+                .get_balance(&testing_env.base_token_mint.unwrap(), &bob)
+        );
+        assert_eq!(position.amount_staked, STAKE_AMOUNT);
+        assert_eq!(position.pending_staking_rewards, 0);
+        assert_eq!(position.vesting_claimable, 0);
+        assert_eq!(position.pending_referral_fees, 0);
+
+        // Rewards accrue from the fees pending on the market, before any staking update
+        testing_env
+            .svm
+            .execute_actions(&[&SwapAction::new(
+                &testing_env,
+                SwapType::Buy,
+                SwapAmountType::ExactOutput,
+                STAKE_AMOUNT,
+                u64::MAX,
+                None,
+            )])
+            .unwrap();
+
+        let position = get_wallet_position(&mut testing_env, &action);
+
+        assert!(position.pending_staking_rewards > 0);
+    }
+
+    #[test]
+    fn wallet_position_without_accounts() {
+        let mut testing_env = TokenMillEnv::default();
+
+        let action = WalletPositionAction::new(&testing_env, make_address("dave"));
+
+        assert_eq!(
+            get_wallet_position(&mut testing_env, &action),
+            WalletPositionSummary::default()
+        );
+    }
+}
@@ -2,17 +2,25 @@ use anchor_lang::prelude::*;
 
 declare_id!("Ee26KkPa8mo4PJRcuHcGhuUQ6vvtyuY424xWpePJub6n");
 
+pub mod clock;
 pub mod constant;
 pub mod errors;
-mod events;
+pub mod events;
 mod instructions;
 pub mod manager;
-mod math;
+pub mod math;
+pub mod pda;
 pub mod state;
 
 
 use instructions::*;
-use manager::swap_manager::{SwapAmountType, SwapType};
+pub use instructions::{
+    GraduationProgressSummary, LaunchParams, MarketCreationParams, SwapQuote, WalletPositionSummary,
+};
+use manager::{
+    curve_manager::{CurveParameters, CurveShape},
+    swap_manager::{SwapAmountType, SwapType},
+};
 use state::*;
 
 #[program]
@@ -35,14 +43,17 @@ pub mod token_mill {
         )
     }
 
-    pub fn create_market(
-        ctx: Context<CreateMarket>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateMarket<'info>>,
         name: String,
         symbol: String,
         uri: String,
         total_supply: u64,
         creator_fee_share: u16,
         staking_fee_share: u16,
+        wallet_buy_limit: Option<WalletBuyLimit>,
+        swap_fee_bps: u16,
     ) -> Result<()> {
         instructions::create_market::handler(
             ctx,
@@ -52,17 +63,22 @@ pub mod token_mill {
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            wallet_buy_limit,
+            swap_fee_bps,
         )
     }
 
-    pub fn create_market_with_spl(
-        ctx: Context<CreateMarketWithSpl>,
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_market_with_spl<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateMarketWithSpl<'info>>,
         name: String,
         symbol: String,
         uri: String,
         total_supply: u64,
         creator_fee_share: u16,
         staking_fee_share: u16,
+        use_token_2022: bool,
+        wallet_buy_limit: Option<WalletBuyLimit>,
     ) -> Result<()> {
         instructions::create_market_with_spl::handler(
             ctx,
@@ -72,23 +88,95 @@ pub mod token_mill {
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            use_token_2022,
+            wallet_buy_limit,
+        )
+    }
+
+    pub fn create_markets_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateMarketsBatch<'info>>,
+        markets: Vec<MarketCreationParams>,
+    ) -> Result<()> {
+        instructions::create_markets_batch::handler(ctx, markets)
+    }
+
+    pub fn launch<'info>(
+        ctx: Context<'_, '_, '_, 'info, Launch<'info>>,
+        params: LaunchParams,
+    ) -> Result<()> {
+        instructions::launch::handler(ctx, params)
+    }
+
+    pub fn create_secondary_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateSecondaryMarket<'info>>,
+        total_supply: u64,
+        creator_fee_share: u16,
+        staking_fee_share: u16,
+    ) -> Result<()> {
+        instructions::create_secondary_market::handler(
+            ctx,
+            total_supply,
+            creator_fee_share,
+            staking_fee_share,
         )
     }
 
     pub fn set_market_prices(
-        ctx: Context<MarketSettingsUpdate>,
+        ctx: Context<MarketPricesUpdate>,
         bid_prices: [u64; constant::PRICES_LENGTH],
         ask_prices: [u64; constant::PRICES_LENGTH],
     ) -> Result<()> {
         instructions::set_market_prices::handler(ctx, bid_prices, ask_prices)
     }
 
-    pub fn swap(
-        ctx: Context<Swap>,
+    pub fn append_prices(
+        ctx: Context<MarketPricesUpdate>,
+        start_index: u8,
+        bid_prices: Vec<u64>,
+        ask_prices: Vec<u64>,
+    ) -> Result<()> {
+        instructions::append_prices::handler(ctx, start_index, bid_prices, ask_prices)
+    }
+
+    pub fn set_prices_for_raise(
+        ctx: Context<MarketPricesUpdate>,
+        curve_shape: CurveShape,
+        target_raise: u64,
+        bid_spread_bps: u16,
+    ) -> Result<()> {
+        instructions::set_prices_for_raise::handler(ctx, curve_shape, target_raise, bid_spread_bps)
+    }
+
+    pub fn set_prices_from_preset(
+        ctx: Context<MarketPricesUpdate>,
+        parameters: CurveParameters,
+    ) -> Result<()> {
+        instructions::set_prices_from_preset::handler(ctx, parameters)
+    }
+
+    pub fn reset_prices(
+        ctx: Context<MarketPricesUpdate>,
+        bid_prices: [u64; constant::PRICES_LENGTH],
+        ask_prices: [u64; constant::PRICES_LENGTH],
+    ) -> Result<()> {
+        instructions::reset_prices::handler(ctx, bid_prices, ask_prices)
+    }
+
+    pub fn compact_price_tail(
+        ctx: Context<MarketSettingsUpdate>,
+        price_points: u8,
+        tail_supply_bps: u16,
+    ) -> Result<()> {
+        instructions::compact_price_tail::handler(ctx, price_points, tail_supply_bps)
+    }
+
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
         swap_type: SwapType,
         swap_amount_type: SwapAmountType,
         amount: u64,
         other_amount_threshold: u64,
+        deadline: Option<i64>,
     ) -> Result<(u64, u64)> {
         instructions::swap::handler(
             ctx,
@@ -96,18 +184,101 @@ pub mod token_mill {
             swap_amount_type,
             amount,
             other_amount_threshold,
+            deadline,
         )
     }
 
+    pub fn prepare_trade_accounts(ctx: Context<PrepareTradeAccounts>) -> Result<()> {
+        instructions::prepare_trade_accounts::handler(ctx)
+    }
+
+    pub fn quote_swap(
+        ctx: Context<QuoteSwapView>,
+        swap_type: SwapType,
+        swap_amount_type: SwapAmountType,
+        amount: u64,
+    ) -> Result<SwapQuote> {
+        instructions::quote_swap::handler(ctx, swap_type, swap_amount_type, amount)
+    }
+
+    pub fn market_health(ctx: Context<MarketHealthView>, max_oracle_staleness: u64) -> Result<u32> {
+        instructions::market_health::handler(ctx, max_oracle_staleness)
+    }
+
+    pub fn graduation_progress(
+        ctx: Context<GraduationProgressView>,
+    ) -> Result<GraduationProgressSummary> {
+        instructions::graduation_progress::handler(ctx)
+    }
+
+    pub fn reconcile_fees(ctx: Context<ReconcileFees>) -> Result<()> {
+        instructions::reconcile_fees::handler(ctx)
+    }
+
+    pub fn backfill_config(ctx: Context<BackfillConfig>) -> Result<()> {
+        instructions::backfill_config::handler(ctx)
+    }
+
+    pub fn backfill_market(ctx: Context<BackfillMarket>) -> Result<()> {
+        instructions::backfill_market::handler(ctx)
+    }
+
+    pub fn sweep_fees(ctx: Context<SweepFees>) -> Result<()> {
+        instructions::sweep_fees::handler(ctx)
+    }
+
+    pub fn sweep_fees_idempotent(ctx: Context<SweepFees>) -> Result<()> {
+        instructions::sweep_fees_idempotent::handler(ctx)
+    }
+
+    pub fn create_creator_activity(ctx: Context<CreateCreatorActivity>) -> Result<()> {
+        instructions::create_creator_activity::handler(ctx)
+    }
+
+    pub fn create_launch_purchase(ctx: Context<CreateLaunchPurchase>) -> Result<()> {
+        instructions::create_launch_purchase::handler(ctx)
+    }
+
+    pub fn wallet_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, WalletPositionView<'info>>,
+    ) -> Result<WalletPositionSummary> {
+        instructions::wallet_position::handler(ctx)
+    }
+
+    pub fn migrate<'info>(ctx: Context<'_, '_, '_, 'info, Migrate<'info>>) -> Result<()> {
+        instructions::graduation::migrate::handler(ctx)
+    }
+
+    pub fn migrate_idempotent<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigrateIdempotent<'info>>,
+    ) -> Result<()> {
+        instructions::graduation::migrate_idempotent::handler(ctx)
+    }
+
+    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+        instructions::graduation::close_market::handler(ctx)
+    }
+
     // Staking
     pub fn create_staking(ctx: Context<CreateStaking>) -> Result<()> {
         instructions::staking::create_staking::handler(ctx)
     }
 
+    pub fn create_staking_idempotent(ctx: Context<CreateStakingIdempotent>) -> Result<()> {
+        instructions::staking::create_staking_idempotent::handler(ctx)
+    }
+
     pub fn create_stake_position(ctx: Context<CreateStakePosition>) -> Result<()> {
         instructions::staking::create_stake_position::handler(ctx)
     }
 
+    pub fn create_indexed_stake_position(
+        ctx: Context<CreateIndexedStakePosition>,
+        index: u32,
+    ) -> Result<()> {
+        instructions::staking::create_indexed_stake_position::handler(ctx, index)
+    }
+
     pub fn deposit(ctx: Context<StakeUpdate>, amount: u64) -> Result<()> {
         instructions::staking::deposit::handler(ctx, amount)
     }
@@ -120,6 +291,256 @@ pub mod token_mill {
         instructions::staking::claim_staking_rewards::handler(ctx)
     }
 
+    pub fn get_staking_apr(ctx: Context<StakingAprView>) -> Result<u64> {
+        instructions::staking::get_staking_apr::handler(ctx)
+    }
+
+    pub fn split_position(ctx: Context<StakeTransfer>, amount: u64) -> Result<()> {
+        instructions::staking::split_position::handler(ctx, amount)
+    }
+
+    pub fn merge_positions(ctx: Context<StakeTransfer>) -> Result<()> {
+        instructions::staking::merge_positions::handler(ctx)
+    }
+
+    pub fn set_claim_delegate(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+        instructions::staking::set_claim_delegate::handler(ctx, delegate)
+    }
+
+    pub fn remove_claim_delegate(ctx: Context<RemoveClaimDelegate>) -> Result<()> {
+        instructions::staking::remove_claim_delegate::handler(ctx)
+    }
+
+    pub fn set_auto_claim(ctx: Context<SetAutoClaim>, auto_claim: bool) -> Result<()> {
+        instructions::staking::set_auto_claim::handler(ctx, auto_claim)
+    }
+
+    pub fn lock_stake(ctx: Context<StakeLock>, lock_tier: u8) -> Result<()> {
+        instructions::staking::lock_stake::handler(ctx, lock_tier)
+    }
+
+    pub fn expire_stake_lock(ctx: Context<StakeLockExpiry>) -> Result<()> {
+        instructions::staking::expire_stake_lock::handler(ctx)
+    }
+
+    pub fn backfill_stake_position(ctx: Context<BackfillStakePosition>) -> Result<()> {
+        instructions::staking::backfill_stake_position::handler(ctx)
+    }
+
+    // Revenue reports
+    pub fn create_revenue_report(ctx: Context<CreateRevenueReport>, epoch: u64) -> Result<()> {
+        instructions::revenue::create_revenue_report::handler(ctx, epoch)
+    }
+
+    pub fn finalize_revenue_report(ctx: Context<FinalizeRevenueReport>) -> Result<()> {
+        instructions::revenue::finalize_revenue_report::handler(ctx)
+    }
+
+    pub fn create_trader_volume(ctx: Context<CreateTraderVolume>) -> Result<()> {
+        instructions::revenue::create_trader_volume::handler(ctx)
+    }
+
+    pub fn claim_trader_rebate(ctx: Context<ClaimTraderRebate>) -> Result<()> {
+        instructions::revenue::claim_trader_rebate::handler(ctx)
+    }
+
+    // Buyback orders
+    pub fn place_buyback_order(ctx: Context<PlaceBuybackOrder>, price_floor: u64) -> Result<()> {
+        instructions::buyback::place_buyback_order::handler(ctx, price_floor)
+    }
+
+    pub fn execute_buyback_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteBuybackOrder<'info>>,
+        quote_amount: u64,
+    ) -> Result<()> {
+        instructions::buyback::execute_buyback_order::handler(ctx, quote_amount)
+    }
+
+    pub fn cancel_buyback_order(ctx: Context<CancelBuybackOrder>) -> Result<()> {
+        instructions::buyback::cancel_buyback_order::handler(ctx)
+    }
+
+    // Buy queue
+    pub fn create_buy_queue(
+        ctx: Context<CreateBuyQueue>,
+        max_quote_amount: u64,
+        open_slot: u64,
+    ) -> Result<()> {
+        instructions::buy_queue::create_buy_queue::handler(ctx, max_quote_amount, open_slot)
+    }
+
+    pub fn place_queued_buy_order(
+        ctx: Context<PlaceQueuedBuyOrder>,
+        quote_amount: u64,
+    ) -> Result<()> {
+        instructions::buy_queue::place_queued_buy_order::handler(ctx, quote_amount)
+    }
+
+    pub fn cancel_queued_buy_order(ctx: Context<CancelQueuedBuyOrder>) -> Result<()> {
+        instructions::buy_queue::cancel_queued_buy_order::handler(ctx)
+    }
+
+    pub fn fill_queued_buy_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, FillQueuedBuyOrder<'info>>,
+    ) -> Result<()> {
+        instructions::buy_queue::fill_queued_buy_order::handler(ctx)
+    }
+
+    pub fn open_buy_queue(ctx: Context<OpenBuyQueue>) -> Result<()> {
+        instructions::buy_queue::open_buy_queue::handler(ctx)
+    }
+
+    pub fn set_protocol_fee_converter(
+        ctx: Context<SetProtocolFeeConverter>,
+        treasury: Pubkey,
+        max_price: u64,
+    ) -> Result<()> {
+        instructions::protocol_fees::set_protocol_fee_converter::handler(ctx, treasury, max_price)
+    }
+
+    pub fn convert_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConvertProtocolFees<'info>>,
+        quote_amount: u64,
+        min_base_amount: u64,
+    ) -> Result<()> {
+        instructions::protocol_fees::convert_protocol_fees::handler(
+            ctx,
+            quote_amount,
+            min_base_amount,
+        )
+    }
+
+    pub fn set_protocol_fee_split(
+        ctx: Context<SetProtocolFeeSplit>,
+        recipients: Vec<ProtocolFeeShare>,
+    ) -> Result<()> {
+        instructions::protocol_fees::set_protocol_fee_split::handler(ctx, recipients)
+    }
+
+    pub fn distribute_protocol_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, DistributeProtocolFees<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::protocol_fees::distribute_protocol_fees::handler(ctx, amount)
+    }
+
+    // Sell orders
+    pub fn split_sell<'info>(
+        ctx: Context<'_, '_, '_, 'info, SplitSell<'info>>,
+        base_amount: u64,
+        limit_price: u64,
+        min_quote_amount_out: u64,
+    ) -> Result<()> {
+        instructions::sell_orders::split_sell::handler(
+            ctx,
+            base_amount,
+            limit_price,
+            min_quote_amount_out,
+        )
+    }
+
+    pub fn execute_sell_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteSellOrder<'info>>,
+        base_amount: u64,
+    ) -> Result<()> {
+        instructions::sell_orders::execute_sell_order::handler(ctx, base_amount)
+    }
+
+    pub fn cancel_sell_order(ctx: Context<CancelSellOrder>) -> Result<()> {
+        instructions::sell_orders::cancel_sell_order::handler(ctx)
+    }
+
+    // Gas vaults
+    pub fn deposit_gas_vault(ctx: Context<DepositGasVault>, amount: u64) -> Result<()> {
+        instructions::gas_vault::deposit_gas_vault::handler(ctx, amount)
+    }
+
+    pub fn withdraw_gas_vault(ctx: Context<WithdrawGasVault>, amount: u64) -> Result<()> {
+        instructions::gas_vault::withdraw_gas_vault::handler(ctx, amount)
+    }
+
+    pub fn create_sponsored_account(ctx: Context<CreateSponsoredAccount>) -> Result<()> {
+        instructions::gas_vault::create_sponsored_account::handler(ctx)
+    }
+
+    // Airdrops
+    pub fn create_quote_airdrop(
+        ctx: Context<CreateQuoteAirdrop>,
+        merkle_root: [u8; 32],
+        amount: u64,
+    ) -> Result<()> {
+        instructions::airdrop::create_quote_airdrop::handler(ctx, merkle_root, amount)
+    }
+
+    pub fn claim_quote_airdrop(
+        ctx: Context<ClaimQuoteAirdrop>,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::airdrop::claim_quote_airdrop::handler(ctx, amount, proof)
+    }
+
+    pub fn close_quote_airdrop(ctx: Context<CloseQuoteAirdrop>) -> Result<()> {
+        instructions::airdrop::close_quote_airdrop::handler(ctx)
+    }
+
+    pub fn publish_holder_rewards(
+        ctx: Context<PublishHolderRewards>,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::airdrop::publish_holder_rewards::handler(ctx, merkle_root)
+    }
+
+    // Price feeds
+    pub fn create_price_feed(ctx: Context<CreatePriceFeed>) -> Result<()> {
+        instructions::oracle::create_price_feed::handler(ctx)
+    }
+
+    pub fn push_price(ctx: Context<PushPrice>) -> Result<()> {
+        instructions::oracle::push_price::handler(ctx)
+    }
+
+    pub fn create_risk_params(ctx: Context<CreateRiskParams>) -> Result<()> {
+        instructions::oracle::create_risk_params::handler(ctx)
+    }
+
+    pub fn push_risk_params(ctx: Context<PushRiskParams>) -> Result<()> {
+        instructions::oracle::push_risk_params::handler(ctx)
+    }
+
+    pub fn create_market_oracle(ctx: Context<CreateMarketOracle>) -> Result<()> {
+        instructions::oracle::create_market_oracle::handler(ctx)
+    }
+
+    pub fn get_twap(ctx: Context<TwapView>, window: i64) -> Result<u64> {
+        instructions::oracle::get_twap::handler(ctx, window)
+    }
+
+    pub fn create_market_stats(ctx: Context<CreateMarketStats>) -> Result<()> {
+        instructions::oracle::create_market_stats::handler(ctx)
+    }
+
+    pub fn roll_market_stats(ctx: Context<RollMarketStats>) -> Result<()> {
+        instructions::oracle::roll_market_stats::handler(ctx)
+    }
+
+    // Creator multisigs
+    pub fn create_creator_multisig(
+        ctx: Context<CreateCreatorMultisig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::multisig::create_creator_multisig::handler(ctx, members, threshold)
+    }
+
+    pub fn execute_creator_instruction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatorInstructionExecution<'info>>,
+        account_count: u8,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::multisig::execute_creator_instruction::handler(ctx, account_count, data)
+    }
+
     // Vesting
     pub fn create_vesting_plan(
         ctx: Context<CreateVestingPlan>,
@@ -137,6 +558,20 @@ pub mod token_mill {
         )
     }
 
+    pub fn create_vesting_plan_from_template(
+        ctx: Context<CreateVestingPlanFromTemplate>,
+        start: i64,
+        vesting_amount: u64,
+        template_index: u8,
+    ) -> Result<()> {
+        instructions::vesting::create_vesting_plan_from_template::handler(
+            ctx,
+            start,
+            vesting_amount,
+            template_index,
+        )
+    }
+
     pub fn release(ctx: Context<Release>) -> Result<u64> {
         instructions::vesting::release::handler(ctx)
     }
@@ -149,10 +584,38 @@ pub mod token_mill {
         instructions::referrals::create_referral_account::handler(ctx, referrer)
     }
 
+    pub fn create_referral_code(ctx: Context<CreateReferralCode>, code: [u8; 32]) -> Result<()> {
+        instructions::referrals::create_referral_code::handler(ctx, code)
+    }
+
+    pub fn create_referrer_binding(ctx: Context<CreateReferrerBinding>) -> Result<()> {
+        instructions::referrals::create_referrer_binding::handler(ctx)
+    }
+
     pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<()> {
         instructions::referrals::claim_referral_fees::handler(ctx)
     }
 
+    pub fn create_market_referral_accrual(ctx: Context<CreateMarketReferralAccrual>) -> Result<()> {
+        instructions::referrals::create_market_referral_accrual::handler(ctx)
+    }
+
+    pub fn claim_market_referral_fees(ctx: Context<ClaimMarketReferralFees>) -> Result<()> {
+        instructions::referrals::claim_market_referral_fees::handler(ctx)
+    }
+
+    pub fn create_market_referral_code(
+        ctx: Context<CreateMarketReferralCode>,
+        code: [u8; 32],
+        creator_fee_share: u16,
+    ) -> Result<()> {
+        instructions::referrals::create_market_referral_code::handler(ctx, code, creator_fee_share)
+    }
+
+    pub fn close_market_referral_code(ctx: Context<CloseMarketReferralCode>) -> Result<()> {
+        instructions::referrals::close_market_referral_code::handler(ctx)
+    }
+
     // Creator instructions
     pub fn update_creator(ctx: Context<MarketSettingsUpdate>, new_creator: Pubkey) -> Result<()> {
         instructions::update_creator::handler(ctx, new_creator)
@@ -170,10 +633,126 @@ pub mod token_mill {
         )
     }
 
+    pub fn adopt_new_fee_defaults(
+        ctx: Context<AdoptNewFeeDefaults>,
+        new_creator_fee_share: u16,
+        new_staking_fee_share: u16,
+    ) -> Result<()> {
+        instructions::adopt_new_fee_defaults::handler(
+            ctx,
+            new_creator_fee_share,
+            new_staking_fee_share,
+        )
+    }
+
+    pub fn boost_staking_rewards(
+        ctx: Context<BoostStakingRewards>,
+        amount: u64,
+        source: StakingBoostSource,
+    ) -> Result<()> {
+        instructions::boost_staking_rewards::handler(ctx, amount, source)
+    }
+
+    pub fn update_market_max_swap_size(
+        ctx: Context<MarketSettingsUpdate>,
+        new_max_swap_size_bps: u16,
+    ) -> Result<()> {
+        instructions::update_market_max_swap_size::handler(ctx, new_max_swap_size_bps)
+    }
+
+    pub fn update_holder_rewards_fee(
+        ctx: Context<MarketSettingsUpdate>,
+        new_holder_rewards_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::update_holder_rewards_fee::handler(ctx, new_holder_rewards_fee_bps)
+    }
+
+    pub fn update_launch_fee(
+        ctx: Context<MarketSettingsUpdate>,
+        launch_fee_bps: u16,
+        launch_fee_slots: u64,
+    ) -> Result<()> {
+        instructions::update_launch_fee::handler(ctx, launch_fee_bps, launch_fee_slots)
+    }
+
+    pub fn update_emission_schedule(
+        ctx: Context<MarketSettingsUpdate>,
+        initial_supply: u64,
+        start: i64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::update_emission_schedule::handler(ctx, initial_supply, start, duration)
+    }
+
+    pub fn update_min_referred_volume(
+        ctx: Context<MarketSettingsUpdate>,
+        new_min_referred_volume: u64,
+    ) -> Result<()> {
+        instructions::update_min_referred_volume::handler(ctx, new_min_referred_volume)
+    }
+
+    pub fn update_max_wallet_holding(
+        ctx: Context<MarketSettingsUpdate>,
+        new_max_wallet_holding_bps: u16,
+    ) -> Result<()> {
+        instructions::update_max_wallet_holding::handler(ctx, new_max_wallet_holding_bps)
+    }
+
+    pub fn update_staking_freeze(
+        ctx: Context<MarketSettingsUpdate>,
+        freeze_staking_on_migration: bool,
+    ) -> Result<()> {
+        instructions::update_staking_freeze::handler(ctx, freeze_staking_on_migration)
+    }
+
+    pub fn update_dynamic_fee(ctx: Context<MarketSettingsUpdate>, dynamic_fee: bool) -> Result<()> {
+        instructions::update_dynamic_fee::handler(ctx, dynamic_fee)
+    }
+
+    pub fn schedule_market_update(
+        ctx: Context<MarketSettingsUpdate>,
+        kind: ScheduledUpdateKind,
+        value: u64,
+        slot: u64,
+    ) -> Result<()> {
+        instructions::schedule_market_update::handler(ctx, kind, value, slot)
+    }
+
+    pub fn cancel_scheduled_update(ctx: Context<MarketSettingsUpdate>, index: u8) -> Result<()> {
+        instructions::cancel_scheduled_update::handler(ctx, index)
+    }
+
+    pub fn update_min_claim_amount(
+        ctx: Context<MarketSettingsUpdate>,
+        new_min_claim_amount: u64,
+    ) -> Result<()> {
+        instructions::update_min_claim_amount::handler(ctx, new_min_claim_amount)
+    }
+
     pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
         instructions::claim_creator_fees::handler(ctx)
     }
 
+    pub fn release_creation_bond(ctx: Context<ReleaseCreationBond>) -> Result<()> {
+        instructions::release_creation_bond::handler(ctx)
+    }
+
+    pub fn request_quote_draw(ctx: Context<QuoteDrawRequest>, amount: u64) -> Result<()> {
+        instructions::request_quote_draw::handler(ctx, amount)
+    }
+
+    pub fn execute_quote_draw(ctx: Context<ExecuteQuoteDraw>) -> Result<()> {
+        instructions::execute_quote_draw::handler(ctx)
+    }
+
+    pub fn create_swap_receipt_tree(
+        ctx: Context<CreateSwapReceiptTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::create_swap_receipt_tree::handler(ctx, max_depth, max_buffer_size)
+    }
+
     // Admin instructions
     pub fn create_quote_asset_badge(ctx: Context<CreateQuoteAssetBadge>) -> Result<()> {
         instructions::create_quote_asset_badge::handler(ctx)
@@ -186,6 +765,13 @@ pub mod token_mill {
         instructions::update_quote_asset_badge::handler(ctx, status)
     }
 
+    pub fn update_quote_token_price_feed(
+        ctx: Context<UpdateQuoteAssetBadge>,
+        price_feed_id: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::update_quote_token_price_feed::handler(ctx, price_feed_id)
+    }
+
     pub fn update_default_fee_shares(
         ctx: Context<ConfigUpdate>,
         new_default_protocol_fee_share: u16,
@@ -205,6 +791,244 @@ pub mod token_mill {
         instructions::update_protocol_fee_recipient::handler(ctx, new_protocol_fee_recipient)
     }
 
+    pub fn update_policy_program(
+        ctx: Context<ConfigUpdate>,
+        new_policy_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_policy_program::handler(ctx, new_policy_program)
+    }
+
+    pub fn update_creation_bond(
+        ctx: Context<ConfigUpdate>,
+        creation_bond: CreationBond,
+    ) -> Result<()> {
+        instructions::update_creation_bond::handler(ctx, creation_bond)
+    }
+
+    pub fn update_creator_draw(
+        ctx: Context<ConfigUpdate>,
+        creator_draw: CreatorDraw,
+    ) -> Result<()> {
+        instructions::update_creator_draw::handler(ctx, creator_draw)
+    }
+
+    pub fn slash_creation_bond(ctx: Context<SlashCreationBond>) -> Result<()> {
+        instructions::slash_creation_bond::handler(ctx)
+    }
+
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        change: ConfigChange,
+    ) -> Result<()> {
+        instructions::queue_config_change::handler(ctx, change)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::execute_config_change::handler(ctx)
+    }
+
+    pub fn cancel_config_change(ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::cancel_config_change::handler(ctx)
+    }
+
+    pub fn update_fee_hook_program(
+        ctx: Context<ConfigUpdate>,
+        new_fee_hook_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_fee_hook_program::handler(ctx, new_fee_hook_program)
+    }
+
+    pub fn update_graduation_hook_program(
+        ctx: Context<ConfigUpdate>,
+        new_graduation_hook_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::update_graduation_hook_program::handler(ctx, new_graduation_hook_program)
+    }
+
+    pub fn update_secondary_market_policy(
+        ctx: Context<ConfigUpdate>,
+        secondary_markets_enabled: bool,
+    ) -> Result<()> {
+        instructions::update_secondary_market_policy::handler(ctx, secondary_markets_enabled)
+    }
+
+    pub fn update_default_curve(
+        ctx: Context<ConfigUpdate>,
+        new_default_curve: Option<CurveParameters>,
+    ) -> Result<()> {
+        instructions::update_default_curve::handler(ctx, new_default_curve)
+    }
+
+    pub fn update_max_pause_duration(
+        ctx: Context<ConfigUpdate>,
+        new_max_pause_duration: u64,
+    ) -> Result<()> {
+        instructions::update_max_pause_duration::handler(ctx, new_max_pause_duration)
+    }
+
+    pub fn update_max_swap_size(
+        ctx: Context<ConfigUpdate>,
+        new_max_swap_size_bps: u16,
+    ) -> Result<()> {
+        instructions::update_max_swap_size::handler(ctx, new_max_swap_size_bps)
+    }
+
+    pub fn update_sweep_tip(ctx: Context<ConfigUpdate>, new_sweep_tip_bps: u16) -> Result<()> {
+        instructions::update_sweep_tip::handler(ctx, new_sweep_tip_bps)
+    }
+
+    pub fn update_creator_pause(
+        ctx: Context<ConfigUpdate>,
+        creator_pause_enabled: bool,
+    ) -> Result<()> {
+        instructions::update_creator_pause::handler(ctx, creator_pause_enabled)
+    }
+
+    pub fn update_max_daily_markets_per_creator(
+        ctx: Context<ConfigUpdate>,
+        new_max_daily_markets_per_creator: u16,
+    ) -> Result<()> {
+        instructions::update_max_daily_markets_per_creator::handler(
+            ctx,
+            new_max_daily_markets_per_creator,
+        )
+    }
+
+    pub fn update_referral_tiers(
+        ctx: Context<ConfigUpdate>,
+        new_referral_tiers: Vec<ReferralTier>,
+    ) -> Result<()> {
+        instructions::update_referral_tiers::handler(ctx, new_referral_tiers)
+    }
+
+    pub fn update_price_bounds(
+        ctx: Context<ConfigUpdate>,
+        new_price_bounds: PriceBounds,
+    ) -> Result<()> {
+        instructions::update_price_bounds::handler(ctx, new_price_bounds)
+    }
+
+    pub fn update_trader_rebate_params(
+        ctx: Context<ConfigUpdate>,
+        new_trader_rebate_params: TraderRebateParams,
+    ) -> Result<()> {
+        instructions::update_trader_rebate_params::handler(ctx, new_trader_rebate_params)
+    }
+
+    pub fn update_verifier(ctx: Context<ConfigUpdate>, new_verifier: Option<Pubkey>) -> Result<()> {
+        instructions::update_verifier::handler(ctx, new_verifier)
+    }
+
+    pub fn set_market_verification(ctx: Context<MarketVerification>, verified: bool) -> Result<()> {
+        instructions::set_market_verification::handler(ctx, verified)
+    }
+
+    pub fn update_referral_settlement(
+        ctx: Context<ConfigUpdate>,
+        referral_settlement: ReferralSettlement,
+    ) -> Result<()> {
+        instructions::update_referral_settlement::handler(ctx, referral_settlement)
+    }
+
+    pub fn update_referral_params(
+        ctx: Context<ReferralParamsUpdate>,
+        referral_fee_share: u16,
+        referral_enabled: bool,
+    ) -> Result<()> {
+        instructions::update_referral_params::handler(ctx, referral_fee_share, referral_enabled)
+    }
+
+    pub fn update_migration_target(
+        ctx: Context<ConfigUpdate>,
+        new_migration_target: MigrationTarget,
+    ) -> Result<()> {
+        instructions::update_migration_target::handler(ctx, new_migration_target)
+    }
+
+    pub fn update_graduation_thresholds(
+        ctx: Context<ConfigUpdate>,
+        new_graduation_quote_threshold: u64,
+        new_graduation_supply_bps: u16,
+    ) -> Result<()> {
+        instructions::update_graduation_thresholds::handler(
+            ctx,
+            new_graduation_quote_threshold,
+            new_graduation_supply_bps,
+        )
+    }
+
+    pub fn update_swap_fee_bounds(
+        ctx: Context<ConfigUpdate>,
+        new_min_swap_fee_bps: u16,
+        new_max_swap_fee_bps: u16,
+    ) -> Result<()> {
+        instructions::update_swap_fee_bounds::handler(
+            ctx,
+            new_min_swap_fee_bps,
+            new_max_swap_fee_bps,
+        )
+    }
+
+    pub fn update_usd_graduation(
+        ctx: Context<ConfigUpdate>,
+        usd_graduation: UsdGraduation,
+    ) -> Result<()> {
+        instructions::update_usd_graduation::handler(ctx, usd_graduation)
+    }
+
+    pub fn update_rent_recipient(
+        ctx: Context<ConfigUpdate>,
+        new_rent_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::update_rent_recipient::handler(ctx, new_rent_recipient)
+    }
+
+    pub fn add_vesting_template(
+        ctx: Context<ConfigUpdate>,
+        template: VestingTemplate,
+    ) -> Result<()> {
+        instructions::add_vesting_template::handler(ctx, template)
+    }
+
+    pub fn pause_until(ctx: Context<MarketPause>, slot: u64) -> Result<()> {
+        instructions::pause_until::handler(ctx, slot)
+    }
+
+    pub fn pause_market(ctx: Context<MarketStatusUpdate>) -> Result<()> {
+        instructions::pause_market::handler(ctx)
+    }
+
+    pub fn unpause_market(ctx: Context<MarketStatusUpdate>) -> Result<()> {
+        instructions::unpause_market::handler(ctx)
+    }
+
+    pub fn migrate_quote_token(
+        ctx: Context<QuoteTokenMigration>,
+        conversion_rate: u64,
+    ) -> Result<()> {
+        instructions::migrate_quote_token::handler(ctx, conversion_rate)
+    }
+
+    pub fn create_denylist(ctx: Context<CreateDenylist>) -> Result<()> {
+        instructions::create_denylist::handler(ctx)
+    }
+
+    pub fn add_to_denylist(ctx: Context<DenylistUpdate>, wallet: Pubkey) -> Result<()> {
+        instructions::add_to_denylist::handler(ctx, wallet)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<DenylistUpdate>, wallet: Pubkey) -> Result<()> {
+        instructions::remove_from_denylist::handler(ctx, wallet)
+    }
+
+    pub fn create_fee_exemption(ctx: Context<CreateFeeExemption>, wallet: Pubkey) -> Result<()> {
+        instructions::create_fee_exemption::handler(ctx, wallet)
+    }
+
+    pub fn close_fee_exemption(ctx: Context<CloseFeeExemption>) -> Result<()> {
+        instructions::close_fee_exemption::handler(ctx)
+    }
+
     pub fn transfer_config_ownership(
         ctx: Context<ConfigUpdate>,
         pending_authority: Option<Pubkey>,
@@ -0,0 +1,269 @@
+use anchor_lang::prelude::*;
+use ruint::aliases::U256;
+
+use crate::{
+    constant::{INTERVAL_NUMBER, MAX_BPS, PRICES_LENGTH, SCALE},
+    errors::TokenMillError,
+    state::Market,
+};
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub enum CurveShape {
+    Linear,          // Price grows by the same amount every interval, starting at 0
+    Exponential,     // Price doubles every interval
+    Logistic,        // Price follows an S-curve, steepest at half of the supply
+    ConstantProduct, // Price of a x * y = k pool with a virtual base reserve of twice the supply
+}
+
+/// Sigmoid `1e6 / (1 + e^-(i - 5))` sampled at every price point.
+const LOGISTIC_WEIGHTS: [u128; PRICES_LENGTH] = [
+    6_693, 17_986, 47_426, 119_203, 268_941, 500_000, 731_059, 880_797, 952_574, 982_014, 993_307,
+];
+
+impl CurveShape {
+    fn weights(&self) -> [u128; PRICES_LENGTH] {
+        let mut weights = [0; PRICES_LENGTH];
+
+        for (i, weight) in weights.iter_mut().enumerate() {
+            *weight = match self {
+                CurveShape::Linear => i as u128,
+                CurveShape::Exponential => 1 << i,
+                CurveShape::Logistic => LOGISTIC_WEIGHTS[i],
+                // Quadruples over the supply, as the virtual base reserve halves
+                CurveShape::ConstantProduct => {
+                    let virtual_reserve = u128::from(2 * INTERVAL_NUMBER);
+
+                    virtual_reserve.pow(2) * 1_000_000 / (virtual_reserve - i as u128).pow(2)
+                }
+            };
+        }
+
+        weights
+    }
+}
+
+/// Curve preset, with prices in the unit of the market price arrays.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct CurveParameters {
+    pub shape: CurveShape,
+    pub start_price: u64,
+    pub end_price: u64,
+    pub bid_spread_bps: u16,
+}
+
+/// Derives the (bid, ask) price arrays going from `start_price` to `end_price` along the shape
+/// of the parameters.
+pub fn get_prices_from_parameters(
+    parameters: &CurveParameters,
+) -> Result<([u64; PRICES_LENGTH], [u64; PRICES_LENGTH])> {
+    require!(
+        parameters.end_price > parameters.start_price
+            && u64::from(parameters.bid_spread_bps) <= MAX_BPS,
+        TokenMillError::InvalidCurveParameters
+    );
+
+    let weights = parameters.shape.weights();
+    let (min_weight, max_weight) = (weights[0], weights[PRICES_LENGTH - 1]);
+    let price_range = u128::from(parameters.end_price - parameters.start_price);
+
+    let mut bid_prices = [0; PRICES_LENGTH];
+    let mut ask_prices = [0; PRICES_LENGTH];
+
+    for i in 0..PRICES_LENGTH {
+        let ask_price = parameters.start_price
+            + u64::try_from(price_range * (weights[i] - min_weight) / (max_weight - min_weight))?;
+
+        ask_prices[i] = ask_price;
+        bid_prices[i] = u64::try_from(
+            u128::from(ask_price) * u128::from(MAX_BPS - u64::from(parameters.bid_spread_bps))
+                / u128::from(MAX_BPS),
+        )?;
+    }
+
+    Ok((bid_prices, ask_prices))
+}
+
+/// Derives the (bid, ask) price arrays of the given shape such that buying the whole supply
+/// of the market costs `target_raise` quote tokens on the ask curve.
+/// Prices are rounded down, so the actual raise only misses the target by rounding dust.
+pub fn get_prices_for_raise(
+    market: &Market,
+    curve_shape: CurveShape,
+    target_raise: u64,
+    bid_spread_bps: u16,
+) -> Result<([u64; PRICES_LENGTH], [u64; PRICES_LENGTH])> {
+    require!(target_raise > 0, TokenMillError::InvalidAmount);
+    require!(
+        u64::from(bid_spread_bps) <= MAX_BPS,
+        TokenMillError::InvalidCurveParameters
+    );
+
+    let weights = curve_shape.weights();
+
+    // Each interval costs `width_scaled * (p_i + p_i+1) / (2 * SCALE)` normalized quote tokens,
+    // so the full curve costs `width_scaled * weights_sum * k / (2 * SCALE^2)` quote tokens
+    // once denormalized, with `p_i = w_i * k`.
+    let weights_sum: u128 = weights.windows(2).map(|w| w[0] + w[1]).sum();
+
+    let numerator =
+        U256::from(2) * U256::from(SCALE) * U256::from(SCALE) * U256::from(target_raise);
+    let denominator = U256::from(market.width_scaled)
+        * U256::from(10).pow(U256::from(market.quote_token_decimals))
+        * U256::from(weights_sum);
+
+    let mut bid_prices = [0; PRICES_LENGTH];
+    let mut ask_prices = [0; PRICES_LENGTH];
+
+    for i in 0..PRICES_LENGTH {
+        let ask_price: u64 = (U256::from(weights[i]) * numerator / denominator)
+            .try_into()
+            .map_err(|_| TokenMillError::PriceTooHigh)?;
+
+        ask_prices[i] = ask_price;
+        bid_prices[i] = u64::try_from(
+            u128::from(ask_price) * u128::from(MAX_BPS - u64::from(bid_spread_bps))
+                / u128::from(MAX_BPS),
+        )?;
+    }
+
+    Ok((bid_prices, ask_prices))
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{manager::swap_manager::SwapAmountType, math::Rounding, state::PriceBounds};
+
+    const TOTAL_SUPPLY: u64 = 1_000_000_000_000;
+
+    fn setup_market(quote_token_decimals: u8) -> Market {
+        let mut market = Market::zeroed();
+
+        market
+            .initialize(
+                255,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                quote_token_decimals,
+                TOTAL_SUPPLY,
+                4_500,
+                4_500,
+            )
+            .unwrap();
+
+        market
+    }
+
+    #[rstest]
+    fn prices_for_raise_hit_target(
+        #[values(
+            CurveShape::Linear,
+            CurveShape::Exponential,
+            CurveShape::Logistic,
+            CurveShape::ConstantProduct
+        )]
+        curve_shape: CurveShape,
+        #[values(6, 9)] quote_token_decimals: u8,
+        #[values(1_000, 85_000)] target_raise_units: u64,
+    ) {
+        let mut market = setup_market(quote_token_decimals);
+        let target_raise = target_raise_units * 10u64.pow(u32::from(quote_token_decimals));
+
+        let (bid_prices, ask_prices) =
+            get_prices_for_raise(&market, curve_shape, target_raise, 1_000).unwrap();
+
+        market
+            .check_and_set_prices(bid_prices, ask_prices, &PriceBounds::default())
+            .unwrap();
+
+        let (base_amount, raise) = market
+            .get_quote_amount_with_parameters(
+                0,
+                TOTAL_SUPPLY,
+                SwapAmountType::ExactOutput,
+                Rounding::Down,
+            )
+            .unwrap();
+
+        assert_eq!(base_amount, TOTAL_SUPPLY);
+        assert!(raise <= target_raise);
+        assert!(target_raise - raise <= target_raise / 1_000_000);
+
+        for i in 0..PRICES_LENGTH {
+            assert_eq!(bid_prices[i], ask_prices[i] * 9 / 10);
+        }
+    }
+
+    #[test]
+    fn exponential_prices_double() {
+        let market = setup_market(9);
+
+        let (_, ask_prices) =
+            get_prices_for_raise(&market, CurveShape::Exponential, 1_000_000_000_000, 0).unwrap();
+
+        assert_eq!(
+            ask_prices[INTERVAL_NUMBER as usize] / ask_prices[0],
+            1 << INTERVAL_NUMBER
+        );
+    }
+
+    #[test]
+    fn constant_product_prices_quadruple() {
+        let market = setup_market(9);
+
+        let (_, ask_prices) =
+            get_prices_for_raise(&market, CurveShape::ConstantProduct, 1_000_000_000_000, 0)
+                .unwrap();
+
+        assert_eq!(ask_prices[INTERVAL_NUMBER as usize] / ask_prices[0], 4);
+    }
+
+    #[rstest]
+    fn prices_from_parameters(
+        #[values(
+            CurveShape::Linear,
+            CurveShape::Exponential,
+            CurveShape::Logistic,
+            CurveShape::ConstantProduct
+        )]
+        shape: CurveShape,
+    ) {
+        let parameters = CurveParameters {
+            shape,
+            start_price: 1_000,
+            end_price: 1_000_000,
+            bid_spread_bps: 1_000,
+        };
+
+        let (bid_prices, ask_prices) = get_prices_from_parameters(&parameters).unwrap();
+
+        assert_eq!(ask_prices[0], parameters.start_price);
+        assert_eq!(ask_prices[PRICES_LENGTH - 1], parameters.end_price);
+        assert!(Market::check_prices(&bid_prices, &ask_prices).is_ok());
+    }
+
+    #[test]
+    fn prices_from_invalid_parameters() {
+        let parameters = CurveParameters {
+            shape: CurveShape::Linear,
+            start_price: 1_000,
+            end_price: 1_000,
+            bid_spread_bps: 0,
+        };
+
+        assert!(get_prices_from_parameters(&parameters).is_err());
+    }
+
+    #[test]
+    fn prices_for_raise_with_invalid_parameters() {
+        let market = setup_market(9);
+
+        assert!(get_prices_for_raise(&market, CurveShape::Linear, 0, 0).is_err());
+        assert!(get_prices_for_raise(&market, CurveShape::Linear, 1, MAX_BPS as u16 + 1).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
+
+use crate::{errors::TokenMillError, manager::swap_manager::SwapType};
+
+// sha256("global:on_swap_fees")[..8]
+pub const ON_SWAP_FEES_DISCRIMINATOR: [u8; 8] = [8, 73, 4, 141, 35, 106, 232, 82];
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OnSwapFeesArgs {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub swap_type: SwapType,
+    pub quote_amount: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+}
+
+/// Notifies the fee hook program designated by the config, if any, of the fee breakdown of a
+/// swap, using an Anchor-style `on_swap_fees` instruction. A failing hook fails the swap.
+/// Remaining accounts are forwarded without signer privileges but keep their writability, so
+/// that the hook can record into its own accounts.
+pub fn notify<'info>(
+    config_fee_hook_program: Option<Pubkey>,
+    fee_hook_program: Option<&UncheckedAccount<'info>>,
+    remaining_accounts: &[AccountInfo<'info>],
+    args: &OnSwapFeesArgs,
+) -> Result<()> {
+    let Some(config_fee_hook_program) = config_fee_hook_program else {
+        return Ok(());
+    };
+
+    let fee_hook_program = fee_hook_program.ok_or(TokenMillError::InvalidFeeHookProgram)?;
+
+    require_keys_eq!(
+        fee_hook_program.key(),
+        config_fee_hook_program,
+        TokenMillError::InvalidFeeHookProgram
+    );
+
+    let mut data = ON_SWAP_FEES_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let accounts = remaining_accounts
+        .iter()
+        .map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.key(), false)
+            } else {
+                AccountMeta::new_readonly(account.key(), false)
+            }
+        })
+        .collect();
+
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(fee_hook_program.to_account_info());
+
+    invoke(
+        &Instruction {
+            program_id: config_fee_hook_program,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
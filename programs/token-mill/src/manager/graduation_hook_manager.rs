@@ -0,0 +1,96 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke_signed},
+};
+
+use crate::errors::TokenMillError;
+
+// sha256("global:on_graduation")[..8]
+pub const ON_GRADUATION_DISCRIMINATOR: [u8; 8] = [220, 181, 142, 32, 221, 86, 151, 217];
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OnGraduationArgs {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub pool: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub price: u64,
+}
+
+/// Checks that the graduation hook program designated by the config, if any, is provided.
+pub fn check<'info>(
+    config_graduation_hook_program: Option<Pubkey>,
+    graduation_hook_program: Option<&UncheckedAccount<'info>>,
+) -> Result<()> {
+    let Some(config_graduation_hook_program) = config_graduation_hook_program else {
+        return Ok(());
+    };
+
+    let graduation_hook_program =
+        graduation_hook_program.ok_or(TokenMillError::InvalidGraduationHookProgram)?;
+
+    require_keys_eq!(
+        graduation_hook_program.key(),
+        config_graduation_hook_program,
+        TokenMillError::InvalidGraduationHookProgram
+    );
+
+    Ok(())
+}
+
+/// Notifies the graduation hook program designated by the config, if any, using an Anchor-style
+/// `on_graduation` instruction, e.g. to post a Wormhole message. A failing hook fails the
+/// migration. The graduation attestation comes first and signs, proving the call comes from a
+/// graduation, the remaining accounts follow without signer privileges but keep their
+/// writability.
+pub fn notify<'info>(
+    config_graduation_hook_program: Option<Pubkey>,
+    graduation_hook_program: Option<&UncheckedAccount<'info>>,
+    graduation_attestation: AccountInfo<'info>,
+    graduation_attestation_seeds: &[&[u8]],
+    remaining_accounts: &[AccountInfo<'info>],
+    args: &OnGraduationArgs,
+) -> Result<()> {
+    check(config_graduation_hook_program, graduation_hook_program)?;
+
+    let (Some(config_graduation_hook_program), Some(graduation_hook_program)) =
+        (config_graduation_hook_program, graduation_hook_program)
+    else {
+        return Ok(());
+    };
+
+    let mut data = ON_GRADUATION_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let mut accounts = vec![AccountMeta::new_readonly(
+        graduation_attestation.key(),
+        true,
+    )];
+
+    accounts.extend(remaining_accounts.iter().map(|account| {
+        if account.is_writable {
+            AccountMeta::new(account.key(), false)
+        } else {
+            AccountMeta::new_readonly(account.key(), false)
+        }
+    }));
+
+    let mut account_infos = vec![graduation_attestation];
+    account_infos.extend_from_slice(remaining_accounts);
+    account_infos.push(graduation_hook_program.to_account_info());
+
+    invoke_signed(
+        &Instruction {
+            program_id: config_graduation_hook_program,
+            accounts,
+            data,
+        },
+        &account_infos,
+        &[graduation_attestation_seeds],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use crate::errors::TokenMillError;
+
+/// Requires the current instruction to be invoked at the top level of the transaction, and to be
+/// its only token mill instruction touching `market`, using the instructions sysvar.
+/// Used on paths that could be sandwiched within a single transaction.
+pub fn check_isolated_call(instructions_sysvar: &AccountInfo, market: &Pubkey) -> Result<()> {
+    require_eq!(
+        get_stack_height(),
+        TRANSACTION_LEVEL_STACK_HEIGHT,
+        TokenMillError::DisallowedCallPattern
+    );
+
+    let current_index = usize::from(load_current_index_checked(instructions_sysvar)?);
+    let mut index = 0;
+
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions_sysvar) {
+        if index != current_index
+            && instruction.program_id == crate::ID
+            && instruction
+                .accounts
+                .iter()
+                .any(|account| account.pubkey == *market)
+        {
+            return Err(TokenMillError::DisallowedCallPattern.into());
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Leaf of a distribution tree, granting `amount` to `claimant` in the distribution `distribution`.
+pub fn get_leaf(distribution: &Pubkey, claimant: &Pubkey, amount: u64) -> [u8; 32] {
+    hashv(&[
+        LEAF_PREFIX,
+        distribution.as_ref(),
+        claimant.as_ref(),
+        &amount.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Hashes two nodes in sorted order, so that proofs don't need to encode the side of each sibling.
+pub fn hash_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    if a <= b {
+        hashv(&[NODE_PREFIX, a, b]).to_bytes()
+    } else {
+        hashv(&[NODE_PREFIX, b, a]).to_bytes()
+    }
+}
+
+pub fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(leaf, |node, sibling| hash_pair(&node, sibling));
+
+    computed_root == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_proofs() {
+        let distribution = Pubkey::new_unique();
+        let leaves = [
+            get_leaf(&distribution, &Pubkey::new_unique(), 100),
+            get_leaf(&distribution, &Pubkey::new_unique(), 200),
+            get_leaf(&distribution, &Pubkey::new_unique(), 300),
+        ];
+
+        let left = hash_pair(&leaves[0], &leaves[1]);
+        let root = hash_pair(&left, &leaves[2]);
+
+        assert!(verify(&[leaves[1], leaves[2]], &root, leaves[0]));
+        assert!(verify(&[leaves[0], leaves[2]], &root, leaves[1]));
+        assert!(verify(&[left], &root, leaves[2]));
+
+        assert!(!verify(&[leaves[2]], &root, leaves[0]));
+        assert!(!verify(&[left], &root, leaves[0]));
+    }
+}
@@ -0,0 +1,171 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
+use ruint::aliases::U256;
+
+use crate::errors::TokenMillError;
+
+pub const RAYDIUM_CP_SWAP_PROGRAM_ID: Pubkey =
+    pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey =
+    pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+// sha256("global:initialize")[..8]
+pub const RAYDIUM_INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+// sha256("global:initialize_pool")[..8]
+pub const METEORA_INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+
+/// Sqrt price range of DAMM v2 pools, as Q64.64 numbers.
+pub const METEORA_MIN_SQRT_PRICE: u128 = 4_295_048_016;
+pub const METEORA_MAX_SQRT_PRICE: u128 = 79_226_673_521_066_979_257_578_248_091;
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RaydiumInitializeArgs {
+    pub init_amount_0: u64,
+    pub init_amount_1: u64,
+    pub open_time: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MeteoraInitializePoolArgs {
+    pub liquidity: u128,
+    pub sqrt_price: u128,
+    pub activation_point: Option<u64>,
+}
+
+/// Creates and seeds a Raydium CPMM pool with the CP-Swap `initialize` instruction.
+/// `accounts` follow its interface, token 0 being the mint with the lowest address.
+pub fn initialize_raydium_pool<'info>(
+    cp_swap_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    args: &RaydiumInitializeArgs,
+) -> Result<()> {
+    invoke_amm(
+        cp_swap_program,
+        RAYDIUM_INITIALIZE_DISCRIMINATOR,
+        args,
+        accounts,
+    )
+}
+
+/// Creates and seeds a Meteora DAMM v2 pool with the `initialize_pool` instruction.
+/// `accounts` follow its interface, the position NFT going to the pool creator.
+pub fn initialize_meteora_pool<'info>(
+    damm_v2_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    args: &MeteoraInitializePoolArgs,
+) -> Result<()> {
+    invoke_amm(
+        damm_v2_program,
+        METEORA_INITIALIZE_POOL_DISCRIMINATOR,
+        args,
+        accounts,
+    )
+}
+
+/// Returns the (sqrt price, liquidity) of a full range DAMM v2 position worth `amount_a` and
+/// `amount_b`. Liquidity is rounded down on both sides, so the deposit never exceeds the amounts.
+pub fn get_meteora_pool_parameters(amount_a: u64, amount_b: u64) -> Result<(u128, u128)> {
+    require!(
+        amount_a > 0 && amount_b > 0,
+        TokenMillError::NothingToMigrate
+    );
+
+    let sqrt_price = ((U256::from(amount_b) << 128) / U256::from(amount_a)).root(2);
+
+    require!(
+        sqrt_price > U256::from(METEORA_MIN_SQRT_PRICE)
+            && sqrt_price < U256::from(METEORA_MAX_SQRT_PRICE),
+        TokenMillError::MathError
+    );
+
+    let max_sqrt_price = U256::from(METEORA_MAX_SQRT_PRICE);
+
+    // amount_a = liquidity * (max_sqrt_price - sqrt_price) / (sqrt_price * max_sqrt_price)
+    let liquidity_a =
+        U256::from(amount_a) * sqrt_price * max_sqrt_price / (max_sqrt_price - sqrt_price);
+    // amount_b = liquidity * (sqrt_price - min_sqrt_price) >> 128
+    let liquidity_b =
+        (U256::from(amount_b) << 128) / (sqrt_price - U256::from(METEORA_MIN_SQRT_PRICE));
+
+    let liquidity =
+        u128::try_from(liquidity_a.min(liquidity_b)).map_err(|_| TokenMillError::MathError)?;
+    let sqrt_price = u128::try_from(sqrt_price).map_err(|_| TokenMillError::MathError)?;
+
+    Ok((sqrt_price, liquidity))
+}
+
+/// Calls an Anchor instruction of an AMM program. The program isn't a dependency, so the
+/// instruction is built by hand, the same way as the policy program calls.
+/// Accounts are forwarded with the signer and writable flags they were received with.
+fn invoke_amm<'info, T: AnchorSerialize>(
+    amm_program: &AccountInfo<'info>,
+    discriminator: [u8; 8],
+    args: &T,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)?;
+
+    let account_metas = accounts
+        .iter()
+        .map(|account| AccountMeta {
+            pubkey: account.key(),
+            is_signer: account.is_signer,
+            is_writable: account.is_writable,
+        })
+        .collect();
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(amm_program.clone());
+
+    invoke(
+        &Instruction {
+            program_id: amm_program.key(),
+            accounts: account_metas,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meteora_pool_parameters() {
+        let (sqrt_price, _) = get_meteora_pool_parameters(1_000_000, 1_000_000).unwrap();
+
+        assert_eq!(sqrt_price, 1 << 64);
+
+        let (sqrt_price, _) = get_meteora_pool_parameters(1_000_000, 4_000_000).unwrap();
+
+        assert_eq!(sqrt_price, 2 << 64);
+
+        let amount_a = 900_000_000_000;
+        let amount_b = 15_000_000_000;
+
+        let (sqrt_price, liquidity) = get_meteora_pool_parameters(amount_a, amount_b).unwrap();
+
+        let sqrt_price = U256::from(sqrt_price);
+        let liquidity = U256::from(liquidity);
+        let max_sqrt_price = U256::from(METEORA_MAX_SQRT_PRICE);
+
+        let deposit_a = liquidity * (max_sqrt_price - sqrt_price) / (sqrt_price * max_sqrt_price);
+        let deposit_b = (liquidity * (sqrt_price - U256::from(METEORA_MIN_SQRT_PRICE))) >> 128;
+
+        assert!(deposit_a <= U256::from(amount_a));
+        assert!(deposit_b <= U256::from(amount_b));
+        // The binding side is deposited in full, up to rounding
+        assert!(
+            deposit_a + U256::from(1) >= U256::from(amount_a)
+                || deposit_b + U256::from(1) >= U256::from(amount_b)
+        );
+
+        assert!(get_meteora_pool_parameters(0, amount_b).is_err());
+    }
+}
@@ -1,3 +1,13 @@
+pub mod curve_manager;
+pub mod fee_hook_manager;
+pub mod graduation_hook_manager;
+pub mod guard_manager;
+pub mod merkle_manager;
+pub mod migration_manager;
+pub mod policy_manager;
+pub mod price_manager;
+pub mod receipt_manager;
+pub mod settlement_manager;
 pub mod staking_manager;
 pub mod swap_manager;
 pub mod token_manager;
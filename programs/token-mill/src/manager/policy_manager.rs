@@ -0,0 +1,80 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, program::invoke},
+};
+
+use crate::{
+    errors::TokenMillError,
+    manager::swap_manager::{SwapAmountType, SwapType},
+};
+
+// sha256("global:validate_create_market")[..8]
+pub const VALIDATE_CREATE_MARKET_DISCRIMINATOR: [u8; 8] = [72, 20, 137, 230, 235, 1, 107, 107];
+// sha256("global:validate_swap")[..8]
+pub const VALIDATE_SWAP_DISCRIMINATOR: [u8; 8] = [218, 214, 34, 73, 72, 12, 65, 203];
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ValidateCreateMarketArgs {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub creator: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub total_supply: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ValidateSwapArgs {
+    pub config: Pubkey,
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub swap_type: SwapType,
+    pub swap_amount_type: SwapAmountType,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+/// Runs the policy program designated by the config, if any. The policy program rejects the
+/// operation by failing, using Anchor-style `validate_*` instructions.
+/// Remaining accounts are forwarded read-only and without signer privileges.
+pub fn validate<'info, T: AnchorSerialize>(
+    config_policy_program: Option<Pubkey>,
+    policy_program: Option<&UncheckedAccount<'info>>,
+    remaining_accounts: &[AccountInfo<'info>],
+    discriminator: [u8; 8],
+    args: &T,
+) -> Result<()> {
+    let Some(config_policy_program) = config_policy_program else {
+        return Ok(());
+    };
+
+    let policy_program = policy_program.ok_or(TokenMillError::InvalidPolicyProgram)?;
+
+    require_keys_eq!(
+        policy_program.key(),
+        config_policy_program,
+        TokenMillError::InvalidPolicyProgram
+    );
+
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)?;
+
+    let accounts = remaining_accounts
+        .iter()
+        .map(|account| AccountMeta::new_readonly(account.key(), false))
+        .collect();
+
+    let mut account_infos = remaining_accounts.to_vec();
+    account_infos.push(policy_program.to_account_info());
+
+    invoke(
+        &Instruction {
+            program_id: config_policy_program,
+            accounts,
+            data,
+        },
+        &account_infos,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use anchor_lang::{prelude::*, solana_program::hash::hash};
+
+use crate::{
+    constant::{MAX_BPS, USD_PRECISION},
+    errors::TokenMillError,
+    state::UsdGraduation,
+};
+
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Mirror of the `PriceUpdateV2` account of the Pyth receiver program, read without depending on
+/// the Pyth SDK.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+impl PriceUpdateV2 {
+    pub fn discriminator() -> [u8; 8] {
+        hash(b"account:PriceUpdateV2").to_bytes()[..8]
+            .try_into()
+            .unwrap()
+    }
+
+    /// Reads a fully verified price update of `feed_id`.
+    pub fn load(price_update: &AccountInfo, feed_id: &[u8; 32]) -> Result<Self> {
+        require_keys_eq!(
+            *price_update.owner,
+            PYTH_RECEIVER_PROGRAM_ID,
+            TokenMillError::InvalidPriceFeed
+        );
+
+        let data = price_update.try_borrow_data()?;
+
+        require!(
+            data.len() > 8 && data[..8] == Self::discriminator(),
+            TokenMillError::InvalidPriceFeed
+        );
+
+        let price_update = Self::deserialize(&mut &data[8..])?;
+
+        require!(
+            price_update.verification_level == VerificationLevel::Full
+                && price_update.price_message.feed_id == *feed_id,
+            TokenMillError::InvalidPriceFeed
+        );
+
+        Ok(price_update)
+    }
+}
+
+/// USD value of `quote_amount`, scaled by `USD_PRECISION`, at a price no older than
+/// `max_price_staleness` seconds and with a confidence interval within `max_price_conf_bps`.
+pub fn get_usd_value(
+    price_message: &PriceFeedMessage,
+    usd_graduation: &UsdGraduation,
+    quote_amount: u64,
+    quote_token_decimals: u8,
+    timestamp: i64,
+) -> Result<u64> {
+    require_gte!(
+        usd_graduation.max_price_staleness,
+        timestamp.saturating_sub(price_message.publish_time),
+        TokenMillError::StalePrice
+    );
+
+    let price = u64::try_from(price_message.price).map_err(|_| TokenMillError::InvalidPriceFeed)?;
+
+    require!(
+        u128::from(price_message.conf) * u128::from(MAX_BPS)
+            <= u128::from(price) * u128::from(usd_graduation.max_price_conf_bps),
+        TokenMillError::PriceConfidenceTooWide
+    );
+
+    // value = quote_amount * price * 10^(exponent - quote_decimals) * USD_PRECISION
+    let value = u128::from(quote_amount) * u128::from(price) * u128::from(USD_PRECISION);
+    let exponent = price_message.exponent - i32::from(quote_token_decimals);
+
+    let value = if exponent >= 0 {
+        10u128
+            .checked_pow(exponent.unsigned_abs())
+            .and_then(|scale| value.checked_mul(scale))
+    } else {
+        10u128
+            .checked_pow(exponent.unsigned_abs())
+            .map(|scale| value / scale)
+    }
+    .ok_or(TokenMillError::MathError)?;
+
+    Ok(u64::try_from(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USD_GRADUATION: UsdGraduation = UsdGraduation {
+        usd_threshold: 69_000_000_000,
+        max_price_staleness: 60,
+        max_price_conf_bps: 100,
+    };
+
+    fn price_message(price: i64, conf: u64, publish_time: i64) -> PriceFeedMessage {
+        PriceFeedMessage {
+            feed_id: [1; 32],
+            price,
+            conf,
+            exponent: -8,
+            publish_time,
+            prev_publish_time: publish_time,
+            ema_price: price,
+            ema_conf: conf,
+        }
+    }
+
+    #[test]
+    fn usd_value() {
+        // 1_000 quote tokens with 9 decimals at $150
+        let price_message = price_message(15_000_000_000, 10_000_000, 1_000);
+
+        assert_eq!(
+            get_usd_value(&price_message, &USD_GRADUATION, 1_000_000_000_000, 9, 1_060).unwrap(),
+            150_000_000_000
+        );
+    }
+
+    #[test]
+    fn usd_value_with_invalid_price() {
+        for (price_message, timestamp, error) in [
+            (
+                price_message(15_000_000_000, 10_000_000, 1_000),
+                1_061,
+                TokenMillError::StalePrice,
+            ),
+            (
+                price_message(15_000_000_000, 150_000_001, 1_000),
+                1_000,
+                TokenMillError::PriceConfidenceTooWide,
+            ),
+            (
+                price_message(-1, 0, 1_000),
+                1_000,
+                TokenMillError::InvalidPriceFeed,
+            ),
+        ] {
+            assert_eq!(
+                get_usd_value(&price_message, &USD_GRADUATION, 1_000, 9, timestamp).unwrap_err(),
+                error.into()
+            );
+        }
+    }
+}
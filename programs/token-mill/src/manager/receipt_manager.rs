@@ -0,0 +1,140 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, keccak::hashv, program::invoke_signed},
+};
+
+use super::swap_manager::SwapType;
+
+pub const SPL_ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const SPL_NOOP_PROGRAM_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// sha256("global:init_empty_merkle_tree")[..8]
+pub const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+// sha256("global:append")[..8]
+pub const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// Leaf of a swap receipt. Indexers rebuild the receipts from the swap events, and prove them
+/// against the concurrent merkle tree of the market.
+pub fn get_swap_receipt_leaf(
+    market: &Pubkey,
+    user: &Pubkey,
+    swap_type: SwapType,
+    base_amount: u64,
+    quote_amount: u64,
+    slot: u64,
+) -> [u8; 32] {
+    hashv(&[
+        market.as_ref(),
+        user.as_ref(),
+        &[swap_type as u8],
+        &base_amount.to_le_bytes(),
+        &quote_amount.to_le_bytes(),
+        &slot.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+/// Initializes a concurrent merkle tree allocated by the caller, owned by the account compression
+/// program, with `authority` as the only account allowed to append to it.
+pub fn init_empty_merkle_tree<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    max_depth: u32,
+    max_buffer_size: u32,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+    max_depth.serialize(&mut data)?;
+    max_buffer_size.serialize(&mut data)?;
+
+    invoke_compression(
+        compression_program,
+        merkle_tree,
+        authority,
+        noop_program,
+        data,
+        authority_seeds,
+    )
+}
+
+pub fn append<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    leaf: [u8; 32],
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    let mut data = APPEND_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&leaf);
+
+    invoke_compression(
+        compression_program,
+        merkle_tree,
+        authority,
+        noop_program,
+        data,
+        authority_seeds,
+    )
+}
+
+/// The account compression program isn't a dependency, so its instructions are built by hand,
+/// the same way as the AMM calls of the migration.
+fn invoke_compression<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop_program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    authority_seeds: &[&[u8]],
+) -> Result<()> {
+    invoke_signed(
+        &Instruction {
+            program_id: compression_program.key(),
+            accounts: vec![
+                AccountMeta::new(merkle_tree.key(), false),
+                AccountMeta::new_readonly(authority.key(), true),
+                AccountMeta::new_readonly(noop_program.key(), false),
+            ],
+            data,
+        },
+        &[
+            merkle_tree.clone(),
+            authority.clone(),
+            noop_program.clone(),
+            compression_program.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_receipt_leaf() {
+        let market = Pubkey::new_unique();
+        let user = Pubkey::new_unique();
+
+        let leaf = get_swap_receipt_leaf(&market, &user, SwapType::Buy, 1, 2, 3);
+
+        assert_eq!(
+            leaf,
+            get_swap_receipt_leaf(&market, &user, SwapType::Buy, 1, 2, 3)
+        );
+        assert_ne!(
+            leaf,
+            get_swap_receipt_leaf(&market, &user, SwapType::Sell, 1, 2, 3)
+        );
+        assert_ne!(
+            leaf,
+            get_swap_receipt_leaf(&market, &user, SwapType::Buy, 1, 2, 4)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    clock::get_clock,
+    constant::DYNAMIC_FEE_WINDOW,
+    errors::TokenMillError,
+    manager::{
+        fee_hook_manager::{self, OnSwapFeesArgs},
+        policy_manager::{self, ValidateSwapArgs, VALIDATE_SWAP_DISCRIMINATOR},
+        swap_manager::{self, SwapOutcome, SwapType},
+        token_manager::transfer_from_pda,
+    },
+    pda::find_revenue_report_address,
+    state::{Denylist, Market, MarketOracle, MarketStats, RevenueReport, TokenMillConfig},
+};
+
+/// Accounts of the checks, accounting and settlement shared by every instruction swapping on the
+/// curve, so that none of them can skip the compliance checks or the fee and volume reporting.
+/// In order: `check_trader` and `load_revenue_report`, then `before_swap` and `after_swap` around
+/// `swap_manager::swap` on the loaded market, then `validate` and `settle` once unloaded.
+pub struct SwapAccounts<'a, 'info> {
+    pub config: &'a Account<'info, TokenMillConfig>,
+    pub market: &'a AccountLoader<'info, Market>,
+    pub quote_token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub market_quote_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub protocol_quote_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub quote_token_program: &'a Interface<'info, TokenInterface>,
+    pub revenue_report: &'a UncheckedAccount<'info>,
+    pub denylist: Option<&'a Account<'info, Denylist>>,
+    pub policy_program: Option<&'a UncheckedAccount<'info>>,
+    pub fee_hook_program: Option<&'a UncheckedAccount<'info>>,
+    pub market_oracle: Option<&'a AccountLoader<'info, MarketOracle>>,
+    pub market_stats: Option<&'a AccountLoader<'info, MarketStats>>,
+    /// Forwarded to the policy program and the fee hook.
+    pub hook_accounts: &'a [AccountInfo<'info>],
+}
+
+impl<'info> SwapAccounts<'_, 'info> {
+    /// Fails if the config enforces a denylist and `trader` is on it.
+    pub fn check_trader(&self, trader: &Pubkey) -> Result<()> {
+        Denylist::check(self.config, self.denylist, trader)
+    }
+
+    /// Swaps can't skip the revenue report of the current epoch, which is loaded once created.
+    pub fn load_revenue_report(&self) -> Result<Option<Account<'info, RevenueReport>>> {
+        let epoch = RevenueReport::get_epoch(get_clock()?.unix_timestamp);
+
+        require_keys_eq!(
+            self.revenue_report.key(),
+            find_revenue_report_address(&self.config.key(), &self.quote_token_mint.key(), epoch).0,
+            TokenMillError::InvalidEpoch
+        );
+
+        if self.revenue_report.owner == &crate::ID {
+            Ok(Some(Account::<RevenueReport>::try_from(
+                self.revenue_report,
+            )?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Records the price of the market in its oracle before the swap. Dynamic fee markets price
+    /// the volatility of the swap from the oracle, which must then be provided.
+    /// Returns the max base amount of the swap, see `Market::get_max_swap_base_amount`.
+    pub fn before_swap(&self, market: &mut Market, swap_type: SwapType) -> Result<Option<u64>> {
+        let max_base_amount =
+            market.get_max_swap_base_amount(swap_type, self.config.max_swap_size_bps)?;
+
+        match self.market_oracle {
+            Some(market_oracle) => {
+                let market_oracle = &mut market_oracle.load_mut()?;
+                let price = market.get_mid_price()?;
+                let timestamp = get_clock()?.unix_timestamp;
+
+                market_oracle.record(price, timestamp)?;
+
+                if market.is_dynamic_fee_enabled() {
+                    let volatility_bps =
+                        market_oracle.get_volatility_bps(DYNAMIC_FEE_WINDOW, price, timestamp)?;
+
+                    swap_manager::update_variable_fee(
+                        market,
+                        volatility_bps,
+                        self.config.max_swap_fee_bps,
+                    );
+                }
+            }
+            None => require!(
+                !market.is_dynamic_fee_enabled(),
+                TokenMillError::MissingMarketOracle
+            ),
+        }
+
+        Ok(max_base_amount)
+    }
+
+    /// Caps the swap to `max_base_amount` and records it into the volume and the stats of the
+    /// market.
+    pub fn after_swap(
+        &self,
+        market: &mut Market,
+        outcome: &SwapOutcome,
+        max_base_amount: Option<u64>,
+    ) -> Result<()> {
+        if let Some(max_base_amount) = max_base_amount {
+            require_gte!(
+                max_base_amount,
+                outcome.base_amount,
+                TokenMillError::SwapSizeExceeded
+            );
+        }
+
+        let timestamp = get_clock()?.unix_timestamp;
+
+        market
+            .volume
+            .record(timestamp, outcome.quote_amount, outcome.swap_fee);
+
+        if let Some(market_stats) = self.market_stats {
+            market_stats.load_mut()?.record_trade(
+                timestamp,
+                outcome.base_amount,
+                outcome.quote_amount,
+                market.get_mid_price()?,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validates the swap with the policy program of the config, if any.
+    pub fn validate(&self, args: &ValidateSwapArgs) -> Result<()> {
+        policy_manager::validate(
+            self.config.policy_program,
+            self.policy_program,
+            self.hook_accounts,
+            VALIDATE_SWAP_DISCRIMINATOR,
+            args,
+        )
+    }
+
+    /// Settles the fees of the swap once the tokens moved: the protocol fee goes to the protocol
+    /// fee recipient along with `off_chain_referral_fee`, the fees are recorded in the revenue
+    /// report and the fee hook of the config is notified. `outcome` is net of the trader rebate.
+    pub fn settle(
+        &self,
+        revenue_report: Option<&mut Account<'info, RevenueReport>>,
+        user: Pubkey,
+        swap_type: SwapType,
+        outcome: &SwapOutcome,
+        off_chain_referral_fee: u64,
+        market_seeds: &[&[u8]],
+    ) -> Result<()> {
+        let SwapOutcome {
+            quote_amount,
+            creator_fee,
+            staking_fee,
+            protocol_fee,
+            referral_fee,
+            ..
+        } = *outcome;
+
+        if protocol_fee + off_chain_referral_fee > 0 {
+            transfer_from_pda(
+                self.quote_token_mint,
+                self.market.to_account_info(),
+                self.market_quote_token_ata,
+                self.protocol_quote_token_ata,
+                self.quote_token_program,
+                protocol_fee + off_chain_referral_fee,
+                market_seeds,
+            )?;
+        }
+
+        if let Some(revenue_report) = revenue_report {
+            revenue_report.record(
+                RevenueReport::get_epoch(get_clock()?.unix_timestamp),
+                protocol_fee,
+                creator_fee,
+                staking_fee,
+                referral_fee,
+            )?;
+            revenue_report.exit(&crate::ID)?;
+        }
+
+        fee_hook_manager::notify(
+            self.config.fee_hook_program,
+            self.fee_hook_program,
+            self.hook_accounts,
+            &OnSwapFeesArgs {
+                config: self.config.key(),
+                market: self.market.key(),
+                user,
+                swap_type,
+                quote_amount,
+                creator_fee,
+                staking_fee,
+                protocol_fee,
+                referral_fee,
+            },
+        )
+    }
+}
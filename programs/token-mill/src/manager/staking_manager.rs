@@ -1,17 +1,30 @@
-use crate::state::{Market, MarketStaking, StakePosition};
+use crate::{
+    constant::{BASE_PRECISION, SCALE, SECONDS_PER_YEAR, VOLUME_WINDOW_DURATION},
+    errors::TokenMillError,
+    state::{Market, MarketStaking, StakePosition},
+};
 use anchor_lang::prelude::*;
+use ruint::aliases::U256;
 
+/// Deposits into a locked position relock it for the full duration of its tier.
 pub fn deposit(
     market: &mut Market,
     staking: &mut MarketStaking,
     stake_position: &mut StakePosition,
     amount: u64,
+    timestamp: i64,
 ) -> Result<u64> {
-    let pending_staking_fees = accrue_rewards(market, staking, stake_position)?;
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
 
     staking.amount_staked += amount;
     stake_position.amount_staked += amount;
 
+    if amount > 0 {
+        stake_position.lock(stake_position.lock_tier, timestamp)?;
+    }
+
+    update_shares(staking, stake_position)?;
+
     Ok(pending_staking_fees)
 }
 
@@ -20,12 +33,15 @@ pub fn deposit_vested(
     staking: &mut MarketStaking,
     stake_position: &mut StakePosition,
     amount: u64,
+    timestamp: i64,
 ) -> Result<u64> {
-    let pending_staking_fees = accrue_rewards(market, staking, stake_position)?;
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
 
     staking.total_amount_vested += amount;
     stake_position.total_amount_vested += amount;
 
+    update_shares(staking, stake_position)?;
+
     Ok(pending_staking_fees)
 }
 
@@ -34,12 +50,20 @@ pub fn withdraw(
     staking: &mut MarketStaking,
     stake_position: &mut StakePosition,
     amount: u64,
+    timestamp: i64,
 ) -> Result<u64> {
-    let pending_staking_fees = accrue_rewards(market, staking, stake_position)?;
+    require!(
+        !stake_position.is_locked(timestamp),
+        TokenMillError::StakeLocked
+    );
+
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
 
     staking.amount_staked -= amount;
     stake_position.amount_staked -= amount;
 
+    update_shares(staking, stake_position)?;
+
     Ok(pending_staking_fees)
 }
 
@@ -48,19 +72,131 @@ pub fn withdraw_vested(
     staking: &mut MarketStaking,
     stake_position: &mut StakePosition,
     amount: u64,
+    timestamp: i64,
 ) -> Result<u64> {
-    let pending_staking_fees = accrue_rewards(market, staking, stake_position)?;
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
 
     staking.total_amount_vested -= amount;
     stake_position.total_amount_vested -= amount;
 
+    update_shares(staking, stake_position)?;
+
     Ok(pending_staking_fees)
 }
 
+/// Moves staked tokens between two positions of the same market.
+/// Both positions are settled first so that rewards accrued until now stay with their owner.
+/// Vested amounts are locked in their vesting plan and can't be moved, and neither can the staked
+/// amount of a locked position. As with deposits, the destination is relocked for the full
+/// duration of its tier, so that moved stake can't take its multiplier without its lock.
+pub fn transfer_stake(
+    market: &mut Market,
+    staking: &mut MarketStaking,
+    stake_position: &mut StakePosition,
+    destination_stake_position: &mut StakePosition,
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    require_gte!(
+        stake_position.amount_staked,
+        amount,
+        TokenMillError::InsufficientStakeAmount
+    );
+    require!(
+        !stake_position.is_locked(timestamp),
+        TokenMillError::StakeLocked
+    );
+
+    accrue_rewards(market, staking, stake_position, timestamp)?;
+    accrue_rewards(market, staking, destination_stake_position, timestamp)?;
+
+    stake_position.amount_staked -= amount;
+    destination_stake_position.amount_staked += amount;
+
+    if amount > 0 {
+        destination_stake_position.lock(destination_stake_position.lock_tier, timestamp)?;
+    }
+
+    update_shares(staking, stake_position)?;
+    update_shares(staking, destination_stake_position)?;
+
+    Ok(())
+}
+
+/// Moves the position to a longer lock. Rewards accrued until now are settled at the previous
+/// multiplier, the new one applies from now on.
+pub fn lock(
+    market: &mut Market,
+    staking: &mut MarketStaking,
+    stake_position: &mut StakePosition,
+    lock_tier: u8,
+    timestamp: i64,
+) -> Result<u64> {
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
+
+    stake_position.lock(lock_tier, timestamp)?;
+
+    update_shares(staking, stake_position)?;
+
+    Ok(pending_staking_fees)
+}
+
+pub fn expire_lock(
+    market: &mut Market,
+    staking: &mut MarketStaking,
+    stake_position: &mut StakePosition,
+    timestamp: i64,
+) -> Result<u64> {
+    require!(
+        stake_position.lock_tier != 0,
+        TokenMillError::InvalidLockTier
+    );
+    require!(
+        !stake_position.is_locked(timestamp),
+        TokenMillError::StakeLocked
+    );
+
+    let pending_staking_fees = accrue_rewards(market, staking, stake_position, timestamp)?;
+
+    update_shares(staking, stake_position)?;
+
+    Ok(pending_staking_fees)
+}
+
+/// Estimated APR in bps of unlocked staked base tokens, valued at the current ask price. Locked
+/// stakes earn the multiplier of their tier on top.
+/// Assumes the swap fees of the last complete volume window recur for a whole year.
+pub fn get_staking_apr(market: &Market, staking: &MarketStaking, timestamp: i64) -> Result<u64> {
+    let (_, swap_fees) = market.volume.get_last_window(timestamp);
+
+    let total_shares = staking.total_shares;
+    let price = market.get_ask_price()?;
+
+    if swap_fees == 0 || total_shares == 0 || price == 0 {
+        return Ok(0);
+    }
+
+    // The staking fee share is in bps, so dividing the yearly staking fees by the staked value
+    // `total_shares * price * 10^quote_decimals / (BASE_PRECISION * SCALE)` yields bps as well.
+    let yearly_staking_fees = U256::from(swap_fees)
+        * U256::from(market.fees.staking_fee_share)
+        * U256::from(SECONDS_PER_YEAR / VOLUME_WINDOW_DURATION);
+
+    let numerator = yearly_staking_fees * U256::from(BASE_PRECISION) * U256::from(SCALE);
+    let denominator = U256::from(total_shares)
+        * U256::from(price)
+        * U256::from(10).pow(U256::from(market.quote_token_decimals));
+
+    Ok((numerator / denominator).try_into().unwrap_or(u64::MAX))
+}
+
+/// Settles the rewards of the position at its current shares, then drops the multiplier of its
+/// lock if expired. Callers refresh the shares once they have updated the position.
 fn accrue_rewards(
     market: &mut Market,
     staking: &mut MarketStaking,
     stake_position: &mut StakePosition,
+    timestamp: i64,
 ) -> Result<u64> {
     let pending_staking_fees = market.fees.pending_staking_fees;
     let acc_reward_amount_per_share = staking.accrue_rewards(pending_staking_fees)?;
@@ -70,6 +206,16 @@ fn accrue_rewards(
     }
 
     stake_position.accrue_rewards(acc_reward_amount_per_share)?;
+    stake_position.expire_lock(timestamp);
 
     Ok(pending_staking_fees)
 }
+
+fn update_shares(staking: &mut MarketStaking, stake_position: &mut StakePosition) -> Result<()> {
+    let shares = stake_position.get_shares()?;
+
+    staking.total_shares = staking.total_shares - stake_position.shares + shares;
+    stake_position.shares = shares;
+
+    Ok(())
+}
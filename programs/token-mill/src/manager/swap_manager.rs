@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{math::Rounding, state::Market};
+use crate::{
+    constant::{DYNAMIC_FEE_MAX_VOLATILITY_BPS, MAX_BPS},
+    errors::TokenMillError,
+    math::Rounding,
+    state::Market,
+};
 
 #[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
 pub enum SwapType {
@@ -14,26 +19,127 @@ pub enum SwapAmountType {
     ExactOutput,
 }
 
+/// Result of the pricing and fee pipeline of a swap, computed without mutating the market.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SwapOutcome {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    /// Spread of buys plus the swap fee of the market, split by `get_fee_distribution`.
+    pub swap_fee: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    /// Sell tax kept in the quote vault for holders, `quote_amount` is net of it.
+    pub holder_rewards_fee: u64,
+    pub new_base_reserve: u64,
+    /// Rounding dust of exact output buys, refunded to the trader out of the protocol fee.
+    pub quote_refund: u64,
+    /// Launch protection fee of buys, routed to staking, `quote_amount` includes it.
+    pub launch_fee: u64,
+}
+
 pub fn swap(
     market: &mut Market,
     swap_type: SwapType,
     swap_amount_type: SwapAmountType,
     amount: u64,
-) -> Result<(u64, u64, u64)> {
+    referral_fee_share: Option<u16>,
+    current_slot: u64,
+    timestamp: i64,
+) -> Result<SwapOutcome> {
+    market.apply_scheduled_updates(current_slot, timestamp);
+
+    let outcome = get_swap_outcome(
+        market,
+        swap_type,
+        swap_amount_type,
+        amount,
+        referral_fee_share,
+        current_slot,
+        timestamp,
+    )?;
+
+    apply_swap_outcome(market, &outcome);
+
+    // Sells can't dip into the quote drawn by the creator, only the vault balance backs them
+    if swap_type == SwapType::Sell && market.creator_drawn_quote > 0 {
+        require_gte!(
+            market.get_raised_quote()?,
+            market.creator_drawn_quote,
+            TokenMillError::InsufficientCurveProceeds
+        );
+    }
+
+    Ok(outcome)
+}
+
+pub fn get_swap_outcome(
+    market: &Market,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+    referral_fee_share: Option<u16>,
+    current_slot: u64,
+    timestamp: i64,
+) -> Result<SwapOutcome> {
+    require!(!market.is_migrated(), TokenMillError::MarketMigrated);
+    require!(market.are_prices_set(), TokenMillError::PricesNotSet);
+
+    let launch_fee_bps = match swap_type {
+        SwapType::Buy => market.get_launch_fee_bps(current_slot),
+        SwapType::Sell => 0,
+    };
+
     let (base_amount, quote_amount) = match (swap_type, swap_amount_type) {
-        (SwapType::Buy, SwapAmountType::ExactInput) => market.get_base_amount_out(amount)?,
+        (SwapType::Buy, SwapAmountType::ExactInput) => {
+            let (base_amount, quote_amount) = market.get_base_amount_out(
+                get_amount_before_fees(amount, launch_fee_bps + market.get_swap_fee_bps())?,
+            )?;
+
+            // Filled up to the unlocked supply, the rest of the quote amount isn't spent
+            let available_supply = market.get_available_supply(timestamp);
+
+            if base_amount > available_supply {
+                market.get_quote_amount(available_supply, SwapAmountType::ExactOutput)?
+            } else {
+                (base_amount, quote_amount)
+            }
+        }
         (SwapType::Buy, SwapAmountType::ExactOutput) => {
+            require_gte!(
+                market.get_available_supply(timestamp),
+                amount,
+                TokenMillError::SupplyLocked
+            );
+
             market.get_quote_amount(amount, swap_amount_type)?
         }
         (SwapType::Sell, SwapAmountType::ExactInput) => {
             market.get_quote_amount(amount, swap_amount_type)?
         }
-        (SwapType::Sell, SwapAmountType::ExactOutput) => market.get_base_amount_in(amount)?,
+        (SwapType::Sell, SwapAmountType::ExactOutput) => market.get_base_amount_in(
+            market
+                .fees
+                .get_gross_sell_amount(amount, market.get_swap_fee_bps())?,
+        )?,
     };
 
-    let mut swap_fee = 0;
+    let holder_rewards_fee = match swap_type {
+        SwapType::Buy => 0,
+        SwapType::Sell => market.fees.get_holder_rewards_fee(quote_amount)?,
+    };
 
-    match swap_type {
+    let market_swap_fee = market.get_swap_fee(quote_amount)?;
+
+    let quote_amount = match swap_type {
+        SwapType::Buy => quote_amount,
+        SwapType::Sell => quote_amount - holder_rewards_fee - market_swap_fee,
+    };
+
+    let mut swap_fee = market_swap_fee;
+
+    let new_base_reserve = match swap_type {
         SwapType::Buy => {
             let (_, buyback_amount) = market.get_quote_amount_with_parameters(
                 market.circulating_supply(),
@@ -43,15 +149,643 @@ pub fn swap(
             )?;
 
             if quote_amount > buyback_amount {
-                swap_fee = quote_amount - buyback_amount;
+                swap_fee += quote_amount - buyback_amount;
             }
 
-            market.base_reserve -= base_amount;
+            market.base_reserve - base_amount
         }
-        SwapType::Sell => {
-            market.base_reserve += base_amount;
+        SwapType::Sell => market.base_reserve + base_amount,
+    };
+
+    let (creator_fee, staking_fee, mut protocol_fee, referral_fee) = market
+        .fees
+        .get_fee_distribution(swap_fee, referral_fee_share)?;
+
+    let quote_refund = match (swap_type, swap_amount_type) {
+        (SwapType::Buy, SwapAmountType::ExactOutput) => {
+            let (_, exact_quote_amount) = market.get_quote_amount_with_parameters(
+                market.circulating_supply(),
+                base_amount,
+                SwapAmountType::ExactOutput,
+                Rounding::Down,
+            )?;
+
+            (quote_amount - exact_quote_amount).min(protocol_fee)
         }
+        _ => 0,
+    };
+
+    swap_fee -= quote_refund;
+    protocol_fee -= quote_refund;
+
+    let launch_fee = get_launch_fee(quote_amount, launch_fee_bps)?;
+
+    let quote_amount = match swap_type {
+        SwapType::Buy => quote_amount + market_swap_fee + launch_fee,
+        SwapType::Sell => quote_amount,
+    };
+
+    Ok(SwapOutcome {
+        base_amount,
+        quote_amount,
+        swap_fee,
+        creator_fee,
+        staking_fee,
+        protocol_fee,
+        referral_fee,
+        holder_rewards_fee,
+        new_base_reserve,
+        quote_refund,
+        launch_fee,
+    })
+}
+
+pub fn apply_swap_outcome(market: &mut Market, outcome: &SwapOutcome) {
+    market.base_reserve = outcome.new_base_reserve;
+    market.traded = 1;
+
+    market.fees.pending_creator_fees += outcome.creator_fee;
+    market.fees.pending_staking_fees += outcome.staking_fee + outcome.launch_fee;
+    market.pending_holder_rewards += outcome.holder_rewards_fee;
+}
+
+/// Waives the swap fee of a fee exempt trader from an applied outcome: buys pay the bid price of
+/// the curve and sells receive it in full. Holder rewards and launch fees still apply.
+pub fn waive_swap_fee(market: &mut Market, outcome: &mut SwapOutcome, swap_type: SwapType) {
+    market.fees.pending_creator_fees -= outcome.creator_fee;
+    market.fees.pending_staking_fees -= outcome.staking_fee;
+
+    match swap_type {
+        SwapType::Buy => outcome.quote_amount -= outcome.swap_fee + outcome.quote_refund,
+        SwapType::Sell => outcome.quote_amount += outcome.swap_fee,
+    }
+
+    outcome.swap_fee = 0;
+    outcome.creator_fee = 0;
+    outcome.staking_fee = 0;
+    outcome.protocol_fee = 0;
+    outcome.referral_fee = 0;
+    outcome.quote_refund = 0;
+}
+
+/// Scales the variable fee of a dynamic fee market with `volatility_bps`, the move of the mid
+/// price away from its recent twap, from 0 up to `max_swap_fee_bps` on top of the swap fee
+/// once the volatility reaches `DYNAMIC_FEE_MAX_VOLATILITY_BPS`.
+pub fn update_variable_fee(market: &mut Market, volatility_bps: u64, max_swap_fee_bps: u16) {
+    let fee_range = u64::from(max_swap_fee_bps.saturating_sub(market.swap_fee_bps));
+
+    // At most the fee range, which fits in a u16
+    market.variable_fee_bps = (fee_range * volatility_bps.min(DYNAMIC_FEE_MAX_VOLATILITY_BPS)
+        / DYNAMIC_FEE_MAX_VOLATILITY_BPS) as u16;
+}
+
+fn get_launch_fee(quote_amount: u64, launch_fee_bps: u16) -> Result<u64> {
+    Ok(u64::try_from(
+        u128::from(quote_amount) * u128::from(launch_fee_bps) / MAX_BPS as u128,
+    )?)
+}
+
+/// Part of an exact input buy left for the curve once the launch and swap fees are set aside.
+fn get_amount_before_fees(amount: u64, fee_bps: u16) -> Result<u64> {
+    Ok(u64::try_from(
+        u128::from(amount) * MAX_BPS as u128 / (MAX_BPS as u128 + u128::from(fee_bps)),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use bytemuck::Zeroable;
+    use joelana_test_utils::utils::token_mill::{constants::*, curve_generator::Curve};
+
+    use super::*;
+    use crate::state::PriceBounds;
+
+    const TOTAL_SUPPLY: u64 = 1_000_000_000_000;
+
+    fn setup_market() -> Market {
+        let mut market = Market::zeroed();
+
+        market
+            .initialize(
+                255,
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                Pubkey::new_unique(),
+                9,
+                TOTAL_SUPPLY,
+                DEFAULT_CREATOR_FEE_SHARE,
+                DEFAULT_STAKING_FEE_SHARE,
+                DEFAULT_REFERRAL_FEE_SHARE,
+            )
+            .unwrap();
+
+        let curve = Curve::default();
+
+        market
+            .check_and_set_prices(curve.bid_prices, curve.ask_prices, &PriceBounds::default())
+            .unwrap();
+
+        market
+    }
+
+    #[test]
+    fn get_swap_outcome_before_prices_set() {
+        let mut market = setup_market();
+
+        market.bid_prices.fill(0);
+        market.ask_prices.fill(0);
+
+        assert_eq!(
+            get_swap_outcome(
+                &market,
+                SwapType::Buy,
+                SwapAmountType::ExactInput,
+                1_000_000_000,
+                None,
+                0,
+                0,
+            )
+            .unwrap_err(),
+            TokenMillError::PricesNotSet.into()
+        );
+    }
+
+    #[test]
+    fn get_swap_outcome_does_not_mutate_market() {
+        let market = setup_market();
+
+        let first = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+        let second = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(market.base_reserve, TOTAL_SUPPLY);
+        assert_eq!(market.fees.pending_creator_fees, 0);
     }
 
-    Ok((base_amount, quote_amount, swap_fee))
+    #[test]
+    fn buy_outcome() {
+        let market = setup_market();
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            Some(DEFAULT_REFERRAL_FEE_SHARE),
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.base_amount, 1_000_000_000);
+        assert_eq!(outcome.new_base_reserve, TOTAL_SUPPLY - 1_000_000_000);
+        assert!(outcome.swap_fee > 0);
+        assert!(outcome.referral_fee > 0);
+        assert_eq!(
+            outcome.creator_fee + outcome.staking_fee + outcome.protocol_fee + outcome.referral_fee,
+            outcome.swap_fee
+        );
+    }
+
+    #[test]
+    fn exact_output_buy_refund() {
+        let market = setup_market();
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_234_567_891,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let (_, exact_quote_amount) = market
+            .get_quote_amount_with_parameters(
+                0,
+                1_234_567_891,
+                SwapAmountType::ExactOutput,
+                Rounding::Down,
+            )
+            .unwrap();
+
+        assert!(outcome.quote_refund <= 1);
+        assert_eq!(
+            outcome.quote_amount - outcome.quote_refund,
+            exact_quote_amount
+        );
+        assert_eq!(
+            outcome.creator_fee + outcome.staking_fee + outcome.protocol_fee,
+            outcome.swap_fee
+        );
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.quote_refund, 0);
+    }
+
+    #[test]
+    fn buy_outcome_without_referral() {
+        let market = setup_market();
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.referral_fee, 0);
+        assert_eq!(
+            outcome.creator_fee + outcome.staking_fee + outcome.protocol_fee,
+            outcome.swap_fee
+        );
+    }
+
+    #[test]
+    fn buy_outcome_with_launch_fee() {
+        let mut market = setup_market();
+
+        let untaxed_outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        market.launch_fee_bps = 1_000;
+        market.launch_fee_slots = 100;
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.launch_fee, untaxed_outcome.quote_amount / 10);
+        assert_eq!(
+            outcome.quote_amount,
+            untaxed_outcome.quote_amount + outcome.launch_fee
+        );
+        assert_eq!(outcome.swap_fee, untaxed_outcome.swap_fee);
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            100,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome, untaxed_outcome);
+
+        let outcome = swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            50,
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.launch_fee > 0);
+        assert!(outcome.quote_amount <= 1_000_000_000);
+        assert_eq!(
+            market.fees.pending_staking_fees,
+            outcome.staking_fee + outcome.launch_fee
+        );
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(outcome.launch_fee, 0);
+    }
+
+    #[test]
+    fn buy_then_sell_outcome() {
+        let mut market = setup_market();
+
+        let buy_outcome = swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(market.base_reserve, buy_outcome.new_base_reserve);
+        assert_eq!(market.fees.pending_creator_fees, buy_outcome.creator_fee);
+        assert_eq!(market.fees.pending_staking_fees, buy_outcome.staking_fee);
+
+        let sell_outcome = swap(
+            &mut market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(sell_outcome.swap_fee, 0);
+        assert_eq!(market.base_reserve, TOTAL_SUPPLY);
+        assert!(sell_outcome.quote_amount < buy_outcome.quote_amount);
+    }
+
+    #[test]
+    fn sell_outcome_with_holder_rewards_fee() {
+        let mut market = setup_market();
+
+        swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let untaxed_outcome = get_swap_outcome(
+            &market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        market.fees.holder_rewards_fee_bps = 500;
+
+        let outcome = swap(
+            &mut market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(
+            outcome.holder_rewards_fee,
+            untaxed_outcome.quote_amount * 500 / 10_000
+        );
+        assert_eq!(
+            outcome.quote_amount + outcome.holder_rewards_fee,
+            untaxed_outcome.quote_amount
+        );
+        assert_eq!(market.pending_holder_rewards, outcome.holder_rewards_fee);
+
+        let buy_outcome = swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(buy_outcome.holder_rewards_fee, 0);
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Sell,
+            SwapAmountType::ExactOutput,
+            1_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.quote_amount >= 1_000_000);
+    }
+
+    #[test]
+    fn swap_outcome_with_market_swap_fee() {
+        let mut market = setup_market();
+
+        let untaxed_outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        market.swap_fee_bps = 100;
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let market_swap_fee = untaxed_outcome.quote_amount / 100;
+
+        assert_eq!(
+            outcome.quote_amount,
+            untaxed_outcome.quote_amount + market_swap_fee
+        );
+        assert_eq!(outcome.swap_fee, untaxed_outcome.swap_fee + market_swap_fee);
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Buy,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.quote_amount <= 1_000_000_000);
+
+        swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.swap_fee > 0);
+        assert_eq!(
+            outcome.swap_fee,
+            outcome.creator_fee + outcome.staking_fee + outcome.protocol_fee
+        );
+
+        let outcome = get_swap_outcome(
+            &market,
+            SwapType::Sell,
+            SwapAmountType::ExactOutput,
+            1_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        assert!(outcome.quote_amount >= 1_000_000);
+    }
+
+    #[test]
+    fn variable_fee_scales_with_volatility() {
+        let mut market = setup_market();
+
+        market.swap_fee_bps = 100;
+
+        update_variable_fee(&mut market, 0, 500);
+        assert_eq!(market.get_swap_fee_bps(), 100);
+
+        update_variable_fee(&mut market, DYNAMIC_FEE_MAX_VOLATILITY_BPS / 4, 500);
+        assert_eq!(market.get_swap_fee_bps(), 200);
+
+        // Capped at the max swap fee
+        update_variable_fee(&mut market, 2 * DYNAMIC_FEE_MAX_VOLATILITY_BPS, 500);
+        assert_eq!(market.get_swap_fee_bps(), 500);
+
+        // The swap fee of the market is above the max
+        update_variable_fee(&mut market, DYNAMIC_FEE_MAX_VOLATILITY_BPS, 50);
+        assert_eq!(market.get_swap_fee_bps(), 100);
+    }
+
+    #[test]
+    fn waive_swap_fee_of_buy_and_sell() {
+        let mut market = setup_market();
+
+        market.swap_fee_bps = 100;
+
+        let mut buy_outcome = swap(
+            &mut market,
+            SwapType::Buy,
+            SwapAmountType::ExactOutput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        let (_, bid_quote_amount) = market
+            .get_quote_amount(1_000_000_000, SwapAmountType::ExactInput)
+            .unwrap();
+
+        waive_swap_fee(&mut market, &mut buy_outcome, SwapType::Buy);
+
+        // Rounded up against the trader
+        assert!(buy_outcome.quote_amount - bid_quote_amount <= 1);
+        assert_eq!(buy_outcome.swap_fee, 0);
+        assert_eq!(buy_outcome.protocol_fee, 0);
+        assert_eq!(market.fees.pending_creator_fees, 0);
+        assert_eq!(market.fees.pending_staking_fees, 0);
+
+        let mut sell_outcome = swap(
+            &mut market,
+            SwapType::Sell,
+            SwapAmountType::ExactInput,
+            1_000_000_000,
+            None,
+            0,
+            0,
+        )
+        .unwrap();
+
+        waive_swap_fee(&mut market, &mut sell_outcome, SwapType::Sell);
+
+        assert_eq!(sell_outcome.quote_amount, bid_quote_amount);
+        assert_eq!(sell_outcome.swap_fee, 0);
+        assert_eq!(market.fees.pending_creator_fees, 0);
+        assert_eq!(market.fees.pending_staking_fees, 0);
+    }
 }
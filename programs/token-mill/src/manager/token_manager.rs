@@ -3,13 +3,21 @@ use anchor_spl::{
     token::Token,
     token_2022::spl_token_2022::{
         self,
-        extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+        extension::{
+            transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType,
+            StateWithExtensions,
+        },
+    },
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
     },
-    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
 
+use crate::{clock::get_clock, errors::TokenMillError};
+
 /// Checks that the mint account only has allowed extensions.
-/// Tax-transfer quote tokens for example would cause some issues with the current implementation.
+/// Transfer fees are withheld from the amounts received by the market, see `get_transfer_fee`.
 pub fn check_mint_extensions(mint_account: &InterfaceAccount<Mint>) -> Result<bool> {
     let mint_account_info = mint_account.to_account_info();
     if *mint_account_info.owner == Token::id() {
@@ -21,7 +29,10 @@ pub fn check_mint_extensions(mint_account: &InterfaceAccount<Mint>) -> Result<bo
     let extensions = mint.get_extension_types()?;
 
     for e in extensions {
-        if e != ExtensionType::MetadataPointer && e != ExtensionType::TokenMetadata {
+        if e != ExtensionType::MetadataPointer
+            && e != ExtensionType::TokenMetadata
+            && e != ExtensionType::TransferFeeConfig
+        {
             return Ok(false);
         }
     }
@@ -29,6 +40,47 @@ pub fn check_mint_extensions(mint_account: &InterfaceAccount<Mint>) -> Result<bo
     Ok(true)
 }
 
+fn get_transfer_fee_config(
+    mint_account: &InterfaceAccount<Mint>,
+) -> Result<Option<TransferFeeConfig>> {
+    let mint_account_info = mint_account.to_account_info();
+    if *mint_account_info.owner == Token::id() {
+        return Ok(None);
+    }
+
+    let mint_data = mint_account_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    Ok(mint.get_extension::<TransferFeeConfig>().ok().copied())
+}
+
+/// Fee withheld by the TransferFee extension of the mint when transferring `amount`,
+/// 0 for mints without the extension.
+pub fn get_transfer_fee(mint_account: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let Some(transfer_fee_config) = get_transfer_fee_config(mint_account)? else {
+        return Ok(0);
+    };
+
+    transfer_fee_config
+        .calculate_epoch_fee(get_clock()?.epoch, amount)
+        .ok_or(TokenMillError::MathError.into())
+}
+
+/// Fee to add to a transfer for `post_fee_amount` to be received, 0 for mints without the
+/// TransferFee extension.
+pub fn get_transfer_inverse_fee(
+    mint_account: &InterfaceAccount<Mint>,
+    post_fee_amount: u64,
+) -> Result<u64> {
+    let Some(transfer_fee_config) = get_transfer_fee_config(mint_account)? else {
+        return Ok(0);
+    };
+
+    transfer_fee_config
+        .calculate_inverse_epoch_fee(get_clock()?.epoch, post_fee_amount)
+        .ok_or(TokenMillError::MathError.into())
+}
+
 pub fn transfer_from_pda<'info>(
     mint: &InterfaceAccount<'info, Mint>,
     pda: AccountInfo<'info>,
@@ -54,6 +106,24 @@ pub fn transfer_from_pda<'info>(
     )
 }
 
+pub fn close_pda_token_account<'info>(
+    pda: AccountInfo<'info>,
+    pda_token_account: &InterfaceAccount<'info, TokenAccount>,
+    recipient: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    pda_seeds: &[&[u8]],
+) -> Result<()> {
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: pda_token_account.to_account_info(),
+            destination: recipient,
+            authority: pda,
+        },
+        &[pda_seeds],
+    ))
+}
+
 pub fn transfer_from_eoa<'info>(
     mint: &InterfaceAccount<'info, Mint>,
     eoa: &Signer<'info>,
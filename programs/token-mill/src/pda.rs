@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{
+    DENYLIST_PDA_SEED, LAUNCH_PURCHASE_PDA_SEED, MARKET_ORACLE_PDA_SEED, MARKET_PDA_SEED,
+    MARKET_STAKING_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED, REFERRAL_ACCOUNT_PDA_SEED,
+    REFERRER_BINDING_PDA_SEED, REVENUE_REPORT_PDA_SEED, STAKING_POSITION_PDA_SEED,
+};
+
+/// Market of `base_token_mint`, from which the other PDAs of the market are derived.
+pub fn find_market_address(base_token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_PDA_SEED.as_bytes(), base_token_mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_market_staking_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_STAKING_PDA_SEED.as_bytes(), market.as_ref()],
+        &crate::ID,
+    )
+}
+
+pub fn find_stake_position_address(market: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            STAKING_POSITION_PDA_SEED.as_bytes(),
+            market.as_ref(),
+            user.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Additional position of `user`, see `create_indexed_stake_position`.
+pub fn find_indexed_stake_position_address(
+    market: &Pubkey,
+    user: &Pubkey,
+    index: u32,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            STAKING_POSITION_PDA_SEED.as_bytes(),
+            market.as_ref(),
+            user.as_ref(),
+            &index.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_quote_token_badge_address(config: &Pubkey, quote_token_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            QUOTE_TOKEN_BADGE_PDA_SEED.as_bytes(),
+            config.as_ref(),
+            quote_token_mint.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_referral_account_address(config: &Pubkey, referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERRAL_ACCOUNT_PDA_SEED.as_bytes(),
+            config.as_ref(),
+            referrer.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+/// Referrer binding of `trader`, whose referral token account its swaps must credit.
+pub fn find_referrer_binding_address(config: &Pubkey, trader: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REFERRER_BINDING_PDA_SEED.as_bytes(),
+            config.as_ref(),
+            trader.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_launch_purchase_address(market: &Pubkey, wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            LAUNCH_PURCHASE_PDA_SEED.as_bytes(),
+            market.as_ref(),
+            wallet.as_ref(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_market_oracle_address(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MARKET_ORACLE_PDA_SEED.as_bytes(), market.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Revenue report of `epoch`, see `RevenueReport::get_epoch`.
+pub fn find_revenue_report_address(
+    config: &Pubkey,
+    quote_token_mint: &Pubkey,
+    epoch: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            REVENUE_REPORT_PDA_SEED.as_bytes(),
+            config.as_ref(),
+            quote_token_mint.as_ref(),
+            &epoch.to_le_bytes(),
+        ],
+        &crate::ID,
+    )
+}
+
+pub fn find_denylist_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DENYLIST_PDA_SEED.as_bytes(), config.as_ref()], &crate::ID)
+}
+
+/// Event authority of the program, passed to every instruction emitting events.
+pub fn find_event_authority_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"__event_authority"], &crate::ID)
+}
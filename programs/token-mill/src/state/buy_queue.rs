@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const BUY_QUEUE_PDA_SEED: &str = "buy_queue";
+
+/// Orders a queue takes, each at least `max_quote_amount / MAX_QUEUED_BUY_ORDERS`, bounding the
+/// fills needed to open the market.
+pub const MAX_QUEUED_BUY_ORDERS: u64 = 200;
+
+/// Buy orders escrowed in quote before a market opens, see `QueuedBuyOrder`. The queue holds a
+/// `MarketPauser::BuyQueue` pause on the market, and once `open_slot` is reached cranks fill its
+/// orders in placement order before `open_buy_queue` lifts the pause. Orders left unfilled at
+/// `close_slot` no longer hold the market paused and are refunded instead.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct BuyQueue {
+    pub bump: u8,
+    pub market: Pubkey,
+    /// Quote spent on the curve at open, orders are filled pro rata when more is queued.
+    pub max_quote_amount: u64,
+    pub open_slot: u64,
+    pub close_slot: u64,
+
+    pub total_quote_queued: u64,
+    pub order_count: u64,
+    pub next_fill_sequence: u64,
+}
+
+impl BuyQueue {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        max_quote_amount: u64,
+        open_slot: u64,
+        close_slot: u64,
+    ) -> Result<()> {
+        require!(max_quote_amount > 0, TokenMillError::InvalidAmount);
+
+        self.bump = bump;
+        self.market = market;
+        self.max_quote_amount = max_quote_amount;
+        self.open_slot = open_slot;
+        self.close_slot = close_slot;
+
+        Ok(())
+    }
+
+    pub fn is_open(&self, current_slot: u64) -> bool {
+        current_slot >= self.open_slot
+    }
+
+    /// Whether the fill window of the queue is over, see `close_slot`.
+    pub fn is_closed(&self, current_slot: u64) -> bool {
+        current_slot >= self.close_slot
+    }
+
+    /// Whether all the orders of the queue have been filled.
+    pub fn is_filled(&self) -> bool {
+        self.next_fill_sequence == self.order_count
+    }
+
+    /// Queues an order of `quote_amount`, returning its sequence.
+    pub fn place(&mut self, quote_amount: u64) -> Result<u64> {
+        require!(
+            self.order_count < MAX_QUEUED_BUY_ORDERS,
+            TokenMillError::BuyQueueFull
+        );
+        require_gte!(
+            quote_amount,
+            self.max_quote_amount / MAX_QUEUED_BUY_ORDERS,
+            TokenMillError::QueuedBuyOrderTooSmall
+        );
+
+        let sequence = self.order_count;
+
+        self.order_count += 1;
+        self.total_quote_queued += quote_amount;
+
+        Ok(sequence)
+    }
+
+    pub fn cancel(&mut self, quote_amount: u64) {
+        self.total_quote_queued -= quote_amount;
+    }
+
+    /// Consumes the next sequence of the queue, returning the quote amount of the order spent on
+    /// the curve.
+    pub fn fill(&mut self, order: &QueuedBuyOrder) -> Result<u64> {
+        require_eq!(
+            order.sequence,
+            self.next_fill_sequence,
+            TokenMillError::InvalidFillSequence
+        );
+
+        self.next_fill_sequence += 1;
+
+        self.get_fill_amount(order.quote_amount)
+    }
+
+    /// Share of `quote_amount` spent at open, scaled down when the queue exceeds `max_quote_amount`.
+    pub fn get_fill_amount(&self, quote_amount: u64) -> Result<u64> {
+        if self.total_quote_queued <= self.max_quote_amount {
+            return Ok(quote_amount);
+        }
+
+        Ok(u64::try_from(
+            u128::from(quote_amount) * u128::from(self.max_quote_amount)
+                / u128::from(self.total_quote_queued),
+        )?)
+    }
+}
+
+pub const QUEUED_BUY_ORDER_PDA_SEED: &str = "queued_buy_order";
+
+/// Quote escrowed by a wallet in the buy queue of a market, one order per wallet. A cancelled
+/// order keeps its sequence with no quote so that the queue still fills in order. Filled orders
+/// are closed to the cranker, covering the token accounts it opens for the owner.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct QueuedBuyOrder {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub quote_amount: u64,
+}
+
+impl QueuedBuyOrder {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        owner: Pubkey,
+        sequence: u64,
+        quote_amount: u64,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.owner = owner;
+        self.sequence = sequence;
+        self.quote_amount = quote_amount;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(sequence: u64, quote_amount: u64) -> QueuedBuyOrder {
+        QueuedBuyOrder {
+            bump: 0,
+            market: Pubkey::default(),
+            owner: Pubkey::default(),
+            sequence,
+            quote_amount,
+        }
+    }
+
+    #[test]
+    fn fill_pro_rata() {
+        let mut buy_queue = BuyQueue {
+            bump: 0,
+            market: Pubkey::default(),
+            max_quote_amount: 1_000,
+            open_slot: 0,
+            close_slot: 0,
+            total_quote_queued: 0,
+            order_count: 0,
+            next_fill_sequence: 0,
+        };
+
+        assert_eq!(buy_queue.place(300).unwrap(), 0);
+        assert_eq!(buy_queue.place(500).unwrap(), 1);
+
+        // Filled in full below the max quote amount
+        assert_eq!(buy_queue.get_fill_amount(500).unwrap(), 500);
+
+        assert_eq!(buy_queue.place(1_200).unwrap(), 2);
+
+        assert_eq!(
+            buy_queue.fill(&order(1, 500)).unwrap_err(),
+            TokenMillError::InvalidFillSequence.into()
+        );
+
+        assert_eq!(buy_queue.fill(&order(0, 300)).unwrap(), 150);
+        assert_eq!(buy_queue.fill(&order(1, 500)).unwrap(), 250);
+
+        assert!(!buy_queue.is_filled());
+
+        assert_eq!(buy_queue.fill(&order(2, 1_200)).unwrap(), 600);
+
+        assert!(buy_queue.is_filled());
+    }
+
+    #[test]
+    fn place_limits() {
+        let mut buy_queue = BuyQueue {
+            bump: 0,
+            market: Pubkey::default(),
+            max_quote_amount: 1_000_000,
+            open_slot: 0,
+            close_slot: 0,
+            total_quote_queued: 0,
+            order_count: 0,
+            next_fill_sequence: 0,
+        };
+
+        let min_quote_amount = 1_000_000 / MAX_QUEUED_BUY_ORDERS;
+
+        assert_eq!(
+            buy_queue.place(min_quote_amount - 1).unwrap_err(),
+            TokenMillError::QueuedBuyOrderTooSmall.into()
+        );
+
+        for sequence in 0..MAX_QUEUED_BUY_ORDERS {
+            assert_eq!(buy_queue.place(min_quote_amount).unwrap(), sequence);
+        }
+
+        assert_eq!(
+            buy_queue.place(min_quote_amount).unwrap_err(),
+            TokenMillError::BuyQueueFull.into()
+        );
+    }
+}
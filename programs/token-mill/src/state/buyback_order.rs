@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+pub const BUYBACK_ORDER_PDA_SEED: &str = "buyback_order";
+
+/// Standing order of the creator of a market to buy base tokens back from the curve.
+/// The vault of the order is funded with creator fees, and cranks spend it while the ask price
+/// of the market is below the price floor.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct BuybackOrder {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub price_floor: u64,
+
+    pub total_quote_amount_spent: u64,
+    pub total_base_amount_bought: u64,
+}
+
+impl BuybackOrder {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, quote_amount: u64, base_amount: u64) {
+        self.total_quote_amount_spent += quote_amount;
+        self.total_base_amount_bought += base_amount;
+    }
+}
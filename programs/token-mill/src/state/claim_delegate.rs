@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const CLAIM_DELEGATE_PDA_SEED: &str = "claim_delegate";
+
+/// Pubkey allowed to trigger the staking and vesting claims of a stake position in place of its owner.
+/// With auto-claim, any keeper can trigger them. Claimed tokens still go to the ATAs of the owner.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ClaimDelegate {
+    pub bump: u8,
+    pub stake_position: Pubkey,
+    pub delegate: Pubkey,
+    pub auto_claim: bool,
+}
+
+impl ClaimDelegate {
+    pub fn initialize(&mut self, bump: u8, stake_position: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.stake_position = stake_position;
+
+        Ok(())
+    }
+
+    /// Checks that a claim is signed either by the owner of the stake position, or by its delegate.
+    /// Claims of positions registered for auto-claim don't need any signer.
+    pub fn check_claim_authority(
+        user: &AccountInfo,
+        claim_delegate: Option<&Account<ClaimDelegate>>,
+        delegate: Option<&Signer>,
+    ) -> Result<()> {
+        if user.is_signer {
+            return Ok(());
+        }
+
+        match (claim_delegate, delegate) {
+            (Some(claim_delegate), _) if claim_delegate.auto_claim => Ok(()),
+            (Some(claim_delegate), Some(delegate)) if claim_delegate.delegate == delegate.key() => {
+                Ok(())
+            }
+            _ => Err(TokenMillError::InvalidAuthority.into()),
+        }
+    }
+}
@@ -1,5 +1,20 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constant::{MAX_BPS, MAX_CREATOR_DRAW_BPS, MAX_REFERRAL_TIERS, MAX_VESTING_TEMPLATES},
+    errors::TokenMillError,
+    manager::{
+        curve_manager::CurveParameters,
+        migration_manager::{METEORA_DAMM_V2_PROGRAM_ID, RAYDIUM_CP_SWAP_PROGRAM_ID},
+    },
+};
+
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Size of the configs created by the first release, whose fields are a prefix of the current
+/// ones. Later fields are appended and read as zero until `backfill_config` grows the config.
+pub const BASELINE_CONFIG_SIZE: usize = 101;
+
 #[account]
 #[derive(InitSpace)]
 pub struct TokenMillConfig {
@@ -8,6 +23,204 @@ pub struct TokenMillConfig {
     pub protocol_fee_recipient: Pubkey,
     pub default_protocol_fee_share: u16,
     pub referral_fee_share: u16,
+    /// Whether creators may open additional quote markets for their base token.
+    pub secondary_markets_enabled: bool,
+    /// External program validating market creations and swaps, see `policy_manager`.
+    pub policy_program: Option<Pubkey>,
+    /// Maximum number of slots a market can be paused for, see `pause_until`.
+    pub max_pause_duration: u64,
+    /// Curve applied to markets at creation, so that they can be traded right away.
+    pub default_curve: Option<CurveParameters>,
+    /// Maximum share of the remaining reserve a single swap can take, 0 meaning no limit.
+    pub max_swap_size_bps: u16,
+    /// Whether swaps and claims are checked against the config denylist, see `Denylist`.
+    pub denylist_enabled: bool,
+    /// Referral fee shares unlocked by referrers past a cumulative referred volume, see `ReferralAccount`.
+    #[max_len(MAX_REFERRAL_TIERS)]
+    pub referral_tiers: Vec<ReferralTier>,
+    /// Vesting schedules of the platform, referenced by index by `create_vesting_plan_from_template`.
+    /// Templates are append-only so that indices stay stable.
+    #[max_len(MAX_VESTING_TEMPLATES)]
+    pub vesting_templates: Vec<VestingTemplate>,
+    /// Bounds every market curve is checked against when its prices are set.
+    pub price_bounds: PriceBounds,
+    /// Rebates of the protocol fee to high-volume traders, snapshotted by each revenue report.
+    pub trader_rebate_params: TraderRebateParams,
+    /// Attests markets as verified launches after off-chain checks, see `set_market_verification`.
+    pub verifier: Option<Pubkey>,
+    /// AMM markets graduate to, see `migrate`.
+    pub migration_target: MigrationTarget,
+    /// Curve proceeds a market must reach before it can be migrated, 0 meaning no threshold.
+    pub graduation_quote_threshold: u64,
+    /// Share of the total supply that must be circulating before a market can be migrated.
+    pub graduation_supply_bps: u16,
+    /// Receives the rent of the accounts closed by `close_market`.
+    pub rent_recipient: Pubkey,
+    /// Markets a wallet can create per day, 0 meaning no limit, see `CreatorActivity`.
+    pub max_daily_markets_per_creator: u16,
+    /// USD value the curve proceeds must reach before a market can be migrated.
+    pub usd_graduation: UsdGraduation,
+    /// Whether referral fees are paid to referrers or left to the platform to settle, see
+    /// `ReferralSettlement`.
+    pub referral_settlement: ReferralSettlement,
+    /// Whether swaps pay referral fees at all, markets can also be disabled one by one, see
+    /// `update_referral_params`.
+    pub referral_enabled: bool,
+    /// External program notified of the fee breakdown of each swap, see `fee_hook_manager`.
+    pub fee_hook_program: Option<Pubkey>,
+    /// Lamports a creator posts when launching a market, see `Market::creation_bond`.
+    pub creation_bond: CreationBond,
+    /// Share of the raised quote creators can draw before graduation, see `Market::request_draw`.
+    pub creator_draw: CreatorDraw,
+    /// Seconds between queuing and executing a sensitive change, see `PendingConfigChange`.
+    pub config_change_delay: i64,
+    /// Share of the fees swept by `sweep_fees` paid to the keeper, up to `MAX_SWEEP_TIP_BPS`.
+    pub sweep_tip_bps: u16,
+    /// Whether market creators can pause their own market, see `pause_market`.
+    pub creator_pause_enabled: bool,
+    /// External program notified of graduations, e.g. to post them cross-chain, see
+    /// `graduation_hook_manager`.
+    pub graduation_hook_program: Option<Pubkey>,
+    /// Bounds of the swap fee creators choose for their market, see `Market::swap_fee_bps`.
+    pub min_swap_fee_bps: u16,
+    pub max_swap_fee_bps: u16,
+    /// Layout of the config, see `TokenMillConfig::backfill`.
+    pub version: u8,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct ReferralTier {
+    pub min_referred_volume: u64,
+    pub referral_fee_share: u16,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct VestingTemplate {
+    pub name: [u8; 32],
+    pub vesting_duration: i64,
+    pub cliff_duration: i64,
+}
+
+/// Bounds of a price curve, a zero maximum meaning no bound.
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct PriceBounds {
+    /// Quote amount raised by selling the whole supply on the ask curve.
+    pub min_total_raise: u64,
+    pub max_total_raise: u64,
+    /// Spread of the bid price below the ask price, checked at every interval.
+    pub min_spread_bps: u16,
+    pub max_spread_bps: u16,
+}
+
+impl PriceBounds {
+    pub fn check(&self) -> Result<()> {
+        require!(
+            self.max_total_raise == 0 || self.min_total_raise <= self.max_total_raise,
+            TokenMillError::InvalidPriceBounds
+        );
+        require!(
+            u64::from(self.min_spread_bps) <= MAX_BPS
+                && u64::from(self.max_spread_bps) <= MAX_BPS
+                && (self.max_spread_bps == 0 || self.min_spread_bps <= self.max_spread_bps),
+            TokenMillError::InvalidPriceBounds
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub enum MigrationTarget {
+    RaydiumCpmm,
+    MeteoraDammV2,
+}
+
+/// With `OffChain`, swaps pay referral fees to the protocol fee recipient along with the protocol
+/// fee and only emit them in `TokenMillOffChainReferralEvent`, so that platforms settling referrals
+/// off-chain don't need accounts per referrer.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub enum ReferralSettlement {
+    OnChain,
+    OffChain,
+}
+
+impl MigrationTarget {
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            MigrationTarget::RaydiumCpmm => RAYDIUM_CP_SWAP_PROGRAM_ID,
+            MigrationTarget::MeteoraDammV2 => METEORA_DAMM_V2_PROGRAM_ID,
+        }
+    }
+}
+
+/// Share of the protocol fee set aside during an epoch for the traders whose volume exceeds
+/// the threshold, see `TraderVolume`.
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct TraderRebateParams {
+    pub volume_threshold: u64,
+    pub protocol_fee_share: u16,
+}
+
+/// Graduation threshold in USD, valuing the curve proceeds with the Pyth price feed set on the
+/// badge of the quote token. A zero threshold means no USD threshold.
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct UsdGraduation {
+    /// Scaled by `USD_PRECISION`.
+    pub usd_threshold: u64,
+    /// Maximum age of the price in seconds.
+    pub max_price_staleness: i64,
+    /// Maximum confidence interval of the price, in bps of the price.
+    pub max_price_conf_bps: u16,
+}
+
+impl UsdGraduation {
+    pub fn check(&self) -> Result<()> {
+        require!(
+            self.usd_threshold == 0
+                || (self.max_price_staleness > 0 && u64::from(self.max_price_conf_bps) <= MAX_BPS),
+            TokenMillError::InvalidGraduationThresholds
+        );
+
+        Ok(())
+    }
+}
+
+/// Refundable bond raising the cost of launching markets impersonating other projects. A zero
+/// amount means no bond. Secondary markets reuse an already launched base token and post none.
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct CreationBond {
+    pub amount: u64,
+    /// Revenue epochs a market must trade through before its bond can be released.
+    pub release_epochs: u16,
+}
+
+/// Operating funds creators can take out of the curve proceeds before graduation, at the expense
+/// of the last holders selling back. A zero share means no draws.
+#[derive(Debug, Default, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct CreatorDraw {
+    /// Share of the raised quote drawn over the life of the market, up to `MAX_CREATOR_DRAW_BPS`.
+    pub max_bps: u16,
+    /// Seconds between a draw request and its execution, letting holders react.
+    pub delay: i64,
+}
+
+impl CreatorDraw {
+    pub fn check(&self) -> Result<()> {
+        require!(
+            self.max_bps <= MAX_CREATOR_DRAW_BPS && self.delay >= 0,
+            TokenMillError::InvalidCreatorDraw
+        );
+
+        Ok(())
+    }
+}
+
+impl TraderRebateParams {
+    pub fn get_rebate_fee(&self, protocol_fee: u64) -> Result<u64> {
+        Ok(u64::try_from(
+            u128::from(protocol_fee) * u128::from(self.protocol_fee_share) / u128::from(MAX_BPS),
+        )?)
+    }
 }
 
 impl TokenMillConfig {
@@ -23,7 +236,125 @@ impl TokenMillConfig {
         self.protocol_fee_recipient = protocol_fee_recipient;
         self.default_protocol_fee_share = protocol_fee_share;
         self.referral_fee_share = referral_fee_share;
+        self.secondary_markets_enabled = false;
+        self.policy_program = None;
+        self.max_pause_duration = 0;
+        self.default_curve = None;
+        self.max_swap_size_bps = 0;
+        self.denylist_enabled = false;
+        self.referral_tiers = Vec::new();
+        self.vesting_templates = Vec::new();
+        self.price_bounds = PriceBounds::default();
+        self.trader_rebate_params = TraderRebateParams::default();
+        self.verifier = None;
+        self.migration_target = MigrationTarget::RaydiumCpmm;
+        self.graduation_quote_threshold = 0;
+        self.graduation_supply_bps = 0;
+        self.rent_recipient = authority;
+        self.max_daily_markets_per_creator = 0;
+        self.usd_graduation = UsdGraduation::default();
+        self.referral_settlement = ReferralSettlement::OnChain;
+        self.referral_enabled = true;
+        self.fee_hook_program = None;
+        self.creation_bond = CreationBond::default();
+        self.creator_draw = CreatorDraw::default();
+        self.config_change_delay = 0;
+        self.sweep_tip_bps = 0;
+        self.creator_pause_enabled = false;
+        self.graduation_hook_program = None;
+        self.min_swap_fee_bps = 0;
+        self.max_swap_fee_bps = 0;
+        self.version = CONFIG_VERSION;
 
         Ok(())
     }
+
+    /// Deserializes a config that may predate its latest fields, reading them as zero, so that
+    /// migration instructions can read the fields of the first release before the config is
+    /// backfilled.
+    pub fn try_deserialize_legacy(data: &[u8]) -> Result<Self> {
+        let mut data = data.to_vec();
+        data.resize(data.len().max(8 + Self::INIT_SPACE), 0);
+
+        Self::try_deserialize(&mut &data[..])
+    }
+
+    pub fn is_backfilled(&self) -> bool {
+        self.version >= CONFIG_VERSION
+    }
+
+    /// Initializes the fields added after the config was created where zero differs from what
+    /// `initialize` would have set. Returns false for configs already up to date.
+    pub fn backfill(&mut self) -> bool {
+        if self.is_backfilled() {
+            return false;
+        }
+
+        self.rent_recipient = self.authority;
+        self.referral_enabled = true;
+
+        self.version = CONFIG_VERSION;
+
+        true
+    }
+
+    pub fn check_swap_fee_bps(&self, swap_fee_bps: u16) -> Result<()> {
+        require!(
+            (self.min_swap_fee_bps..=self.max_swap_fee_bps).contains(&swap_fee_bps),
+            TokenMillError::InvalidSwapFee
+        );
+
+        Ok(())
+    }
+
+    pub fn get_sweep_tip(&self, amount: u64) -> Result<u64> {
+        Ok(u64::try_from(
+            u128::from(amount) * u128::from(self.sweep_tip_bps) / u128::from(MAX_BPS),
+        )?)
+    }
+
+    /// Parameters covered by `ConfigChange` can only be updated directly while no delay is set.
+    pub fn check_no_config_change_delay(&self) -> Result<()> {
+        require!(
+            self.config_change_delay == 0,
+            TokenMillError::ConfigChangeTimelocked
+        );
+
+        Ok(())
+    }
+
+    /// Tiers must be sorted by strictly increasing volume, and never lower the share of a previous tier.
+    pub fn check_referral_tiers(referral_tiers: &[ReferralTier]) -> Result<()> {
+        require!(
+            referral_tiers.len() <= MAX_REFERRAL_TIERS,
+            TokenMillError::InvalidReferralTiers
+        );
+
+        for (i, tier) in referral_tiers.iter().enumerate() {
+            require_gte!(
+                MAX_BPS,
+                u64::from(tier.referral_fee_share),
+                TokenMillError::InvalidReferralTiers
+            );
+
+            if let Some(previous_tier) = i.checked_sub(1).map(|j| referral_tiers[j]) {
+                require!(
+                    tier.min_referred_volume > previous_tier.min_referred_volume
+                        && tier.referral_fee_share >= previous_tier.referral_fee_share,
+                    TokenMillError::InvalidReferralTiers
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Share of the highest tier reached by the referred volume, 0 if no tier is reached.
+    pub fn get_referral_tier_fee_share(&self, referred_volume: u64) -> u16 {
+        self.referral_tiers
+            .iter()
+            .rev()
+            .find(|tier| referred_volume >= tier.min_referred_volume)
+            .map_or(0, |tier| tier.referral_fee_share)
+    }
 }
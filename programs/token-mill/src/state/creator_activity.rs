@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MARKET_CREATION_WINDOW_DURATION, errors::TokenMillError, state::TokenMillConfig,
+};
+
+pub const CREATOR_ACTIVITY_PDA_SEED: &str = "creator_activity";
+
+/// Market creations of a wallet on a config, throttled by `max_daily_markets_per_creator`.
+/// Creations are counted over fixed windows, and the limit applies to a sliding window weighting
+/// the previous one by its overlap.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct CreatorActivity {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub creator: Pubkey,
+
+    pub window_start: i64,
+    pub market_count: u32,
+    pub previous_market_count: u32,
+}
+
+impl CreatorActivity {
+    pub fn initialize(&mut self, bump: u8, config: Pubkey, creator: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.config = config;
+        self.creator = creator;
+
+        Ok(())
+    }
+
+    /// Number of markets created during the sliding window ending at `timestamp`.
+    pub fn get_market_count(&self, timestamp: i64) -> u64 {
+        let window_start = timestamp - timestamp % MARKET_CREATION_WINDOW_DURATION;
+
+        let (market_count, previous_market_count) = if window_start == self.window_start {
+            (self.market_count, self.previous_market_count)
+        } else if window_start == self.window_start + MARKET_CREATION_WINDOW_DURATION {
+            (0, self.market_count)
+        } else {
+            (0, 0)
+        };
+
+        let overlap = MARKET_CREATION_WINDOW_DURATION - (timestamp - window_start);
+
+        u64::from(market_count)
+            + u64::from(previous_market_count) * overlap as u64
+                / MARKET_CREATION_WINDOW_DURATION as u64
+    }
+
+    pub fn record_market_creation(
+        &mut self,
+        timestamp: i64,
+        max_daily_markets_per_creator: u16,
+    ) -> Result<()> {
+        require_gt!(
+            u64::from(max_daily_markets_per_creator),
+            self.get_market_count(timestamp),
+            TokenMillError::MarketCreationLimitExceeded
+        );
+
+        let window_start = timestamp - timestamp % MARKET_CREATION_WINDOW_DURATION;
+
+        if window_start != self.window_start {
+            self.previous_market_count =
+                if window_start == self.window_start + MARKET_CREATION_WINDOW_DURATION {
+                    self.market_count
+                } else {
+                    0
+                };
+
+            self.window_start = window_start;
+            self.market_count = 0;
+        }
+
+        self.market_count += 1;
+
+        Ok(())
+    }
+
+    /// Records a market creation when the config throttles creators.
+    /// The creator activity account is only required once the config has a limit.
+    pub fn check(
+        config: &TokenMillConfig,
+        creator_activity: Option<&mut Account<CreatorActivity>>,
+        timestamp: i64,
+    ) -> Result<()> {
+        if config.max_daily_markets_per_creator == 0 {
+            return Ok(());
+        }
+
+        let creator_activity = creator_activity.ok_or(TokenMillError::InvalidCreatorActivity)?;
+
+        creator_activity.record_market_creation(timestamp, config.max_daily_markets_per_creator)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = MARKET_CREATION_WINDOW_DURATION;
+
+    #[test]
+    fn sliding_window() {
+        let mut creator_activity = CreatorActivity {
+            bump: 0,
+            config: Pubkey::default(),
+            creator: Pubkey::default(),
+            window_start: 0,
+            market_count: 0,
+            previous_market_count: 0,
+        };
+
+        creator_activity.record_market_creation(DAY / 2, 2).unwrap();
+        creator_activity.record_market_creation(DAY - 1, 2).unwrap();
+
+        assert!(creator_activity.record_market_creation(DAY - 1, 2).is_err());
+
+        // Half of the previous window still counts
+        assert_eq!(creator_activity.get_market_count(DAY + DAY / 2), 1);
+
+        assert!(creator_activity.record_market_creation(DAY, 2).is_err());
+
+        creator_activity
+            .record_market_creation(DAY + DAY / 2, 2)
+            .unwrap();
+
+        assert_eq!(creator_activity.market_count, 1);
+        assert_eq!(creator_activity.previous_market_count, 2);
+
+        creator_activity.record_market_creation(3 * DAY, 2).unwrap();
+
+        assert_eq!(creator_activity.market_count, 1);
+        assert_eq!(creator_activity.previous_market_count, 0);
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::MAX_MULTISIG_MEMBERS, errors::TokenMillError};
+
+pub const CREATOR_MULTISIG_PDA_SEED: &str = "creator_multisig";
+pub const CREATOR_VAULT_PDA_SEED: &str = "creator_vault";
+
+/// Members of a multisig holding the creator role of markets through its vault.
+/// The vault is a system account the multisig signs for, see `execute_creator_instruction`, so it
+/// can also pay the rent of the accounts created by creator instructions.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct CreatorMultisig {
+    pub bump: u8,
+    pub vault_bump: u8,
+    pub create_key: Pubkey,
+
+    pub threshold: u8,
+    #[max_len(MAX_MULTISIG_MEMBERS)]
+    pub members: Vec<Pubkey>,
+}
+
+impl CreatorMultisig {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        vault_bump: u8,
+        create_key: Pubkey,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        require!(
+            threshold > 0
+                && usize::from(threshold) <= members.len()
+                && members.len() <= MAX_MULTISIG_MEMBERS,
+            TokenMillError::InvalidMultisig
+        );
+
+        for (i, member) in members.iter().enumerate() {
+            require!(
+                !members[..i].contains(member),
+                TokenMillError::InvalidMultisig
+            );
+        }
+
+        self.bump = bump;
+        self.vault_bump = vault_bump;
+        self.create_key = create_key;
+        self.threshold = threshold;
+        self.members = members;
+
+        Ok(())
+    }
+
+    /// Fails unless at least `threshold` distinct members signed among `accounts`.
+    pub fn check_signers(&self, accounts: &[AccountInfo]) -> Result<()> {
+        let signers = self
+            .members
+            .iter()
+            .filter(|member| {
+                accounts
+                    .iter()
+                    .any(|account| account.is_signer && account.key == *member)
+            })
+            .count();
+
+        require_gte!(
+            signers,
+            usize::from(self.threshold),
+            TokenMillError::InsufficientMultisigSigners
+        );
+
+        Ok(())
+    }
+}
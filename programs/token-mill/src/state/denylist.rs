@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::MAX_DENYLIST_LENGTH, errors::TokenMillError, state::TokenMillConfig};
+
+pub const DENYLIST_PDA_SEED: &str = "denylist";
+
+/// Wallets barred from swapping and claiming on the markets of a config.
+#[account]
+#[derive(InitSpace)]
+pub struct Denylist {
+    pub bump: u8,
+    pub config: Pubkey,
+    #[max_len(MAX_DENYLIST_LENGTH)]
+    pub wallets: Vec<Pubkey>,
+}
+
+impl Denylist {
+    pub fn initialize(&mut self, bump: u8, config: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.config = config;
+        self.wallets = Vec::new();
+
+        Ok(())
+    }
+
+    pub fn add(&mut self, wallet: Pubkey) -> Result<()> {
+        require!(
+            !self.wallets.contains(&wallet),
+            TokenMillError::WalletAlreadyDenied
+        );
+        require_gt!(
+            MAX_DENYLIST_LENGTH,
+            self.wallets.len(),
+            TokenMillError::DenylistFull
+        );
+
+        self.wallets.push(wallet);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, wallet: Pubkey) -> Result<()> {
+        let index = self
+            .wallets
+            .iter()
+            .position(|denied| *denied == wallet)
+            .ok_or(TokenMillError::WalletNotDenied)?;
+
+        self.wallets.swap_remove(index);
+
+        Ok(())
+    }
+
+    /// Fails if the config enforces a denylist and `wallet` is on it.
+    /// The denylist account is only required once the config has one.
+    pub fn check(
+        config: &TokenMillConfig,
+        denylist: Option<&Account<Denylist>>,
+        wallet: &Pubkey,
+    ) -> Result<()> {
+        if !config.denylist_enabled {
+            return Ok(());
+        }
+
+        let denylist = denylist.ok_or(TokenMillError::InvalidDenylist)?;
+
+        require!(
+            !denylist.wallets.contains(wallet),
+            TokenMillError::WalletDenied
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+pub const FEE_EXEMPTION_PDA_SEED: &str = "fee_exemption";
+
+/// Granted by the config authority to a designated market maker, waiving the swap fee of its
+/// swaps on the market, see `swap_manager::waive_swap_fee`.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct FeeExemption {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+}
+
+impl FeeExemption {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, wallet: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.wallet = wallet;
+
+        Ok(())
+    }
+}
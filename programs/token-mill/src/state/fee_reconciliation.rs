@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+pub const FEE_RECONCILIATION_PDA_SEED: &str = "fee_reconciliation";
+
+/// Audit trail of `reconcile_fees` for a market. The quote vault is expected to hold at least the
+/// curve proceeds and the pending fees tracked by the market. It holds more than that, as the
+/// staking rewards, referral accruals and quote airdrops waiting to be claimed and the rounding
+/// dust of the fee split are not tracked by the market, so only deficits are discrepancies.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct FeeReconciliation {
+    pub bump: u8,
+    pub market: Pubkey,
+
+    pub reconciliation_count: u64,
+    pub discrepancy_count: u64,
+
+    pub last_reconciliation_slot: u64,
+    pub last_quote_vault_amount: u64,
+    pub last_expected_amount: u64,
+    /// Largest deficit of the vault ever recorded.
+    pub max_deficit: u64,
+    /// Slot of the last recorded discrepancy, 0 if none.
+    pub last_discrepancy_slot: u64,
+}
+
+impl FeeReconciliation {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+
+        Ok(())
+    }
+
+    /// Records a reconciliation and returns the deficit of the vault, 0 when it covers
+    /// `expected_amount`.
+    pub fn record(&mut self, slot: u64, quote_vault_amount: u64, expected_amount: u64) -> u64 {
+        let deficit = expected_amount.saturating_sub(quote_vault_amount);
+
+        self.reconciliation_count += 1;
+        self.last_reconciliation_slot = slot;
+        self.last_quote_vault_amount = quote_vault_amount;
+        self.last_expected_amount = expected_amount;
+
+        if deficit > 0 {
+            self.discrepancy_count += 1;
+            self.last_discrepancy_slot = slot;
+            self.max_deficit = self.max_deficit.max(deficit);
+        }
+
+        deficit
+    }
+}
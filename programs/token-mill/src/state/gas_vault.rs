@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+pub const GAS_VAULT_PDA_SEED: &str = "gas_vault";
+pub const SPONSORED_ACCOUNT_PDA_SEED: &str = "sponsored_account";
+
+/// SOL deposited by the creator of a market to pay the rent of the token accounts its community
+/// members need to claim. The vault holds the lamports itself, on top of its own rent.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct GasVault {
+    pub bump: u8,
+    pub market: Pubkey,
+
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub total_sponsored: u64,
+    pub sponsored_accounts: u32,
+}
+
+impl GasVault {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+
+        Ok(())
+    }
+
+    /// Lamports of the vault that can be spent, keeping the vault itself rent exempt.
+    pub fn get_available_lamports(lamports: u64) -> Result<u64> {
+        let rent = Rent::get()?.minimum_balance(8 + GasVault::INIT_SPACE);
+
+        Ok(lamports.saturating_sub(rent))
+    }
+
+    pub fn record_sponsorship(&mut self, lamports: u64) {
+        self.total_sponsored += lamports;
+        self.sponsored_accounts += 1;
+    }
+}
+
+/// Marks a token account whose rent was paid by a gas vault, so that closing and reopening it
+/// can't drain the vault.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct SponsoredAccount {
+    pub bump: u8,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::constant::BASE_PRECISION;
+
+pub const GRADUATION_ATTESTATION_PDA_SEED: &str = "graduation_attestation";
+
+/// Created by `migrate`, its existence under the program attests that the market graduated.
+/// Cross-chain readers can verify it without trusting an indexer, and it signs the call of the
+/// graduation hook of the config, see `graduation_hook_manager`.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct GraduationAttestation {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub pool: Pubkey,
+
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    /// Initial price of the pool, in quote token units per `BASE_PRECISION` base token units.
+    pub price: u64,
+
+    pub slot: u64,
+    pub timestamp: i64,
+}
+
+impl GraduationAttestation {
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        base_token_mint: Pubkey,
+        quote_token_mint: Pubkey,
+        pool: Pubkey,
+        base_amount: u64,
+        quote_amount: u64,
+        clock: &Clock,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.base_token_mint = base_token_mint;
+        self.quote_token_mint = quote_token_mint;
+        self.pool = pool;
+        self.base_amount = base_amount;
+        self.quote_amount = quote_amount;
+        self.price = u64::try_from(
+            u128::from(quote_amount) * u128::from(BASE_PRECISION) / u128::from(base_amount),
+        )?;
+        self.slot = clock.slot;
+        self.timestamp = clock.unix_timestamp;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const LAUNCH_PURCHASE_PDA_SEED: &str = "launch_purchase";
+
+/// Base amount bought by a wallet on a market during its launch window, capped by the
+/// `max_buy_per_wallet` of the market.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct LaunchPurchase {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub wallet: Pubkey,
+    pub base_amount: u64,
+}
+
+impl LaunchPurchase {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, wallet: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.wallet = wallet;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, base_amount: u64, max_buy_per_wallet: u64) -> Result<()> {
+        self.base_amount += base_amount;
+
+        require_gte!(
+            max_buy_per_wallet,
+            self.base_amount,
+            TokenMillError::WalletBuyLimitExceeded
+        );
+
+        Ok(())
+    }
+}
@@ -5,24 +5,130 @@ use anchor_lang::prelude::*;
 use crate::{
     constant::*,
     errors::TokenMillError,
-    manager::swap_manager::SwapAmountType,
+    manager::swap_manager::{SwapAmountType, SwapType},
     math::{div, get_delta_base_in, get_delta_base_out, mul_div, Rounding},
+    state::{CreationBond, CreatorDraw, PriceBounds},
 };
 
 pub const MARKET_PDA_SEED: &str = "market";
 
+/// Bumped whenever fields are added to the layout, see `Market::backfill`.
+pub const MARKET_VERSION: u8 = 2;
+
+/// Size of the markets created by the first release, whose fields keep their offsets in the
+/// current layout. Later fields reuse its trailing padding or are appended after it.
+pub const BASELINE_MARKET_SIZE: usize = 360;
+
+/// Who paused a market. The config authority can lift any pause, the creator only its own.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum MarketPauser {
+    Authority = 1,
+    Creator = 2,
+    /// Held by the buy queue of the market until its orders are filled, see `open_buy_queue`.
+    BuyQueue = 3,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum MarketKind {
+    /// Launch market, seeded by the base mint only. Mints the base token supply.
+    Primary,
+    /// Additional quote market for an existing base mint, seeded by both mints.
+    /// Its base reserve is deposited by the creator.
+    Secondary,
+}
+
+/// Owned signer seeds of a market PDA.
+pub struct MarketSeeds {
+    base_token_mint: Pubkey,
+    quote_token_mint: Option<Pubkey>,
+    bump: [u8; 1],
+}
+
+impl MarketSeeds {
+    pub fn signer_seeds(&self) -> Vec<&[u8]> {
+        let mut seeds = vec![MARKET_PDA_SEED.as_bytes(), self.base_token_mint.as_ref()];
+
+        if let Some(quote_token_mint) = &self.quote_token_mint {
+            seeds.push(quote_token_mint.as_ref());
+        }
+
+        seeds.push(&self.bump);
+
+        seeds
+    }
+}
+
+/// Market parameter a creator can change at a future slot, see `schedule_update`.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum ScheduledUpdateKind {
+    /// Slot at which the launch fee starts decaying, dropped once the market traded.
+    LaunchSlot,
+    /// Start of the emission schedule, dropped once the emission started.
+    EmissionStart,
+    MinClaimAmount,
+}
+
+/// Change of a market parameter to `value`, applied by the first instruction reading it at or
+/// after `slot`. Empty entries have a 0 slot.
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct ScheduledUpdate {
+    pub slot: u64,
+    pub value: u64,
+    pub kind: u8,
+
+    _space: [u8; 7],
+}
+
+impl ScheduledUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.slot == 0
+    }
+
+    pub fn kind(&self) -> ScheduledUpdateKind {
+        match self.kind {
+            0 => ScheduledUpdateKind::LaunchSlot,
+            1 => ScheduledUpdateKind::EmissionStart,
+            _ => ScheduledUpdateKind::MinClaimAmount,
+        }
+    }
+}
+
+/// Fair launch limit set at creation, capping the base amount each wallet can buy during the
+/// first `launch_window_seconds` of the market.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub struct WalletBuyLimit {
+    pub max_buy_per_wallet: u64,
+    pub launch_window_seconds: i64,
+}
+
 #[zero_copy]
 #[derive(Debug, InitSpace)]
 pub struct MarketFees {
     /// staking_fee_share + creator_fee_share + protocol_fee_share = 100%
     pub staking_fee_share: u16,
     pub creator_fee_share: u16,
-    _space: u32,
+    /// Referral share of the protocol fee, copied from the config at creation.
+    /// Config changes only apply to markets opting in, see `adopt_new_fee_defaults`.
+    pub referral_fee_share: u16,
+    /// Sell tax set by the creator, in bps of the quote proceeds of sells.
+    pub holder_rewards_fee_bps: u16,
 
     pub pending_staking_fees: u64,
     pub pending_creator_fees: u64,
 }
 
+/// Swap counters of the current and the previous volume window.
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct MarketVolume {
+    pub window_start: i64,
+    pub quote_volume: u64,
+    pub swap_fees: u64,
+    pub previous_quote_volume: u64,
+    pub previous_swap_fees: u64,
+}
+
 #[account(zero_copy)]
 #[derive(Debug, InitSpace)]
 pub struct Market {
@@ -44,8 +150,92 @@ pub struct Market {
 
     pub quote_token_decimals: u8,
     pub bump: u8,
-
-    _space: [u8; 6],
+    pub kind: u8,
+    /// Set while a guarded instruction runs on the market, see `lock`.
+    pub locked: u8,
+    /// Swap size limit set by the creator, only applied when below the limit of the config.
+    pub max_swap_size_bps: u16,
+    /// Number of prices written by `append_prices` while the curve is incomplete.
+    pub appended_prices: u8,
+    /// Set once the reserves have been moved to an AMM pool by `migrate`, halting the curve.
+    pub migrated: u8,
+    /// Attested by the verifier of the config, see `set_market_verification`.
+    pub verified: u8,
+    /// Number of points of a curve whose tail is compacted by `compact_price_tail`, 0 for the
+    /// full curve. The last used interval then covers the rest of the supply.
+    pub price_points: u8,
+    /// Extra fee of buys at the launch slot, in bps of the quote amount, see `get_launch_fee_bps`.
+    pub launch_fee_bps: u16,
+    /// Set by the first swap, freezing the curve, see `reset_prices`.
+    pub traded: u8,
+    /// Set by the creator to stop new staking deposits once migrated, see `is_staking_frozen`.
+    pub freeze_staking_on_migration: u8,
+    /// Cleared by the config authority to stop paying referral fees, see `update_referral_params`.
+    pub referral_enabled: u8,
+    /// Layout version the market was created or backfilled with, see `backfill`.
+    pub version: u8,
+    /// Fee chosen by the creator within the bounds of the config, in bps of the quote amount,
+    /// charged on top of buys and out of sells, see `get_swap_fee`.
+    pub swap_fee_bps: u16,
+    /// Set by `pause_market` until `unpause_market`, or by `create_buy_queue` until
+    /// `open_buy_queue`, halting swaps, see `MarketPauser`.
+    pub paused: u8,
+    /// Set by the creator to scale the swap fee with the volatility of the market, see
+    /// `swap_manager::update_variable_fee`.
+    pub dynamic_fee: u8,
+    /// Fee added to `swap_fee_bps` by the dynamic fee mode, as of the latest swap with the oracle.
+    pub variable_fee_bps: u16,
+    /// Cap set by the creator on the base tokens a wallet can hold after a buy, in bps of the total
+    /// supply, 0 meaning no cap, see `get_max_wallet_holding`.
+    pub max_wallet_holding_bps: u16,
+
+    /// Sell taxes waiting to be distributed to holders, see `publish_holder_rewards`.
+    pub pending_holder_rewards: u64,
+
+    /// Swaps are halted until this slot.
+    pub paused_until_slot: u64,
+
+    pub volume: MarketVolume,
+
+    /// Quote volume a referrer must bring to this market before claiming its referral fees.
+    pub min_referred_volume: u64,
+
+    /// Smallest amount of creator fees or staking rewards that can be claimed at once.
+    pub min_claim_amount: u64,
+
+    /// Slot at which the curve went live, starting the launch fee ramp.
+    pub launch_slot: u64,
+    /// Number of slots over which the launch fee decays to 0.
+    pub launch_fee_slots: u64,
+
+    /// Base amount a wallet can buy until `launch_window_end`, see `LaunchPurchase`.
+    pub max_buy_per_wallet: u64,
+    pub launch_window_end: i64,
+
+    /// Supply tradable at `emission_start`, the rest of the supply unlocking linearly over
+    /// `emission_duration`, see `get_unlocked_supply`. 0 durations for fully unlocked markets.
+    pub emission_initial_supply: u64,
+    pub emission_start: i64,
+    pub emission_duration: i64,
+
+    /// Parameter changes queued by the creator, see `apply_scheduled_updates`.
+    pub scheduled_updates: [ScheduledUpdate; MAX_SCHEDULED_UPDATES],
+
+    /// Lamports posted by the creator at creation and held by the market account on top of its
+    /// rent, refunded once the market graduated or traded until `creation_bond_release_time`.
+    pub creation_bond: u64,
+    pub creation_bond_release_time: i64,
+
+    /// Curve proceeds drawn by the creator before graduation, no longer held by the quote vault.
+    pub creator_drawn_quote: u64,
+    /// Draw requested by the creator, executable from `pending_draw_unlock_time`.
+    pub pending_draw_amount: u64,
+    pub pending_draw_unlock_time: i64,
+
+    /// Quote tokens of the vault owed in old units to airdrop claimants and to referrers held back
+    /// by `min_referred_volume`, which must be paid out before `migrate_quote_token`.
+    pub airdropped_quote: u64,
+    pub held_referral_fees: u64,
 }
 
 impl MarketFees {
@@ -53,6 +243,21 @@ impl MarketFees {
         &mut self,
         swap_fee: u64,
         referral_fee_share: Option<u16>,
+    ) -> Result<(u64, u64, u64, u64)> {
+        let (creator_fee, staking_fee, protocol_fee, referral_fee) =
+            self.get_fee_distribution(swap_fee, referral_fee_share)?;
+
+        self.pending_creator_fees += creator_fee;
+        self.pending_staking_fees += staking_fee;
+
+        Ok((creator_fee, staking_fee, protocol_fee, referral_fee))
+    }
+
+    /// Splits a swap fee into (creator, staking, protocol, referral) parts without accruing it.
+    pub fn get_fee_distribution(
+        &self,
+        swap_fee: u64,
+        referral_fee_share: Option<u16>,
     ) -> Result<(u64, u64, u64, u64)> {
         let creator_fee = u64::try_from(
             u128::from(swap_fee) * u128::from(self.creator_fee_share) / MAX_BPS as u128,
@@ -72,11 +277,61 @@ impl MarketFees {
 
         let protocol_fee = remaining_fee - referral_fee;
 
-        self.pending_creator_fees += creator_fee;
-        self.pending_staking_fees += staking_fee;
-
         Ok((creator_fee, staking_fee, protocol_fee, referral_fee))
     }
+
+    /// Holder rewards fee taken out of the quote proceeds of a sell.
+    pub fn get_holder_rewards_fee(&self, quote_amount: u64) -> Result<u64> {
+        Ok(u64::try_from(
+            u128::from(quote_amount) * u128::from(self.holder_rewards_fee_bps) / MAX_BPS as u128,
+        )?)
+    }
+
+    /// Quote proceeds a sell needs for `quote_amount` to be left once the holder rewards fee
+    /// and the swap fee of the market are taken out.
+    pub fn get_gross_sell_amount(&self, quote_amount: u64, swap_fee_bps: u16) -> Result<u64> {
+        div(
+            u128::from(quote_amount) * MAX_BPS as u128,
+            MAX_BPS as u128 - u128::from(self.holder_rewards_fee_bps) - u128::from(swap_fee_bps),
+            Rounding::Up,
+        )
+    }
+}
+
+impl MarketVolume {
+    pub fn record(&mut self, timestamp: i64, quote_amount: u64, swap_fee: u64) {
+        self.roll(timestamp);
+
+        self.quote_volume += quote_amount;
+        self.swap_fees += swap_fee;
+    }
+
+    /// Starts the window of `timestamp` if it isn't the current one, keeping the last complete
+    /// window as the previous one.
+    pub fn roll(&mut self, timestamp: i64) {
+        let window_start = timestamp - timestamp % VOLUME_WINDOW_DURATION;
+
+        if window_start != self.window_start {
+            (self.previous_quote_volume, self.previous_swap_fees) = self.get_last_window(timestamp);
+
+            self.window_start = window_start;
+            self.quote_volume = 0;
+            self.swap_fees = 0;
+        }
+    }
+
+    /// Returns the (quote volume, swap fees) of the last complete window before `timestamp`.
+    pub fn get_last_window(&self, timestamp: i64) -> (u64, u64) {
+        let window_start = timestamp - timestamp % VOLUME_WINDOW_DURATION;
+
+        if window_start == self.window_start {
+            (self.previous_quote_volume, self.previous_swap_fees)
+        } else if window_start == self.window_start + VOLUME_WINDOW_DURATION {
+            (self.quote_volume, self.swap_fees)
+        } else {
+            (0, 0)
+        }
+    }
 }
 
 impl Market {
@@ -92,6 +347,7 @@ impl Market {
         total_supply: u64,
         creator_fee_share: u16,
         staking_fee_share: u16,
+        referral_fee_share: u16,
     ) -> Result<()> {
         if total_supply > MAX_TOTAL_SUPPLY
             || total_supply / INTERVAL_NUMBER < BASE_PRECISION
@@ -100,33 +356,875 @@ impl Market {
             return Err(TokenMillError::InvalidTotalSupply.into());
         }
 
-        self.bump = bump;
-        self.config = config;
-        self.creator = creator;
-        self.base_token_mint = base_token_mint;
-        self.quote_token_mint = quote_token_mint;
-        self.quote_token_decimals = quote_token_decimals;
-        self.total_supply = total_supply;
-        self.base_reserve = total_supply;
-        self.width_scaled = u64::try_from(
-            u128::from(total_supply / INTERVAL_NUMBER) * SCALE / u128::from(BASE_PRECISION),
+        self.bump = bump;
+        self.config = config;
+        self.creator = creator;
+        self.base_token_mint = base_token_mint;
+        self.quote_token_mint = quote_token_mint;
+        self.quote_token_decimals = quote_token_decimals;
+        self.total_supply = total_supply;
+        self.base_reserve = total_supply;
+        self.width_scaled = u64::try_from(
+            u128::from(total_supply / INTERVAL_NUMBER) * SCALE / u128::from(BASE_PRECISION),
+        )?;
+
+        self.fees.creator_fee_share = creator_fee_share;
+        self.fees.staking_fee_share = staking_fee_share;
+        self.fees.referral_fee_share = referral_fee_share;
+        self.referral_enabled = 1;
+        self.version = MARKET_VERSION;
+        Ok(())
+    }
+
+    pub fn is_backfilled(&self) -> bool {
+        self.version >= MARKET_VERSION
+    }
+
+    /// Initializes the fields added after the market was created from its existing state, as
+    /// `initialize` would have, so readers don't need a separate path for legacy markets.
+    /// Markets of the first release read the referral share of the config at swap time, so they
+    /// take `referral_fee_share` from it. Returns false for markets already up to date.
+    pub fn backfill(&mut self, referral_fee_share: u16) -> bool {
+        if self.is_backfilled() {
+            return false;
+        }
+
+        if self.version < 1 {
+            self.fees.referral_fee_share = referral_fee_share;
+            self.referral_enabled = 1;
+            self.traded = u8::from(self.base_reserve < self.total_supply);
+        }
+
+        // Version 2 added `paused`, zeroed by the realloc of `backfill_market`
+
+        self.version = MARKET_VERSION;
+
+        true
+    }
+
+    pub fn is_secondary(&self) -> bool {
+        self.kind == MarketKind::Secondary as u8
+    }
+
+    pub fn is_verified(&self) -> bool {
+        self.verified != 0
+    }
+
+    pub fn is_migrated(&self) -> bool {
+        self.migrated != 0
+    }
+
+    pub fn is_dynamic_fee_enabled(&self) -> bool {
+        self.dynamic_fee != 0
+    }
+
+    /// Swap fee of the market in bps, including the variable fee of the dynamic fee mode.
+    pub fn get_swap_fee_bps(&self) -> u16 {
+        self.swap_fee_bps + self.variable_fee_bps
+    }
+
+    /// Swap fee of the market on the quote amount of a swap, distributed as the spread is.
+    pub fn get_swap_fee(&self, quote_amount: u64) -> Result<u64> {
+        Ok(u64::try_from(
+            u128::from(quote_amount) * u128::from(self.get_swap_fee_bps()) / MAX_BPS as u128,
+        )?)
+    }
+
+    pub fn is_referral_enabled(&self) -> bool {
+        self.referral_enabled != 0
+    }
+
+    /// The referral share is taken from the fee left by the creator and staking shares, so the
+    /// creator, staking and referral parts stay within `MAX_BPS` of the fee as long as the share
+    /// does.
+    pub fn set_referral_params(
+        &mut self,
+        referral_fee_share: u16,
+        referral_enabled: bool,
+    ) -> Result<()> {
+        let remaining_share = MAX_BPS
+            - u64::from(self.fees.creator_fee_share)
+            - u64::from(self.fees.staking_fee_share);
+        let referral_part = remaining_share * u64::from(referral_fee_share) / MAX_BPS;
+
+        require!(
+            referral_fee_share <= MAX_BPS as u16
+                && u64::from(self.fees.creator_fee_share)
+                    + u64::from(self.fees.staking_fee_share)
+                    + referral_part
+                    <= MAX_BPS,
+            TokenMillError::InvalidFeeShare
+        );
+
+        self.fees.referral_fee_share = referral_fee_share;
+        self.referral_enabled = u8::from(referral_enabled);
+
+        Ok(())
+    }
+
+    /// Migrated markets no longer earn curve fees, positions opted into the freeze keep their
+    /// residual rewards and can exit but no new deposits are accepted.
+    pub fn is_staking_frozen(&self) -> bool {
+        self.freeze_staking_on_migration != 0 && self.is_migrated()
+    }
+
+    pub fn has_traded(&self) -> bool {
+        self.traded != 0
+    }
+
+    /// Records the bond required by the config, returning the lamports the creator must post.
+    pub fn post_creation_bond(&mut self, creation_bond: &CreationBond, timestamp: i64) -> u64 {
+        self.creation_bond = creation_bond.amount;
+        self.creation_bond_release_time =
+            timestamp + i64::from(creation_bond.release_epochs) * REVENUE_EPOCH_DURATION;
+
+        creation_bond.amount
+    }
+
+    /// Clears the bond of a migrated market, or of a market that traded until its release time,
+    /// returning the lamports to refund to the creator.
+    pub fn release_creation_bond(&mut self, timestamp: i64) -> Result<u64> {
+        require_gt!(self.creation_bond, 0, TokenMillError::InvalidAmount);
+
+        require!(
+            self.is_migrated()
+                || (self.has_traded() && timestamp >= self.creation_bond_release_time),
+            TokenMillError::CreationBondLocked
+        );
+
+        Ok(std::mem::take(&mut self.creation_bond))
+    }
+
+    /// Clears the bond of a market found impersonating another project, returning the lamports
+    /// to send to the protocol fee recipient.
+    pub fn slash_creation_bond(&mut self) -> Result<u64> {
+        require_gt!(self.creation_bond, 0, TokenMillError::InvalidAmount);
+
+        Ok(std::mem::take(&mut self.creation_bond))
+    }
+
+    /// Quote amount the creator can still draw, `max_bps` of the raised quote minus past draws.
+    pub fn get_max_draw_amount(&self, max_bps: u16) -> Result<u64> {
+        let max_drawn_quote = u64::try_from(
+            u128::from(self.get_raised_quote()?) * u128::from(max_bps) / u128::from(MAX_BPS),
+        )?;
+
+        Ok(max_drawn_quote.saturating_sub(self.creator_drawn_quote))
+    }
+
+    /// Queues a draw of `amount` quote tokens, replacing any pending one, see `execute_draw`.
+    pub fn request_draw(
+        &mut self,
+        creator_draw: &CreatorDraw,
+        amount: u64,
+        timestamp: i64,
+    ) -> Result<i64> {
+        require!(!self.is_migrated(), TokenMillError::MarketMigrated);
+        require!(creator_draw.max_bps > 0, TokenMillError::InvalidCreatorDraw);
+        require_gt!(amount, 0, TokenMillError::InvalidAmount);
+
+        require_gte!(
+            self.get_max_draw_amount(creator_draw.max_bps)?,
+            amount,
+            TokenMillError::CreatorDrawExceeded
+        );
+
+        self.pending_draw_amount = amount;
+        self.pending_draw_unlock_time = timestamp + creator_draw.delay;
+
+        Ok(self.pending_draw_unlock_time)
+    }
+
+    /// Clears the pending draw once unlocked, returning the quote amount to send to the creator.
+    /// The cap is checked again as sells since the request may have lowered the raised quote.
+    pub fn execute_draw(&mut self, creator_draw: &CreatorDraw, timestamp: i64) -> Result<u64> {
+        require!(!self.is_migrated(), TokenMillError::MarketMigrated);
+        require_gt!(self.pending_draw_amount, 0, TokenMillError::InvalidAmount);
+        require_gte!(
+            timestamp,
+            self.pending_draw_unlock_time,
+            TokenMillError::CreatorDrawLocked
+        );
+
+        let amount = std::mem::take(&mut self.pending_draw_amount);
+
+        require_gte!(
+            self.get_max_draw_amount(creator_draw.max_bps)?,
+            amount,
+            TokenMillError::CreatorDrawExceeded
+        );
+
+        self.creator_drawn_quote += amount;
+
+        Ok(amount)
+    }
+
+    /// Halts the curve, returning the (base, quote) amounts seeding the pool: the unsold base
+    /// reserve and the curve proceeds. Fees stay in the quote vault of the market.
+    /// Graduation requires curve proceeds of at least `graduation_quote_threshold` and a
+    /// circulating supply of at least `graduation_supply_bps` of the total supply.
+    pub fn migrate(
+        &mut self,
+        graduation_quote_threshold: u64,
+        graduation_supply_bps: u16,
+    ) -> Result<(u64, u64)> {
+        require!(!self.is_migrated(), TokenMillError::MarketMigrated);
+
+        let base_amount = self.base_reserve;
+        let quote_amount = self.get_curve_proceeds()?;
+
+        require!(
+            base_amount > 0 && quote_amount > 0,
+            TokenMillError::NothingToMigrate
+        );
+
+        require!(
+            self.meets_graduation_thresholds(graduation_quote_threshold, graduation_supply_bps)?,
+            TokenMillError::GraduationThresholdNotMet
+        );
+
+        self.base_reserve = 0;
+        self.migrated = 1;
+
+        Ok((base_amount, quote_amount))
+    }
+
+    pub fn meets_graduation_thresholds(
+        &self,
+        graduation_quote_threshold: u64,
+        graduation_supply_bps: u16,
+    ) -> Result<bool> {
+        Ok(self.get_curve_proceeds()? >= graduation_quote_threshold
+            && u128::from(self.circulating_supply()) * u128::from(MAX_BPS)
+                >= u128::from(self.total_supply) * u128::from(graduation_supply_bps))
+    }
+
+    /// Share of the total supply in circulation, in bps.
+    pub fn get_supply_sold_bps(&self) -> u16 {
+        if self.total_supply == 0 {
+            return 0;
+        }
+
+        // Fits in a u16 as the circulating supply is at most the total supply
+        (u128::from(self.circulating_supply()) * u128::from(MAX_BPS)
+            / u128::from(self.total_supply)) as u16
+    }
+
+    /// Last multiple of `GRADUATION_MILESTONE_BPS` reached by the supply sold, swaps crossing one
+    /// emit a progress event.
+    pub fn get_graduation_milestone_bps(&self) -> u16 {
+        self.get_supply_sold_bps() / GRADUATION_MILESTONE_BPS * GRADUATION_MILESTONE_BPS
+    }
+
+    /// Moves the curve to a new quote token worth `conversion_rate` old quote tokens, scaled by
+    /// `SCALE`, returning the new quote amount owed for `quote_vault_amount`, rounded up.
+    /// Prices are rescaled rounding bids down and asks up, and quote amounts rounding down, so the
+    /// converted vault keeps covering the curve proceeds and the pending fees.
+    /// Only primary markets can migrate, as the seeds of secondary markets include their quote
+    /// mint.
+    pub fn migrate_quote_token(
+        &mut self,
+        quote_token_mint: Pubkey,
+        quote_token_decimals: u8,
+        conversion_rate: u64,
+        quote_vault_amount: u64,
+    ) -> Result<u64> {
+        require!(!self.is_migrated(), TokenMillError::MarketMigrated);
+        require!(
+            !self.is_secondary()
+                && quote_token_mint != self.quote_token_mint
+                && self.are_prices_set(),
+            TokenMillError::InvalidQuoteTokenMigration
+        );
+        require!(conversion_rate > 0, TokenMillError::InvalidConversionRate);
+        require!(
+            self.airdropped_quote == 0 && self.held_referral_fees == 0,
+            TokenMillError::InvalidQuoteTokenMigration
+        );
+
+        let old_quote_precision = u128::pow(10, u32::from(self.quote_token_decimals));
+        let new_quote_precision = u128::pow(10, u32::from(quote_token_decimals));
+
+        let convert = |amount: u64, precision_ratio: (u128, u128), rounding: Rounding| {
+            let amount = mul_div(
+                u128::from(amount),
+                u128::from(conversion_rate) * precision_ratio.0,
+                SCALE * precision_ratio.1,
+                rounding,
+            )
+            .ok_or(TokenMillError::MathError)?;
+
+            u64::try_from(amount).map_err(|_| TokenMillError::MathError)
+        };
+
+        // Prices are in quote tokens, amounts in quote units
+        let prices = (1, 1);
+        let amounts = (new_quote_precision, old_quote_precision);
+
+        for i in 0..PRICES_LENGTH {
+            self.bid_prices[i] = convert(self.bid_prices[i], prices, Rounding::Down)?;
+            self.ask_prices[i] = convert(self.ask_prices[i], prices, Rounding::Up)?;
+        }
+
+        require!(
+            self.bid_prices[self.last_price_index()] > 0,
+            TokenMillError::InvalidConversionRate
+        );
+
+        let fees = &mut self.fees;
+
+        fees.pending_staking_fees = convert(fees.pending_staking_fees, amounts, Rounding::Down)?;
+        fees.pending_creator_fees = convert(fees.pending_creator_fees, amounts, Rounding::Down)?;
+
+        self.pending_holder_rewards =
+            convert(self.pending_holder_rewards, amounts, Rounding::Down)?;
+
+        // Rounded up so that draws never exceed the share of the raised quote
+        self.creator_drawn_quote = convert(self.creator_drawn_quote, amounts, Rounding::Up)?;
+        self.pending_draw_amount = convert(self.pending_draw_amount, amounts, Rounding::Down)?;
+
+        self.min_referred_volume = convert(self.min_referred_volume, amounts, Rounding::Down)?;
+        self.min_claim_amount = convert(self.min_claim_amount, amounts, Rounding::Down)?;
+
+        let volume = &mut self.volume;
+
+        volume.quote_volume = convert(volume.quote_volume, amounts, Rounding::Down)?;
+        volume.swap_fees = convert(volume.swap_fees, amounts, Rounding::Down)?;
+        volume.previous_quote_volume =
+            convert(volume.previous_quote_volume, amounts, Rounding::Down)?;
+        volume.previous_swap_fees = convert(volume.previous_swap_fees, amounts, Rounding::Down)?;
+
+        self.quote_token_mint = quote_token_mint;
+        self.quote_token_decimals = quote_token_decimals;
+
+        Ok(convert(quote_vault_amount, amounts, Rounding::Up)?)
+    }
+
+    /// Flags a guarded instruction as running, failing if one already is, i.e. on reentrant CPIs.
+    /// Guarded instructions clear the flag before returning, so it never outlives a transaction.
+    pub fn lock(&mut self) -> Result<()> {
+        require!(self.locked == 0, TokenMillError::ReentrancyDetected);
+
+        self.locked = 1;
+
+        Ok(())
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = 0;
+    }
+
+    pub fn is_paused(&self, current_slot: u64) -> bool {
+        self.paused != 0 || current_slot < self.paused_until_slot
+    }
+
+    /// Whether the market is paused by anything but its buy queue, whose orders fill through it.
+    pub fn is_paused_except_buy_queue(&self, current_slot: u64) -> bool {
+        (self.paused != 0 && self.paused != MarketPauser::BuyQueue as u8)
+            || current_slot < self.paused_until_slot
+    }
+
+    /// The authority can take over a pause of the creator, so that the creator can't lift it.
+    pub fn pause(&mut self, pauser: MarketPauser) -> Result<()> {
+        require!(
+            self.paused == 0 || (pauser == MarketPauser::Authority && self.paused != pauser as u8),
+            TokenMillError::InvalidPauseState
+        );
+
+        self.paused = pauser as u8;
+
+        Ok(())
+    }
+
+    pub fn unpause(&mut self, pauser: MarketPauser) -> Result<()> {
+        require!(self.paused != 0, TokenMillError::InvalidPauseState);
+        require!(
+            pauser == MarketPauser::Authority || self.paused == pauser as u8,
+            TokenMillError::InvalidAuthority
+        );
+
+        self.paused = 0;
+
+        Ok(())
+    }
+
+    pub fn set_wallet_buy_limit(
+        &mut self,
+        wallet_buy_limit: &WalletBuyLimit,
+        timestamp: i64,
+    ) -> Result<()> {
+        require!(
+            wallet_buy_limit.max_buy_per_wallet > 0
+                && wallet_buy_limit.launch_window_seconds > 0
+                && wallet_buy_limit.launch_window_seconds <= MAX_LAUNCH_WINDOW_DURATION,
+            TokenMillError::InvalidWalletBuyLimit
+        );
+
+        self.max_buy_per_wallet = wallet_buy_limit.max_buy_per_wallet;
+        self.launch_window_end = timestamp + wallet_buy_limit.launch_window_seconds;
+
+        Ok(())
+    }
+
+    /// Base amount a wallet can buy in total while the launch window is open.
+    pub fn get_max_buy_per_wallet(&self, timestamp: i64) -> Option<u64> {
+        (timestamp < self.launch_window_end).then_some(self.max_buy_per_wallet)
+    }
+
+    pub fn set_emission_schedule(
+        &mut self,
+        initial_supply: u64,
+        start: i64,
+        duration: i64,
+    ) -> Result<()> {
+        require!(
+            initial_supply <= self.total_supply && start >= 0 && duration >= 0,
+            TokenMillError::InvalidEmissionSchedule
+        );
+
+        self.emission_initial_supply = initial_supply;
+        self.emission_start = start;
+        self.emission_duration = duration;
+
+        Ok(())
+    }
+
+    /// Queues the change of a parameter at `slot`, returning the index of its entry.
+    /// Launch changes can only be queued before the launch they move: the launch slot until the
+    /// first trade, and not before `slot` so the ramp can't restart retroactively, and the
+    /// emission start until the emission started.
+    pub fn schedule_update(
+        &mut self,
+        kind: ScheduledUpdateKind,
+        value: u64,
+        slot: u64,
+        current_slot: u64,
+        timestamp: i64,
+    ) -> Result<usize> {
+        require_gt!(slot, current_slot, TokenMillError::InvalidScheduledUpdate);
+
+        let is_valid = match kind {
+            ScheduledUpdateKind::LaunchSlot => {
+                self.are_prices_set() && !self.has_traded() && value >= slot
+            }
+            ScheduledUpdateKind::EmissionStart => {
+                self.emission_duration > 0
+                    && timestamp < self.emission_start
+                    && i64::try_from(value).is_ok()
+            }
+            ScheduledUpdateKind::MinClaimAmount => true,
+        };
+
+        require!(is_valid, TokenMillError::InvalidScheduledUpdate);
+
+        let index = self
+            .scheduled_updates
+            .iter()
+            .position(ScheduledUpdate::is_empty)
+            .ok_or(TokenMillError::ScheduledUpdatesFull)?;
+
+        self.scheduled_updates[index] = ScheduledUpdate {
+            slot,
+            value,
+            kind: kind as u8,
+            _space: [0; 7],
+        };
+
+        Ok(index)
+    }
+
+    pub fn cancel_scheduled_update(&mut self, index: usize) -> Result<ScheduledUpdate> {
+        let update = self
+            .scheduled_updates
+            .get_mut(index)
+            .filter(|update| !update.is_empty())
+            .ok_or(TokenMillError::InvalidScheduledUpdate)?;
+
+        let cancelled_update = *update;
+        update.slot = 0;
+
+        Ok(cancelled_update)
+    }
+
+    /// Applies the updates due at `current_slot` in slot order, so the creator doesn't have to
+    /// send them at the exact slot. Called by the instructions reading the scheduled parameters
+    /// before using them. Launch changes whose launch happened in the meantime are dropped.
+    pub fn apply_scheduled_updates(&mut self, current_slot: u64, timestamp: i64) {
+        while let Some(index) = (0..MAX_SCHEDULED_UPDATES)
+            .filter(|&i| {
+                let update = &self.scheduled_updates[i];
+                !update.is_empty() && update.slot <= current_slot
+            })
+            .min_by_key(|&i| self.scheduled_updates[i].slot)
+        {
+            let update = self.scheduled_updates[index];
+            self.scheduled_updates[index].slot = 0;
+
+            match update.kind() {
+                ScheduledUpdateKind::LaunchSlot => {
+                    if !self.has_traded() {
+                        self.launch_slot = update.value;
+                    }
+                }
+                ScheduledUpdateKind::EmissionStart => {
+                    if timestamp < self.emission_start {
+                        // Checked when scheduled
+                        self.emission_start = update.value as i64;
+                    }
+                }
+                ScheduledUpdateKind::MinClaimAmount => self.min_claim_amount = update.value,
+            }
+        }
+    }
+
+    /// Supply that can be in circulation at `timestamp`, i.e. bought from the curve.
+    pub fn get_unlocked_supply(&self, timestamp: i64) -> u64 {
+        if self.emission_duration == 0 {
+            return self.total_supply;
+        }
+
+        let elapsed = (timestamp - self.emission_start).clamp(0, self.emission_duration);
+        let locked_supply = self.total_supply - self.emission_initial_supply;
+
+        // Fits in a u64 as `elapsed <= emission_duration`
+        self.emission_initial_supply
+            + (u128::from(locked_supply) * elapsed as u128 / self.emission_duration as u128) as u64
+    }
+
+    /// Base amount buys can take from the curve at `timestamp`.
+    pub fn get_available_supply(&self, timestamp: i64) -> u64 {
+        self.get_unlocked_supply(timestamp)
+            .saturating_sub(self.circulating_supply())
+    }
+
+    /// Extra fee of buys protecting the launch against snipers, decaying linearly from
+    /// `launch_fee_bps` at the launch slot to 0 once `launch_fee_slots` have passed.
+    pub fn get_launch_fee_bps(&self, current_slot: u64) -> u16 {
+        let elapsed_slots = current_slot.saturating_sub(self.launch_slot);
+
+        if !self.are_prices_set() || elapsed_slots >= self.launch_fee_slots {
+            return 0;
+        }
+
+        let remaining_slots = self.launch_fee_slots - elapsed_slots;
+
+        // Fits in a u16 as `remaining_slots <= launch_fee_slots`
+        (u128::from(self.launch_fee_bps) * u128::from(remaining_slots)
+            / u128::from(self.launch_fee_slots)) as u16
+    }
+
+    /// Base tokens the base token account of a buyer can hold once the buy is settled.
+    pub fn get_max_wallet_holding(&self) -> Result<Option<u64>> {
+        if self.max_wallet_holding_bps == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(u64::try_from(
+            u128::from(self.total_supply) * u128::from(self.max_wallet_holding_bps)
+                / MAX_BPS as u128,
+        )?))
+    }
+
+    /// Maximum base amount a single swap can move, as a share of the base reserve for buys and of
+    /// the circulating supply for sells. The lowest non-zero limit of the config and the market applies.
+    pub fn get_max_swap_base_amount(
+        &self,
+        swap_type: SwapType,
+        config_max_swap_size_bps: u16,
+    ) -> Result<Option<u64>> {
+        let max_swap_size_bps = self.get_max_swap_size_bps(config_max_swap_size_bps);
+
+        if max_swap_size_bps == 0 {
+            return Ok(None);
+        }
+
+        let reserve = match swap_type {
+            SwapType::Buy => self.base_reserve,
+            SwapType::Sell => self.circulating_supply(),
+        };
+
+        Ok(Some(u64::try_from(
+            u128::from(reserve) * u128::from(max_swap_size_bps) / MAX_BPS as u128,
+        )?))
+    }
+
+    /// Lowest non-zero limit of the config and the market, 0 meaning no limit.
+    pub fn get_max_swap_size_bps(&self, config_max_swap_size_bps: u16) -> u16 {
+        match (config_max_swap_size_bps, self.max_swap_size_bps) {
+            (0, max_swap_size_bps) | (max_swap_size_bps, 0) => max_swap_size_bps,
+            (config_max_swap_size_bps, market_max_swap_size_bps) => {
+                min(config_max_swap_size_bps, market_max_swap_size_bps)
+            }
+        }
+    }
+
+    pub fn seeds(&self) -> MarketSeeds {
+        MarketSeeds {
+            base_token_mint: self.base_token_mint,
+            quote_token_mint: self.is_secondary().then_some(self.quote_token_mint),
+            bump: [self.bump],
+        }
+    }
+
+    pub fn check_and_set_prices(
+        &mut self,
+        bid_prices: [u64; PRICES_LENGTH],
+        ask_prices: [u64; PRICES_LENGTH],
+        price_bounds: &PriceBounds,
+    ) -> Result<()> {
+        if self.are_prices_set() {
+            return Err(TokenMillError::PricesAlreadySet.into());
+        }
+
+        self.check_market_prices(&bid_prices, &ask_prices)?;
+
+        self.bid_prices = bid_prices;
+        self.ask_prices = ask_prices;
+
+        self.check_price_bounds(price_bounds)
+    }
+
+    /// Overwrites the price curve as long as no base token has left the market and no swap ever
+    /// went through it, so a round trip can't reopen the curve.
+    /// Allows the creator to fix a mistyped curve without abandoning the mint.
+    pub fn reset_prices(
+        &mut self,
+        bid_prices: [u64; PRICES_LENGTH],
+        ask_prices: [u64; PRICES_LENGTH],
+        price_bounds: &PriceBounds,
+    ) -> Result<()> {
+        if self.circulating_supply() != 0 {
+            return Err(TokenMillError::CirculatingSupplyNotZero.into());
+        }
+
+        require!(!self.has_traded(), TokenMillError::MarketAlreadyTraded);
+
+        self.check_market_prices(&bid_prices, &ask_prices)?;
+
+        self.bid_prices = bid_prices;
+        self.ask_prices = ask_prices;
+
+        self.check_price_bounds(price_bounds)
+    }
+
+    /// Writes a chunk of the price curve starting at `start_index`, for creations that can't fit
+    /// both price arrays in their transaction. Chunks are appended in order and checked against the
+    /// prices already written, the curve is only set once the last chunk lands.
+    /// Returns whether the curve is complete.
+    pub fn append_prices(
+        &mut self,
+        start_index: u8,
+        bid_prices: &[u64],
+        ask_prices: &[u64],
+        price_bounds: &PriceBounds,
+    ) -> Result<bool> {
+        if self.are_prices_set() {
+            return Err(TokenMillError::PricesAlreadySet.into());
+        }
+
+        let start_index = usize::from(start_index);
+        let end_index = start_index + bid_prices.len();
+        let price_points = self.last_price_index() + 1;
+
+        require!(
+            start_index == usize::from(self.appended_prices)
+                && !bid_prices.is_empty()
+                && bid_prices.len() == ask_prices.len()
+                && end_index <= price_points,
+            TokenMillError::InvalidPriceChunk
+        );
+
+        let mut new_bid_prices = self.bid_prices;
+        let mut new_ask_prices = self.ask_prices;
+
+        new_bid_prices[start_index..end_index].copy_from_slice(bid_prices);
+        new_ask_prices[start_index..end_index].copy_from_slice(ask_prices);
+
+        if end_index == price_points {
+            self.check_and_set_prices(new_bid_prices, new_ask_prices, price_bounds)?;
+        } else {
+            Self::check_price_ordering(&new_bid_prices[..end_index], &new_ask_prices[..end_index])?;
+
+            self.bid_prices = new_bid_prices;
+            self.ask_prices = new_ask_prices;
+        }
+
+        self.appended_prices = end_index as u8;
+
+        Ok(end_index == price_points)
+    }
+
+    pub fn check_prices(
+        bid_prices: &[u64; PRICES_LENGTH],
+        ask_prices: &[u64; PRICES_LENGTH],
+    ) -> Result<()> {
+        Self::check_price_ordering(bid_prices, ask_prices)?;
+
+        if ask_prices[INTERVAL_NUMBER as usize] > MAX_PRICE {
+            return Err(TokenMillError::PriceTooHigh.into());
+        }
+
+        Ok(())
+    }
+
+    /// Same checks as `check_prices` on the points used by the market, the points past a
+    /// compacted tail must be left empty.
+    fn check_market_prices(
+        &self,
+        bid_prices: &[u64; PRICES_LENGTH],
+        ask_prices: &[u64; PRICES_LENGTH],
+    ) -> Result<()> {
+        let last_index = self.last_price_index();
+
+        require!(
+            bid_prices[last_index + 1..]
+                .iter()
+                .chain(&ask_prices[last_index + 1..])
+                .all(|price| *price == 0),
+            TokenMillError::InvalidPriceTail
+        );
+
+        Self::check_price_ordering(&bid_prices[..=last_index], &ask_prices[..=last_index])?;
+
+        if ask_prices[last_index] > MAX_PRICE {
+            return Err(TokenMillError::PriceTooHigh.into());
+        }
+
+        Ok(())
+    }
+
+    /// Merges the last `tail_supply_bps` of the supply into a single interval, the rest of the
+    /// supply being split evenly between the other `price_points - 1` points.
+    pub fn compact_price_tail(&mut self, price_points: u8, tail_supply_bps: u16) -> Result<()> {
+        require!(
+            !self.are_prices_set() && self.appended_prices == 0,
+            TokenMillError::PricesAlreadySet
+        );
+
+        require!(
+            (3..=PRICES_LENGTH).contains(&usize::from(price_points))
+                && tail_supply_bps > 0
+                && u64::from(tail_supply_bps) < MAX_BPS,
+            TokenMillError::InvalidPriceTail
+        );
+
+        let head_supply = u128::from(self.total_supply)
+            * u128::from(MAX_BPS - u64::from(tail_supply_bps))
+            / u128::from(MAX_BPS);
+        let width = head_supply / u128::from(price_points - 2);
+
+        require_gte!(
+            width,
+            u128::from(BASE_PRECISION),
+            TokenMillError::InvalidPriceTail
+        );
+
+        self.width_scaled = u64::try_from(width * SCALE / u128::from(BASE_PRECISION))?;
+        self.price_points = price_points;
+
+        Ok(())
+    }
+
+    /// Index of the last point of the curve.
+    pub fn last_price_index(&self) -> usize {
+        match self.price_points {
+            0 => INTERVAL_NUMBER as usize,
+            price_points => usize::from(price_points) - 1,
+        }
+    }
+
+    /// Normalized width of the interval starting at point `i`, only the last one differs.
+    fn interval_width(&self, i: usize) -> u128 {
+        let width = u128::from(self.width_scaled);
+        let tail_index = self.last_price_index() - 1;
+
+        if i < tail_index {
+            return width;
+        }
+
+        u128::from(self.total_supply) * SCALE / u128::from(BASE_PRECISION)
+            - width * tail_index as u128
+    }
+
+    /// Interval containing `normalized_supply`, with the supply already used within it.
+    fn get_interval(&self, normalized_supply: u128) -> Result<(usize, u128)> {
+        let width = u128::from(self.width_scaled);
+        let tail_index = self.last_price_index() - 1;
+        let tail_start = width * tail_index as u128;
+
+        if normalized_supply < tail_start {
+            return Ok((
+                usize::try_from(normalized_supply / width)?,
+                normalized_supply % width,
+            ));
+        }
+
+        let tail_supply_used = normalized_supply - tail_start;
+        let tail_width = self.interval_width(tail_index);
+
+        if tail_supply_used >= tail_width {
+            Ok((tail_index + 1, tail_supply_used - tail_width))
+        } else {
+            Ok((tail_index, tail_supply_used))
+        }
+    }
+
+    /// Checks the curve of the market against the config bounds. The total raise is computed on
+    /// the market itself, so this runs once the prices are written and the error reverts them.
+    fn check_price_bounds(&self, price_bounds: &PriceBounds) -> Result<()> {
+        for (bid_price, ask_price) in self.bid_prices.iter().zip(self.ask_prices.iter()) {
+            if *ask_price == 0 {
+                continue;
+            }
+
+            let spread_bps =
+                u128::from(ask_price - bid_price) * u128::from(MAX_BPS) / u128::from(*ask_price);
+
+            require_gte!(
+                spread_bps,
+                u128::from(price_bounds.min_spread_bps),
+                TokenMillError::SpreadTooNarrow
+            );
+
+            if price_bounds.max_spread_bps != 0 {
+                require_gte!(
+                    u128::from(price_bounds.max_spread_bps),
+                    spread_bps,
+                    TokenMillError::SpreadTooWide
+                );
+            }
+        }
+
+        if price_bounds.min_total_raise == 0 && price_bounds.max_total_raise == 0 {
+            return Ok(());
+        }
+
+        let (_, total_raise) = self.get_quote_amount_with_parameters(
+            0,
+            self.total_supply,
+            SwapAmountType::ExactOutput,
+            Rounding::Up,
         )?;
 
-        self.fees.creator_fee_share = creator_fee_share;
-        self.fees.staking_fee_share = staking_fee_share;
+        require_gte!(
+            total_raise,
+            price_bounds.min_total_raise,
+            TokenMillError::TotalRaiseTooLow
+        );
+
+        if price_bounds.max_total_raise != 0 {
+            require_gte!(
+                price_bounds.max_total_raise,
+                total_raise,
+                TokenMillError::TotalRaiseTooHigh
+            );
+        }
+
         Ok(())
     }
 
-    pub fn check_and_set_prices(
-        &mut self,
-        bid_prices: [u64; PRICES_LENGTH],
-        ask_prices: [u64; PRICES_LENGTH],
-    ) -> Result<()> {
-        if self.are_prices_set() {
-            return Err(TokenMillError::PricesAlreadySet.into());
-        }
-
-        for i in 0..PRICES_LENGTH {
+    fn check_price_ordering(bid_prices: &[u64], ask_prices: &[u64]) -> Result<()> {
+        for i in 0..bid_prices.len() {
             let bid_price = bid_prices[i];
             let ask_price = ask_prices[i];
 
@@ -139,24 +1237,130 @@ impl Market {
             }
         }
 
-        if ask_prices[INTERVAL_NUMBER as usize] > MAX_PRICE {
-            return Err(TokenMillError::PriceTooHigh.into());
-        }
-
-        self.bid_prices = bid_prices;
-        self.ask_prices = ask_prices;
-
         Ok(())
     }
 
     pub fn are_prices_set(&self) -> bool {
-        self.ask_prices[INTERVAL_NUMBER as usize] != 0
+        self.ask_prices[self.last_price_index()] != 0
     }
 
     pub fn circulating_supply(&self) -> u64 {
         self.total_supply - self.base_reserve
     }
 
+    /// Quote amount owed to the holders of the circulating supply if they all sold it back at the
+    /// bid prices, i.e. the raised quote minus the creator draws. Fees share the quote vault with
+    /// these curve proceeds, until they are moved to the pool of a migrated market.
+    pub fn get_curve_proceeds(&self) -> Result<u64> {
+        Ok(self
+            .get_raised_quote()?
+            .saturating_sub(self.creator_drawn_quote))
+    }
+
+    /// Quote amount the curve paid in for the circulating supply.
+    pub fn get_raised_quote(&self) -> Result<u64> {
+        let circulating_supply = self.circulating_supply();
+
+        if circulating_supply == 0 || self.is_migrated() {
+            return Ok(0);
+        }
+
+        let (_, quote_amount) = self.get_quote_amount_with_parameters(
+            0,
+            circulating_supply,
+            SwapAmountType::ExactInput,
+            Rounding::Down,
+        )?;
+
+        Ok(quote_amount)
+    }
+
+    /// Fees accrued in the quote vault of the market and not distributed yet.
+    pub fn get_pending_fees(&self) -> u64 {
+        self.fees.pending_creator_fees
+            + self.fees.pending_staking_fees
+            + self.pending_holder_rewards
+    }
+
+    /// Reconciles a fee withdrawal of `amount` against the quote vault balance, so that fee
+    /// claims can't dip into the curve proceeds.
+    pub fn check_fee_withdrawal(&self, quote_vault_amount: u64, amount: u64) -> Result<()> {
+        let fee_balance = quote_vault_amount.saturating_sub(self.get_curve_proceeds()?);
+
+        require_gte!(fee_balance, amount, TokenMillError::InsufficientFeeBalance);
+
+        Ok(())
+    }
+
+    /// Ask price at the current circulating supply, interpolated within its interval.
+    pub fn get_ask_price(&self) -> Result<u64> {
+        self.get_price(&self.ask_prices)
+    }
+
+    /// Bid price at the current circulating supply, interpolated within its interval.
+    pub fn get_bid_price(&self) -> Result<u64> {
+        self.get_price(&self.bid_prices)
+    }
+
+    /// Mid price at the current circulating supply, as tracked by the market oracle.
+    pub fn get_mid_price(&self) -> Result<u64> {
+        let bid_price = self.get_bid_price()?;
+
+        Ok(bid_price + (self.get_ask_price()? - bid_price) / 2)
+    }
+
+    /// Base amount that can be sold to the curve before the bid price falls below `price`.
+    /// Rounds the supply of the limit up, so that the whole amount sells at or above `price`.
+    pub fn get_base_amount_to_bid_price(&self, price: u64) -> Result<u64> {
+        let bid_prices = &self.bid_prices;
+
+        if price <= bid_prices[0] {
+            return Ok(self.circulating_supply());
+        }
+
+        let Some(i) = (0..self.last_price_index()).find(|&i| bid_prices[i + 1] >= price) else {
+            return Ok(0);
+        };
+
+        let normalized_limit_supply = u128::from(self.width_scaled) * i as u128
+            + mul_div(
+                u128::from(price - bid_prices[i]),
+                self.interval_width(i),
+                u128::from(bid_prices[i + 1] - bid_prices[i]),
+                Rounding::Up,
+            )
+            .ok_or(TokenMillError::MathError)?;
+
+        let limit_supply = div(
+            normalized_limit_supply * u128::from(BASE_PRECISION),
+            SCALE,
+            Rounding::Up,
+        )?;
+
+        Ok(self.circulating_supply().saturating_sub(limit_supply))
+    }
+
+    fn get_price(&self, price_curve: &[u64; PRICES_LENGTH]) -> Result<u64> {
+        let normalized_supply =
+            u128::from(self.circulating_supply()) * SCALE / u128::from(BASE_PRECISION);
+
+        let last_index = self.last_price_index();
+        let (i, interval_supply_already_used) = self.get_interval(normalized_supply)?;
+
+        if i >= last_index {
+            return Ok(price_curve[last_index]);
+        }
+
+        let price_0 = price_curve[i];
+        let price_1 = price_curve[i + 1];
+
+        Ok(price_0
+            + u64::try_from(
+                u128::from(price_1 - price_0) * interval_supply_already_used
+                    / self.interval_width(i),
+            )?)
+    }
+
     pub fn get_quote_amount(
         &self,
         base_amount: u64,
@@ -191,25 +1395,26 @@ impl Market {
 
         let mut normalized_quote_amount = 0;
 
-        let mut i = usize::try_from(normalized_supply / u128::from(self.width_scaled))?;
-        let mut interval_supply_already_used = normalized_supply % u128::from(self.width_scaled);
+        let last_index = self.last_price_index();
+        let (mut i, mut interval_supply_already_used) = self.get_interval(normalized_supply)?;
 
         let mut price_0 = price_curve[i];
         i += 1;
 
-        while normalized_base_amount_left > 0 && i < PRICES_LENGTH {
+        while normalized_base_amount_left > 0 && i <= last_index {
             let price_1 = price_curve[i];
+            let width = self.interval_width(i - 1);
 
             let delta_base = min(
                 normalized_base_amount_left,
-                u128::from(self.width_scaled) - interval_supply_already_used,
+                width - interval_supply_already_used,
             );
 
             let delta_quote = mul_div(
                 delta_base,
                 u128::from(price_1 - price_0) * (delta_base + 2 * interval_supply_already_used)
-                    + 2 * u128::from(price_0) * u128::from(self.width_scaled),
-                2 * SCALE * u128::from(self.width_scaled),
+                    + 2 * u128::from(price_0) * width,
+                2 * SCALE * width,
                 rounding,
             )
             .ok_or(TokenMillError::MathError)?;
@@ -249,12 +1454,9 @@ impl Market {
         let mut normalized_quote_amount_left = u128::from(quote_amount) * SCALE / quote_precision;
         let mut normalized_base_amount = 0;
 
-        let mut i = usize::try_from(normalized_supply / u128::from(self.width_scaled))?;
-        let mut interval_supply_available = normalized_supply % u128::from(self.width_scaled);
+        let (mut i, mut interval_supply_available) = self.get_interval(normalized_supply)?;
 
-        if interval_supply_available == 0 {
-            interval_supply_available = u128::from(self.width_scaled);
-        } else {
+        if interval_supply_available > 0 {
             i += 1;
         }
 
@@ -262,11 +1464,16 @@ impl Market {
 
         while normalized_quote_amount_left > 0 && i > 0 {
             let price_0 = price_curve[i - 1];
+            let width = self.interval_width(i - 1);
+
+            if interval_supply_available == 0 {
+                interval_supply_available = width;
+            }
 
             let (delta_base, delta_quote) = get_delta_base_in(
                 price_0.into(),
                 price_1.into(),
-                self.width_scaled.into(),
+                width,
                 interval_supply_available,
                 normalized_quote_amount_left,
             )?;
@@ -274,7 +1481,7 @@ impl Market {
             normalized_base_amount += delta_base;
             normalized_quote_amount_left -= delta_quote;
 
-            interval_supply_available = u128::from(self.width_scaled);
+            interval_supply_available = 0;
             price_1 = price_0;
 
             i -= 1;
@@ -306,18 +1513,18 @@ impl Market {
         let mut normalized_quote_amount_left = u128::from(quote_amount) * SCALE / quote_precision;
         let mut normalized_base_amount = 0;
 
-        let mut i = usize::try_from(normalized_supply / u128::from(self.width_scaled))?;
-        let mut interval_supply_already_used = normalized_supply % u128::from(self.width_scaled);
+        let last_index = self.last_price_index();
+        let (mut i, mut interval_supply_already_used) = self.get_interval(normalized_supply)?;
 
         let mut price_0 = price_curve[i];
 
-        while normalized_quote_amount_left > 0 && i < PRICES_LENGTH - 1 {
+        while normalized_quote_amount_left > 0 && i < last_index {
             let price_1 = price_curve[i + 1];
 
             let (delta_base, delta_quote) = get_delta_base_out(
                 price_0.into(),
                 price_1.into(),
-                self.width_scaled.into(),
+                self.interval_width(i),
                 interval_supply_already_used,
                 normalized_quote_amount_left,
             )?;
@@ -351,8 +1558,778 @@ impl Market {
 #[cfg(test)]
 mod tests {
     use anchor_lang::Space;
+    use bytemuck::Zeroable;
+    use joelana_test_utils::{assert_layout, assert_prefix_layout};
+
+    use crate::{
+        constant::{
+            MAX_BPS, MAX_LAUNCH_WINDOW_DURATION, PRICES_LENGTH, REVENUE_EPOCH_DURATION, SCALE,
+            VOLUME_WINDOW_DURATION,
+        },
+        errors::TokenMillError,
+        manager::swap_manager::SwapType,
+        state::{
+            CreationBond, CreatorDraw, Market, MarketFees, MarketPauser, MarketVolume, PriceBounds,
+            WalletBuyLimit, BASELINE_MARKET_SIZE,
+        },
+    };
+
+    #[test]
+    fn volume_windows() {
+        let mut volume = MarketVolume::zeroed();
+
+        volume.record(10, 100, 1);
+        volume.record(VOLUME_WINDOW_DURATION - 1, 100, 1);
+
+        assert_eq!(volume.get_last_window(VOLUME_WINDOW_DURATION - 1), (0, 0));
+        assert_eq!(volume.get_last_window(VOLUME_WINDOW_DURATION), (200, 2));
+
+        volume.record(VOLUME_WINDOW_DURATION + 1, 50, 1);
+
+        assert_eq!(volume.get_last_window(VOLUME_WINDOW_DURATION + 1), (200, 2));
+        assert_eq!(volume.get_last_window(2 * VOLUME_WINDOW_DURATION), (50, 1));
+
+        volume.record(4 * VOLUME_WINDOW_DURATION, 10, 1);
+
+        assert_eq!(volume.get_last_window(4 * VOLUME_WINDOW_DURATION), (0, 0));
+        assert_eq!(volume.quote_volume, 10);
+    }
+
+    #[test]
+    fn lock() {
+        let mut market = Market::zeroed();
+
+        market.lock().unwrap();
+
+        assert!(market.lock().is_err());
+
+        market.unlock();
+
+        assert!(market.lock().is_ok());
+    }
+
+    #[test]
+    fn pause() {
+        let mut market = Market::zeroed();
+
+        market.pause(MarketPauser::Creator).unwrap();
+
+        assert!(market.is_paused(0));
+        assert!(market.pause(MarketPauser::Creator).is_err());
+
+        market.pause(MarketPauser::Authority).unwrap();
+
+        assert_eq!(
+            market.unpause(MarketPauser::Creator).unwrap_err(),
+            TokenMillError::InvalidAuthority.into()
+        );
+        assert!(market.pause(MarketPauser::Authority).is_err());
+
+        market.unpause(MarketPauser::Authority).unwrap();
+
+        assert!(!market.is_paused(0));
+        assert!(market.unpause(MarketPauser::Authority).is_err());
+    }
+
+    #[test]
+    fn pause_by_buy_queue() {
+        let mut market = Market::zeroed();
+
+        market.pause(MarketPauser::BuyQueue).unwrap();
+
+        assert!(market.is_paused(0));
+        assert!(!market.is_paused_except_buy_queue(0));
+        assert_eq!(
+            market.unpause(MarketPauser::Creator).unwrap_err(),
+            TokenMillError::InvalidAuthority.into()
+        );
+
+        market.paused_until_slot = 10;
+
+        assert!(market.is_paused_except_buy_queue(9));
+        assert!(!market.is_paused_except_buy_queue(10));
+
+        market.unpause(MarketPauser::BuyQueue).unwrap();
+
+        assert!(!market.is_paused(10));
+    }
+
+    #[test]
+    fn backfill() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000;
+        market.base_reserve = 800_000;
 
-    use crate::state::Market;
+        assert!(!market.is_backfilled());
+        assert!(market.backfill(3_000));
+
+        assert!(market.is_backfilled());
+        assert_eq!(market.fees.referral_fee_share, 3_000);
+        assert!(market.is_referral_enabled());
+        assert!(market.has_traded());
+
+        // Fields changed since then are left untouched
+        market.referral_enabled = 0;
+
+        assert!(!market.backfill(3_000));
+        assert!(!market.is_referral_enabled());
+
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000;
+        market.base_reserve = 1_000_000;
+
+        assert!(market.backfill(3_000));
+        assert!(!market.has_traded());
+    }
+
+    #[test]
+    fn max_swap_base_amount() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000;
+        market.base_reserve = 800_000;
+
+        assert_eq!(
+            market.get_max_swap_base_amount(SwapType::Buy, 0).unwrap(),
+            None
+        );
+        assert_eq!(
+            market
+                .get_max_swap_base_amount(SwapType::Buy, 1_000)
+                .unwrap(),
+            Some(80_000)
+        );
+        assert_eq!(
+            market
+                .get_max_swap_base_amount(SwapType::Sell, 1_000)
+                .unwrap(),
+            Some(20_000)
+        );
+
+        market.max_swap_size_bps = 500;
+
+        assert_eq!(
+            market.get_max_swap_base_amount(SwapType::Buy, 0).unwrap(),
+            Some(40_000)
+        );
+        assert_eq!(
+            market
+                .get_max_swap_base_amount(SwapType::Buy, 1_000)
+                .unwrap(),
+            Some(40_000)
+        );
+        assert_eq!(
+            market.get_max_swap_base_amount(SwapType::Buy, 100).unwrap(),
+            Some(8_000)
+        );
+    }
+
+    #[test]
+    fn launch_fee_ramp() {
+        let mut market = Market::zeroed();
+        market.launch_fee_bps = 3_000;
+        market.launch_fee_slots = 100;
+        market.launch_slot = 1_000;
+
+        // Curve not live yet
+        assert_eq!(market.get_launch_fee_bps(1_000), 0);
+
+        market.ask_prices[PRICES_LENGTH - 1] = 1;
+
+        assert_eq!(market.get_launch_fee_bps(1_000), 3_000);
+        assert_eq!(market.get_launch_fee_bps(1_025), 2_250);
+        assert_eq!(market.get_launch_fee_bps(1_099), 30);
+        assert_eq!(market.get_launch_fee_bps(1_100), 0);
+
+        market.launch_fee_slots = 0;
+
+        assert_eq!(market.get_launch_fee_bps(1_000), 0);
+    }
+
+    #[test]
+    fn unlocked_supply() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000;
+        market.base_reserve = 1_000_000;
+
+        assert_eq!(market.get_unlocked_supply(0), 1_000_000);
+
+        market.set_emission_schedule(100_000, 1_000, 900).unwrap();
+
+        assert_eq!(market.get_unlocked_supply(0), 100_000);
+        assert_eq!(market.get_unlocked_supply(1_000), 100_000);
+        assert_eq!(market.get_unlocked_supply(1_450), 550_000);
+        assert_eq!(market.get_unlocked_supply(1_900), 1_000_000);
+        assert_eq!(market.get_unlocked_supply(i64::MAX), 1_000_000);
+
+        market.base_reserve = 800_000;
+
+        assert_eq!(market.get_available_supply(1_000), 0);
+        assert_eq!(market.get_available_supply(1_450), 350_000);
+
+        assert_eq!(
+            market
+                .set_emission_schedule(1_000_001, 1_000, 900)
+                .unwrap_err(),
+            TokenMillError::InvalidEmissionSchedule.into()
+        );
+        assert_eq!(
+            market
+                .set_emission_schedule(100_000, 1_000, -1)
+                .unwrap_err(),
+            TokenMillError::InvalidEmissionSchedule.into()
+        );
+    }
+
+    #[test]
+    fn wallet_buy_limit() {
+        let mut market = Market::zeroed();
+
+        assert_eq!(market.get_max_buy_per_wallet(0), None);
+
+        let mut wallet_buy_limit = WalletBuyLimit {
+            max_buy_per_wallet: 1_000,
+            launch_window_seconds: 600,
+        };
+
+        market.set_wallet_buy_limit(&wallet_buy_limit, 100).unwrap();
+
+        assert_eq!(market.get_max_buy_per_wallet(699), Some(1_000));
+        assert_eq!(market.get_max_buy_per_wallet(700), None);
+
+        wallet_buy_limit.launch_window_seconds = MAX_LAUNCH_WINDOW_DURATION + 1;
+
+        assert_eq!(
+            market
+                .set_wallet_buy_limit(&wallet_buy_limit, 100)
+                .unwrap_err(),
+            TokenMillError::InvalidWalletBuyLimit.into()
+        );
+    }
+
+    #[test]
+    fn migrate_quote_token() {
+        let mut market = Market::zeroed();
+        market.quote_token_decimals = 9;
+        market.bid_prices = std::array::from_fn(|i| i as u64 * 1_000 + 1);
+        market.ask_prices = std::array::from_fn(|i| i as u64 * 1_000 + 3);
+        market.min_claim_amount = 1_000_000_000;
+        market.fees.pending_creator_fees = 3_000_000_000;
+        market.creator_drawn_quote = 1_000_000_001;
+        market.pending_draw_amount = 1_000_000_001;
+
+        let quote_token_mint = Pubkey::new_unique();
+
+        // From a 9 decimals quote token depegged to 0.5 to a 6 decimals one
+        let quote_amount = market
+            .migrate_quote_token(quote_token_mint, 6, SCALE as u64 / 2, 3_000_000_001)
+            .unwrap();
+
+        assert_eq!(quote_amount, 1_500_001);
+        assert_eq!(market.bid_prices[0], 0);
+        assert_eq!(market.ask_prices[0], 2);
+        assert_eq!(market.bid_prices[PRICES_LENGTH - 1], 5_000);
+        assert_eq!(market.ask_prices[PRICES_LENGTH - 1], 5_002);
+        assert_eq!(market.min_claim_amount, 500_000);
+        assert_eq!(market.fees.pending_creator_fees, 1_500_000);
+        assert_eq!(market.creator_drawn_quote, 500_001);
+        assert_eq!(market.pending_draw_amount, 500_000);
+        assert_eq!(market.quote_token_mint, quote_token_mint);
+        assert_eq!(market.quote_token_decimals, 6);
+
+        assert_eq!(
+            market
+                .migrate_quote_token(quote_token_mint, 6, SCALE as u64, 0)
+                .unwrap_err(),
+            TokenMillError::InvalidQuoteTokenMigration.into()
+        );
+
+        // A rate flooring the prices to 0 would break the curve
+        assert_eq!(
+            market
+                .migrate_quote_token(Pubkey::new_unique(), 6, 1, 0)
+                .unwrap_err(),
+            TokenMillError::InvalidConversionRate.into()
+        );
+
+        // Airdrop leaves and held referral fees are owed in the old quote token
+        market.airdropped_quote = 1;
+
+        assert_eq!(
+            market
+                .migrate_quote_token(Pubkey::new_unique(), 6, SCALE as u64, 0)
+                .unwrap_err(),
+            TokenMillError::InvalidQuoteTokenMigration.into()
+        );
+
+        market.airdropped_quote = 0;
+        market.held_referral_fees = 1;
+
+        assert_eq!(
+            market
+                .migrate_quote_token(Pubkey::new_unique(), 6, SCALE as u64, 0)
+                .unwrap_err(),
+            TokenMillError::InvalidQuoteTokenMigration.into()
+        );
+    }
+
+    #[test]
+    fn fee_withdrawal() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 1_000_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+        market.bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 1_000_000_000);
+
+        assert_eq!(market.get_curve_proceeds().unwrap(), 0);
+
+        // One interval sold, between 0.1 and 0.2 quote per base token
+        market.base_reserve -= 100_000_000;
+
+        assert_eq!(market.get_curve_proceeds().unwrap(), 15_000_000);
+
+        assert!(market.check_fee_withdrawal(15_000_100, 100).is_ok());
+        assert!(market.check_fee_withdrawal(15_000_100, 101).is_err());
+        assert!(market.check_fee_withdrawal(14_000_000, 1).is_err());
+    }
+
+    #[test]
+    fn creator_draw() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 900_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+        market.bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 1_000_000_000);
+
+        let creator_draw = CreatorDraw {
+            max_bps: 500,
+            delay: 100,
+        };
+
+        // 5% of the 15_000_000 raised
+        assert_eq!(market.get_max_draw_amount(500).unwrap(), 750_000);
+        assert_eq!(
+            market
+                .request_draw(&creator_draw, 750_001, 1_000)
+                .unwrap_err(),
+            TokenMillError::CreatorDrawExceeded.into()
+        );
+        assert_eq!(
+            market.request_draw(&creator_draw, 500_000, 1_000).unwrap(),
+            1_100
+        );
+
+        assert_eq!(
+            market.execute_draw(&creator_draw, 1_099).unwrap_err(),
+            TokenMillError::CreatorDrawLocked.into()
+        );
+        assert_eq!(market.execute_draw(&creator_draw, 1_100).unwrap(), 500_000);
+        assert!(market.execute_draw(&creator_draw, 1_100).is_err());
+
+        assert_eq!(market.get_raised_quote().unwrap(), 15_000_000);
+        assert_eq!(market.get_curve_proceeds().unwrap(), 14_500_000);
+        assert_eq!(market.get_max_draw_amount(500).unwrap(), 250_000);
+
+        assert_eq!(
+            market
+                .request_draw(&CreatorDraw::default(), 1, 1_100)
+                .unwrap_err(),
+            TokenMillError::InvalidCreatorDraw.into()
+        );
+    }
+
+    #[test]
+    fn migrate() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 1_000_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+        market.bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 1_000_000_000);
+
+        assert!(market.migrate(0, 0).is_err());
+
+        // One interval sold, between 0.1 and 0.2 quote per base token
+        market.base_reserve -= 100_000_000;
+
+        assert!(market.migrate(15_000_001, 0).is_err());
+        assert!(market.migrate(0, 1_001).is_err());
+        assert_eq!(
+            market.migrate(15_000_000, 1_000).unwrap(),
+            (900_000_000, 15_000_000)
+        );
+
+        assert!(market.is_migrated());
+        assert!(!market.is_staking_frozen());
+        assert_eq!(market.base_reserve, 0);
+        assert_eq!(market.get_curve_proceeds().unwrap(), 0);
+        assert!(market.check_fee_withdrawal(100, 100).is_ok());
+        assert!(market.migrate(0, 0).is_err());
+    }
+
+    #[test]
+    fn staking_frozen() {
+        let mut market = Market::zeroed();
+        market.freeze_staking_on_migration = 1;
+
+        assert!(!market.is_staking_frozen());
+
+        market.migrated = 1;
+
+        assert!(market.is_staking_frozen());
+
+        market.freeze_staking_on_migration = 0;
+
+        assert!(!market.is_staking_frozen());
+    }
+
+    #[test]
+    fn referral_params() {
+        let mut market = Market::zeroed();
+        market.fees.creator_fee_share = 6_000;
+        market.fees.staking_fee_share = 2_000;
+
+        market.set_referral_params(MAX_BPS as u16, false).unwrap();
+
+        assert_eq!(market.fees.referral_fee_share, MAX_BPS as u16);
+        assert!(!market.is_referral_enabled());
+
+        // The referral part can take the whole remaining fee but no more
+        let (creator_fee, staking_fee, protocol_fee, referral_fee) = market
+            .fees
+            .get_fee_distribution(10_000, Some(market.fees.referral_fee_share))
+            .unwrap();
+
+        assert_eq!(
+            (creator_fee, staking_fee, protocol_fee, referral_fee),
+            (6_000, 2_000, 0, 2_000)
+        );
+
+        assert_eq!(
+            market
+                .set_referral_params(MAX_BPS as u16 + 1, true)
+                .unwrap_err(),
+            TokenMillError::InvalidFeeShare.into()
+        );
+        assert!(!market.is_referral_enabled());
+    }
+
+    #[test]
+    fn creation_bond() {
+        let mut market = Market::zeroed();
+
+        let creation_bond = CreationBond {
+            amount: 1_000_000_000,
+            release_epochs: 2,
+        };
+
+        assert_eq!(
+            market.post_creation_bond(&creation_bond, 1_000),
+            1_000_000_000
+        );
+
+        let release_time = 1_000 + 2 * REVENUE_EPOCH_DURATION;
+
+        assert_eq!(market.creation_bond_release_time, release_time);
+
+        // Untraded markets keep their bond past the release time
+        assert_eq!(
+            market.release_creation_bond(release_time).unwrap_err(),
+            TokenMillError::CreationBondLocked.into()
+        );
+
+        market.traded = 1;
+
+        assert_eq!(
+            market.release_creation_bond(release_time - 1).unwrap_err(),
+            TokenMillError::CreationBondLocked.into()
+        );
+        assert_eq!(
+            market.release_creation_bond(release_time).unwrap(),
+            1_000_000_000
+        );
+        assert!(market.release_creation_bond(release_time).is_err());
+
+        // Graduation releases the bond right away
+        market.post_creation_bond(&creation_bond, 1_000);
+        market.migrated = 1;
+
+        assert_eq!(market.release_creation_bond(1_000).unwrap(), 1_000_000_000);
+
+        market.post_creation_bond(&creation_bond, 1_000);
+
+        assert_eq!(market.slash_creation_bond().unwrap(), 1_000_000_000);
+        assert!(market.slash_creation_bond().is_err());
+        assert!(market.release_creation_bond(1_000).is_err());
+    }
+
+    #[test]
+    fn graduation_milestones() {
+        let mut market = Market::zeroed();
+
+        assert_eq!(market.get_graduation_milestone_bps(), 0);
+
+        market.total_supply = 1_000_000;
+        market.base_reserve = 1_000_000 - 149_999;
+
+        assert_eq!(market.get_supply_sold_bps(), 1_499);
+        assert_eq!(market.get_graduation_milestone_bps(), 1_000);
+
+        market.base_reserve -= 1;
+
+        assert_eq!(market.get_graduation_milestone_bps(), 1_500);
+
+        market.base_reserve = 0;
+
+        assert_eq!(market.get_graduation_milestone_bps(), 10_000);
+    }
+
+    #[test]
+    fn scheduled_updates() {
+        let mut market = Market::zeroed();
+
+        market
+            .schedule_update(ScheduledUpdateKind::MinClaimAmount, 5, 20, 0, 0)
+            .unwrap();
+        market
+            .schedule_update(ScheduledUpdateKind::MinClaimAmount, 3, 10, 0, 0)
+            .unwrap();
+
+        market.apply_scheduled_updates(9, 0);
+
+        assert_eq!(market.min_claim_amount, 0);
+
+        // Applied in slot order
+        market.apply_scheduled_updates(20, 0);
+
+        assert_eq!(market.min_claim_amount, 5);
+        assert!(market
+            .scheduled_updates
+            .iter()
+            .all(ScheduledUpdate::is_empty));
+
+        assert_eq!(
+            market
+                .schedule_update(ScheduledUpdateKind::MinClaimAmount, 5, 20, 20, 0)
+                .unwrap_err(),
+            TokenMillError::InvalidScheduledUpdate.into()
+        );
+
+        for _ in 0..MAX_SCHEDULED_UPDATES {
+            market
+                .schedule_update(ScheduledUpdateKind::MinClaimAmount, 5, 30, 20, 0)
+                .unwrap();
+        }
+
+        assert_eq!(
+            market
+                .schedule_update(ScheduledUpdateKind::MinClaimAmount, 5, 30, 20, 0)
+                .unwrap_err(),
+            TokenMillError::ScheduledUpdatesFull.into()
+        );
+
+        market.cancel_scheduled_update(1).unwrap();
+
+        assert!(market.scheduled_updates[1].is_empty());
+        assert_eq!(
+            market.cancel_scheduled_update(1).unwrap_err(),
+            TokenMillError::InvalidScheduledUpdate.into()
+        );
+    }
+
+    #[test]
+    fn scheduled_emission_start() {
+        let mut market = Market::zeroed();
+        market.emission_start = 1_000;
+        market.emission_duration = 100;
+
+        market
+            .schedule_update(ScheduledUpdateKind::EmissionStart, 2_000, 10, 0, 500)
+            .unwrap();
+
+        assert_eq!(
+            market
+                .schedule_update(ScheduledUpdateKind::EmissionStart, 2_000, 10, 0, 1_000)
+                .unwrap_err(),
+            TokenMillError::InvalidScheduledUpdate.into()
+        );
+
+        // The emission started before the update was applied
+        market.apply_scheduled_updates(10, 1_000);
+
+        assert_eq!(market.emission_start, 1_000);
+
+        market
+            .schedule_update(ScheduledUpdateKind::EmissionStart, 2_000, 20, 10, 900)
+            .unwrap();
+        market.apply_scheduled_updates(20, 900);
+
+        assert_eq!(market.emission_start, 2_000);
+
+        // Launch slot changes need a live curve that didn't trade yet
+        assert_eq!(
+            market
+                .schedule_update(ScheduledUpdateKind::LaunchSlot, 30, 30, 20, 900)
+                .unwrap_err(),
+            TokenMillError::InvalidScheduledUpdate.into()
+        );
+    }
+
+    #[test]
+    fn base_amount_to_bid_price() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 500_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+        market.bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 1_000_000_000);
+
+        // Half the supply is circulating, at a bid price of 0.6 quote per base token
+        assert_eq!(market.get_bid_price().unwrap(), 6_000_000_000);
+
+        assert_eq!(
+            market.get_base_amount_to_bid_price(1_000_000_000).unwrap(),
+            500_000_000
+        );
+        assert_eq!(
+            market.get_base_amount_to_bid_price(3_500_000_000).unwrap(),
+            250_000_000
+        );
+        assert_eq!(
+            market.get_base_amount_to_bid_price(6_000_000_000).unwrap(),
+            0
+        );
+        assert_eq!(market.get_base_amount_to_bid_price(u64::MAX).unwrap(), 0);
+
+        market.base_reserve -= market.get_base_amount_to_bid_price(3_500_000_000).unwrap();
+
+        assert!(market.get_bid_price().unwrap() >= 3_500_000_000);
+    }
+
+    #[test]
+    fn price_bounds() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 1_000_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+
+        // 10% spread, buying the whole supply costs 600 quote tokens
+        let ask_prices = std::array::from_fn(|i| (i as u64 + 1) * 1_000_000_000);
+        let bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 900_000_000);
+
+        let mut check = |price_bounds: PriceBounds| {
+            market.ask_prices = [0; PRICES_LENGTH];
+            market.check_and_set_prices(bid_prices, ask_prices, &price_bounds)
+        };
+
+        assert!(check(PriceBounds::default()).is_ok());
+        assert!(check(PriceBounds {
+            min_total_raise: 600_000_000,
+            max_total_raise: 600_000_000,
+            min_spread_bps: 1_000,
+            max_spread_bps: 1_000,
+        })
+        .is_ok());
+
+        let cases = [
+            (
+                PriceBounds {
+                    min_total_raise: 600_000_001,
+                    ..Default::default()
+                },
+                TokenMillError::TotalRaiseTooLow,
+            ),
+            (
+                PriceBounds {
+                    max_total_raise: 599_999_999,
+                    ..Default::default()
+                },
+                TokenMillError::TotalRaiseTooHigh,
+            ),
+            (
+                PriceBounds {
+                    min_spread_bps: 1_001,
+                    ..Default::default()
+                },
+                TokenMillError::SpreadTooNarrow,
+            ),
+            (
+                PriceBounds {
+                    max_spread_bps: 999,
+                    ..Default::default()
+                },
+                TokenMillError::SpreadTooWide,
+            ),
+        ];
+
+        for (price_bounds, error) in cases {
+            assert_eq!(check(price_bounds).unwrap_err(), error.into());
+        }
+    }
+
+    #[test]
+    fn compacted_tail() {
+        let mut market = Market::zeroed();
+        market.total_supply = 1_000_000_000;
+        market.base_reserve = 1_000_000_000;
+        market.width_scaled = 1_000_000_000_000;
+        market.quote_token_decimals = 6;
+
+        assert!(market.compact_price_tail(2, 5_000).is_err());
+        assert!(market.compact_price_tail(6, 10_000).is_err());
+
+        market.compact_price_tail(6, 5_000).unwrap();
+
+        assert_eq!(market.last_price_index(), 5);
+        assert_eq!(market.width_scaled, 1_250_000_000_000);
+
+        // Same linear curve as `price_bounds`, the last half of the supply in a single interval
+        let mut ask_prices = [0; PRICES_LENGTH];
+        let mut bid_prices = [0; PRICES_LENGTH];
+
+        for i in 0..5 {
+            ask_prices[i] = 1_000_000_000 + i as u64 * 1_250_000_000;
+            bid_prices[i] = ask_prices[i] / 10 * 9;
+        }
+
+        ask_prices[5] = 11_000_000_000;
+        bid_prices[5] = 9_900_000_000;
+
+        let full_bid_prices = std::array::from_fn(|i| (i as u64 + 1) * 900_000_000);
+
+        assert_eq!(
+            market
+                .check_and_set_prices(full_bid_prices, ask_prices, &PriceBounds::default())
+                .unwrap_err(),
+            TokenMillError::InvalidPriceTail.into()
+        );
+
+        market
+            .check_and_set_prices(
+                bid_prices,
+                ask_prices,
+                &PriceBounds {
+                    min_total_raise: 600_000_000,
+                    max_total_raise: 600_000_000,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        assert!(market.are_prices_set());
+        assert!(market.compact_price_tail(6, 5_000).is_err());
+
+        market.base_reserve = 250_000_000;
+
+        assert_eq!(market.get_ask_price().unwrap(), 8_500_000_000);
+
+        let (base_amount, quote_amount) = market.get_base_amount_out(u64::MAX).unwrap();
+
+        assert_eq!(base_amount, 250_000_000);
+        assert_eq!(quote_amount, 243_750_000);
+    }
 
     #[test]
     fn size() {
@@ -362,4 +2339,112 @@ mod tests {
 
         assert!(size < 10_240);
     }
+
+    /// Fields of the first release, read in place from existing markets. New fields never go
+    /// before them, these offsets can't change.
+    #[test]
+    fn baseline_layout() {
+        assert_prefix_layout!(Market, BASELINE_MARKET_SIZE, {
+            config: 0,
+            creator: 32,
+            base_token_mint: 64,
+            quote_token_mint: 96,
+            base_reserve: 128,
+            bid_prices: 136,
+            ask_prices: 224,
+            width_scaled: 312,
+            total_supply: 320,
+            fees: 328,
+            quote_token_decimals: 352,
+            bump: 353,
+        });
+
+        assert_prefix_layout!(MarketFees, 24, {
+            staking_fee_share: 0,
+            creator_fee_share: 2,
+            pending_staking_fees: 8,
+            pending_creator_fees: 16,
+        });
+
+        // `MarketFees` is embedded in the baseline fields, so it can't grow
+        assert_eq!(std::mem::size_of::<MarketFees>(), 24);
+    }
+
+    #[test]
+    fn layout() {
+        assert_layout!(Market, 640, {
+            config: 0,
+            creator: 32,
+            base_token_mint: 64,
+            quote_token_mint: 96,
+            base_reserve: 128,
+            bid_prices: 136,
+            ask_prices: 224,
+            width_scaled: 312,
+            total_supply: 320,
+            fees: 328,
+            quote_token_decimals: 352,
+            bump: 353,
+            kind: 354,
+            locked: 355,
+            max_swap_size_bps: 356,
+            appended_prices: 358,
+            migrated: 359,
+            verified: 360,
+            price_points: 361,
+            launch_fee_bps: 362,
+            traded: 364,
+            freeze_staking_on_migration: 365,
+            referral_enabled: 366,
+            version: 367,
+            swap_fee_bps: 368,
+            paused: 370,
+            dynamic_fee: 371,
+            variable_fee_bps: 372,
+            max_wallet_holding_bps: 374,
+            pending_holder_rewards: 376,
+            paused_until_slot: 384,
+            volume: 392,
+            min_referred_volume: 432,
+            min_claim_amount: 440,
+            launch_slot: 448,
+            launch_fee_slots: 456,
+            max_buy_per_wallet: 464,
+            launch_window_end: 472,
+            emission_initial_supply: 480,
+            emission_start: 488,
+            emission_duration: 496,
+            scheduled_updates: 504,
+            creation_bond: 600,
+            creation_bond_release_time: 608,
+            creator_drawn_quote: 616,
+            pending_draw_amount: 624,
+            pending_draw_unlock_time: 632,
+            airdropped_quote: 640,
+            held_referral_fees: 648,
+        });
+
+        assert_layout!(ScheduledUpdate, 24, {
+            slot: 0,
+            value: 8,
+            kind: 16,
+        });
+
+        assert_layout!(MarketFees, 24, {
+            staking_fee_share: 0,
+            creator_fee_share: 2,
+            referral_fee_share: 4,
+            holder_rewards_fee_bps: 6,
+            pending_staking_fees: 8,
+            pending_creator_fees: 16,
+        });
+
+        assert_layout!(MarketVolume, 40, {
+            window_start: 0,
+            quote_volume: 8,
+            swap_fees: 16,
+            previous_quote_volume: 24,
+            previous_swap_fees: 32,
+        });
+    }
 }
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::MAX_BPS, errors::TokenMillError};
+
+pub const MARKET_ORACLE_PDA_SEED: &str = "market_oracle";
+pub const MARKET_ORACLE_OBSERVATIONS: usize = 64;
+
+/// Cumulative mid price of the market at `timestamp`, i.e. the sum of the prices weighted by
+/// the number of seconds they held.
+/// The cumulative price is stored as little endian bytes to keep the account 8 bytes aligned.
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,
+    cumulative_price: [u8; 16],
+}
+
+impl Observation {
+    pub fn cumulative_price(&self) -> u128 {
+        u128::from_le_bytes(self.cumulative_price)
+    }
+}
+
+/// Ring buffer of price observations of a market, recorded by swaps, from which consumers read a
+/// time weighted average price, see `get_twap`.
+/// A swap records the price that held since the previous observation before moving the curve, so
+/// a price pushed within a block only weighs from the next second onwards.
+#[account(zero_copy)]
+#[derive(Debug, InitSpace)]
+pub struct MarketOracle {
+    pub market: Pubkey,
+
+    pub observations: [Observation; MARKET_ORACLE_OBSERVATIONS],
+
+    /// Index of the latest observation.
+    pub index: u16,
+    /// Number of observations written, up to `MARKET_ORACLE_OBSERVATIONS`.
+    pub length: u16,
+    pub bump: u8,
+
+    _space: [u8; 3],
+}
+
+impl MarketOracle {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, timestamp: i64) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+
+        self.observations[0].timestamp = timestamp;
+        self.length = 1;
+
+        Ok(())
+    }
+
+    fn latest(&self) -> &Observation {
+        &self.observations[usize::from(self.index)]
+    }
+
+    /// Accrues `price`, the mid price that held since the latest observation, up to `timestamp`.
+    /// Swaps within the second of the latest observation don't add one.
+    pub fn record(&mut self, price: u64, timestamp: i64) -> Result<()> {
+        let latest = *self.latest();
+
+        if timestamp <= latest.timestamp {
+            return Ok(());
+        }
+
+        let cumulative_price = latest
+            .cumulative_price()
+            .wrapping_add(u128::from(price) * (timestamp - latest.timestamp) as u128);
+
+        self.index = (self.index + 1) % MARKET_ORACLE_OBSERVATIONS as u16;
+        self.length = (self.length + 1).min(MARKET_ORACLE_OBSERVATIONS as u16);
+
+        self.observations[usize::from(self.index)] = Observation {
+            timestamp,
+            cumulative_price: cumulative_price.to_le_bytes(),
+        };
+
+        Ok(())
+    }
+
+    /// Cumulative price at `target`, with `price` the current mid price of the market.
+    /// Prices only move at observations, so the cumulative price is interpolated between them.
+    fn get_cumulative_price(&self, target: i64, price: u64) -> Result<u128> {
+        let latest = self.latest();
+
+        if target >= latest.timestamp {
+            return Ok(latest
+                .cumulative_price()
+                .wrapping_add(u128::from(price) * (target - latest.timestamp) as u128));
+        }
+
+        let mut next = *latest;
+
+        for i in 1..usize::from(self.length) {
+            let index = (usize::from(self.index) + MARKET_ORACLE_OBSERVATIONS - i)
+                % MARKET_ORACLE_OBSERVATIONS;
+            let observation = self.observations[index];
+
+            if target >= observation.timestamp {
+                let delta = next
+                    .cumulative_price()
+                    .wrapping_sub(observation.cumulative_price());
+
+                return Ok(observation.cumulative_price().wrapping_add(
+                    delta * (target - observation.timestamp) as u128
+                        / (next.timestamp - observation.timestamp) as u128,
+                ));
+            }
+
+            next = observation;
+        }
+
+        Err(TokenMillError::InvalidTwapWindow.into())
+    }
+
+    /// Time weighted average mid price of the last `window` seconds, scaled by `SCALE`.
+    /// The window can't start before the oldest observation.
+    pub fn get_twap(&self, window: i64, price: u64, timestamp: i64) -> Result<u64> {
+        require_gt!(window, 0, TokenMillError::InvalidTwapWindow);
+
+        let start = self.get_cumulative_price(timestamp - window, price)?;
+        let end = self.get_cumulative_price(timestamp, price)?;
+
+        Ok(u64::try_from(end.wrapping_sub(start) / window as u128)?)
+    }
+
+    /// Distance of `price` from the twap of the last `window` seconds, in bps of the twap.
+    /// The window is shortened to the oldest observation, a fresh oracle has no volatility.
+    pub fn get_volatility_bps(&self, window: i64, price: u64, timestamp: i64) -> Result<u64> {
+        let oldest = (usize::from(self.index) + MARKET_ORACLE_OBSERVATIONS + 1
+            - usize::from(self.length))
+            % MARKET_ORACLE_OBSERVATIONS;
+        let window = window.min(timestamp - self.observations[oldest].timestamp);
+
+        if window <= 0 {
+            return Ok(0);
+        }
+
+        let twap = self.get_twap(window, price, timestamp)?;
+
+        if twap == 0 {
+            return Ok(0);
+        }
+
+        Ok(u64::try_from(
+            u128::from(price.abs_diff(twap)) * MAX_BPS as u128 / u128::from(twap),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use joelana_test_utils::assert_layout;
+
+    fn new_oracle() -> MarketOracle {
+        let mut oracle = MarketOracle {
+            market: Pubkey::default(),
+            observations: [Observation {
+                timestamp: 0,
+                cumulative_price: [0; 16],
+            }; MARKET_ORACLE_OBSERVATIONS],
+            index: 0,
+            length: 0,
+            bump: 0,
+            _space: [0; 3],
+        };
+
+        oracle.initialize(0, Pubkey::default(), 1_000).unwrap();
+
+        oracle
+    }
+
+    #[test]
+    fn twap() {
+        let mut oracle = new_oracle();
+
+        // 100 held for 100s, then 300 for 50s, the price is now 200
+        oracle.record(100, 1_100).unwrap();
+        oracle.record(300, 1_150).unwrap();
+
+        assert_eq!(oracle.get_twap(150, 200, 1_150).unwrap(), 500 / 3);
+        assert_eq!(oracle.get_twap(100, 200, 1_200).unwrap(), 250);
+        // Starts in the middle of the first interval
+        assert_eq!(oracle.get_twap(100, 200, 1_150).unwrap(), 200);
+
+        assert!(oracle.get_twap(151, 200, 1_150).is_err());
+        assert!(oracle.get_twap(0, 200, 1_150).is_err());
+    }
+
+    #[test]
+    fn twap_after_wrap_around() {
+        let mut oracle = new_oracle();
+
+        for i in 1..=MARKET_ORACLE_OBSERVATIONS as i64 {
+            oracle.record(100, 1_000 + 10 * i).unwrap();
+        }
+
+        assert_eq!(oracle.length, MARKET_ORACLE_OBSERVATIONS as u16);
+        assert_eq!(oracle.index, 0);
+
+        // The initial observation was overwritten
+        assert!(oracle.get_twap(640, 100, 1_640).is_err());
+        assert_eq!(oracle.get_twap(630, 100, 1_640).unwrap(), 100);
+    }
+
+    #[test]
+    fn record_within_same_second() {
+        let mut oracle = new_oracle();
+
+        oracle.record(100, 1_100).unwrap();
+        oracle.record(1_000_000, 1_100).unwrap();
+
+        assert_eq!(oracle.length, 2);
+        assert_eq!(oracle.get_twap(100, 100, 1_100).unwrap(), 100);
+    }
+
+    #[test]
+    fn volatility() {
+        let mut oracle = new_oracle();
+
+        assert_eq!(oracle.get_volatility_bps(300, 100, 1_000).unwrap(), 0);
+
+        oracle.record(100, 1_100).unwrap();
+
+        // The window is shortened to the 100s of history
+        assert_eq!(oracle.get_volatility_bps(300, 100, 1_100).unwrap(), 0);
+        assert_eq!(oracle.get_volatility_bps(300, 150, 1_100).unwrap(), 5_000);
+        assert_eq!(oracle.get_volatility_bps(300, 50, 1_100).unwrap(), 5_000);
+
+        // 150 held for the last 100s of the 200s window
+        oracle.record(150, 1_200).unwrap();
+
+        assert_eq!(oracle.get_volatility_bps(200, 150, 1_200).unwrap(), 2_000);
+    }
+
+    #[test]
+    fn layout() {
+        assert_layout!(Observation, 24, {
+            timestamp: 0,
+            cumulative_price: 8,
+        });
+
+        assert_layout!(MarketOracle, 1_576, {
+            market: 0,
+            observations: 32,
+            index: 1_568,
+            length: 1_570,
+            bump: 1_572,
+        });
+    }
+}
@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{MAX_BPS, VOLATILITY_EWMA_ALPHA_BPS, VOLUME_WINDOW_DURATION},
+    errors::TokenMillError,
+};
+
+pub const MARKET_STATS_PDA_SEED: &str = "market_stats";
+pub const MARKET_STATS_BUCKETS: usize = 24;
+pub const MARKET_STATS_BUCKET_DURATION: i64 = 3_600; // 1 hour
+
+/// Swaps of the market within an hour, prices being mid prices after the swaps, scaled by
+/// `SCALE`.
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct StatsBucket {
+    /// Hour of the bucket, in number of `MARKET_STATS_BUCKET_DURATION` since the epoch.
+    pub hour: u64,
+    pub base_volume: u64,
+    pub quote_volume: u64,
+    pub trade_count: u64,
+    pub open_price: u64,
+    pub high_price: u64,
+    pub low_price: u64,
+    pub close_price: u64,
+}
+
+/// Stats of a market, recorded by the swaps that provide the account, see `record_trade`, and
+/// snapshotted daily by a permissionless crank, see `roll_market_stats`. The account holds the
+/// lamports rewarding the keepers on top of its own rent, funded with plain transfers.
+/// Cumulative volumes are stored as little endian bytes to keep the account 8 bytes aligned.
+#[account(zero_copy)]
+#[derive(Debug, InitSpace)]
+pub struct MarketStats {
+    pub market: Pubkey,
+
+    /// Last volume window rolled over, in number of `VOLUME_WINDOW_DURATION` since the epoch.
+    pub window: u64,
+    /// Mid price of the market at the last rollover, scaled by `SCALE`.
+    pub price: u64,
+    /// Counters of the last complete window at the last rollover.
+    pub quote_volume: u64,
+    pub swap_fees: u64,
+    /// Exponentially weighted moving average of the price changes between rollovers, in bps.
+    pub volatility_bps: u64,
+
+    pub total_keeper_rewards: u64,
+
+    total_base_volume: [u8; 16],
+    total_quote_volume: [u8; 16],
+    pub trade_count: u64,
+    /// Mid price of the market after the last recorded swap, scaled by `SCALE`.
+    pub last_trade_price: u64,
+    pub last_trade_timestamp: i64,
+
+    /// Ring buffer of the last 24 hours, indexed by hour.
+    pub buckets: [StatsBucket; MARKET_STATS_BUCKETS],
+
+    pub bump: u8,
+
+    _space: [u8; 7],
+}
+
+impl MarketStats {
+    pub fn get_window(timestamp: i64) -> u64 {
+        (timestamp / VOLUME_WINDOW_DURATION) as u64
+    }
+
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        price: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.window = Self::get_window(timestamp);
+        self.price = price;
+
+        Ok(())
+    }
+
+    /// Snapshots the counters of the last complete window and accrues the price change since the
+    /// last rollover into the volatility. Windows without a rollover count as a single change.
+    pub fn rollover(
+        &mut self,
+        window: u64,
+        price: u64,
+        quote_volume: u64,
+        swap_fees: u64,
+    ) -> Result<()> {
+        require_gt!(window, self.window, TokenMillError::RolloverTooEarly);
+
+        let price_change_bps = if self.price == 0 {
+            0
+        } else {
+            u128::from(price.abs_diff(self.price)) * MAX_BPS as u128 / u128::from(self.price)
+        };
+
+        // Weighted average of values that fit in a u64, so it fits as well
+        self.volatility_bps = u64::try_from(
+            (u128::from(self.volatility_bps) * u128::from(MAX_BPS - VOLATILITY_EWMA_ALPHA_BPS)
+                + price_change_bps.min(u64::MAX as u128) * u128::from(VOLATILITY_EWMA_ALPHA_BPS))
+                / MAX_BPS as u128,
+        )?;
+
+        self.window = window;
+        self.price = price;
+        self.quote_volume = quote_volume;
+        self.swap_fees = swap_fees;
+
+        Ok(())
+    }
+
+    pub fn total_base_volume(&self) -> u128 {
+        u128::from_le_bytes(self.total_base_volume)
+    }
+
+    pub fn total_quote_volume(&self) -> u128 {
+        u128::from_le_bytes(self.total_quote_volume)
+    }
+
+    /// Records a swap of `base_amount` for `quote_amount`, `price` being the mid price of the
+    /// market after the swap. Buckets older than a day are overwritten by the new hours.
+    pub fn record_trade(
+        &mut self,
+        timestamp: i64,
+        base_amount: u64,
+        quote_amount: u64,
+        price: u64,
+    ) {
+        self.total_base_volume = (self.total_base_volume() + u128::from(base_amount)).to_le_bytes();
+        self.total_quote_volume =
+            (self.total_quote_volume() + u128::from(quote_amount)).to_le_bytes();
+        self.trade_count += 1;
+        self.last_trade_price = price;
+        self.last_trade_timestamp = timestamp;
+
+        let hour = (timestamp / MARKET_STATS_BUCKET_DURATION) as u64;
+        let bucket = &mut self.buckets[hour as usize % MARKET_STATS_BUCKETS];
+
+        if bucket.hour != hour || bucket.trade_count == 0 {
+            *bucket = StatsBucket {
+                hour,
+                base_volume: 0,
+                quote_volume: 0,
+                trade_count: 0,
+                open_price: price,
+                high_price: price,
+                low_price: price,
+                close_price: price,
+            };
+        }
+
+        bucket.base_volume += base_amount;
+        bucket.quote_volume += quote_amount;
+        bucket.trade_count += 1;
+        bucket.high_price = bucket.high_price.max(price);
+        bucket.low_price = bucket.low_price.min(price);
+        bucket.close_price = price;
+    }
+
+    /// Returns the (base volume, quote volume, trade count) of the last 24 hours before
+    /// `timestamp`, the current hour included.
+    pub fn get_rolling_volume(&self, timestamp: i64) -> (u64, u64, u64) {
+        let hour = (timestamp / MARKET_STATS_BUCKET_DURATION) as u64;
+
+        self.buckets
+            .iter()
+            .filter(|bucket| {
+                bucket.trade_count > 0
+                    && bucket.hour <= hour
+                    && hour - bucket.hour < MARKET_STATS_BUCKETS as u64
+            })
+            .fold(
+                (0, 0, 0),
+                |(base_volume, quote_volume, trade_count), bucket| {
+                    (
+                        base_volume + bucket.base_volume,
+                        quote_volume + bucket.quote_volume,
+                        trade_count + bucket.trade_count,
+                    )
+                },
+            )
+    }
+
+    /// Lamports of the account that can reward keepers, keeping the account itself rent exempt.
+    pub fn get_available_lamports(lamports: u64) -> Result<u64> {
+        let rent = Rent::get()?.minimum_balance(8 + MarketStats::INIT_SPACE);
+
+        Ok(lamports.saturating_sub(rent))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use joelana_test_utils::assert_layout;
+
+    fn new_stats() -> MarketStats {
+        let mut stats = MarketStats::zeroed();
+
+        stats
+            .initialize(0, Pubkey::default(), 1_000, VOLUME_WINDOW_DURATION)
+            .unwrap();
+
+        stats
+    }
+
+    #[test]
+    fn rollover() {
+        let mut stats = new_stats();
+
+        // +10%, then -10%
+        stats.rollover(2, 1_100, 500, 5).unwrap();
+
+        assert_eq!(stats.volatility_bps, 200);
+        assert_eq!(stats.quote_volume, 500);
+        assert_eq!(stats.swap_fees, 5);
+
+        stats.rollover(4, 990, 0, 0).unwrap();
+
+        assert_eq!(stats.volatility_bps, 360);
+        assert_eq!(stats.window, 4);
+        assert_eq!(stats.price, 990);
+        assert_eq!(stats.quote_volume, 0);
+    }
+
+    #[test]
+    fn rollover_within_window() {
+        let mut stats = new_stats();
+
+        assert_eq!(
+            stats.rollover(1, 1_100, 0, 0).unwrap_err(),
+            TokenMillError::RolloverTooEarly.into()
+        );
+    }
+
+    #[test]
+    fn record_trade() {
+        let mut stats = new_stats();
+
+        stats.record_trade(3_600, 100, 1_000, 10);
+        stats.record_trade(3_700, 50, 600, 12);
+        stats.record_trade(7_200, 10, 110, 11);
+
+        assert_eq!(stats.total_base_volume(), 160);
+        assert_eq!(stats.total_quote_volume(), 1_710);
+        assert_eq!(stats.trade_count, 3);
+        assert_eq!(stats.last_trade_price, 11);
+        assert_eq!(stats.last_trade_timestamp, 7_200);
+
+        let bucket = stats.buckets[1];
+
+        assert_eq!(bucket.hour, 1);
+        assert_eq!(bucket.base_volume, 150);
+        assert_eq!(bucket.quote_volume, 1_600);
+        assert_eq!(bucket.trade_count, 2);
+        assert_eq!(
+            (
+                bucket.open_price,
+                bucket.high_price,
+                bucket.low_price,
+                bucket.close_price
+            ),
+            (10, 12, 10, 12)
+        );
+
+        assert_eq!(stats.get_rolling_volume(7_200), (160, 1_710, 3));
+        // The bucket of hour 1 is out of the last 24 hours
+        assert_eq!(stats.get_rolling_volume(25 * 3_600), (10, 110, 1));
+    }
+
+    #[test]
+    fn record_trade_after_wrap_around() {
+        let mut stats = new_stats();
+
+        stats.record_trade(3_600, 100, 1_000, 10);
+        stats.record_trade(25 * 3_600, 10, 110, 11);
+
+        let bucket = stats.buckets[1];
+
+        assert_eq!(bucket.hour, 25);
+        assert_eq!(bucket.base_volume, 10);
+        assert_eq!(bucket.open_price, 11);
+        assert_eq!(stats.get_rolling_volume(25 * 3_600), (10, 110, 1));
+    }
+
+    #[test]
+    fn layout() {
+        assert_layout!(StatsBucket, 64, {
+            hour: 0,
+            base_volume: 8,
+            quote_volume: 16,
+            trade_count: 24,
+            open_price: 32,
+            high_price: 40,
+            low_price: 48,
+            close_price: 56,
+        });
+
+        assert_layout!(MarketStats, 1_680, {
+            market: 0,
+            window: 32,
+            price: 40,
+            quote_volume: 48,
+            swap_fees: 56,
+            volatility_bps: 64,
+            total_keeper_rewards: 72,
+            total_base_volume: 80,
+            total_quote_volume: 96,
+            trade_count: 112,
+            last_trade_price: 120,
+            last_trade_timestamp: 128,
+            buckets: 136,
+            bump: 1_672,
+        });
+    }
+}
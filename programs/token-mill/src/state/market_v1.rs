@@ -314,64 +314,6 @@ impl Market {
         Ok((base_amount_swapped, quote_amount_swapped))
     }
 
-    pub fn check_threshold_and_migrate(&self, net_sol: u64) -> Result<()> {
-        if net_sol < 60000 || net_sol > 63000 {
-            return Err(TokenMillError::NetSOLThreshold.into());
-        }
-
-        if self.circulating_supply() >= (self.total_supply * 80) / 100 {
-            self.migrate_to_raydium()?;
-        }
-
-        Ok(())
-    }
-
-    pub fn migrate_to_raydium(&mut self) -> Result<()> {
-        // Logic to lock liquidity and disable bonding curve
-        self.lock_liquidity()?;
-        self.disable_bonding_curve()?;
-        self.payout_creator(200)?;
-        
-        // Optionally, set referral_enabled to false after migration
-        self.fees.referral_enabled = false;
-
-        Ok(())
-    }
-
-    pub fn lock_liquidity(&mut self) -> Result<()> {
-        // Logic to lock liquidity
-        // This could involve transferring tokens to a vault or changing state variables
-        // Assuming we have a vault account where liquidity will be locked
-        let vault_account = self.get_vault_account()?;
-        
-        // Transfer liquidity to vault (pseudo code)
-        // transfer_tokens_to_vault(vault_account, self.base_reserve)?;
-
-        // Mark liquidity as locked
-        self.base_reserve = 0; // or another mechanism to indicate locked state
-
-        Ok(())
-    }
-
-    pub fn disable_bonding_curve(&mut self) -> Result<()> {
-        // Logic to disable the bonding curve
-        // This could involve setting a flag or changing parameters that govern the bonding curve
-        self.width_scaled = 0; // Disable bonding curve by setting width to zero
-
-        Ok(())
-    }
-
-    pub fn payout_creator(&self, amount: u64) -> Result<()> {
-        // Logic to payout creator
-        // This will involve transferring SOL or tokens to the creator's address
-        let creator_account = self.creator; // Assuming creator is a Pubkey
-
-        // Transfer the specified amount to the creator (pseudo code)
-        // transfer_tokens(creator_account, amount)?;
-
-        Ok(())
-    }
-
     pub fn distribute_fee(&mut self, swap_fee: u64) -> Result<(u64, u64, u64, u64)> {
         let mut creator_fee = 0;
         let mut staking_fee = 0;
@@ -412,12 +354,6 @@ impl Market {
         Ok(())
     }
 
-    // Helper function to get the vault account (pseudo code)
-    fn get_vault_account(&self) -> Result<Pubkey> {
-        // Logic to derive or fetch the vault account
-        Ok(Pubkey::new_unique()) // Placeholder for actual vault account logic
-    }
-
 }
 
 #[cfg(test)]
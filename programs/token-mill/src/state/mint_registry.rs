@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+pub const MINT_REGISTRY_PDA_SEED: &str = "mint_registry";
+
+/// Global (config-independent) record of the market trading a given base mint.
+/// Its creation fails if the base mint is already registered, so competing curves can't coexist.
+/// Secondary quote markets opened by the creator are only counted.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct MintRegistry {
+    pub bump: u8,
+    pub base_token_mint: Pubkey,
+    pub active_market: Pubkey,
+    pub secondary_market_count: u32,
+}
+
+impl MintRegistry {
+    pub fn initialize(&mut self, bump: u8, base_token_mint: Pubkey, market: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.base_token_mint = base_token_mint;
+        self.active_market = market;
+        self.secondary_market_count = 0;
+
+        Ok(())
+    }
+}
@@ -1,13 +1,61 @@
+pub mod buy_queue;
+pub mod buyback_order;
+pub mod claim_delegate;
 pub mod config;
+pub mod creator_activity;
+pub mod creator_multisig;
+pub mod denylist;
+pub mod fee_exemption;
+pub mod fee_reconciliation;
+pub mod gas_vault;
+pub mod graduation_attestation;
+pub mod launch_purchase;
 pub mod market;
+pub mod market_oracle;
+pub mod market_stats;
+pub mod mint_registry;
+pub mod pending_config_change;
+pub mod price_feed;
+pub mod protocol_fee_converter;
+pub mod protocol_fee_split;
+pub mod quote_airdrop;
 pub mod quote_token_badge;
 pub mod referral;
+pub mod revenue_report;
+pub mod risk_params;
+pub mod sell_order;
 pub mod staking;
+pub mod swap_receipt_tree;
+pub mod trader_volume;
 pub mod vesting;
 
+pub use buy_queue::*;
+pub use buyback_order::*;
+pub use claim_delegate::*;
 pub use config::*;
+pub use creator_activity::*;
+pub use creator_multisig::*;
+pub use denylist::*;
+pub use fee_exemption::*;
+pub use fee_reconciliation::*;
+pub use gas_vault::*;
+pub use graduation_attestation::*;
+pub use launch_purchase::*;
 pub use market::*;
+pub use market_oracle::*;
+pub use market_stats::*;
+pub use mint_registry::*;
+pub use pending_config_change::*;
+pub use price_feed::*;
+pub use protocol_fee_converter::*;
+pub use protocol_fee_split::*;
+pub use quote_airdrop::*;
 pub use quote_token_badge::*;
 pub use referral::*;
+pub use revenue_report::*;
+pub use risk_params::*;
+pub use sell_order::*;
 pub use staking::*;
+pub use swap_receipt_tree::*;
+pub use trader_volume::*;
 pub use vesting::*;
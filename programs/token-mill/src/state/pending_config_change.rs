@@ -0,0 +1,331 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{
+        MAX_BPS, MAX_CONFIG_CHANGE_DELAY, MAX_PROTOCOL_FEE_RECIPIENTS, MAX_REFERRAL_TIERS,
+        MAX_SWAP_FEE_BPS,
+    },
+    errors::TokenMillError,
+    state::{
+        MigrationTarget, ProtocolFeeShare, ProtocolFeeSplit, ReferralTier, TokenMillConfig,
+        UsdGraduation,
+    },
+};
+
+pub const PENDING_CONFIG_CHANGE_PDA_SEED: &str = "pending_config_change";
+
+/// Sensitive config change queued by the authority, executable once `config_change_delay` has
+/// elapsed. There is one pending change per kind, see `ConfigChange::kind`.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PendingConfigChange {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub change: ConfigChange,
+    pub executable_at: i64,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub enum ConfigChange {
+    DefaultFeeShares {
+        default_protocol_fee_share: u16,
+        referral_fee_share: u16,
+    },
+    ProtocolFeeRecipient {
+        protocol_fee_recipient: Pubkey,
+    },
+    GraduationThresholds {
+        graduation_quote_threshold: u64,
+        graduation_supply_bps: u16,
+    },
+    ConfigChangeDelay {
+        config_change_delay: i64,
+    },
+    /// Applied to the protocol fee split of the config rather than to the config, see
+    /// `execute_config_change`. Only the first `recipient_count` recipients are set.
+    ProtocolFeeSplit {
+        recipients: [ProtocolFeeShare; MAX_PROTOCOL_FEE_RECIPIENTS],
+        recipient_count: u8,
+    },
+    ReferralParams {
+        referral_fee_share: u16,
+        referral_enabled: bool,
+    },
+    UsdGraduation {
+        usd_graduation: UsdGraduation,
+    },
+    MigrationTarget {
+        migration_target: MigrationTarget,
+    },
+    GraduationHookProgram {
+        graduation_hook_program: Option<Pubkey>,
+    },
+    SwapFeeBounds {
+        min_swap_fee_bps: u16,
+        max_swap_fee_bps: u16,
+    },
+    /// Only the first `tier_count` tiers are set.
+    ReferralTiers {
+        tiers: [ReferralTier; MAX_REFERRAL_TIERS],
+        tier_count: u8,
+    },
+    PolicyProgram {
+        policy_program: Option<Pubkey>,
+    },
+    FeeHookProgram {
+        fee_hook_program: Option<Pubkey>,
+    },
+    /// Applied to the denylist of the config rather than to the config, see
+    /// `execute_config_change`. One wallet is added or removed per change.
+    DenylistUpdate {
+        wallet: Pubkey,
+        denied: bool,
+    },
+}
+
+impl ConfigChange {
+    /// Seed of the pending change account.
+    pub fn kind(&self) -> u8 {
+        match self {
+            ConfigChange::DefaultFeeShares { .. } => 0,
+            ConfigChange::ProtocolFeeRecipient { .. } => 1,
+            ConfigChange::GraduationThresholds { .. } => 2,
+            ConfigChange::ConfigChangeDelay { .. } => 3,
+            ConfigChange::ProtocolFeeSplit { .. } => 4,
+            ConfigChange::ReferralParams { .. } => 5,
+            ConfigChange::UsdGraduation { .. } => 6,
+            ConfigChange::MigrationTarget { .. } => 7,
+            ConfigChange::GraduationHookProgram { .. } => 8,
+            ConfigChange::SwapFeeBounds { .. } => 9,
+            ConfigChange::ReferralTiers { .. } => 10,
+            ConfigChange::PolicyProgram { .. } => 11,
+            ConfigChange::FeeHookProgram { .. } => 12,
+            ConfigChange::DenylistUpdate { .. } => 13,
+        }
+    }
+
+    /// Queues the recipients of the protocol fee split, see `ProtocolFeeSplit::set_recipients`.
+    pub fn protocol_fee_split(recipients: &[ProtocolFeeShare]) -> Result<Self> {
+        require!(
+            recipients.len() <= MAX_PROTOCOL_FEE_RECIPIENTS,
+            TokenMillError::InvalidProtocolFeeSplit
+        );
+
+        let mut shares = [ProtocolFeeShare {
+            recipient: Pubkey::default(),
+            weight_bps: 0,
+        }; MAX_PROTOCOL_FEE_RECIPIENTS];
+        shares[..recipients.len()].copy_from_slice(recipients);
+
+        Ok(ConfigChange::ProtocolFeeSplit {
+            recipients: shares,
+            recipient_count: recipients.len() as u8,
+        })
+    }
+
+    /// Queues the referral tiers of the config, see `TokenMillConfig::check_referral_tiers`.
+    pub fn referral_tiers(referral_tiers: &[ReferralTier]) -> Result<Self> {
+        require!(
+            referral_tiers.len() <= MAX_REFERRAL_TIERS,
+            TokenMillError::InvalidReferralTiers
+        );
+
+        let mut tiers = [ReferralTier {
+            min_referred_volume: 0,
+            referral_fee_share: 0,
+        }; MAX_REFERRAL_TIERS];
+        tiers[..referral_tiers.len()].copy_from_slice(referral_tiers);
+
+        Ok(ConfigChange::ReferralTiers {
+            tiers,
+            tier_count: referral_tiers.len() as u8,
+        })
+    }
+
+    /// Referral tiers of a `ReferralTiers` change, None for other kinds.
+    pub fn get_referral_tiers(&self) -> Option<Vec<ReferralTier>> {
+        match self {
+            ConfigChange::ReferralTiers { tiers, tier_count } => {
+                Some(tiers[..usize::from(*tier_count).min(MAX_REFERRAL_TIERS)].to_vec())
+            }
+            _ => None,
+        }
+    }
+
+    /// Recipients of a `ProtocolFeeSplit` change, None for other kinds.
+    pub fn get_protocol_fee_recipients(&self) -> Option<Vec<ProtocolFeeShare>> {
+        match self {
+            ConfigChange::ProtocolFeeSplit {
+                recipients,
+                recipient_count,
+            } => Some(
+                recipients[..usize::from(*recipient_count).min(MAX_PROTOCOL_FEE_RECIPIENTS)]
+                    .to_vec(),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn check(&self) -> Result<()> {
+        match *self {
+            ConfigChange::DefaultFeeShares {
+                default_protocol_fee_share,
+                referral_fee_share,
+            } => require!(
+                u64::from(default_protocol_fee_share) <= MAX_BPS
+                    && u64::from(referral_fee_share) <= MAX_BPS,
+                TokenMillError::InvalidFeeShare
+            ),
+            ConfigChange::ProtocolFeeRecipient { .. } => {}
+            ConfigChange::GraduationThresholds {
+                graduation_supply_bps,
+                ..
+            } => require_gte!(
+                MAX_BPS,
+                u64::from(graduation_supply_bps),
+                TokenMillError::InvalidGraduationThresholds
+            ),
+            ConfigChange::ConfigChangeDelay {
+                config_change_delay,
+            } => require!(
+                (0..=MAX_CONFIG_CHANGE_DELAY).contains(&config_change_delay),
+                TokenMillError::InvalidConfigChange
+            ),
+            ConfigChange::ProtocolFeeSplit {
+                recipient_count, ..
+            } => {
+                require!(
+                    usize::from(recipient_count) <= MAX_PROTOCOL_FEE_RECIPIENTS,
+                    TokenMillError::InvalidProtocolFeeSplit
+                );
+
+                if let Some(recipients) = self.get_protocol_fee_recipients() {
+                    ProtocolFeeSplit::check_recipients(&recipients)?;
+                }
+            }
+            ConfigChange::ReferralParams {
+                referral_fee_share, ..
+            } => require_gte!(
+                MAX_BPS,
+                u64::from(referral_fee_share),
+                TokenMillError::InvalidFeeShare
+            ),
+            ConfigChange::UsdGraduation { usd_graduation } => usd_graduation.check()?,
+            ConfigChange::MigrationTarget { .. }
+            | ConfigChange::GraduationHookProgram { .. }
+            | ConfigChange::PolicyProgram { .. }
+            | ConfigChange::FeeHookProgram { .. }
+            | ConfigChange::DenylistUpdate { .. } => {}
+            ConfigChange::SwapFeeBounds {
+                min_swap_fee_bps,
+                max_swap_fee_bps,
+            } => require!(
+                min_swap_fee_bps <= max_swap_fee_bps
+                    && u64::from(max_swap_fee_bps) <= MAX_SWAP_FEE_BPS,
+                TokenMillError::InvalidSwapFee
+            ),
+            ConfigChange::ReferralTiers { tier_count, .. } => {
+                require!(
+                    usize::from(tier_count) <= MAX_REFERRAL_TIERS,
+                    TokenMillError::InvalidReferralTiers
+                );
+
+                if let Some(referral_tiers) = self.get_referral_tiers() {
+                    TokenMillConfig::check_referral_tiers(&referral_tiers)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn apply(&self, config: &mut TokenMillConfig) -> Result<()> {
+        self.check()?;
+
+        match *self {
+            ConfigChange::DefaultFeeShares {
+                default_protocol_fee_share,
+                referral_fee_share,
+            } => {
+                config.default_protocol_fee_share = default_protocol_fee_share;
+                config.referral_fee_share = referral_fee_share;
+            }
+            ConfigChange::ProtocolFeeRecipient {
+                protocol_fee_recipient,
+            } => config.protocol_fee_recipient = protocol_fee_recipient,
+            ConfigChange::GraduationThresholds {
+                graduation_quote_threshold,
+                graduation_supply_bps,
+            } => {
+                config.graduation_quote_threshold = graduation_quote_threshold;
+                config.graduation_supply_bps = graduation_supply_bps;
+            }
+            ConfigChange::ConfigChangeDelay {
+                config_change_delay,
+            } => config.config_change_delay = config_change_delay,
+            ConfigChange::ProtocolFeeSplit { .. } | ConfigChange::DenylistUpdate { .. } => {}
+            ConfigChange::ReferralParams {
+                referral_fee_share,
+                referral_enabled,
+            } => {
+                config.referral_fee_share = referral_fee_share;
+                config.referral_enabled = referral_enabled;
+            }
+            ConfigChange::UsdGraduation { usd_graduation } => {
+                config.usd_graduation = usd_graduation
+            }
+            ConfigChange::MigrationTarget { migration_target } => {
+                config.migration_target = migration_target
+            }
+            ConfigChange::GraduationHookProgram {
+                graduation_hook_program,
+            } => config.graduation_hook_program = graduation_hook_program,
+            ConfigChange::SwapFeeBounds {
+                min_swap_fee_bps,
+                max_swap_fee_bps,
+            } => {
+                config.min_swap_fee_bps = min_swap_fee_bps;
+                config.max_swap_fee_bps = max_swap_fee_bps;
+            }
+            ConfigChange::ReferralTiers { .. } => {
+                config.referral_tiers = self.get_referral_tiers().unwrap_or_default()
+            }
+            ConfigChange::PolicyProgram { policy_program } => {
+                config.policy_program = policy_program
+            }
+            ConfigChange::FeeHookProgram { fee_hook_program } => {
+                config.fee_hook_program = fee_hook_program
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl PendingConfigChange {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        config: Pubkey,
+        change: ConfigChange,
+        executable_at: i64,
+    ) -> Result<()> {
+        change.check()?;
+
+        self.bump = bump;
+        self.config = config;
+        self.change = change;
+        self.executable_at = executable_at;
+
+        Ok(())
+    }
+
+    pub fn execute(&self, config: &mut TokenMillConfig, current_time: i64) -> Result<()> {
+        require!(
+            current_time >= self.executable_at,
+            TokenMillError::ConfigChangeLocked
+        );
+
+        self.change.apply(config)
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+pub const PRICE_FEED_PDA_SEED: &str = "price_feed";
+
+/// Price of a market in a standardized oracle layout, for integrations that only read common
+/// oracle formats. Prices are in quote per base token, with `expo` decimals.
+/// A crank keeps the feed up to date, consumers should check `publish_slot` for staleness.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct PriceFeed {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub base_token_mint: Pubkey,
+    pub quote_token_mint: Pubkey,
+
+    pub expo: i32,
+    /// Mid price of the curve.
+    pub price: i64,
+    /// Half of the bid-ask spread.
+    pub conf: u64,
+    pub publish_time: i64,
+    pub publish_slot: u64,
+}
+
+impl PriceFeed {
+    /// Curve prices are scaled by `SCALE`.
+    pub const EXPO: i32 = -10;
+
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        base_token_mint: Pubkey,
+        quote_token_mint: Pubkey,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.base_token_mint = base_token_mint;
+        self.quote_token_mint = quote_token_mint;
+        self.expo = Self::EXPO;
+
+        Ok(())
+    }
+
+    pub fn update(&mut self, bid_price: u64, ask_price: u64, clock: &Clock) -> Result<()> {
+        let conf = (ask_price - bid_price) / 2;
+
+        self.price = i64::try_from(bid_price + conf)?;
+        self.conf = conf;
+        self.publish_time = clock.unix_timestamp;
+        self.publish_slot = clock.slot;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+pub const PROTOCOL_FEE_CONVERTER_PDA_SEED: &str = "protocol_fee_converter";
+
+/// Converts the protocol fees of a config into the protocol token by buying it on `market`, see
+/// `convert_protocol_fees`. The protocol fee recipient opts in by approving the converter as
+/// delegate of its quote token account, the amount approved bounding what cranks can spend.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ProtocolFeeConverter {
+    pub bump: u8,
+    pub config: Pubkey,
+    /// Market of the protocol token, its quote token being the one of the converted fees.
+    pub market: Pubkey,
+    /// Wallet receiving the protocol tokens bought.
+    pub treasury: Pubkey,
+    /// Highest ask price a conversion can leave the market at, scaled by `SCALE`.
+    pub max_price: u64,
+
+    pub total_quote_amount_converted: u64,
+    pub total_base_amount_bought: u64,
+}
+
+impl ProtocolFeeConverter {
+    pub fn initialize(&mut self, bump: u8, config: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.config = config;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, quote_amount: u64, base_amount: u64) {
+        self.total_quote_amount_converted += quote_amount;
+        self.total_base_amount_bought += base_amount;
+    }
+}
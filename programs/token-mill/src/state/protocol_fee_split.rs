@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{MAX_BPS, MAX_PROTOCOL_FEE_RECIPIENTS},
+    errors::TokenMillError,
+};
+
+pub const PROTOCOL_FEE_SPLIT_PDA_SEED: &str = "protocol_fee_split";
+
+/// Splits the protocol fees of a config between several wallets, see `distribute_protocol_fees`.
+/// The protocol fee recipient opts in by approving the split as delegate of its quote token
+/// account, the amount approved bounding what cranks can distribute.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ProtocolFeeSplit {
+    pub bump: u8,
+    pub config: Pubkey,
+    #[max_len(MAX_PROTOCOL_FEE_RECIPIENTS)]
+    pub recipients: Vec<ProtocolFeeShare>,
+
+    pub total_distributed: u64,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub struct ProtocolFeeShare {
+    pub recipient: Pubkey,
+    pub weight_bps: u16,
+}
+
+impl ProtocolFeeSplit {
+    pub fn initialize(&mut self, bump: u8, config: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.config = config;
+
+        Ok(())
+    }
+
+    pub fn set_recipients(&mut self, recipients: Vec<ProtocolFeeShare>) -> Result<()> {
+        Self::check_recipients(&recipients)?;
+
+        self.recipients = recipients;
+
+        Ok(())
+    }
+
+    /// Recipients must be distinct, with non-zero weights adding up to `MAX_BPS`.
+    pub fn check_recipients(recipients: &[ProtocolFeeShare]) -> Result<()> {
+        require!(
+            !recipients.is_empty() && recipients.len() <= MAX_PROTOCOL_FEE_RECIPIENTS,
+            TokenMillError::InvalidProtocolFeeSplit
+        );
+
+        for (i, share) in recipients.iter().enumerate() {
+            require!(
+                share.weight_bps > 0
+                    && !recipients[..i]
+                        .iter()
+                        .any(|previous_share| previous_share.recipient == share.recipient),
+                TokenMillError::InvalidProtocolFeeSplit
+            );
+        }
+
+        require_eq!(
+            recipients
+                .iter()
+                .map(|share| u64::from(share.weight_bps))
+                .sum::<u64>(),
+            MAX_BPS,
+            TokenMillError::InvalidProtocolFeeSplit
+        );
+
+        Ok(())
+    }
+
+    /// Amounts of each recipient, in order. The rounding dust goes to the first recipient.
+    pub fn get_amounts(&self, amount: u64) -> Result<Vec<u64>> {
+        let mut amounts = self
+            .recipients
+            .iter()
+            .map(|share| {
+                Ok(u64::try_from(
+                    u128::from(amount) * u128::from(share.weight_bps) / u128::from(MAX_BPS),
+                )?)
+            })
+            .collect::<Result<Vec<u64>>>()?;
+
+        amounts[0] += amount - amounts.iter().sum::<u64>();
+
+        Ok(amounts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn share(name: u8, weight_bps: u16) -> ProtocolFeeShare {
+        ProtocolFeeShare {
+            recipient: Pubkey::new_from_array([name; 32]),
+            weight_bps,
+        }
+    }
+
+    fn new_split(recipients: Vec<ProtocolFeeShare>) -> Result<ProtocolFeeSplit> {
+        let mut protocol_fee_split = ProtocolFeeSplit {
+            bump: 0,
+            config: Pubkey::default(),
+            recipients: Vec::new(),
+            total_distributed: 0,
+        };
+
+        protocol_fee_split.set_recipients(recipients)?;
+
+        Ok(protocol_fee_split)
+    }
+
+    #[test]
+    fn get_amounts() {
+        let protocol_fee_split = new_split(vec![share(1, 7_000), share(2, 3_000)]).unwrap();
+
+        assert_eq!(protocol_fee_split.get_amounts(1_000).unwrap(), [700, 300]);
+        assert_eq!(protocol_fee_split.get_amounts(1_001).unwrap(), [701, 300]);
+        assert_eq!(protocol_fee_split.get_amounts(0).unwrap(), [0, 0]);
+    }
+
+    #[test]
+    fn set_invalid_recipients() {
+        for recipients in [
+            vec![],
+            vec![share(1, 7_000), share(2, 2_000)],
+            vec![share(1, 10_000), share(2, 0)],
+            vec![share(1, 5_000), share(1, 5_000)],
+            vec![share(1, 1_250); MAX_PROTOCOL_FEE_RECIPIENTS + 1],
+        ] {
+            assert_eq!(
+                new_split(recipients).unwrap_err(),
+                TokenMillError::InvalidProtocolFeeSplit.into()
+            );
+        }
+    }
+}
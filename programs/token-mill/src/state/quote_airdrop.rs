@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+pub const QUOTE_AIRDROP_PDA_SEED: &str = "quote_airdrop";
+pub const AIRDROP_CLAIM_PDA_SEED: &str = "airdrop_claim";
+
+/// Pending fees of the market funding an airdrop, the unclaimed tokens return to them on closure.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, InitSpace, Copy, Clone, PartialEq)]
+pub enum QuoteAirdropSource {
+    CreatorFees,
+    /// Holder snapshot of the sell taxes, see `publish_holder_rewards`.
+    HolderRewards,
+}
+
+/// Quote tokens set aside from the pending fees of a market, claimable by the leaves of
+/// a Merkle tree. The tokens stay in the quote vault of the market until claimed.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct QuoteAirdrop {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub merkle_root: [u8; 32],
+
+    pub total_amount: u64,
+    pub amount_claimed: u64,
+
+    pub source: QuoteAirdropSource,
+}
+
+impl QuoteAirdrop {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        merkle_root: [u8; 32],
+        total_amount: u64,
+        source: QuoteAirdropSource,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.merkle_root = merkle_root;
+        self.total_amount = total_amount;
+        self.source = source;
+
+        Ok(())
+    }
+
+    pub fn get_amount_left(&self) -> u64 {
+        self.total_amount - self.amount_claimed
+    }
+}
+
+/// Receipt of a claim, preventing the same leaf from being claimed twice.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct AirdropClaim {
+    pub bump: u8,
+    pub airdrop: Pubkey,
+    pub claimant: Pubkey,
+    pub amount: u64,
+}
@@ -13,12 +13,15 @@ pub enum QuoteTokenBadgeStatus {
 pub struct QuoteTokenBadge {
     pub bump: u8,
     pub status: QuoteTokenBadgeStatus,
+    /// Pyth feed of the USD price of the quote token, see `UsdGraduation`.
+    pub price_feed_id: Option<[u8; 32]>,
 }
 
 impl QuoteTokenBadge {
     pub fn initialize(&mut self, bump: u8) -> Result<()> {
         self.bump = bump;
         self.status = QuoteTokenBadgeStatus::Enabled;
+        self.price_feed_id = None;
 
         Ok(())
     }
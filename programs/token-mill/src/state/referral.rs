@@ -1,5 +1,11 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constant::{MAX_BPS, MAX_REFERRAL_CODE_FEE_SHARE},
+    errors::TokenMillError,
+    state::TokenMillConfig,
+};
+
 pub const REFERRAL_ACCOUNT_PDA_SEED: &str = "referral";
 
 #[account]
@@ -8,6 +14,11 @@ pub struct ReferralAccount {
     pub bump: u8,
     pub config: Pubkey,
     pub referrer: Pubkey,
+    /// Volume of the swaps referred with the account as stats account
+    pub referred_volume: u64,
+    /// Share of the referral tier reached at the last claim, see `TokenMillConfig::referral_tiers`.
+    /// Swaps apply the tier of the current referred volume.
+    pub tier_fee_share: u16,
 }
 
 impl ReferralAccount {
@@ -17,4 +28,201 @@ impl ReferralAccount {
         self.referrer = referrer;
         Ok(())
     }
+
+    /// Referral fee share of the swaps referred to the account, picked from the referral tiers of
+    /// the config by the volume referred so far and never below the share of the market.
+    pub fn get_referral_fee_share(
+        &self,
+        config: &TokenMillConfig,
+        market_referral_fee_share: u16,
+    ) -> u16 {
+        config
+            .get_referral_tier_fee_share(self.referred_volume)
+            .max(market_referral_fee_share)
+    }
+
+    pub fn record(&mut self, quote_amount: u64) {
+        self.referred_volume = self.referred_volume.saturating_add(quote_amount);
+    }
+
+    /// Snapshots the tier reached for the claim event.
+    pub fn update_tier(&mut self, config: &TokenMillConfig) {
+        self.tier_fee_share = config.get_referral_tier_fee_share(self.referred_volume);
+    }
+}
+
+pub const REFERRAL_CODE_PDA_SEED: &str = "referral_code";
+
+/// Human readable code registered by a referrer, e.g. "alice", resolving to its referral account.
+/// Codes are lowercase alphanumeric or underscores, zero padded to 32 bytes.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ReferralCode {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub code: [u8; 32],
+    pub referrer: Pubkey,
+    pub referral_account: Pubkey,
+}
+
+impl ReferralCode {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        config: Pubkey,
+        code: [u8; 32],
+        referrer: Pubkey,
+        referral_account: Pubkey,
+    ) -> Result<()> {
+        let length = code.iter().position(|&c| c == 0).unwrap_or(code.len());
+
+        require!(
+            length > 0
+                && code[..length]
+                    .iter()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || *c == b'_')
+                && code[length..].iter().all(|&c| c == 0),
+            TokenMillError::InvalidReferralCode
+        );
+
+        self.bump = bump;
+        self.config = config;
+        self.code = code;
+        self.referrer = referrer;
+        self.referral_account = referral_account;
+
+        Ok(())
+    }
+
+    /// Referral codes can lead the remaining accounts of a swap, ahead of the accounts of the
+    /// policy program.
+    pub fn is_referral_code(account_info: &AccountInfo) -> bool {
+        account_info.owner == &crate::ID
+            && account_info
+                .try_borrow_data()
+                .is_ok_and(|data| data.starts_with(&ReferralCode::DISCRIMINATOR))
+    }
+}
+
+pub const REFERRER_BINDING_PDA_SEED: &str = "referrer_binding";
+
+/// Referrer of a trader within a config, bound by the first referred swap of the trader passing
+/// the binding. Frontends read it to credit the bound referral token account on the later swaps
+/// of the trader that don't name a referrer.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct ReferrerBinding {
+    pub bump: u8,
+    pub config: Pubkey,
+    pub trader: Pubkey,
+    /// Default key until bound.
+    pub referral_token_account: Pubkey,
+}
+
+impl ReferrerBinding {
+    pub fn initialize(&mut self, bump: u8, config: Pubkey, trader: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.config = config;
+        self.trader = trader;
+
+        Ok(())
+    }
+
+    pub fn is_bound(&self) -> bool {
+        self.referral_token_account != Pubkey::default()
+    }
+
+    /// Binds `referral_token_account` if no referrer is bound yet, returning whether it did.
+    pub fn bind(&mut self, referral_token_account: Pubkey) -> bool {
+        if self.is_bound() {
+            return false;
+        }
+
+        self.referral_token_account = referral_token_account;
+
+        true
+    }
+}
+
+pub const MARKET_REFERRAL_ACCRUAL_PDA_SEED: &str = "market_referral_accrual";
+
+/// Referral fees of a market held back for a single referral token account, until the
+/// referred volume reaches the minimum set by the market creator.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct MarketReferralAccrual {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub referral_token_account: Pubkey,
+    pub referred_volume: u64,
+    pub pending_fees: u64,
+}
+
+impl MarketReferralAccrual {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, referral_token_account: Pubkey) {
+        self.bump = bump;
+        self.market = market;
+        self.referral_token_account = referral_token_account;
+    }
+
+    pub fn record(&mut self, quote_amount: u64, referral_fee: u64) {
+        self.referred_volume = self.referred_volume.saturating_add(quote_amount);
+        self.pending_fees += referral_fee;
+    }
+}
+
+pub const MARKET_REFERRAL_CODE_PDA_SEED: &str = "market_referral_code";
+
+/// Referral code registered by the creator of a market for a referral token account.
+/// Swaps referred with the code pay a share of their creator fee to the referral token account,
+/// on top of the referral fee of the config.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct MarketReferralCode {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub code: [u8; 32],
+    pub referral_token_account: Pubkey,
+    /// Share of the creator fee, capped by `MAX_REFERRAL_CODE_FEE_SHARE`.
+    pub creator_fee_share: u16,
+
+    pub referred_volume: u64,
+    pub referral_fees: u64,
+}
+
+impl MarketReferralCode {
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        market: Pubkey,
+        code: [u8; 32],
+        referral_token_account: Pubkey,
+        creator_fee_share: u16,
+    ) -> Result<()> {
+        require_gte!(
+            MAX_REFERRAL_CODE_FEE_SHARE,
+            u64::from(creator_fee_share),
+            TokenMillError::InvalidFeeShare
+        );
+
+        self.bump = bump;
+        self.market = market;
+        self.code = code;
+        self.referral_token_account = referral_token_account;
+        self.creator_fee_share = creator_fee_share;
+
+        Ok(())
+    }
+
+    /// Part of the creator fee of a swap redirected to the referral token account.
+    pub fn get_creator_fee_cut(&self, creator_fee: u64) -> Result<u64> {
+        Ok(u64::try_from(
+            u128::from(creator_fee) * u128::from(self.creator_fee_share) / u128::from(MAX_BPS),
+        )?)
+    }
+
+    pub fn record(&mut self, quote_amount: u64, referral_fee: u64) {
+        self.referred_volume = self.referred_volume.saturating_add(quote_amount);
+        self.referral_fees += referral_fee;
+    }
 }
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::REVENUE_EPOCH_DURATION,
+    errors::TokenMillError,
+    math::{mul_div, Rounding},
+    state::{TraderRebateParams, TraderVolume},
+};
+
+pub const REVENUE_REPORT_PDA_SEED: &str = "revenue_report";
+
+/// Fee totals of every swap of a config and quote token during one epoch.
+/// Swaps accrue into the report of the current epoch, and a crank finalizes it once the epoch is over.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RevenueReport {
+    pub bump: u8,
+    pub finalized: bool,
+    pub config: Pubkey,
+    pub quote_token_mint: Pubkey,
+    pub epoch: u64,
+
+    pub protocol_fees: u64,
+    pub creator_fees: u64,
+    pub staking_fees: u64,
+    pub referral_fees: u64,
+
+    /// Snapshot of the config parameters at the creation of the report.
+    pub trader_rebate_params: TraderRebateParams,
+    /// Protocol fees set aside for the traders, held by the quote token ATA of the report.
+    pub trader_rebate_fees: u64,
+    /// Sum of the volumes of the traders above the threshold.
+    pub trader_rebate_volume: u64,
+}
+
+impl RevenueReport {
+    pub fn get_epoch(timestamp: i64) -> u64 {
+        (timestamp / REVENUE_EPOCH_DURATION) as u64
+    }
+
+    pub fn initialize(
+        &mut self,
+        bump: u8,
+        config: Pubkey,
+        quote_token_mint: Pubkey,
+        epoch: u64,
+        trader_rebate_params: TraderRebateParams,
+    ) -> Result<()> {
+        self.bump = bump;
+        self.finalized = false;
+        self.config = config;
+        self.quote_token_mint = quote_token_mint;
+        self.epoch = epoch;
+        self.trader_rebate_params = trader_rebate_params;
+
+        Ok(())
+    }
+
+    pub fn record(
+        &mut self,
+        current_epoch: u64,
+        protocol_fee: u64,
+        creator_fee: u64,
+        staking_fee: u64,
+        referral_fee: u64,
+    ) -> Result<()> {
+        require_eq!(self.epoch, current_epoch, TokenMillError::InvalidEpoch);
+
+        self.protocol_fees += protocol_fee;
+        self.creator_fees += creator_fee;
+        self.staking_fees += staking_fee;
+        self.referral_fees += referral_fee;
+
+        Ok(())
+    }
+
+    /// Adds the swap to the volume of the trader, and returns the share of the protocol fee set
+    /// aside for the rebates. Record the net protocol fee with `record` afterwards.
+    pub fn record_trader_volume(
+        &mut self,
+        trader_volume: &mut TraderVolume,
+        quote_amount: u64,
+        protocol_fee: u64,
+    ) -> Result<u64> {
+        let volume_threshold = self.trader_rebate_params.volume_threshold;
+
+        let rebate_volume_before = trader_volume.volume.saturating_sub(volume_threshold);
+        trader_volume.volume += quote_amount;
+        let rebate_volume_after = trader_volume.volume.saturating_sub(volume_threshold);
+
+        self.trader_rebate_volume += rebate_volume_after - rebate_volume_before;
+
+        let trader_rebate_fee = self.trader_rebate_params.get_rebate_fee(protocol_fee)?;
+        self.trader_rebate_fees += trader_rebate_fee;
+
+        Ok(trader_rebate_fee)
+    }
+
+    /// Share of the set aside fees owed to the trader, pro rata of its volume above the threshold.
+    pub fn get_trader_rebate(&self, trader_volume: &TraderVolume) -> Result<u64> {
+        let rebate_volume = trader_volume
+            .volume
+            .saturating_sub(self.trader_rebate_params.volume_threshold);
+
+        if rebate_volume == 0 {
+            return Ok(0);
+        }
+
+        Ok(u64::try_from(
+            mul_div(
+                u128::from(self.trader_rebate_fees),
+                u128::from(rebate_volume),
+                u128::from(self.trader_rebate_volume),
+                Rounding::Down,
+            )
+            .ok_or(TokenMillError::MathError)?,
+        )?)
+    }
+
+    pub fn finalize(&mut self, current_epoch: u64) -> Result<()> {
+        require!(current_epoch > self.epoch, TokenMillError::EpochNotOver);
+
+        self.finalized = true;
+
+        Ok(())
+    }
+}
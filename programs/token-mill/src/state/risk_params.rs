@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::MAX_BPS, state::Market};
+
+pub const RISK_PARAMS_PDA_SEED: &str = "risk_params";
+
+/// Risk data of a market in a fixed layout, for lending and structured-product integrations.
+/// A crank keeps it up to date, see `push_risk_params`, consumers should check `update_slot` for
+/// staleness.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct RiskParams {
+    pub bump: u8,
+    pub market: Pubkey,
+
+    /// Share of the reserve a single swap can move, 0 meaning no limit.
+    pub max_swap_size_bps: u16,
+    /// Swaps are halted until this slot, see `pause_until`.
+    pub paused_until_slot: u64,
+    /// Swaps are halted until the market is unpaused, see `pause_market`.
+    pub paused: bool,
+    /// Half of the bid-ask spread in bps of the mid price, `MAX_BPS` while prices aren't set.
+    pub price_conf_bps: u16,
+    pub migrated: bool,
+    /// The whole base reserve has been bought, the market is ready to be migrated.
+    pub curve_completed: bool,
+
+    pub update_time: i64,
+    pub update_slot: u64,
+}
+
+impl RiskParams {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+
+        Ok(())
+    }
+
+    pub fn update(
+        &mut self,
+        market: &Market,
+        config_max_swap_size_bps: u16,
+        clock: &Clock,
+    ) -> Result<()> {
+        self.max_swap_size_bps = market.get_max_swap_size_bps(config_max_swap_size_bps);
+        self.paused_until_slot = market.paused_until_slot;
+        self.paused = market.paused != 0;
+        self.price_conf_bps = Self::get_price_conf_bps(market)?;
+        self.migrated = market.is_migrated();
+        self.curve_completed = market.are_prices_set() && market.base_reserve == 0;
+        self.update_time = clock.unix_timestamp;
+        self.update_slot = clock.slot;
+
+        Ok(())
+    }
+
+    fn get_price_conf_bps(market: &Market) -> Result<u16> {
+        if !market.are_prices_set() {
+            return Ok(MAX_BPS as u16);
+        }
+
+        let mid_price = market.get_mid_price()?;
+
+        if mid_price == 0 {
+            return Ok(MAX_BPS as u16);
+        }
+
+        let conf = (market.get_ask_price()? - market.get_bid_price()?) / 2;
+
+        Ok(u16::try_from(
+            (u128::from(conf) * u128::from(MAX_BPS) / u128::from(mid_price)).min(MAX_BPS.into()),
+        )?)
+    }
+}
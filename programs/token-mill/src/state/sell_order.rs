@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+pub const SELL_ORDER_PDA_SEED: &str = "sell_order";
+
+/// Resting part of a split sell, see `split_sell`. The vault of the order holds the base tokens
+/// left unsold, and cranks sell them to the curve while the bid price is above the limit price.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct SellOrder {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub limit_price: u64,
+
+    pub total_base_amount_sold: u64,
+    pub total_quote_amount_received: u64,
+}
+
+impl SellOrder {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, owner: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.owner = owner;
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, base_amount: u64, quote_amount: u64) {
+        self.total_base_amount_sold += base_amount;
+        self.total_quote_amount_received += quote_amount;
+    }
+}
@@ -1,10 +1,47 @@
 use anchor_lang::prelude::*;
 
-use crate::constant::STAKING_SCALE;
+use crate::{
+    constant::{MAX_BPS, STAKING_SCALE},
+    errors::TokenMillError,
+};
 
 pub const MARKET_STAKING_PDA_SEED: &str = "market_staking";
 pub const STAKING_POSITION_PDA_SEED: &str = "stake_position";
 
+/// Lock duration a stake position can opt into, and the multiplier in bps it applies to the reward
+/// shares of the staked amount while locked.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StakingLockTier {
+    pub duration: i64,
+    pub multiplier_bps: u16,
+}
+
+pub const STAKING_LOCK_TIERS: [StakingLockTier; 4] = [
+    StakingLockTier {
+        duration: 0,
+        multiplier_bps: 10_000,
+    },
+    StakingLockTier {
+        duration: 2_592_000, // 30 days
+        multiplier_bps: 12_500,
+    },
+    StakingLockTier {
+        duration: 7_776_000, // 90 days
+        multiplier_bps: 15_000,
+    },
+    StakingLockTier {
+        duration: 15_552_000, // 180 days
+        multiplier_bps: 20_000,
+    },
+];
+
+/// Origin of the quote tokens of a creator staking boost, see `boost_staking_rewards`.
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum StakingBoostSource {
+    Wallet,
+    CreatorFees,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct MarketStaking {
@@ -12,6 +49,8 @@ pub struct MarketStaking {
     pub amount_staked: u64,
     pub total_amount_vested: u64,
     pub acc_reward_amount_per_share: u128,
+    /// Sum of the reward shares of the positions, see `StakePosition::get_shares`.
+    pub total_shares: u64,
 }
 
 impl MarketStaking {
@@ -19,20 +58,34 @@ impl MarketStaking {
         self.market = market;
         self.amount_staked = 0;
         self.acc_reward_amount_per_share = 0;
+        self.total_shares = 0;
 
         Ok(())
     }
 
     pub fn accrue_rewards(&mut self, pending_rewards: u64) -> Result<u128> {
-        let total_shares = self.amount_staked + self.total_amount_vested;
-
-        if total_shares > 0 && pending_rewards > 0 {
+        if self.total_shares > 0 && pending_rewards > 0 {
             self.acc_reward_amount_per_share +=
-                (u128::from(pending_rewards) * STAKING_SCALE) / u128::from(total_shares);
+                (u128::from(pending_rewards) * STAKING_SCALE) / u128::from(self.total_shares);
         }
 
         Ok(self.acc_reward_amount_per_share)
     }
+
+    /// Counts the stake of a staking created before reward shares, whose positions all weigh
+    /// their amounts. Shares are never below the amounts, so a total of 0 for a non-empty staking
+    /// means it wasn't counted yet. Returns false once counted.
+    pub fn backfill(&mut self) -> bool {
+        let amount = self.amount_staked + self.total_amount_vested;
+
+        if self.total_shares > 0 || amount == 0 {
+            return false;
+        }
+
+        self.total_shares = amount;
+
+        true
+    }
 }
 
 #[account]
@@ -44,6 +97,11 @@ pub struct StakePosition {
     pub total_amount_vested: u64,
     pub pending_rewards: u64,
     pub acc_reward_amount_per_share: u128,
+    /// Index in `STAKING_LOCK_TIERS`, reset to 0 once the lock expires, see `expire_lock`.
+    pub lock_tier: u8,
+    pub unlock_timestamp: i64,
+    /// Reward shares as of the latest update of the position, see `get_shares`.
+    pub shares: u64,
 }
 
 impl StakePosition {
@@ -55,11 +113,9 @@ impl StakePosition {
     }
 
     pub fn accrue_rewards(&mut self, acc_reward_amount_per_share: u128) -> Result<()> {
-        let total_shares = self.amount_staked + self.total_amount_vested;
-
-        if total_shares > 0 {
+        if self.shares > 0 {
             self.pending_rewards += u64::try_from(
-                u128::from(total_shares)
+                u128::from(self.shares)
                     * (acc_reward_amount_per_share - self.acc_reward_amount_per_share)
                     / STAKING_SCALE,
             )?;
@@ -69,4 +125,139 @@ impl StakePosition {
 
         Ok(())
     }
+
+    /// Same as `MarketStaking::backfill` for the shares of the position, which are already part
+    /// of the total of the backfilled staking.
+    pub fn backfill(&mut self) -> bool {
+        let amount = self.amount_staked + self.total_amount_vested;
+
+        if self.shares > 0 || amount == 0 {
+            return false;
+        }
+
+        self.shares = amount;
+
+        true
+    }
+
+    pub fn is_locked(&self, timestamp: i64) -> bool {
+        timestamp < self.unlock_timestamp
+    }
+
+    /// Staked amount weighted by the multiplier of the lock tier, plus the vested amount.
+    pub fn get_shares(&self) -> Result<u64> {
+        let multiplier_bps = STAKING_LOCK_TIERS[usize::from(self.lock_tier)].multiplier_bps;
+
+        Ok(u64::try_from(
+            u128::from(self.amount_staked) * u128::from(multiplier_bps) / u128::from(MAX_BPS),
+        )? + self.total_amount_vested)
+    }
+
+    /// Locks the position in `lock_tier` for the full duration of the tier. Locks can only be
+    /// extended, neither the tier nor the unlock timestamp can decrease.
+    pub fn lock(&mut self, lock_tier: u8, timestamp: i64) -> Result<()> {
+        let tier = STAKING_LOCK_TIERS
+            .get(usize::from(lock_tier))
+            .ok_or(TokenMillError::InvalidLockTier)?;
+        let unlock_timestamp = timestamp + tier.duration;
+
+        require!(
+            lock_tier >= self.lock_tier && unlock_timestamp >= self.unlock_timestamp,
+            TokenMillError::InvalidLockTier
+        );
+
+        self.lock_tier = lock_tier;
+        self.unlock_timestamp = unlock_timestamp;
+
+        Ok(())
+    }
+
+    /// Drops the multiplier of an expired lock, returning whether there was one.
+    pub fn expire_lock(&mut self, timestamp: i64) -> bool {
+        if self.lock_tier == 0 || self.is_locked(timestamp) {
+            return false;
+        }
+
+        self.lock_tier = 0;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stake_position(amount_staked: u64, total_amount_vested: u64) -> StakePosition {
+        StakePosition {
+            market: Pubkey::default(),
+            user: Pubkey::default(),
+            amount_staked,
+            total_amount_vested,
+            pending_rewards: 0,
+            acc_reward_amount_per_share: 0,
+            lock_tier: 0,
+            unlock_timestamp: 0,
+            shares: 0,
+        }
+    }
+
+    #[test]
+    fn lock() {
+        let mut stake_position = stake_position(1_000, 100);
+
+        assert_eq!(stake_position.get_shares().unwrap(), 1_100);
+
+        stake_position.lock(2, 0).unwrap();
+
+        // Vested amounts aren't weighted
+        assert_eq!(stake_position.get_shares().unwrap(), 1_600);
+        assert!(stake_position.is_locked(STAKING_LOCK_TIERS[2].duration - 1));
+
+        // Extended in place, never shortened
+        assert_eq!(
+            stake_position.lock(1, 1_000).unwrap_err(),
+            TokenMillError::InvalidLockTier.into()
+        );
+        assert_eq!(
+            stake_position.lock(4, 1_000).unwrap_err(),
+            TokenMillError::InvalidLockTier.into()
+        );
+
+        stake_position.lock(3, 1_000).unwrap();
+
+        assert_eq!(stake_position.get_shares().unwrap(), 2_100);
+        assert_eq!(
+            stake_position.unlock_timestamp,
+            1_000 + STAKING_LOCK_TIERS[3].duration
+        );
+
+        assert!(!stake_position.expire_lock(stake_position.unlock_timestamp - 1));
+        assert!(stake_position.expire_lock(stake_position.unlock_timestamp));
+        assert_eq!(stake_position.get_shares().unwrap(), 1_100);
+    }
+
+    #[test]
+    fn backfill() {
+        let mut staking = MarketStaking {
+            market: Pubkey::default(),
+            amount_staked: 1_000,
+            total_amount_vested: 100,
+            acc_reward_amount_per_share: 0,
+            total_shares: 0,
+        };
+
+        assert!(staking.backfill());
+        assert_eq!(staking.total_shares, 1_100);
+        assert!(!staking.backfill());
+
+        let mut stake_position = stake_position(1_000, 100);
+
+        assert!(stake_position.backfill());
+        assert_eq!(stake_position.shares, stake_position.get_shares().unwrap());
+        assert!(!stake_position.backfill());
+
+        // Empty positions have nothing to count
+        assert!(!stake_position(0, 0).backfill());
+    }
 }
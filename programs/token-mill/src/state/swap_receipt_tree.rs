@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+pub const SWAP_RECEIPT_TREE_PDA_SEED: &str = "swap_receipt_tree";
+
+/// Concurrent merkle tree of the market collecting a receipt of every swap passing it, see
+/// `get_swap_receipt_leaf`. Receipts are compressed state, the tree account being the only rent.
+/// The account is the authority of the tree.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct SwapReceiptTree {
+    pub bump: u8,
+    pub market: Pubkey,
+    pub merkle_tree: Pubkey,
+
+    pub receipt_count: u64,
+}
+
+impl SwapReceiptTree {
+    pub fn initialize(&mut self, bump: u8, market: Pubkey, merkle_tree: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.market = market;
+        self.merkle_tree = merkle_tree;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+pub const TRADER_VOLUME_PDA_SEED: &str = "trader_volume";
+
+/// Swap volume of a wallet during the epoch of a revenue report. Swaps passing it set aside part
+/// of their protocol fee, redeemable by the traders above the volume threshold once the report is
+/// finalized.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TraderVolume {
+    pub bump: u8,
+    pub revenue_report: Pubkey,
+    pub owner: Pubkey,
+    pub volume: u64,
+}
+
+impl TraderVolume {
+    pub fn initialize(&mut self, bump: u8, revenue_report: Pubkey, owner: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.revenue_report = revenue_report;
+        self.owner = owner;
+        self.volume = 0;
+
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::TokenMillError;
+
 #[account]
 #[derive(InitSpace)]
 pub struct VestingPlan {
@@ -29,6 +31,38 @@ impl VestingPlan {
         Ok(())
     }
 
+    pub fn check_schedule(
+        start: i64,
+        vesting_duration: i64,
+        cliff_duration: i64,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(start > 0, TokenMillError::InvalidVestingDuration);
+
+        Self::check_durations(vesting_duration, cliff_duration)?;
+
+        require!(
+            start + vesting_duration > current_time,
+            TokenMillError::InvalidVestingStartTime
+        );
+
+        Ok(())
+    }
+
+    pub fn check_durations(vesting_duration: i64, cliff_duration: i64) -> Result<()> {
+        require!(
+            vesting_duration > 0 && cliff_duration > 0,
+            TokenMillError::InvalidVestingDuration
+        );
+
+        require!(
+            vesting_duration > cliff_duration,
+            TokenMillError::InvalidVestingDuration
+        );
+
+        Ok(())
+    }
+
     pub fn release(&mut self, current_time: i64) -> Result<u64> {
         let elapsed_time = current_time - self.start;
 
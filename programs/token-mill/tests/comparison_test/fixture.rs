@@ -5,6 +5,8 @@ use token_mill::manager::swap_manager::{SwapAmountType, SwapType};
 pub struct Fixture {
     pub svm_engine: SvmEngine,
     pub evm_engine: EvmEngine,
+    /// Exact output buys since the last fee claim, each refunding up to 1 unit of protocol fee
+    pub exact_output_buys: u64,
 }
 
 #[derive(Clone, Debug)]
@@ -22,6 +24,7 @@ impl Fixture {
         Self {
             svm_engine,
             evm_engine,
+            exact_output_buys: 0,
         }
     }
 
@@ -37,6 +40,10 @@ impl Fixture {
             amount,
         } = scenario;
 
+        if swap_type == SwapType::Buy && swap_amount_type == SwapAmountType::ExactOutput {
+            self.exact_output_buys += 1;
+        }
+
         let (amount_in_svm, amount_out_svm) =
             self.svm_engine.swap(swap_type, swap_amount_type, amount);
         let (amount_in_evm, amount_out_evm) =
@@ -50,10 +57,15 @@ impl Fixture {
     }
 
     pub fn swap_and_compare_allow_imprecision(&mut self, scenario: SwapScenario, threshold: u64) {
+        let refunded_dust = u64::from(
+            scenario.swap_type == SwapType::Buy
+                && scenario.swap_amount_type == SwapAmountType::ExactOutput,
+        );
+
         let (amount_in_svm, amount_out_svm, amount_in_evm, amount_out_evm) = self.swap(scenario);
 
-        // Rounding errors are only allowed if they are in favor of the protocol. Threshold is variable as uncertainties are bigger with smaller amounts
-        assert!((amount_in_svm >= amount_in_evm && amount_in_svm <= amount_in_evm + threshold));
+        // Rounding errors are only allowed if they are in favor of the protocol, except for the dust refunded on exact output buys. Threshold is variable as uncertainties are bigger with smaller amounts
+        assert!((amount_in_svm + refunded_dust >= amount_in_evm && amount_in_svm <= amount_in_evm + threshold));
         assert!((amount_out_svm <= amount_out_evm && amount_out_svm + threshold >= amount_out_evm));
     }
 
@@ -63,7 +75,12 @@ impl Fixture {
 
         assert_eq!(creator_fee_svm, creator_fee_evm);
         assert_eq!(referral_fee_svm, referral_fee_evm);
-        assert_eq!(protocol_fee_svm, protocol_fee_evm);
+        assert!(
+            protocol_fee_svm <= protocol_fee_evm
+                && protocol_fee_svm + self.exact_output_buys >= protocol_fee_evm
+        );
+
+        self.exact_output_buys = 0;
     }
 
     pub fn deposit(&mut self, amount: u64) {
@@ -0,0 +1,164 @@
+import { BN } from "@coral-xyz/anchor";
+import { ComputeBudgetProgram, Transaction } from "@solana/web3.js";
+
+// Mirrors `programs/token-mill/src/constant.rs`
+const SCALE = 1e10;
+const BASE_PRECISION = 1e6;
+const INTERVAL_NUMBER = 10;
+
+// Conservative estimates. Every interval crossed by a swap adds a price step to the curve
+// walk, and the margin covers the optional accounts (referral, launch purchase, staking).
+const SWAP_BASE_COMPUTE_UNITS = 80_000;
+const SWAP_INTERVAL_COMPUTE_UNITS = 6_000;
+const COMPUTE_UNITS_MARGIN = 1.2;
+const MAX_COMPUTE_UNITS = 1_400_000;
+
+type SwapType = { buy: {} } | { sell: {} };
+type SwapAmountType = { exactInput: {} } | { exactOutput: {} };
+
+type MarketAccount = {
+  baseReserve: BN;
+  totalSupply: BN;
+  widthScaled: BN;
+  bidPrices: BN[];
+  askPrices: BN[];
+  pricePoints: number;
+  quoteTokenDecimals: number;
+};
+
+/**
+ * Off-chain simulation of the curve walk of a swap, in whole tokens.
+ * Returns the number of intervals the swap touches, which drives its compute cost.
+ */
+export function getSwapIntervals(
+  market: MarketAccount,
+  swapType: SwapType,
+  swapAmountType: SwapAmountType,
+  amount: BN
+): number {
+  const lastIndex =
+    market.pricePoints === 0 ? INTERVAL_NUMBER : market.pricePoints - 1;
+  const width = Number(market.widthScaled.toString()) / SCALE;
+  const totalSupply = Number(market.totalSupply.toString()) / BASE_PRECISION;
+  const circulatingSupply =
+    totalSupply - Number(market.baseReserve.toString()) / BASE_PRECISION;
+
+  // Start of the interval `i`, the last one spans the rest of the supply
+  const intervalStart = (i: number) =>
+    i >= lastIndex ? totalSupply : Math.min(i * width, totalSupply);
+  const intervalOf = (supply: number) =>
+    Math.min(Math.floor(supply / width), lastIndex - 1);
+
+  const isBuy = "buy" in swapType;
+  const isBaseAmount = isBuy === "exactOutput" in swapAmountType;
+
+  let baseAmount = Number(amount.toString()) / BASE_PRECISION;
+
+  if (!isBaseAmount) {
+    baseAmount = getBaseAmount(
+      isBuy ? market.askPrices : market.bidPrices,
+      Number(amount.toString()) / 10 ** market.quoteTokenDecimals,
+      circulatingSupply,
+      isBuy,
+      intervalStart,
+      intervalOf,
+      lastIndex
+    );
+  }
+
+  const endSupply = isBuy
+    ? Math.min(circulatingSupply + baseAmount, totalSupply)
+    : Math.max(circulatingSupply - baseAmount, 0);
+
+  return Math.abs(intervalOf(endSupply) - intervalOf(circulatingSupply)) + 1;
+}
+
+// Walks the curve from `supply` until `quoteAmount` is spent or received
+function getBaseAmount(
+  prices: BN[],
+  quoteAmount: number,
+  supply: number,
+  isBuy: boolean,
+  intervalStart: (i: number) => number,
+  intervalOf: (supply: number) => number,
+  lastIndex: number
+): number {
+  const priceAt = (i: number) => Number(prices[i].toString()) / SCALE;
+
+  let baseAmount = 0;
+  let i = intervalOf(supply);
+
+  while (quoteAmount > 0 && i >= 0 && i < lastIndex) {
+    const start = intervalStart(i);
+    const end = intervalStart(i + 1);
+    const slope = (priceAt(i + 1) - priceAt(i)) / (end - start);
+    const priceOf = (s: number) => priceAt(i) + slope * (s - start);
+
+    const bound = isBuy ? end : start;
+    const price = priceOf(supply);
+    const delta = Math.abs(bound - supply);
+    const intervalQuote = (delta * (price + priceOf(bound))) / 2;
+
+    if (intervalQuote >= quoteAmount) {
+      // Linear price within the interval, the remaining amount is bounded by the current price
+      baseAmount += price > 0 ? Math.min(quoteAmount / price, delta) : delta;
+      break;
+    }
+
+    quoteAmount -= intervalQuote;
+    baseAmount += delta;
+    supply = bound;
+    i += isBuy ? 1 : -1;
+  }
+
+  return baseAmount;
+}
+
+/** Compute unit limit for a swap, with a margin over the simulated curve walk. */
+export function getSwapComputeUnits(
+  market: MarketAccount,
+  swapType: SwapType,
+  swapAmountType: SwapAmountType,
+  amount: BN
+): number {
+  const intervals = getSwapIntervals(market, swapType, swapAmountType, amount);
+
+  return Math.min(
+    Math.ceil(
+      (SWAP_BASE_COMPUTE_UNITS + intervals * SWAP_INTERVAL_COMPUTE_UNITS) *
+        COMPUTE_UNITS_MARGIN
+    ),
+    MAX_COMPUTE_UNITS
+  );
+}
+
+/**
+ * Prepends the compute budget instructions of a swap to its transaction, so that large fills
+ * crossing many intervals don't run out of compute units.
+ */
+export function withSwapComputeBudget(
+  transaction: Transaction,
+  market: MarketAccount,
+  swapType: SwapType,
+  swapAmountType: SwapAmountType,
+  amount: BN,
+  microLamportsPerUnit?: number
+): Transaction {
+  const instructions = [
+    ComputeBudgetProgram.setComputeUnitLimit({
+      units: getSwapComputeUnits(market, swapType, swapAmountType, amount),
+    }),
+  ];
+
+  if (microLamportsPerUnit !== undefined) {
+    instructions.push(
+      ComputeBudgetProgram.setComputeUnitPrice({
+        microLamports: microLamportsPerUnit,
+      })
+    );
+  }
+
+  transaction.instructions.unshift(...instructions);
+
+  return transaction;
+}
@@ -69,7 +69,7 @@ const marketBaseTokenAta = spl.getAssociatedTokenAddressSync(
 
 {
   const transaction = await program.methods
-    .createMarket("Test Market", "TM", "", new BN(1_000_000e6), 3_000, 4_000)
+    .createMarket("Test Market", "TM", "", new BN(1_000_000e6), 3_000, 4_000, null, 0)
     .accountsPartial({
       config,
       market,
@@ -2,6 +2,8 @@ import anchor, { BN, Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
 import * as spl from "@solana/spl-token";
 
+import { withSwapComputeBudget } from "./computeBudget";
+
 import { type TokenMill } from "../../../target/types/token_mill";
 import TokenMillIdl from "../../../target/idl/token_mill.json";
 
@@ -51,14 +53,16 @@ const userQuoteTokenAta = spl.getAssociatedTokenAddressSync(
 const u64Max = new BN(2).pow(new BN(64)).sub(new BN(1));
 
 const swapActions = [];
-swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(100e6), u64Max]);
-swapActions.push([{ sell: {} }, { exactInput: {} }, new BN(50e6), new BN(0)]);
-swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(300e6), u64Max]);
-swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(432e6), u64Max]);
-swapActions.push([{ sell: {} }, { exactInput: {} }, new BN(100e6), new BN(0)]);
+swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(100e6), u64Max, null]);
+swapActions.push([{ sell: {} }, { exactInput: {} }, new BN(50e6), new BN(0), null]);
+swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(300e6), u64Max, null]);
+swapActions.push([{ buy: {} }, { exactOutput: {} }, new BN(432e6), u64Max, null]);
+swapActions.push([{ sell: {} }, { exactInput: {} }, new BN(100e6), new BN(0), null]);
 
 for (const action of swapActions) {
-  const transaction = await program.methods
+  const marketAccount = await program.account.market.fetch(market);
+
+  const swapTransaction = await program.methods
     .swap(...action)
     .accountsPartial({
       config,
@@ -78,6 +82,15 @@ for (const action of swapActions) {
     .signers([wallet.payer])
     .transaction();
 
+  // Big fills cross several intervals of the curve and need a larger compute budget
+  const transaction = withSwapComputeBudget(
+    swapTransaction,
+    marketAccount,
+    action[0],
+    action[1],
+    action[2]
+  );
+
   const transactionSignature = await connection.sendTransaction(transaction, [
     wallet.payer,
   ]);